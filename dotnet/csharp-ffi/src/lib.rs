@@ -4,6 +4,7 @@
 // Callers must ensure proper pointer validity and alignment
 
 use sketch_oxide::cardinality::HyperLogLog;
+use sketch_oxide::common::hash::hash_bytes;
 use sketch_oxide::{Mergeable, Sketch};
 use std::slice;
 
@@ -1022,18 +1023,20 @@ pub unsafe extern "C" fn ribbon_insert(ptr: *mut RibbonFilter, data: *const u8,
 }
 
 /// Finalizes a RibbonFilter (must be called before contains)
+///
+/// Returns false if the filter is too small for its keys and stays unfinalized.
 #[no_mangle]
-pub unsafe extern "C" fn ribbon_finalize(ptr: *mut RibbonFilter) {
+pub unsafe extern "C" fn ribbon_finalize(ptr: *mut RibbonFilter) -> bool {
     if ptr.is_null() {
-        return;
+        return false;
     }
-    (*ptr).finalize();
+    (*ptr).finalize().is_ok()
 }
 
-/// Checks if an element may be in RibbonFilter
+/// Checks if an element may be in RibbonFilter (false until finalized)
 #[no_mangle]
 pub unsafe extern "C" fn ribbon_contains(ptr: *const RibbonFilter, data: *const u8, len: usize) -> bool {
-    if ptr.is_null() || data.is_null() {
+    if ptr.is_null() || data.is_null() || !(*ptr).is_finalized() {
         return false;
     }
     let bytes = slice::from_raw_parts(data, len);
@@ -1662,12 +1665,7 @@ pub unsafe extern "C" fn slidinghll_update(ptr: *mut SlidingHyperLogLog, data: *
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    let _ = (*ptr).update(&hash, timestamp);
+    let _ = (*ptr).update(&bytes, timestamp);
 }
 
 /// Estimates cardinality within window
@@ -1718,12 +1716,7 @@ pub unsafe extern "C" fn minhash_update(ptr: *mut MinHash, data: *const u8, len:
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update(&bytes);
 }
 
 /// Computes Jaccard similarity between two MinHashes
@@ -1770,12 +1763,7 @@ pub unsafe extern "C" fn simhash_update(ptr: *mut SimHash, data: *const u8, len:
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update(&bytes);
 }
 
 /// Gets fingerprint from SimHash
@@ -2438,12 +2426,7 @@ pub unsafe extern "C" fn ultraloglog_update(ptr: *mut UltraLogLog, data: *const
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).add(&bytes);
 }
 
 /// Estimates the cardinality
@@ -2523,12 +2506,7 @@ pub unsafe extern "C" fn cpc_update(ptr: *mut CpcSketch, data: *const u8, len: u
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update_bytes(bytes);
 }
 
 /// Estimates the cardinality
@@ -2701,12 +2679,7 @@ pub unsafe extern "C" fn theta_update(ptr: *mut ThetaSketch, data: *const u8, le
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update(&bytes);
 }
 
 /// Estimates the cardinality
@@ -2748,12 +2721,7 @@ pub unsafe extern "C" fn countmin_update(ptr: *mut CountMinSketch, data: *const
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update(&bytes);
 }
 
 /// Estimates count in CountMinSketch
@@ -2763,12 +2731,7 @@ pub unsafe extern "C" fn countmin_estimate(ptr: *const CountMinSketch, data: *co
         return 0;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).estimate(&hash) as u64
+    (*ptr).estimate(&bytes) as u64
 }
 
 /// Merges CountMinSketch
@@ -2820,12 +2783,7 @@ pub unsafe extern "C" fn countsketch_update(ptr: *mut CountSketch, data: *const
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash, weight);
+    (*ptr).update(&bytes, weight);
 }
 
 /// Estimates count in CountSketch
@@ -2835,12 +2793,7 @@ pub unsafe extern "C" fn countsketch_estimate(ptr: *const CountSketch, data: *co
         return 0;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).estimate(&hash)
+    (*ptr).estimate(&bytes)
 }
 
 /// Merges CountSketch
@@ -2892,12 +2845,7 @@ pub unsafe extern "C" fn conservativecountmin_update(ptr: *mut ConservativeCount
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash);
+    (*ptr).update(&bytes);
 }
 
 /// Estimates count in ConservativeCountMin
@@ -2907,12 +2855,7 @@ pub unsafe extern "C" fn conservativecountmin_estimate(ptr: *const ConservativeC
         return 0;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).estimate(&hash) as u64
+    (*ptr).estimate(&bytes) as u64
 }
 
 /// Merges ConservativeCountMin
@@ -2964,11 +2907,7 @@ pub unsafe extern "C" fn spacesaving_update(ptr: *mut SpaceSaving<u64>, data: *c
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
+    let hash = hash_bytes(bytes);
     (*ptr).update(hash);
 }
 
@@ -3021,11 +2960,7 @@ pub unsafe extern "C" fn frequentitems_update(ptr: *mut FrequentItems<u64>, data
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
+    let hash = hash_bytes(bytes);
     (*ptr).update(hash);
 }
 
@@ -3140,12 +3075,7 @@ pub unsafe extern "C" fn salsa_update(ptr: *mut SALSA, data: *const u8, len: usi
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash, weight);
+    (*ptr).update(&bytes, weight);
 }
 
 /// Estimates count in SALSA
@@ -3155,12 +3085,7 @@ pub unsafe extern "C" fn salsa_estimate(ptr: *const SALSA, data: *const u8, len:
         return 0;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    let (estimate, _) = (*ptr).estimate(&hash);
+    let (estimate, _) = (*ptr).estimate(&bytes);
     estimate
 }
 
@@ -3213,12 +3138,7 @@ pub unsafe extern "C" fn removableuniversalsketch_update(ptr: *mut RemovableUniv
         return;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).update(&hash, delta);
+    (*ptr).update(&bytes, delta);
 }
 
 /// Estimates count in RemovableUniversalSketch
@@ -3228,12 +3148,7 @@ pub unsafe extern "C" fn removableuniversalsketch_estimate(ptr: *const Removable
         return 0;
     }
     let bytes = slice::from_raw_parts(data, len);
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    bytes.hash(&mut hasher);
-    let hash = hasher.finish();
-    (*ptr).estimate(&hash) as u64
+    (*ptr).estimate(&bytes) as u64
 }
 
 /// Merges RemovableUniversalSketch
@@ -0,0 +1,57 @@
+// Pointer lifecycle helpers for sketches handed to Java as `jlong` handles
+//
+// Every JNI class owns a boxed sketch and passes its address back on each
+// call. These helpers keep the boxing, null checks and freeing in one place.
+
+use jni::sys::jlong;
+
+/// Moves a sketch to the heap and returns its address as a Java handle
+pub(crate) fn into_handle<T>(value: T) -> jlong {
+    Box::into_raw(Box::new(value)) as jlong
+}
+
+/// Borrows the sketch behind a handle, or `None` for a null handle
+///
+/// # Safety
+///
+/// `ptr` must be 0 or a live handle created by [`into_handle`] for `T`.
+pub(crate) unsafe fn handle_ref<'a, T>(ptr: jlong) -> Option<&'a T> {
+    (ptr as *const T).as_ref()
+}
+
+/// Mutably borrows the sketch behind a handle, or `None` for a null handle
+///
+/// # Safety
+///
+/// `ptr` must be 0 or a live handle created by [`into_handle`] for `T`, with
+/// no other borrow of it active.
+pub(crate) unsafe fn handle_mut<'a, T>(ptr: jlong) -> Option<&'a mut T> {
+    (ptr as *mut T).as_mut()
+}
+
+/// Drops the sketch behind a handle; null handles are ignored
+///
+/// # Safety
+///
+/// `ptr` must be 0 or a live handle created by [`into_handle`] for `T`, and
+/// must not be used again afterwards.
+pub(crate) unsafe fn free_handle<T>(ptr: jlong) {
+    if ptr != 0 {
+        drop(Box::from_raw(ptr as *mut T));
+    }
+}
+
+/// Defines the `free` JNI entry point for a sketch class
+///
+/// ```ignore
+/// jni_free!(Java_com_sketches_oxide_BloomFilter_free, BloomFilter);
+/// ```
+macro_rules! jni_free {
+    ($fn_name:ident, $ty:ty) => {
+        /// Free native memory
+        #[no_mangle]
+        pub extern "system" fn $fn_name(_env: JNIEnv, _: JObject, ptr: jlong) {
+            unsafe { handle::free_handle::<$ty>(ptr) }
+        }
+    };
+}
@@ -2,12 +2,14 @@
 // Provides access to probabilistic data structure algorithms from Java
 // v0.1.6 Expansion: Complete multi-language support
 
+#[macro_use]
+mod handle;
+
+use handle::{handle_mut, handle_ref, into_handle};
 use jni::objects::{JByteArray, JClass, JObject};
 use jni::sys::{jboolean, jbyteArray, jdouble, jint, jlong, JNI_FALSE};
 use jni::JNIEnv;
-
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use sketch_oxide::common::hash::hash_bytes;
 
 use sketch_oxide::{
     // Cardinality Estimation
@@ -286,12 +288,7 @@ pub extern "system" fn Java_com_sketches_oxide_CpcSketch_update(
 
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        cpc.update(&hash);
+        cpc.update_bytes(&bytes);
     }
 }
 
@@ -515,8 +512,7 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_new(
     n: jlong,
     fpr: jdouble,
 ) -> jlong {
-    let bf = BloomFilter::new(n as usize, fpr);
-    Box::into_raw(Box::new(bf)) as jlong
+    into_handle(BloomFilter::new(n as usize, fpr))
 }
 
 /// Insert an item
@@ -527,11 +523,9 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_insert(
     ptr: jlong,
     data: jbyteArray,
 ) {
-    if ptr == 0 {
+    let Some(bf) = (unsafe { handle_mut::<BloomFilter>(ptr) }) else {
         return;
-    }
-
-    let bf = unsafe { &mut *(ptr as *mut BloomFilter) };
+    };
 
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
@@ -547,11 +541,9 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_contains(
     ptr: jlong,
     data: jbyteArray,
 ) -> jboolean {
-    if ptr == 0 {
+    let Some(bf) = (unsafe { handle_ref::<BloomFilter>(ptr) }) else {
         return 0;
-    }
-
-    let bf = unsafe { &*(ptr as *const BloomFilter) };
+    };
 
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
@@ -568,11 +560,14 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_merge(
     ptr1: jlong,
     ptr2: jlong,
 ) {
-    if ptr1 == 0 || ptr2 == 0 {
+    let (Some(bf1), Some(bf2)) = (unsafe {
+        (
+            handle_mut::<BloomFilter>(ptr1),
+            handle_ref::<BloomFilter>(ptr2),
+        )
+    }) else {
         return;
-    }
-    let bf1 = unsafe { &mut *(ptr1 as *mut BloomFilter) };
-    let bf2 = unsafe { &*(ptr2 as *const BloomFilter) };
+    };
     bf1.merge(bf2);
 }
 
@@ -583,10 +578,9 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_serialize(
     _: JObject,
     ptr: jlong,
 ) -> jbyteArray {
-    if ptr == 0 {
+    let Some(bf) = (unsafe { handle_ref::<BloomFilter>(ptr) }) else {
         return std::ptr::null_mut();
-    }
-    let bf = unsafe { &*(ptr as *const BloomFilter) };
+    };
     let data = bf.to_bytes();
     match env.byte_array_from_slice(&data) {
         Ok(arr) => arr.into_raw(),
@@ -604,23 +598,13 @@ pub extern "system" fn Java_com_sketches_oxide_BloomFilter_deserialize(
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(data_vec) = env.convert_byte_array(arr) {
         if let Ok(bf) = BloomFilter::from_bytes(&data_vec) {
-            return Box::into_raw(Box::new(bf)) as jlong;
+            return into_handle(bf);
         }
     }
     0
 }
 
-/// Free native memory
-#[no_mangle]
-pub extern "system" fn Java_com_sketches_oxide_BloomFilter_free(
-    _env: JNIEnv,
-    _: JObject,
-    ptr: jlong,
-) {
-    if ptr != 0 {
-        let _ = unsafe { Box::from_raw(ptr as *mut BloomFilter) };
-    }
-}
+jni_free!(Java_com_sketches_oxide_BloomFilter_free, BloomFilter);
 
 // ============================================================================
 // FREQUENCY ESTIMATION - CountMinSketch
@@ -1026,9 +1010,11 @@ pub extern "system" fn Java_com_sketches_oxide_RibbonFilter_insert(
 }
 
 /// Finalize RibbonFilter after insertions
+///
+/// Throws `IllegalStateException` if the filter is too small for its keys.
 #[no_mangle]
 pub extern "system" fn Java_com_sketches_oxide_RibbonFilter_finalize(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JObject,
     ptr: jlong,
 ) {
@@ -1036,7 +1022,9 @@ pub extern "system" fn Java_com_sketches_oxide_RibbonFilter_finalize(
         return;
     }
     let rf = unsafe { &mut *(ptr as *mut RibbonFilter) };
-    rf.finalize();
+    if let Err(e) = rf.finalize() {
+        let _ = env.throw_new("java/lang/IllegalStateException", e.to_string());
+    }
 }
 
 /// Check if item exists in RibbonFilter
@@ -1051,6 +1039,10 @@ pub extern "system" fn Java_com_sketches_oxide_RibbonFilter_contains(
         return 0;
     }
     let rf = unsafe { &*(ptr as *const RibbonFilter) };
+    // Querying an unfinalized filter panics, which must not cross into the JVM
+    if !rf.is_finalized() {
+        return 0;
+    }
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
         Ok(bytes) => rf.contains(&bytes) as jboolean,
@@ -1602,7 +1594,7 @@ pub extern "system" fn Java_com_sketches_oxide_SplineSketch_merge(
     }
     let sketch1 = unsafe { &mut *(ptr1 as *mut SplineSketch) };
     let sketch2 = unsafe { &*(ptr2 as *const SplineSketch) };
-    sketch1.merge_into(sketch2);
+    let _ = sketch1.merge(sketch2);
 }
 
 /// Free SplineSketch
@@ -1813,12 +1805,7 @@ pub extern "system" fn Java_com_sketches_oxide_CountSketch_update(
     let sketch = unsafe { &mut *(ptr as *mut CountSketch) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash, delta as i64);
+        sketch.update(&bytes, delta as i64);
     }
 }
 
@@ -1836,14 +1823,7 @@ pub extern "system" fn Java_com_sketches_oxide_CountSketch_estimate(
     let sketch = unsafe { &*(ptr as *const CountSketch) };
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
-        Ok(bytes) => {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            bytes.hash(&mut hasher);
-            let hash = hasher.finish();
-            sketch.estimate(&hash) as jlong
-        }
+        Ok(bytes) => sketch.estimate(&bytes) as jlong,
         Err(_) => 0,
     }
 }
@@ -1922,12 +1902,7 @@ pub extern "system" fn Java_com_sketches_oxide_ConservativeCountMin_update(
     let sketch = unsafe { &mut *(ptr as *mut ConservativeCountMin) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash);
+        sketch.update(&bytes);
     }
 }
 
@@ -1945,14 +1920,7 @@ pub extern "system" fn Java_com_sketches_oxide_ConservativeCountMin_estimate(
     let sketch = unsafe { &*(ptr as *const ConservativeCountMin) };
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
-        Ok(bytes) => {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            bytes.hash(&mut hasher);
-            let hash = hasher.finish();
-            sketch.estimate(&hash) as jlong
-        }
+        Ok(bytes) => sketch.estimate(&bytes) as jlong,
         Err(_) => 0,
     }
 }
@@ -2102,12 +2070,7 @@ pub extern "system" fn Java_com_sketches_oxide_SALSA_update(
     let sketch = unsafe { &mut *(ptr as *mut SALSA) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash, count as u64);
+        sketch.update(&bytes, count as u64);
     }
 }
 
@@ -2126,12 +2089,7 @@ pub extern "system" fn Java_com_sketches_oxide_SALSA_estimate(
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
         Ok(bytes) => {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            bytes.hash(&mut hasher);
-            let hash = hasher.finish();
-            let (estimate, _confidence) = sketch.estimate(&hash);
+            let (estimate, _confidence) = sketch.estimate(&bytes);
             estimate as jlong
         }
         Err(_) => 0,
@@ -2181,12 +2139,7 @@ pub extern "system" fn Java_com_sketches_oxide_RemovableUniversalSketch_update(
     let sketch = unsafe { &mut *(ptr as *mut RemovableUniversalSketch) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash, delta);
+        sketch.update(&bytes, delta);
     }
 }
 
@@ -2204,14 +2157,7 @@ pub extern "system" fn Java_com_sketches_oxide_RemovableUniversalSketch_estimate
     let sketch = unsafe { &*(ptr as *const RemovableUniversalSketch) };
     let arr = unsafe { JByteArray::from_raw(data) };
     match env.convert_byte_array(arr) {
-        Ok(bytes) => {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            bytes.hash(&mut hasher);
-            let hash = hasher.finish();
-            sketch.estimate(&hash)
-        }
+        Ok(bytes) => sketch.estimate(&bytes),
         Err(_) => 0,
     }
 }
@@ -2345,12 +2291,7 @@ pub extern "system" fn Java_com_sketches_oxide_MinHash_update(
     let sketch = unsafe { &mut *(ptr as *mut MinHash) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash);
+        sketch.update(&bytes);
     }
 }
 
@@ -2417,12 +2358,7 @@ pub extern "system" fn Java_com_sketches_oxide_SimHash_update(
     let sketch = unsafe { &mut *(ptr as *mut SimHash) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        sketch.update(&hash);
+        sketch.update(&bytes);
     }
 }
 
@@ -2699,12 +2635,7 @@ pub extern "system" fn Java_com_sketches_oxide_SlidingHyperLogLog_update(
     let sketch = unsafe { &mut *(ptr as *mut SlidingHyperLogLog) };
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
-        let _ = sketch.update(&hash, timestamp as u64);
+        let _ = sketch.update(&bytes, timestamp as u64);
     }
 }
 
@@ -3257,9 +3188,7 @@ pub extern "system" fn Java_com_sketches_oxide_SpaceSaving_update(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &mut *(ptr as *mut SpaceSaving<u64>) };
         sketch.update(hash);
@@ -3279,9 +3208,7 @@ pub extern "system" fn Java_com_sketches_oxide_SpaceSaving_estimate(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &*(ptr as *const SpaceSaving<u64>) };
         match sketch.estimate(&hash) {
@@ -3366,9 +3293,7 @@ pub extern "system" fn Java_com_sketches_oxide_FrequentItems_update(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &mut *(ptr as *mut FrequentItems<u64>) };
         sketch.update(hash);
@@ -3389,9 +3314,7 @@ pub extern "system" fn Java_com_sketches_oxide_FrequentItems_update_by(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &mut *(ptr as *mut FrequentItems<u64>) };
         sketch.update_by(hash, count as u64);
@@ -3411,9 +3334,7 @@ pub extern "system" fn Java_com_sketches_oxide_FrequentItems_estimate(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &*(ptr as *const FrequentItems<u64>) };
         match sketch.get_estimate(&hash) {
@@ -3496,9 +3417,7 @@ pub extern "system" fn Java_com_sketches_oxide_ReservoirSampling_update(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &mut *(ptr as *mut ReservoirSampling<u64>) };
         sketch.update(hash);
@@ -3591,9 +3510,7 @@ pub extern "system" fn Java_com_sketches_oxide_VarOptSampling_update(
     }
     let arr = unsafe { JByteArray::from_raw(data) };
     if let Ok(bytes) = env.convert_byte_array(arr) {
-        let mut hasher = DefaultHasher::new();
-        bytes.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = hash_bytes(&bytes);
 
         let sketch = unsafe { &mut *(ptr as *mut VarOptSampling<u64>) };
         sketch.update(hash, weight);
@@ -1,4 +1,4 @@
-import { BloomFilter } from '../index'
+import { BloomFilter, HyperLogLog } from '../index'
 
 describe('BloomFilter', () => {
   describe('constructor', () => {
@@ -23,6 +23,18 @@ describe('BloomFilter', () => {
       expect(() => new BloomFilter(1000000, 0.01)).not.toThrow()
     })
 
+    it('should size from a HyperLogLog estimate', () => {
+      const hll = new HyperLogLog(12)
+      for (let i = 0; i < 1000; i++) {
+        hll.update(Buffer.from(`item-${i}`))
+      }
+      const bf = BloomFilter.forEstimated(hll, 0.01)
+      for (let i = 0; i < 1000; i++) {
+        bf.insert(Buffer.from(`item-${i}`))
+      }
+      expect(bf.contains(Buffer.from('item-42'))).toBe(true)
+    })
+
     it('should throw on invalid n (zero)', () => {
       expect(() => new BloomFilter(0, 0.01)).toThrow()
     })
@@ -272,6 +284,52 @@ describe('BloomFilter', () => {
     })
   })
 
+  describe('intersect', () => {
+    it('should keep keys present in both filters', () => {
+      const a = new BloomFilter(1000, 0.01)
+      const b = new BloomFilter(1000, 0.01)
+      for (let i = 0; i < 500; i++) {
+        a.insert(Buffer.from(`key-${i}`))
+      }
+      for (let i = 250; i < 750; i++) {
+        b.insert(Buffer.from(`key-${i}`))
+      }
+
+      const both = a.intersect(b)
+      for (let i = 250; i < 500; i++) {
+        expect(both.contains(Buffer.from(`key-${i}`))).toBe(true)
+      }
+      let leaked = 0
+      for (let i = 0; i < 250; i++) {
+        if (both.contains(Buffer.from(`key-${i}`))) leaked++
+      }
+      expect(leaked).toBeLessThan(25)
+    })
+
+    it('should reject filters with different parameters', () => {
+      const a = new BloomFilter(1000, 0.01)
+      const b = new BloomFilter(2000, 0.01)
+      expect(() => a.intersect(b)).toThrow()
+    })
+  })
+
+  describe('saturation', () => {
+    it('should report the current FPR rising past the design FPR', () => {
+      const bf = new BloomFilter(1000, 0.01)
+      const design = bf.falsePositiveRate()
+      expect(bf.currentFalsePositiveRate()).toBe(0)
+      expect(bf.isSaturated(0.01)).toBe(false)
+
+      for (let i = 0; i < 5000; i++) {
+        bf.insert(Buffer.from(`item-${i}`))
+      }
+
+      expect(bf.currentFalsePositiveRate()).toBeGreaterThan(design)
+      expect(bf.isSaturated(0.01)).toBe(true)
+      expect(bf.falsePositiveRate()).toBe(design)
+    })
+  })
+
   describe('edge cases', () => {
     let bf: BloomFilter
 
@@ -1,4 +1,4 @@
-import { DDSketch } from '../index'
+import { DDSketch, DDSketchMapping } from '../index'
 
 describe('DDSketch', () => {
   describe('constructor', () => {
@@ -32,6 +32,36 @@ describe('DDSketch', () => {
     })
   })
 
+  describe('mapping', () => {
+    it('should default to the logarithmic mapping', () => {
+      expect(new DDSketch(0.01).mapping()).toBe(DDSketchMapping.Logarithmic)
+    })
+
+    it('should keep relative accuracy with interpolated mappings', () => {
+      for (const mapping of [
+        DDSketchMapping.CubicallyInterpolated,
+        DDSketchMapping.LinearlyInterpolated,
+      ]) {
+        const ds = DDSketch.withMapping(0.01, mapping)
+        expect(ds.mapping()).toBe(mapping)
+        for (let i = 1; i <= 1000; i++) {
+          ds.update(i)
+        }
+        const p50 = ds.quantile(0.5)!
+        expect(Math.abs(p50 - 500) / 500).toBeLessThanOrEqual(0.01)
+
+        const restored = DDSketch.deserialize(ds.serialize())
+        expect(restored.mapping()).toBe(mapping)
+      }
+    })
+
+    it('should not merge sketches with different mappings', () => {
+      const a = new DDSketch(0.01)
+      const b = DDSketch.withMapping(0.01, DDSketchMapping.CubicallyInterpolated)
+      expect(() => a.mergeWith(b)).toThrow()
+    })
+  })
+
   describe('update and quantile', () => {
     let ds: DDSketch
 
@@ -451,4 +481,30 @@ describe('DDSketch', () => {
       expect(result1).toBeDefined()
     })
   })
+
+  describe('protobuf', () => {
+    // DataDog reference encoding of 1.01, 1.03, 1.03, -10, 0, 0 at 1% accuracy
+    const fixture =
+      '0a0909fd4a815abf52f03f12121210000000000000f03f0000000000000040' +
+      '1a0d1208000000000000f03f18e601210000000000000040'
+
+    it('should match the reference encoding', () => {
+      const ds = new DDSketch(0.01)
+      for (const v of [1.01, 1.03, 1.03, -10, 0, 0]) {
+        ds.update(v)
+      }
+      expect(ds.toProtoBytes().toString('hex')).toBe(fixture)
+    })
+
+    it('should read the reference encoding', () => {
+      const ds = DDSketch.fromProtoBytes(Buffer.from(fixture, 'hex'))
+      expect(ds.count()).toBe(6)
+      expect(ds.quantile(0.5)).toBe(0)
+    })
+
+    it('should reject interpolated mappings', () => {
+      const ds = DDSketch.withMapping(0.01, DDSketchMapping.CubicallyInterpolated)
+      expect(() => ds.toProtoBytes()).toThrow()
+    })
+  })
 })
@@ -1,4 +1,4 @@
-import { HyperLogLog } from '../index'
+import { AdaptiveHyperLogLog, GroupedHll, HyperLogLog, hllIntersection } from '../index'
 
 describe('HyperLogLog', () => {
   describe('constructor', () => {
@@ -151,6 +151,50 @@ describe('HyperLogLog', () => {
     })
   })
 
+  describe('updateBatchAsync', () => {
+    it('should ingest 1M items without blocking the event loop', async () => {
+      const items = Array.from({ length: 1_000_000 }, (_, i) => Buffer.from(`item-${i}`))
+      const hll = new HyperLogLog(14)
+
+      let ticks = 0
+      const timer = setInterval(() => ticks++, 0)
+      await hll.updateBatchAsync(items)
+      clearInterval(timer)
+
+      expect(ticks).toBeGreaterThan(0)
+      const error = Math.abs(hll.estimate() - 1_000_000) / 1_000_000
+      expect(error).toBeLessThan(0.03)
+    }, 30_000)
+
+    it('should match synchronous updates', async () => {
+      const items = Array.from({ length: 1000 }, (_, i) => Buffer.from(`key${i}`))
+      const batched = new HyperLogLog(12)
+      const sync = new HyperLogLog(12)
+      await batched.updateBatchAsync(items)
+      items.forEach((item) => sync.update(item))
+      expect(batched.estimate()).toBe(sync.estimate())
+    })
+  })
+
+  describe('estimateUnion', () => {
+    it('should estimate the union without modifying either sketch', () => {
+      const hll1 = new HyperLogLog(14)
+      const hll2 = new HyperLogLog(14)
+      hll1.update(Buffer.from('a'))
+      hll1.update(Buffer.from('b'))
+      hll2.update(Buffer.from('b'))
+      hll2.update(Buffer.from('c'))
+      const union = hll1.estimateUnion(hll2)
+      expect(union).toBeGreaterThanOrEqual(2.7)
+      expect(union).toBeLessThanOrEqual(3.3)
+      expect(hll1.estimate()).toBeLessThanOrEqual(2.1)
+    })
+
+    it('should fail for different precision', () => {
+      expect(() => new HyperLogLog(14).estimateUnion(new HyperLogLog(12))).toThrow()
+    })
+  })
+
   describe('reset', () => {
     it('should reset sketch state', () => {
       const hll = new HyperLogLog(14)
@@ -255,4 +299,127 @@ describe('HyperLogLog', () => {
       expect(estimate).toBeLessThan(2100)
     })
   })
+
+  describe('hllIntersection', () => {
+    it('should estimate the intersection of several sketches', () => {
+      const a = new HyperLogLog(14)
+      const b = new HyperLogLog(14)
+      const c = new HyperLogLog(14)
+      for (let i = 0; i < 20000; i++) a.update(Buffer.from(`item-${i}`))
+      for (let i = 5000; i < 25000; i++) b.update(Buffer.from(`item-${i}`))
+      for (let i = 10000; i < 30000; i++) c.update(Buffer.from(`item-${i}`))
+      const estimate = hllIntersection([a, b, c])
+      expect(estimate).toBeGreaterThan(8500)
+      expect(estimate).toBeLessThan(11500)
+    })
+
+    it('should throw on empty input or precision mismatch', () => {
+      expect(() => hllIntersection([])).toThrow()
+      expect(() => hllIntersection([new HyperLogLog(12), new HyperLogLog(14)])).toThrow()
+    })
+  })
+
+  describe('foldTo', () => {
+    it('should produce a lower-precision sketch with a close estimate', () => {
+      const hll = new HyperLogLog(16)
+      for (let i = 0; i < 10000; i++) hll.update(Buffer.from(`item-${i}`))
+      const small = hll.foldTo(12)
+      expect(small.precision()).toBe(12)
+      expect(small.estimate()).toBeGreaterThan(9500)
+      expect(small.estimate()).toBeLessThan(10500)
+    })
+
+    it('should throw when folding to a higher precision', () => {
+      expect(() => new HyperLogLog(12).foldTo(14)).toThrow()
+    })
+  })
+
+  describe('numeric updates', () => {
+    it('updateU64 should match update with the little-endian bytes', () => {
+      const numeric = new HyperLogLog(12)
+      const bytes = new HyperLogLog(12)
+      numeric.updateU64(5n)
+      const le = Buffer.alloc(8)
+      le.writeBigUInt64LE(5n)
+      bytes.update(le)
+      expect(numeric.serialize().equals(bytes.serialize())).toBe(true)
+    })
+
+    it('updateI64 and updateF64 should match their little-endian bytes', () => {
+      const numeric = new HyperLogLog(12)
+      const bytes = new HyperLogLog(12)
+      numeric.updateI64(-5n)
+      numeric.updateF64(2.5)
+      const i64 = Buffer.alloc(8)
+      i64.writeBigInt64LE(-5n)
+      const f64 = Buffer.alloc(8)
+      f64.writeDoubleLE(2.5)
+      bytes.update(i64)
+      bytes.update(f64)
+      expect(numeric.serialize().equals(bytes.serialize())).toBe(true)
+    })
+
+    it('should reject values outside the 64-bit range', () => {
+      const hll = new HyperLogLog(12)
+      expect(() => hll.updateU64(-1n)).toThrow()
+      expect(() => hll.updateU64(1n << 64n)).toThrow()
+      expect(() => hll.updateI64(1n << 63n)).toThrow()
+    })
+  })
+
+  describe('golden values', () => {
+    it('should hash byte input exactly like the Rust core', () => {
+      // Pinned in sketch_oxide/tests/hash_functions_test.rs
+      const hll = new HyperLogLog(10)
+      hll.update(Buffer.from('sketch_oxide'))
+      const bytes = hll.serialize()
+      expect(bytes[1 + 132]).toBe(6)
+      const nonzero = Array.from(bytes.subarray(1)).filter((r) => r !== 0)
+      expect(nonzero).toEqual([6])
+    })
+  })
+
+  describe('AdaptiveHyperLogLog', () => {
+    it('should start sparse at the minimum precision and grow', () => {
+      const hll = new AdaptiveHyperLogLog(6, 12)
+      hll.update(Buffer.from('item'))
+      expect(hll.currentPrecision()).toBe(6)
+      expect(hll.isSparse()).toBe(true)
+
+      for (let i = 0; i < 20000; i++) {
+        hll.update(Buffer.from(`item-${i}`))
+      }
+      expect(hll.currentPrecision()).toBe(12)
+      expect(Math.abs(hll.estimate() - 20000) / 20000).toBeLessThan(0.1)
+      expect(hll.toHyperLogLog().precision()).toBe(12)
+    })
+
+    it('should reject min precision above max precision', () => {
+      expect(() => new AdaptiveHyperLogLog(12, 10)).toThrow()
+    })
+  })
+
+  describe('GroupedHll', () => {
+    it('should estimate per group and evict small groups over budget', () => {
+      const grouped = new GroupedHll(10, 20000)
+      for (let i = 0; i < 1000; i++) {
+        grouped.update(Buffer.from('big'), Buffer.from(`user-${i}`))
+      }
+      for (let i = 0; i < 500; i++) {
+        grouped.update(Buffer.from(`group-${i}`), Buffer.from(`item-${i}`))
+      }
+
+      expect(grouped.memoryUsage()).toBeLessThanOrEqual(20000)
+      expect(grouped.evictedGroups()).toBeGreaterThan(0)
+      expect(grouped.containsGroup(Buffer.from('big'))).toBe(true)
+      expect(Math.abs(grouped.estimate(Buffer.from('big')) - 1000) / 1000).toBeLessThan(0.1)
+      expect(grouped.numGroups() + grouped.evictedGroups()).toBe(501)
+      expect(grouped.otherEstimate()).toBeGreaterThan(0)
+    })
+
+    it('should return 0 for unknown groups', () => {
+      const grouped = new GroupedHll(12, 1 << 20)
+      expect(grouped.estimate(Buffer.from('missing'))).toBe(0)
+    })
+  })
 })
@@ -529,6 +529,24 @@ describe('SlidingHyperLogLog', () => {
     })
   })
 
+  describe('distinctSince', () => {
+    it('should not count returning items as new', () => {
+      const hll = new SlidingHyperLogLog(12, 3600n)
+
+      for (let i = 0; i < 100; i++) {
+        hll.update(Buffer.from(`item_${i}`), 1000n)
+      }
+      for (let i = 50; i < 150; i++) {
+        hll.update(Buffer.from(`item_${i}`), 2000n)
+      }
+
+      const newItems = hll.distinctSince(1500n)
+      expect(newItems).toBeGreaterThan(40)
+      expect(newItems).toBeLessThan(60)
+      expect(hll.estimateWindow(2000n, 500n)).toBeGreaterThan(90)
+    })
+  })
+
   describe('decay', () => {
     it('should remove expired entries', () => {
       const hll = new SlidingHyperLogLog(12, 3600n)
@@ -58,6 +58,18 @@ describe('VacuumFilter', () => {
         expect(filter.contains(Buffer.from(`key${i}`))).toBe(true)
       }
     })
+
+    it('should measure FPR on absent keys', () => {
+      const filter = new VacuumFilter(1000, 0.01)
+      for (let i = 0; i < 500; i++) {
+        filter.insert(Buffer.from(`key${i}`))
+      }
+
+      const absent = Array.from({ length: 5000 }, (_, i) => Buffer.from(`absent${i}`))
+      expect(filter.measuredFpr(absent)).toBeLessThan(0.05)
+      expect(filter.measuredFpr([Buffer.from('key1')])).toBe(1)
+      expect(filter.measuredFpr([])).toBe(0)
+    })
   })
 
   describe('delete', () => {
@@ -317,6 +329,18 @@ describe('NitroSketch', () => {
     })
   })
 
+  describe('setRate', () => {
+    it('should change and clamp the sample rate', () => {
+      const nitro = new NitroSketch(new CountMinSketch(0.01, 0.01), 0.5)
+      expect(nitro.currentRate()).toBe(0.5)
+      nitro.setRate(0.1)
+      expect(nitro.currentRate()).toBe(0.1)
+      nitro.setRate(5)
+      expect(nitro.currentRate()).toBe(1)
+      expect(() => nitro.setRate(0)).toThrow()
+    })
+  })
+
   describe('updateSampled', () => {
     it('should update with sampling', () => {
       const base = new CountMinSketch(0.01, 0.01)
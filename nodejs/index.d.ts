@@ -3,6 +3,18 @@
 
 /* auto-generated by NAPI-RS */
 
+/** Estimator applied to HyperLogLog registers by `estimateWith` */
+export const enum HllEstimator {
+  /** Harmonic mean with linear counting for small cardinalities */
+  Original = 0,
+  /**
+   * Default used by `estimate()`: `Original` plus the 32-bit large-range
+   * correction (not HyperLogLog++)
+   */
+  Classic32BitCorrected = 1,
+  /** Ertl maximum likelihood; no bias around the linear counting switch */
+  MaximumLikelihood = 2
+}
 /** Result of weighted cardinality estimation */
 export interface WeightedCardinalityResult {
   /** The estimated weighted cardinality */
@@ -10,6 +22,17 @@ export interface WeightedCardinalityResult {
   /** The error bound (95% confidence interval radius) */
   errorBound: number
 }
+/** Jaccard similarity with its confidence interval from `ThetaSketch.jaccard` */
+export interface JaccardBounds {
+  lower: number
+  estimate: number
+  upper: number
+}
+/** Key with its estimated change between two Count-Min Sketches */
+export interface TopMover {
+  key: Buffer
+  delta: number
+}
 /** Result object for Space-Saving heavy hitter queries */
 export interface HeavyHitterResult {
   key: string
@@ -27,17 +50,66 @@ export interface FrequentItemResult {
   lowerBound: number
   upperBound: number
 }
+/** How a Ribbon filter was built */
+export interface RibbonBuildResult {
+  /** "solved" when every key is represented by the solution, "overflow" otherwise */
+  path: string
+  /** Number of keys stored in the exact overflow set */
+  overflowKeys: number
+}
+/** DDSketch index mapping */
+export const enum DDSketchMapping {
+  Logarithmic = 0,
+  CubicallyInterpolated = 1,
+  LinearlyInterpolated = 2
+}
 /** REQ Sketch mode */
 export const enum ReqSketchMode {
   HighRankAccuracy = 0,
   LowRankAccuracy = 1
 }
+/** Contiguous run of OpenTelemetry exponential histogram buckets */
+export interface OtelBuckets {
+  /** Index of the first bucket in `bucketCounts` */
+  offset: number
+  bucketCounts: Array<number>
+}
+/** OpenTelemetry exponential histogram from `toOtelExponential` */
+export interface OtelExpoHistogram {
+  scale: number
+  count: number
+  /** Estimated sum of all values */
+  sum: number
+  min?: number
+  max?: number
+  zeroThreshold: number
+  zeroCount: number
+  positive: OtelBuckets
+  /** Buckets for negative values, indexed by magnitude */
+  negative: OtelBuckets
+}
+/** Normalized rank interval from `KllSketch.rankWithBounds` */
+export interface RankBounds {
+  lower: number
+  upper: number
+}
+/** Quantile estimate with the retained samples bracketing it */
+export interface SplineQueryBounds {
+  lower: number
+  estimate: number
+  upper: number
+}
 /** Weighted item in a VarOpt sample */
 export interface WeightedSampleItem {
   item: Buffer
   weight: number
   adjustedWeight: number
 }
+/** Heavy hitter in a sliding window top-k */
+export interface WindowTopKItem {
+  item: Buffer
+  count: number
+}
 /** Count estimate with bounds */
 export interface CountWithBounds {
   estimate: bigint
@@ -93,6 +165,50 @@ export interface NitroSketchStats {
   unsampledCount: number
   totalItemsEstimated: number
 }
+/**
+ * Estimate the size of the intersection of several HyperLogLog sketches
+ *
+ * Uses inclusion-exclusion over all 2^n - 1 subset unions. Error grows
+ * quickly with the number of sketches and with small overlaps.
+ *
+ * # Arguments
+ * * `sketches` - Between 1 and 6 HyperLogLog sketches with the same precision
+ *
+ * # Throws
+ * - If the array is empty or has more than 6 sketches
+ * - If precisions don't match
+ *
+ * # Example
+ * ```javascript
+ * const { HyperLogLog, hllIntersection } = require('@sketch-oxide/node');
+ * const a = new HyperLogLog(14);
+ * const b = new HyperLogLog(14);
+ * // ... add items ...
+ * console.log(hllIntersection([a, b]));
+ * ```
+ */
+export declare function hllIntersection(sketches: Array<HyperLogLog>): number
+/**
+ * Estimate the size of an equi-join between the streams behind two VarOpt samples
+ *
+ * Rows are matched on their whole item buffer and weights are treated as row
+ * multiplicities, so the estimate is the sum of adjusted-weight products over
+ * matching sampled rows. Draw the two samples independently (different seeds).
+ *
+ * # Arguments
+ * * `a` - Sample of the first stream
+ * * `b` - Sample of the second stream
+ *
+ * # Example
+ * ```javascript
+ * const { VarOptSampling, estimateJoinSize } = require('@sketch-oxide/node');
+ * const a = VarOptSampling.withSeed(500, 1n);
+ * const b = VarOptSampling.withSeed(500, 2n);
+ * // ... add rows keyed by Buffer ...
+ * console.log(estimateJoinSize(a, b));
+ * ```
+ */
+export declare function estimateJoinSize(a: VarOptSampling, b: VarOptSampling): number
 /**
  * HyperLogLog cardinality estimator
  *
@@ -126,6 +242,18 @@ export declare class HyperLogLog {
    * ```
    */
   constructor(precision: number)
+  /**
+   * Create the most precise HyperLogLog whose registers fit in a byte budget
+   *
+   * # Arguments
+   * * `bytes` - Memory available for the registers (at least 16)
+   *
+   * # Example
+   * ```javascript
+   * const hll = HyperLogLog.forByteBudget(8192); // precision 13, ~1.15% error
+   * ```
+   */
+  static forByteBudget(bytes: number): HyperLogLog
   /**
    * Add an item to the sketch
    *
@@ -139,6 +267,67 @@ export declare class HyperLogLog {
    * ```
    */
   update(item: Buffer): void
+  /**
+   * Add an unsigned 64-bit integer without converting it to a Buffer
+   *
+   * Hashes the value's fixed 8-byte little-endian encoding, so
+   * `updateU64(5n)` has the same effect as `update` with those bytes and
+   * as `update_u64` in the Rust crate.
+   *
+   * # Throws
+   * - If the value is negative or exceeds 64 bits
+   *
+   * # Example
+   * ```javascript
+   * hll.updateU64(12345n);
+   * ```
+   */
+  updateU64(value: bigint): void
+  /**
+   * Add a signed 64-bit integer (8-byte little-endian two's complement)
+   *
+   * # Throws
+   * - If the value doesn't fit in 64 bits
+   */
+  updateI64(value: bigint): void
+  /**
+   * Add a number by its 8-byte little-endian IEEE 754 encoding
+   *
+   * `0` and `-0` are hashed as distinct values.
+   */
+  updateF64(value: number): void
+  /**
+   * Add a pre-computed 64-bit hash, bypassing the internal hasher
+   *
+   * The top `precision` bits pick the register and the leading zeros of
+   * the rest give the rank, so the hash must be well distributed (e.g.
+   * XXHash64); raw IDs or counters give meaningless estimates.
+   *
+   * # Throws
+   * - If the value is negative or exceeds 64 bits
+   *
+   * # Example
+   * ```javascript
+   * hll.updateHash(0x9e3779b97f4a7c15n);
+   * ```
+   */
+  updateHash(hash: bigint): void
+  /**
+   * Add a batch of items on the libuv threadpool
+   *
+   * Hashing and register updates run off the main thread, so large
+   * batches don't block the event loop; only copying the items happens
+   * synchronously. Do not call any other method on this sketch, including
+   * another `updateBatchAsync`, until the returned promise settles: the
+   * sketch is not locked while the batch runs.
+   *
+   * # Example
+   * ```javascript
+   * const items = Array.from({ length: 100000 }, (_, i) => Buffer.from(`user-${i}`));
+   * await hll.updateBatchAsync(items);
+   * ```
+   */
+  updateBatchAsync(items: Array<Buffer>): Promise<void>
   /**
    * Get current cardinality estimate
    *
@@ -152,6 +341,15 @@ export declare class HyperLogLog {
    * ```
    */
   estimate(): number
+  /**
+   * Estimate the cardinality with a specific estimator
+   *
+   * # Example
+   * ```javascript
+   * const ml = hll.estimateWith(HllEstimator.MaximumLikelihood);
+   * ```
+   */
+  estimateWith(estimator: HllEstimator): number
   /**
    * Merge another HyperLogLog sketch into this one
    *
@@ -172,6 +370,32 @@ export declare class HyperLogLog {
    * ```
    */
   merge(other: HyperLogLog): void
+  /**
+   * Estimate the cardinality of the union without modifying either sketch
+   *
+   * # Arguments
+   * * `other` - Another HyperLogLog with same precision
+   *
+   * # Throws
+   * - If the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const total = hll1.estimateUnion(hll2);
+   * ```
+   */
+  estimateUnion(other: HyperLogLog): number
+  /**
+   * Get a JSON summary of parameters and state for debugging
+   *
+   * Not meant for reconstruction; use `serialize()` for that.
+   *
+   * # Example
+   * ```javascript
+   * console.log(JSON.parse(hll.toJson()).estimate);
+   * ```
+   */
+  toJson(): string
   /**
    * Create a new sketch instance (reset)
    * Note: In Rust, we create a new instance instead of resetting
@@ -189,6 +413,36 @@ export declare class HyperLogLog {
    * ```
    */
   precision(): number
+  /**
+   * Fold the sketch down to a lower precision
+   *
+   * # Arguments
+   * * `targetPrecision` - Precision of the returned sketch (4 to current)
+   *
+   * # Throws
+   * - If targetPrecision exceeds the current precision or is below 4
+   *
+   * # Example
+   * ```javascript
+   * const hll = new HyperLogLog(16);
+   * const small = hll.foldTo(12);
+   * console.log(small.precision()); // 12
+   * ```
+   */
+  foldTo(targetPrecision: number): HyperLogLog
+  /**
+   * Stable 64-bit hash of the sketch content, as BigInt
+   *
+   * Identical sketches, including serialize/deserialize copies, share the
+   * same hash. Not a cryptographic hash: distinct sketches can collide.
+   *
+   * # Example
+   * ```javascript
+   * const copy = HyperLogLog.deserialize(hll.serialize());
+   * console.log(copy.contentHash() === hll.contentHash()); // true
+   * ```
+   */
+  contentHash(): bigint
   /**
    * Serialize the sketch to binary format
    *
@@ -226,6 +480,104 @@ export declare class HyperLogLog {
    */
   toString(): string
 }
+/**
+ * HyperLogLog whose precision grows with the cardinality
+ *
+ * Items are recorded at `maxPrecision` (sparsely while few registers are
+ * touched) and reported at a current precision that starts at `minPrecision`
+ * and increases as the estimate crosses load thresholds. Saves memory when
+ * most sketches stay small.
+ *
+ * # Example
+ * ```javascript
+ * const hll = new AdaptiveHyperLogLog(6, 14);
+ * hll.update(Buffer.from('item1'));
+ * console.log(hll.currentPrecision()); // 6
+ * ```
+ */
+export declare class AdaptiveHyperLogLog {
+  /**
+   * Create an adaptive HyperLogLog
+   *
+   * # Arguments
+   * * `minPrecision` - Precision to start reporting at (4-18)
+   * * `maxPrecision` - Precision registers are recorded at (4-18)
+   * * `loadThreshold` - Estimate / registers ratio that raises the precision (default 0.5)
+   *
+   * # Throws
+   * - If a precision is out of range, `minPrecision > maxPrecision`, or
+   *   the threshold is not positive
+   */
+  constructor(minPrecision: number, maxPrecision: number, loadThreshold?: number | undefined | null)
+  /**
+   * Add an item to the sketch
+   *
+   * Hashes the bytes exactly like `HyperLogLog.update`.
+   */
+  update(item: Buffer): void
+  /** Get the cardinality estimate at the current precision */
+  estimate(): number
+  /** Get the precision the sketch currently reports at */
+  currentPrecision(): number
+  /** Get the precision registers are recorded at */
+  maxPrecision(): number
+  /** Whether registers are still stored sparsely */
+  isSparse(): boolean
+  /** Get the standard error at the current precision */
+  standardError(): number
+  /**
+   * Merge another adaptive sketch with the same `maxPrecision`
+   *
+   * # Throws
+   * - If the max precisions differ
+   */
+  merge(other: AdaptiveHyperLogLog): void
+  /** Convert to a regular HyperLogLog at the current precision */
+  toHyperLogLog(): HyperLogLog
+  toString(): string
+}
+/**
+ * Distinct counts per group under a global memory budget
+ *
+ * Each group is an adaptive HyperLogLog that stays sparse while small. When
+ * the budget is exceeded, the smallest groups are merged into a shared
+ * "other" bucket and dropped until usage is back to 90% of the budget.
+ *
+ * # Example
+ * ```javascript
+ * const grouped = new GroupedHll(12, 1 << 20);
+ * grouped.update(Buffer.from('page_a'), Buffer.from('user1'));
+ * console.log(grouped.estimate(Buffer.from('page_a'))); // ~1
+ * ```
+ */
+export declare class GroupedHll {
+  /**
+   * Create a grouped HyperLogLog
+   *
+   * # Arguments
+   * * `precision` - Max precision of each group's HyperLogLog (4-18)
+   * * `memoryBudget` - Bytes available for all groups together
+   *
+   * # Throws
+   * - If the precision is out of range or the budget cannot hold one group
+   */
+  constructor(precision: number, memoryBudget: number)
+  /** Add an item to the distinct set of a group */
+  update(group: Buffer, item: Buffer): void
+  /** Get the distinct count estimate of a group, or 0 if it is not tracked */
+  estimate(group: Buffer): number
+  /** Get the distinct count estimate over all evicted groups combined */
+  otherEstimate(): number
+  /** Whether a group is currently tracked */
+  containsGroup(group: Buffer): boolean
+  /** Get the number of tracked groups */
+  numGroups(): number
+  /** Get the number of groups evicted into the other bucket so far */
+  evictedGroups(): number
+  /** Get the approximate bytes used by the tracked groups */
+  memoryUsage(): number
+  toString(): string
+}
 /**
  * UltraLogLog cardinality estimator - 28% more space-efficient than HyperLogLog
  *
@@ -265,6 +617,18 @@ export declare class UltraLogLog {
    * ```
    */
   constructor(precision: number)
+  /**
+   * Create the most precise UltraLogLog whose registers fit in a byte budget
+   *
+   * # Arguments
+   * * `bytes` - Memory available for the registers (at least 16)
+   *
+   * # Example
+   * ```javascript
+   * const ull = UltraLogLog.forByteBudget(8192); // precision 13, ~1.15% error
+   * ```
+   */
+  static forByteBudget(bytes: number): UltraLogLog
   /**
    * Add an item to the sketch
    *
@@ -278,6 +642,35 @@ export declare class UltraLogLog {
    * ```
    */
   update(item: Buffer): void
+  /**
+   * Add an unsigned 64-bit integer without converting it to a Buffer
+   *
+   * Hashes the value's fixed 8-byte little-endian encoding, so
+   * `updateU64(5n)` has the same effect as `update` with those bytes and
+   * as `update_u64` in the Rust crate.
+   *
+   * # Throws
+   * - If the value is negative or exceeds 64 bits
+   *
+   * # Example
+   * ```javascript
+   * ull.updateU64(12345n);
+   * ```
+   */
+  updateU64(value: bigint): void
+  /**
+   * Add a signed 64-bit integer (8-byte little-endian two's complement)
+   *
+   * # Throws
+   * - If the value doesn't fit in 64 bits
+   */
+  updateI64(value: bigint): void
+  /**
+   * Add a number by its 8-byte little-endian IEEE 754 encoding
+   *
+   * `0` and `-0` are hashed as distinct values.
+   */
+  updateF64(value: number): void
   /**
    * Get current cardinality estimate
    *
@@ -323,6 +716,32 @@ export declare class UltraLogLog {
    * ```
    */
   merge(other: UltraLogLog): void
+  /**
+   * Estimate the cardinality of the union without modifying either sketch
+   *
+   * # Arguments
+   * * `other` - Another UltraLogLog with same precision
+   *
+   * # Throws
+   * - If the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const total = ull1.estimateUnion(ull2);
+   * ```
+   */
+  estimateUnion(other: UltraLogLog): number
+  /**
+   * Get a JSON summary of parameters and state for debugging
+   *
+   * Not meant for reconstruction; use `serialize()` for that.
+   *
+   * # Example
+   * ```javascript
+   * console.log(JSON.parse(ull.toJson()).estimate);
+   * ```
+   */
+  toJson(): string
   /**
    * Check if the sketch is empty (no items added)
    *
@@ -533,6 +952,32 @@ export declare class CpcSketch {
    * ```
    */
   merge(other: CpcSketch): void
+  /**
+   * Estimate the cardinality of the union without modifying either sketch
+   *
+   * # Arguments
+   * * `other` - Another CpcSketch with same lgK
+   *
+   * # Throws
+   * - If the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const total = cpc1.estimateUnion(cpc2);
+   * ```
+   */
+  estimateUnion(other: CpcSketch): number
+  /**
+   * Get a JSON summary of parameters and state for debugging
+   *
+   * Not meant for reconstruction; use `serialize()` for that.
+   *
+   * # Example
+   * ```javascript
+   * console.log(JSON.parse(cpc.toJson()).estimate);
+   * ```
+   */
+  toJson(): string
   /**
    * Serialize the sketch to binary format
    *
@@ -576,6 +1021,81 @@ export declare class CpcSketch {
    */
   toString(): string
 }
+/**
+ * Distinct counter that picks its own representation
+ *
+ * Counts exactly up to `threshold` distinct items, then switches to a
+ * CpcSketch. Trades a little CPU for not having to choose a sketch.
+ *
+ * # Example
+ * ```javascript
+ * const { DistinctCounter } = require('@sketch-oxide/node');
+ * const counter = new DistinctCounter();
+ * counter.add(Buffer.from('alice'));
+ * counter.add(Buffer.from('alice'));
+ * console.log(counter.estimate()); // 1
+ * console.log(counter.isExact()); // true
+ * ```
+ */
+export declare class DistinctCounter {
+  /**
+   * Create a new distinct counter
+   *
+   * # Arguments
+   * * `threshold` - Largest distinct count kept exactly (default: 1024, 0 = sketch only)
+   * * `lgK` - CPC lg_k used after promotion, 4-26 (default: 11)
+   *
+   * # Throws
+   * - If lgK is out of valid range (4-26)
+   *
+   * # Example
+   * ```javascript
+   * const counter = new DistinctCounter(10000, 12);
+   * ```
+   */
+  constructor(threshold?: number | undefined | null, lgK?: number | undefined | null)
+  /**
+   * Add an item to the counter
+   *
+   * # Example
+   * ```javascript
+   * counter.add(Buffer.from('user-42'));
+   * ```
+   */
+  add(item: Buffer): void
+  /** Get the number of distinct items, exact while `isExact()` is true */
+  estimate(): number
+  /** Check whether the count is still exact (not yet promoted to CPC) */
+  isExact(): boolean
+  /**
+   * Merge another DistinctCounter into this one
+   *
+   * # Throws
+   * - If the counters use different lgK values
+   *
+   * # Example
+   * ```javascript
+   * a.merge(b);
+   * ```
+   */
+  merge(other: DistinctCounter): void
+  /** Get the largest distinct count kept exactly */
+  threshold(): number
+  /** Get the lg_k of the CPC sketch used after promotion */
+  lgK(): number
+  /** Check if no items have been added */
+  isEmpty(): boolean
+  /**
+   * Get string representation
+   *
+   * # Example
+   * ```javascript
+   * console.log(counter.toString());
+   * // "DistinctCounter(threshold=1024, lgK=11, exact=true, estimate=2)"
+   * ```
+   */
+  toString(): string
+}
 /**
  * QSketch for weighted cardinality estimation
  *
@@ -714,6 +1234,18 @@ export declare class QSketch {
    * ```
    */
   estimateWeightedCardinality(): WeightedCardinalityResult
+  /**
+   * Get the q-quantile of the per-item weight distribution
+   *
+   * Each distinct item counts once with its accumulated weight. `q` is
+   * clamped to [0, 1]; an empty sketch returns 0.
+   *
+   * # Example
+   * ```javascript
+   * const medianWeight = qs.weightQuantile(0.5);
+   * ```
+   */
+  weightQuantile(q: number): number
   /**
    * Get the basic cardinality estimate (without error bounds)
    *
@@ -894,6 +1426,58 @@ export declare class ThetaSketch {
    * ```
    */
   update(item: Buffer): void
+  /**
+   * Add a value that was already hashed by another tool
+   *
+   * The 64-bit hash is used as-is (the seed is not applied) and must be
+   * uniformly distributed. Feed a sketch through either `update` or
+   * `updateHash`, not both.
+   */
+  updateHash(hash: bigint): void
+  /**
+   * Add an unsigned 64-bit integer without converting it to a Buffer
+   *
+   * Hashes the value's fixed 8-byte little-endian encoding, so
+   * `updateU64(5n)` has the same effect as `update` with those bytes and
+   * as `update_u64` in the Rust crate.
+   *
+   * # Throws
+   * - If the value is negative or exceeds 64 bits
+   *
+   * # Example
+   * ```javascript
+   * sketch.updateU64(12345n);
+   * ```
+   */
+  updateU64(value: bigint): void
+  /**
+   * Add a signed 64-bit integer (8-byte little-endian two's complement)
+   *
+   * # Throws
+   * - If the value doesn't fit in 64 bits
+   */
+  updateI64(value: bigint): void
+  /**
+   * Add a number by its 8-byte little-endian IEEE 754 encoding
+   *
+   * `0` and `-0` are hashed as distinct values.
+   */
+  updateF64(value: number): void
+  /**
+   * Add a batch of items on the libuv threadpool
+   *
+   * Hashing and sampling run off the main thread, so large batches don't
+   * block the event loop; only copying the items happens synchronously.
+   * Do not call any other method on this sketch, including another
+   * `updateBatchAsync`, until the returned promise settles: the sketch is
+   * not locked while the batch runs.
+   *
+   * # Example
+   * ```javascript
+   * await theta.updateBatchAsync([Buffer.from('a'), Buffer.from('b')]);
+   * ```
+   */
+  updateBatchAsync(items: Array<Buffer>): Promise<void>
   /**
    * Get current cardinality estimate
    *
@@ -947,6 +1531,13 @@ export declare class ThetaSketch {
    * ```
    */
   getTheta(): bigint
+  /**
+   * Stable 64-bit hash of the sketch content, as BigInt
+   *
+   * Equal to `compact().contentHash()`. Identical sketches share the same
+   * hash. Not a cryptographic hash: distinct sketches can collide.
+   */
+  contentHash(): bigint
   /**
    * Get the nominal capacity (k = 2^lgK)
    *
@@ -960,6 +1551,34 @@ export declare class ThetaSketch {
    * ```
    */
   capacity(): number
+  /**
+   * Lower theta until the compact image fits in `maxBytes`
+   *
+   * Drops the largest retained hashes and recomputes theta so the
+   * estimate stays unbiased. Error grows as ~1/sqrt(retained entries).
+   *
+   * # Arguments
+   * * `maxBytes` - Byte budget (24-byte preamble plus 8 bytes per entry)
+   *
+   * # Example
+   * ```javascript
+   * theta.trimToBytes(8 * 1024);
+   * ```
+   */
+  trimToBytes(maxBytes: number): void
+  /**
+   * Freeze the sketch into a read-only CompactThetaSketch
+   *
+   * The compact form keeps the same estimate in a sorted array, without
+   * hash table overhead, and cannot be updated.
+   *
+   * # Example
+   * ```javascript
+   * const frozen = theta.compact();
+   * console.log(frozen.estimate());
+   * ```
+   */
+  compact(): CompactThetaSketch
   /**
    * Compute union with another sketch: |A ∪ B|
    *
@@ -982,6 +1601,62 @@ export declare class ThetaSketch {
    * ```
    */
   union(other: ThetaSketch): ThetaSketch
+  /**
+   * Estimate the cardinality of the union without modifying either sketch
+   *
+   * # Arguments
+   * * `other` - Another ThetaSketch with same lgK and seed
+   *
+   * # Throws
+   * - If the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const total = sketch1.estimateUnion(sketch2);
+   * ```
+   */
+  estimateUnion(other: ThetaSketch): number
+  /**
+   * Compute the union of an array of sketches in one pass
+   *
+   * # Arguments
+   * * `sketches` - ThetaSketches with the same lgK and seed
+   *
+   * # Throws
+   * - If the array is empty or the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const union = ThetaSketch.unionAll([a, b, c]);
+   * ```
+   */
+  static unionAll(sketches: Array<ThetaSketch>): ThetaSketch
+  /**
+   * Compute the intersection of an array of sketches
+   *
+   * # Arguments
+   * * `sketches` - ThetaSketches with the same lgK and seed
+   *
+   * # Throws
+   * - If the array is empty or the sketches are incompatible
+   *
+   * # Example
+   * ```javascript
+   * const common = ThetaSketch.intersectAll([a, b, c]);
+   * ```
+   */
+  static intersectAll(sketches: Array<ThetaSketch>): ThetaSketch
+  /**
+   * Get a JSON summary of parameters and state for debugging
+   *
+   * Not meant for reconstruction.
+   *
+   * # Example
+   * ```javascript
+   * console.log(JSON.parse(sketch.toJson()).estimate);
+   * ```
+   */
+  toJson(): string
   /**
    * Compute intersection with another sketch: |A ∩ B|
    *
@@ -1049,6 +1724,23 @@ export declare class ThetaSketch {
    * ```
    */
   jaccardSimilarity(other: ThetaSketch): number
+  /**
+   * Estimate Jaccard similarity with confidence bounds
+   *
+   * Uses the joint-theta estimator from Apache DataSketches, which bounds
+   * the error better than dividing separate intersection and union
+   * estimates. The bounds cover roughly two standard deviations and are
+   * exact while neither sketch samples. The lgK values may differ.
+   *
+   * # Throws
+   * - If sketches have different seeds
+   *
+   * # Example
+   * ```javascript
+   * const { lower, estimate, upper } = sketchA.jaccard(sketchB);
+   * ```
+   */
+  jaccard(other: ThetaSketch): JaccardBounds
   /**
    * Get string representation
    *
@@ -1060,6 +1752,52 @@ export declare class ThetaSketch {
    */
   toString(): string
 }
+/**
+ * Read-only Theta Sketch produced by `ThetaSketch.compact()`
+ *
+ * Holds the retained hashes in a sorted array. Supports estimates, set
+ * operations with other compact sketches, and serialization.
+ */
+export declare class CompactThetaSketch {
+  estimate(): number
+  isEmpty(): boolean
+  memoryUsage(): number
+  serialize(): Buffer
+  static deserialize(data: Buffer): CompactThetaSketch
+  /** Get the number of retained hash entries */
+  numRetained(): number
+  /** Get the theta value */
+  getTheta(): bigint
+  /**
+   * Stable 64-bit hash of the sketch content, as BigInt
+   *
+   * Survives serialize/deserialize. Not a cryptographic hash: distinct
+   * sketches can collide.
+   */
+  contentHash(): bigint
+  /**
+   * Compute union with another compact sketch: |A ∪ B|
+   *
+   * # Throws
+   * - If sketches have different lgK or seed
+   */
+  union(other: CompactThetaSketch): CompactThetaSketch
+  /**
+   * Compute intersection with another compact sketch: |A ∩ B|
+   *
+   * # Throws
+   * - If sketches have different lgK or seed
+   */
+  intersect(other: CompactThetaSketch): CompactThetaSketch
+  /**
+   * Compute difference: |A - B| (items in A but not in B)
+   *
+   * # Throws
+   * - If sketches have different lgK or seed
+   */
+  difference(other: CompactThetaSketch): CompactThetaSketch
+  toString(): string
+}
 /**
  * Count-Min Sketch for frequency estimation
  *
@@ -1100,12 +1838,102 @@ export declare class CountMinSketch {
    * ```
    */
   constructor(epsilon: number, delta: number)
+  /**
+   * Create a Count-Min Sketch with exact dimensions and per-row hash seeds
+   *
+   * Use this to match a sketch produced by another implementation.
+   *
+   * # Arguments
+   * * `width` - Number of counters per row (at least 3)
+   * * `depth` - Number of rows
+   * * `seeds` - One hash seed per row (length must equal depth)
+   *
+   * # Throws
+   * - If dimensions are out of range or seed count doesn't match depth
+   *
+   * # Example
+   * ```javascript
+   * const cms = CountMinSketch.withDimensionsSeeded(1000, 3, [11n, 22n, 33n]);
+   * ```
+   */
+  static withDimensionsSeeded(width: number, depth: number, seeds: Array<bigint>): CountMinSketch
   /** Add an item to the sketch (increment count by 1) */
   update(item: Buffer): void
+  /** Add an unsigned 64-bit integer by its 8-byte little-endian encoding */
+  updateU64(value: bigint): void
+  /** Add a signed 64-bit integer by its 8-byte little-endian encoding */
+  updateI64(value: bigint): void
+  /** Add a number by its 8-byte little-endian IEEE 754 encoding */
+  updateF64(value: number): void
+  /**
+   * Add a batch of items on the libuv threadpool
+   *
+   * Hashing and counter updates run off the main thread, so large batches don't
+   * block the event loop; only copying the items happens synchronously.
+   * Do not call any other method on this sketch, including another
+   * `updateBatchAsync`, until the returned promise settles: the sketch is
+   * not locked while the batch runs.
+   *
+   * # Example
+   * ```javascript
+   * await cms.updateBatchAsync([Buffer.from('a'), Buffer.from('b')]);
+   * ```
+   */
+  updateBatchAsync(items: Array<Buffer>): Promise<void>
+  /**
+   * Add a signed delta to an item's count; decrements clamp counters at 0
+   *
+   * Estimates stay upper bounds only while no item is deleted more often
+   * than it was inserted.
+   */
+  updateSigned(item: Buffer, delta: number): void
   /** Estimate the frequency of an item */
   estimate(item: Buffer): number
+  /** Get the total count N of all updates */
+  totalCount(): number
+  /**
+   * Estimate the fraction of the stream made up by an item
+   *
+   * Returns `estimate(item) / totalCount()`, or 0 when empty; the result
+   * overestimates the true fraction by at most epsilon.
+   *
+   * # Example
+   * ```javascript
+   * const share = cms.estimateFrequency(Buffer.from('/login'));
+   * ```
+   */
+  estimateFrequency(item: Buffer): number
+  /**
+   * Check if an item's estimated frequency exceeds `phi`
+   *
+   * Items with a true frequency down to `phi - epsilon` may also be reported.
+   *
+   * # Example
+   * ```javascript
+   * if (cms.isHeavyHitter(Buffer.from('/login'), 0.01)) { ... }
+   * ```
+   */
+  isHeavyHitter(item: Buffer, phi: number): boolean
+  /**
+   * Get the candidate keys whose estimate grew the most since `previous`
+   *
+   * The sketch cannot enumerate keys, so candidates must be supplied.
+   * Results are sorted by descending delta.
+   *
+   * # Example
+   * ```javascript
+   * const movers = thisMinute.topMovers(lastMinute, [Buffer.from('/login')], 10);
+   * ```
+   */
+  topMovers(previous: CountMinSketch, candidateKeys: Array<Buffer>, k: number): Array<TopMover>
   /** Merge another Count-Min Sketch into this one */
   merge(other: CountMinSketch): void
+  /**
+   * Check the sketch's internal invariants, throwing if any is violated
+   *
+   * Use after `deserialize` or `merge` to detect a corrupted sketch.
+   */
+  validate(): void
   /** Get the width of the sketch */
   width(): number
   /** Get the depth of the sketch */
@@ -1114,6 +1942,20 @@ export declare class CountMinSketch {
   epsilon(): number
   /** Get the delta parameter */
   delta(): number
+  /**
+   * Get the raw counter grid, one array per row (diagnostic)
+   *
+   * Copies every counter, so call it for visualization or debugging
+   * rather than on a hot path.
+   */
+  counters(): Array<Array<number>>
+  /**
+   * Get the per-row hash seeds, or null for the default hashing scheme
+   *
+   * Pass them to `withDimensionsSeeded` with the same width and depth to
+   * build a sketch on another node that hashes identically and can be merged.
+   */
+  seeds(): Array<bigint> | null
   /** Serialize the sketch to binary format */
   serialize(): Buffer
   /** Deserialize from binary format */
@@ -1121,6 +1963,52 @@ export declare class CountMinSketch {
   /** Get string representation */
   toString(): string
 }
+/**
+ * HyperLogLog that also records the exact number of updates
+ *
+ * Useful for comparing the estimate against the true stream length
+ * while validating accuracy.
+ *
+ * # Example
+ * ```javascript
+ * const { CountedHyperLogLog } = require('@sketch-oxide/node');
+ * const hll = new CountedHyperLogLog(14);
+ * hll.update(Buffer.from('a'));
+ * hll.update(Buffer.from('a'));
+ * console.log(hll.trueCount(), hll.estimate()); // 2n, ~1
+ * ```
+ */
+export declare class CountedHyperLogLog {
+  /** Create a new counted HyperLogLog with given precision */
+  constructor(precision: number)
+  /** Add an item to the sketch and count the update */
+  update(item: Buffer): void
+  /** Get current cardinality estimate */
+  estimate(): number
+  /** Get the exact number of updates seen */
+  trueCount(): bigint
+}
+/**
+ * Count-Min Sketch that also records the exact number of updates
+ *
+ * # Example
+ * ```javascript
+ * const { CountedCountMinSketch } = require('@sketch-oxide/node');
+ * const cms = new CountedCountMinSketch(0.01, 0.01);
+ * cms.update(Buffer.from('apple'));
+ * console.log(cms.trueCount(), cms.estimate(Buffer.from('apple'))); // 1n, ~1
+ * ```
+ */
+export declare class CountedCountMinSketch {
+  /** Create a new counted Count-Min Sketch with specified error bounds */
+  constructor(epsilon: number, delta: number)
+  /** Add an item to the sketch and count the update */
+  update(item: Buffer): void
+  /** Estimate the frequency of an item */
+  estimate(item: Buffer): number
+  /** Get the exact number of updates seen */
+  trueCount(): bigint
+}
 /**
  * Count Sketch for unbiased frequency estimation
  *
@@ -1147,6 +2035,8 @@ export declare class CountSketch {
   estimate(item: Buffer): number
   /** Estimate inner product of two frequency vectors */
   innerProduct(other: CountSketch): number
+  /** Estimate the second frequency moment (self-join size) */
+  estimateF2(): number
   /** Merge another Count Sketch into this one */
   merge(other: CountSketch): void
   width(): number
@@ -1170,6 +2060,7 @@ export declare class ConservativeCountMin {
   updateCount(item: Buffer, count: number): void
   estimate(item: Buffer): number
   merge(other: ConservativeCountMin): void
+  validate(): void
   width(): number
   depth(): number
   epsilon(): number
@@ -1189,7 +2080,10 @@ export declare class ConservativeCountMin {
 export declare class SpaceSaving {
   constructor(epsilon: number)
   static withCapacity(capacity: number): SpaceSaving
+  static withByteBudget(bytes: number, avgKeyLen: number): SpaceSaving
   update(item: Buffer): void
+  updateBy(item: Buffer, count: number): void
+  updateWeighted(item: Buffer, weight: number): void
   estimate(item: Buffer): HeavyHitterResult | null
   heavyHitters(threshold: number): Array<HeavyHitterResult>
   topK(k: number): Array<HeavyHitterResult>
@@ -1214,6 +2108,8 @@ export declare class FrequentItems {
   numItems(): number
   maxSize(): number
   offset(): number
+  serialize(): Buffer
+  static deserialize(data: Buffer): FrequentItems
   toString(): string
 }
 /**
@@ -1235,6 +2131,30 @@ export declare class BinaryFuseFilter {
   static deserialize(data: Buffer): BinaryFuseFilter
   toString(): string
 }
+/**
+ * Counting Fuse Filter: a Binary Fuse-style filter that supports deletes
+ *
+ * Inserts and deletes re-solve the whole table, so they suit occasional
+ * updates. Check needsRebuild() after heavy deletion churn.
+ */
+export declare class CountingFuseFilter {
+  /** Create an empty filter sized for `capacity` BigInt items */
+  constructor(capacity: number)
+  /** Create a filter from an array of BigInt items */
+  static fromItems(items: Array<bigint>): CountingFuseFilter
+  /** Insert an item, returning false if it was already present */
+  insert(item: bigint): boolean
+  /** Check if an item might be in the set */
+  contains(item: bigint): boolean
+  /** Delete an item, returning false if it was not present */
+  delete(item: bigint): boolean
+  needsRebuild(): boolean
+  rebuild(): void
+  len(): number
+  isEmpty(): boolean
+  capacity(): number
+  toString(): string
+}
 /**
  * Bloom Filter for probabilistic membership testing
  *
@@ -1243,14 +2163,42 @@ export declare class BinaryFuseFilter {
  */
 export declare class BloomFilter {
   constructor(n: number, fpr?: number | undefined | null)
+  static forEstimated(hll: HyperLogLog, fpr?: number | undefined | null): BloomFilter
   insert(key: Buffer): void
   contains(key: Buffer): boolean
+  hashIndices(key: Buffer): Array<number>
+  /**
+   * Insert by a 64-bit hash computed once and shared across filters
+   *
+   * Keys inserted this way are found by `containsHash`, not `contains`.
+   */
+  insertHash(hash: bigint): void
+  /** Check a key inserted with `insertHash` */
+  containsHash(hash: bigint): boolean
   clear(): void
   mergeWith(other: BloomFilter): void
+  /**
+   * Create a filter whose bits are the AND of both filters
+   *
+   * Approximates the intersection: keys in both filters are always found,
+   * but keys in neither can also match when their bits overlap.
+   *
+   * # Throws
+   * - If the parameters differ
+   */
+  intersect(other: BloomFilter): BloomFilter
   isEmpty(): boolean
   len(): number
   falsePositiveRate(): number
+  currentFalsePositiveRate(): number
+  isSaturated(threshold: number): boolean
   memoryUsage(): number
+  /**
+   * Serialize using the sparse encoding (smaller while under ~1/8 of bits are set)
+   *
+   * `BloomFilter.deserialize` accepts both this and the plain format.
+   */
+  serializeCompressed(): Buffer
   serialize(): Buffer
   static deserialize(data: Buffer): BloomFilter
   toString(): string
@@ -1258,6 +2206,20 @@ export declare class BloomFilter {
 /** Blocked Bloom Filter for cache-efficient membership testing */
 export declare class BlockedBloomFilter {
   constructor(n: number, fpr?: number | undefined | null)
+  /**
+   * Create a filter with 256-, 512- or 1024-bit blocks
+   *
+   * Match the CPU's cache line (1024 bits for 128-byte lines). Smaller
+   * blocks are more prone to crowding and give a higher false positive rate.
+   *
+   * # Example
+   * ```javascript
+   * const filter = BlockedBloomFilter.withBlockBits(10000, 0.01, 1024);
+   * ```
+   */
+  static withBlockBits(n: number, fpr: number, blockBits: number): BlockedBloomFilter
+  /** Get the number of bits per block */
+  blockBits(): number
   insert(key: Buffer): void
   contains(key: Buffer): boolean
   clear(): void
@@ -1277,6 +2239,13 @@ export declare class CountingBloomFilter {
   remove(key: Buffer): boolean
   contains(key: Buffer): boolean
   countEstimate(key: Buffer): number
+  /**
+   * Check if a key might have been inserted at least `k` times
+   *
+   * Collisions with other keys can overstate the count, so this can
+   * return false positives.
+   */
+  containsAtLeast(key: Buffer, k: number): boolean
   clear(): void
   mergeWith(other: CountingBloomFilter): void
   isEmpty(): boolean
@@ -1291,14 +2260,32 @@ export declare class CountingBloomFilter {
 /** Cuckoo Filter for membership testing with deletions */
 export declare class CuckooFilter {
   constructor(capacity: number)
+  static forEstimated(hll: HyperLogLog, fpr: number): CuckooFilter
+  /** Create a filter with the fingerprint width chosen for a target FPR */
+  static withFpr(capacity: number, fpr: number): CuckooFilter
   insert(key: Buffer): void
   remove(key: Buffer): boolean
+  /** Add every fingerprint of `other`; folds onto the smaller bucket count */
+  unionInto(other: CuckooFilter): void
   contains(key: Buffer): boolean
+  /**
+   * Insert by a 64-bit hash computed once and shared across filters
+   *
+   * Keys inserted this way are found by `containsHash`, not `contains`.
+   */
+  insertHash(hash: bigint): void
+  /** Check a key inserted with `insertHash` */
+  containsHash(hash: bigint): boolean
+  /** Fraction of known-absent keys reported as present (diagnostic) */
+  measuredFpr(absentKeys: Array<Buffer>): number
   clear(): void
   isEmpty(): boolean
   len(): number
   capacity(): number
   loadFactor(): number
+  fingerprintBits(): number
+  /** False positive rate bound at full load for the fingerprint width */
+  falsePositiveRate(): number
   memoryUsage(): number
   serialize(): Buffer
   static deserialize(data: Buffer): CuckooFilter
@@ -1308,7 +2295,10 @@ export declare class CuckooFilter {
 export declare class RibbonFilter {
   constructor(n: number, fpr?: number | undefined | null)
   insert(key: Buffer): void
-  build(): void
+  /** Build the filter; throws if the filter is too small for its keys */
+  build(): RibbonBuildResult
+  /** How the filter was built, or null before build() */
+  buildInfo(): RibbonBuildResult | null
   contains(key: Buffer): boolean
   isFinalized(): boolean
   len(): number
@@ -1374,6 +2364,14 @@ export declare class StableBloomFilter {
   decrementCount(): number
   /** Get the fill ratio of the filter (0.0 to 1.0) */
   fillRatio(): number
+  /**
+   * Estimate the probability that an item inserted `inserts_ago` operations
+   * ago has decayed out of the filter
+   *
+   * # Arguments
+   * * `inserts_ago` - Number of inserts performed since the item was added
+   */
+  falseNegativeProbability(insertsAgo: number): number
   /** Clear all data from the filter */
   clear(): void
   /** Get memory usage in bytes */
@@ -1388,11 +2386,28 @@ export declare class StableBloomFilter {
 /** DDSketch for quantile estimation with relative error guarantees */
 export declare class DDSketch {
   constructor(relativeAccuracy: number)
+  /** Create a DDSketch with a specific index mapping */
+  static withMapping(relativeAccuracy: number, mapping: DDSketchMapping): DDSketch
+  /** Get the index mapping */
+  mapping(): DDSketchMapping
   update(value: number): void
   updateBatch(values: Array<number>): void
+  /**
+   * Add a value, throwing instead of skipping NaN or an infinity
+   *
+   * `update` and `updateBatch` skip non-finite values and count them in
+   * `rejectedCount()`.
+   */
+  updateStrict(value: number): void
+  /** Number of non-finite values skipped by `update` and `updateBatch` */
+  rejectedCount(): number
   quantile(q: number): number | null
   quantiles(quantiles: Array<number>): Array<number>
+  /** Estimated number of values in [low, high], resolved to whole bins */
+  countBetween(low: number, high: number): number
   mergeWith(other: DDSketch): void
+  /** Return a new sketch holding both inputs; neither is modified */
+  merged(other: DDSketch): DDSketch
   count(): number
   min(): number | null
   max(): number | null
@@ -1400,6 +2415,23 @@ export declare class DDSketch {
   serialize(): Buffer
   static deserialize(data: Buffer): DDSketch
   toString(): string
+  /**
+   * Serialize in DataDog's protobuf DDSketch format
+   *
+   * Readable by sketches-go and sketches-java. Only sketches using the
+   * logarithmic mapping can be encoded.
+   *
+   * # Throws
+   * - If the sketch uses an interpolated mapping
+   */
+  toProtoBytes(): Buffer
+  /**
+   * Deserialize from DataDog's protobuf DDSketch format
+   *
+   * # Throws
+   * - If the bytes are not a valid DDSketch message or use interpolation
+   */
+  static fromProtoBytes(data: Buffer): DDSketch
 }
 /** REQ Sketch for streaming quantile estimation (PODS 2021) */
 export declare class ReqSketch {
@@ -1408,26 +2440,96 @@ export declare class ReqSketch {
   updateBatch(values: Array<number>): void
   quantile(q: number): number | null
   quantiles(quantiles: Array<number>): Array<number>
+  /**
+   * Merge into a new sketch at the smaller of the two k values
+   *
+   * Throws if the modes differ (HRA and LRA sketches cannot be combined).
+   */
   mergeWith(other: ReqSketch): ReqSketch
   count(): number
   min(): number | null
   max(): number | null
+  normalizedRankError(): number
+  normalizedRankErrorAt(rank: number): number
   isEmpty(): boolean
   toString(): string
 }
 /** T-Digest for quantile estimation with tail accuracy */
 export declare class TDigest {
   constructor(compression?: number | undefined | null)
+  /**
+   * Create a digest that compresses every `bufferSize` raw values
+   *
+   * Larger buffers ingest bursts faster at 8 bytes per pending value;
+   * the default is 5 × compression. Accuracy is largely unaffected.
+   */
+  static withBuffer(compression: number, bufferSize: number): TDigest
   update(value: number): void
   updateBatch(values: Array<number>): void
+  /** Add a value with a weight; `cdf` ranks by total weight */
+  updateWeighted(value: number, weight: number): void
+  /**
+   * Add a value, throwing instead of skipping NaN or an infinity
+   *
+   * `update` and `updateBatch` skip non-finite values and count them in
+   * `rejectedCount()`.
+   */
+  updateStrict(value: number): void
+  /** Number of non-finite values skipped by `update` and `updateBatch` */
+  rejectedCount(): number
+  subtract(value: number, weight: number): void
   quantile(q: number): number
   quantiles(quantiles: Array<number>): Array<number>
   cdf(value: number): number
+  /** Estimated number of values in [low, high], from the CDF at both bounds */
+  countBetween(low: number, high: number): number
   trimmedMean(low: number, high: number): number
   mergeWith(other: TDigest): void
+  /** Return a new sketch holding both inputs; neither is modified */
+  merged(other: TDigest): TDigest
+  /**
+   * Merge an array of digests into a new one, independent of array order
+   *
+   * # Throws
+   * - If the array is empty
+   *
+   * # Example
+   * ```javascript
+   * const total = TDigest.mergeMany([a, b, c]);
+   * ```
+   */
+  static mergeMany(digests: Array<TDigest>): TDigest
   count(): number
   compression(): number
+  /** Raw values buffered before a compression */
+  bufferSize(): number
+  /** Values waiting in the buffer, not yet merged into centroids */
+  pending(): number
   centroidCount(): number
+  centroidBudgetUsed(): number
+  /**
+   * Export as an OpenTelemetry exponential histogram
+   *
+   * Fills OTel's base-2^(2^-scale) buckets from the CDF. The conversion is
+   * lossy: counts carry the sketch's error and values keep only their bucket.
+   *
+   * # Arguments
+   * * `scale` - Largest OTel scale to use, in [-10, 20]
+   * * `maxBuckets` - Limit on positive and on negative buckets (default: 160);
+   *   the scale is lowered until both fit, and the result reports it
+   *
+   * # Example
+   * ```javascript
+   * const { scale, zeroCount, positive, negative } = sketch.toOtelExponential(3);
+   * ```
+   */
+  toOtelExponential(scale: number, maxBuckets?: number | undefined | null): OtelExpoHistogram
+  /**
+   * Approximate Kolmogorov-Smirnov distance `sup |CDF_a(x) - CDF_b(x)|`
+   *
+   * Returns null if either sketch is empty.
+   */
+  static ksDistance(a: TDigest, b: TDigest): number | null
   min(): number
   max(): number
   isEmpty(): boolean
@@ -1435,26 +2537,104 @@ export declare class TDigest {
   static deserialize(data: Buffer): TDigest
   toString(): string
 }
+/** Count, sum, min, max and T-Digest quantiles fed by a single update */
+export declare class StreamSummary {
+  constructor(compression?: number | undefined | null)
+  update(value: number): void
+  updateBatch(values: Array<number>): void
+  count(): bigint
+  sum(): number
+  min(): number
+  max(): number
+  mean(): number | null
+  quantile(q: number): number
+  mergeWith(other: StreamSummary): void
+  isEmpty(): boolean
+  serialize(): Buffer
+  static deserialize(data: Buffer): StreamSummary
+  toString(): string
+}
 /** KLL Sketch for quantile estimation (Karnin 2016) */
 export declare class KllSketch {
   constructor(k?: number | undefined | null)
   update(value: number): void
   updateBatch(values: Array<number>): void
+  /** Add `weight` copies of a value; `rank` is relative to total weight */
+  updateWeighted(value: number, weight: number): void
   quantile(rank: number): number | null
   quantiles(ranks: Array<number>): Array<number>
   rank(value: number): number
+  /** Rank of a value ± the sketch's normalized rank error, clamped to [0, 1] */
+  rankWithBounds(value: number): RankBounds
   mergeWith(other: KllSketch): void
+  /** Return a new sketch holding both inputs; neither is modified */
+  merged(other: KllSketch): KllSketch
   k(): number
   count(): number
   min(): number
   max(): number
   normalizedRankError(): number
+  normalizedRankErrorAt(rank: number): number
   numRetained(): number
+  effectiveK(): number
+  /**
+   * Export as an OpenTelemetry exponential histogram
+   *
+   * Fills OTel's base-2^(2^-scale) buckets from the CDF. The conversion is
+   * lossy: counts carry the sketch's error and values keep only their bucket.
+   *
+   * # Arguments
+   * * `scale` - Largest OTel scale to use, in [-10, 20]
+   * * `maxBuckets` - Limit on positive and on negative buckets (default: 160);
+   *   the scale is lowered until both fit, and the result reports it
+   *
+   * # Example
+   * ```javascript
+   * const { scale, zeroCount, positive, negative } = sketch.toOtelExponential(3);
+   * ```
+   */
+  toOtelExponential(scale: number, maxBuckets?: number | undefined | null): OtelExpoHistogram
+  /**
+   * Approximate Kolmogorov-Smirnov distance `sup |CDF_a(x) - CDF_b(x)|`
+   *
+   * Returns null if either sketch is empty.
+   */
+  static ksDistance(a: KllSketch, b: KllSketch): number | null
   isEmpty(): boolean
   serialize(): Buffer
   static deserialize(data: Buffer): KllSketch
   toString(): string
 }
+/**
+ * Greenwald-Khanna sketch with a deterministic rank error bound
+ *
+ * Every quantile and rank answer is within `epsilon * count` ranks of the
+ * truth, whatever the input order.
+ *
+ * # Example
+ * ```javascript
+ * const gk = new GkSketch(0.01);
+ * gk.updateBatch([5, 1, 9, 3]);
+ * console.log(gk.quantile(0.5));
+ * ```
+ */
+export declare class GkSketch {
+  /** Create a sketch with rank error bound `epsilon`, in (0, 1) */
+  constructor(epsilon: number)
+  update(value: number): void
+  updateBatch(values: Array<number>): void
+  quantile(q: number): number | null
+  quantiles(qs: Array<number>): Array<number>
+  rank(value: number): number
+  mergeWith(other: GkSketch): void
+  epsilon(): number
+  count(): number
+  numTuples(): number
+  isEmpty(): boolean
+  serialize(): Buffer
+  static deserialize(data: Buffer): GkSketch
+  toString(): string
+}
 /**
  * SplineSketch for quantile estimation
  *
@@ -1497,6 +2677,19 @@ export declare class SplineSketch {
    * The estimated value at that quantile
    */
   query(quantile: number): number
+  /**
+   * Query a quantile with lower and upper bounds
+   *
+   * The bounds are the retained samples on either side of the target
+   * rank until the sketch first compresses or takes a weighted update;
+   * after that they are the observed minimum and maximum.
+   *
+   * # Throws
+   * - If the sketch is empty
+   */
+  queryWithBounds(quantile: number): SplineQueryBounds
+  /** Get a bound on the normalized rank error (1.0 once compressed) */
+  maxError(): number
   /** Get the minimum value seen */
   min(): number | null
   /** Get the maximum value seen */
@@ -1512,6 +2705,9 @@ export declare class SplineSketch {
    *
    * # Arguments
    * * `other` - Another SplineSketch to merge
+   *
+   * # Errors
+   * Throws if the sketches have different maxSamples
    */
   mergeWith(other: SplineSketch): void
   /** Reset the sketch to empty state */
@@ -1656,6 +2852,8 @@ export declare class SlidingWindowCounter {
   errorBound(): number
   /** Get memory usage in bytes */
   memoryUsage(): number
+  /** Merge another SlidingWindowCounter (same windowSize and epsilon) */
+  merge(other: SlidingWindowCounter): void
   /** Serialize to binary format */
   serialize(): Buffer
   /** Deserialize from binary format */
@@ -1663,6 +2861,115 @@ export declare class SlidingWindowCounter {
   /** Get string representation */
   toString(): string
 }
+/**
+ * Per-key event counts over a sliding window, from time-bucketed Count-Min Sketches
+ *
+ * Memory is numSlots × Count-Min size; slots are allocated on first use.
+ */
+export declare class SlidingWindowFrequency {
+  /** Create a sliding window frequency sketch split into `numSlots` time buckets */
+  constructor(windowSize: bigint, numSlots: number, epsilon: number, delta: number)
+  /** Record one occurrence of key at timestamp */
+  updateAt(key: Buffer, timestamp: bigint): void
+  /** Estimate occurrences of key in [currentTime - window, currentTime] */
+  countWindow(key: Buffer, currentTime: bigint, window: bigint): bigint
+  /** Free slots no longer reachable from currentTime */
+  expire(currentTime: bigint): void
+  /** Remove all recorded events */
+  clear(): void
+  /** Get the largest queryable window */
+  windowSize(): bigint
+  /** Get the number of time slots */
+  numSlots(): number
+  /** Get the time units covered by each slot */
+  slotDuration(): bigint
+  /** Get bytes used by allocated Count-Min counters */
+  memoryUsage(): number
+  toString(): string
+}
+/**
+ * Top-k heavy hitters over a sliding window, from time-bucketed HeavyKeepers
+ *
+ * Window queries sum the per-slot counters; only items that were among the
+ * top k of some slot are reported.
+ */
+export declare class SlidingTopK {
+  /** Create a sliding window top-k tracker split into `numSlots` time buckets */
+  constructor(k: number, windowSize: bigint, numSlots: number, epsilon: number, delta: number)
+  /** Record one occurrence of item at timestamp */
+  updateAt(item: Buffer, timestamp: bigint): void
+  /** Get the top-k items in [currentTime - window, currentTime], largest first */
+  topKWindow(currentTime: bigint, window: bigint): Array<WindowTopKItem>
+  /** Free slots no longer reachable from currentTime */
+  expire(currentTime: bigint): void
+  /** Remove all recorded events */
+  clear(): void
+  /** Get the number of heavy hitters reported */
+  k(): number
+  /** Get the largest queryable window */
+  windowSize(): bigint
+  /** Get the number of time slots */
+  numSlots(): number
+  /** Get the time units covered by each slot */
+  slotDuration(): bigint
+  toString(): string
+}
+/**
+ * Sampled per-key event counts over a sliding window, for line-rate telemetry
+ *
+ * Each update is recorded with probability sampleRate into time-bucketed
+ * Count-Min Sketches; window queries scale the sampled counts back up.
+ * Keys with fewer than 1 / sampleRate events in a window are estimated
+ * coarsely.
+ */
+export declare class SlidingNitroSketch {
+  /** Create a sampled sliding window sketch split into `numSlots` time buckets */
+  constructor(windowSize: bigint, numSlots: number, sampleRate: number, epsilon: number, delta: number)
+  /** Offer one occurrence of key at timestamp, recorded with probability sampleRate */
+  updateSampledAt(key: Buffer, timestamp: bigint): void
+  /** Estimate occurrences of key in [currentTime - window, currentTime] */
+  queryWindow(key: Buffer, currentTime: bigint, window: bigint): number
+  /** Free slots no longer reachable from currentTime */
+  expire(currentTime: bigint): void
+  /** Remove all recorded events */
+  clear(): void
+  /** Get the probability of recording each update */
+  sampleRate(): number
+  /** Get the largest queryable window */
+  windowSize(): bigint
+  /** Get the number of time slots */
+  numSlots(): number
+  /** Get the time units covered by each slot */
+  slotDuration(): bigint
+  /** Get bytes used by allocated Count-Min counters */
+  memoryUsage(): number
+  toString(): string
+}
+/**
+ * Approximate per-key rate limiter over a sliding window
+ *
+ * Admits between limit / (1 + epsilon) and limit / (1 - epsilon) requests
+ * per window; counts below 1 / epsilon are exact.
+ */
+export declare class SlidingRateLimiter {
+  /** Create a limiter admitting `limit` requests per key every `windowSeconds` */
+  constructor(limit: bigint, windowSeconds: bigint, epsilon: number)
+  /** Record a request for key at now; false (and not counted) if over the limit */
+  tryAcquire(key: Buffer, now: bigint): boolean
+  /** Approximate admitted requests for key in [now - windowSeconds, now] */
+  count(key: Buffer, now: bigint): bigint
+  /** Drop keys with no admissions in the window ending at now */
+  expire(now: bigint): void
+  /** Forget every key */
+  clear(): void
+  /** Get the per-key limit */
+  limit(): bigint
+  /** Get the window length */
+  windowSeconds(): bigint
+  /** Get the number of tracked keys */
+  numKeys(): number
+  toString(): string
+}
 /** Exponential Histogram with formal error bounds */
 export declare class ExponentialHistogram {
   /** Create a new Exponential Histogram */
@@ -1671,6 +2978,12 @@ export declare class ExponentialHistogram {
   insert(timestamp: bigint, count: bigint): void
   /** Get count estimate with bounds */
   count(currentTime: bigint): CountWithBounds
+  /** Get count estimate with bounds, ignoring expired buckets without mutating state */
+  countAsOf(currentTime: bigint): CountWithBounds
+  /** Get count estimate with bounds for a specific range without mutating state */
+  countRangeAsOf(start: bigint, end: bigint): CountWithBounds
+  /** Get the approximate timestamp of the n-th most recent event */
+  timestampForCount(n: bigint): bigint | null
   /** Expire old buckets */
   expire(currentTime: bigint): void
   /** Clear all buckets */
@@ -1704,6 +3017,13 @@ export declare class ElasticSketch {
   constructor(bucketCount: number, depth: number)
   /** Create with custom elastic ratio */
   static withElasticRatio(bucketCount: number, depth: number, elasticRatio: number): ElasticSketch
+  /**
+   * Create with custom elastic ratio and promotion ratio
+   *
+   * A colliding item evicts a bucket's resident once its votes reach
+   * `promotionRatio × frequency`; lower values favour late heavy hitters.
+   */
+  static withPromotionRatio(bucketCount: number, depth: number, elasticRatio: number, promotionRatio: number): ElasticSketch
   /** Update with item and count */
   update(item: Buffer, count: bigint): void
   /** Estimate frequency of an item */
@@ -1712,12 +3032,16 @@ export declare class ElasticSketch {
   heavyHitters(threshold: bigint): Array<HeavyHitter>
   /** Reset the sketch */
   reset(): void
+  /** Clear all counts, keeping dimensions and ratios (same as `reset`) */
+  clear(): void
   /** Get bucket count */
   bucketCount(): number
   /** Get depth */
   depth(): number
   /** Get elastic ratio */
   elasticRatio(): number
+  /** Get promotion ratio (Infinity unless built with withPromotionRatio) */
+  promotionRatio(): number
   /** Get total count */
   totalCount(): bigint
   /** Check if empty */
@@ -1726,6 +3050,12 @@ export declare class ElasticSketch {
   memoryUsage(): number
   /** Merge another ElasticSketch */
   merge(other: ElasticSketch): void
+  /**
+   * Check the sketch's internal invariants, throwing if any is violated
+   *
+   * Use after `deserialize` or `merge` to detect a corrupted sketch.
+   */
+  validate(): void
   /** Serialize to binary format */
   serialize(): Buffer
   /** Deserialize from binary format */
@@ -1755,7 +3085,9 @@ export declare class SALSA {
   width(): number
   /** Get depth */
   depth(): number
-  /** Merge another SALSA sketch */
+  /** Clear all counts, keeping epsilon, delta and dimensions */
+  clear(): void
+  /** Merge another SALSA sketch, promoting to the higher adaptation level */
   merge(other: SALSA): void
   /** Get string representation */
   toString(): string
@@ -1780,6 +3112,28 @@ export declare class RemovableUniversalSketch {
   depth(): number
   /** Merge another RemovableUniversalSketch */
   merge(other: RemovableUniversalSketch): void
+  /**
+   * Serialize the sketch to binary format
+   *
+   * # Example
+   * ```javascript
+   * const data = sketch.serialize();
+   * fs.writeFileSync('rus.bin', data);
+   * ```
+   */
+  serialize(): Buffer
+  /**
+   * Deserialize from binary format
+   *
+   * # Arguments
+   * * `data` - Binary data from serialize()
+   *
+   * # Example
+   * ```javascript
+   * const sketch = RemovableUniversalSketch.deserialize(fs.readFileSync('rus.bin'));
+   * ```
+   */
+  static deserialize(data: Buffer): RemovableUniversalSketch
   /** Get string representation */
   toString(): string
 }
@@ -1843,6 +3197,30 @@ export declare class HeavyKeeper {
    * ```
    */
   update(item: Buffer): void
+  /**
+   * Add `count` occurrences of an item at once
+   *
+   * # Throws
+   * - If count is negative
+   *
+   * # Example
+   * ```javascript
+   * hk.updateBy(Buffer.from('flow'), 1500);
+   * ```
+   */
+  updateBy(item: Buffer, count: number): void
+  /**
+   * Add a fractional weight for an item, e.g. bytes in a flow
+   *
+   * The weight is rounded stochastically; non-finite or non-positive
+   * weights are ignored.
+   *
+   * # Example
+   * ```javascript
+   * hk.updateWeighted(Buffer.from('flow'), 1500.5);
+   * ```
+   */
+  updateWeighted(item: Buffer, weight: number): void
   /**
    * Estimate the frequency of an item
    *
@@ -2271,6 +3649,21 @@ export declare class SlidingHyperLogLog {
    * ```
    */
   estimateTotal(): number
+  /**
+   * Estimate distinct items first seen after a cutoff
+   *
+   * Unlike `estimateWindow`, items seen before the cutoff and again
+   * after it are not counted.
+   *
+   * # Arguments
+   * * `cutoffTs` - Cutoff timestamp in seconds
+   *
+   * # Example
+   * ```javascript
+   * const newUsers = hll.distinctSince(startOfWeek);
+   * ```
+   */
+  distinctSince(cutoffTs: bigint): number
   /**
    * Remove expired entries outside the window
    *
@@ -2364,6 +3757,24 @@ export declare class VacuumFilter {
    * ```
    */
   contains(key: Buffer): boolean
+  /**
+   * Measure the false positive rate on known-absent keys
+   *
+   * Diagnostic only: the result depends on the sample, which must not
+   * overlap the inserted keys.
+   *
+   * # Arguments
+   * * `absentKeys` - Keys known not to have been inserted
+   *
+   * # Returns
+   * Fraction of `absentKeys` reported as present (0 for an empty array)
+   *
+   * # Example
+   * ```javascript
+   * const fpr = filter.measuredFpr([Buffer.from('x'), Buffer.from('y')]);
+   * ```
+   */
+  measuredFpr(absentKeys: Array<Buffer>): number
   /**
    * Delete an element from the filter
    *
@@ -2380,6 +3791,23 @@ export declare class VacuumFilter {
    * ```
    */
   delete(key: Buffer): boolean
+  /**
+   * Add every fingerprint of another filter
+   *
+   * The other filter must use fingerprints at least as wide as this one's
+   * (i.e. a target FPR at most as large). The result uses the smaller of
+   * the two bucket counts; filters cannot grow from fingerprints alone.
+   *
+   * # Throws
+   * - If the other filter has narrower fingerprints
+   * - If the combined items do not fit
+   *
+   * # Example
+   * ```javascript
+   * filter.unionInto(otherFilter);
+   * ```
+   */
+  unionInto(other: VacuumFilter): void
   /**
    * Get current load factor (0.0 to 1.0)
    *
@@ -2452,6 +3880,28 @@ export declare class VacuumFilter {
    * ```
    */
   clear(): void
+  /**
+   * Serialize the filter to binary format
+   *
+   * # Example
+   * ```javascript
+   * const data = filter.serialize();
+   * fs.writeFileSync('vacuum.bin', data);
+   * ```
+   */
+  serialize(): Buffer
+  /**
+   * Deserialize from binary format
+   *
+   * # Arguments
+   * * `data` - Binary data from serialize()
+   *
+   * # Example
+   * ```javascript
+   * const filter = VacuumFilter.deserialize(fs.readFileSync('vacuum.bin'));
+   * ```
+   */
+  static deserialize(data: Buffer): VacuumFilter
   /** Get string representation */
   toString(): string
 }
@@ -2668,6 +4118,26 @@ export declare class NitroSketch {
    * ```
    */
   sync(unsampledWeight: number): void
+  /**
+   * Change the sample rate for subsequent updates
+   *
+   * The rate is clamped to (0, 1]. Samples are weighted by the rate in
+   * force when they were taken, so sync() stays unbiased across changes.
+   *
+   * # Arguments
+   * * `rate` - New sample rate
+   *
+   * # Throws
+   * - If rate is not a positive finite number
+   *
+   * # Example
+   * ```javascript
+   * if (backlog > limit) nitro.setRate(nitro.currentRate() / 2);
+   * ```
+   */
+  setRate(rate: number): void
+  /** Get the sample rate applied to new updates */
+  currentRate(): number
   /**
    * Get statistics about sampling
    *
@@ -2921,6 +4391,25 @@ export declare class LearnedBloomFilter {
    * ```
    */
   contains(key: Buffer): boolean
+  /**
+   * Add members without retraining the model
+   *
+   * Keys the model does not already accept go into the backup filter, so
+   * there are still no false negatives. The model is not updated; check
+   * `needsRetrain()` and rebuild with `LearnedBloomFilter.new` when it
+   * returns `true`.
+   *
+   * # Example
+   * ```javascript
+   * filter.addMembers([Buffer.from('late1'), Buffer.from('late2')]);
+   * if (filter.needsRetrain()) {
+   *   filter = LearnedBloomFilter.new(allKeys, 0.01);
+   * }
+   * ```
+   */
+  addMembers(newKeys: Array<Buffer>): void
+  /** Whether the backup filter has outgrown its size and the filter should be rebuilt */
+  needsRetrain(): boolean
   /**
    * Get memory usage in bytes
    *
@@ -2933,3 +4422,23 @@ export declare class LearnedBloomFilter {
   /** Get string representation */
   toString(): string
 }
+export declare namespace sizing {
+  /**
+   * Bytes of HyperLogLog registers needed for a relative standard error
+   *
+   * # Example
+   * ```javascript
+   * const { sizing } = require('@sketch-oxide/node');
+   * console.log(sizing.bytesForRse(0.01)); // 16384
+   * ```
+   */
+  export function bytesForRse(target: number): number
+  /** Bytes of counters for a Count-Min Sketch with error bound ε and failure probability δ */
+  export function cmsBytesFor(epsilon: number, delta: number): number
+  /**
+   * Worst-case bytes for a KLL sketch with the given rank error after `n` items
+   *
+   * KLL grows with the stream, so the item count is required.
+   */
+  export function kllBytesFor(rankError: number, n: number): number
+}
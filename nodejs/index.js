@@ -310,37 +310,54 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { HyperLogLog, UltraLogLog, CpcSketch, QSketch, ThetaSketch, CountMinSketch, CountSketch, ConservativeCountMin, SpaceSaving, FrequentItemsErrorType, FrequentItems, BinaryFuseFilter, BloomFilter, BlockedBloomFilter, CountingBloomFilter, CuckooFilter, RibbonFilter, StableBloomFilter, DDSketch, ReqSketchMode, ReqSketch, TDigest, KllSketch, SplineSketch, MinHash, SimHash, ReservoirSampling, VarOptSampling, SlidingWindowCounter, ExponentialHistogram, ElasticSketch, SALSA, RemovableUniversalSketch, HeavyKeeper, RatelessIBLT, Grafite, MementoFilter, SlidingHyperLogLog, VacuumFilter, GRF, NitroSketch, UnivMon, LearnedBloomFilter } = nativeBinding
+const { HllEstimator, HyperLogLog, hllIntersection, AdaptiveHyperLogLog, GroupedHll, UltraLogLog, CpcSketch, DistinctCounter, QSketch, ThetaSketch, CompactThetaSketch, CountMinSketch, CountedHyperLogLog, CountedCountMinSketch, CountSketch, ConservativeCountMin, SpaceSaving, FrequentItemsErrorType, FrequentItems, BinaryFuseFilter, CountingFuseFilter, BloomFilter, BlockedBloomFilter, CountingBloomFilter, CuckooFilter, RibbonFilter, StableBloomFilter, DDSketchMapping, DDSketch, ReqSketchMode, ReqSketch, TDigest, StreamSummary, KllSketch, GkSketch, SplineSketch, MinHash, SimHash, ReservoirSampling, VarOptSampling, estimateJoinSize, SlidingWindowCounter, SlidingWindowFrequency, SlidingTopK, SlidingNitroSketch, SlidingRateLimiter, ExponentialHistogram, ElasticSketch, SALSA, RemovableUniversalSketch, HeavyKeeper, RatelessIBLT, Grafite, MementoFilter, SlidingHyperLogLog, VacuumFilter, GRF, NitroSketch, UnivMon, LearnedBloomFilter, sizing } = nativeBinding
 
+module.exports.HllEstimator = HllEstimator
 module.exports.HyperLogLog = HyperLogLog
+module.exports.hllIntersection = hllIntersection
+module.exports.AdaptiveHyperLogLog = AdaptiveHyperLogLog
+module.exports.GroupedHll = GroupedHll
 module.exports.UltraLogLog = UltraLogLog
 module.exports.CpcSketch = CpcSketch
+module.exports.DistinctCounter = DistinctCounter
 module.exports.QSketch = QSketch
 module.exports.ThetaSketch = ThetaSketch
+module.exports.CompactThetaSketch = CompactThetaSketch
 module.exports.CountMinSketch = CountMinSketch
+module.exports.CountedHyperLogLog = CountedHyperLogLog
+module.exports.CountedCountMinSketch = CountedCountMinSketch
 module.exports.CountSketch = CountSketch
 module.exports.ConservativeCountMin = ConservativeCountMin
 module.exports.SpaceSaving = SpaceSaving
 module.exports.FrequentItemsErrorType = FrequentItemsErrorType
 module.exports.FrequentItems = FrequentItems
 module.exports.BinaryFuseFilter = BinaryFuseFilter
+module.exports.CountingFuseFilter = CountingFuseFilter
 module.exports.BloomFilter = BloomFilter
 module.exports.BlockedBloomFilter = BlockedBloomFilter
 module.exports.CountingBloomFilter = CountingBloomFilter
 module.exports.CuckooFilter = CuckooFilter
 module.exports.RibbonFilter = RibbonFilter
 module.exports.StableBloomFilter = StableBloomFilter
+module.exports.DDSketchMapping = DDSketchMapping
 module.exports.DDSketch = DDSketch
 module.exports.ReqSketchMode = ReqSketchMode
 module.exports.ReqSketch = ReqSketch
 module.exports.TDigest = TDigest
+module.exports.StreamSummary = StreamSummary
 module.exports.KllSketch = KllSketch
+module.exports.GkSketch = GkSketch
 module.exports.SplineSketch = SplineSketch
 module.exports.MinHash = MinHash
 module.exports.SimHash = SimHash
 module.exports.ReservoirSampling = ReservoirSampling
 module.exports.VarOptSampling = VarOptSampling
+module.exports.estimateJoinSize = estimateJoinSize
 module.exports.SlidingWindowCounter = SlidingWindowCounter
+module.exports.SlidingWindowFrequency = SlidingWindowFrequency
+module.exports.SlidingTopK = SlidingTopK
+module.exports.SlidingNitroSketch = SlidingNitroSketch
+module.exports.SlidingRateLimiter = SlidingRateLimiter
 module.exports.ExponentialHistogram = ExponentialHistogram
 module.exports.ElasticSketch = ElasticSketch
 module.exports.SALSA = SALSA
@@ -355,3 +372,4 @@ module.exports.GRF = GRF
 module.exports.NitroSketch = NitroSketch
 module.exports.UnivMon = UnivMon
 module.exports.LearnedBloomFilter = LearnedBloomFilter
+module.exports.sizing = sizing
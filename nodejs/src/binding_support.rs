@@ -0,0 +1,135 @@
+// Declarative helpers for the boilerplate shared by most sketch bindings
+
+/// Generates a `#[napi]` wrapper class around a sketch_oxide type
+///
+/// Emits the class struct (holding the sketch in `inner`) and one `#[napi]`
+/// impl with the common methods selected by the optional sections below.
+/// Each section names the Rust method it forwards to. Sections are optional
+/// but must appear in this order:
+///
+/// | Section                          | JS method(s)                          |
+/// |----------------------------------|---------------------------------------|
+/// | `update => m;`                   | `update(item: Buffer): void`          |
+/// | `insert => m;`                   | `insert(key: Buffer): void`           |
+/// | `contains => m;`                 | `contains(key: Buffer): boolean`      |
+/// | `estimate => m;`                 | `estimate(): number`                  |
+/// | `clear => m;`                    | `clear(): void`                       |
+/// | `is_empty => m;`                 | `isEmpty(): boolean`                  |
+/// | `len => m;`                      | `len(): number`                       |
+/// | `memory_usage => m;`             | `memoryUsage(): number`               |
+/// | `serialize => to, from;`         | `serialize(): Buffer`, `static deserialize(data: Buffer)` |
+///
+/// Constructors and sketch-specific methods go in a separate `#[napi] impl`
+/// block next to the macro invocation.
+///
+/// # Example
+///
+/// ```ignore
+/// sketch_binding! {
+///     /// Docs for the JS class
+///     pub struct BloomFilter(RustBloomFilter);
+///     insert => insert;
+///     contains => contains;
+///     serialize => to_bytes, from_bytes;
+/// }
+///
+/// #[napi]
+/// impl BloomFilter {
+///     #[napi(constructor)]
+///     pub fn new(n: u32) -> Self { /* ... */ }
+/// }
+/// ```
+macro_rules! sketch_binding {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident($inner:ty);
+        $(update => $update:ident;)?
+        $(insert => $insert:ident;)?
+        $(contains => $contains:ident;)?
+        $(estimate => $estimate:ident;)?
+        $(clear => $clear:ident;)?
+        $(is_empty => $is_empty:ident;)?
+        $(len => $len:ident;)?
+        $(memory_usage => $memory_usage:ident;)?
+        $(serialize => $to_bytes:ident, $from_bytes:ident;)?
+    ) => {
+        $(#[$meta])*
+        #[napi]
+        pub struct $name {
+            inner: $inner,
+        }
+
+        #[napi]
+        impl $name {
+            $(
+                #[napi]
+                pub fn update(&mut self, item: Buffer) {
+                    self.inner.$update(&item);
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn insert(&mut self, key: Buffer) {
+                    self.inner.$insert(&key);
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn contains(&self, key: Buffer) -> bool {
+                    self.inner.$contains(&key)
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn estimate(&self) -> f64 {
+                    self.inner.$estimate()
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn clear(&mut self) {
+                    self.inner.$clear();
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn isEmpty(&self) -> bool {
+                    self.inner.$is_empty()
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn len(&self) -> u32 {
+                    self.inner.$len() as u32
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn memoryUsage(&self) -> u32 {
+                    self.inner.$memory_usage() as u32
+                }
+            )?
+
+            $(
+                #[napi]
+                pub fn serialize(&self) -> Buffer {
+                    Buffer::from(self.inner.$to_bytes())
+                }
+
+                #[napi(factory)]
+                pub fn deserialize(data: Buffer) -> Result<Self> {
+                    <$inner>::$from_bytes(&data)
+                        .map(|inner| Self { inner })
+                        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+                }
+            )?
+        }
+    };
+}
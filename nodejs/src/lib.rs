@@ -1,13 +1,22 @@
 #![allow(non_snake_case)]
 
+#[macro_use]
+mod binding_support;
+
 use napi::bindgen_prelude::*;
+use napi::JsBuffer;
 use napi_derive::napi;
+use sketch_oxide::cardinality::AdaptiveHyperLogLog as RustAdaptiveHyperLogLog;
+use sketch_oxide::cardinality::CompactThetaSketch as RustCompactThetaSketch;
 use sketch_oxide::cardinality::CpcSketch as RustCpcSketch;
+use sketch_oxide::cardinality::DistinctCounter as RustDistinctCounter;
+use sketch_oxide::cardinality::Estimator as RustEstimator;
+use sketch_oxide::cardinality::GroupedHll as RustGroupedHll;
 use sketch_oxide::cardinality::HyperLogLog as RustHyperLogLog;
 use sketch_oxide::cardinality::QSketch as RustQSketch;
 use sketch_oxide::cardinality::ThetaSketch as RustThetaSketch;
 use sketch_oxide::cardinality::UltraLogLog as RustUltraLogLog;
-use sketch_oxide::common::RangeFilter;
+use sketch_oxide::common::{Counted as RustCounted, RangeFilter};
 use sketch_oxide::frequency::ConservativeCountMin as RustConservativeCountMin;
 use sketch_oxide::frequency::CountMinSketch as RustCountMinSketch;
 use sketch_oxide::frequency::CountSketch as RustCountSketch;
@@ -18,13 +27,18 @@ use sketch_oxide::frequency::{ErrorType as RustErrorType, FrequentItems as RustF
 use sketch_oxide::membership::{
     BinaryFuseFilter as RustBinaryFuseFilter, BlockedBloomFilter as RustBlockedBloomFilter,
     BloomFilter as RustBloomFilter, CountingBloomFilter as RustCountingBloomFilter,
-    CuckooFilter as RustCuckooFilter, LearnedBloomFilter as RustLearnedBloomFilter,
+    CountingFuseFilter as RustCountingFuseFilter, CuckooFilter as RustCuckooFilter,
+    LearnedBloomFilter as RustLearnedBloomFilter, RibbonBuildInfo,
     RibbonFilter as RustRibbonFilter, StableBloomFilter as RustStableBloomFilter,
     VacuumFilter as RustVacuumFilter,
 };
+use sketch_oxide::quantiles::otel::OTEL_DEFAULT_MAX_BUCKETS;
+use sketch_oxide::quantiles::{ks_distance, tdigest_ks_distance};
 use sketch_oxide::quantiles::{
-    DDSketch as RustDDSketch, KllSketch as RustKllSketch, ReqMode, ReqSketch as RustReqSketch,
-    SplineSketch as RustSplineSketch, TDigest as RustTDigest,
+    DDSketch as RustDDSketch, GkSketch as RustGkSketch, KllSketch as RustKllSketch,
+    Mapping as RustMapping, OtelBuckets as RustOtelBuckets,
+    OtelExpoHistogram as RustOtelExpoHistogram, ReqMode, ReqSketch as RustReqSketch,
+    SplineSketch as RustSplineSketch, StreamSummary as RustStreamSummary, TDigest as RustTDigest,
 };
 use sketch_oxide::range_filters::{
     Grafite as RustGrafite, MementoFilter as RustMementoFilter, GRF as RustGRF,
@@ -32,10 +46,110 @@ use sketch_oxide::range_filters::{
 use sketch_oxide::reconciliation::RatelessIBLT as RustRatelessIBLT;
 use sketch_oxide::streaming::SlidingHyperLogLog as RustSlidingHyperLogLog;
 use sketch_oxide::universal::UnivMon as RustUnivMon;
-use sketch_oxide::{Mergeable, Reconcilable, Sketch};
+use sketch_oxide::{CardinalitySketch, Mergeable, Reconcilable, Sketch};
 use std::hash::{Hash, Hasher};
 use twox_hash::XxHash64;
 
+// ============================================================================
+// Numeric updates shared by the cardinality and frequency sketches
+// ============================================================================
+
+/// Converts a BigInt to u64, rejecting values that don't fit exactly
+fn bigint_to_u64(value: BigInt) -> Result<u64> {
+    let (negative, value, lossless) = value.get_u64();
+    if negative || !lossless {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "value must be an unsigned 64-bit integer",
+        ));
+    }
+    Ok(value)
+}
+
+/// Converts a BigInt to i64, rejecting values that don't fit exactly
+fn bigint_to_i64(value: BigInt) -> Result<i64> {
+    let (value, lossless) = value.get_i64();
+    if !lossless {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "value must be a signed 64-bit integer",
+        ));
+    }
+    Ok(value)
+}
+
+// ============================================================================
+// Async batch ingestion shared by the high-throughput sketches
+// ============================================================================
+
+/// A sketch class that can ingest raw byte items off the main thread
+pub trait BatchUpdate: Send + 'static {
+    /// Inserts one item exactly as the synchronous `update` would
+    fn update_bytes(&mut self, item: &[u8]);
+}
+
+/// Runs a batch of updates on the libuv threadpool
+///
+/// The items are copied into one contiguous buffer on the main thread, so
+/// the task owns its input and never touches JS memory while computing.
+/// The `Reference` keeps the JS object alive until the task completes, and
+/// the task is dropped back on the main thread after `resolve`.
+pub struct UpdateBatchTask<T: BatchUpdate> {
+    sketch: Reference<T>,
+    data: Vec<u8>,
+    ends: Vec<usize>,
+}
+
+impl<T: BatchUpdate> UpdateBatchTask<T> {
+    /// Copies the items out of the JS array
+    ///
+    /// Reads each element as a borrowed `JsBuffer` rather than `Buffer`,
+    /// which would create and release a napi reference per item and make
+    /// this main-thread copy slower than a synchronous update loop.
+    fn new(sketch: Reference<T>, items: Array) -> Result<Self> {
+        let mut data = Vec::new();
+        let mut ends = Vec::with_capacity(items.len() as usize);
+        for i in 0..items.len() {
+            let item = items.get::<JsBuffer>(i)?.ok_or_else(|| {
+                Error::new(Status::InvalidArg, format!("items[{}] is not a Buffer", i))
+            })?;
+            data.extend_from_slice(&item.into_value()?);
+            ends.push(data.len());
+        }
+        Ok(UpdateBatchTask { sketch, data, ends })
+    }
+}
+
+impl<T: BatchUpdate> Task for UpdateBatchTask<T> {
+    type Output = ();
+    type JsValue = ();
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let mut start = 0;
+        for &end in &self.ends {
+            self.sketch.update_bytes(&self.data[start..end]);
+            start = end;
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+        Ok(())
+    }
+}
+
+/// Estimator applied to HyperLogLog registers by `estimateWith`
+#[napi]
+pub enum HllEstimator {
+    /// Harmonic mean with linear counting for small cardinalities
+    Original,
+    /// Default used by `estimate()`: `Original` plus the 32-bit large-range
+    /// correction (not HyperLogLog++)
+    Classic32BitCorrected,
+    /// Ertl maximum likelihood; no bias around the linear counting switch
+    MaximumLikelihood,
+}
+
 /// HyperLogLog cardinality estimator
 ///
 /// Provides ~1.04/sqrt(m) standard error where m = 2^precision
@@ -53,6 +167,12 @@ pub struct HyperLogLog {
     inner: RustHyperLogLog,
 }
 
+impl BatchUpdate for HyperLogLog {
+    fn update_bytes(&mut self, item: &[u8]) {
+        self.inner.update(&item);
+    }
+}
+
 #[napi]
 impl HyperLogLog {
     /// Create a new HyperLogLog with given precision
@@ -82,6 +202,30 @@ impl HyperLogLog {
             })
     }
 
+    /// Create the most precise HyperLogLog whose registers fit in a byte budget
+    ///
+    /// # Arguments
+    /// * `bytes` - Memory available for the registers (at least 16)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const hll = HyperLogLog.forByteBudget(8192); // precision 13, ~1.15% error
+    /// ```
+    #[napi(factory)]
+    pub fn forByteBudget(bytes: i64) -> Result<Self> {
+        if bytes < 0 {
+            return Err(Error::new(Status::InvalidArg, "bytes must be >= 0"));
+        }
+        RustHyperLogLog::for_byte_budget(bytes as usize)
+            .map(|inner| HyperLogLog { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("HyperLogLog creation failed: {}", e),
+                )
+            })
+    }
+
     /// Add an item to the sketch
     ///
     /// # Arguments
@@ -95,12 +239,92 @@ impl HyperLogLog {
     #[napi]
     pub fn update(&mut self, item: Buffer) -> Result<()> {
         // Note: NAPI Buffer needs to be converted to Vec for the Rust trait
-        // Future optimization: Add async_batch() method for better throughput
         let data: Vec<u8> = item.to_vec();
         self.inner.update(&data);
         Ok(())
     }
 
+    /// Add an unsigned 64-bit integer without converting it to a Buffer
+    ///
+    /// Hashes the value's fixed 8-byte little-endian encoding, so
+    /// `updateU64(5n)` has the same effect as `update` with those bytes and
+    /// as `update_u64` in the Rust crate.
+    ///
+    /// # Throws
+    /// - If the value is negative or exceeds 64 bits
+    ///
+    /// # Example
+    /// ```javascript
+    /// hll.updateU64(12345n);
+    /// ```
+    #[napi]
+    pub fn updateU64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_u64(bigint_to_u64(value)?);
+        Ok(())
+    }
+
+    /// Add a signed 64-bit integer (8-byte little-endian two's complement)
+    ///
+    /// # Throws
+    /// - If the value doesn't fit in 64 bits
+    #[napi]
+    pub fn updateI64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_i64(bigint_to_i64(value)?);
+        Ok(())
+    }
+
+    /// Add a number by its 8-byte little-endian IEEE 754 encoding
+    ///
+    /// `0` and `-0` are hashed as distinct values.
+    #[napi]
+    pub fn updateF64(&mut self, value: f64) {
+        self.inner.update_f64(value);
+    }
+
+    /// Add a pre-computed 64-bit hash, bypassing the internal hasher
+    ///
+    /// The top `precision` bits pick the register and the leading zeros of
+    /// the rest give the rank, so the hash must be well distributed (e.g.
+    /// XXHash64); raw IDs or counters give meaningless estimates.
+    ///
+    /// # Throws
+    /// - If the value is negative or exceeds 64 bits
+    ///
+    /// # Example
+    /// ```javascript
+    /// hll.updateHash(0x9e3779b97f4a7c15n);
+    /// ```
+    #[napi]
+    pub fn updateHash(&mut self, hash: BigInt) -> Result<()> {
+        self.inner.update_hash(bigint_to_u64(hash)?);
+        Ok(())
+    }
+
+    /// Add a batch of items on the libuv threadpool
+    ///
+    /// Hashing and register updates run off the main thread, so large
+    /// batches don't block the event loop; only copying the items happens
+    /// synchronously. Do not call any other method on this sketch, including
+    /// another `updateBatchAsync`, until the returned promise settles: the
+    /// sketch is not locked while the batch runs.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const items = Array.from({ length: 100000 }, (_, i) => Buffer.from(`user-${i}`));
+    /// await hll.updateBatchAsync(items);
+    /// ```
+    #[napi(
+        ts_args_type = "items: Array<Buffer>",
+        ts_return_type = "Promise<void>"
+    )]
+    pub fn updateBatchAsync(
+        &self,
+        reference: Reference<HyperLogLog>,
+        items: Array,
+    ) -> Result<AsyncTask<UpdateBatchTask<HyperLogLog>>> {
+        Ok(AsyncTask::new(UpdateBatchTask::new(reference, items)?))
+    }
+
     /// Get current cardinality estimate
     ///
     /// # Returns
@@ -116,6 +340,21 @@ impl HyperLogLog {
         Ok(self.inner.estimate())
     }
 
+    /// Estimate the cardinality with a specific estimator
+    ///
+    /// # Example
+    /// ```javascript
+    /// const ml = hll.estimateWith(HllEstimator.MaximumLikelihood);
+    /// ```
+    #[napi]
+    pub fn estimateWith(&self, estimator: HllEstimator) -> f64 {
+        self.inner.estimate_with(match estimator {
+            HllEstimator::Original => RustEstimator::Original,
+            HllEstimator::Classic32BitCorrected => RustEstimator::Classic32BitCorrected,
+            HllEstimator::MaximumLikelihood => RustEstimator::MaximumLikelihood,
+        })
+    }
+
     /// Merge another HyperLogLog sketch into this one
     ///
     /// # Arguments
@@ -140,6 +379,38 @@ impl HyperLogLog {
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Estimate the cardinality of the union without modifying either sketch
+    ///
+    /// # Arguments
+    /// * `other` - Another HyperLogLog with same precision
+    ///
+    /// # Throws
+    /// - If the sketches are incompatible
+    ///
+    /// # Example
+    /// ```javascript
+    /// const total = hll1.estimateUnion(hll2);
+    /// ```
+    #[napi]
+    pub fn estimateUnion(&self, other: &HyperLogLog) -> Result<f64> {
+        self.inner
+            .estimate_union(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    /// Get a JSON summary of parameters and state for debugging
+    ///
+    /// Not meant for reconstruction; use `serialize()` for that.
+    ///
+    /// # Example
+    /// ```javascript
+    /// console.log(JSON.parse(hll.toJson()).estimate);
+    /// ```
+    #[napi]
+    pub fn toJson(&self) -> String {
+        self.inner.to_json()
+    }
+
     /// Create a new sketch instance (reset)
     /// Note: In Rust, we create a new instance instead of resetting
     /// Use `new HyperLogLog(precision)` instead
@@ -162,6 +433,43 @@ impl HyperLogLog {
         Ok(self.inner.precision())
     }
 
+    /// Fold the sketch down to a lower precision
+    ///
+    /// # Arguments
+    /// * `targetPrecision` - Precision of the returned sketch (4 to current)
+    ///
+    /// # Throws
+    /// - If targetPrecision exceeds the current precision or is below 4
+    ///
+    /// # Example
+    /// ```javascript
+    /// const hll = new HyperLogLog(16);
+    /// const small = hll.foldTo(12);
+    /// console.log(small.precision()); // 12
+    /// ```
+    #[napi]
+    pub fn foldTo(&self, target_precision: u8) -> Result<HyperLogLog> {
+        self.inner
+            .fold_to(target_precision)
+            .map(|inner| HyperLogLog { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Fold failed: {}", e)))
+    }
+
+    /// Stable 64-bit hash of the sketch content, as BigInt
+    ///
+    /// Identical sketches, including serialize/deserialize copies, share the
+    /// same hash. Not a cryptographic hash: distinct sketches can collide.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const copy = HyperLogLog.deserialize(hll.serialize());
+    /// console.log(copy.contentHash() === hll.contentHash()); // true
+    /// ```
+    #[napi]
+    pub fn contentHash(&self) -> BigInt {
+        BigInt::from(self.inner.content_hash())
+    }
+
     /// Serialize the sketch to binary format
     ///
     /// # Returns
@@ -217,261 +525,328 @@ impl HyperLogLog {
     }
 }
 
-// =============================================================================
-// UltraLogLog - State-of-the-art cardinality estimation (VLDB 2024)
-// =============================================================================
+/// Estimate the size of the intersection of several HyperLogLog sketches
+///
+/// Uses inclusion-exclusion over all 2^n - 1 subset unions. Error grows
+/// quickly with the number of sketches and with small overlaps.
+///
+/// # Arguments
+/// * `sketches` - Between 1 and 6 HyperLogLog sketches with the same precision
+///
+/// # Throws
+/// - If the array is empty or has more than 6 sketches
+/// - If precisions don't match
+///
+/// # Example
+/// ```javascript
+/// const { HyperLogLog, hllIntersection } = require('@sketch-oxide/node');
+/// const a = new HyperLogLog(14);
+/// const b = new HyperLogLog(14);
+/// // ... add items ...
+/// console.log(hllIntersection([a, b]));
+/// ```
+#[napi]
+pub fn hllIntersection(sketches: Vec<ClassInstance<HyperLogLog>>) -> Result<f64> {
+    let inners: Vec<&RustHyperLogLog> = sketches.iter().map(|s| &s.inner).collect();
+    sketch_oxide::cardinality::hll_intersection(&inners).map_err(|e| {
+        Error::new(
+            Status::InvalidArg,
+            format!("HyperLogLog intersection failed: {}", e),
+        )
+    })
+}
 
-/// UltraLogLog cardinality estimator - 28% more space-efficient than HyperLogLog
+/// HyperLogLog whose precision grows with the cardinality
 ///
-/// UltraLogLog is a state-of-the-art algorithm from VLDB 2024 that provides
-/// the same accuracy as HyperLogLog while using 28% less memory.
+/// Items are recorded at `maxPrecision` (sparsely while few registers are
+/// touched) and reported at a current precision that starts at `minPrecision`
+/// and increases as the estimate crosses load thresholds. Saves memory when
+/// most sketches stay small.
 ///
 /// # Example
 /// ```javascript
-/// const { UltraLogLog } = require('@sketch-oxide/node');
-/// const ull = new UltraLogLog(12);
-/// ull.update(Buffer.from('item1'));
-/// ull.update(Buffer.from('item2'));
-/// console.log(ull.estimate()); // ~2
+/// const hll = new AdaptiveHyperLogLog(6, 14);
+/// hll.update(Buffer.from('item1'));
+/// console.log(hll.currentPrecision()); // 6
 /// ```
 #[napi]
-pub struct UltraLogLog {
-    inner: RustUltraLogLog,
+pub struct AdaptiveHyperLogLog {
+    inner: RustAdaptiveHyperLogLog,
 }
 
 #[napi]
-impl UltraLogLog {
-    /// Create a new UltraLogLog with given precision
+impl AdaptiveHyperLogLog {
+    /// Create an adaptive HyperLogLog
     ///
     /// # Arguments
-    /// * `precision` - Number of bits for the hash (4-18, typical 12-14)
-    ///   - precision 4: 16 registers, 16 bytes
-    ///   - precision 8: 256 registers, 256 bytes
-    ///   - precision 12: 4096 registers, 4 KB (recommended)
-    ///   - precision 16: 65536 registers, 64 KB
-    ///   - precision 18: 262144 registers, 256 KB
-    ///
-    /// # Returns
-    /// A new UltraLogLog instance
+    /// * `minPrecision` - Precision to start reporting at (4-18)
+    /// * `maxPrecision` - Precision registers are recorded at (4-18)
+    /// * `loadThreshold` - Estimate / registers ratio that raises the precision (default 0.5)
     ///
     /// # Throws
-    /// - If precision is out of valid range (4-18)
-    ///
-    /// # Example
-    /// ```javascript
-    /// const ull = new UltraLogLog(12);
-    /// ```
+    /// - If a precision is out of range, `minPrecision > maxPrecision`, or
+    ///   the threshold is not positive
     #[napi(constructor)]
-    pub fn new(precision: u8) -> Result<Self> {
-        RustUltraLogLog::new(precision)
-            .map(|inner| UltraLogLog { inner })
+    pub fn new(min_precision: u8, max_precision: u8, load_threshold: Option<f64>) -> Result<Self> {
+        let result = match load_threshold {
+            Some(threshold) => {
+                RustAdaptiveHyperLogLog::with_threshold(min_precision, max_precision, threshold)
+            }
+            None => RustAdaptiveHyperLogLog::new(min_precision, max_precision),
+        };
+        result
+            .map(|inner| AdaptiveHyperLogLog { inner })
             .map_err(|e| {
                 Error::new(
                     Status::InvalidArg,
-                    format!("UltraLogLog creation failed: {}", e),
+                    format!("AdaptiveHyperLogLog creation failed: {}", e),
                 )
             })
     }
 
     /// Add an item to the sketch
     ///
-    /// # Arguments
-    /// * `item` - Binary data to add
-    ///
-    /// # Example
-    /// ```javascript
-    /// ull.update(Buffer.from('item1'));
-    /// ull.update(Buffer.from('hello world'));
-    /// ```
+    /// Hashes the bytes exactly like `HyperLogLog.update`.
     #[napi]
     pub fn update(&mut self, item: Buffer) -> Result<()> {
         let data: Vec<u8> = item.to_vec();
-        self.inner.add(&data);
+        self.inner.update(&data);
         Ok(())
     }
 
-    /// Get current cardinality estimate
-    ///
-    /// # Returns
-    /// Estimated number of unique items
-    ///
-    /// # Example
-    /// ```javascript
-    /// const estimate = ull.estimate();
-    /// console.log(estimate); // e.g., 1000000
-    /// ```
+    /// Get the cardinality estimate at the current precision
     #[napi]
-    pub fn estimate(&self) -> Result<f64> {
-        Ok(self.inner.estimate())
+    pub fn estimate(&self) -> f64 {
+        self.inner.estimate()
     }
 
-    /// Get the cardinality estimate (alias for estimate)
-    ///
-    /// # Returns
-    /// Estimated number of unique items
-    ///
-    /// # Example
-    /// ```javascript
-    /// const cardinality = ull.cardinality();
-    /// ```
+    /// Get the precision the sketch currently reports at
     #[napi]
-    pub fn cardinality(&self) -> Result<f64> {
-        Ok(self.inner.cardinality())
+    pub fn currentPrecision(&self) -> u8 {
+        self.inner.current_precision()
     }
 
-    /// Merge another UltraLogLog sketch into this one
-    ///
-    /// # Arguments
-    /// * `other` - Another UltraLogLog with same precision
+    /// Get the precision registers are recorded at
+    #[napi]
+    pub fn maxPrecision(&self) -> u8 {
+        self.inner.max_precision()
+    }
+
+    /// Whether registers are still stored sparsely
+    #[napi]
+    pub fn isSparse(&self) -> bool {
+        self.inner.is_sparse()
+    }
+
+    /// Get the standard error at the current precision
+    #[napi]
+    pub fn standardError(&self) -> f64 {
+        self.inner.standard_error()
+    }
+
+    /// Merge another adaptive sketch with the same `maxPrecision`
     ///
     /// # Throws
-    /// - If precisions don't match
-    ///
-    /// # Example
-    /// ```javascript
-    /// const ull1 = new UltraLogLog(12);
-    /// const ull2 = new UltraLogLog(12);
-    /// ull1.update(Buffer.from('a'));
-    /// ull2.update(Buffer.from('b'));
-    /// ull1.merge(ull2);
-    /// console.log(ull1.estimate()); // ~2
-    /// ```
+    /// - If the max precisions differ
     #[napi]
-    pub fn merge(&mut self, other: &UltraLogLog) -> Result<()> {
+    pub fn merge(&mut self, other: &AdaptiveHyperLogLog) -> Result<()> {
         self.inner
             .merge(&other.inner)
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
-    /// Check if the sketch is empty (no items added)
-    ///
-    /// # Returns
-    /// True if no items have been added
-    ///
-    /// # Example
-    /// ```javascript
-    /// const ull = new UltraLogLog(12);
-    /// console.log(ull.isEmpty()); // true
-    /// ull.update(Buffer.from('item'));
-    /// console.log(ull.isEmpty()); // false
-    /// ```
+    /// Convert to a regular HyperLogLog at the current precision
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn toHyperLogLog(&self) -> HyperLogLog {
+        HyperLogLog {
+            inner: self.inner.to_hyperloglog(),
+        }
     }
 
-    /// Serialize the sketch to binary format
-    ///
-    /// # Returns
-    /// Binary representation suitable for storage/transmission
-    ///
-    /// # Example
-    /// ```javascript
-    /// const data = ull.serialize();
-    /// fs.writeFileSync('ull.bin', data);
-    /// ```
     #[napi]
-    pub fn serialize(&self) -> Result<Buffer> {
-        let bytes = self.inner.serialize();
-        Ok(Buffer::from(bytes))
+    pub fn toString(&self) -> String {
+        format!(
+            "AdaptiveHyperLogLog(precision={}, max_precision={}, sparse={}, estimate={:.0})",
+            self.inner.current_precision(),
+            self.inner.max_precision(),
+            self.inner.is_sparse(),
+            self.inner.estimate()
+        )
     }
+}
 
-    /// Deserialize from binary format
+/// Distinct counts per group under a global memory budget
+///
+/// Each group is an adaptive HyperLogLog that stays sparse while small. When
+/// the budget is exceeded, the smallest groups are merged into a shared
+/// "other" bucket and dropped until usage is back to 90% of the budget.
+///
+/// # Example
+/// ```javascript
+/// const grouped = new GroupedHll(12, 1 << 20);
+/// grouped.update(Buffer.from('page_a'), Buffer.from('user1'));
+/// console.log(grouped.estimate(Buffer.from('page_a'))); // ~1
+/// ```
+#[napi]
+pub struct GroupedHll {
+    inner: RustGroupedHll<Vec<u8>>,
+}
+
+#[napi]
+impl GroupedHll {
+    /// Create a grouped HyperLogLog
     ///
     /// # Arguments
-    /// * `data` - Binary data from serialize()
-    ///
-    /// # Returns
-    /// A new UltraLogLog instance
+    /// * `precision` - Max precision of each group's HyperLogLog (4-18)
+    /// * `memoryBudget` - Bytes available for all groups together
     ///
     /// # Throws
-    /// - If data is invalid or corrupted
-    ///
-    /// # Example
-    /// ```javascript
-    /// const data = fs.readFileSync('ull.bin');
-    /// const ull = UltraLogLog.deserialize(data);
-    /// ```
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustUltraLogLog::deserialize(&data)
-            .map(|inner| UltraLogLog { inner })
+    /// - If the precision is out of range or the budget cannot hold one group
+    #[napi(constructor)]
+    pub fn new(precision: u8, memory_budget: u32) -> Result<Self> {
+        RustGroupedHll::new(precision, memory_budget as usize)
+            .map(|inner| GroupedHll { inner })
             .map_err(|e| {
                 Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
+                    Status::InvalidArg,
+                    format!("GroupedHll creation failed: {}", e),
                 )
             })
     }
 
-    /// Get string representation
-    ///
-    /// # Example
-    /// ```javascript
-    /// console.log(ull.toString());
-    /// // "UltraLogLog(estimate=1000000)"
-    /// ```
+    /// Add an item to the distinct set of a group
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "UltraLogLog(estimate={:.0})",
-            self.inner.estimate()
-        ))
+    pub fn update(&mut self, group: Buffer, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&group.to_vec(), &data);
+        Ok(())
+    }
+
+    /// Get the distinct count estimate of a group, or 0 if it is not tracked
+    #[napi]
+    pub fn estimate(&self, group: Buffer) -> f64 {
+        self.inner.estimate(&group.to_vec())
+    }
+
+    /// Get the distinct count estimate over all evicted groups combined
+    #[napi]
+    pub fn otherEstimate(&self) -> f64 {
+        self.inner.other_estimate()
+    }
+
+    /// Whether a group is currently tracked
+    #[napi]
+    pub fn containsGroup(&self, group: Buffer) -> bool {
+        self.inner.contains_group(&group.to_vec())
+    }
+
+    /// Get the number of tracked groups
+    #[napi]
+    pub fn numGroups(&self) -> u32 {
+        self.inner.num_groups() as u32
+    }
+
+    /// Get the number of groups evicted into the other bucket so far
+    #[napi]
+    pub fn evictedGroups(&self) -> i64 {
+        self.inner.evicted_groups() as i64
+    }
+
+    /// Get the approximate bytes used by the tracked groups
+    #[napi]
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "GroupedHll(precision={}, groups={}, evicted={}, memory={}/{})",
+            self.inner.precision(),
+            self.inner.num_groups(),
+            self.inner.evicted_groups(),
+            self.inner.memory_usage(),
+            self.inner.memory_budget()
+        )
     }
 }
 
 // =============================================================================
-// CpcSketch - Most space-efficient cardinality estimator
+// UltraLogLog - State-of-the-art cardinality estimation (VLDB 2024)
 // =============================================================================
 
-/// CPC (Compressed Probabilistic Counting) Sketch
+/// UltraLogLog cardinality estimator - 28% more space-efficient than HyperLogLog
 ///
-/// CPC is 30-40% more space-efficient than HyperLogLog for the same accuracy.
-/// It achieves this through adaptive compression and multiple operational modes.
+/// UltraLogLog is a state-of-the-art algorithm from VLDB 2024 that provides
+/// the same accuracy as HyperLogLog while using 28% less memory.
 ///
 /// # Example
 /// ```javascript
-/// const { CpcSketch } = require('@sketch-oxide/node');
-/// const cpc = new CpcSketch(11);
-/// cpc.update(Buffer.from('item1'));
-/// cpc.update(Buffer.from('item2'));
-/// console.log(cpc.estimate()); // ~2
+/// const { UltraLogLog } = require('@sketch-oxide/node');
+/// const ull = new UltraLogLog(12);
+/// ull.update(Buffer.from('item1'));
+/// ull.update(Buffer.from('item2'));
+/// console.log(ull.estimate()); // ~2
 /// ```
 #[napi]
-pub struct CpcSketch {
-    inner: RustCpcSketch,
+pub struct UltraLogLog {
+    inner: RustUltraLogLog,
 }
 
 #[napi]
-impl CpcSketch {
-    /// Create a new CPC sketch with given lg_k parameter
+impl UltraLogLog {
+    /// Create a new UltraLogLog with given precision
     ///
     /// # Arguments
-    /// * `lgK` - Log2 of k parameter (4-26), higher = more accurate but more memory
-    ///   - lgK 4: k=16, very high error (~20%)
-    ///   - lgK 8: k=256, ~6% error
-    ///   - lgK 11: k=2048, ~2% error (recommended default)
-    ///   - lgK 12: k=4096, ~1.5% error
-    ///   - lgK 16: k=65536, ~0.4% error
+    /// * `precision` - Number of bits for the hash (4-18, typical 12-14)
+    ///   - precision 4: 16 registers, 16 bytes
+    ///   - precision 8: 256 registers, 256 bytes
+    ///   - precision 12: 4096 registers, 4 KB (recommended)
+    ///   - precision 16: 65536 registers, 64 KB
+    ///   - precision 18: 262144 registers, 256 KB
     ///
     /// # Returns
-    /// A new CpcSketch instance
+    /// A new UltraLogLog instance
     ///
     /// # Throws
-    /// - If lgK is out of valid range (4-26)
+    /// - If precision is out of valid range (4-18)
     ///
     /// # Example
     /// ```javascript
-    /// const cpc = new CpcSketch(11);
+    /// const ull = new UltraLogLog(12);
     /// ```
     #[napi(constructor)]
-    pub fn new(lg_k: u8) -> Result<Self> {
-        RustCpcSketch::new(lg_k)
-            .map(|inner| CpcSketch { inner })
+    pub fn new(precision: u8) -> Result<Self> {
+        RustUltraLogLog::new(precision)
+            .map(|inner| UltraLogLog { inner })
             .map_err(|e| {
                 Error::new(
                     Status::InvalidArg,
-                    format!("CpcSketch creation failed: {}", e),
+                    format!("UltraLogLog creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Create the most precise UltraLogLog whose registers fit in a byte budget
+    ///
+    /// # Arguments
+    /// * `bytes` - Memory available for the registers (at least 16)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const ull = UltraLogLog.forByteBudget(8192); // precision 13, ~1.15% error
+    /// ```
+    #[napi(factory)]
+    pub fn forByteBudget(bytes: i64) -> Result<Self> {
+        if bytes < 0 {
+            return Err(Error::new(Status::InvalidArg, "bytes must be >= 0"));
+        }
+        RustUltraLogLog::for_byte_budget(bytes as usize)
+            .map(|inner| UltraLogLog { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("UltraLogLog creation failed: {}", e),
                 )
             })
     }
@@ -483,22 +858,53 @@ impl CpcSketch {
     ///
     /// # Example
     /// ```javascript
-    /// cpc.update(Buffer.from('item1'));
-    /// cpc.update(Buffer.from('hello world'));
+    /// ull.update(Buffer.from('item1'));
+    /// ull.update(Buffer.from('hello world'));
     /// ```
     #[napi]
     pub fn update(&mut self, item: Buffer) -> Result<()> {
         let data: Vec<u8> = item.to_vec();
-        // Hash the data to u64 for the CPC sketch
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        data.hash(&mut hasher);
-        let hash = hasher.finish();
-        Sketch::update(&mut self.inner, &hash);
+        self.inner.add(&data);
+        Ok(())
+    }
+
+    /// Add an unsigned 64-bit integer without converting it to a Buffer
+    ///
+    /// Hashes the value's fixed 8-byte little-endian encoding, so
+    /// `updateU64(5n)` has the same effect as `update` with those bytes and
+    /// as `update_u64` in the Rust crate.
+    ///
+    /// # Throws
+    /// - If the value is negative or exceeds 64 bits
+    ///
+    /// # Example
+    /// ```javascript
+    /// ull.updateU64(12345n);
+    /// ```
+    #[napi]
+    pub fn updateU64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_u64(bigint_to_u64(value)?);
+        Ok(())
+    }
+
+    /// Add a signed 64-bit integer (8-byte little-endian two's complement)
+    ///
+    /// # Throws
+    /// - If the value doesn't fit in 64 bits
+    #[napi]
+    pub fn updateI64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_i64(bigint_to_i64(value)?);
         Ok(())
     }
 
+    /// Add a number by its 8-byte little-endian IEEE 754 encoding
+    ///
+    /// `0` and `-0` are hashed as distinct values.
+    #[napi]
+    pub fn updateF64(&mut self, value: f64) {
+        self.inner.update_f64(value);
+    }
+
     /// Get current cardinality estimate
     ///
     /// # Returns
@@ -506,113 +912,99 @@ impl CpcSketch {
     ///
     /// # Example
     /// ```javascript
-    /// const estimate = cpc.estimate();
+    /// const estimate = ull.estimate();
     /// console.log(estimate); // e.g., 1000000
     /// ```
     #[napi]
     pub fn estimate(&self) -> Result<f64> {
-        Ok(Sketch::estimate(&self.inner))
+        Ok(self.inner.estimate())
     }
 
-    /// Get the lg_k parameter
+    /// Get the cardinality estimate (alias for estimate)
     ///
     /// # Returns
-    /// The lg_k value used to create this sketch
+    /// Estimated number of unique items
     ///
     /// # Example
     /// ```javascript
-    /// const cpc = new CpcSketch(11);
-    /// console.log(cpc.lgK()); // 11
+    /// const cardinality = ull.cardinality();
     /// ```
     #[napi]
-    pub fn lgK(&self) -> Result<u8> {
-        Ok(self.inner.lg_k())
+    pub fn cardinality(&self) -> Result<f64> {
+        Ok(self.inner.cardinality())
     }
 
-    /// Get the current operational flavor/mode
+    /// Merge another UltraLogLog sketch into this one
     ///
-    /// CPC uses different representations as cardinality grows:
-    /// - Empty: No items observed
-    /// - Sparse: Few items, space-efficient
-    /// - Hybrid: Transitioning
-    /// - Pinned: Dense uncompressed
-    /// - Sliding: Dense compressed (maximum efficiency)
+    /// # Arguments
+    /// * `other` - Another UltraLogLog with same precision
     ///
-    /// # Returns
-    /// String describing the current mode
+    /// # Throws
+    /// - If precisions don't match
     ///
     /// # Example
     /// ```javascript
-    /// console.log(cpc.flavor()); // "Sparse"
+    /// const ull1 = new UltraLogLog(12);
+    /// const ull2 = new UltraLogLog(12);
+    /// ull1.update(Buffer.from('a'));
+    /// ull2.update(Buffer.from('b'));
+    /// ull1.merge(ull2);
+    /// console.log(ull1.estimate()); // ~2
     /// ```
     #[napi]
-    pub fn flavor(&self) -> Result<String> {
-        Ok(self.inner.flavor().to_string())
+    pub fn merge(&mut self, other: &UltraLogLog) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
-    /// Clear the sketch to empty state
+    /// Estimate the cardinality of the union without modifying either sketch
+    ///
+    /// # Arguments
+    /// * `other` - Another UltraLogLog with same precision
+    ///
+    /// # Throws
+    /// - If the sketches are incompatible
     ///
     /// # Example
     /// ```javascript
-    /// cpc.clear();
-    /// console.log(cpc.isEmpty()); // true
+    /// const total = ull1.estimateUnion(ull2);
     /// ```
     #[napi]
-    pub fn clear(&mut self) -> Result<()> {
-        self.inner.clear();
-        Ok(())
+    pub fn estimateUnion(&self, other: &UltraLogLog) -> Result<f64> {
+        self.inner
+            .estimate_union(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
     }
 
-    /// Check if the sketch is empty
+    /// Get a JSON summary of parameters and state for debugging
     ///
-    /// # Returns
-    /// True if no items have been added
+    /// Not meant for reconstruction; use `serialize()` for that.
     ///
     /// # Example
     /// ```javascript
-    /// const cpc = new CpcSketch(11);
-    /// console.log(cpc.isEmpty()); // true
+    /// console.log(JSON.parse(ull.toJson()).estimate);
     /// ```
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(Sketch::is_empty(&self.inner))
+    pub fn toJson(&self) -> String {
+        self.inner.to_json()
     }
 
-    /// Get the approximate size in bytes
+    /// Check if the sketch is empty (no items added)
     ///
     /// # Returns
-    /// Approximate memory usage in bytes
-    ///
-    /// # Example
-    /// ```javascript
-    /// console.log(cpc.sizeBytes()); // e.g., 1024
-    /// ```
-    #[napi]
-    pub fn sizeBytes(&self) -> Result<u32> {
-        Ok(self.inner.size_bytes() as u32)
-    }
-
-    /// Merge another CpcSketch into this one
-    ///
-    /// # Arguments
-    /// * `other` - Another CpcSketch with same lg_k
-    ///
-    /// # Throws
-    /// - If lg_k values don't match
+    /// True if no items have been added
     ///
     /// # Example
     /// ```javascript
-    /// const cpc1 = new CpcSketch(11);
-    /// const cpc2 = new CpcSketch(11);
-    /// cpc1.update(Buffer.from('a'));
-    /// cpc2.update(Buffer.from('b'));
-    /// cpc1.merge(cpc2);
-    /// console.log(cpc1.estimate()); // ~2
+    /// const ull = new UltraLogLog(12);
+    /// console.log(ull.isEmpty()); // true
+    /// ull.update(Buffer.from('item'));
+    /// console.log(ull.isEmpty()); // false
     /// ```
     #[napi]
-    pub fn merge(&mut self, other: &CpcSketch) -> Result<()> {
-        Mergeable::merge(&mut self.inner, &other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
     }
 
     /// Serialize the sketch to binary format
@@ -622,12 +1014,12 @@ impl CpcSketch {
     ///
     /// # Example
     /// ```javascript
-    /// const data = cpc.serialize();
-    /// fs.writeFileSync('cpc.bin', data);
+    /// const data = ull.serialize();
+    /// fs.writeFileSync('ull.bin', data);
     /// ```
     #[napi]
     pub fn serialize(&self) -> Result<Buffer> {
-        let bytes = Sketch::serialize(&self.inner);
+        let bytes = self.inner.serialize();
         Ok(Buffer::from(bytes))
     }
 
@@ -637,20 +1029,20 @@ impl CpcSketch {
     /// * `data` - Binary data from serialize()
     ///
     /// # Returns
-    /// A new CpcSketch instance
+    /// A new UltraLogLog instance
     ///
     /// # Throws
     /// - If data is invalid or corrupted
     ///
     /// # Example
     /// ```javascript
-    /// const data = fs.readFileSync('cpc.bin');
-    /// const cpc = CpcSketch.deserialize(data);
+    /// const data = fs.readFileSync('ull.bin');
+    /// const ull = UltraLogLog.deserialize(data);
     /// ```
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustCpcSketch::from_bytes(&data)
-            .map(|inner| CpcSketch { inner })
+        RustUltraLogLog::deserialize(&data)
+            .map(|inner| UltraLogLog { inner })
             .map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
@@ -663,273 +1055,236 @@ impl CpcSketch {
     ///
     /// # Example
     /// ```javascript
-    /// console.log(cpc.toString());
-    /// // "CpcSketch(lgK=11, flavor=Sparse, estimate=1000)"
+    /// console.log(ull.toString());
+    /// // "UltraLogLog(estimate=1000000)"
     /// ```
     #[napi]
     pub fn toString(&self) -> Result<String> {
         Ok(format!(
-            "CpcSketch(lgK={}, flavor={}, estimate={:.0})",
-            self.inner.lg_k(),
-            self.inner.flavor(),
-            Sketch::estimate(&self.inner)
+            "UltraLogLog(estimate={:.0})",
+            self.inner.estimate()
         ))
     }
 }
 
 // =============================================================================
-// QSketch - Weighted cardinality estimation
+// CpcSketch - Most space-efficient cardinality estimator
 // =============================================================================
 
-/// QSketch for weighted cardinality estimation
+/// CPC (Compressed Probabilistic Counting) Sketch
 ///
-/// QSketch maintains a probabilistic sample of weighted elements to estimate
-/// the cardinality of weighted sets with bounded error. Unlike standard
-/// cardinality sketches that treat all items equally, QSketch accounts for
-/// item weights in the cardinality estimate.
+/// CPC is 30-40% more space-efficient than HyperLogLog for the same accuracy.
+/// It achieves this through adaptive compression and multiple operational modes.
 ///
 /// # Example
 /// ```javascript
-/// const { QSketch } = require('@sketch-oxide/node');
-/// const qs = new QSketch(256);
-/// qs.update(Buffer.from('user1'), 100.0);
-/// qs.update(Buffer.from('user2'), 250.0);
-/// const [estimate, error] = qs.estimateWeightedCardinality();
-/// console.log(`Weighted cardinality: ${estimate} +/- ${error}`);
+/// const { CpcSketch } = require('@sketch-oxide/node');
+/// const cpc = new CpcSketch(11);
+/// cpc.update(Buffer.from('item1'));
+/// cpc.update(Buffer.from('item2'));
+/// console.log(cpc.estimate()); // ~2
 /// ```
 #[napi]
-pub struct QSketch {
-    inner: RustQSketch,
+pub struct CpcSketch {
+    inner: RustCpcSketch,
 }
 
 #[napi]
-impl QSketch {
-    /// Create a new QSketch with given maximum samples
+impl CpcSketch {
+    /// Create a new CPC sketch with given lg_k parameter
     ///
     /// # Arguments
-    /// * `maxSamples` - Maximum number of samples to maintain (minimum 32)
+    /// * `lgK` - Log2 of k parameter (4-26), higher = more accurate but more memory
+    ///   - lgK 4: k=16, very high error (~20%)
+    ///   - lgK 8: k=256, ~6% error
+    ///   - lgK 11: k=2048, ~2% error (recommended default)
+    ///   - lgK 12: k=4096, ~1.5% error
+    ///   - lgK 16: k=65536, ~0.4% error
     ///
     /// # Returns
-    /// A new QSketch instance
+    /// A new CpcSketch instance
     ///
     /// # Throws
-    /// - If maxSamples is less than 32
+    /// - If lgK is out of valid range (4-26)
     ///
     /// # Example
     /// ```javascript
-    /// const qs = new QSketch(256);
+    /// const cpc = new CpcSketch(11);
     /// ```
     #[napi(constructor)]
-    pub fn new(max_samples: u32) -> Result<Self> {
-        if max_samples < 32 {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "maxSamples must be at least 32".to_string(),
-            ));
-        }
-        Ok(QSketch {
-            inner: RustQSketch::new(max_samples as usize),
-        })
-    }
-
-    /// Create a new QSketch with a specific seed for reproducibility
-    ///
-    /// # Arguments
-    /// * `maxSamples` - Maximum number of samples to maintain (minimum 32)
-    /// * `seed` - Random seed for reproducibility
-    ///
-    /// # Returns
-    /// A new QSketch instance
-    ///
-    /// # Example
-    /// ```javascript
-    /// const qs = QSketch.withSeed(256, 42n);
-    /// ```
-    #[napi(factory)]
-    pub fn withSeed(max_samples: u32, seed: BigInt) -> Result<Self> {
-        if max_samples < 32 {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "maxSamples must be at least 32".to_string(),
-            ));
-        }
-        let (_, seed_val, _) = seed.get_u64();
-        Ok(QSketch {
-            inner: RustQSketch::with_seed(max_samples as usize, seed_val),
-        })
+    pub fn new(lg_k: u8) -> Result<Self> {
+        RustCpcSketch::new(lg_k)
+            .map(|inner| CpcSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("CpcSketch creation failed: {}", e),
+                )
+            })
     }
 
-    /// Add a weighted item to the sketch
+    /// Add an item to the sketch
     ///
     /// # Arguments
     /// * `item` - Binary data to add
-    /// * `weight` - Weight of the item (must be positive)
-    ///
-    /// # Throws
-    /// - If weight is not positive or not finite
     ///
     /// # Example
     /// ```javascript
-    /// qs.update(Buffer.from('user_123'), 100.0);
-    /// qs.update(Buffer.from('user_456'), 250.0);
+    /// cpc.update(Buffer.from('item1'));
+    /// cpc.update(Buffer.from('hello world'));
     /// ```
     #[napi]
-    pub fn update(&mut self, item: Buffer, weight: f64) -> Result<()> {
-        if weight <= 0.0 || !weight.is_finite() {
-            return Err(Error::new(
-                Status::InvalidArg,
-                format!("Weight must be positive and finite, got {}", weight),
-            ));
-        }
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(&data, weight);
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        self.inner.update_bytes(&item);
         Ok(())
     }
 
-    /// Get the maximum number of samples this sketch can maintain
+    /// Get current cardinality estimate
     ///
     /// # Returns
-    /// Maximum sample count
+    /// Estimated number of unique items
     ///
     /// # Example
     /// ```javascript
-    /// const qs = new QSketch(256);
-    /// console.log(qs.maxSamples()); // 256
+    /// const estimate = cpc.estimate();
+    /// console.log(estimate); // e.g., 1000000
     /// ```
     #[napi]
-    pub fn maxSamples(&self) -> Result<u32> {
-        Ok(self.inner.max_samples() as u32)
+    pub fn estimate(&self) -> Result<f64> {
+        Ok(Sketch::estimate(&self.inner))
     }
 
-    /// Get the current number of samples in the sketch
+    /// Get the lg_k parameter
     ///
     /// # Returns
-    /// Current sample count
+    /// The lg_k value used to create this sketch
     ///
     /// # Example
     /// ```javascript
-    /// console.log(qs.sampleCount()); // e.g., 150
+    /// const cpc = new CpcSketch(11);
+    /// console.log(cpc.lgK()); // 11
     /// ```
     #[napi]
-    pub fn sampleCount(&self) -> Result<u32> {
-        Ok(self.inner.sample_count() as u32)
+    pub fn lgK(&self) -> Result<u8> {
+        Ok(self.inner.lg_k())
     }
 
-    /// Get the sum of all weights added to the sketch
+    /// Get the current operational flavor/mode
+    ///
+    /// CPC uses different representations as cardinality grows:
+    /// - Empty: No items observed
+    /// - Sparse: Few items, space-efficient
+    /// - Hybrid: Transitioning
+    /// - Pinned: Dense uncompressed
+    /// - Sliding: Dense compressed (maximum efficiency)
     ///
     /// # Returns
-    /// Total weight of all items
+    /// String describing the current mode
     ///
     /// # Example
     /// ```javascript
-    /// qs.update(Buffer.from('item1'), 10.5);
-    /// qs.update(Buffer.from('item2'), 20.5);
-    /// console.log(qs.totalWeight()); // 31.0
+    /// console.log(cpc.flavor()); // "Sparse"
     /// ```
     #[napi]
-    pub fn totalWeight(&self) -> Result<f64> {
-        Ok(self.inner.total_weight())
+    pub fn flavor(&self) -> Result<String> {
+        Ok(self.inner.flavor().to_string())
     }
 
-    /// Get the approximate number of distinct elements
-    ///
-    /// # Returns
-    /// Estimated distinct element count
+    /// Clear the sketch to empty state
     ///
     /// # Example
     /// ```javascript
-    /// const distinct = qs.estimateDistinctElements();
-    /// console.log(distinct); // e.g., 1000
+    /// cpc.clear();
+    /// console.log(cpc.isEmpty()); // true
     /// ```
     #[napi]
-    pub fn estimateDistinctElements(&self) -> Result<i64> {
-        Ok(self.inner.estimate_distinct_elements() as i64)
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
+        Ok(())
     }
 
-    /// Estimate the weighted cardinality with confidence bounds
-    ///
-    /// Returns an object with estimate and errorBound, where errorBound
-    /// represents the radius of a 95% confidence interval.
+    /// Check if the sketch is empty
     ///
     /// # Returns
-    /// Object with { estimate: number, errorBound: number }
+    /// True if no items have been added
     ///
     /// # Example
     /// ```javascript
-    /// const result = qs.estimateWeightedCardinality();
-    /// console.log(`Estimate: ${result.estimate} +/- ${result.errorBound}`);
+    /// const cpc = new CpcSketch(11);
+    /// console.log(cpc.isEmpty()); // true
     /// ```
     #[napi]
-    pub fn estimateWeightedCardinality(&self) -> Result<WeightedCardinalityResult> {
-        let (estimate, error_bound) = self.inner.estimate_weighted_cardinality();
-        Ok(WeightedCardinalityResult {
-            estimate,
-            error_bound,
-        })
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(Sketch::is_empty(&self.inner))
     }
 
-    /// Get the basic cardinality estimate (without error bounds)
+    /// Get the approximate size in bytes
     ///
     /// # Returns
-    /// Estimated cardinality
+    /// Approximate memory usage in bytes
     ///
     /// # Example
     /// ```javascript
-    /// const estimate = qs.estimate();
+    /// console.log(cpc.sizeBytes()); // e.g., 1024
     /// ```
     #[napi]
-    pub fn estimate(&self) -> Result<f64> {
-        Ok(Sketch::estimate(&self.inner))
+    pub fn sizeBytes(&self) -> Result<u32> {
+        Ok(self.inner.size_bytes() as u32)
     }
 
-    /// Check if the sketch is empty
+    /// Merge another CpcSketch into this one
     ///
-    /// # Returns
-    /// True if no items have been added
+    /// # Arguments
+    /// * `other` - Another CpcSketch with same lg_k
+    ///
+    /// # Throws
+    /// - If lg_k values don't match
     ///
     /// # Example
     /// ```javascript
-    /// const qs = new QSketch(256);
-    /// console.log(qs.isEmpty()); // true
+    /// const cpc1 = new CpcSketch(11);
+    /// const cpc2 = new CpcSketch(11);
+    /// cpc1.update(Buffer.from('a'));
+    /// cpc2.update(Buffer.from('b'));
+    /// cpc1.merge(cpc2);
+    /// console.log(cpc1.estimate()); // ~2
     /// ```
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(Sketch::is_empty(&self.inner))
+    pub fn merge(&mut self, other: &CpcSketch) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
-    /// Reset the sketch to empty state
+    /// Estimate the cardinality of the union without modifying either sketch
+    ///
+    /// # Arguments
+    /// * `other` - Another CpcSketch with same lgK
+    ///
+    /// # Throws
+    /// - If the sketches are incompatible
     ///
     /// # Example
     /// ```javascript
-    /// qs.reset();
-    /// console.log(qs.isEmpty()); // true
+    /// const total = cpc1.estimateUnion(cpc2);
     /// ```
     #[napi]
-    pub fn reset(&mut self) -> Result<()> {
-        self.inner.reset();
-        Ok(())
+    pub fn estimateUnion(&self, other: &CpcSketch) -> Result<f64> {
+        self.inner
+            .estimate_union(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
     }
 
-    /// Merge another QSketch into this one
-    ///
-    /// # Arguments
-    /// * `other` - Another QSketch with same maxSamples
+    /// Get a JSON summary of parameters and state for debugging
     ///
-    /// # Throws
-    /// - If maxSamples values don't match
+    /// Not meant for reconstruction; use `serialize()` for that.
     ///
     /// # Example
     /// ```javascript
-    /// const qs1 = new QSketch(256);
-    /// const qs2 = new QSketch(256);
-    /// qs1.update(Buffer.from('a'), 100.0);
-    /// qs2.update(Buffer.from('b'), 200.0);
-    /// qs1.merge(qs2);
+    /// console.log(JSON.parse(cpc.toJson()).estimate);
     /// ```
     #[napi]
-    pub fn merge(&mut self, other: &QSketch) -> Result<()> {
-        Mergeable::merge(&mut self.inner, &other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    pub fn toJson(&self) -> String {
+        self.inner.to_json()
     }
 
     /// Serialize the sketch to binary format
@@ -939,8 +1294,8 @@ impl QSketch {
     ///
     /// # Example
     /// ```javascript
-    /// const data = qs.serialize();
-    /// fs.writeFileSync('qs.bin', data);
+    /// const data = cpc.serialize();
+    /// fs.writeFileSync('cpc.bin', data);
     /// ```
     #[napi]
     pub fn serialize(&self) -> Result<Buffer> {
@@ -954,20 +1309,20 @@ impl QSketch {
     /// * `data` - Binary data from serialize()
     ///
     /// # Returns
-    /// A new QSketch instance
+    /// A new CpcSketch instance
     ///
     /// # Throws
     /// - If data is invalid or corrupted
     ///
     /// # Example
     /// ```javascript
-    /// const data = fs.readFileSync('qs.bin');
-    /// const qs = QSketch.deserialize(data);
+    /// const data = fs.readFileSync('cpc.bin');
+    /// const cpc = CpcSketch.deserialize(data);
     /// ```
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustQSketch::from_bytes(&data)
-            .map(|inner| QSketch { inner })
+        RustCpcSketch::from_bytes(&data)
+            .map(|inner| CpcSketch { inner })
             .map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
@@ -980,476 +1335,455 @@ impl QSketch {
     ///
     /// # Example
     /// ```javascript
-    /// console.log(qs.toString());
-    /// // "QSketch(maxSamples=256, samples=150, totalWeight=1500.0)"
+    /// console.log(cpc.toString());
+    /// // "CpcSketch(lgK=11, flavor=Sparse, estimate=1000)"
     /// ```
     #[napi]
     pub fn toString(&self) -> Result<String> {
         Ok(format!(
-            "QSketch(maxSamples={}, samples={}, totalWeight={:.1})",
-            self.inner.max_samples(),
-            self.inner.sample_count(),
-            self.inner.total_weight()
+            "CpcSketch(lgK={}, flavor={}, estimate={:.0})",
+            self.inner.lg_k(),
+            self.inner.flavor(),
+            Sketch::estimate(&self.inner)
         ))
     }
 }
 
-/// Result of weighted cardinality estimation
-#[napi(object)]
-pub struct WeightedCardinalityResult {
-    /// The estimated weighted cardinality
-    pub estimate: f64,
-    /// The error bound (95% confidence interval radius)
-    pub error_bound: f64,
-}
-
 // =============================================================================
-// ThetaSketch - Set operations (union, intersection, difference)
+// DistinctCounter - Exact below a threshold, CPC above it
 // =============================================================================
 
-/// Theta Sketch for cardinality estimation with set operations
+/// Distinct counter that picks its own representation
 ///
-/// ThetaSketch is the only sketch that supports intersection and difference
-/// operations, making it ideal for computing Jaccard similarity, overlap
-/// analysis, and set-based queries.
+/// Counts exactly up to `threshold` distinct items, then switches to a
+/// CpcSketch. Trades a little CPU for not having to choose a sketch.
 ///
 /// # Example
 /// ```javascript
-/// const { ThetaSketch } = require('@sketch-oxide/node');
-/// const sketchA = new ThetaSketch(12);
-/// const sketchB = new ThetaSketch(12);
-///
-/// // Add items
-/// for (let i = 0; i < 100; i++) sketchA.update(Buffer.from(`item${i}`));
-/// for (let i = 50; i < 150; i++) sketchB.update(Buffer.from(`item${i}`));
-///
-/// // Set operations
-/// const union = sketchA.union(sketchB);
-/// const intersection = sketchA.intersect(sketchB);
-/// const difference = sketchA.difference(sketchB);
-///
-/// console.log(`Union: ${union.estimate()}`);        // ~150
-/// console.log(`Intersection: ${intersection.estimate()}`); // ~50
-/// console.log(`Difference: ${difference.estimate()}`);     // ~50
+/// const { DistinctCounter } = require('@sketch-oxide/node');
+/// const counter = new DistinctCounter();
+/// counter.add(Buffer.from('alice'));
+/// counter.add(Buffer.from('alice'));
+/// console.log(counter.estimate()); // 1
+/// console.log(counter.isExact()); // true
 /// ```
 #[napi]
-pub struct ThetaSketch {
-    inner: RustThetaSketch,
+pub struct DistinctCounter {
+    inner: RustDistinctCounter,
 }
 
 #[napi]
-impl ThetaSketch {
-    /// Create a new Theta Sketch with specified lg_k
+impl DistinctCounter {
+    /// Create a new distinct counter
     ///
     /// # Arguments
-    /// * `lgK` - log2(k), determines accuracy and memory (4-26)
-    ///   - k = 2^lgK (nominal entries)
-    ///   - Memory: ~8k bytes
-    ///   - Error: ~1/sqrt(k)
-    ///   - lgK=12 (k=4096): ~1.6% error, 32KB (recommended)
-    ///   - lgK=14 (k=16384): ~0.8% error, 128KB
-    ///   - lgK=16 (k=65536): ~0.4% error, 512KB
-    ///
-    /// # Returns
-    /// A new ThetaSketch instance
+    /// * `threshold` - Largest distinct count kept exactly (default: 1024, 0 = sketch only)
+    /// * `lgK` - CPC lg_k used after promotion, 4-26 (default: 11)
     ///
     /// # Throws
     /// - If lgK is out of valid range (4-26)
     ///
     /// # Example
     /// ```javascript
-    /// const theta = new ThetaSketch(12);
+    /// const counter = new DistinctCounter(10000, 12);
     /// ```
     #[napi(constructor)]
-    pub fn new(lg_k: u8) -> Result<Self> {
-        RustThetaSketch::new(lg_k)
-            .map(|inner| ThetaSketch { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ThetaSketch creation failed: {}", e),
-                )
-            })
-    }
-
-    /// Create a Theta Sketch with custom seed
-    ///
-    /// Use the same seed for all sketches that will be combined in set operations.
-    ///
-    /// # Arguments
-    /// * `lgK` - log2(k) parameter (4-26)
-    /// * `seed` - Custom seed for hash function
-    ///
-    /// # Returns
-    /// A new ThetaSketch instance
-    ///
-    /// # Example
-    /// ```javascript
-    /// const theta = ThetaSketch.withSeed(12, 12345n);
-    /// ```
-    #[napi(factory)]
-    pub fn withSeed(lg_k: u8, seed: BigInt) -> Result<Self> {
-        let (_, seed_val, _) = seed.get_u64();
-        RustThetaSketch::with_seed(lg_k, seed_val)
-            .map(|inner| ThetaSketch { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ThetaSketch creation failed: {}", e),
-                )
-            })
+    pub fn new(threshold: Option<u32>, lg_k: Option<u8>) -> Result<Self> {
+        RustDistinctCounter::with_threshold(
+            threshold.map_or(1024, |t| t as usize),
+            lg_k.unwrap_or(11),
+        )
+        .map(|inner| DistinctCounter { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("DistinctCounter creation failed: {}", e),
+            )
+        })
     }
 
-    /// Add an item to the sketch
-    ///
-    /// # Arguments
-    /// * `item` - Binary data to add
+    /// Add an item to the counter
     ///
     /// # Example
     /// ```javascript
-    /// theta.update(Buffer.from('item1'));
-    /// theta.update(Buffer.from('hello world'));
+    /// counter.add(Buffer.from('user-42'));
     /// ```
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(&data);
+    pub fn add(&mut self, item: Buffer) -> Result<()> {
+        self.inner.add(&item);
         Ok(())
     }
 
-    /// Get current cardinality estimate
-    ///
-    /// # Returns
-    /// Estimated number of unique items
-    ///
-    /// # Example
-    /// ```javascript
-    /// const estimate = theta.estimate();
-    /// console.log(estimate); // e.g., 1000
-    /// ```
+    /// Get the number of distinct items, exact while `isExact()` is true
     #[napi]
     pub fn estimate(&self) -> Result<f64> {
         Ok(self.inner.estimate())
     }
 
-    /// Check if the sketch is empty
+    /// Check whether the count is still exact (not yet promoted to CPC)
+    #[napi]
+    pub fn isExact(&self) -> Result<bool> {
+        Ok(self.inner.is_exact())
+    }
+
+    /// Merge another DistinctCounter into this one
     ///
-    /// # Returns
-    /// True if no items have been added
+    /// # Throws
+    /// - If the counters use different lgK values
     ///
     /// # Example
     /// ```javascript
-    /// const theta = new ThetaSketch(12);
-    /// console.log(theta.isEmpty()); // true
+    /// a.merge(b);
     /// ```
     #[napi]
+    pub fn merge(&mut self, other: &DistinctCounter) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    /// Get the largest distinct count kept exactly
+    #[napi]
+    pub fn threshold(&self) -> Result<u32> {
+        Ok(self.inner.threshold() as u32)
+    }
+
+    /// Get the lg_k of the CPC sketch used after promotion
+    #[napi]
+    pub fn lgK(&self) -> Result<u8> {
+        Ok(self.inner.lg_k())
+    }
+
+    /// Check if no items have been added
+    #[napi]
     pub fn isEmpty(&self) -> Result<bool> {
         Ok(self.inner.is_empty())
     }
 
-    /// Get the number of retained hash entries
-    ///
-    /// # Returns
-    /// Number of hash entries currently stored
+    /// Get string representation
     ///
     /// # Example
     /// ```javascript
-    /// console.log(theta.numRetained()); // e.g., 1000
+    /// console.log(counter.toString());
+    /// // "DistinctCounter(threshold=1024, lgK=11, exact=true, estimate=2)"
     /// ```
     #[napi]
-    pub fn numRetained(&self) -> Result<u32> {
-        Ok(self.inner.num_retained() as u32)
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "DistinctCounter(threshold={}, lgK={}, exact={}, estimate={:.0})",
+            self.inner.threshold(),
+            self.inner.lg_k(),
+            self.inner.is_exact(),
+            self.inner.estimate()
+        ))
     }
+}
 
-    /// Get the current theta value
+// =============================================================================
+// QSketch - Weighted cardinality estimation
+// =============================================================================
+
+/// QSketch for weighted cardinality estimation
+///
+/// QSketch maintains a probabilistic sample of weighted elements to estimate
+/// the cardinality of weighted sets with bounded error. Unlike standard
+/// cardinality sketches that treat all items equally, QSketch accounts for
+/// item weights in the cardinality estimate.
+///
+/// # Example
+/// ```javascript
+/// const { QSketch } = require('@sketch-oxide/node');
+/// const qs = new QSketch(256);
+/// qs.update(Buffer.from('user1'), 100.0);
+/// qs.update(Buffer.from('user2'), 250.0);
+/// const [estimate, error] = qs.estimateWeightedCardinality();
+/// console.log(`Weighted cardinality: ${estimate} +/- ${error}`);
+/// ```
+#[napi]
+pub struct QSketch {
+    inner: RustQSketch,
+}
+
+#[napi]
+impl QSketch {
+    /// Create a new QSketch with given maximum samples
     ///
-    /// - u64::MAX means no sampling (exact mode)
-    /// - Values < u64::MAX indicate sampling is active
+    /// # Arguments
+    /// * `maxSamples` - Maximum number of samples to maintain (minimum 32)
     ///
     /// # Returns
-    /// Current theta threshold as BigInt
+    /// A new QSketch instance
+    ///
+    /// # Throws
+    /// - If maxSamples is less than 32
     ///
     /// # Example
     /// ```javascript
-    /// const theta = sketch.getTheta();
+    /// const qs = new QSketch(256);
     /// ```
-    #[napi]
-    pub fn getTheta(&self) -> Result<BigInt> {
-        Ok(BigInt::from(self.inner.get_theta()))
+    #[napi(constructor)]
+    pub fn new(max_samples: u32) -> Result<Self> {
+        if max_samples < 32 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "maxSamples must be at least 32".to_string(),
+            ));
+        }
+        Ok(QSketch {
+            inner: RustQSketch::new(max_samples as usize),
+        })
     }
 
-    /// Get the nominal capacity (k = 2^lgK)
+    /// Create a new QSketch with a specific seed for reproducibility
+    ///
+    /// # Arguments
+    /// * `maxSamples` - Maximum number of samples to maintain (minimum 32)
+    /// * `seed` - Random seed for reproducibility
     ///
     /// # Returns
-    /// Nominal capacity
+    /// A new QSketch instance
     ///
     /// # Example
     /// ```javascript
-    /// const theta = new ThetaSketch(12);
-    /// console.log(theta.capacity()); // 4096
+    /// const qs = QSketch.withSeed(256, 42n);
     /// ```
-    #[napi]
-    pub fn capacity(&self) -> Result<u32> {
-        Ok(self.inner.capacity() as u32)
+    #[napi(factory)]
+    pub fn withSeed(max_samples: u32, seed: BigInt) -> Result<Self> {
+        if max_samples < 32 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "maxSamples must be at least 32".to_string(),
+            ));
+        }
+        let (_, seed_val, _) = seed.get_u64();
+        Ok(QSketch {
+            inner: RustQSketch::with_seed(max_samples as usize, seed_val),
+        })
     }
 
-    /// Compute union with another sketch: |A ∪ B|
-    ///
-    /// Returns a new sketch representing items in either A or B (or both).
-    /// Both sketches must have the same lgK and seed.
+    /// Add a weighted item to the sketch
     ///
     /// # Arguments
-    /// * `other` - Another ThetaSketch
-    ///
-    /// # Returns
-    /// New ThetaSketch with union result
+    /// * `item` - Binary data to add
+    /// * `weight` - Weight of the item (must be positive)
     ///
     /// # Throws
-    /// - If sketches have different lgK or seed
+    /// - If weight is not positive or not finite
     ///
     /// # Example
     /// ```javascript
-    /// const union = sketchA.union(sketchB);
-    /// console.log(union.estimate()); // |A ∪ B|
+    /// qs.update(Buffer.from('user_123'), 100.0);
+    /// qs.update(Buffer.from('user_456'), 250.0);
     /// ```
     #[napi]
-    pub fn union(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
-        self.inner
-            .union(&other.inner)
-            .map(|inner| ThetaSketch { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    pub fn update(&mut self, item: Buffer, weight: f64) -> Result<()> {
+        if weight <= 0.0 || !weight.is_finite() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Weight must be positive and finite, got {}", weight),
+            ));
+        }
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&data, weight);
+        Ok(())
     }
 
-    /// Compute intersection with another sketch: |A ∩ B|
+    /// Get the maximum number of samples this sketch can maintain
     ///
-    /// Returns a new sketch representing items in both A and B.
-    /// Both sketches must have the same lgK and seed.
+    /// # Returns
+    /// Maximum sample count
     ///
-    /// # Arguments
-    /// * `other` - Another ThetaSketch
+    /// # Example
+    /// ```javascript
+    /// const qs = new QSketch(256);
+    /// console.log(qs.maxSamples()); // 256
+    /// ```
+    #[napi]
+    pub fn maxSamples(&self) -> Result<u32> {
+        Ok(self.inner.max_samples() as u32)
+    }
+
+    /// Get the current number of samples in the sketch
     ///
     /// # Returns
-    /// New ThetaSketch with intersection result
-    ///
-    /// # Throws
-    /// - If sketches have different lgK or seed
+    /// Current sample count
     ///
     /// # Example
     /// ```javascript
-    /// const intersection = sketchA.intersect(sketchB);
-    /// console.log(intersection.estimate()); // |A ∩ B|
+    /// console.log(qs.sampleCount()); // e.g., 150
     /// ```
     #[napi]
-    pub fn intersect(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
-        self.inner
-            .intersect(&other.inner)
-            .map(|inner| ThetaSketch { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))
+    pub fn sampleCount(&self) -> Result<u32> {
+        Ok(self.inner.sample_count() as u32)
     }
 
-    /// Compute difference: |A - B| (items in A but not in B)
+    /// Get the sum of all weights added to the sketch
     ///
-    /// Returns a new sketch representing items in A that are not in B.
-    /// Both sketches must have the same lgK and seed.
-    /// Note: A - B != B - A (not commutative)
+    /// # Returns
+    /// Total weight of all items
     ///
-    /// # Arguments
-    /// * `other` - Another ThetaSketch
+    /// # Example
+    /// ```javascript
+    /// qs.update(Buffer.from('item1'), 10.5);
+    /// qs.update(Buffer.from('item2'), 20.5);
+    /// console.log(qs.totalWeight()); // 31.0
+    /// ```
+    #[napi]
+    pub fn totalWeight(&self) -> Result<f64> {
+        Ok(self.inner.total_weight())
+    }
+
+    /// Get the approximate number of distinct elements
     ///
     /// # Returns
-    /// New ThetaSketch with difference result
-    ///
-    /// # Throws
-    /// - If sketches have different lgK or seed
+    /// Estimated distinct element count
     ///
     /// # Example
     /// ```javascript
-    /// const difference = sketchA.difference(sketchB);
-    /// console.log(difference.estimate()); // |A - B|
+    /// const distinct = qs.estimateDistinctElements();
+    /// console.log(distinct); // e.g., 1000
     /// ```
     #[napi]
-    pub fn difference(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
-        self.inner
-            .difference(&other.inner)
-            .map(|inner| ThetaSketch { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Difference failed: {}", e)))
+    pub fn estimateDistinctElements(&self) -> Result<i64> {
+        Ok(self.inner.estimate_distinct_elements() as i64)
     }
 
-    /// Compute Jaccard similarity: |A ∩ B| / |A ∪ B|
-    ///
-    /// Returns the Jaccard similarity coefficient between two sets,
-    /// a value between 0 (no overlap) and 1 (identical sets).
+    /// Estimate the weighted cardinality with confidence bounds
     ///
-    /// # Arguments
-    /// * `other` - Another ThetaSketch
+    /// Returns an object with estimate and errorBound, where errorBound
+    /// represents the radius of a 95% confidence interval.
     ///
     /// # Returns
-    /// Jaccard similarity coefficient (0.0 to 1.0)
-    ///
-    /// # Throws
-    /// - If sketches have different lgK or seed
+    /// Object with { estimate: number, errorBound: number }
     ///
     /// # Example
     /// ```javascript
-    /// const similarity = sketchA.jaccardSimilarity(sketchB);
-    /// console.log(`Similarity: ${(similarity * 100).toFixed(1)}%`);
+    /// const result = qs.estimateWeightedCardinality();
+    /// console.log(`Estimate: ${result.estimate} +/- ${result.errorBound}`);
     /// ```
     #[napi]
-    pub fn jaccardSimilarity(&self, other: &ThetaSketch) -> Result<f64> {
-        let union = self
-            .inner
-            .union(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))?;
-        let intersection = self
-            .inner
-            .intersect(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))?;
-
-        let union_est = union.estimate();
-        if union_est == 0.0 {
-            return Ok(0.0);
-        }
-        Ok(intersection.estimate() / union_est)
+    pub fn estimateWeightedCardinality(&self) -> Result<WeightedCardinalityResult> {
+        let (estimate, error_bound) = self.inner.estimate_weighted_cardinality();
+        Ok(WeightedCardinalityResult {
+            estimate,
+            error_bound,
+        })
     }
 
-    /// Get string representation
+    /// Get the q-quantile of the per-item weight distribution
+    ///
+    /// Each distinct item counts once with its accumulated weight. `q` is
+    /// clamped to [0, 1]; an empty sketch returns 0.
     ///
     /// # Example
     /// ```javascript
-    /// console.log(theta.toString());
-    /// // "ThetaSketch(capacity=4096, retained=1000, estimate=1000)"
+    /// const medianWeight = qs.weightQuantile(0.5);
     /// ```
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "ThetaSketch(capacity={}, retained={}, estimate={:.0})",
-            self.inner.capacity(),
-            self.inner.num_retained(),
-            self.inner.estimate()
-        ))
+    pub fn weightQuantile(&self, q: f64) -> f64 {
+        self.inner.weight_quantile(q)
     }
-}
-
-// =============================================================================
-// FREQUENCY ESTIMATION SKETCHES
-// =============================================================================
-
-// ============================================================================
-// COUNT-MIN SKETCH
-// ============================================================================
-
-/// Count-Min Sketch for frequency estimation
-///
-/// A space-efficient probabilistic data structure for estimating item frequencies
-/// in a data stream. The sketch guarantees:
-/// - Never underestimates (always returns count >= true count)
-/// - Error bounded by epsilon*N with probability 1-delta (where N is total stream size)
-///
-/// # Example
-/// ```javascript
-/// const { CountMinSketch } = require('@sketch-oxide/node');
-/// const cms = new CountMinSketch(0.01, 0.01);
-/// cms.update(Buffer.from('apple'));
-/// cms.update(Buffer.from('apple'));
-/// cms.update(Buffer.from('banana'));
-/// console.log(cms.estimate(Buffer.from('apple'))); // ~2
-/// ```
-#[napi]
-pub struct CountMinSketch {
-    inner: RustCountMinSketch,
-}
 
-#[napi]
-impl CountMinSketch {
-    /// Create a new Count-Min Sketch with specified error bounds
-    ///
-    /// # Arguments
-    /// * `epsilon` - Error bound (0 < epsilon < 1): estimates are within epsilon*N of true value
-    /// * `delta` - Failure probability (0 < delta < 1): guarantee holds with probability 1-delta
+    /// Get the basic cardinality estimate (without error bounds)
     ///
     /// # Returns
-    /// A new CountMinSketch instance
-    ///
-    /// # Throws
-    /// - If epsilon is not in (0, 1)
-    /// - If delta is not in (0, 1)
+    /// Estimated cardinality
     ///
     /// # Example
     /// ```javascript
-    /// // 1% error bound, 1% failure probability
-    /// const cms = new CountMinSketch(0.01, 0.01);
+    /// const estimate = qs.estimate();
     /// ```
-    #[napi(constructor)]
-    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
-        RustCountMinSketch::new(epsilon, delta)
-            .map(|inner| CountMinSketch { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("CountMinSketch creation failed: {}", e),
-                )
-            })
-    }
-
-    /// Add an item to the sketch (increment count by 1)
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(&data);
-        Ok(())
+    pub fn estimate(&self) -> Result<f64> {
+        Ok(Sketch::estimate(&self.inner))
     }
 
-    /// Estimate the frequency of an item
-    #[napi]
-    pub fn estimate(&self, item: Buffer) -> Result<i64> {
-        let data: Vec<u8> = item.to_vec();
-        Ok(self.inner.estimate(&data) as i64)
-    }
-
-    /// Merge another Count-Min Sketch into this one
-    #[napi]
-    pub fn merge(&mut self, other: &CountMinSketch) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
-    }
-
-    /// Get the width of the sketch
-    #[napi]
-    pub fn width(&self) -> Result<i64> {
-        Ok(self.inner.width() as i64)
-    }
-
-    /// Get the depth of the sketch
+    /// Check if the sketch is empty
+    ///
+    /// # Returns
+    /// True if no items have been added
+    ///
+    /// # Example
+    /// ```javascript
+    /// const qs = new QSketch(256);
+    /// console.log(qs.isEmpty()); // true
+    /// ```
     #[napi]
-    pub fn depth(&self) -> Result<i64> {
-        Ok(self.inner.depth() as i64)
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(Sketch::is_empty(&self.inner))
     }
 
-    /// Get the epsilon parameter
+    /// Reset the sketch to empty state
+    ///
+    /// # Example
+    /// ```javascript
+    /// qs.reset();
+    /// console.log(qs.isEmpty()); // true
+    /// ```
     #[napi]
-    pub fn epsilon(&self) -> Result<f64> {
-        Ok(self.inner.epsilon())
+    pub fn reset(&mut self) -> Result<()> {
+        self.inner.reset();
+        Ok(())
     }
 
-    /// Get the delta parameter
+    /// Merge another QSketch into this one
+    ///
+    /// # Arguments
+    /// * `other` - Another QSketch with same maxSamples
+    ///
+    /// # Throws
+    /// - If maxSamples values don't match
+    ///
+    /// # Example
+    /// ```javascript
+    /// const qs1 = new QSketch(256);
+    /// const qs2 = new QSketch(256);
+    /// qs1.update(Buffer.from('a'), 100.0);
+    /// qs2.update(Buffer.from('b'), 200.0);
+    /// qs1.merge(qs2);
+    /// ```
     #[napi]
-    pub fn delta(&self) -> Result<f64> {
-        Ok(self.inner.delta())
+    pub fn merge(&mut self, other: &QSketch) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
     /// Serialize the sketch to binary format
+    ///
+    /// # Returns
+    /// Binary representation suitable for storage/transmission
+    ///
+    /// # Example
+    /// ```javascript
+    /// const data = qs.serialize();
+    /// fs.writeFileSync('qs.bin', data);
+    /// ```
     #[napi]
     pub fn serialize(&self) -> Result<Buffer> {
-        let bytes = self.inner.serialize();
+        let bytes = Sketch::serialize(&self.inner);
         Ok(Buffer::from(bytes))
     }
 
     /// Deserialize from binary format
+    ///
+    /// # Arguments
+    /// * `data` - Binary data from serialize()
+    ///
+    /// # Returns
+    /// A new QSketch instance
+    ///
+    /// # Throws
+    /// - If data is invalid or corrupted
+    ///
+    /// # Example
+    /// ```javascript
+    /// const data = fs.readFileSync('qs.bin');
+    /// const qs = QSketch.deserialize(data);
+    /// ```
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustCountMinSketch::deserialize(&data)
-            .map(|inner| CountMinSketch { inner })
+        RustQSketch::from_bytes(&data)
+            .map(|inner| QSketch { inner })
             .map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
@@ -1459,896 +1793,3317 @@ impl CountMinSketch {
     }
 
     /// Get string representation
+    ///
+    /// # Example
+    /// ```javascript
+    /// console.log(qs.toString());
+    /// // "QSketch(maxSamples=256, samples=150, totalWeight=1500.0)"
+    /// ```
     #[napi]
     pub fn toString(&self) -> Result<String> {
         Ok(format!(
-            "CountMinSketch(width={}, depth={}, epsilon={}, delta={})",
-            self.inner.width(),
-            self.inner.depth(),
-            self.inner.epsilon(),
-            self.inner.delta()
+            "QSketch(maxSamples={}, samples={}, totalWeight={:.1})",
+            self.inner.max_samples(),
+            self.inner.sample_count(),
+            self.inner.total_weight()
         ))
     }
 }
 
-// ============================================================================
-// COUNT SKETCH
-// ============================================================================
+/// Result of weighted cardinality estimation
+#[napi(object)]
+pub struct WeightedCardinalityResult {
+    /// The estimated weighted cardinality
+    pub estimate: f64,
+    /// The error bound (95% confidence interval radius)
+    pub error_bound: f64,
+}
 
-/// Count Sketch for unbiased frequency estimation
+/// Jaccard similarity with its confidence interval from `ThetaSketch.jaccard`
+#[napi(object)]
+pub struct JaccardBounds {
+    pub lower: f64,
+    pub estimate: f64,
+    pub upper: f64,
+}
+
+// =============================================================================
+// ThetaSketch - Set operations (union, intersection, difference)
+// =============================================================================
+
+/// Theta Sketch for cardinality estimation with set operations
 ///
-/// Unlike Count-Min Sketch, Count Sketch:
-/// - Can estimate both positive and negative frequencies (supports deletions)
-/// - Has E[estimate] = true_count (unbiased)
-/// - Provides L2 error guarantees
+/// ThetaSketch is the only sketch that supports intersection and difference
+/// operations, making it ideal for computing Jaccard similarity, overlap
+/// analysis, and set-based queries.
 ///
 /// # Example
 /// ```javascript
-/// const { CountSketch } = require('@sketch-oxide/node');
-/// const cs = new CountSketch(0.1, 0.01);
-/// cs.update(Buffer.from('apple'), 5);
-/// cs.update(Buffer.from('apple'), -2);  // Decrement by 2
-/// console.log(cs.estimate(Buffer.from('apple'))); // ~3
+/// const { ThetaSketch } = require('@sketch-oxide/node');
+/// const sketchA = new ThetaSketch(12);
+/// const sketchB = new ThetaSketch(12);
+///
+/// // Add items
+/// for (let i = 0; i < 100; i++) sketchA.update(Buffer.from(`item${i}`));
+/// for (let i = 50; i < 150; i++) sketchB.update(Buffer.from(`item${i}`));
+///
+/// // Set operations
+/// const union = sketchA.union(sketchB);
+/// const intersection = sketchA.intersect(sketchB);
+/// const difference = sketchA.difference(sketchB);
+///
+/// console.log(`Union: ${union.estimate()}`);        // ~150
+/// console.log(`Intersection: ${intersection.estimate()}`); // ~50
+/// console.log(`Difference: ${difference.estimate()}`);     // ~50
 /// ```
 #[napi]
-pub struct CountSketch {
-    inner: RustCountSketch,
+pub struct ThetaSketch {
+    inner: RustThetaSketch,
+}
+
+impl BatchUpdate for ThetaSketch {
+    fn update_bytes(&mut self, item: &[u8]) {
+        self.inner.update(&item);
+    }
 }
 
 #[napi]
-impl CountSketch {
-    /// Create a new Count Sketch with specified error bounds
+impl ThetaSketch {
+    /// Create a new Theta Sketch with specified lg_k
+    ///
+    /// # Arguments
+    /// * `lgK` - log2(k), determines accuracy and memory (4-26)
+    ///   - k = 2^lgK (nominal entries)
+    ///   - Memory: ~8k bytes
+    ///   - Error: ~1/sqrt(k)
+    ///   - lgK=12 (k=4096): ~1.6% error, 32KB (recommended)
+    ///   - lgK=14 (k=16384): ~0.8% error, 128KB
+    ///   - lgK=16 (k=65536): ~0.4% error, 512KB
+    ///
+    /// # Returns
+    /// A new ThetaSketch instance
+    ///
+    /// # Throws
+    /// - If lgK is out of valid range (4-26)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const theta = new ThetaSketch(12);
+    /// ```
     #[napi(constructor)]
-    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
-        RustCountSketch::new(epsilon, delta)
-            .map(|inner| CountSketch { inner })
+    pub fn new(lg_k: u8) -> Result<Self> {
+        RustThetaSketch::new(lg_k)
+            .map(|inner| ThetaSketch { inner })
             .map_err(|e| {
                 Error::new(
                     Status::InvalidArg,
-                    format!("CountSketch creation failed: {}", e),
+                    format!("ThetaSketch creation failed: {}", e),
                 )
             })
     }
 
-    /// Update the sketch with an item and delta
+    /// Create a Theta Sketch with custom seed
+    ///
+    /// Use the same seed for all sketches that will be combined in set operations.
+    ///
+    /// # Arguments
+    /// * `lgK` - log2(k) parameter (4-26)
+    /// * `seed` - Custom seed for hash function
+    ///
+    /// # Returns
+    /// A new ThetaSketch instance
+    ///
+    /// # Example
+    /// ```javascript
+    /// const theta = ThetaSketch.withSeed(12, 12345n);
+    /// ```
+    #[napi(factory)]
+    pub fn withSeed(lg_k: u8, seed: BigInt) -> Result<Self> {
+        let (_, seed_val, _) = seed.get_u64();
+        RustThetaSketch::with_seed(lg_k, seed_val)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("ThetaSketch creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Add an item to the sketch
+    ///
+    /// # Arguments
+    /// * `item` - Binary data to add
+    ///
+    /// # Example
+    /// ```javascript
+    /// theta.update(Buffer.from('item1'));
+    /// theta.update(Buffer.from('hello world'));
+    /// ```
     #[napi]
-    pub fn update(&mut self, item: Buffer, delta: i64) -> Result<()> {
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
         let data: Vec<u8> = item.to_vec();
-        self.inner.update(&data, delta);
+        self.inner.update(&data);
         Ok(())
     }
 
-    /// Estimate the frequency of an item
+    /// Add a value that was already hashed by another tool
+    ///
+    /// The 64-bit hash is used as-is (the seed is not applied) and must be
+    /// uniformly distributed. Feed a sketch through either `update` or
+    /// `updateHash`, not both.
     #[napi]
-    pub fn estimate(&self, item: Buffer) -> Result<i64> {
-        let data: Vec<u8> = item.to_vec();
-        Ok(self.inner.estimate(&data))
+    pub fn updateHash(&mut self, hash: BigInt) -> Result<()> {
+        self.inner.update_hash(bigint_to_u64(hash)?);
+        Ok(())
     }
 
-    /// Estimate inner product of two frequency vectors
-    #[napi]
+    /// Add an unsigned 64-bit integer without converting it to a Buffer
+    ///
+    /// Hashes the value's fixed 8-byte little-endian encoding, so
+    /// `updateU64(5n)` has the same effect as `update` with those bytes and
+    /// as `update_u64` in the Rust crate.
+    ///
+    /// # Throws
+    /// - If the value is negative or exceeds 64 bits
+    ///
+    /// # Example
+    /// ```javascript
+    /// sketch.updateU64(12345n);
+    /// ```
+    #[napi]
+    pub fn updateU64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_u64(bigint_to_u64(value)?);
+        Ok(())
+    }
+
+    /// Add a signed 64-bit integer (8-byte little-endian two's complement)
+    ///
+    /// # Throws
+    /// - If the value doesn't fit in 64 bits
+    #[napi]
+    pub fn updateI64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_i64(bigint_to_i64(value)?);
+        Ok(())
+    }
+
+    /// Add a number by its 8-byte little-endian IEEE 754 encoding
+    ///
+    /// `0` and `-0` are hashed as distinct values.
+    #[napi]
+    pub fn updateF64(&mut self, value: f64) {
+        self.inner.update_f64(value);
+    }
+
+    /// Add a batch of items on the libuv threadpool
+    ///
+    /// Hashing and sampling run off the main thread, so large batches don't
+    /// block the event loop; only copying the items happens synchronously.
+    /// Do not call any other method on this sketch, including another
+    /// `updateBatchAsync`, until the returned promise settles: the sketch is
+    /// not locked while the batch runs.
+    ///
+    /// # Example
+    /// ```javascript
+    /// await theta.updateBatchAsync([Buffer.from('a'), Buffer.from('b')]);
+    /// ```
+    #[napi(
+        ts_args_type = "items: Array<Buffer>",
+        ts_return_type = "Promise<void>"
+    )]
+    pub fn updateBatchAsync(
+        &self,
+        reference: Reference<ThetaSketch>,
+        items: Array,
+    ) -> Result<AsyncTask<UpdateBatchTask<ThetaSketch>>> {
+        Ok(AsyncTask::new(UpdateBatchTask::new(reference, items)?))
+    }
+
+    /// Get current cardinality estimate
+    ///
+    /// # Returns
+    /// Estimated number of unique items
+    ///
+    /// # Example
+    /// ```javascript
+    /// const estimate = theta.estimate();
+    /// console.log(estimate); // e.g., 1000
+    /// ```
+    #[napi]
+    pub fn estimate(&self) -> Result<f64> {
+        Ok(self.inner.estimate())
+    }
+
+    /// Check if the sketch is empty
+    ///
+    /// # Returns
+    /// True if no items have been added
+    ///
+    /// # Example
+    /// ```javascript
+    /// const theta = new ThetaSketch(12);
+    /// console.log(theta.isEmpty()); // true
+    /// ```
+    #[napi]
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
+    }
+
+    /// Get the number of retained hash entries
+    ///
+    /// # Returns
+    /// Number of hash entries currently stored
+    ///
+    /// # Example
+    /// ```javascript
+    /// console.log(theta.numRetained()); // e.g., 1000
+    /// ```
+    #[napi]
+    pub fn numRetained(&self) -> Result<u32> {
+        Ok(self.inner.num_retained() as u32)
+    }
+
+    /// Get the current theta value
+    ///
+    /// - u64::MAX means no sampling (exact mode)
+    /// - Values < u64::MAX indicate sampling is active
+    ///
+    /// # Returns
+    /// Current theta threshold as BigInt
+    ///
+    /// # Example
+    /// ```javascript
+    /// const theta = sketch.getTheta();
+    /// ```
+    #[napi]
+    pub fn getTheta(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.get_theta()))
+    }
+
+    /// Stable 64-bit hash of the sketch content, as BigInt
+    ///
+    /// Equal to `compact().contentHash()`. Identical sketches share the same
+    /// hash. Not a cryptographic hash: distinct sketches can collide.
+    #[napi]
+    pub fn contentHash(&self) -> BigInt {
+        BigInt::from(self.inner.content_hash())
+    }
+
+    /// Get the nominal capacity (k = 2^lgK)
+    ///
+    /// # Returns
+    /// Nominal capacity
+    ///
+    /// # Example
+    /// ```javascript
+    /// const theta = new ThetaSketch(12);
+    /// console.log(theta.capacity()); // 4096
+    /// ```
+    #[napi]
+    pub fn capacity(&self) -> Result<u32> {
+        Ok(self.inner.capacity() as u32)
+    }
+
+    /// Lower theta until the compact image fits in `maxBytes`
+    ///
+    /// Drops the largest retained hashes and recomputes theta so the
+    /// estimate stays unbiased. Error grows as ~1/sqrt(retained entries).
+    ///
+    /// # Arguments
+    /// * `maxBytes` - Byte budget (24-byte preamble plus 8 bytes per entry)
+    ///
+    /// # Example
+    /// ```javascript
+    /// theta.trimToBytes(8 * 1024);
+    /// ```
+    #[napi]
+    pub fn trimToBytes(&mut self, max_bytes: u32) -> Result<()> {
+        self.inner.trim_to_bytes(max_bytes as usize);
+        Ok(())
+    }
+
+    /// Freeze the sketch into a read-only CompactThetaSketch
+    ///
+    /// The compact form keeps the same estimate in a sorted array, without
+    /// hash table overhead, and cannot be updated.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const frozen = theta.compact();
+    /// console.log(frozen.estimate());
+    /// ```
+    #[napi]
+    pub fn compact(&self) -> CompactThetaSketch {
+        CompactThetaSketch {
+            inner: self.inner.compact(),
+        }
+    }
+
+    /// Compute union with another sketch: |A ∪ B|
+    ///
+    /// Returns a new sketch representing items in either A or B (or both).
+    /// Both sketches must have the same lgK and seed.
+    ///
+    /// # Arguments
+    /// * `other` - Another ThetaSketch
+    ///
+    /// # Returns
+    /// New ThetaSketch with union result
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    ///
+    /// # Example
+    /// ```javascript
+    /// const union = sketchA.union(sketchB);
+    /// console.log(union.estimate()); // |A ∪ B|
+    /// ```
+    #[napi]
+    pub fn union(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
+        self.inner
+            .union(&other.inner)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    /// Estimate the cardinality of the union without modifying either sketch
+    ///
+    /// # Arguments
+    /// * `other` - Another ThetaSketch with same lgK and seed
+    ///
+    /// # Throws
+    /// - If the sketches are incompatible
+    ///
+    /// # Example
+    /// ```javascript
+    /// const total = sketch1.estimateUnion(sketch2);
+    /// ```
+    #[napi]
+    pub fn estimateUnion(&self, other: &ThetaSketch) -> Result<f64> {
+        self.inner
+            .estimate_union(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    /// Compute the union of an array of sketches in one pass
+    ///
+    /// # Arguments
+    /// * `sketches` - ThetaSketches with the same lgK and seed
+    ///
+    /// # Throws
+    /// - If the array is empty or the sketches are incompatible
+    ///
+    /// # Example
+    /// ```javascript
+    /// const union = ThetaSketch.unionAll([a, b, c]);
+    /// ```
+    #[napi(factory)]
+    pub fn unionAll(sketches: Vec<ClassInstance<ThetaSketch>>) -> Result<ThetaSketch> {
+        let inners: Vec<&RustThetaSketch> = sketches.iter().map(|s| &s.inner).collect();
+        RustThetaSketch::union_all(&inners)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    /// Compute the intersection of an array of sketches
+    ///
+    /// # Arguments
+    /// * `sketches` - ThetaSketches with the same lgK and seed
+    ///
+    /// # Throws
+    /// - If the array is empty or the sketches are incompatible
+    ///
+    /// # Example
+    /// ```javascript
+    /// const common = ThetaSketch.intersectAll([a, b, c]);
+    /// ```
+    #[napi(factory)]
+    pub fn intersectAll(sketches: Vec<ClassInstance<ThetaSketch>>) -> Result<ThetaSketch> {
+        let inners: Vec<&RustThetaSketch> = sketches.iter().map(|s| &s.inner).collect();
+        RustThetaSketch::intersect_all(&inners)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))
+    }
+
+    /// Get a JSON summary of parameters and state for debugging
+    ///
+    /// Not meant for reconstruction.
+    ///
+    /// # Example
+    /// ```javascript
+    /// console.log(JSON.parse(sketch.toJson()).estimate);
+    /// ```
+    #[napi]
+    pub fn toJson(&self) -> String {
+        self.inner.to_json()
+    }
+
+    /// Compute intersection with another sketch: |A ∩ B|
+    ///
+    /// Returns a new sketch representing items in both A and B.
+    /// Both sketches must have the same lgK and seed.
+    ///
+    /// # Arguments
+    /// * `other` - Another ThetaSketch
+    ///
+    /// # Returns
+    /// New ThetaSketch with intersection result
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    ///
+    /// # Example
+    /// ```javascript
+    /// const intersection = sketchA.intersect(sketchB);
+    /// console.log(intersection.estimate()); // |A ∩ B|
+    /// ```
+    #[napi]
+    pub fn intersect(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
+        self.inner
+            .intersect(&other.inner)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))
+    }
+
+    /// Compute difference: |A - B| (items in A but not in B)
+    ///
+    /// Returns a new sketch representing items in A that are not in B.
+    /// Both sketches must have the same lgK and seed.
+    /// Note: A - B != B - A (not commutative)
+    ///
+    /// # Arguments
+    /// * `other` - Another ThetaSketch
+    ///
+    /// # Returns
+    /// New ThetaSketch with difference result
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    ///
+    /// # Example
+    /// ```javascript
+    /// const difference = sketchA.difference(sketchB);
+    /// console.log(difference.estimate()); // |A - B|
+    /// ```
+    #[napi]
+    pub fn difference(&self, other: &ThetaSketch) -> Result<ThetaSketch> {
+        self.inner
+            .difference(&other.inner)
+            .map(|inner| ThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Difference failed: {}", e)))
+    }
+
+    /// Compute Jaccard similarity: |A ∩ B| / |A ∪ B|
+    ///
+    /// Returns the Jaccard similarity coefficient between two sets,
+    /// a value between 0 (no overlap) and 1 (identical sets).
+    ///
+    /// # Arguments
+    /// * `other` - Another ThetaSketch
+    ///
+    /// # Returns
+    /// Jaccard similarity coefficient (0.0 to 1.0)
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    ///
+    /// # Example
+    /// ```javascript
+    /// const similarity = sketchA.jaccardSimilarity(sketchB);
+    /// console.log(`Similarity: ${(similarity * 100).toFixed(1)}%`);
+    /// ```
+    #[napi]
+    pub fn jaccardSimilarity(&self, other: &ThetaSketch) -> Result<f64> {
+        let union = self
+            .inner
+            .union(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))?;
+        let intersection = self
+            .inner
+            .intersect(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))?;
+
+        let union_est = union.estimate();
+        if union_est == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(intersection.estimate() / union_est)
+    }
+
+    /// Estimate Jaccard similarity with confidence bounds
+    ///
+    /// Uses the joint-theta estimator from Apache DataSketches, which bounds
+    /// the error better than dividing separate intersection and union
+    /// estimates. The bounds cover roughly two standard deviations and are
+    /// exact while neither sketch samples. The lgK values may differ.
+    ///
+    /// # Throws
+    /// - If sketches have different seeds
+    ///
+    /// # Example
+    /// ```javascript
+    /// const { lower, estimate, upper } = sketchA.jaccard(sketchB);
+    /// ```
+    #[napi]
+    pub fn jaccard(&self, other: &ThetaSketch) -> Result<JaccardBounds> {
+        let (lower, estimate, upper) = self
+            .inner
+            .jaccard(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Jaccard failed: {}", e)))?;
+        Ok(JaccardBounds {
+            lower,
+            estimate,
+            upper,
+        })
+    }
+
+    /// Get string representation
+    ///
+    /// # Example
+    /// ```javascript
+    /// console.log(theta.toString());
+    /// // "ThetaSketch(capacity=4096, retained=1000, estimate=1000)"
+    /// ```
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "ThetaSketch(capacity={}, retained={}, estimate={:.0})",
+            self.inner.capacity(),
+            self.inner.num_retained(),
+            self.inner.estimate()
+        ))
+    }
+}
+
+sketch_binding! {
+    /// Read-only Theta Sketch produced by `ThetaSketch.compact()`
+    ///
+    /// Holds the retained hashes in a sorted array. Supports estimates, set
+    /// operations with other compact sketches, and serialization.
+    pub struct CompactThetaSketch(RustCompactThetaSketch);
+    estimate => estimate;
+    is_empty => is_empty;
+    memory_usage => memory_usage;
+    serialize => to_bytes, from_bytes;
+}
+
+#[napi]
+impl CompactThetaSketch {
+    /// Get the number of retained hash entries
+    #[napi]
+    pub fn numRetained(&self) -> u32 {
+        self.inner.num_retained() as u32
+    }
+
+    /// Get the theta value
+    #[napi]
+    pub fn getTheta(&self) -> BigInt {
+        BigInt::from(self.inner.get_theta())
+    }
+
+    /// Stable 64-bit hash of the sketch content, as BigInt
+    ///
+    /// Survives serialize/deserialize. Not a cryptographic hash: distinct
+    /// sketches can collide.
+    #[napi]
+    pub fn contentHash(&self) -> BigInt {
+        BigInt::from(self.inner.content_hash())
+    }
+
+    /// Compute union with another compact sketch: |A ∪ B|
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    #[napi]
+    pub fn union(&self, other: &CompactThetaSketch) -> Result<CompactThetaSketch> {
+        self.inner
+            .union(&other.inner)
+            .map(|inner| CompactThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    /// Compute intersection with another compact sketch: |A ∩ B|
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    #[napi]
+    pub fn intersect(&self, other: &CompactThetaSketch) -> Result<CompactThetaSketch> {
+        self.inner
+            .intersect(&other.inner)
+            .map(|inner| CompactThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersection failed: {}", e)))
+    }
+
+    /// Compute difference: |A - B| (items in A but not in B)
+    ///
+    /// # Throws
+    /// - If sketches have different lgK or seed
+    #[napi]
+    pub fn difference(&self, other: &CompactThetaSketch) -> Result<CompactThetaSketch> {
+        self.inner
+            .difference(&other.inner)
+            .map(|inner| CompactThetaSketch { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Difference failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "CompactThetaSketch(lg_k={}, retained={}, estimate={:.0})",
+            self.inner.lg_k(),
+            self.inner.num_retained(),
+            self.inner.estimate()
+        )
+    }
+}
+
+// =============================================================================
+// FREQUENCY ESTIMATION SKETCHES
+// =============================================================================
+
+// ============================================================================
+// COUNT-MIN SKETCH
+// ============================================================================
+
+/// Key with its estimated change between two Count-Min Sketches
+#[napi(object)]
+pub struct TopMover {
+    pub key: Buffer,
+    pub delta: i64,
+}
+
+/// Count-Min Sketch for frequency estimation
+///
+/// A space-efficient probabilistic data structure for estimating item frequencies
+/// in a data stream. The sketch guarantees:
+/// - Never underestimates (always returns count >= true count)
+/// - Error bounded by epsilon*N with probability 1-delta (where N is total stream size)
+///
+/// # Example
+/// ```javascript
+/// const { CountMinSketch } = require('@sketch-oxide/node');
+/// const cms = new CountMinSketch(0.01, 0.01);
+/// cms.update(Buffer.from('apple'));
+/// cms.update(Buffer.from('apple'));
+/// cms.update(Buffer.from('banana'));
+/// console.log(cms.estimate(Buffer.from('apple'))); // ~2
+/// ```
+#[napi]
+pub struct CountMinSketch {
+    inner: RustCountMinSketch,
+}
+
+impl BatchUpdate for CountMinSketch {
+    fn update_bytes(&mut self, item: &[u8]) {
+        self.inner.update(&item);
+    }
+}
+
+#[napi]
+impl CountMinSketch {
+    /// Create a new Count-Min Sketch with specified error bounds
+    ///
+    /// # Arguments
+    /// * `epsilon` - Error bound (0 < epsilon < 1): estimates are within epsilon*N of true value
+    /// * `delta` - Failure probability (0 < delta < 1): guarantee holds with probability 1-delta
+    ///
+    /// # Returns
+    /// A new CountMinSketch instance
+    ///
+    /// # Throws
+    /// - If epsilon is not in (0, 1)
+    /// - If delta is not in (0, 1)
+    ///
+    /// # Example
+    /// ```javascript
+    /// // 1% error bound, 1% failure probability
+    /// const cms = new CountMinSketch(0.01, 0.01);
+    /// ```
+    #[napi(constructor)]
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
+        RustCountMinSketch::new(epsilon, delta)
+            .map(|inner| CountMinSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("CountMinSketch creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Create a Count-Min Sketch with exact dimensions and per-row hash seeds
+    ///
+    /// Use this to match a sketch produced by another implementation.
+    ///
+    /// # Arguments
+    /// * `width` - Number of counters per row (at least 3)
+    /// * `depth` - Number of rows
+    /// * `seeds` - One hash seed per row (length must equal depth)
+    ///
+    /// # Throws
+    /// - If dimensions are out of range or seed count doesn't match depth
+    ///
+    /// # Example
+    /// ```javascript
+    /// const cms = CountMinSketch.withDimensionsSeeded(1000, 3, [11n, 22n, 33n]);
+    /// ```
+    #[napi(factory)]
+    pub fn withDimensionsSeeded(width: u32, depth: u32, seeds: Vec<BigInt>) -> Result<Self> {
+        let seeds: Vec<u64> = seeds.iter().map(|s| s.get_u64().1).collect();
+        RustCountMinSketch::with_dimensions_seeded(width as usize, depth as usize, &seeds)
+            .map(|inner| CountMinSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("CountMinSketch creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Add an item to the sketch (increment count by 1)
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&data);
+        Ok(())
+    }
+
+    /// Add an unsigned 64-bit integer by its 8-byte little-endian encoding
+    #[napi]
+    pub fn updateU64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_u64(bigint_to_u64(value)?);
+        Ok(())
+    }
+
+    /// Add a signed 64-bit integer by its 8-byte little-endian encoding
+    #[napi]
+    pub fn updateI64(&mut self, value: BigInt) -> Result<()> {
+        self.inner.update_i64(bigint_to_i64(value)?);
+        Ok(())
+    }
+
+    /// Add a number by its 8-byte little-endian IEEE 754 encoding
+    #[napi]
+    pub fn updateF64(&mut self, value: f64) {
+        self.inner.update_f64(value);
+    }
+
+    /// Add a batch of items on the libuv threadpool
+    ///
+    /// Hashing and counter updates run off the main thread, so large batches don't
+    /// block the event loop; only copying the items happens synchronously.
+    /// Do not call any other method on this sketch, including another
+    /// `updateBatchAsync`, until the returned promise settles: the sketch is
+    /// not locked while the batch runs.
+    ///
+    /// # Example
+    /// ```javascript
+    /// await cms.updateBatchAsync([Buffer.from('a'), Buffer.from('b')]);
+    /// ```
+    #[napi(
+        ts_args_type = "items: Array<Buffer>",
+        ts_return_type = "Promise<void>"
+    )]
+    pub fn updateBatchAsync(
+        &self,
+        reference: Reference<CountMinSketch>,
+        items: Array,
+    ) -> Result<AsyncTask<UpdateBatchTask<CountMinSketch>>> {
+        Ok(AsyncTask::new(UpdateBatchTask::new(reference, items)?))
+    }
+
+    /// Add a signed delta to an item's count; decrements clamp counters at 0
+    ///
+    /// Estimates stay upper bounds only while no item is deleted more often
+    /// than it was inserted.
+    #[napi]
+    pub fn updateSigned(&mut self, item: Buffer, delta: i64) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update_signed(&data, delta);
+        Ok(())
+    }
+
+    /// Estimate the frequency of an item
+    #[napi]
+    pub fn estimate(&self, item: Buffer) -> Result<i64> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.estimate(&data) as i64)
+    }
+
+    /// Get the total count N of all updates
+    #[napi]
+    pub fn totalCount(&self) -> Result<i64> {
+        Ok(self.inner.total_count() as i64)
+    }
+
+    /// Estimate the fraction of the stream made up by an item
+    ///
+    /// Returns `estimate(item) / totalCount()`, or 0 when empty; the result
+    /// overestimates the true fraction by at most epsilon.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const share = cms.estimateFrequency(Buffer.from('/login'));
+    /// ```
+    #[napi]
+    pub fn estimateFrequency(&self, item: Buffer) -> Result<f64> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.estimate_frequency(&data))
+    }
+
+    /// Check if an item's estimated frequency exceeds `phi`
+    ///
+    /// Items with a true frequency down to `phi - epsilon` may also be reported.
+    ///
+    /// # Example
+    /// ```javascript
+    /// if (cms.isHeavyHitter(Buffer.from('/login'), 0.01)) { ... }
+    /// ```
+    #[napi]
+    pub fn isHeavyHitter(&self, item: Buffer, phi: f64) -> Result<bool> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.is_heavy_hitter(&data, phi))
+    }
+
+    /// Get the candidate keys whose estimate grew the most since `previous`
+    ///
+    /// The sketch cannot enumerate keys, so candidates must be supplied.
+    /// Results are sorted by descending delta.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const movers = thisMinute.topMovers(lastMinute, [Buffer.from('/login')], 10);
+    /// ```
+    #[napi]
+    pub fn topMovers(
+        &self,
+        previous: &CountMinSketch,
+        candidate_keys: Vec<Buffer>,
+        k: u32,
+    ) -> Vec<TopMover> {
+        let keys: Vec<&[u8]> = candidate_keys.iter().map(|k| k.as_ref()).collect();
+        self.inner
+            .top_movers(&previous.inner, &keys, k as usize)
+            .into_iter()
+            .map(|(key, delta)| TopMover {
+                key: key.into(),
+                delta,
+            })
+            .collect()
+    }
+
+    /// Merge another Count-Min Sketch into this one
+    #[napi]
+    pub fn merge(&mut self, other: &CountMinSketch) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    /// Check the sketch's internal invariants, throwing if any is violated
+    ///
+    /// Use after `deserialize` or `merge` to detect a corrupted sketch.
+    #[napi]
+    pub fn validate(&self) -> Result<()> {
+        self.inner
+            .validate()
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Validation failed: {}", e)))
+    }
+
+    /// Get the width of the sketch
+    #[napi]
+    pub fn width(&self) -> Result<i64> {
+        Ok(self.inner.width() as i64)
+    }
+
+    /// Get the depth of the sketch
+    #[napi]
+    pub fn depth(&self) -> Result<i64> {
+        Ok(self.inner.depth() as i64)
+    }
+
+    /// Get the epsilon parameter
+    #[napi]
+    pub fn epsilon(&self) -> Result<f64> {
+        Ok(self.inner.epsilon())
+    }
+
+    /// Get the delta parameter
+    #[napi]
+    pub fn delta(&self) -> Result<f64> {
+        Ok(self.inner.delta())
+    }
+
+    /// Get the raw counter grid, one array per row (diagnostic)
+    ///
+    /// Copies every counter, so call it for visualization or debugging
+    /// rather than on a hot path.
+    #[napi]
+    pub fn counters(&self) -> Vec<Vec<i64>> {
+        self.inner
+            .counters()
+            .chunks(self.inner.width())
+            .map(|row| row.iter().map(|&c| c as i64).collect())
+            .collect()
+    }
+
+    /// Get the per-row hash seeds, or null for the default hashing scheme
+    ///
+    /// Pass them to `withDimensionsSeeded` with the same width and depth to
+    /// build a sketch on another node that hashes identically and can be merged.
+    #[napi]
+    pub fn seeds(&self) -> Option<Vec<BigInt>> {
+        self.inner
+            .seeds()
+            .map(|seeds| seeds.iter().map(|&seed| BigInt::from(seed)).collect())
+    }
+
+    /// Serialize the sketch to binary format
+    #[napi]
+    pub fn serialize(&self) -> Result<Buffer> {
+        let bytes = self.inner.serialize();
+        Ok(Buffer::from(bytes))
+    }
+
+    /// Deserialize from binary format
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustCountMinSketch::deserialize(&data)
+            .map(|inner| CountMinSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
+    /// Get string representation
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "CountMinSketch(width={}, depth={}, epsilon={}, delta={})",
+            self.inner.width(),
+            self.inner.depth(),
+            self.inner.epsilon(),
+            self.inner.delta()
+        ))
+    }
+}
+
+// ============================================================================
+// COUNTED WRAPPERS (exact update counts for debugging estimator accuracy)
+// ============================================================================
+
+/// HyperLogLog that also records the exact number of updates
+///
+/// Useful for comparing the estimate against the true stream length
+/// while validating accuracy.
+///
+/// # Example
+/// ```javascript
+/// const { CountedHyperLogLog } = require('@sketch-oxide/node');
+/// const hll = new CountedHyperLogLog(14);
+/// hll.update(Buffer.from('a'));
+/// hll.update(Buffer.from('a'));
+/// console.log(hll.trueCount(), hll.estimate()); // 2n, ~1
+/// ```
+#[napi]
+pub struct CountedHyperLogLog {
+    inner: RustCounted<RustHyperLogLog>,
+}
+
+#[napi]
+impl CountedHyperLogLog {
+    /// Create a new counted HyperLogLog with given precision
+    #[napi(constructor)]
+    pub fn new(precision: u8) -> Result<Self> {
+        RustHyperLogLog::new(precision)
+            .map(|hll| CountedHyperLogLog {
+                inner: RustCounted::new(hll),
+            })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("HyperLogLog creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Add an item to the sketch and count the update
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update_with(|hll| hll.update(&data));
+        Ok(())
+    }
+
+    /// Get current cardinality estimate
+    #[napi]
+    pub fn estimate(&self) -> Result<f64> {
+        Ok(self.inner.estimate())
+    }
+
+    /// Get the exact number of updates seen
+    #[napi]
+    pub fn trueCount(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.true_count()))
+    }
+}
+
+/// Count-Min Sketch that also records the exact number of updates
+///
+/// # Example
+/// ```javascript
+/// const { CountedCountMinSketch } = require('@sketch-oxide/node');
+/// const cms = new CountedCountMinSketch(0.01, 0.01);
+/// cms.update(Buffer.from('apple'));
+/// console.log(cms.trueCount(), cms.estimate(Buffer.from('apple'))); // 1n, ~1
+/// ```
+#[napi]
+pub struct CountedCountMinSketch {
+    inner: RustCounted<RustCountMinSketch>,
+}
+
+#[napi]
+impl CountedCountMinSketch {
+    /// Create a new counted Count-Min Sketch with specified error bounds
+    #[napi(constructor)]
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
+        RustCountMinSketch::new(epsilon, delta)
+            .map(|cms| CountedCountMinSketch {
+                inner: RustCounted::new(cms),
+            })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("CountMinSketch creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Add an item to the sketch and count the update
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update_with(|cms| cms.update(&data));
+        Ok(())
+    }
+
+    /// Estimate the frequency of an item
+    #[napi]
+    pub fn estimate(&self, item: Buffer) -> Result<i64> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.inner().estimate(&data) as i64)
+    }
+
+    /// Get the exact number of updates seen
+    #[napi]
+    pub fn trueCount(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.true_count()))
+    }
+}
+
+// ============================================================================
+// COUNT SKETCH
+// ============================================================================
+
+/// Count Sketch for unbiased frequency estimation
+///
+/// Unlike Count-Min Sketch, Count Sketch:
+/// - Can estimate both positive and negative frequencies (supports deletions)
+/// - Has E[estimate] = true_count (unbiased)
+/// - Provides L2 error guarantees
+///
+/// # Example
+/// ```javascript
+/// const { CountSketch } = require('@sketch-oxide/node');
+/// const cs = new CountSketch(0.1, 0.01);
+/// cs.update(Buffer.from('apple'), 5);
+/// cs.update(Buffer.from('apple'), -2);  // Decrement by 2
+/// console.log(cs.estimate(Buffer.from('apple'))); // ~3
+/// ```
+#[napi]
+pub struct CountSketch {
+    inner: RustCountSketch,
+}
+
+#[napi]
+impl CountSketch {
+    /// Create a new Count Sketch with specified error bounds
+    #[napi(constructor)]
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
+        RustCountSketch::new(epsilon, delta)
+            .map(|inner| CountSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("CountSketch creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Update the sketch with an item and delta
+    #[napi]
+    pub fn update(&mut self, item: Buffer, delta: i64) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&data, delta);
+        Ok(())
+    }
+
+    /// Estimate the frequency of an item
+    #[napi]
+    pub fn estimate(&self, item: Buffer) -> Result<i64> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.estimate(&data))
+    }
+
+    /// Estimate inner product of two frequency vectors
+    #[napi]
     pub fn innerProduct(&self, other: &CountSketch) -> Result<i64> {
         Ok(self.inner.inner_product(&other.inner))
     }
 
-    /// Merge another Count Sketch into this one
+    /// Estimate the second frequency moment (self-join size)
+    #[napi]
+    pub fn estimateF2(&self) -> f64 {
+        self.inner.estimate_f2()
+    }
+
+    /// Merge another Count Sketch into this one
+    #[napi]
+    pub fn merge(&mut self, other: &CountSketch) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn width(&self) -> Result<i64> {
+        Ok(self.inner.width() as i64)
+    }
+
+    #[napi]
+    pub fn depth(&self) -> Result<i64> {
+        Ok(self.inner.depth() as i64)
+    }
+
+    #[napi]
+    pub fn epsilon(&self) -> Result<f64> {
+        Ok(self.inner.epsilon())
+    }
+
+    #[napi]
+    pub fn delta(&self) -> Result<f64> {
+        Ok(self.inner.delta())
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Result<Buffer> {
+        let bytes = self.inner.serialize();
+        Ok(Buffer::from(bytes))
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustCountSketch::deserialize(&data)
+            .map(|inner| CountSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "CountSketch(width={}, depth={}, epsilon={}, delta={})",
+            self.inner.width(),
+            self.inner.depth(),
+            self.inner.epsilon(),
+            self.inner.delta()
+        ))
+    }
+}
+
+// ============================================================================
+// CONSERVATIVE COUNT-MIN SKETCH
+// ============================================================================
+
+/// Conservative Update Count-Min Sketch for improved frequency estimation
+///
+/// More accurate than standard Count-Min (up to 10x less overestimation)
+/// but does NOT support deletions.
+#[napi]
+pub struct ConservativeCountMin {
+    inner: RustConservativeCountMin,
+}
+
+#[napi]
+impl ConservativeCountMin {
+    #[napi(constructor)]
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
+        RustConservativeCountMin::new(epsilon, delta)
+            .map(|inner| ConservativeCountMin { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("ConservativeCountMin creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi(factory)]
+    pub fn withDimensions(width: i64, depth: i64) -> Result<Self> {
+        RustConservativeCountMin::with_dimensions(width as usize, depth as usize)
+            .map(|inner| ConservativeCountMin { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("ConservativeCountMin creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&data);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn updateCount(&mut self, item: Buffer, count: i64) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update_count(&data, count as u64);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn estimate(&self, item: Buffer) -> Result<i64> {
+        let data: Vec<u8> = item.to_vec();
+        Ok(self.inner.estimate(&data) as i64)
+    }
+
+    #[napi]
+    pub fn merge(&mut self, other: &ConservativeCountMin) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn validate(&self) -> Result<()> {
+        self.inner
+            .validate()
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Validation failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn width(&self) -> Result<i64> {
+        Ok(self.inner.width() as i64)
+    }
+
+    #[napi]
+    pub fn depth(&self) -> Result<i64> {
+        Ok(self.inner.depth() as i64)
+    }
+
+    #[napi]
+    pub fn epsilon(&self) -> Result<f64> {
+        Ok(self.inner.epsilon())
+    }
+
+    #[napi]
+    pub fn delta(&self) -> Result<f64> {
+        Ok(self.inner.delta())
+    }
+
+    #[napi]
+    pub fn totalCount(&self) -> Result<i64> {
+        Ok(self.inner.total_count() as i64)
+    }
+
+    #[napi]
+    pub fn memoryUsage(&self) -> Result<i64> {
+        Ok(self.inner.memory_usage() as i64)
+    }
+
+    #[napi]
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Result<Buffer> {
+        let bytes = self.inner.to_bytes();
+        Ok(Buffer::from(bytes))
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustConservativeCountMin::from_bytes(&data)
+            .map(|inner| ConservativeCountMin { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "ConservativeCountMin(width={}, depth={}, epsilon={:.4}, delta={:.4})",
+            self.inner.width(),
+            self.inner.depth(),
+            self.inner.epsilon(),
+            self.inner.delta()
+        ))
+    }
+}
+
+// ============================================================================
+// SPACE-SAVING SKETCH
+// ============================================================================
+
+/// Result object for Space-Saving heavy hitter queries
+#[napi(object)]
+pub struct HeavyHitterResult {
+    pub key: String,
+    pub lower_bound: i64,
+    pub upper_bound: i64,
+}
+
+/// Space-Saving Sketch for Heavy Hitters Detection
+///
+/// Finds the most frequent items with guaranteed no false negatives.
+#[napi]
+pub struct SpaceSaving {
+    inner: RustSpaceSaving<Vec<u8>>,
+}
+
+#[napi]
+impl SpaceSaving {
+    #[napi(constructor)]
+    pub fn new(epsilon: f64) -> Result<Self> {
+        RustSpaceSaving::new(epsilon)
+            .map(|inner| SpaceSaving { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("SpaceSaving creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi(factory)]
+    pub fn withCapacity(capacity: i64) -> Result<Self> {
+        RustSpaceSaving::with_capacity(capacity as usize)
+            .map(|inner| SpaceSaving { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("SpaceSaving creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi(factory)]
+    pub fn withByteBudget(bytes: i64, avg_key_len: i64) -> Result<Self> {
+        if bytes < 0 || avg_key_len < 0 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "bytes and avgKeyLen must be >= 0",
+            ));
+        }
+        RustSpaceSaving::with_byte_budget(bytes as usize, avg_key_len as usize)
+            .map(|inner| SpaceSaving { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("SpaceSaving creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(data);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn updateBy(&mut self, item: Buffer, count: i64) -> Result<()> {
+        if count < 0 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "count must be non-negative".to_string(),
+            ));
+        }
+        self.inner.update_by(item.to_vec(), count as u64);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn updateWeighted(&mut self, item: Buffer, weight: f64) -> Result<()> {
+        self.inner.update_weighted(item.to_vec(), weight);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn estimate(&self, item: Buffer) -> Result<Option<HeavyHitterResult>> {
+        let data: Vec<u8> = item.to_vec();
+        match self.inner.estimate(&data) {
+            Some((lower, upper)) => Ok(Some(HeavyHitterResult {
+                key: hex::encode(&data),
+                lower_bound: lower as i64,
+                upper_bound: upper as i64,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    #[napi]
+    pub fn heavyHitters(&self, threshold: f64) -> Result<Vec<HeavyHitterResult>> {
+        let results = self.inner.heavy_hitters(threshold);
+        Ok(results
+            .into_iter()
+            .map(|(key, lower, upper)| HeavyHitterResult {
+                key: hex::encode(&key),
+                lower_bound: lower as i64,
+                upper_bound: upper as i64,
+            })
+            .collect())
+    }
+
+    #[napi]
+    pub fn topK(&self, k: i64) -> Result<Vec<HeavyHitterResult>> {
+        let results = self.inner.top_k(k as usize);
+        Ok(results
+            .into_iter()
+            .map(|(key, lower, upper)| HeavyHitterResult {
+                key: hex::encode(&key),
+                lower_bound: lower as i64,
+                upper_bound: upper as i64,
+            })
+            .collect())
+    }
+
+    #[napi]
+    pub fn merge(&mut self, other: &SpaceSaving) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn capacity(&self) -> Result<i64> {
+        Ok(self.inner.capacity() as i64)
+    }
+
+    #[napi]
+    pub fn streamLength(&self) -> Result<i64> {
+        Ok(self.inner.stream_length() as i64)
+    }
+
+    #[napi]
+    pub fn epsilon(&self) -> Result<f64> {
+        Ok(self.inner.epsilon())
+    }
+
+    #[napi]
+    pub fn numItems(&self) -> Result<i64> {
+        Ok(self.inner.num_items() as i64)
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
+    }
+
+    #[napi]
+    pub fn maxError(&self) -> Result<i64> {
+        Ok(self.inner.max_error() as i64)
+    }
+
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "SpaceSaving(capacity={}, numItems={}, streamLength={}, epsilon={:.4})",
+            self.inner.capacity(),
+            self.inner.num_items(),
+            self.inner.stream_length(),
+            self.inner.epsilon()
+        ))
+    }
+}
+
+// ============================================================================
+// FREQUENT ITEMS SKETCH
+// ============================================================================
+
+/// Error type for frequent items queries
+#[napi]
+pub enum FrequentItemsErrorType {
+    NoFalsePositives,
+    NoFalseNegatives,
+}
+
+/// Result object for FrequentItems queries
+#[napi(object)]
+pub struct FrequentItemResult {
+    pub key: String,
+    pub lower_bound: i64,
+    pub upper_bound: i64,
+}
+
+/// Frequent Items sketch based on Misra-Gries algorithm
+#[napi]
+pub struct FrequentItems {
+    inner: RustFrequentItems<Vec<u8>>,
+}
+
+#[napi]
+impl FrequentItems {
+    #[napi(constructor)]
+    pub fn new(max_size: i64) -> Result<Self> {
+        RustFrequentItems::new(max_size as usize)
+            .map(|inner| FrequentItems { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("FrequentItems creation failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(data);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn updateBy(&mut self, item: Buffer, count: i64) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update_by(data, count as u64);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn getEstimate(&self, item: Buffer) -> Result<Option<FrequentItemResult>> {
+        let data: Vec<u8> = item.to_vec();
+        match self.inner.get_estimate(&data) {
+            Some((lower, upper)) => Ok(Some(FrequentItemResult {
+                key: hex::encode(&data),
+                lower_bound: lower as i64,
+                upper_bound: upper as i64,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    #[napi]
+    pub fn frequentItems(
+        &self,
+        error_type: FrequentItemsErrorType,
+    ) -> Result<Vec<FrequentItemResult>> {
+        let rust_error_type = match error_type {
+            FrequentItemsErrorType::NoFalsePositives => RustErrorType::NoFalsePositives,
+            FrequentItemsErrorType::NoFalseNegatives => RustErrorType::NoFalseNegatives,
+        };
+        let results = self.inner.frequent_items(rust_error_type);
+        Ok(results
+            .into_iter()
+            .map(|(key, lower, upper)| FrequentItemResult {
+                key: hex::encode(&key),
+                lower_bound: lower as i64,
+                upper_bound: upper as i64,
+            })
+            .collect())
+    }
+
+    #[napi]
+    pub fn merge(&mut self, other: &FrequentItems) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
+    }
+
+    #[napi]
+    pub fn numItems(&self) -> Result<i64> {
+        Ok(self.inner.num_items() as i64)
+    }
+
+    #[napi]
+    pub fn maxSize(&self) -> Result<i64> {
+        Ok(self.inner.max_size() as i64)
+    }
+
+    #[napi]
+    pub fn offset(&self) -> Result<i64> {
+        Ok(self.inner.offset() as i64)
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustFrequentItems::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Deserialization failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "FrequentItems(maxSize={}, numItems={}, offset={})",
+            self.inner.max_size(),
+            self.inner.num_items(),
+            self.inner.offset()
+        ))
+    }
+}
+
+// =============================================================================
+// MEMBERSHIP TESTING
+// =============================================================================
+
+/// Binary Fuse Filter for probabilistic membership testing
+///
+/// State-of-the-art filter with ~75% better space efficiency than Bloom filters.
+/// This is an immutable data structure - build from a complete set of items.
+#[napi]
+pub struct BinaryFuseFilter {
+    inner: RustBinaryFuseFilter,
+}
+
+#[napi]
+impl BinaryFuseFilter {
+    /// Create a Binary Fuse Filter from an array of BigInt items
+    #[napi(factory)]
+    pub fn fromItems(items: Vec<BigInt>, bits_per_entry: u8) -> Result<Self> {
+        let u64_items: Vec<u64> = items
+            .into_iter()
+            .map(|bi| {
+                let (_, value, _) = bi.get_u64();
+                value
+            })
+            .collect();
+
+        RustBinaryFuseFilter::from_items(u64_items.into_iter(), bits_per_entry)
+            .map(|inner| BinaryFuseFilter { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("BinaryFuseFilter creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Check if an item might be in the set
+    #[napi]
+    pub fn contains(&self, item: BigInt) -> bool {
+        let (_, value, _) = item.get_u64();
+        self.inner.contains(&value)
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[napi]
+    pub fn bitsPerEntry(&self) -> f64 {
+        self.inner.bits_per_entry()
+    }
+
+    #[napi]
+    pub fn estimatedFpr(&self) -> f64 {
+        self.inner.estimated_fpr()
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.serialize())
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustBinaryFuseFilter::deserialize(&data)
+            .map(|inner| BinaryFuseFilter { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "BinaryFuseFilter(size={}, bits_per_entry={:.1}, fpr={:.4}%)",
+            self.inner.len(),
+            self.inner.bits_per_entry(),
+            self.inner.estimated_fpr() * 100.0
+        )
+    }
+}
+
+/// Counting Fuse Filter: a Binary Fuse-style filter that supports deletes
+///
+/// Inserts and deletes re-solve the whole table, so they suit occasional
+/// updates. Check needsRebuild() after heavy deletion churn.
+#[napi]
+pub struct CountingFuseFilter {
+    inner: RustCountingFuseFilter,
+}
+
+#[napi]
+impl CountingFuseFilter {
+    /// Create an empty filter sized for `capacity` BigInt items
+    #[napi(constructor)]
+    pub fn new(capacity: u32) -> Result<Self> {
+        RustCountingFuseFilter::new(capacity as usize)
+            .map(|inner| CountingFuseFilter { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    /// Create a filter from an array of BigInt items
+    #[napi(factory)]
+    pub fn fromItems(items: Vec<BigInt>) -> Result<Self> {
+        let u64_items = items
+            .into_iter()
+            .map(bigint_to_u64)
+            .collect::<Result<Vec<u64>>>()?;
+        RustCountingFuseFilter::from_items(u64_items)
+            .map(|inner| CountingFuseFilter { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("CountingFuseFilter creation failed: {}", e),
+                )
+            })
+    }
+
+    /// Insert an item, returning false if it was already present
+    #[napi]
+    pub fn insert(&mut self, item: BigInt) -> Result<bool> {
+        let value = bigint_to_u64(item)?;
+        self.inner
+            .insert(&value)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    /// Check if an item might be in the set
+    #[napi]
+    pub fn contains(&self, item: BigInt) -> Result<bool> {
+        Ok(self.inner.contains(&bigint_to_u64(item)?))
+    }
+
+    /// Delete an item, returning false if it was not present
+    #[napi]
+    pub fn delete(&mut self, item: BigInt) -> Result<bool> {
+        self.inner
+            .delete(&bigint_to_u64(item)?)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn needsRebuild(&self) -> bool {
+        self.inner.needs_rebuild()
+    }
+
+    #[napi]
+    pub fn rebuild(&mut self) -> Result<()> {
+        self.inner
+            .rebuild()
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[napi]
+    pub fn capacity(&self) -> u32 {
+        self.inner.capacity() as u32
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "CountingFuseFilter(size={}, capacity={})",
+            self.inner.len(),
+            self.inner.capacity()
+        )
+    }
+}
+
+sketch_binding! {
+    /// Bloom Filter for probabilistic membership testing
+    ///
+    /// Classic probabilistic data structure supporting dynamic insertions.
+    /// Zero false negatives guaranteed.
+    pub struct BloomFilter(RustBloomFilter);
+    insert => insert;
+    contains => contains;
+    clear => clear;
+    is_empty => is_empty;
+    len => len;
+    memory_usage => memory_usage;
+    serialize => to_bytes, from_bytes;
+}
+
+#[napi]
+impl BloomFilter {
+    #[napi(constructor)]
+    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if n == 0 {
+            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        Ok(Self {
+            inner: RustBloomFilter::new(n as usize, fpr),
+        })
+    }
+
+    #[napi(factory)]
+    pub fn forEstimated(hll: &HyperLogLog, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        Ok(Self {
+            inner: RustBloomFilter::for_estimated(&hll.inner, fpr),
+        })
+    }
+
+    #[napi]
+    pub fn hashIndices(&self, key: Buffer) -> Vec<u32> {
+        self.inner
+            .hash_indices(&key)
+            .into_iter()
+            .map(|i| i as u32)
+            .collect()
+    }
+
+    /// Insert by a 64-bit hash computed once and shared across filters
+    ///
+    /// Keys inserted this way are found by `containsHash`, not `contains`.
+    #[napi]
+    pub fn insertHash(&mut self, hash: BigInt) -> Result<()> {
+        self.inner.insert_hash(bigint_to_u64(hash)?);
+        Ok(())
+    }
+
+    /// Check a key inserted with `insertHash`
+    #[napi]
+    pub fn containsHash(&self, hash: BigInt) -> Result<bool> {
+        Ok(self.inner.contains_hash(bigint_to_u64(hash)?))
+    }
+
+    #[napi]
+    pub fn mergeWith(&mut self, other: &BloomFilter) -> Result<()> {
+        if self.inner.params() != other.inner.params() {
+            return Err(Error::new(Status::InvalidArg, "Parameters must match"));
+        }
+        self.inner.merge(&other.inner);
+        Ok(())
+    }
+
+    /// Create a filter whose bits are the AND of both filters
+    ///
+    /// Approximates the intersection: keys in both filters are always found,
+    /// but keys in neither can also match when their bits overlap.
+    ///
+    /// # Throws
+    /// - If the parameters differ
+    #[napi]
+    pub fn intersect(&self, other: &BloomFilter) -> Result<BloomFilter> {
+        self.inner
+            .intersect(&other.inner)
+            .map(|inner| BloomFilter { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Intersect failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn falsePositiveRate(&self) -> f64 {
+        self.inner.false_positive_rate()
+    }
+
+    #[napi]
+    pub fn currentFalsePositiveRate(&self) -> f64 {
+        self.inner.current_false_positive_rate()
+    }
+
+    #[napi]
+    pub fn isSaturated(&self, threshold: f64) -> bool {
+        self.inner.is_saturated(threshold)
+    }
+
+    /// Serialize using the sparse encoding (smaller while under ~1/8 of bits are set)
+    ///
+    /// `BloomFilter.deserialize` accepts both this and the plain format.
+    #[napi]
+    pub fn serializeCompressed(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes_compressed())
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        let (n, m, k) = self.inner.params();
+        format!(
+            "BloomFilter(n={}, bits={}, hashes={}, fpr={:.4}%)",
+            n,
+            m,
+            k,
+            self.inner.false_positive_rate() * 100.0
+        )
+    }
+}
+
+/// Blocked Bloom Filter for cache-efficient membership testing
+#[napi]
+pub struct BlockedBloomFilter {
+    inner: RustBlockedBloomFilter,
+}
+
+#[napi]
+impl BlockedBloomFilter {
+    #[napi(constructor)]
+    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if n == 0 {
+            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        Ok(Self {
+            inner: RustBlockedBloomFilter::new(n as usize, fpr),
+        })
+    }
+
+    /// Create a filter with 256-, 512- or 1024-bit blocks
+    ///
+    /// Match the CPU's cache line (1024 bits for 128-byte lines). Smaller
+    /// blocks are more prone to crowding and give a higher false positive rate.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const filter = BlockedBloomFilter.withBlockBits(10000, 0.01, 1024);
+    /// ```
+    #[napi(factory)]
+    pub fn withBlockBits(n: u32, fpr: f64, block_bits: u32) -> Result<Self> {
+        if n == 0 {
+            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        if ![256, 512, 1024].contains(&block_bits) {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "blockBits must be 256, 512 or 1024",
+            ));
+        }
+        Ok(Self {
+            inner: RustBlockedBloomFilter::with_block_bits(n as usize, fpr, block_bits as usize),
+        })
+    }
+
+    /// Get the number of bits per block
+    #[napi]
+    pub fn blockBits(&self) -> u32 {
+        self.inner.block_bits() as u32
+    }
+
+    #[napi]
+    pub fn insert(&mut self, key: Buffer) {
+        self.inner.insert(&key);
+    }
+
+    #[napi]
+    pub fn contains(&self, key: Buffer) -> bool {
+        self.inner.contains(&key)
+    }
+
+    #[napi]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[napi]
+    pub fn mergeWith(&mut self, other: &BlockedBloomFilter) -> Result<()> {
+        if self.inner.params() != other.inner.params()
+            || self.inner.block_bits() != other.inner.block_bits()
+        {
+            return Err(Error::new(Status::InvalidArg, "Parameters must match"));
+        }
+        self.inner.merge(&other.inner);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    #[napi]
+    pub fn falsePositiveRate(&self) -> f64 {
+        self.inner.false_positive_rate()
+    }
+
+    #[napi]
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustBlockedBloomFilter::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        let (n, num_blocks, k) = self.inner.params();
+        format!(
+            "BlockedBloomFilter(n={}, blocks={}, hashes={}, fpr={:.4}%)",
+            n,
+            num_blocks,
+            k,
+            self.inner.false_positive_rate() * 100.0
+        )
+    }
+}
+
+/// Counting Bloom Filter with deletion support
+#[napi]
+pub struct CountingBloomFilter {
+    inner: RustCountingBloomFilter,
+}
+
+#[napi]
+impl CountingBloomFilter {
+    #[napi(constructor)]
+    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if n == 0 {
+            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        Ok(Self {
+            inner: RustCountingBloomFilter::new(n as usize, fpr),
+        })
+    }
+
+    #[napi]
+    pub fn insert(&mut self, key: Buffer) {
+        self.inner.insert(&key);
+    }
+
     #[napi]
-    pub fn merge(&mut self, other: &CountSketch) -> Result<()> {
+    pub fn remove(&mut self, key: Buffer) -> bool {
+        self.inner.remove(&key)
+    }
+
+    #[napi]
+    pub fn contains(&self, key: Buffer) -> bool {
+        self.inner.contains(&key)
+    }
+
+    #[napi]
+    pub fn countEstimate(&self, key: Buffer) -> u8 {
+        self.inner.count_estimate(&key)
+    }
+
+    /// Check if a key might have been inserted at least `k` times
+    ///
+    /// Collisions with other keys can overstate the count, so this can
+    /// return false positives.
+    #[napi]
+    pub fn containsAtLeast(&self, key: Buffer, k: u8) -> bool {
+        self.inner.contains_at_least(&key, k)
+    }
+
+    #[napi]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[napi]
+    pub fn mergeWith(&mut self, other: &CountingBloomFilter) -> Result<()> {
         self.inner
             .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    #[napi]
+    pub fn hasOverflow(&self) -> bool {
+        self.inner.has_overflow()
+    }
+
+    #[napi]
+    pub fn falsePositiveRate(&self) -> f64 {
+        self.inner.false_positive_rate()
+    }
+
+    #[napi]
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustCountingBloomFilter::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "CountingBloomFilter(count={}, fpr={:.4}%)",
+            self.inner.len(),
+            self.inner.false_positive_rate() * 100.0
+        )
+    }
+}
+
+/// Cuckoo Filter for membership testing with deletions
+#[napi]
+pub struct CuckooFilter {
+    inner: RustCuckooFilter,
+}
+
+#[napi]
+impl CuckooFilter {
+    #[napi(constructor)]
+    pub fn new(capacity: u32) -> Result<Self> {
+        RustCuckooFilter::new(capacity as usize)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    #[napi(factory)]
+    pub fn forEstimated(hll: &HyperLogLog, fpr: f64) -> Result<Self> {
+        RustCuckooFilter::for_estimated(&hll.inner, fpr)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    /// Create a filter with the fingerprint width chosen for a target FPR
+    #[napi(factory)]
+    pub fn withFpr(capacity: u32, fpr: f64) -> Result<Self> {
+        RustCuckooFilter::with_fpr(capacity as usize, fpr)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    #[napi]
+    pub fn insert(&mut self, key: Buffer) -> Result<()> {
+        self.inner
+            .insert(&key)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn remove(&mut self, key: Buffer) -> bool {
+        self.inner.remove(&key)
+    }
+
+    /// Add every fingerprint of `other`; folds onto the smaller bucket count
+    #[napi]
+    pub fn unionInto(&mut self, other: &CuckooFilter) -> Result<()> {
+        self.inner
+            .union_into(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
+    #[napi]
+    pub fn contains(&self, key: Buffer) -> bool {
+        self.inner.contains(&key)
+    }
+
+    /// Insert by a 64-bit hash computed once and shared across filters
+    ///
+    /// Keys inserted this way are found by `containsHash`, not `contains`.
+    #[napi]
+    pub fn insertHash(&mut self, hash: BigInt) -> Result<()> {
+        self.inner
+            .insert_hash(bigint_to_u64(hash)?)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    /// Check a key inserted with `insertHash`
+    #[napi]
+    pub fn containsHash(&self, hash: BigInt) -> Result<bool> {
+        Ok(self.inner.contains_hash(bigint_to_u64(hash)?))
+    }
+
+    /// Fraction of known-absent keys reported as present (diagnostic)
+    #[napi]
+    pub fn measuredFpr(&self, absent_keys: Vec<Buffer>) -> f64 {
+        let keys: Vec<&[u8]> = absent_keys.iter().map(|k| k.as_ref()).collect();
+        self.inner.measured_fpr(&keys)
+    }
+
+    #[napi]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    #[napi]
+    pub fn capacity(&self) -> u32 {
+        self.inner.capacity() as u32
+    }
+
+    #[napi]
+    pub fn loadFactor(&self) -> f64 {
+        self.inner.load_factor()
+    }
+
+    #[napi]
+    pub fn fingerprintBits(&self) -> u32 {
+        self.inner.fingerprint_bits() as u32
+    }
+
+    /// False positive rate bound at full load for the fingerprint width
+    #[napi]
+    pub fn falsePositiveRate(&self) -> f64 {
+        self.inner.false_positive_rate()
+    }
+
+    #[napi]
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustCuckooFilter::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "CuckooFilter(count={}, capacity={}, load={:.1}%)",
+            self.inner.len(),
+            self.inner.capacity(),
+            self.inner.load_factor() * 100.0
+        )
+    }
+}
+
+/// How a Ribbon filter was built
+#[napi(object)]
+pub struct RibbonBuildResult {
+    /// "solved" when every key is represented by the solution, "overflow" otherwise
+    pub path: String,
+    /// Number of keys stored in the exact overflow set
+    pub overflow_keys: u32,
+}
+
+/// Ribbon Filter for space-efficient membership testing
+#[napi]
+pub struct RibbonFilter {
+    inner: RustRibbonFilter,
+}
+
+#[napi]
+impl RibbonFilter {
+    #[napi(constructor)]
+    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if n == 0 {
+            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        Ok(Self {
+            inner: RustRibbonFilter::new(n as usize, fpr),
+        })
+    }
+
+    #[napi]
+    pub fn insert(&mut self, key: Buffer) -> Result<()> {
+        if self.inner.is_finalized() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Cannot insert after finalization",
+            ));
+        }
+        self.inner.insert(&key);
+        Ok(())
+    }
+
+    /// Build the filter; throws if the filter is too small for its keys
+    #[napi]
+    pub fn build(&mut self) -> Result<RibbonBuildResult> {
+        self.inner
+            .finalize()
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?;
+        Ok(self.buildInfo().unwrap())
+    }
+
+    /// How the filter was built, or null before build()
+    #[napi]
+    pub fn buildInfo(&self) -> Option<RibbonBuildResult> {
+        self.inner.build_info().map(|info| match info {
+            RibbonBuildInfo::Solved => RibbonBuildResult {
+                path: "solved".to_string(),
+                overflow_keys: 0,
+            },
+            RibbonBuildInfo::Overflow { keys } => RibbonBuildResult {
+                path: "overflow".to_string(),
+                overflow_keys: keys as u32,
+            },
+        })
+    }
+
+    #[napi]
+    pub fn contains(&self, key: Buffer) -> Result<bool> {
+        if !self.inner.is_finalized() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Must call build() before querying",
+            ));
+        }
+        Ok(self.inner.contains(&key))
+    }
+
+    #[napi]
+    pub fn isFinalized(&self) -> bool {
+        self.inner.is_finalized()
     }
 
     #[napi]
-    pub fn width(&self) -> Result<i64> {
-        Ok(self.inner.width() as i64)
+    pub fn len(&self) -> u32 {
+        self.inner.len() as u32
     }
 
     #[napi]
-    pub fn depth(&self) -> Result<i64> {
-        Ok(self.inner.depth() as i64)
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
     }
 
     #[napi]
-    pub fn epsilon(&self) -> Result<f64> {
-        Ok(self.inner.epsilon())
+    pub fn falsePositiveRate(&self) -> f64 {
+        self.inner.false_positive_rate()
     }
 
     #[napi]
-    pub fn delta(&self) -> Result<f64> {
-        Ok(self.inner.delta())
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
     }
 
     #[napi]
     pub fn serialize(&self) -> Result<Buffer> {
-        let bytes = self.inner.serialize();
-        Ok(Buffer::from(bytes))
+        if !self.inner.is_finalized() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Must call build() before serialization",
+            ));
+        }
+        Ok(Buffer::from(self.inner.to_bytes()))
     }
 
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustCountSketch::deserialize(&data)
-            .map(|inner| CountSketch { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
-                )
-            })
+        RustRibbonFilter::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
 
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "CountSketch(width={}, depth={}, epsilon={}, delta={})",
-            self.inner.width(),
-            self.inner.depth(),
-            self.inner.epsilon(),
-            self.inner.delta()
-        ))
+    pub fn toString(&self) -> String {
+        format!(
+            "RibbonFilter(count={}, finalized={}, fpr={:.4}%)",
+            self.inner.len(),
+            self.inner.is_finalized(),
+            self.inner.false_positive_rate() * 100.0
+        )
     }
 }
 
-// ============================================================================
-// CONSERVATIVE COUNT-MIN SKETCH
-// ============================================================================
-
-/// Conservative Update Count-Min Sketch for improved frequency estimation
+/// StableBloomFilter for membership testing with decay mechanism
 ///
-/// More accurate than standard Count-Min (up to 10x less overestimation)
-/// but does NOT support deletions.
+/// A Bloom filter variant that decays counts over time, useful for tracking
+/// recently-seen items while removing old ones without explicit deletion.
+///
+/// # Example
+/// ```javascript
+/// const { StableBloomFilter } = require('@sketch-oxide/node');
+/// const sbf = new StableBloomFilter(1000, 0.01);
+/// sbf.insert(Buffer.from('item1'));
+/// console.log(sbf.contains(Buffer.from('item1'))); // true
+/// ```
 #[napi]
-pub struct ConservativeCountMin {
-    inner: RustConservativeCountMin,
+pub struct StableBloomFilter {
+    inner: RustStableBloomFilter,
 }
 
 #[napi]
-impl ConservativeCountMin {
+impl StableBloomFilter {
+    /// Create a new StableBloomFilter
+    ///
+    /// # Arguments
+    /// * `expected_items` - Expected number of items to store
+    /// * `fpr` - Target false positive rate (0 < fpr < 1)
     #[napi(constructor)]
-    pub fn new(epsilon: f64, delta: f64) -> Result<Self> {
-        RustConservativeCountMin::new(epsilon, delta)
-            .map(|inner| ConservativeCountMin { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ConservativeCountMin creation failed: {}", e),
-                )
-            })
-    }
-
-    #[napi(factory)]
-    pub fn withDimensions(width: i64, depth: i64) -> Result<Self> {
-        RustConservativeCountMin::with_dimensions(width as usize, depth as usize)
-            .map(|inner| ConservativeCountMin { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ConservativeCountMin creation failed: {}", e),
-                )
-            })
-    }
-
-    #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(&data);
-        Ok(())
+    pub fn new(expected_items: u32, fpr: Option<f64>) -> Result<Self> {
+        let fpr = fpr.unwrap_or(0.01);
+        if expected_items == 0 {
+            return Err(Error::new(Status::InvalidArg, "expected_items must be > 0"));
+        }
+        if fpr <= 0.0 || fpr >= 1.0 {
+            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+        }
+        RustStableBloomFilter::new(expected_items as usize, fpr)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Insert a key into the filter
+    ///
+    /// # Arguments
+    /// * `key` - Binary data to insert
     #[napi]
-    pub fn updateCount(&mut self, item: Buffer, count: i64) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update_count(&data, count as u64);
+    pub fn insert(&mut self, key: Buffer) -> Result<()> {
+        self.inner.insert(&key);
         Ok(())
     }
 
+    /// Check if a key might be in the set
+    ///
+    /// # Returns
+    /// True if key might be present, False if definitely not present
     #[napi]
-    pub fn estimate(&self, item: Buffer) -> Result<i64> {
-        let data: Vec<u8> = item.to_vec();
-        Ok(self.inner.estimate(&data) as i64)
+    pub fn contains(&self, key: Buffer) -> bool {
+        self.inner.contains(&key)
     }
 
+    /// Get the count estimate for a key
+    ///
+    /// # Arguments
+    /// * `key` - Binary data to check
+    ///
+    /// # Returns
+    /// Counter value (0-255 depending on counter_bits)
     #[napi]
-    pub fn merge(&mut self, other: &ConservativeCountMin) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    pub fn getCount(&self, key: Buffer) -> u32 {
+        self.inner.get_count(&key) as u32
     }
 
+    /// Get the number of counters in this filter
     #[napi]
-    pub fn width(&self) -> Result<i64> {
-        Ok(self.inner.width() as i64)
+    pub fn numCounters(&self) -> u32 {
+        self.inner.num_counters() as u32
     }
 
+    /// Get the number of hash functions used
     #[napi]
-    pub fn depth(&self) -> Result<i64> {
-        Ok(self.inner.depth() as i64)
+    pub fn numHashes(&self) -> u32 {
+        self.inner.num_hashes() as u32
     }
 
+    /// Get the number of decrements performed
     #[napi]
-    pub fn epsilon(&self) -> Result<f64> {
-        Ok(self.inner.epsilon())
+    pub fn decrementCount(&self) -> u32 {
+        self.inner.decrement_count() as u32
     }
 
+    /// Get the fill ratio of the filter (0.0 to 1.0)
     #[napi]
-    pub fn delta(&self) -> Result<f64> {
-        Ok(self.inner.delta())
+    pub fn fillRatio(&self) -> f64 {
+        self.inner.fill_ratio()
     }
 
+    /// Estimate the probability that an item inserted `inserts_ago` operations
+    /// ago has decayed out of the filter
+    ///
+    /// # Arguments
+    /// * `inserts_ago` - Number of inserts performed since the item was added
     #[napi]
-    pub fn totalCount(&self) -> Result<i64> {
-        Ok(self.inner.total_count() as i64)
+    pub fn falseNegativeProbability(&self, inserts_ago: i64) -> Result<f64> {
+        if inserts_ago < 0 {
+            return Err(Error::new(Status::InvalidArg, "inserts_ago must be >= 0"));
+        }
+        Ok(self.inner.false_negative_probability(inserts_ago as u64))
     }
 
+    /// Clear all data from the filter
     #[napi]
-    pub fn memoryUsage(&self) -> Result<i64> {
-        Ok(self.inner.memory_usage() as i64)
+    pub fn clear(&mut self) {
+        self.inner.clear();
     }
 
+    /// Get memory usage in bytes
     #[napi]
-    pub fn clear(&mut self) -> Result<()> {
-        self.inner.clear();
-        Ok(())
+    pub fn memoryUsage(&self) -> u32 {
+        self.inner.memory_usage() as u32
     }
 
+    /// Serialize the filter to binary format
     #[napi]
-    pub fn serialize(&self) -> Result<Buffer> {
-        let bytes = self.inner.to_bytes();
-        Ok(Buffer::from(bytes))
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
     }
 
+    /// Deserialize a filter from binary format
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustConservativeCountMin::from_bytes(&data)
-            .map(|inner| ConservativeCountMin { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
-                )
-            })
+        RustStableBloomFilter::from_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
 
+    /// Get a string representation
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "ConservativeCountMin(width={}, depth={}, epsilon={:.4}, delta={:.4})",
-            self.inner.width(),
-            self.inner.depth(),
-            self.inner.epsilon(),
-            self.inner.delta()
-        ))
+    pub fn toString(&self) -> String {
+        format!(
+            "StableBloomFilter(counters={}, hashes={}, fill={:.2}%)",
+            self.inner.num_counters(),
+            self.inner.num_hashes(),
+            self.inner.fill_ratio() * 100.0
+        )
     }
 }
 
-// ============================================================================
-// SPACE-SAVING SKETCH
-// ============================================================================
+// =============================================================================
+// QUANTILE ESTIMATION
+// =============================================================================
 
-/// Result object for Space-Saving heavy hitter queries
-#[napi(object)]
-pub struct HeavyHitterResult {
-    pub key: String,
-    pub lower_bound: i64,
-    pub upper_bound: i64,
+/// DDSketch index mapping
+#[napi]
+pub enum DDSketchMapping {
+    Logarithmic,
+    CubicallyInterpolated,
+    LinearlyInterpolated,
 }
 
-/// Space-Saving Sketch for Heavy Hitters Detection
-///
-/// Finds the most frequent items with guaranteed no false negatives.
-#[napi]
-pub struct SpaceSaving {
-    inner: RustSpaceSaving<Vec<u8>>,
+/// DDSketch for quantile estimation with relative error guarantees
+#[napi(js_name = "DDSketch")]
+pub struct DDSketch {
+    inner: RustDDSketch,
 }
 
 #[napi]
-impl SpaceSaving {
-    #[napi(constructor)]
-    pub fn new(epsilon: f64) -> Result<Self> {
-        RustSpaceSaving::new(epsilon)
-            .map(|inner| SpaceSaving { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("SpaceSaving creation failed: {}", e),
-                )
-            })
+impl DDSketch {
+    #[napi(constructor)]
+    pub fn new(relative_accuracy: f64) -> Result<Self> {
+        RustDDSketch::new(relative_accuracy)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Create a DDSketch with a specific index mapping
     #[napi(factory)]
-    pub fn withCapacity(capacity: i64) -> Result<Self> {
-        RustSpaceSaving::with_capacity(capacity as usize)
-            .map(|inner| SpaceSaving { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("SpaceSaving creation failed: {}", e),
-                )
-            })
+    pub fn withMapping(relative_accuracy: f64, mapping: DDSketchMapping) -> Result<Self> {
+        let rust_mapping = match mapping {
+            DDSketchMapping::Logarithmic => RustMapping::Logarithmic,
+            DDSketchMapping::CubicallyInterpolated => RustMapping::CubicallyInterpolated,
+            DDSketchMapping::LinearlyInterpolated => RustMapping::LinearlyInterpolated,
+        };
+        RustDDSketch::with_mapping(relative_accuracy, rust_mapping)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Get the index mapping
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(data);
-        Ok(())
+    pub fn mapping(&self) -> DDSketchMapping {
+        match self.inner.mapping() {
+            RustMapping::Logarithmic => DDSketchMapping::Logarithmic,
+            RustMapping::CubicallyInterpolated => DDSketchMapping::CubicallyInterpolated,
+            RustMapping::LinearlyInterpolated => DDSketchMapping::LinearlyInterpolated,
+        }
     }
 
     #[napi]
-    pub fn estimate(&self, item: Buffer) -> Result<Option<HeavyHitterResult>> {
-        let data: Vec<u8> = item.to_vec();
-        match self.inner.estimate(&data) {
-            Some((lower, upper)) => Ok(Some(HeavyHitterResult {
-                key: hex::encode(&data),
-                lower_bound: lower as i64,
-                upper_bound: upper as i64,
-            })),
-            None => Ok(None),
+    pub fn update(&mut self, value: f64) {
+        Sketch::update(&mut self.inner, &value);
+    }
+
+    #[napi]
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        for val in values {
+            Sketch::update(&mut self.inner, &val);
         }
     }
 
+    /// Add a value, throwing instead of skipping NaN or an infinity
+    ///
+    /// `update` and `updateBatch` skip non-finite values and count them in
+    /// `rejectedCount()`.
     #[napi]
-    pub fn heavyHitters(&self, threshold: f64) -> Result<Vec<HeavyHitterResult>> {
-        let results = self.inner.heavy_hitters(threshold);
-        Ok(results
-            .into_iter()
-            .map(|(key, lower, upper)| HeavyHitterResult {
-                key: hex::encode(&key),
-                lower_bound: lower as i64,
-                upper_bound: upper as i64,
-            })
-            .collect())
+    pub fn updateStrict(&mut self, value: f64) -> Result<()> {
+        self.inner
+            .try_add(value)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Number of non-finite values skipped by `update` and `updateBatch`
     #[napi]
-    pub fn topK(&self, k: i64) -> Result<Vec<HeavyHitterResult>> {
-        let results = self.inner.top_k(k as usize);
-        Ok(results
-            .into_iter()
-            .map(|(key, lower, upper)| HeavyHitterResult {
-                key: hex::encode(&key),
-                lower_bound: lower as i64,
-                upper_bound: upper as i64,
-            })
-            .collect())
+    pub fn rejectedCount(&self) -> i64 {
+        self.inner.rejected_count() as i64
     }
 
     #[napi]
-    pub fn merge(&mut self, other: &SpaceSaving) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.inner.quantile(q)
     }
 
     #[napi]
-    pub fn capacity(&self) -> Result<i64> {
-        Ok(self.inner.capacity() as i64)
+    pub fn quantiles(&self, quantiles: Vec<f64>) -> Vec<f64> {
+        quantiles
+            .iter()
+            .filter_map(|&q| self.inner.quantile(q))
+            .collect()
     }
 
+    /// Estimated number of values in [low, high], resolved to whole bins
     #[napi]
-    pub fn streamLength(&self) -> Result<i64> {
-        Ok(self.inner.stream_length() as i64)
+    pub fn countBetween(&self, low: f64, high: f64) -> f64 {
+        self.inner.count_between(low, high)
     }
 
     #[napi]
-    pub fn epsilon(&self) -> Result<f64> {
-        Ok(self.inner.epsilon())
+    pub fn mergeWith(&mut self, other: &DDSketch) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Return a new sketch holding both inputs; neither is modified
     #[napi]
-    pub fn numItems(&self) -> Result<i64> {
-        Ok(self.inner.num_items() as i64)
+    pub fn merged(&self, other: &DDSketch) -> Result<DDSketch> {
+        self.inner
+            .merged(&other.inner)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn count(&self) -> i64 {
+        self.inner.count() as i64
     }
 
     #[napi]
-    pub fn maxError(&self) -> Result<i64> {
-        Ok(self.inner.max_error() as i64)
+    pub fn min(&self) -> Option<f64> {
+        self.inner.min()
     }
 
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "SpaceSaving(capacity={}, numItems={}, streamLength={}, epsilon={:.4})",
-            self.inner.capacity(),
-            self.inner.num_items(),
-            self.inner.stream_length(),
-            self.inner.epsilon()
-        ))
+    pub fn max(&self) -> Option<f64> {
+        self.inner.max()
     }
-}
 
-// ============================================================================
-// FREQUENT ITEMS SKETCH
-// ============================================================================
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        Sketch::is_empty(&self.inner)
+    }
 
-/// Error type for frequent items queries
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(Sketch::serialize(&self.inner))
+    }
+
+    #[napi(factory, ts_return_type = "DDSketch")]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustDDSketch::deserialize(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    #[napi]
+    pub fn toString(&self) -> String {
+        format!(
+            "DDSketch(count={}, min={:.2}, max={:.2})",
+            self.inner.count(),
+            self.inner.min().unwrap_or(0.0),
+            self.inner.max().unwrap_or(0.0)
+        )
+    }
+}
+
+#[cfg(feature = "ddsketch-proto")]
 #[napi]
-pub enum FrequentItemsErrorType {
-    NoFalsePositives,
-    NoFalseNegatives,
+impl DDSketch {
+    /// Serialize in DataDog's protobuf DDSketch format
+    ///
+    /// Readable by sketches-go and sketches-java. Only sketches using the
+    /// logarithmic mapping can be encoded.
+    ///
+    /// # Throws
+    /// - If the sketch uses an interpolated mapping
+    #[napi]
+    pub fn toProtoBytes(&self) -> Result<Buffer> {
+        self.inner
+            .to_proto_bytes()
+            .map(Buffer::from)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+
+    /// Deserialize from DataDog's protobuf DDSketch format
+    ///
+    /// # Throws
+    /// - If the bytes are not a valid DDSketch message or use interpolation
+    #[napi(factory, ts_return_type = "DDSketch")]
+    pub fn fromProtoBytes(data: Buffer) -> Result<Self> {
+        RustDDSketch::from_proto_bytes(&data)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
 }
 
-/// Result object for FrequentItems queries
-#[napi(object)]
-pub struct FrequentItemResult {
-    pub key: String,
-    pub lower_bound: i64,
-    pub upper_bound: i64,
+/// REQ Sketch mode
+#[napi]
+pub enum ReqSketchMode {
+    HighRankAccuracy,
+    LowRankAccuracy,
 }
 
-/// Frequent Items sketch based on Misra-Gries algorithm
+/// REQ Sketch for streaming quantile estimation (PODS 2021)
 #[napi]
-pub struct FrequentItems {
-    inner: RustFrequentItems<Vec<u8>>,
+pub struct ReqSketch {
+    inner: RustReqSketch,
 }
 
 #[napi]
-impl FrequentItems {
+impl ReqSketch {
     #[napi(constructor)]
-    pub fn new(max_size: i64) -> Result<Self> {
-        RustFrequentItems::new(max_size as usize)
-            .map(|inner| FrequentItems { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("FrequentItems creation failed: {}", e),
-                )
-            })
+    pub fn new(k: u32, mode: ReqSketchMode) -> Result<Self> {
+        let rust_mode = match mode {
+            ReqSketchMode::HighRankAccuracy => ReqMode::HighRankAccuracy,
+            ReqSketchMode::LowRankAccuracy => ReqMode::LowRankAccuracy,
+        };
+        RustReqSketch::new(k as usize, rust_mode)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e))
     }
 
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update(data);
-        Ok(())
+    pub fn update(&mut self, value: f64) {
+        self.inner.update(value);
     }
 
     #[napi]
-    pub fn updateBy(&mut self, item: Buffer, count: i64) -> Result<()> {
-        let data: Vec<u8> = item.to_vec();
-        self.inner.update_by(data, count as u64);
-        Ok(())
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        for val in values {
+            self.inner.update(val);
+        }
     }
 
     #[napi]
-    pub fn getEstimate(&self, item: Buffer) -> Result<Option<FrequentItemResult>> {
-        let data: Vec<u8> = item.to_vec();
-        match self.inner.get_estimate(&data) {
-            Some((lower, upper)) => Ok(Some(FrequentItemResult {
-                key: hex::encode(&data),
-                lower_bound: lower as i64,
-                upper_bound: upper as i64,
-            })),
-            None => Ok(None),
-        }
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.inner.quantile(q)
     }
 
     #[napi]
-    pub fn frequentItems(
-        &self,
-        error_type: FrequentItemsErrorType,
-    ) -> Result<Vec<FrequentItemResult>> {
-        let rust_error_type = match error_type {
-            FrequentItemsErrorType::NoFalsePositives => RustErrorType::NoFalsePositives,
-            FrequentItemsErrorType::NoFalseNegatives => RustErrorType::NoFalseNegatives,
-        };
-        let results = self.inner.frequent_items(rust_error_type);
-        Ok(results
-            .into_iter()
-            .map(|(key, lower, upper)| FrequentItemResult {
-                key: hex::encode(&key),
-                lower_bound: lower as i64,
-                upper_bound: upper as i64,
-            })
-            .collect())
+    pub fn quantiles(&self, quantiles: Vec<f64>) -> Vec<f64> {
+        quantiles
+            .iter()
+            .filter_map(|&q| self.inner.quantile(q))
+            .collect()
     }
 
+    /// Merge into a new sketch at the smaller of the two k values
+    ///
+    /// Throws if the modes differ (HRA and LRA sketches cannot be combined).
     #[napi]
-    pub fn merge(&mut self, other: &FrequentItems) -> Result<()> {
+    pub fn mergeWith(&self, other: &ReqSketch) -> Result<ReqSketch> {
         self.inner
             .merge(&other.inner)
+            .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn count(&self) -> i64 {
+        self.inner.n() as i64
+    }
+
+    #[napi]
+    pub fn min(&self) -> Option<f64> {
+        self.inner.min()
+    }
+
+    #[napi]
+    pub fn max(&self) -> Option<f64> {
+        self.inner.max()
+    }
+
+    #[napi]
+    pub fn normalizedRankError(&self) -> f64 {
+        self.inner.normalized_rank_error()
     }
 
     #[napi]
-    pub fn numItems(&self) -> Result<i64> {
-        Ok(self.inner.num_items() as i64)
+    pub fn normalizedRankErrorAt(&self, rank: f64) -> f64 {
+        self.inner.normalized_rank_error_at(rank)
     }
 
     #[napi]
-    pub fn maxSize(&self) -> Result<i64> {
-        Ok(self.inner.max_size() as i64)
+    pub fn isEmpty(&self) -> bool {
+        self.inner.is_empty()
     }
 
     #[napi]
-    pub fn offset(&self) -> Result<i64> {
-        Ok(self.inner.offset() as i64)
+    pub fn toString(&self) -> String {
+        format!(
+            "ReqSketch(count={}, min={:.2}, max={:.2})",
+            self.inner.n(),
+            self.inner.min().unwrap_or(0.0),
+            self.inner.max().unwrap_or(0.0)
+        )
     }
+}
 
-    #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "FrequentItems(maxSize={}, numItems={}, offset={})",
-            self.inner.max_size(),
-            self.inner.num_items(),
-            self.inner.offset()
-        ))
+/// Contiguous run of OpenTelemetry exponential histogram buckets
+#[napi(object)]
+pub struct OtelBuckets {
+    /// Index of the first bucket in `bucketCounts`
+    pub offset: i32,
+    pub bucket_counts: Vec<i64>,
+}
+
+impl From<RustOtelBuckets> for OtelBuckets {
+    fn from(buckets: RustOtelBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            bucket_counts: buckets
+                .bucket_counts
+                .into_iter()
+                .map(|c| c as i64)
+                .collect(),
+        }
     }
 }
 
-// =============================================================================
-// MEMBERSHIP TESTING
-// =============================================================================
+/// OpenTelemetry exponential histogram from `toOtelExponential`
+#[napi(object)]
+pub struct OtelExpoHistogram {
+    pub scale: i32,
+    pub count: i64,
+    /// Estimated sum of all values
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub zero_threshold: f64,
+    pub zero_count: i64,
+    pub positive: OtelBuckets,
+    /// Buckets for negative values, indexed by magnitude
+    pub negative: OtelBuckets,
+}
 
-/// Binary Fuse Filter for probabilistic membership testing
-///
-/// State-of-the-art filter with ~75% better space efficiency than Bloom filters.
-/// This is an immutable data structure - build from a complete set of items.
+/// Converts an exported OpenTelemetry histogram, reporting invalid arguments
+fn otel_histogram(
+    histogram: sketch_oxide::common::Result<RustOtelExpoHistogram>,
+) -> Result<OtelExpoHistogram> {
+    let histogram = histogram.map_err(|e| {
+        Error::new(
+            Status::InvalidArg,
+            format!("OpenTelemetry export failed: {}", e),
+        )
+    })?;
+    Ok(OtelExpoHistogram {
+        scale: histogram.scale,
+        count: histogram.count as i64,
+        sum: histogram.sum,
+        min: histogram.min,
+        max: histogram.max,
+        zero_threshold: histogram.zero_threshold,
+        zero_count: histogram.zero_count as i64,
+        positive: histogram.positive.into(),
+        negative: histogram.negative.into(),
+    })
+}
+
+/// T-Digest for quantile estimation with tail accuracy
 #[napi]
-pub struct BinaryFuseFilter {
-    inner: RustBinaryFuseFilter,
+pub struct TDigest {
+    inner: RustTDigest,
 }
 
 #[napi]
-impl BinaryFuseFilter {
-    /// Create a Binary Fuse Filter from an array of BigInt items
-    #[napi(factory)]
-    pub fn fromItems(items: Vec<BigInt>, bits_per_entry: u8) -> Result<Self> {
-        let u64_items: Vec<u64> = items
-            .into_iter()
-            .map(|bi| {
-                let (_, value, _) = bi.get_u64();
-                value
-            })
-            .collect();
+impl TDigest {
+    #[napi(constructor)]
+    pub fn new(compression: Option<f64>) -> Self {
+        Self {
+            inner: RustTDigest::new(compression.unwrap_or(100.0)),
+        }
+    }
 
-        RustBinaryFuseFilter::from_items(u64_items.into_iter(), bits_per_entry)
-            .map(|inner| BinaryFuseFilter { inner })
+    /// Create a digest that compresses every `bufferSize` raw values
+    ///
+    /// Larger buffers ingest bursts faster at 8 bytes per pending value;
+    /// the default is 5 × compression. Accuracy is largely unaffected.
+    #[napi(factory)]
+    pub fn withBuffer(compression: f64, buffer_size: u32) -> Result<Self> {
+        RustTDigest::with_buffer(compression, buffer_size as usize)
+            .map(|inner| TDigest { inner })
             .map_err(|e| {
                 Error::new(
                     Status::InvalidArg,
-                    format!("BinaryFuseFilter creation failed: {}", e),
+                    format!("TDigest creation failed: {}", e),
                 )
             })
     }
 
-    /// Check if an item might be in the set
     #[napi]
-    pub fn contains(&self, item: BigInt) -> bool {
-        let (_, value, _) = item.get_u64();
-        self.inner.contains(&value)
+    pub fn update(&mut self, value: f64) {
+        self.inner.update(value);
     }
 
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        self.inner.update_batch(&values);
     }
 
+    /// Add a value with a weight; `cdf` ranks by total weight
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn updateWeighted(&mut self, value: f64, weight: f64) {
+        self.inner.update_weighted(value, weight);
     }
 
+    /// Add a value, throwing instead of skipping NaN or an infinity
+    ///
+    /// `update` and `updateBatch` skip non-finite values and count them in
+    /// `rejectedCount()`.
     #[napi]
-    pub fn bitsPerEntry(&self) -> f64 {
-        self.inner.bits_per_entry()
+    pub fn updateStrict(&mut self, value: f64) -> Result<()> {
+        self.inner
+            .try_update(value)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Number of non-finite values skipped by `update` and `updateBatch`
     #[napi]
-    pub fn estimatedFpr(&self) -> f64 {
-        self.inner.estimated_fpr()
+    pub fn rejectedCount(&self) -> i64 {
+        self.inner.rejected_count() as i64
     }
 
     #[napi]
-    pub fn serialize(&self) -> Buffer {
-        Buffer::from(self.inner.serialize())
-    }
-
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustBinaryFuseFilter::deserialize(&data)
-            .map(|inner| BinaryFuseFilter { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
-                )
-            })
+    pub fn subtract(&mut self, value: f64, weight: f64) {
+        self.inner.subtract(value, weight);
     }
 
     #[napi]
-    pub fn toString(&self) -> String {
-        format!(
-            "BinaryFuseFilter(size={}, bits_per_entry={:.1}, fpr={:.4}%)",
-            self.inner.len(),
-            self.inner.bits_per_entry(),
-            self.inner.estimated_fpr() * 100.0
-        )
-    }
-}
-
-/// Bloom Filter for probabilistic membership testing
-///
-/// Classic probabilistic data structure supporting dynamic insertions.
-/// Zero false negatives guaranteed.
-#[napi]
-pub struct BloomFilter {
-    inner: RustBloomFilter,
-}
-
-#[napi]
-impl BloomFilter {
-    #[napi(constructor)]
-    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
-        let fpr = fpr.unwrap_or(0.01);
-        if n == 0 {
-            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
-        }
-        if fpr <= 0.0 || fpr >= 1.0 {
-            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
-        }
-        Ok(Self {
-            inner: RustBloomFilter::new(n as usize, fpr),
-        })
+    pub fn quantile(&mut self, q: f64) -> f64 {
+        self.inner.quantile(q)
     }
 
     #[napi]
-    pub fn insert(&mut self, key: Buffer) {
-        self.inner.insert(&key);
+    pub fn quantiles(&mut self, quantiles: Vec<f64>) -> Vec<f64> {
+        quantiles.iter().map(|&q| self.inner.quantile(q)).collect()
     }
 
     #[napi]
-    pub fn contains(&self, key: Buffer) -> bool {
-        self.inner.contains(&key)
+    pub fn cdf(&mut self, value: f64) -> f64 {
+        self.inner.cdf(value)
     }
 
+    /// Estimated number of values in [low, high], from the CDF at both bounds
     #[napi]
-    pub fn clear(&mut self) {
-        self.inner.clear();
+    pub fn countBetween(&self, low: f64, high: f64) -> f64 {
+        self.inner.count_between(low, high)
     }
 
     #[napi]
-    pub fn mergeWith(&mut self, other: &BloomFilter) -> Result<()> {
-        if self.inner.params() != other.inner.params() {
-            return Err(Error::new(Status::InvalidArg, "Parameters must match"));
-        }
-        self.inner.merge(&other.inner);
-        Ok(())
+    pub fn trimmedMean(&mut self, low: f64, high: f64) -> f64 {
+        self.inner.trimmed_mean(low, high)
     }
 
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn mergeWith(&mut self, other: &TDigest) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Return a new sketch holding both inputs; neither is modified
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn merged(&self, other: &TDigest) -> Result<TDigest> {
+        self.inner
+            .merged(&other.inner)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
-    #[napi]
-    pub fn falsePositiveRate(&self) -> f64 {
-        self.inner.false_positive_rate()
+    /// Merge an array of digests into a new one, independent of array order
+    ///
+    /// # Throws
+    /// - If the array is empty
+    ///
+    /// # Example
+    /// ```javascript
+    /// const total = TDigest.mergeMany([a, b, c]);
+    /// ```
+    #[napi(factory)]
+    pub fn mergeMany(digests: Vec<ClassInstance<TDigest>>) -> Result<TDigest> {
+        let inners: Vec<&RustTDigest> = digests.iter().map(|d| &d.inner).collect();
+        RustTDigest::merge_many(&inners)
+            .map(|inner| TDigest { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn count(&self) -> f64 {
+        self.inner.count()
     }
 
     #[napi]
-    pub fn serialize(&self) -> Buffer {
-        Buffer::from(self.inner.to_bytes())
-    }
-
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustBloomFilter::from_bytes(&data)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    pub fn compression(&self) -> f64 {
+        self.inner.compression()
     }
 
+    /// Raw values buffered before a compression
     #[napi]
-    pub fn toString(&self) -> String {
-        let (n, m, k) = self.inner.params();
-        format!(
-            "BloomFilter(n={}, bits={}, hashes={}, fpr={:.4}%)",
-            n,
-            m,
-            k,
-            self.inner.false_positive_rate() * 100.0
-        )
-    }
-}
-
-/// Blocked Bloom Filter for cache-efficient membership testing
-#[napi]
-pub struct BlockedBloomFilter {
-    inner: RustBlockedBloomFilter,
-}
-
-#[napi]
-impl BlockedBloomFilter {
-    #[napi(constructor)]
-    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
-        let fpr = fpr.unwrap_or(0.01);
-        if n == 0 {
-            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
-        }
-        if fpr <= 0.0 || fpr >= 1.0 {
-            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
-        }
-        Ok(Self {
-            inner: RustBlockedBloomFilter::new(n as usize, fpr),
-        })
+    pub fn bufferSize(&self) -> u32 {
+        self.inner.buffer_size() as u32
     }
 
+    /// Values waiting in the buffer, not yet merged into centroids
     #[napi]
-    pub fn insert(&mut self, key: Buffer) {
-        self.inner.insert(&key);
+    pub fn pending(&self) -> u32 {
+        self.inner.pending() as u32
     }
 
     #[napi]
-    pub fn contains(&self, key: Buffer) -> bool {
-        self.inner.contains(&key)
+    pub fn centroidCount(&self) -> u32 {
+        self.inner.centroid_count() as u32
     }
 
     #[napi]
-    pub fn clear(&mut self) {
-        self.inner.clear();
+    pub fn centroidBudgetUsed(&self) -> f64 {
+        self.inner.centroid_budget_used()
     }
 
+    /// Export as an OpenTelemetry exponential histogram
+    ///
+    /// Fills OTel's base-2^(2^-scale) buckets from the CDF. The conversion is
+    /// lossy: counts carry the sketch's error and values keep only their bucket.
+    ///
+    /// # Arguments
+    /// * `scale` - Largest OTel scale to use, in [-10, 20]
+    /// * `maxBuckets` - Limit on positive and on negative buckets (default: 160);
+    ///   the scale is lowered until both fit, and the result reports it
+    ///
+    /// # Example
+    /// ```javascript
+    /// const { scale, zeroCount, positive, negative } = sketch.toOtelExponential(3);
+    /// ```
     #[napi]
-    pub fn mergeWith(&mut self, other: &BlockedBloomFilter) -> Result<()> {
-        if self.inner.params() != other.inner.params() {
-            return Err(Error::new(Status::InvalidArg, "Parameters must match"));
-        }
-        self.inner.merge(&other.inner);
-        Ok(())
+    pub fn toOtelExponential(
+        &self,
+        scale: i32,
+        max_buckets: Option<u32>,
+    ) -> Result<OtelExpoHistogram> {
+        otel_histogram(self.inner.to_otel_exponential_with_max_buckets(
+            scale,
+            max_buckets.map_or(OTEL_DEFAULT_MAX_BUCKETS, |max| max as usize),
+        ))
     }
 
+    /// Approximate Kolmogorov-Smirnov distance `sup |CDF_a(x) - CDF_b(x)|`
+    ///
+    /// Returns null if either sketch is empty.
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn ksDistance(a: &TDigest, b: &TDigest) -> Option<f64> {
+        tdigest_ks_distance(&a.inner, &b.inner)
     }
 
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn min(&self) -> f64 {
+        self.inner.min()
     }
 
-    #[napi]
-    pub fn falsePositiveRate(&self) -> f64 {
-        self.inner.false_positive_rate()
+    #[napi]
+    pub fn max(&self) -> f64 {
+        self.inner.max()
     }
 
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn isEmpty(&self) -> bool {
+        Sketch::is_empty(&self.inner)
     }
 
     #[napi]
-    pub fn serialize(&self) -> Buffer {
+    pub fn serialize(&mut self) -> Buffer {
         Buffer::from(self.inner.to_bytes())
     }
 
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustBlockedBloomFilter::from_bytes(&data)
+        RustTDigest::from_bytes(&data)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
 
     #[napi]
     pub fn toString(&self) -> String {
-        let (n, num_blocks, k) = self.inner.params();
         format!(
-            "BlockedBloomFilter(n={}, blocks={}, hashes={}, fpr={:.4}%)",
-            n,
-            num_blocks,
-            k,
-            self.inner.false_positive_rate() * 100.0
+            "TDigest(count={:.0}, centroids={}, compression={:.0})",
+            self.inner.count(),
+            self.inner.centroid_count(),
+            self.inner.compression()
         )
     }
 }
 
-/// Counting Bloom Filter with deletion support
+/// Count, sum, min, max and T-Digest quantiles fed by a single update
 #[napi]
-pub struct CountingBloomFilter {
-    inner: RustCountingBloomFilter,
+pub struct StreamSummary {
+    inner: RustStreamSummary,
 }
 
 #[napi]
-impl CountingBloomFilter {
+impl StreamSummary {
     #[napi(constructor)]
-    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
-        let fpr = fpr.unwrap_or(0.01);
-        if n == 0 {
-            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
-        }
-        if fpr <= 0.0 || fpr >= 1.0 {
-            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
+    pub fn new(compression: Option<f64>) -> Self {
+        Self {
+            inner: RustStreamSummary::new(compression.unwrap_or(100.0)),
         }
-        Ok(Self {
-            inner: RustCountingBloomFilter::new(n as usize, fpr),
-        })
-    }
-
-    #[napi]
-    pub fn insert(&mut self, key: Buffer) {
-        self.inner.insert(&key);
     }
 
     #[napi]
-    pub fn remove(&mut self, key: Buffer) -> bool {
-        self.inner.remove(&key)
+    pub fn update(&mut self, value: f64) {
+        self.inner.update(value);
     }
 
     #[napi]
-    pub fn contains(&self, key: Buffer) -> bool {
-        self.inner.contains(&key)
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        for value in values {
+            self.inner.update(value);
+        }
     }
 
     #[napi]
-    pub fn countEstimate(&self, key: Buffer) -> u8 {
-        self.inner.count_estimate(&key)
+    pub fn count(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.count()))
     }
 
     #[napi]
-    pub fn clear(&mut self) {
-        self.inner.clear();
+    pub fn sum(&self) -> f64 {
+        self.inner.sum()
     }
 
     #[napi]
-    pub fn mergeWith(&mut self, other: &CountingBloomFilter) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    pub fn min(&self) -> f64 {
+        self.inner.min()
     }
 
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn max(&self) -> f64 {
+        self.inner.max()
     }
 
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn mean(&self) -> Option<f64> {
+        self.inner.mean()
     }
 
     #[napi]
-    pub fn hasOverflow(&self) -> bool {
-        self.inner.has_overflow()
+    pub fn quantile(&mut self, q: f64) -> f64 {
+        self.inner.quantile(q)
     }
 
     #[napi]
-    pub fn falsePositiveRate(&self) -> f64 {
-        self.inner.false_positive_rate()
+    pub fn mergeWith(&mut self, other: &StreamSummary) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn isEmpty(&self) -> bool {
+        Sketch::is_empty(&self.inner)
     }
 
     #[napi]
     pub fn serialize(&self) -> Buffer {
-        Buffer::from(self.inner.to_bytes())
+        Buffer::from(Sketch::serialize(&self.inner))
     }
 
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustCountingBloomFilter::from_bytes(&data)
+        <RustStreamSummary as Sketch>::deserialize(&data)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
@@ -2356,187 +5111,189 @@ impl CountingBloomFilter {
     #[napi]
     pub fn toString(&self) -> String {
         format!(
-            "CountingBloomFilter(count={}, fpr={:.4}%)",
-            self.inner.len(),
-            self.inner.false_positive_rate() * 100.0
+            "StreamSummary(count={}, sum={:.2}, min={:.2}, max={:.2})",
+            self.inner.count(),
+            self.inner.sum(),
+            self.inner.min(),
+            self.inner.max()
         )
     }
 }
 
-/// Cuckoo Filter for membership testing with deletions
+/// Normalized rank interval from `KllSketch.rankWithBounds`
+#[napi(object)]
+pub struct RankBounds {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// KLL Sketch for quantile estimation (Karnin 2016)
 #[napi]
-pub struct CuckooFilter {
-    inner: RustCuckooFilter,
+pub struct KllSketch {
+    inner: RustKllSketch,
 }
 
 #[napi]
-impl CuckooFilter {
+impl KllSketch {
     #[napi(constructor)]
-    pub fn new(capacity: u32) -> Result<Self> {
-        RustCuckooFilter::new(capacity as usize)
+    pub fn new(k: Option<u32>) -> Result<Self> {
+        RustKllSketch::new(k.unwrap_or(200) as u16)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
     #[napi]
-    pub fn insert(&mut self, key: Buffer) -> Result<()> {
-        self.inner
-            .insert(&key)
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    pub fn update(&mut self, value: f64) {
+        self.inner.update(value);
     }
 
     #[napi]
-    pub fn remove(&mut self, key: Buffer) -> bool {
-        self.inner.remove(&key)
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        for val in values {
+            self.inner.update(val);
+        }
     }
 
+    /// Add `weight` copies of a value; `rank` is relative to total weight
     #[napi]
-    pub fn contains(&self, key: Buffer) -> bool {
-        self.inner.contains(&key)
+    pub fn updateWeighted(&mut self, value: f64, weight: i64) -> Result<()> {
+        if weight < 0 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "weight must be non-negative".to_string(),
+            ));
+        }
+        self.inner.update_weighted(value, weight as u64);
+        Ok(())
     }
 
     #[napi]
-    pub fn clear(&mut self) {
-        self.inner.clear();
+    pub fn quantile(&mut self, rank: f64) -> Option<f64> {
+        self.inner.quantile(rank)
     }
 
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn quantiles(&mut self, ranks: Vec<f64>) -> Vec<f64> {
+        ranks
+            .iter()
+            .filter_map(|&r| self.inner.quantile(r))
+            .collect()
     }
 
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn rank(&mut self, value: f64) -> f64 {
+        self.inner.rank(value)
     }
 
+    /// Rank of a value ± the sketch's normalized rank error, clamped to [0, 1]
     #[napi]
-    pub fn capacity(&self) -> u32 {
-        self.inner.capacity() as u32
+    pub fn rankWithBounds(&self, value: f64) -> RankBounds {
+        let (lower, upper) = self.inner.rank_with_bounds(value);
+        RankBounds { lower, upper }
     }
 
     #[napi]
-    pub fn loadFactor(&self) -> f64 {
-        self.inner.load_factor()
+    pub fn mergeWith(&mut self, other: &KllSketch) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
+    /// Return a new sketch holding both inputs; neither is modified
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn merged(&self, other: &KllSketch) -> Result<KllSketch> {
+        self.inner
+            .merged(&other.inner)
+            .map(|inner| Self { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
     #[napi]
-    pub fn serialize(&self) -> Buffer {
-        Buffer::from(self.inner.to_bytes())
-    }
-
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustCuckooFilter::from_bytes(&data)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    pub fn k(&self) -> u32 {
+        self.inner.k() as u32
     }
 
     #[napi]
-    pub fn toString(&self) -> String {
-        format!(
-            "CuckooFilter(count={}, capacity={}, load={:.1}%)",
-            self.inner.len(),
-            self.inner.capacity(),
-            self.inner.load_factor() * 100.0
-        )
+    pub fn count(&self) -> i64 {
+        self.inner.count() as i64
     }
-}
-
-/// Ribbon Filter for space-efficient membership testing
-#[napi]
-pub struct RibbonFilter {
-    inner: RustRibbonFilter,
-}
 
-#[napi]
-impl RibbonFilter {
-    #[napi(constructor)]
-    pub fn new(n: u32, fpr: Option<f64>) -> Result<Self> {
-        let fpr = fpr.unwrap_or(0.01);
-        if n == 0 {
-            return Err(Error::new(Status::InvalidArg, "n must be > 0"));
-        }
-        if fpr <= 0.0 || fpr >= 1.0 {
-            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
-        }
-        Ok(Self {
-            inner: RustRibbonFilter::new(n as usize, fpr),
-        })
+    #[napi]
+    pub fn min(&self) -> f64 {
+        self.inner.min()
     }
 
     #[napi]
-    pub fn insert(&mut self, key: Buffer) -> Result<()> {
-        if self.inner.is_finalized() {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "Cannot insert after finalization",
-            ));
-        }
-        self.inner.insert(&key);
-        Ok(())
+    pub fn max(&self) -> f64 {
+        self.inner.max()
     }
 
     #[napi]
-    pub fn build(&mut self) {
-        self.inner.finalize();
+    pub fn normalizedRankError(&self) -> f64 {
+        self.inner.normalized_rank_error()
     }
 
     #[napi]
-    pub fn contains(&self, key: Buffer) -> Result<bool> {
-        if !self.inner.is_finalized() {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "Must call build() before querying",
-            ));
-        }
-        Ok(self.inner.contains(&key))
+    pub fn normalizedRankErrorAt(&self, rank: f64) -> f64 {
+        self.inner.normalized_rank_error_at(rank)
     }
 
     #[napi]
-    pub fn isFinalized(&self) -> bool {
-        self.inner.is_finalized()
+    pub fn numRetained(&self) -> u32 {
+        self.inner.num_retained() as u32
     }
 
     #[napi]
-    pub fn len(&self) -> u32 {
-        self.inner.len() as u32
+    pub fn effectiveK(&self) -> u32 {
+        self.inner.effective_k() as u32
     }
 
+    /// Export as an OpenTelemetry exponential histogram
+    ///
+    /// Fills OTel's base-2^(2^-scale) buckets from the CDF. The conversion is
+    /// lossy: counts carry the sketch's error and values keep only their bucket.
+    ///
+    /// # Arguments
+    /// * `scale` - Largest OTel scale to use, in [-10, 20]
+    /// * `maxBuckets` - Limit on positive and on negative buckets (default: 160);
+    ///   the scale is lowered until both fit, and the result reports it
+    ///
+    /// # Example
+    /// ```javascript
+    /// const { scale, zeroCount, positive, negative } = sketch.toOtelExponential(3);
+    /// ```
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    pub fn toOtelExponential(
+        &self,
+        scale: i32,
+        max_buckets: Option<u32>,
+    ) -> Result<OtelExpoHistogram> {
+        otel_histogram(self.inner.to_otel_exponential_with_max_buckets(
+            scale,
+            max_buckets.map_or(OTEL_DEFAULT_MAX_BUCKETS, |max| max as usize),
+        ))
     }
 
+    /// Approximate Kolmogorov-Smirnov distance `sup |CDF_a(x) - CDF_b(x)|`
+    ///
+    /// Returns null if either sketch is empty.
     #[napi]
-    pub fn falsePositiveRate(&self) -> f64 {
-        self.inner.false_positive_rate()
+    pub fn ksDistance(a: &KllSketch, b: &KllSketch) -> Option<f64> {
+        ks_distance(&a.inner, &b.inner)
     }
 
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn isEmpty(&self) -> bool {
+        Sketch::is_empty(&self.inner)
     }
 
     #[napi]
-    pub fn serialize(&self) -> Result<Buffer> {
-        if !self.inner.is_finalized() {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "Must call build() before serialization",
-            ));
-        }
-        Ok(Buffer::from(self.inner.to_bytes()))
+    pub fn serialize(&mut self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
     }
 
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustRibbonFilter::from_bytes(&data)
+        RustKllSketch::from_bytes(&data)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
@@ -2544,828 +5301,874 @@ impl RibbonFilter {
     #[napi]
     pub fn toString(&self) -> String {
         format!(
-            "RibbonFilter(count={}, finalized={}, fpr={:.4}%)",
-            self.inner.len(),
-            self.inner.is_finalized(),
-            self.inner.false_positive_rate() * 100.0
+            "KllSketch(k={}, count={}, min={:.2}, max={:.2})",
+            self.inner.k(),
+            self.inner.count(),
+            self.inner.min(),
+            self.inner.max()
         )
     }
 }
 
-/// StableBloomFilter for membership testing with decay mechanism
+/// Greenwald-Khanna sketch with a deterministic rank error bound
 ///
-/// A Bloom filter variant that decays counts over time, useful for tracking
-/// recently-seen items while removing old ones without explicit deletion.
+/// Every quantile and rank answer is within `epsilon * count` ranks of the
+/// truth, whatever the input order.
 ///
 /// # Example
 /// ```javascript
-/// const { StableBloomFilter } = require('@sketch-oxide/node');
-/// const sbf = new StableBloomFilter(1000, 0.01);
-/// sbf.insert(Buffer.from('item1'));
-/// console.log(sbf.contains(Buffer.from('item1'))); // true
+/// const gk = new GkSketch(0.01);
+/// gk.updateBatch([5, 1, 9, 3]);
+/// console.log(gk.quantile(0.5));
 /// ```
 #[napi]
-pub struct StableBloomFilter {
-    inner: RustStableBloomFilter,
+pub struct GkSketch {
+    inner: RustGkSketch,
 }
 
 #[napi]
-impl StableBloomFilter {
-    /// Create a new StableBloomFilter
-    ///
-    /// # Arguments
-    /// * `expected_items` - Expected number of items to store
-    /// * `fpr` - Target false positive rate (0 < fpr < 1)
+impl GkSketch {
+    /// Create a sketch with rank error bound `epsilon`, in (0, 1)
     #[napi(constructor)]
-    pub fn new(expected_items: u32, fpr: Option<f64>) -> Result<Self> {
-        let fpr = fpr.unwrap_or(0.01);
-        if expected_items == 0 {
-            return Err(Error::new(Status::InvalidArg, "expected_items must be > 0"));
-        }
-        if fpr <= 0.0 || fpr >= 1.0 {
-            return Err(Error::new(Status::InvalidArg, "fpr must be in (0, 1)"));
-        }
-        RustStableBloomFilter::new(expected_items as usize, fpr)
+    pub fn new(epsilon: f64) -> Result<Self> {
+        RustGkSketch::new(epsilon)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
-    /// Insert a key into the filter
-    ///
-    /// # Arguments
-    /// * `key` - Binary data to insert
     #[napi]
-    pub fn insert(&mut self, key: Buffer) -> Result<()> {
-        self.inner.insert(&key);
-        Ok(())
+    pub fn update(&mut self, value: f64) {
+        self.inner.update(value);
     }
 
-    /// Check if a key might be in the set
-    ///
-    /// # Returns
-    /// True if key might be present, False if definitely not present
     #[napi]
-    pub fn contains(&self, key: Buffer) -> bool {
-        self.inner.contains(&key)
+    pub fn updateBatch(&mut self, values: Vec<f64>) {
+        for val in values {
+            self.inner.update(val);
+        }
     }
 
-    /// Get the count estimate for a key
-    ///
-    /// # Arguments
-    /// * `key` - Binary data to check
-    ///
-    /// # Returns
-    /// Counter value (0-255 depending on counter_bits)
     #[napi]
-    pub fn getCount(&self, key: Buffer) -> u32 {
-        self.inner.get_count(&key) as u32
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.inner.quantile(q)
     }
 
-    /// Get the number of counters in this filter
     #[napi]
-    pub fn numCounters(&self) -> u32 {
-        self.inner.num_counters() as u32
+    pub fn quantiles(&self, qs: Vec<f64>) -> Vec<f64> {
+        qs.iter().filter_map(|&q| self.inner.quantile(q)).collect()
     }
 
-    /// Get the number of hash functions used
     #[napi]
-    pub fn numHashes(&self) -> u32 {
-        self.inner.num_hashes() as u32
+    pub fn rank(&self, value: f64) -> f64 {
+        self.inner.rank(value)
     }
 
-    /// Get the number of decrements performed
     #[napi]
-    pub fn decrementCount(&self) -> u32 {
-        self.inner.decrement_count() as u32
+    pub fn mergeWith(&mut self, other: &GkSketch) -> Result<()> {
+        Mergeable::merge(&mut self.inner, &other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
     }
 
-    /// Get the fill ratio of the filter (0.0 to 1.0)
     #[napi]
-    pub fn fillRatio(&self) -> f64 {
-        self.inner.fill_ratio()
+    pub fn epsilon(&self) -> f64 {
+        self.inner.epsilon()
     }
 
-    /// Clear all data from the filter
     #[napi]
-    pub fn clear(&mut self) {
-        self.inner.clear();
+    pub fn count(&self) -> i64 {
+        self.inner.count() as i64
     }
 
-    /// Get memory usage in bytes
     #[napi]
-    pub fn memoryUsage(&self) -> u32 {
-        self.inner.memory_usage() as u32
+    pub fn numTuples(&self) -> u32 {
+        self.inner.num_tuples() as u32
+    }
+
+    #[napi]
+    pub fn isEmpty(&self) -> bool {
+        Sketch::is_empty(&self.inner)
     }
 
-    /// Serialize the filter to binary format
     #[napi]
     pub fn serialize(&self) -> Buffer {
         Buffer::from(self.inner.to_bytes())
     }
 
-    /// Deserialize a filter from binary format
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustStableBloomFilter::from_bytes(&data)
+        RustGkSketch::from_bytes(&data)
             .map(|inner| Self { inner })
             .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
     }
 
-    /// Get a string representation
     #[napi]
     pub fn toString(&self) -> String {
         format!(
-            "StableBloomFilter(counters={}, hashes={}, fill={:.2}%)",
-            self.inner.num_counters(),
-            self.inner.num_hashes(),
-            self.inner.fill_ratio() * 100.0
+            "GkSketch(epsilon={}, count={}, tuples={})",
+            self.inner.epsilon(),
+            self.inner.count(),
+            self.inner.num_tuples()
         )
     }
 }
 
-// =============================================================================
-// QUANTILE ESTIMATION
-// =============================================================================
+/// Quantile estimate with the retained samples bracketing it
+#[napi(object)]
+pub struct SplineQueryBounds {
+    pub lower: i64,
+    pub estimate: i64,
+    pub upper: i64,
+}
 
-/// DDSketch for quantile estimation with relative error guarantees
-#[napi(js_name = "DDSketch")]
-pub struct DDSketch {
-    inner: RustDDSketch,
+/// SplineSketch for quantile estimation
+///
+/// A space-efficient sketch for estimating quantiles of a data stream.
+/// Uses spline-based interpolation for smooth quantile estimates.
+///
+/// # Example
+/// ```javascript
+/// const { SplineSketch } = require('@sketch-oxide/node');
+/// const sketch = new SplineSketch(256);
+/// for (let i = 1; i <= 1000; i++) {
+///   sketch.insert(i, 1.0);
+/// }
+/// console.log(sketch.query(0.5)); // ~500
+/// ```
+#[napi]
+pub struct SplineSketch {
+    inner: RustSplineSketch,
 }
 
 #[napi]
-impl DDSketch {
+impl SplineSketch {
+    /// Create a new SplineSketch
+    ///
+    /// # Arguments
+    /// * `max_samples` - Maximum number of samples to retain
     #[napi(constructor)]
-    pub fn new(relative_accuracy: f64) -> Result<Self> {
-        RustDDSketch::new(relative_accuracy)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    pub fn new(max_samples: Option<u32>) -> Self {
+        let max_samples = max_samples.unwrap_or(256) as usize;
+        Self {
+            inner: RustSplineSketch::new(max_samples),
+        }
     }
 
+    /// Add a value to the sketch
+    ///
+    /// # Arguments
+    /// * `value` - The value to add
+    /// * `weight` - The weight of the value (default 1.0)
     #[napi]
-    pub fn update(&mut self, value: f64) {
-        Sketch::update(&mut self.inner, &value);
+    pub fn insert(&mut self, value: i64, weight: Option<f64>) -> Result<()> {
+        let weight = weight.unwrap_or(1.0);
+        self.inner.update(value as u64, weight);
+        Ok(())
     }
 
+    /// Query a quantile value
+    ///
+    /// # Arguments
+    /// * `quantile` - The quantile to query (0.0 to 1.0)
+    ///
+    /// # Returns
+    /// The estimated value at that quantile
     #[napi]
-    pub fn updateBatch(&mut self, values: Vec<f64>) {
-        for val in values {
-            Sketch::update(&mut self.inner, &val);
-        }
+    pub fn query(&self, quantile: f64) -> i64 {
+        self.inner.query(quantile) as i64
     }
 
+    /// Query a quantile with lower and upper bounds
+    ///
+    /// The bounds are the retained samples on either side of the target
+    /// rank until the sketch first compresses or takes a weighted update;
+    /// after that they are the observed minimum and maximum.
+    ///
+    /// # Throws
+    /// - If the sketch is empty
     #[napi]
-    pub fn quantile(&self, q: f64) -> Option<f64> {
-        self.inner.quantile(q)
+    pub fn queryWithBounds(&self, quantile: f64) -> Result<SplineQueryBounds> {
+        if self.inner.sample_count() == 0 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Cannot query empty sketch".to_string(),
+            ));
+        }
+        let (lower, estimate, upper) = self.inner.query_with_bounds(quantile);
+        Ok(SplineQueryBounds {
+            lower: lower as i64,
+            estimate: estimate as i64,
+            upper: upper as i64,
+        })
     }
 
+    /// Get a bound on the normalized rank error (1.0 once compressed)
     #[napi]
-    pub fn quantiles(&self, quantiles: Vec<f64>) -> Vec<f64> {
-        quantiles
-            .iter()
-            .filter_map(|&q| self.inner.quantile(q))
-            .collect()
+    pub fn maxError(&self) -> f64 {
+        self.inner.max_error()
     }
 
+    /// Get the minimum value seen
     #[napi]
-    pub fn mergeWith(&mut self, other: &DDSketch) -> Result<()> {
-        Mergeable::merge(&mut self.inner, &other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    pub fn min(&self) -> Option<i64> {
+        self.inner.min().map(|v| v as i64)
     }
 
+    /// Get the maximum value seen
     #[napi]
-    pub fn count(&self) -> i64 {
-        self.inner.count() as i64
+    pub fn max(&self) -> Option<i64> {
+        self.inner.max().map(|v| v as i64)
     }
 
+    /// Get the number of samples retained
     #[napi]
-    pub fn min(&self) -> Option<f64> {
-        self.inner.min()
+    pub fn sampleCount(&self) -> u32 {
+        self.inner.sample_count() as u32
     }
 
+    /// Get the maximum number of samples
     #[napi]
-    pub fn max(&self) -> Option<f64> {
-        self.inner.max()
+    pub fn maxSamples(&self) -> u32 {
+        self.inner.max_samples() as u32
     }
 
+    /// Get the total weight of all values
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        Sketch::is_empty(&self.inner)
+    pub fn totalWeight(&self) -> f64 {
+        self.inner.total_weight()
     }
 
+    /// Merge another SplineSketch into this one
+    ///
+    /// # Arguments
+    /// * `other` - Another SplineSketch to merge
+    ///
+    /// # Errors
+    /// Throws if the sketches have different maxSamples
     #[napi]
-    pub fn serialize(&self) -> Buffer {
-        Buffer::from(Sketch::serialize(&self.inner))
+    pub fn mergeWith(&mut self, other: &SplineSketch) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
-    #[napi(factory, ts_return_type = "DDSketch")]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustDDSketch::deserialize(&data)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    /// Reset the sketch to empty state
+    #[napi]
+    pub fn reset(&mut self) {
+        self.inner.reset();
     }
 
+    /// Get a string representation
     #[napi]
     pub fn toString(&self) -> String {
+        let min_str = self
+            .inner
+            .min()
+            .map_or("N/A".to_string(), |v| format!("{}", v));
+        let max_str = self
+            .inner
+            .max()
+            .map_or("N/A".to_string(), |v| format!("{}", v));
         format!(
-            "DDSketch(count={}, min={:.2}, max={:.2})",
-            self.inner.count(),
-            self.inner.min().unwrap_or(0.0),
-            self.inner.max().unwrap_or(0.0)
+            "SplineSketch(samples={}/{}, min={}, max={})",
+            self.inner.sample_count(),
+            self.inner.max_samples(),
+            min_str,
+            max_str
         )
     }
 }
 
-/// REQ Sketch mode
-#[napi]
-pub enum ReqSketchMode {
-    HighRankAccuracy,
-    LowRankAccuracy,
-}
+// New bindings for similarity, sampling, streaming, and additional frequency algorithms
+// To be merged into lib.rs
+
+// =============================================================================
+// SIMILARITY ALGORITHMS
+// =============================================================================
+
+use sketch_oxide::similarity::MinHash as RustMinHash;
+use sketch_oxide::similarity::SimHash as RustSimHash;
 
-/// REQ Sketch for streaming quantile estimation (PODS 2021)
+/// MinHash sketch for Jaccard similarity estimation
+///
+/// Approximates the Jaccard similarity |A ∩ B| / |A ∪ B| using k hash functions.
+/// Standard error is approximately 1/sqrt(k).
 #[napi]
-pub struct ReqSketch {
-    inner: RustReqSketch,
+pub struct MinHash {
+    inner: RustMinHash,
 }
 
 #[napi]
-impl ReqSketch {
+impl MinHash {
+    /// Create a new MinHash sketch with specified number of permutations
     #[napi(constructor)]
-    pub fn new(k: u32, mode: ReqSketchMode) -> Result<Self> {
-        let rust_mode = match mode {
-            ReqSketchMode::HighRankAccuracy => ReqMode::HighRankAccuracy,
-            ReqSketchMode::LowRankAccuracy => ReqMode::LowRankAccuracy,
-        };
-        RustReqSketch::new(k as usize, rust_mode)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, e))
-    }
-
-    #[napi]
-    pub fn update(&mut self, value: f64) {
-        self.inner.update(value);
-    }
-
-    #[napi]
-    pub fn updateBatch(&mut self, values: Vec<f64>) {
-        for val in values {
-            self.inner.update(val);
-        }
+    pub fn new(num_perm: u32) -> Result<Self> {
+        RustMinHash::new(num_perm as usize)
+            .map(|inner| MinHash { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("MinHash creation failed: {}", e),
+                )
+            })
     }
 
+    /// Add an item to the set
     #[napi]
-    pub fn quantile(&self, q: f64) -> Option<f64> {
-        self.inner.quantile(q)
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        self.inner.update(&item.to_vec());
+        Ok(())
     }
 
+    /// Estimate Jaccard similarity with another MinHash
     #[napi]
-    pub fn quantiles(&self, quantiles: Vec<f64>) -> Vec<f64> {
-        quantiles
-            .iter()
-            .filter_map(|&q| self.inner.quantile(q))
-            .collect()
+    pub fn jaccardSimilarity(&self, other: &MinHash) -> Result<f64> {
+        self.inner
+            .jaccard_similarity(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Similarity failed: {}", e)))
     }
 
+    /// Merge another MinHash (union operation)
     #[napi]
-    pub fn mergeWith(&self, other: &ReqSketch) -> Result<ReqSketch> {
+    pub fn merge(&mut self, other: &MinHash) -> Result<()> {
         self.inner
             .merge(&other.inner)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, e))
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Get the number of permutations
     #[napi]
-    pub fn count(&self) -> i64 {
-        self.inner.n() as i64
+    pub fn numPerm(&self) -> Result<u32> {
+        Ok(self.inner.num_perm() as u32)
     }
 
+    /// Check if empty
     #[napi]
-    pub fn min(&self) -> Option<f64> {
-        self.inner.min()
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
     }
 
+    /// Serialize to binary format
     #[napi]
-    pub fn max(&self) -> Option<f64> {
-        self.inner.max()
+    pub fn serialize(&self) -> Result<Buffer> {
+        Ok(Buffer::from(self.inner.serialize()))
     }
 
-    #[napi]
-    pub fn isEmpty(&self) -> bool {
-        self.inner.is_empty()
+    /// Deserialize from binary format
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustMinHash::deserialize(&data)
+            .map(|inner| MinHash { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
     }
 
+    /// Get string representation
     #[napi]
-    pub fn toString(&self) -> String {
-        format!(
-            "ReqSketch(count={}, min={:.2}, max={:.2})",
-            self.inner.n(),
-            self.inner.min().unwrap_or(0.0),
-            self.inner.max().unwrap_or(0.0)
-        )
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "MinHash(numPerm={}, isEmpty={})",
+            self.inner.num_perm(),
+            self.inner.is_empty()
+        ))
     }
 }
 
-/// T-Digest for quantile estimation with tail accuracy
+/// SimHash sketch for near-duplicate detection
 #[napi]
-pub struct TDigest {
-    inner: RustTDigest,
+pub struct SimHash {
+    inner: RustSimHash,
 }
 
 #[napi]
-impl TDigest {
+impl SimHash {
+    /// Create a new SimHash sketch
     #[napi(constructor)]
-    pub fn new(compression: Option<f64>) -> Self {
-        Self {
-            inner: RustTDigest::new(compression.unwrap_or(100.0)),
-        }
-    }
-
-    #[napi]
-    pub fn update(&mut self, value: f64) {
-        self.inner.update(value);
-    }
-
-    #[napi]
-    pub fn updateBatch(&mut self, values: Vec<f64>) {
-        self.inner.update_batch(&values);
-    }
-
-    #[napi]
-    pub fn quantile(&mut self, q: f64) -> f64 {
-        self.inner.quantile(q)
-    }
-
-    #[napi]
-    pub fn quantiles(&mut self, quantiles: Vec<f64>) -> Vec<f64> {
-        quantiles.iter().map(|&q| self.inner.quantile(q)).collect()
-    }
-
-    #[napi]
-    pub fn cdf(&mut self, value: f64) -> f64 {
-        self.inner.cdf(value)
+    pub fn new() -> Result<Self> {
+        Ok(SimHash {
+            inner: RustSimHash::new(),
+        })
     }
 
+    /// Add a feature to the sketch
     #[napi]
-    pub fn trimmedMean(&mut self, low: f64, high: f64) -> f64 {
-        self.inner.trimmed_mean(low, high)
+    pub fn update(&mut self, feature: Buffer) -> Result<()> {
+        self.inner.update(&feature.to_vec());
+        Ok(())
     }
 
+    /// Add a weighted feature
     #[napi]
-    pub fn mergeWith(&mut self, other: &TDigest) -> Result<()> {
-        Mergeable::merge(&mut self.inner, &other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    pub fn updateWeighted(&mut self, feature: Buffer, weight: i64) -> Result<()> {
+        self.inner.update_weighted(&feature.to_vec(), weight);
+        Ok(())
     }
 
+    /// Get the 64-bit fingerprint
     #[napi]
-    pub fn count(&self) -> f64 {
-        self.inner.count()
+    pub fn fingerprint(&mut self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.fingerprint()))
     }
 
+    /// Compute Hamming distance to another SimHash (0-64)
     #[napi]
-    pub fn compression(&self) -> f64 {
-        self.inner.compression()
+    pub fn hammingDistance(&mut self, other: &mut SimHash) -> Result<u32> {
+        Ok(self.inner.hamming_distance(&mut other.inner))
     }
 
+    /// Compute similarity as (64 - hammingDistance) / 64
     #[napi]
-    pub fn centroidCount(&self) -> u32 {
-        self.inner.centroid_count() as u32
+    pub fn similarity(&mut self, other: &mut SimHash) -> Result<f64> {
+        Ok(self.inner.similarity(&mut other.inner))
     }
 
+    /// Get number of features added
     #[napi]
-    pub fn min(&self) -> f64 {
-        self.inner.min()
+    pub fn len(&self) -> Result<u32> {
+        Ok(self.inner.len() as u32)
     }
 
+    /// Check if empty
     #[napi]
-    pub fn max(&self) -> f64 {
-        self.inner.max()
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
     }
 
+    /// Merge another SimHash
     #[napi]
-    pub fn isEmpty(&self) -> bool {
-        Sketch::is_empty(&self.inner)
+    pub fn merge(&mut self, other: &SimHash) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Serialize to binary format
     #[napi]
-    pub fn serialize(&mut self) -> Buffer {
-        Buffer::from(self.inner.to_bytes())
+    pub fn serialize(&mut self) -> Result<Buffer> {
+        Ok(Buffer::from(self.inner.to_bytes()))
     }
 
+    /// Deserialize from binary format
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustTDigest::from_bytes(&data)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+        RustSimHash::from_bytes(&data)
+            .map(|inner| SimHash { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
     }
 
+    /// Get string representation
     #[napi]
-    pub fn toString(&self) -> String {
-        format!(
-            "TDigest(count={:.0}, centroids={}, compression={:.0})",
-            self.inner.count(),
-            self.inner.centroid_count(),
-            self.inner.compression()
-        )
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "SimHash(features={}, isEmpty={})",
+            self.inner.len(),
+            self.inner.is_empty()
+        ))
     }
 }
 
-/// KLL Sketch for quantile estimation (Karnin 2016)
+// =============================================================================
+// SAMPLING ALGORITHMS
+// =============================================================================
+
+use sketch_oxide::sampling::ReservoirSampling as RustReservoirSampling;
+use sketch_oxide::sampling::VarOptSampling as RustVarOptSampling;
+
+/// Reservoir Sampling for uniform random samples from streams
 #[napi]
-pub struct KllSketch {
-    inner: RustKllSketch,
+pub struct ReservoirSampling {
+    inner: RustReservoirSampling<Vec<u8>>,
 }
 
 #[napi]
-impl KllSketch {
+impl ReservoirSampling {
+    /// Create a new Reservoir Sampling instance
     #[napi(constructor)]
-    pub fn new(k: Option<u32>) -> Result<Self> {
-        RustKllSketch::new(k.unwrap_or(200) as u16)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
-    }
-
-    #[napi]
-    pub fn update(&mut self, value: f64) {
-        self.inner.update(value);
+    pub fn new(k: u32) -> Result<Self> {
+        RustReservoirSampling::new(k as usize)
+            .map(|inner| ReservoirSampling { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("ReservoirSampling creation failed: {}", e),
+                )
+            })
     }
 
-    #[napi]
-    pub fn updateBatch(&mut self, values: Vec<f64>) {
-        for val in values {
-            self.inner.update(val);
-        }
+    /// Create with seed for reproducibility
+    #[napi(factory)]
+    pub fn withSeed(k: u32, seed: BigInt) -> Result<Self> {
+        let (_, seed_val, _) = seed.get_u64();
+        RustReservoirSampling::with_seed(k as usize, seed_val)
+            .map(|inner| ReservoirSampling { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("ReservoirSampling creation failed: {}", e),
+                )
+            })
     }
 
+    /// Add an item to the stream
     #[napi]
-    pub fn quantile(&mut self, rank: f64) -> Option<f64> {
-        self.inner.quantile(rank)
+    pub fn update(&mut self, item: Buffer) -> Result<()> {
+        self.inner.update(item.to_vec());
+        Ok(())
     }
 
+    /// Get the current sample
     #[napi]
-    pub fn quantiles(&mut self, ranks: Vec<f64>) -> Vec<f64> {
-        ranks
+    pub fn sample(&self) -> Result<Vec<Buffer>> {
+        Ok(self
+            .inner
+            .sample()
             .iter()
-            .filter_map(|&r| self.inner.quantile(r))
-            .collect()
+            .map(|v| Buffer::from(v.clone()))
+            .collect())
     }
 
+    /// Check if empty
     #[napi]
-    pub fn rank(&mut self, value: f64) -> f64 {
-        self.inner.rank(value)
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
     }
 
+    /// Get current sample size
     #[napi]
-    pub fn mergeWith(&mut self, other: &KllSketch) -> Result<()> {
-        Mergeable::merge(&mut self.inner, &other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    pub fn len(&self) -> Result<u32> {
+        Ok(self.inner.len() as u32)
     }
 
+    /// Get maximum capacity
     #[napi]
-    pub fn k(&self) -> u32 {
-        self.inner.k() as u32
+    pub fn capacity(&self) -> Result<u32> {
+        Ok(self.inner.capacity() as u32)
     }
 
+    /// Get total items seen
     #[napi]
-    pub fn count(&self) -> i64 {
-        self.inner.count() as i64
+    pub fn count(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.count()))
     }
 
+    /// Get inclusion probability
     #[napi]
-    pub fn min(&self) -> f64 {
-        self.inner.min()
+    pub fn inclusionProbability(&self) -> Result<f64> {
+        Ok(self.inner.inclusion_probability())
     }
 
+    /// Clear the reservoir
     #[napi]
-    pub fn max(&self) -> f64 {
-        self.inner.max()
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
+        Ok(())
     }
 
+    /// Merge another reservoir sample
     #[napi]
-    pub fn normalizedRankError(&self) -> f64 {
-        self.inner.normalized_rank_error()
+    pub fn merge(&mut self, other: &ReservoirSampling) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Get string representation
     #[napi]
-    pub fn numRetained(&self) -> u32 {
-        self.inner.num_retained() as u32
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "ReservoirSampling(capacity={}, len={}, count={})",
+            self.inner.capacity(),
+            self.inner.len(),
+            self.inner.count()
+        ))
     }
+}
 
-    #[napi]
-    pub fn isEmpty(&self) -> bool {
-        Sketch::is_empty(&self.inner)
-    }
+/// Weighted item in a VarOpt sample
+#[napi(object)]
+pub struct WeightedSampleItem {
+    pub item: Buffer,
+    pub weight: f64,
+    pub adjusted_weight: f64,
+}
 
-    #[napi]
-    pub fn serialize(&mut self) -> Buffer {
-        Buffer::from(self.inner.to_bytes())
+/// VarOpt Sampling for variance-optimal weighted samples
+#[napi]
+pub struct VarOptSampling {
+    inner: RustVarOptSampling<Vec<u8>>,
+}
+
+#[napi]
+impl VarOptSampling {
+    /// Create a new VarOpt Sampling instance
+    #[napi(constructor)]
+    pub fn new(k: u32) -> Result<Self> {
+        RustVarOptSampling::new(k as usize)
+            .map(|inner| VarOptSampling { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("VarOptSampling creation failed: {}", e),
+                )
+            })
     }
 
+    /// Create with seed for reproducibility
     #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustKllSketch::from_bytes(&data)
-            .map(|inner| Self { inner })
-            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    pub fn withSeed(k: u32, seed: BigInt) -> Result<Self> {
+        let (_, seed_val, _) = seed.get_u64();
+        RustVarOptSampling::with_seed(k as usize, seed_val)
+            .map(|inner| VarOptSampling { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("VarOptSampling creation failed: {}", e),
+                )
+            })
     }
 
+    /// Add a weighted item
     #[napi]
-    pub fn toString(&self) -> String {
-        format!(
-            "KllSketch(k={}, count={}, min={:.2}, max={:.2})",
-            self.inner.k(),
-            self.inner.count(),
-            self.inner.min(),
-            self.inner.max()
-        )
+    pub fn update(&mut self, item: Buffer, weight: f64) -> Result<()> {
+        if weight <= 0.0 || !weight.is_finite() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "Weight must be positive and finite",
+            ));
+        }
+        self.inner.update(item.to_vec(), weight);
+        Ok(())
     }
-}
-
-/// SplineSketch for quantile estimation
-///
-/// A space-efficient sketch for estimating quantiles of a data stream.
-/// Uses spline-based interpolation for smooth quantile estimates.
-///
-/// # Example
-/// ```javascript
-/// const { SplineSketch } = require('@sketch-oxide/node');
-/// const sketch = new SplineSketch(256);
-/// for (let i = 1; i <= 1000; i++) {
-///   sketch.insert(i, 1.0);
-/// }
-/// console.log(sketch.query(0.5)); // ~500
-/// ```
-#[napi]
-pub struct SplineSketch {
-    inner: RustSplineSketch,
-}
 
-#[napi]
-impl SplineSketch {
-    /// Create a new SplineSketch
-    ///
-    /// # Arguments
-    /// * `max_samples` - Maximum number of samples to retain
-    #[napi(constructor)]
-    pub fn new(max_samples: Option<u32>) -> Self {
-        let max_samples = max_samples.unwrap_or(256) as usize;
-        Self {
-            inner: RustSplineSketch::new(max_samples),
-        }
+    /// Get the current sample
+    #[napi]
+    pub fn sample(&self) -> Result<Vec<WeightedSampleItem>> {
+        Ok(self
+            .inner
+            .sample()
+            .iter()
+            .map(|wi| WeightedSampleItem {
+                item: Buffer::from(wi.item.clone()),
+                weight: wi.weight,
+                adjusted_weight: wi.adjusted_weight,
+            })
+            .collect())
     }
 
-    /// Add a value to the sketch
-    ///
-    /// # Arguments
-    /// * `value` - The value to add
-    /// * `weight` - The weight of the value (default 1.0)
+    /// Check if empty
     #[napi]
-    pub fn insert(&mut self, value: i64, weight: Option<f64>) -> Result<()> {
-        let weight = weight.unwrap_or(1.0);
-        self.inner.update(value as u64, weight);
-        Ok(())
+    pub fn isEmpty(&self) -> Result<bool> {
+        Ok(self.inner.is_empty())
     }
 
-    /// Query a quantile value
-    ///
-    /// # Arguments
-    /// * `quantile` - The quantile to query (0.0 to 1.0)
-    ///
-    /// # Returns
-    /// The estimated value at that quantile
+    /// Get current sample size
     #[napi]
-    pub fn query(&self, quantile: f64) -> i64 {
-        self.inner.query(quantile) as i64
+    pub fn len(&self) -> Result<u32> {
+        Ok(self.inner.len() as u32)
     }
 
-    /// Get the minimum value seen
+    /// Get maximum capacity
     #[napi]
-    pub fn min(&self) -> Option<i64> {
-        self.inner.min().map(|v| v as i64)
+    pub fn capacity(&self) -> Result<u32> {
+        Ok(self.inner.capacity() as u32)
     }
 
-    /// Get the maximum value seen
+    /// Get total items seen
     #[napi]
-    pub fn max(&self) -> Option<i64> {
-        self.inner.max().map(|v| v as i64)
+    pub fn count(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.count()))
     }
 
-    /// Get the number of samples retained
+    /// Get current threshold
     #[napi]
-    pub fn sampleCount(&self) -> u32 {
-        self.inner.sample_count() as u32
+    pub fn threshold(&self) -> Result<f64> {
+        Ok(self.inner.threshold())
     }
 
-    /// Get the maximum number of samples
+    /// Get total weight in sample
     #[napi]
-    pub fn maxSamples(&self) -> u32 {
-        self.inner.max_samples() as u32
+    pub fn totalWeight(&self) -> Result<f64> {
+        Ok(self.inner.total_weight())
     }
 
-    /// Get the total weight of all values
+    /// Estimate total weight of stream
     #[napi]
-    pub fn totalWeight(&self) -> f64 {
-        self.inner.total_weight()
+    pub fn estimateTotalWeight(&self) -> Result<f64> {
+        Ok(self.inner.estimate_total_weight())
     }
 
-    /// Merge another SplineSketch into this one
-    ///
-    /// # Arguments
-    /// * `other` - Another SplineSketch to merge
+    /// Clear the sampler
     #[napi]
-    pub fn mergeWith(&mut self, other: &SplineSketch) -> Result<()> {
-        self.inner.merge_into(&other.inner);
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
         Ok(())
     }
 
-    /// Reset the sketch to empty state
+    /// Merge another VarOpt sample
     #[napi]
-    pub fn reset(&mut self) {
-        self.inner.reset();
+    pub fn merge(&mut self, other: &VarOptSampling) -> Result<()> {
+        self.inner
+            .merge(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
-    /// Get a string representation
+    /// Get string representation
     #[napi]
-    pub fn toString(&self) -> String {
-        let min_str = self
-            .inner
-            .min()
-            .map_or("N/A".to_string(), |v| format!("{}", v));
-        let max_str = self
-            .inner
-            .max()
-            .map_or("N/A".to_string(), |v| format!("{}", v));
-        format!(
-            "SplineSketch(samples={}/{}, min={}, max={})",
-            self.inner.sample_count(),
-            self.inner.max_samples(),
-            min_str,
-            max_str
-        )
+    pub fn toString(&self) -> Result<String> {
+        Ok(format!(
+            "VarOptSampling(capacity={}, len={}, count={}, threshold={:.2})",
+            self.inner.capacity(),
+            self.inner.len(),
+            self.inner.count(),
+            self.inner.threshold()
+        ))
     }
 }
 
-// New bindings for similarity, sampling, streaming, and additional frequency algorithms
-// To be merged into lib.rs
+/// Estimate the size of an equi-join between the streams behind two VarOpt samples
+///
+/// Rows are matched on their whole item buffer and weights are treated as row
+/// multiplicities, so the estimate is the sum of adjusted-weight products over
+/// matching sampled rows. Draw the two samples independently (different seeds).
+///
+/// # Arguments
+/// * `a` - Sample of the first stream
+/// * `b` - Sample of the second stream
+///
+/// # Example
+/// ```javascript
+/// const { VarOptSampling, estimateJoinSize } = require('@sketch-oxide/node');
+/// const a = VarOptSampling.withSeed(500, 1n);
+/// const b = VarOptSampling.withSeed(500, 2n);
+/// // ... add rows keyed by Buffer ...
+/// console.log(estimateJoinSize(a, b));
+/// ```
+#[napi]
+pub fn estimateJoinSize(a: &VarOptSampling, b: &VarOptSampling) -> f64 {
+    sketch_oxide::sampling::estimate_join_size(&a.inner, &b.inner, |item| item.clone())
+}
 
 // =============================================================================
-// SIMILARITY ALGORITHMS
+// STREAMING ALGORITHMS
 // =============================================================================
 
-use sketch_oxide::similarity::MinHash as RustMinHash;
-use sketch_oxide::similarity::SimHash as RustSimHash;
+use sketch_oxide::streaming::ExponentialHistogram as RustExponentialHistogram;
+use sketch_oxide::streaming::SlidingNitroSketch as RustSlidingNitroSketch;
+use sketch_oxide::streaming::SlidingRateLimiter as RustSlidingRateLimiter;
+use sketch_oxide::streaming::SlidingTopK as RustSlidingTopK;
+use sketch_oxide::streaming::SlidingWindowCounter as RustSlidingWindowCounter;
+use sketch_oxide::streaming::SlidingWindowFrequency as RustSlidingWindowFrequency;
 
-/// MinHash sketch for Jaccard similarity estimation
-///
-/// Approximates the Jaccard similarity |A ∩ B| / |A ∪ B| using k hash functions.
-/// Standard error is approximately 1/sqrt(k).
+/// Sliding Window Counter using Exponential Histogram
 #[napi]
-pub struct MinHash {
-    inner: RustMinHash,
+pub struct SlidingWindowCounter {
+    inner: RustSlidingWindowCounter,
 }
 
 #[napi]
-impl MinHash {
-    /// Create a new MinHash sketch with specified number of permutations
+impl SlidingWindowCounter {
+    /// Create a new Sliding Window Counter
     #[napi(constructor)]
-    pub fn new(num_perm: u32) -> Result<Self> {
-        RustMinHash::new(num_perm as usize)
-            .map(|inner| MinHash { inner })
+    pub fn new(window_size: BigInt, epsilon: f64) -> Result<Self> {
+        let (_, window_val, _) = window_size.get_u64();
+        RustSlidingWindowCounter::new(window_val, epsilon)
+            .map(|inner| SlidingWindowCounter { inner })
             .map_err(|e| {
                 Error::new(
                     Status::InvalidArg,
-                    format!("MinHash creation failed: {}", e),
+                    format!("SlidingWindowCounter creation failed: {}", e),
                 )
             })
     }
 
-    /// Add an item to the set
+    /// Increment at given timestamp
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        self.inner.update(&item.to_vec());
+    pub fn increment(&mut self, timestamp: BigInt) -> Result<()> {
+        let (_, ts, _) = timestamp.get_u64();
+        self.inner.increment(ts);
         Ok(())
     }
 
-    /// Estimate Jaccard similarity with another MinHash
-    #[napi]
-    pub fn jaccardSimilarity(&self, other: &MinHash) -> Result<f64> {
-        self.inner
-            .jaccard_similarity(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Similarity failed: {}", e)))
-    }
-
-    /// Merge another MinHash (union operation)
-    #[napi]
-    pub fn merge(&mut self, other: &MinHash) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
-    }
-
-    /// Get the number of permutations
-    #[napi]
-    pub fn numPerm(&self) -> Result<u32> {
-        Ok(self.inner.num_perm() as u32)
-    }
-
-    /// Check if empty
+    /// Increment by amount at given timestamp
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn incrementBy(&mut self, timestamp: BigInt, count: BigInt) -> Result<()> {
+        let (_, ts, _) = timestamp.get_u64();
+        let (_, cnt, _) = count.get_u64();
+        self.inner.increment_by(ts, cnt);
+        Ok(())
     }
 
-    /// Serialize to binary format
+    /// Get count within window ending at given time
     #[napi]
-    pub fn serialize(&self) -> Result<Buffer> {
-        Ok(Buffer::from(self.inner.serialize()))
-    }
-
-    /// Deserialize from binary format
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustMinHash::deserialize(&data)
-            .map(|inner| MinHash { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
-                )
-            })
+    pub fn count(&self, current_time: BigInt) -> Result<BigInt> {
+        let (_, ts, _) = current_time.get_u64();
+        Ok(BigInt::from(self.inner.count(ts)))
     }
 
-    /// Get string representation
+    /// Get count for specific range
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "MinHash(numPerm={}, isEmpty={})",
-            self.inner.num_perm(),
-            self.inner.is_empty()
-        ))
-    }
-}
-
-/// SimHash sketch for near-duplicate detection
-#[napi]
-pub struct SimHash {
-    inner: RustSimHash,
-}
-
-#[napi]
-impl SimHash {
-    /// Create a new SimHash sketch
-    #[napi(constructor)]
-    pub fn new() -> Result<Self> {
-        Ok(SimHash {
-            inner: RustSimHash::new(),
-        })
+    pub fn countRange(&self, start: BigInt, end: BigInt) -> Result<BigInt> {
+        let (_, s, _) = start.get_u64();
+        let (_, e, _) = end.get_u64();
+        Ok(BigInt::from(self.inner.count_range(s, e)))
     }
 
-    /// Add a feature to the sketch
+    /// Expire old buckets
     #[napi]
-    pub fn update(&mut self, feature: Buffer) -> Result<()> {
-        self.inner.update(&feature.to_vec());
+    pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
+        let (_, ts, _) = current_time.get_u64();
+        self.inner.expire(ts);
         Ok(())
     }
 
-    /// Add a weighted feature
+    /// Clear all buckets
     #[napi]
-    pub fn updateWeighted(&mut self, feature: Buffer, weight: i64) -> Result<()> {
-        self.inner.update_weighted(&feature.to_vec(), weight);
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
         Ok(())
     }
 
-    /// Get the 64-bit fingerprint
+    /// Get window size
     #[napi]
-    pub fn fingerprint(&mut self) -> Result<BigInt> {
-        Ok(BigInt::from(self.inner.fingerprint()))
+    pub fn windowSize(&self) -> Result<BigInt> {
+        Ok(BigInt::from(self.inner.window_size()))
     }
 
-    /// Compute Hamming distance to another SimHash (0-64)
+    /// Get error bound
     #[napi]
-    pub fn hammingDistance(&mut self, other: &mut SimHash) -> Result<u32> {
-        Ok(self.inner.hamming_distance(&mut other.inner))
+    pub fn epsilon(&self) -> Result<f64> {
+        Ok(self.inner.epsilon())
     }
 
-    /// Compute similarity as (64 - hammingDistance) / 64
+    /// Get number of buckets
     #[napi]
-    pub fn similarity(&mut self, other: &mut SimHash) -> Result<f64> {
-        Ok(self.inner.similarity(&mut other.inner))
+    pub fn numBuckets(&self) -> Result<u32> {
+        Ok(self.inner.num_buckets() as u32)
     }
 
-    /// Get number of features added
+    /// Get theoretical error bound
     #[napi]
-    pub fn len(&self) -> Result<u32> {
-        Ok(self.inner.len() as u32)
+    pub fn errorBound(&self) -> Result<f64> {
+        Ok(self.inner.error_bound())
     }
 
-    /// Check if empty
+    /// Get memory usage in bytes
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn memoryUsage(&self) -> Result<u32> {
+        Ok(self.inner.memory_usage() as u32)
     }
 
-    /// Merge another SimHash
+    /// Merge another SlidingWindowCounter (same windowSize and epsilon)
     #[napi]
-    pub fn merge(&mut self, other: &SimHash) -> Result<()> {
+    pub fn merge(&mut self, other: &SlidingWindowCounter) -> Result<()> {
         self.inner
             .merge(&other.inner)
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
@@ -3373,15 +6176,15 @@ impl SimHash {
 
     /// Serialize to binary format
     #[napi]
-    pub fn serialize(&mut self) -> Result<Buffer> {
+    pub fn serialize(&self) -> Result<Buffer> {
         Ok(Buffer::from(self.inner.to_bytes()))
     }
 
     /// Deserialize from binary format
     #[napi(factory)]
     pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustSimHash::from_bytes(&data)
-            .map(|inner| SimHash { inner })
+        RustSlidingWindowCounter::from_bytes(&data)
+            .map(|inner| SlidingWindowCounter { inner })
             .map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
@@ -3394,405 +6197,412 @@ impl SimHash {
     #[napi]
     pub fn toString(&self) -> Result<String> {
         Ok(format!(
-            "SimHash(features={}, isEmpty={})",
-            self.inner.len(),
-            self.inner.is_empty()
+            "SlidingWindowCounter(windowSize={}, epsilon={:.4}, buckets={})",
+            self.inner.window_size(),
+            self.inner.epsilon(),
+            self.inner.num_buckets()
         ))
     }
 }
 
-// =============================================================================
-// SAMPLING ALGORITHMS
-// =============================================================================
-
-use sketch_oxide::sampling::ReservoirSampling as RustReservoirSampling;
-use sketch_oxide::sampling::VarOptSampling as RustVarOptSampling;
-
-/// Reservoir Sampling for uniform random samples from streams
+/// Per-key event counts over a sliding window, from time-bucketed Count-Min Sketches
+///
+/// Memory is numSlots × Count-Min size; slots are allocated on first use.
 #[napi]
-pub struct ReservoirSampling {
-    inner: RustReservoirSampling<Vec<u8>>,
+pub struct SlidingWindowFrequency {
+    inner: RustSlidingWindowFrequency,
 }
 
 #[napi]
-impl ReservoirSampling {
-    /// Create a new Reservoir Sampling instance
+impl SlidingWindowFrequency {
+    /// Create a sliding window frequency sketch split into `numSlots` time buckets
     #[napi(constructor)]
-    pub fn new(k: u32) -> Result<Self> {
-        RustReservoirSampling::new(k as usize)
-            .map(|inner| ReservoirSampling { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ReservoirSampling creation failed: {}", e),
-                )
-            })
-    }
-
-    /// Create with seed for reproducibility
-    #[napi(factory)]
-    pub fn withSeed(k: u32, seed: BigInt) -> Result<Self> {
-        let (_, seed_val, _) = seed.get_u64();
-        RustReservoirSampling::with_seed(k as usize, seed_val)
-            .map(|inner| ReservoirSampling { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("ReservoirSampling creation failed: {}", e),
-                )
-            })
+    pub fn new(window_size: BigInt, num_slots: u32, epsilon: f64, delta: f64) -> Result<Self> {
+        RustSlidingWindowFrequency::new(
+            bigint_to_u64(window_size)?,
+            num_slots as usize,
+            epsilon,
+            delta,
+        )
+        .map(|inner| SlidingWindowFrequency { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("SlidingWindowFrequency creation failed: {}", e),
+            )
+        })
     }
 
-    /// Add an item to the stream
+    /// Record one occurrence of key at timestamp
     #[napi]
-    pub fn update(&mut self, item: Buffer) -> Result<()> {
-        self.inner.update(item.to_vec());
+    pub fn updateAt(&mut self, key: Buffer, timestamp: BigInt) -> Result<()> {
+        let data: Vec<u8> = key.to_vec();
+        self.inner.update(&data, bigint_to_u64(timestamp)?);
         Ok(())
     }
 
-    /// Get the current sample
-    #[napi]
-    pub fn sample(&self) -> Result<Vec<Buffer>> {
-        Ok(self
-            .inner
-            .sample()
-            .iter()
-            .map(|v| Buffer::from(v.clone()))
-            .collect())
-    }
-
-    /// Check if empty
+    /// Estimate occurrences of key in [currentTime - window, currentTime]
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn countWindow(&self, key: Buffer, current_time: BigInt, window: BigInt) -> Result<BigInt> {
+        let data: Vec<u8> = key.to_vec();
+        Ok(BigInt::from(self.inner.count_window(
+            &data,
+            bigint_to_u64(current_time)?,
+            bigint_to_u64(window)?,
+        )))
     }
 
-    /// Get current sample size
+    /// Free slots no longer reachable from currentTime
     #[napi]
-    pub fn len(&self) -> Result<u32> {
-        Ok(self.inner.len() as u32)
+    pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
+        self.inner.expire(bigint_to_u64(current_time)?);
+        Ok(())
     }
 
-    /// Get maximum capacity
+    /// Remove all recorded events
     #[napi]
-    pub fn capacity(&self) -> Result<u32> {
-        Ok(self.inner.capacity() as u32)
+    pub fn clear(&mut self) {
+        self.inner.clear();
     }
 
-    /// Get total items seen
+    /// Get the largest queryable window
     #[napi]
-    pub fn count(&self) -> Result<BigInt> {
-        Ok(BigInt::from(self.inner.count()))
+    pub fn windowSize(&self) -> BigInt {
+        BigInt::from(self.inner.window_size())
     }
 
-    /// Get inclusion probability
+    /// Get the number of time slots
     #[napi]
-    pub fn inclusionProbability(&self) -> Result<f64> {
-        Ok(self.inner.inclusion_probability())
+    pub fn numSlots(&self) -> u32 {
+        self.inner.num_slots() as u32
     }
 
-    /// Clear the reservoir
+    /// Get the time units covered by each slot
     #[napi]
-    pub fn clear(&mut self) -> Result<()> {
-        self.inner.clear();
-        Ok(())
+    pub fn slotDuration(&self) -> BigInt {
+        BigInt::from(self.inner.slot_duration())
     }
 
-    /// Merge another reservoir sample
+    /// Get bytes used by allocated Count-Min counters
     #[napi]
-    pub fn merge(&mut self, other: &ReservoirSampling) -> Result<()> {
-        self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+    pub fn memoryUsage(&self) -> f64 {
+        self.inner.memory_usage() as f64
     }
 
-    /// Get string representation
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "ReservoirSampling(capacity={}, len={}, count={})",
-            self.inner.capacity(),
-            self.inner.len(),
-            self.inner.count()
-        ))
+    pub fn toString(&self) -> String {
+        format!(
+            "SlidingWindowFrequency(windowSize={}, numSlots={}, memory={})",
+            self.inner.window_size(),
+            self.inner.num_slots(),
+            self.inner.memory_usage()
+        )
     }
 }
 
-/// Weighted item in a VarOpt sample
+/// Heavy hitter in a sliding window top-k
 #[napi(object)]
-pub struct WeightedSampleItem {
+pub struct WindowTopKItem {
     pub item: Buffer,
-    pub weight: f64,
-    pub adjusted_weight: f64,
+    pub count: u32,
 }
 
-/// VarOpt Sampling for variance-optimal weighted samples
+/// Top-k heavy hitters over a sliding window, from time-bucketed HeavyKeepers
+///
+/// Window queries sum the per-slot counters; only items that were among the
+/// top k of some slot are reported.
 #[napi]
-pub struct VarOptSampling {
-    inner: RustVarOptSampling<Vec<u8>>,
+pub struct SlidingTopK {
+    inner: RustSlidingTopK,
 }
 
 #[napi]
-impl VarOptSampling {
-    /// Create a new VarOpt Sampling instance
+impl SlidingTopK {
+    /// Create a sliding window top-k tracker split into `numSlots` time buckets
     #[napi(constructor)]
-    pub fn new(k: u32) -> Result<Self> {
-        RustVarOptSampling::new(k as usize)
-            .map(|inner| VarOptSampling { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("VarOptSampling creation failed: {}", e),
-                )
-            })
-    }
-
-    /// Create with seed for reproducibility
-    #[napi(factory)]
-    pub fn withSeed(k: u32, seed: BigInt) -> Result<Self> {
-        let (_, seed_val, _) = seed.get_u64();
-        RustVarOptSampling::with_seed(k as usize, seed_val)
-            .map(|inner| VarOptSampling { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("VarOptSampling creation failed: {}", e),
-                )
-            })
+    pub fn new(
+        k: u32,
+        window_size: BigInt,
+        num_slots: u32,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Self> {
+        RustSlidingTopK::new(
+            k as usize,
+            bigint_to_u64(window_size)?,
+            num_slots as usize,
+            epsilon,
+            delta,
+        )
+        .map(|inner| SlidingTopK { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("SlidingTopK creation failed: {}", e),
+            )
+        })
     }
 
-    /// Add a weighted item
+    /// Record one occurrence of item at timestamp
     #[napi]
-    pub fn update(&mut self, item: Buffer, weight: f64) -> Result<()> {
-        if weight <= 0.0 || !weight.is_finite() {
-            return Err(Error::new(
-                Status::InvalidArg,
-                "Weight must be positive and finite",
-            ));
-        }
-        self.inner.update(item.to_vec(), weight);
+    pub fn updateAt(&mut self, item: Buffer, timestamp: BigInt) -> Result<()> {
+        let data: Vec<u8> = item.to_vec();
+        self.inner.update(&data, bigint_to_u64(timestamp)?);
         Ok(())
     }
 
-    /// Get the current sample
+    /// Get the top-k items in [currentTime - window, currentTime], largest first
     #[napi]
-    pub fn sample(&self) -> Result<Vec<WeightedSampleItem>> {
+    pub fn topKWindow(&self, current_time: BigInt, window: BigInt) -> Result<Vec<WindowTopKItem>> {
         Ok(self
             .inner
-            .sample()
-            .iter()
-            .map(|wi| WeightedSampleItem {
-                item: Buffer::from(wi.item.clone()),
-                weight: wi.weight,
-                adjusted_weight: wi.adjusted_weight,
+            .top_k_window(bigint_to_u64(current_time)?, bigint_to_u64(window)?)
+            .into_iter()
+            .map(|(item, count)| WindowTopKItem {
+                item: item.into(),
+                count,
             })
             .collect())
     }
 
-    /// Check if empty
+    /// Free slots no longer reachable from currentTime
     #[napi]
-    pub fn isEmpty(&self) -> Result<bool> {
-        Ok(self.inner.is_empty())
+    pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
+        self.inner.expire(bigint_to_u64(current_time)?);
+        Ok(())
     }
 
-    /// Get current sample size
+    /// Remove all recorded events
     #[napi]
-    pub fn len(&self) -> Result<u32> {
-        Ok(self.inner.len() as u32)
+    pub fn clear(&mut self) {
+        self.inner.clear();
     }
 
-    /// Get maximum capacity
+    /// Get the number of heavy hitters reported
     #[napi]
-    pub fn capacity(&self) -> Result<u32> {
-        Ok(self.inner.capacity() as u32)
+    pub fn k(&self) -> u32 {
+        self.inner.k() as u32
     }
 
-    /// Get total items seen
+    /// Get the largest queryable window
     #[napi]
-    pub fn count(&self) -> Result<BigInt> {
-        Ok(BigInt::from(self.inner.count()))
+    pub fn windowSize(&self) -> BigInt {
+        BigInt::from(self.inner.window_size())
     }
 
-    /// Get current threshold
+    /// Get the number of time slots
     #[napi]
-    pub fn threshold(&self) -> Result<f64> {
-        Ok(self.inner.threshold())
+    pub fn numSlots(&self) -> u32 {
+        self.inner.num_slots() as u32
     }
 
-    /// Get total weight in sample
+    /// Get the time units covered by each slot
     #[napi]
-    pub fn totalWeight(&self) -> Result<f64> {
-        Ok(self.inner.total_weight())
+    pub fn slotDuration(&self) -> BigInt {
+        BigInt::from(self.inner.slot_duration())
     }
 
-    /// Estimate total weight of stream
     #[napi]
-    pub fn estimateTotalWeight(&self) -> Result<f64> {
-        Ok(self.inner.estimate_total_weight())
+    pub fn toString(&self) -> String {
+        format!(
+            "SlidingTopK(k={}, windowSize={}, numSlots={})",
+            self.inner.k(),
+            self.inner.window_size(),
+            self.inner.num_slots()
+        )
     }
+}
 
-    /// Clear the sampler
-    #[napi]
-    pub fn clear(&mut self) -> Result<()> {
-        self.inner.clear();
-        Ok(())
+/// Sampled per-key event counts over a sliding window, for line-rate telemetry
+///
+/// Each update is recorded with probability sampleRate into time-bucketed
+/// Count-Min Sketches; window queries scale the sampled counts back up.
+/// Keys with fewer than 1 / sampleRate events in a window are estimated
+/// coarsely.
+#[napi]
+pub struct SlidingNitroSketch {
+    inner: RustSlidingNitroSketch,
+}
+
+#[napi]
+impl SlidingNitroSketch {
+    /// Create a sampled sliding window sketch split into `numSlots` time buckets
+    #[napi(constructor)]
+    pub fn new(
+        window_size: BigInt,
+        num_slots: u32,
+        sample_rate: f64,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Self> {
+        RustSlidingNitroSketch::new(
+            bigint_to_u64(window_size)?,
+            num_slots as usize,
+            sample_rate,
+            epsilon,
+            delta,
+        )
+        .map(|inner| SlidingNitroSketch { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("SlidingNitroSketch creation failed: {}", e),
+            )
+        })
     }
 
-    /// Merge another VarOpt sample
+    /// Offer one occurrence of key at timestamp, recorded with probability sampleRate
     #[napi]
-    pub fn merge(&mut self, other: &VarOptSampling) -> Result<()> {
+    pub fn updateSampledAt(&mut self, key: Buffer, timestamp: BigInt) -> Result<()> {
         self.inner
-            .merge(&other.inner)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
+            .update_sampled_at(&key, bigint_to_u64(timestamp)?);
+        Ok(())
     }
 
-    /// Get string representation
+    /// Estimate occurrences of key in [currentTime - window, currentTime]
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "VarOptSampling(capacity={}, len={}, count={}, threshold={:.2})",
-            self.inner.capacity(),
-            self.inner.len(),
-            self.inner.count(),
-            self.inner.threshold()
-        ))
+    pub fn queryWindow(&self, key: Buffer, current_time: BigInt, window: BigInt) -> Result<f64> {
+        Ok(self
+            .inner
+            .query_window(&key, bigint_to_u64(current_time)?, bigint_to_u64(window)?))
     }
-}
-
-// =============================================================================
-// STREAMING ALGORITHMS
-// =============================================================================
 
-use sketch_oxide::streaming::ExponentialHistogram as RustExponentialHistogram;
-use sketch_oxide::streaming::SlidingWindowCounter as RustSlidingWindowCounter;
+    /// Free slots no longer reachable from currentTime
+    #[napi]
+    pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
+        self.inner.expire(bigint_to_u64(current_time)?);
+        Ok(())
+    }
 
-/// Sliding Window Counter using Exponential Histogram
-#[napi]
-pub struct SlidingWindowCounter {
-    inner: RustSlidingWindowCounter,
-}
+    /// Remove all recorded events
+    #[napi]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
 
-#[napi]
-impl SlidingWindowCounter {
-    /// Create a new Sliding Window Counter
-    #[napi(constructor)]
-    pub fn new(window_size: BigInt, epsilon: f64) -> Result<Self> {
-        let (_, window_val, _) = window_size.get_u64();
-        RustSlidingWindowCounter::new(window_val, epsilon)
-            .map(|inner| SlidingWindowCounter { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::InvalidArg,
-                    format!("SlidingWindowCounter creation failed: {}", e),
-                )
-            })
+    /// Get the probability of recording each update
+    #[napi]
+    pub fn sampleRate(&self) -> f64 {
+        self.inner.sample_rate()
     }
 
-    /// Increment at given timestamp
+    /// Get the largest queryable window
     #[napi]
-    pub fn increment(&mut self, timestamp: BigInt) -> Result<()> {
-        let (_, ts, _) = timestamp.get_u64();
-        self.inner.increment(ts);
-        Ok(())
+    pub fn windowSize(&self) -> BigInt {
+        BigInt::from(self.inner.window_size())
     }
 
-    /// Increment by amount at given timestamp
+    /// Get the number of time slots
     #[napi]
-    pub fn incrementBy(&mut self, timestamp: BigInt, count: BigInt) -> Result<()> {
-        let (_, ts, _) = timestamp.get_u64();
-        let (_, cnt, _) = count.get_u64();
-        self.inner.increment_by(ts, cnt);
-        Ok(())
+    pub fn numSlots(&self) -> u32 {
+        self.inner.num_slots() as u32
     }
 
-    /// Get count within window ending at given time
+    /// Get the time units covered by each slot
     #[napi]
-    pub fn count(&self, current_time: BigInt) -> Result<BigInt> {
-        let (_, ts, _) = current_time.get_u64();
-        Ok(BigInt::from(self.inner.count(ts)))
+    pub fn slotDuration(&self) -> BigInt {
+        BigInt::from(self.inner.slot_duration())
     }
 
-    /// Get count for specific range
+    /// Get bytes used by allocated Count-Min counters
     #[napi]
-    pub fn countRange(&self, start: BigInt, end: BigInt) -> Result<BigInt> {
-        let (_, s, _) = start.get_u64();
-        let (_, e, _) = end.get_u64();
-        Ok(BigInt::from(self.inner.count_range(s, e)))
+    pub fn memoryUsage(&self) -> f64 {
+        self.inner.memory_usage() as f64
     }
 
-    /// Expire old buckets
     #[napi]
-    pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
-        let (_, ts, _) = current_time.get_u64();
-        self.inner.expire(ts);
-        Ok(())
+    pub fn toString(&self) -> String {
+        format!(
+            "SlidingNitroSketch(windowSize={}, numSlots={}, sampleRate={})",
+            self.inner.window_size(),
+            self.inner.num_slots(),
+            self.inner.sample_rate()
+        )
+    }
+}
+
+/// Approximate per-key rate limiter over a sliding window
+///
+/// Admits between limit / (1 + epsilon) and limit / (1 - epsilon) requests
+/// per window; counts below 1 / epsilon are exact.
+#[napi]
+pub struct SlidingRateLimiter {
+    inner: RustSlidingRateLimiter<Vec<u8>>,
+}
+
+#[napi]
+impl SlidingRateLimiter {
+    /// Create a limiter admitting `limit` requests per key every `windowSeconds`
+    #[napi(constructor)]
+    pub fn new(limit: BigInt, window_seconds: BigInt, epsilon: f64) -> Result<Self> {
+        RustSlidingRateLimiter::new(
+            bigint_to_u64(limit)?,
+            bigint_to_u64(window_seconds)?,
+            epsilon,
+        )
+        .map(|inner| SlidingRateLimiter { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("SlidingRateLimiter creation failed: {}", e),
+            )
+        })
     }
 
-    /// Clear all buckets
+    /// Record a request for key at now; false (and not counted) if over the limit
     #[napi]
-    pub fn clear(&mut self) -> Result<()> {
-        self.inner.clear();
-        Ok(())
+    pub fn tryAcquire(&mut self, key: Buffer, now: BigInt) -> Result<bool> {
+        Ok(self.inner.try_acquire(&key.to_vec(), bigint_to_u64(now)?))
     }
 
-    /// Get window size
+    /// Approximate admitted requests for key in [now - windowSeconds, now]
     #[napi]
-    pub fn windowSize(&self) -> Result<BigInt> {
-        Ok(BigInt::from(self.inner.window_size()))
+    pub fn count(&self, key: Buffer, now: BigInt) -> Result<BigInt> {
+        Ok(BigInt::from(
+            self.inner.count(&key.to_vec(), bigint_to_u64(now)?),
+        ))
     }
 
-    /// Get error bound
+    /// Drop keys with no admissions in the window ending at now
     #[napi]
-    pub fn epsilon(&self) -> Result<f64> {
-        Ok(self.inner.epsilon())
+    pub fn expire(&mut self, now: BigInt) -> Result<()> {
+        self.inner.expire(bigint_to_u64(now)?);
+        Ok(())
     }
 
-    /// Get number of buckets
+    /// Forget every key
     #[napi]
-    pub fn numBuckets(&self) -> Result<u32> {
-        Ok(self.inner.num_buckets() as u32)
+    pub fn clear(&mut self) {
+        self.inner.clear();
     }
 
-    /// Get theoretical error bound
+    /// Get the per-key limit
     #[napi]
-    pub fn errorBound(&self) -> Result<f64> {
-        Ok(self.inner.error_bound())
+    pub fn limit(&self) -> BigInt {
+        BigInt::from(self.inner.limit())
     }
 
-    /// Get memory usage in bytes
+    /// Get the window length
     #[napi]
-    pub fn memoryUsage(&self) -> Result<u32> {
-        Ok(self.inner.memory_usage() as u32)
+    pub fn windowSeconds(&self) -> BigInt {
+        BigInt::from(self.inner.window_seconds())
     }
 
-    /// Serialize to binary format
+    /// Get the number of tracked keys
     #[napi]
-    pub fn serialize(&self) -> Result<Buffer> {
-        Ok(Buffer::from(self.inner.to_bytes()))
-    }
-
-    /// Deserialize from binary format
-    #[napi(factory)]
-    pub fn deserialize(data: Buffer) -> Result<Self> {
-        RustSlidingWindowCounter::from_bytes(&data)
-            .map(|inner| SlidingWindowCounter { inner })
-            .map_err(|e| {
-                Error::new(
-                    Status::GenericFailure,
-                    format!("Deserialization failed: {}", e),
-                )
-            })
+    pub fn numKeys(&self) -> u32 {
+        self.inner.num_keys() as u32
     }
 
-    /// Get string representation
     #[napi]
-    pub fn toString(&self) -> Result<String> {
-        Ok(format!(
-            "SlidingWindowCounter(windowSize={}, epsilon={:.4}, buckets={})",
-            self.inner.window_size(),
+    pub fn toString(&self) -> String {
+        format!(
+            "SlidingRateLimiter(limit={}, windowSeconds={}, epsilon={}, keys={})",
+            self.inner.limit(),
+            self.inner.window_seconds(),
             self.inner.epsilon(),
-            self.inner.num_buckets()
-        ))
+            self.inner.num_keys()
+        )
     }
 }
 
@@ -3847,6 +6657,38 @@ impl ExponentialHistogram {
         })
     }
 
+    /// Get count estimate with bounds, ignoring expired buckets without mutating state
+    #[napi]
+    pub fn countAsOf(&self, current_time: BigInt) -> Result<CountWithBounds> {
+        let (_, ts, _) = current_time.get_u64();
+        let (estimate, lower, upper) = self.inner.count_as_of(ts);
+        Ok(CountWithBounds {
+            estimate: BigInt::from(estimate),
+            lower: BigInt::from(lower),
+            upper: BigInt::from(upper),
+        })
+    }
+
+    /// Get count estimate with bounds for a specific range without mutating state
+    #[napi]
+    pub fn countRangeAsOf(&self, start: BigInt, end: BigInt) -> Result<CountWithBounds> {
+        let (_, s, _) = start.get_u64();
+        let (_, e, _) = end.get_u64();
+        let (estimate, lower, upper) = self.inner.count_range_as_of(s, e);
+        Ok(CountWithBounds {
+            estimate: BigInt::from(estimate),
+            lower: BigInt::from(lower),
+            upper: BigInt::from(upper),
+        })
+    }
+
+    /// Get the approximate timestamp of the n-th most recent event
+    #[napi]
+    pub fn timestampForCount(&self, n: BigInt) -> Result<Option<BigInt>> {
+        let (_, n, _) = n.get_u64();
+        Ok(self.inner.timestamp_for_count(n).map(BigInt::from))
+    }
+
     /// Expire old buckets
     #[napi]
     pub fn expire(&mut self, current_time: BigInt) -> Result<()> {
@@ -3993,6 +6835,32 @@ impl ElasticSketch {
             })
     }
 
+    /// Create with custom elastic ratio and promotion ratio
+    ///
+    /// A colliding item evicts a bucket's resident once its votes reach
+    /// `promotionRatio × frequency`; lower values favour late heavy hitters.
+    #[napi(factory)]
+    pub fn withPromotionRatio(
+        bucket_count: u32,
+        depth: u32,
+        elastic_ratio: f64,
+        promotion_ratio: f64,
+    ) -> Result<Self> {
+        RustElasticSketch::with_promotion_ratio(
+            bucket_count as usize,
+            depth as usize,
+            elastic_ratio,
+            promotion_ratio,
+        )
+        .map(|inner| ElasticSketch { inner })
+        .map_err(|e| {
+            Error::new(
+                Status::InvalidArg,
+                format!("ElasticSketch creation failed: {}", e),
+            )
+        })
+    }
+
     /// Update with item and count
     #[napi]
     pub fn update(&mut self, item: Buffer, count: BigInt) -> Result<()> {
@@ -4029,6 +6897,13 @@ impl ElasticSketch {
         Ok(())
     }
 
+    /// Clear all counts, keeping dimensions and ratios (same as `reset`)
+    #[napi]
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
+        Ok(())
+    }
+
     /// Get bucket count
     #[napi]
     pub fn bucketCount(&self) -> Result<u32> {
@@ -4047,6 +6922,12 @@ impl ElasticSketch {
         Ok(self.inner.elastic_ratio())
     }
 
+    /// Get promotion ratio (Infinity unless built with withPromotionRatio)
+    #[napi]
+    pub fn promotionRatio(&self) -> f64 {
+        self.inner.promotion_ratio()
+    }
+
     /// Get total count
     #[napi]
     pub fn totalCount(&self) -> Result<BigInt> {
@@ -4073,6 +6954,16 @@ impl ElasticSketch {
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Check the sketch's internal invariants, throwing if any is violated
+    ///
+    /// Use after `deserialize` or `merge` to detect a corrupted sketch.
+    #[napi]
+    pub fn validate(&self) -> Result<()> {
+        self.inner
+            .validate()
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Validation failed: {}", e)))
+    }
+
     /// Serialize to binary format
     #[napi]
     pub fn serialize(&self) -> Result<Buffer> {
@@ -4188,7 +7079,14 @@ impl SALSA {
         Ok(self.inner.depth() as u32)
     }
 
-    /// Merge another SALSA sketch
+    /// Clear all counts, keeping epsilon, delta and dimensions
+    #[napi]
+    pub fn clear(&mut self) -> Result<()> {
+        self.inner.clear();
+        Ok(())
+    }
+
+    /// Merge another SALSA sketch, promoting to the higher adaptation level
     #[napi]
     pub fn merge(&mut self, other: &SALSA) -> Result<()> {
         self.inner
@@ -4282,6 +7180,39 @@ impl RemovableUniversalSketch {
             .map_err(|e| Error::new(Status::InvalidArg, format!("Merge failed: {}", e)))
     }
 
+    /// Serialize the sketch to binary format
+    ///
+    /// # Example
+    /// ```javascript
+    /// const data = sketch.serialize();
+    /// fs.writeFileSync('rus.bin', data);
+    /// ```
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    /// Deserialize from binary format
+    ///
+    /// # Arguments
+    /// * `data` - Binary data from serialize()
+    ///
+    /// # Example
+    /// ```javascript
+    /// const sketch = RemovableUniversalSketch.deserialize(fs.readFileSync('rus.bin'));
+    /// ```
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustRemovableUniversalSketch::from_bytes(&data)
+            .map(|inner| RemovableUniversalSketch { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
     /// Get string representation
     #[napi]
     pub fn toString(&self) -> Result<String> {
@@ -4384,6 +7315,42 @@ impl HeavyKeeper {
         Ok(())
     }
 
+    /// Add `count` occurrences of an item at once
+    ///
+    /// # Throws
+    /// - If count is negative
+    ///
+    /// # Example
+    /// ```javascript
+    /// hk.updateBy(Buffer.from('flow'), 1500);
+    /// ```
+    #[napi]
+    pub fn updateBy(&mut self, item: Buffer, count: i64) -> Result<()> {
+        if count < 0 {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "count must be non-negative".to_string(),
+            ));
+        }
+        self.inner.update_by(&item, count as u64);
+        Ok(())
+    }
+
+    /// Add a fractional weight for an item, e.g. bytes in a flow
+    ///
+    /// The weight is rounded stochastically; non-finite or non-positive
+    /// weights are ignored.
+    ///
+    /// # Example
+    /// ```javascript
+    /// hk.updateWeighted(Buffer.from('flow'), 1500.5);
+    /// ```
+    #[napi]
+    pub fn updateWeighted(&mut self, item: Buffer, weight: f64) -> Result<()> {
+        self.inner.update_weighted(&item, weight);
+        Ok(())
+    }
+
     /// Estimate the frequency of an item
     ///
     /// # Arguments
@@ -4998,6 +7965,23 @@ impl SlidingHyperLogLog {
         self.inner.estimate_total()
     }
 
+    /// Estimate distinct items first seen after a cutoff
+    ///
+    /// Unlike `estimateWindow`, items seen before the cutoff and again
+    /// after it are not counted.
+    ///
+    /// # Arguments
+    /// * `cutoffTs` - Cutoff timestamp in seconds
+    ///
+    /// # Example
+    /// ```javascript
+    /// const newUsers = hll.distinctSince(startOfWeek);
+    /// ```
+    #[napi]
+    pub fn distinctSince(&self, cutoff_ts: BigInt) -> f64 {
+        self.inner.distinct_since(cutoff_ts.get_u64().1)
+    }
+
     /// Remove expired entries outside the window
     ///
     /// # Arguments
@@ -5129,6 +8113,27 @@ impl VacuumFilter {
         self.inner.contains(&data)
     }
 
+    /// Measure the false positive rate on known-absent keys
+    ///
+    /// Diagnostic only: the result depends on the sample, which must not
+    /// overlap the inserted keys.
+    ///
+    /// # Arguments
+    /// * `absentKeys` - Keys known not to have been inserted
+    ///
+    /// # Returns
+    /// Fraction of `absentKeys` reported as present (0 for an empty array)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const fpr = filter.measuredFpr([Buffer.from('x'), Buffer.from('y')]);
+    /// ```
+    #[napi]
+    pub fn measuredFpr(&self, absent_keys: Vec<Buffer>) -> f64 {
+        let keys: Vec<&[u8]> = absent_keys.iter().map(|k| k.as_ref()).collect();
+        self.inner.measured_fpr(&keys)
+    }
+
     /// Delete an element from the filter
     ///
     /// # Arguments
@@ -5150,6 +8155,27 @@ impl VacuumFilter {
             .map_err(|e| Error::new(Status::GenericFailure, format!("Delete failed: {}", e)))
     }
 
+    /// Add every fingerprint of another filter
+    ///
+    /// The other filter must use fingerprints at least as wide as this one's
+    /// (i.e. a target FPR at most as large). The result uses the smaller of
+    /// the two bucket counts; filters cannot grow from fingerprints alone.
+    ///
+    /// # Throws
+    /// - If the other filter has narrower fingerprints
+    /// - If the combined items do not fit
+    ///
+    /// # Example
+    /// ```javascript
+    /// filter.unionInto(otherFilter);
+    /// ```
+    #[napi]
+    pub fn unionInto(&mut self, other: &VacuumFilter) -> Result<()> {
+        self.inner
+            .union_into(&other.inner)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("Union failed: {}", e)))
+    }
+
     /// Get current load factor (0.0 to 1.0)
     ///
     /// # Example
@@ -5243,6 +8269,39 @@ impl VacuumFilter {
         self.inner.clear();
     }
 
+    /// Serialize the filter to binary format
+    ///
+    /// # Example
+    /// ```javascript
+    /// const data = filter.serialize();
+    /// fs.writeFileSync('vacuum.bin', data);
+    /// ```
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        Buffer::from(self.inner.to_bytes())
+    }
+
+    /// Deserialize from binary format
+    ///
+    /// # Arguments
+    /// * `data` - Binary data from serialize()
+    ///
+    /// # Example
+    /// ```javascript
+    /// const filter = VacuumFilter.deserialize(fs.readFileSync('vacuum.bin'));
+    /// ```
+    #[napi(factory)]
+    pub fn deserialize(data: Buffer) -> Result<Self> {
+        RustVacuumFilter::from_bytes(&data)
+            .map(|inner| VacuumFilter { inner })
+            .map_err(|e| {
+                Error::new(
+                    Status::GenericFailure,
+                    format!("Deserialization failed: {}", e),
+                )
+            })
+    }
+
     /// Get string representation
     #[napi]
     pub fn toString(&self) -> String {
@@ -5549,6 +8608,34 @@ impl NitroSketch {
             .map_err(|e| Error::new(Status::GenericFailure, format!("Sync failed: {}", e)))
     }
 
+    /// Change the sample rate for subsequent updates
+    ///
+    /// The rate is clamped to (0, 1]. Samples are weighted by the rate in
+    /// force when they were taken, so sync() stays unbiased across changes.
+    ///
+    /// # Arguments
+    /// * `rate` - New sample rate
+    ///
+    /// # Throws
+    /// - If rate is not a positive finite number
+    ///
+    /// # Example
+    /// ```javascript
+    /// if (backlog > limit) nitro.setRate(nitro.currentRate() / 2);
+    /// ```
+    #[napi]
+    pub fn setRate(&mut self, rate: f64) -> Result<()> {
+        self.inner
+            .set_rate(rate)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+    }
+
+    /// Get the sample rate applied to new updates
+    #[napi]
+    pub fn currentRate(&self) -> f64 {
+        self.inner.current_rate()
+    }
+
     /// Get statistics about sampling
     ///
     /// # Returns
@@ -5891,6 +8978,32 @@ impl LearnedBloomFilter {
         self.inner.contains(&data)
     }
 
+    /// Add members without retraining the model
+    ///
+    /// Keys the model does not already accept go into the backup filter, so
+    /// there are still no false negatives. The model is not updated; check
+    /// `needsRetrain()` and rebuild with `LearnedBloomFilter.new` when it
+    /// returns `true`.
+    ///
+    /// # Example
+    /// ```javascript
+    /// filter.addMembers([Buffer.from('late1'), Buffer.from('late2')]);
+    /// if (filter.needsRetrain()) {
+    ///   filter = LearnedBloomFilter.new(allKeys, 0.01);
+    /// }
+    /// ```
+    #[napi]
+    pub fn addMembers(&mut self, new_keys: Vec<Buffer>) {
+        let keys: Vec<&[u8]> = new_keys.iter().map(|b| b.as_ref()).collect();
+        self.inner.add_members(&keys);
+    }
+
+    /// Whether the backup filter has outgrown its size and the filter should be rebuilt
+    #[napi]
+    pub fn needsRetrain(&self) -> bool {
+        self.inner.needs_retrain()
+    }
+
     /// Get memory usage in bytes
     ///
     /// # Example
@@ -5912,6 +9025,48 @@ impl LearnedBloomFilter {
     }
 }
 
+// ============================================================================
+// SIZING
+// ============================================================================
+
+/// Bytes of HyperLogLog registers needed for a relative standard error
+///
+/// # Example
+/// ```javascript
+/// const { sizing } = require('@sketch-oxide/node');
+/// console.log(sizing.bytesForRse(0.01)); // 16384
+/// ```
+#[napi(namespace = "sizing")]
+pub fn bytesForRse(target: f64) -> Result<u32> {
+    sketch_oxide::cardinality::bytes_for_rse(target)
+        .map(|bytes| bytes as u32)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Sizing failed: {}", e)))
+}
+
+/// Bytes of counters for a Count-Min Sketch with error bound ε and failure probability δ
+#[napi(namespace = "sizing")]
+pub fn cmsBytesFor(epsilon: f64, delta: f64) -> Result<f64> {
+    sketch_oxide::frequency::cms_bytes_for(epsilon, delta)
+        .map(|bytes| bytes as f64)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Sizing failed: {}", e)))
+}
+
+/// Worst-case bytes for a KLL sketch with the given rank error after `n` items
+///
+/// KLL grows with the stream, so the item count is required.
+#[napi(namespace = "sizing")]
+pub fn kllBytesFor(rank_error: f64, n: f64) -> Result<f64> {
+    if n.is_nan() || n < 0.0 {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "Sizing failed: n must be non-negative".to_string(),
+        ));
+    }
+    sketch_oxide::quantiles::kll_bytes_for(rank_error, n as u64)
+        .map(|bytes| bytes as f64)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Sizing failed: {}", e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6351,7 +9506,8 @@ mod tests {
         let mut rf = RibbonFilter::new(100, Some(0.01)).unwrap();
         let _ = rf.insert(Buffer::from(b"key1".to_vec()));
         let _ = rf.insert(Buffer::from(b"key2".to_vec()));
-        rf.build();
+        assert!(rf.buildInfo().is_none());
+        assert_eq!(rf.build().unwrap().path, "solved");
         assert!(rf.contains(Buffer::from(b"key1".to_vec())).unwrap());
         assert!(rf.contains(Buffer::from(b"key2".to_vec())).unwrap());
     }
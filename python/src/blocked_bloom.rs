@@ -95,7 +95,9 @@ impl BlockedBloomFilter {
     /// Raises:
     ///     ValueError: If filters have different sizes
     fn merge(&mut self, other: &BlockedBloomFilter) -> PyResult<()> {
-        if self.inner.params() != other.inner.params() {
+        if self.inner.params() != other.inner.params()
+            || self.inner.block_bits() != other.inner.block_bits()
+        {
             return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
                 "Blocked Bloom filters must have same parameters to merge",
             ));
@@ -122,10 +122,13 @@ impl BloomFilter {
         self.inner.len()
     }
 
-    /// Get the theoretical false positive rate
+    /// Get the theoretical false positive rate at the design capacity
+    ///
+    /// Computed as (1 - e^(-k*n/m))^k from the filter's parameters, so it
+    /// does not change as elements are inserted.
     ///
     /// Returns:
-    ///     float: Current false positive rate based on fill ratio
+    ///     float: False positive rate at the design capacity
     fn false_positive_rate(&self) -> f64 {
         self.inner.false_positive_rate()
     }
@@ -85,8 +85,13 @@ impl RibbonFilter {
     ///
     /// Must be called after all inserts and before any contains() calls.
     /// Calling finalize() multiple times is safe (idempotent).
-    fn finalize(&mut self) {
-        self.inner.finalize();
+    ///
+    /// Raises:
+    ///     ValueError: If the filter is too small to represent its keys
+    fn finalize(&mut self) -> PyResult<()> {
+        self.inner
+            .finalize()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
 
     /// Check if a key might be in the set
@@ -3,7 +3,7 @@
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use sketch_oxide::quantiles::SplineSketch as RustSplineSketch;
-use sketch_oxide::{Mergeable, Sketch};
+use sketch_oxide::Sketch;
 
 /// SplineSketch for high-accuracy quantile estimation with monotone cubic spline interpolation
 ///
@@ -8,7 +8,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use sketch_oxide::common::Mergeable;
-use sketch_oxide::quantiles::DDSketch;
+use sketch_oxide::quantiles::{DDSketch, Mapping};
 
 /// Benchmark add operations with different accuracy levels
 fn bench_ddsketch_add(c: &mut Criterion) {
@@ -31,6 +31,30 @@ fn bench_ddsketch_add(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark add throughput for each index mapping
+fn bench_ddsketch_add_mappings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ddsketch_add_mappings");
+    group.throughput(Throughput::Elements(10000));
+
+    let values: Vec<f64> = (1..=10000).map(|i| (i as f64) * 1.37).collect();
+    for (name, mapping) in [
+        ("logarithmic", Mapping::Logarithmic),
+        ("cubic", Mapping::CubicallyInterpolated),
+        ("linear", Mapping::LinearlyInterpolated),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut dd = DDSketch::with_mapping(0.01, mapping).unwrap();
+                for &v in &values {
+                    dd.add(black_box(v));
+                }
+                dd
+            });
+        });
+    }
+    group.finish();
+}
+
 /// Benchmark add operations with different value ranges
 fn bench_ddsketch_add_ranges(c: &mut Criterion) {
     let mut group = c.benchmark_group("ddsketch_add_ranges");
@@ -250,6 +274,7 @@ fn bench_ddsketch_min_max(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_ddsketch_add,
+    bench_ddsketch_add_mappings,
     bench_ddsketch_add_ranges,
     bench_ddsketch_quantile,
     bench_ddsketch_quantile_types,
@@ -68,7 +68,7 @@ fn bench_filter_contains_hit(c: &mut Criterion) {
         for key in &keys {
             ribbon.insert(key);
         }
-        ribbon.finalize();
+        ribbon.finalize().unwrap();
 
         group.bench_with_input(BenchmarkId::new("ribbon", n), &keys, |b, keys| {
             let mut idx = 0;
@@ -135,7 +135,7 @@ fn bench_filter_contains_miss(c: &mut Criterion) {
         for key in &keys {
             ribbon.insert(key);
         }
-        ribbon.finalize();
+        ribbon.finalize().unwrap();
 
         group.bench_with_input(BenchmarkId::new("ribbon", n), &miss_keys, |b, miss_keys| {
             let mut idx = 0;
@@ -193,7 +193,7 @@ fn bench_filter_memory_usage(c: &mut Criterion) {
         for key in &keys {
             ribbon.insert(key);
         }
-        ribbon.finalize();
+        ribbon.finalize().unwrap();
 
         group.bench_with_input(BenchmarkId::new("ribbon", n), &ribbon, |b, filter| {
             b.iter(|| {
@@ -261,7 +261,7 @@ fn bench_filter_serialization(c: &mut Criterion) {
     for key in &keys {
         ribbon.insert(key);
     }
-    ribbon.finalize();
+    ribbon.finalize().unwrap();
 
     group.bench_function("ribbon_to_bytes", |b| {
         b.iter(|| {
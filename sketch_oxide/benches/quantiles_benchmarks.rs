@@ -23,6 +23,32 @@ fn bench_tdigest_update(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: T-Digest ingest across buffer sizes (compression 100)
+fn bench_tdigest_buffer_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tdigest_buffer_size");
+    let values: Vec<f64> = (0..100_000u64)
+        .map(|i| (i.wrapping_mul(2_654_435_761) % 100_000) as f64)
+        .collect();
+
+    for buffer_size in [100, 500, 5_000, 50_000] {
+        group.bench_with_input(
+            BenchmarkId::new("ingest_100k", buffer_size),
+            &buffer_size,
+            |b, &buffer_size| {
+                b.iter(|| {
+                    let mut td = TDigest::with_buffer(100.0, buffer_size).unwrap();
+                    for &v in &values {
+                        td.update(black_box(v));
+                    }
+                    black_box(td.quantile(0.99))
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmark: T-Digest quantile operations
 fn bench_tdigest_quantile(c: &mut Criterion) {
     let mut group = c.benchmark_group("tdigest_quantile");
@@ -115,6 +141,7 @@ fn bench_quantile_comparison(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_tdigest_update,
+    bench_tdigest_buffer_size,
     bench_tdigest_quantile,
     bench_kll_update,
     bench_kll_quantile,
@@ -0,0 +1,433 @@
+//! Adaptive HyperLogLog: precision that grows with the cardinality
+//!
+//! Many workloads hold a large number of sketches, most of which only ever see
+//! a handful of items. A fixed-precision HyperLogLog pays for 2^p registers in
+//! every one of them.
+//!
+//! # Approach
+//!
+//! Registers cannot be refined after the fact: raising precision would need
+//! the original hashes. So every hash is recorded at `max_precision`, and the
+//! sketch reports at a lower *current precision* obtained by folding (see
+//! [`HyperLogLog::fold_to`]), which is exact:
+//!
+//! 1. While few registers are touched, they are kept in a sparse map from
+//!    register index to rank, so a small sketch costs a few bytes per item.
+//! 2. Once the sparse map would be larger than a dense array, it is converted
+//!    into `2^max_precision` one-byte registers.
+//!
+//! The current precision starts at `min_precision` and increases by one each
+//! time the estimated cardinality exceeds `load_threshold * 2^p`. Below that
+//! load, linear counting at precision `p` is already within about
+//! `0.8 / sqrt(2^p)` relative error, so extra registers would not help. As the
+//! cardinality grows, the precision (and the reported
+//! [`standard_error`](AdaptiveHyperLogLog::standard_error)) improves until it
+//! reaches `max_precision`.
+//!
+//! The current precision never decreases. Estimates and
+//! [`to_hyperloglog`](AdaptiveHyperLogLog::to_hyperloglog) are identical to a
+//! `HyperLogLog` at `max_precision` folded down to the current precision.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::cardinality::AdaptiveHyperLogLog;
+//!
+//! let mut hll = AdaptiveHyperLogLog::new(6, 14).unwrap();
+//! hll.update(&"user_1");
+//! assert_eq!(hll.current_precision(), 6);
+//! assert!(hll.is_sparse());
+//!
+//! for i in 0..100_000 {
+//!     hll.update(&i);
+//! }
+//! assert_eq!(hll.current_precision(), 14);
+//! assert!((hll.estimate() - 100_000.0).abs() / 100_000.0 < 0.05);
+//! ```
+
+use super::hyperloglog::{fold_register, register_for_hash};
+use super::HyperLogLog;
+use crate::common::{Sketch, SketchError};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use twox_hash::XxHash64;
+
+/// Default load (estimate / registers) at which the precision is raised
+const DEFAULT_LOAD_THRESHOLD: f64 = 0.5;
+
+/// Approximate bytes per sparse map entry, used to decide when to go dense
+const SPARSE_ENTRY_BYTES: usize = 8;
+
+/// Register storage at `max_precision`
+#[derive(Clone, Debug)]
+enum Registers {
+    /// Non-zero registers only: index -> rank
+    Sparse(HashMap<u32, u8>),
+    /// All `2^max_precision` registers
+    Dense(Vec<u8>),
+}
+
+/// HyperLogLog whose reporting precision grows with the cardinality
+///
+/// Uses the same hashing as [`HyperLogLog`], so items added to both land in
+/// corresponding registers.
+///
+/// # Thread Safety
+///
+/// `AdaptiveHyperLogLog` is `Send + Sync`. Updates take `&mut self`; sketches
+/// filled on separate threads can be merged afterwards.
+#[derive(Clone, Debug)]
+pub struct AdaptiveHyperLogLog {
+    /// Precision the sketch starts reporting at
+    min_precision: u8,
+    /// Precision at which registers are recorded
+    max_precision: u8,
+    /// Precision the sketch currently reports at
+    current_precision: u8,
+    /// Load at which the current precision is raised
+    load_threshold: f64,
+    /// Number of non-zero registers at `max_precision`
+    nonzero: usize,
+    registers: Registers,
+}
+
+impl AdaptiveHyperLogLog {
+    /// Creates an adaptive sketch growing from `min_precision` to `max_precision`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if either precision is outside 4-18 or
+    /// `min_precision > max_precision`
+    pub fn new(min_precision: u8, max_precision: u8) -> Result<Self, SketchError> {
+        Self::with_threshold(min_precision, max_precision, DEFAULT_LOAD_THRESHOLD)
+    }
+
+    /// Creates an adaptive sketch with a custom load threshold
+    ///
+    /// The precision is raised while the estimate exceeds
+    /// `load_threshold * 2^current_precision`. Smaller thresholds grow the
+    /// precision earlier, trading memory in exported sketches for accuracy.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` for invalid precisions, or if
+    /// `load_threshold` is not positive and finite
+    pub fn with_threshold(
+        min_precision: u8,
+        max_precision: u8,
+        load_threshold: f64,
+    ) -> Result<Self, SketchError> {
+        // Reuse HyperLogLog's precision validation for both bounds
+        HyperLogLog::new(min_precision)?;
+        HyperLogLog::new(max_precision)?;
+        if min_precision > max_precision {
+            return Err(SketchError::InvalidParameter {
+                param: "min_precision".to_string(),
+                value: min_precision.to_string(),
+                constraint: format!("must not exceed max_precision {}", max_precision),
+            });
+        }
+        if !(load_threshold.is_finite() && load_threshold > 0.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "load_threshold".to_string(),
+                value: load_threshold.to_string(),
+                constraint: "must be positive and finite".to_string(),
+            });
+        }
+
+        Ok(AdaptiveHyperLogLog {
+            min_precision,
+            max_precision,
+            current_precision: min_precision,
+            load_threshold,
+            nonzero: 0,
+            registers: Registers::Sparse(HashMap::new()),
+        })
+    }
+
+    /// Updates the sketch with a hashable item
+    pub fn update<T: Hash>(&mut self, item: &T) {
+        let mut hasher = XxHash64::with_seed(0);
+        item.hash(&mut hasher);
+        self.update_hash(hasher.finish());
+    }
+
+    /// Updates the sketch with a pre-computed hash value
+    pub fn update_hash(&mut self, hash: u64) {
+        let (idx, rho) = register_for_hash(hash, self.max_precision);
+        self.set_register(idx, rho);
+        self.grow_precision();
+    }
+
+    /// Returns the precision the sketch currently reports at
+    pub fn current_precision(&self) -> u8 {
+        self.current_precision
+    }
+
+    /// Returns the precision the sketch started at
+    pub fn min_precision(&self) -> u8 {
+        self.min_precision
+    }
+
+    /// Returns the precision at which registers are recorded
+    pub fn max_precision(&self) -> u8 {
+        self.max_precision
+    }
+
+    /// Returns the load at which the current precision is raised
+    pub fn load_threshold(&self) -> f64 {
+        self.load_threshold
+    }
+
+    /// Returns an empty sketch with the same precisions and threshold
+    pub(crate) fn clone_empty(&self) -> Self {
+        AdaptiveHyperLogLog {
+            current_precision: self.min_precision,
+            nonzero: 0,
+            registers: Registers::Sparse(HashMap::new()),
+            ..*self
+        }
+    }
+
+    /// Returns true while registers are stored sparsely
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.registers, Registers::Sparse(_))
+    }
+
+    /// Returns the approximate bytes used by register storage
+    ///
+    /// Counts `8` bytes per sparse entry, or one byte per dense register.
+    pub fn memory_usage(&self) -> usize {
+        match &self.registers {
+            Registers::Sparse(map) => map.len() * SPARSE_ENTRY_BYTES,
+            Registers::Dense(regs) => regs.len(),
+        }
+    }
+
+    /// Returns the number of non-zero registers at max precision
+    pub fn num_nonzero(&self) -> usize {
+        self.nonzero
+    }
+
+    /// Returns true if no item has been added
+    pub fn is_empty(&self) -> bool {
+        self.nonzero == 0
+    }
+
+    /// Standard error at the current precision: 1.04 / sqrt(2^p)
+    pub fn standard_error(&self) -> f64 {
+        1.04 / ((1u64 << self.current_precision) as f64).sqrt()
+    }
+
+    /// Estimates the cardinality at the current precision
+    pub fn estimate(&self) -> f64 {
+        self.to_hyperloglog().estimate()
+    }
+
+    /// Returns a regular HyperLogLog at the current precision
+    pub fn to_hyperloglog(&self) -> HyperLogLog {
+        let precision = self.current_precision;
+        let shift = self.max_precision - precision;
+        let mut registers = vec![0u8; 1usize << precision];
+
+        let mut fold = |idx: usize, rho: u8| {
+            let (folded_idx, folded_rho) = fold_register(idx, rho, shift);
+            if folded_rho > registers[folded_idx] {
+                registers[folded_idx] = folded_rho;
+            }
+        };
+        match &self.registers {
+            Registers::Sparse(map) => map.iter().for_each(|(&idx, &rho)| fold(idx as usize, rho)),
+            Registers::Dense(regs) => regs
+                .iter()
+                .enumerate()
+                .filter(|(_, &rho)| rho != 0)
+                .for_each(|(idx, &rho)| fold(idx, rho)),
+        }
+
+        HyperLogLog::from_registers(precision, registers)
+    }
+
+    /// Merges another adaptive sketch into this one
+    ///
+    /// The result reports at the larger of the two current precisions, or
+    /// higher if the merged estimate calls for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the max precisions differ
+    pub fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        if self.max_precision != other.max_precision {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "Max precision mismatch: {} vs {}",
+                    self.max_precision, other.max_precision
+                ),
+            });
+        }
+
+        match &other.registers {
+            Registers::Sparse(map) => {
+                for (&idx, &rho) in map {
+                    self.set_register(idx as usize, rho);
+                }
+            }
+            Registers::Dense(regs) => {
+                for (idx, &rho) in regs.iter().enumerate().filter(|(_, &rho)| rho != 0) {
+                    self.set_register(idx, rho);
+                }
+            }
+        }
+        self.current_precision = self.current_precision.max(other.current_precision);
+        self.grow_precision();
+        Ok(())
+    }
+
+    /// Raises register `idx` (at max precision) to at least `rho`
+    fn set_register(&mut self, idx: usize, rho: u8) {
+        match &mut self.registers {
+            Registers::Sparse(map) => {
+                let entry = map.entry(idx as u32).or_insert(0);
+                if *entry == 0 {
+                    self.nonzero += 1;
+                }
+                if rho > *entry {
+                    *entry = rho;
+                }
+                if map.len() * SPARSE_ENTRY_BYTES > 1usize << self.max_precision {
+                    self.densify();
+                }
+            }
+            Registers::Dense(regs) => {
+                if regs[idx] == 0 {
+                    self.nonzero += 1;
+                }
+                if rho > regs[idx] {
+                    regs[idx] = rho;
+                }
+            }
+        }
+    }
+
+    /// Converts the sparse map into a dense register array
+    fn densify(&mut self) {
+        if let Registers::Sparse(map) = &self.registers {
+            let mut regs = vec![0u8; 1usize << self.max_precision];
+            for (&idx, &rho) in map {
+                regs[idx as usize] = rho;
+            }
+            self.registers = Registers::Dense(regs);
+        }
+    }
+
+    /// Raises the current precision while the load threshold is exceeded
+    ///
+    /// Uses linear counting over the non-zero registers at max precision,
+    /// which is O(1) and accurate well past every threshold below max.
+    fn grow_precision(&mut self) {
+        let m = (1usize << self.max_precision) as f64;
+        let zeros = m - self.nonzero as f64;
+        let estimate = if zeros > 0.0 {
+            m * (m / zeros).ln()
+        } else {
+            f64::INFINITY
+        };
+
+        while self.current_precision < self.max_precision
+            && estimate > self.load_threshold * (1u64 << self.current_precision) as f64
+        {
+            self.current_precision += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_sparse_at_min_precision() {
+        let mut hll = AdaptiveHyperLogLog::new(8, 14).unwrap();
+        assert!(hll.is_empty());
+        for i in 0..50 {
+            hll.update(&i);
+        }
+        assert!(hll.is_sparse());
+        assert_eq!(hll.current_precision(), 8);
+        assert!((hll.estimate() - 50.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_precision_grows_with_cardinality() {
+        let mut hll = AdaptiveHyperLogLog::new(6, 12).unwrap();
+        let mut last = hll.current_precision();
+        for i in 0..50_000 {
+            hll.update(&i);
+            assert!(hll.current_precision() >= last);
+            last = hll.current_precision();
+        }
+        assert_eq!(hll.current_precision(), 12);
+        assert!(!hll.is_sparse());
+        assert!((hll.estimate() - 50_000.0).abs() / 50_000.0 < 0.05);
+    }
+
+    #[test]
+    fn test_matches_folded_hyperloglog() {
+        let mut adaptive = AdaptiveHyperLogLog::new(6, 14).unwrap();
+        let mut full = HyperLogLog::new(14).unwrap();
+        for i in 0..3_000 {
+            adaptive.update(&i);
+            full.update(&i);
+        }
+
+        let folded = full.fold_to(adaptive.current_precision()).unwrap();
+        assert_eq!(adaptive.to_hyperloglog().registers(), folded.registers());
+        assert_eq!(adaptive.estimate(), folded.estimate());
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let mut hll = AdaptiveHyperLogLog::new(4, 10).unwrap();
+        assert_eq!(hll.memory_usage(), 0);
+        hll.update(&1);
+        assert_eq!(hll.memory_usage(), 8);
+        assert_eq!(hll.num_nonzero(), 1);
+
+        for i in 0..10_000 {
+            hll.update(&i);
+        }
+        assert!(!hll.is_sparse());
+        assert_eq!(hll.memory_usage(), 1024);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = AdaptiveHyperLogLog::new(6, 14).unwrap();
+        let mut b = AdaptiveHyperLogLog::new(6, 14).unwrap();
+        for i in 0..10 {
+            a.update(&i);
+        }
+        for i in 0..20_000 {
+            b.update(&i);
+        }
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.current_precision(), b.current_precision());
+        assert_eq!(
+            a.to_hyperloglog().registers(),
+            b.to_hyperloglog().registers()
+        );
+
+        let other = AdaptiveHyperLogLog::new(6, 12).unwrap();
+        assert!(a.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(AdaptiveHyperLogLog::new(3, 12).is_err());
+        assert!(AdaptiveHyperLogLog::new(4, 19).is_err());
+        assert!(AdaptiveHyperLogLog::new(12, 10).is_err());
+        assert!(AdaptiveHyperLogLog::with_threshold(4, 10, 0.0).is_err());
+        assert!(AdaptiveHyperLogLog::with_threshold(4, 10, f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,391 @@
+//! Compact Theta Sketch - read-only form for the query phase
+//!
+//! Mirrors the UpdateSketch/CompactSketch split of Apache DataSketches: once a
+//! [`ThetaSketch`](super::ThetaSketch) is built, [`ThetaSketch::compact`]
+//! freezes it into a sorted array of retained hashes. The compact form drops
+//! the hash table overhead, cannot be updated, and still answers estimates and
+//! set operations against other compact sketches.
+//!
+//! [`ThetaSketch::compact`]: super::ThetaSketch::compact
+//!
+//! # Serialization
+//!
+//! The image has the same size as [`ThetaSketch::compact_size_bytes`]: a
+//! 24-byte preamble followed by each retained hash as 8 little-endian bytes,
+//! in ascending order.
+//!
+//! ```text
+//! [magic: "THTC"][version: u8][lg_k: u8][reserved: 2 bytes]
+//! [seed: u64][theta: u64][hashes: u64 * num_retained]
+//! ```
+//!
+//! [`ThetaSketch::compact_size_bytes`]: super::ThetaSketch::compact_size_bytes
+
+use crate::common::validation;
+use crate::error::{Result, SketchError};
+use std::cmp::Ordering;
+
+/// Magic bytes prefixed to serialized compact Theta sketches
+const SERIAL_MAGIC: &[u8; 4] = b"THTC";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
+/// Preamble size: header, lg_k, padding, seed and theta
+const PREAMBLE_BYTES: usize = 24;
+
+/// Immutable Theta sketch holding its retained hashes in a sorted array.
+///
+/// # Thread Safety
+///
+/// `CompactThetaSketch` is `Send + Sync` and immutable, so it can be shared
+/// read-only through an `Arc` without locking.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::cardinality::ThetaSketch;
+///
+/// let mut a = ThetaSketch::new(12).unwrap();
+/// let mut b = ThetaSketch::new(12).unwrap();
+/// for i in 0..1000 {
+///     a.update(&i);
+///     b.update(&(i + 500));
+/// }
+///
+/// let (a, b) = (a.compact(), b.compact());
+/// let union = a.union(&b).unwrap();
+/// assert!((union.estimate() - 1500.0).abs() < 50.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompactThetaSketch {
+    /// log2(k) of the sketch this was compacted from
+    lg_k: u8,
+
+    /// Retained hashes, ascending, all < theta
+    entries: Box<[u64]>,
+
+    /// Sampling threshold
+    theta: u64,
+
+    /// Hash seed of the source sketch
+    seed: u64,
+}
+
+impl CompactThetaSketch {
+    /// Builds a compact sketch from retained hashes in any order.
+    pub(crate) fn from_parts(lg_k: u8, mut entries: Vec<u64>, theta: u64, seed: u64) -> Self {
+        entries.sort_unstable();
+        Self {
+            lg_k,
+            entries: entries.into_boxed_slice(),
+            theta,
+            seed,
+        }
+    }
+
+    /// Returns the estimated cardinality, identical to the source sketch's.
+    pub fn estimate(&self) -> f64 {
+        if self.entries.is_empty() {
+            return 0.0;
+        }
+
+        if self.theta == u64::MAX {
+            self.entries.len() as f64
+        } else {
+            self.entries.len() as f64 * (u64::MAX as f64 / self.theta as f64)
+        }
+    }
+
+    /// Returns true if the sketch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of retained entries.
+    pub fn num_retained(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the theta value.
+    pub fn get_theta(&self) -> u64 {
+        self.theta
+    }
+
+    /// Returns log2 of the nominal capacity.
+    pub fn lg_k(&self) -> u8 {
+        self.lg_k
+    }
+
+    /// Returns the approximate heap and inline size in bytes.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + 8 * self.entries.len()
+    }
+
+    /// Computes union with another compact sketch: |A ∪ B|
+    ///
+    /// Matches [`ThetaSketch::union`](super::ThetaSketch::union) on the
+    /// source sketches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the lg_k or seed values differ.
+    pub fn union(&self, other: &Self) -> Result<Self> {
+        self.check_compatibility(other)?;
+        let theta = self.theta.min(other.theta);
+        let entries = merge_sorted(&self.entries, &other.entries, theta, |a, b| a || b);
+        Ok(self.with_entries(entries, theta))
+    }
+
+    /// Computes intersection with another compact sketch: |A ∩ B|
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the lg_k or seed values differ.
+    pub fn intersect(&self, other: &Self) -> Result<Self> {
+        self.check_compatibility(other)?;
+        let theta = self.theta.min(other.theta);
+        let entries = merge_sorted(&self.entries, &other.entries, theta, |a, b| a && b);
+        Ok(self.with_entries(entries, theta))
+    }
+
+    /// Computes difference: |A - B| (items in A but not in B)
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the lg_k or seed values differ.
+    pub fn difference(&self, other: &Self) -> Result<Self> {
+        self.check_compatibility(other)?;
+        let theta = self.theta.min(other.theta);
+        let entries = merge_sorted(&self.entries, &other.entries, theta, |a, b| a && !b);
+        Ok(self.with_entries(entries, theta))
+    }
+
+    /// Serializes the sketch to its compact image.
+    ///
+    /// Hashes are written in ascending order and [`from_bytes`](Self::from_bytes)
+    /// accepts nothing else, so the image is canonical: reloading and
+    /// re-serializing it yields identical bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PREAMBLE_BYTES + 8 * self.entries.len());
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
+        bytes.push(self.lg_k);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.theta.to_le_bytes());
+        for &hash in self.entries.iter() {
+            bytes.extend_from_slice(&hash.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Returns a stable 64-bit hash of the sketch's content.
+    ///
+    /// XXHash64 of the canonical image from [`to_bytes`](Self::to_bytes), so
+    /// sketches with the same lg_k, seed, theta and retained hashes always
+    /// hash equal, including after a serialization round-trip. This is not a
+    /// cryptographic hash: distinct sketches can collide.
+    pub fn content_hash(&self) -> u64 {
+        crate::common::hash::xxhash(&self.to_bytes(), 0)
+    }
+
+    /// Deserializes a sketch written by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `DeserializationError` for truncated or corrupt input,
+    /// including hashes that are unsorted or not below theta.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        validation::validate_byte_size(bytes.len())?;
+        let body = validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?;
+        validation::validate_min_size(bytes.len(), PREAMBLE_BYTES)?;
+
+        let lg_k = body[0];
+        validation::validate_lg_k(lg_k)
+            .map_err(|e| SketchError::DeserializationError(e.to_string()))?;
+
+        let read_u64 = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let seed = read_u64(8);
+        let theta = read_u64(16);
+
+        let payload = &bytes[PREAMBLE_BYTES..];
+        if !payload.len().is_multiple_of(8) {
+            return Err(SketchError::DeserializationError(format!(
+                "Compact Theta payload of {} bytes is not a whole number of hashes",
+                payload.len()
+            )));
+        }
+
+        let entries: Vec<u64> = payload
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        if entries.windows(2).any(|w| w[0] >= w[1]) || entries.last().is_some_and(|&h| h >= theta) {
+            return Err(SketchError::DeserializationError(
+                "Compact Theta hashes must be strictly ascending and below theta".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            lg_k,
+            entries: entries.into_boxed_slice(),
+            theta,
+            seed,
+        })
+    }
+
+    fn with_entries(&self, entries: Vec<u64>, theta: u64) -> Self {
+        Self {
+            lg_k: self.lg_k,
+            entries: entries.into_boxed_slice(),
+            theta,
+            seed: self.seed,
+        }
+    }
+
+    fn check_compatibility(&self, other: &Self) -> Result<()> {
+        if self.lg_k != other.lg_k {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("lg_k mismatch: {} vs {}", self.lg_k, other.lg_k),
+            });
+        }
+
+        if self.seed != other.seed {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("seed mismatch: {} vs {}", self.seed, other.seed),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Merges two ascending hash arrays below `theta`, keeping each hash for
+/// which `keep(in_a, in_b)` holds.
+fn merge_sorted(a: &[u64], b: &[u64], theta: u64, keep: impl Fn(bool, bool) -> bool) -> Vec<u64> {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    loop {
+        let (hash, in_a, in_b) = match (a.get(i), b.get(j)) {
+            (Some(&x), Some(&y)) => match x.cmp(&y) {
+                Ordering::Less => (x, true, false),
+                Ordering::Greater => (y, false, true),
+                Ordering::Equal => (x, true, true),
+            },
+            (Some(&x), None) => (x, true, false),
+            (None, Some(&y)) => (y, false, true),
+            (None, None) => break,
+        };
+        if hash >= theta {
+            break;
+        }
+        i += in_a as usize;
+        j += in_b as usize;
+        if keep(in_a, in_b) {
+            out.push(hash);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cardinality::ThetaSketch;
+
+    fn sketch(range: std::ops::Range<u64>) -> ThetaSketch {
+        let mut sketch = ThetaSketch::new(10).unwrap();
+        for i in range {
+            sketch.update(&i);
+        }
+        sketch
+    }
+
+    #[test]
+    fn test_compact_preserves_estimate() {
+        for range in [0..0, 0..100, 0..50_000] {
+            let sketch = sketch(range);
+            let compact = sketch.compact();
+            assert_eq!(compact.estimate(), sketch.estimate());
+            assert_eq!(compact.num_retained(), sketch.num_retained());
+            assert_eq!(compact.get_theta(), sketch.get_theta());
+            assert_eq!(compact.is_empty(), sketch.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_compact_set_operations_match() {
+        // Exact mode and estimation mode with different thetas
+        for (ra, rb) in [(0..600, 300..900), (0..40_000, 10_000..80_000)] {
+            let (a, b) = (sketch(ra), sketch(rb));
+            let (ca, cb) = (a.compact(), b.compact());
+
+            assert_eq!(ca.union(&cb).unwrap(), a.union(&b).unwrap().compact());
+            assert_eq!(
+                ca.intersect(&cb).unwrap(),
+                a.intersect(&b).unwrap().compact()
+            );
+            assert_eq!(
+                ca.difference(&cb).unwrap(),
+                a.difference(&b).unwrap().compact()
+            );
+            assert_eq!(
+                cb.difference(&ca).unwrap(),
+                b.difference(&a).unwrap().compact()
+            );
+        }
+    }
+
+    #[test]
+    fn test_compact_incompatible() {
+        let a = ThetaSketch::new(10).unwrap().compact();
+        let b = ThetaSketch::new(12).unwrap().compact();
+        let c = ThetaSketch::with_seed(10, 1).unwrap().compact();
+        assert!(a.union(&b).is_err());
+        assert!(a.intersect(&c).is_err());
+    }
+
+    #[test]
+    fn test_compact_serialization() {
+        let sketch = sketch(0..20_000);
+        let compact = sketch.compact();
+        let bytes = compact.to_bytes();
+        assert_eq!(bytes.len(), sketch.compact_size_bytes());
+        assert_eq!(CompactThetaSketch::from_bytes(&bytes).unwrap(), compact);
+
+        let empty = ThetaSketch::new(10).unwrap().compact();
+        assert_eq!(
+            CompactThetaSketch::from_bytes(&empty.to_bytes()).unwrap(),
+            empty
+        );
+
+        for len in [0, 5, 23, bytes.len() - 3] {
+            assert!(CompactThetaSketch::from_bytes(&bytes[..len]).is_err());
+        }
+
+        // Out-of-order hashes are rejected
+        let mut swapped = bytes.clone();
+        let (first, second) = swapped[24..40].split_at_mut(8);
+        first.swap_with_slice(second);
+        assert!(CompactThetaSketch::from_bytes(&swapped).is_err());
+    }
+
+    #[test]
+    fn test_repeated_round_trips_are_byte_identical() {
+        for n in [0, 100, 20_000] {
+            let compact = sketch(0..n).compact();
+            let original = compact.to_bytes();
+
+            let mut bytes = original.clone();
+            for round in 0..100 {
+                let restored = CompactThetaSketch::from_bytes(&bytes).unwrap();
+                assert_eq!(restored, compact, "n={} round {}", n, round);
+                assert_eq!(restored.estimate(), compact.estimate());
+                bytes = restored.to_bytes();
+                assert_eq!(bytes, original, "n={} round {}", n, round);
+            }
+        }
+    }
+}
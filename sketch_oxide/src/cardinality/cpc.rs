@@ -40,13 +40,27 @@
 //! // Should be close to 10,000 with ~1.5% error
 //! ```
 
-use crate::common::{hash::hash_value, Mergeable, Sketch, SketchError};
+use crate::common::{
+    hash::{hash_bytes, hash_value},
+    validation, CardinalitySketch, LgK, Mergeable, Sketch, SketchError,
+};
 use std::collections::HashMap;
 
+/// Magic bytes prefixed to serialized CPC sketches
+const SERIAL_MAGIC: &[u8; 4] = b"CPCS";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
 /// CPC Sketch for cardinality estimation with maximum space efficiency
 ///
 /// Uses adaptive compression and multiple operational modes to minimize memory usage
 /// while maintaining accuracy comparable to HyperLogLog.
+///
+/// # Thread Safety
+///
+/// `CpcSketch` is `Send + Sync`. Updates take `&mut self`; guard a shared
+/// sketch with a `Mutex` or merge per-thread sketches.
 #[derive(Clone, Debug)]
 pub struct CpcSketch {
     /// Log2 of k parameter (4-26)
@@ -148,6 +162,23 @@ impl CpcSketch {
         })
     }
 
+    /// Creates a new CPC sketch from an already-validated lg_k
+    ///
+    /// Infallible counterpart of [`new`](Self::new); see [`LgK`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::CpcSketch;
+    /// use sketch_oxide::common::LgK;
+    ///
+    /// let cpc = CpcSketch::new_checked(LgK::L11);
+    /// assert_eq!(cpc.lg_k(), 11);
+    /// ```
+    pub fn new_checked(lg_k: LgK) -> Self {
+        Self::new(lg_k.get()).expect("LgK is validated on construction")
+    }
+
     /// Returns the lg_k parameter
     pub fn lg_k(&self) -> u8 {
         self.lg_k
@@ -164,6 +195,52 @@ impl CpcSketch {
         }
     }
 
+    /// Returns a JSON summary of parameters and state for debugging
+    ///
+    /// Reports lg_k, the current flavor, the coupon count and the estimate,
+    /// but not the coupon table itself.
+    /// Not meant for reconstruction; use the binary serialization for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::CpcSketch;
+    ///
+    /// let cpc = CpcSketch::new(10).unwrap();
+    /// assert!(cpc.to_json().starts_with(r#"{"type":"CpcSketch","lg_k":10,"flavor":"Empty","#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"type":"CpcSketch","lg_k":{},"flavor":"{}","num_coupons":{},"estimate":{}}}"#,
+            self.lg_k,
+            self.flavor(),
+            self.num_coupons,
+            Sketch::estimate(self)
+        )
+    }
+
+    /// Adds a byte string, hashed with [`hash_bytes`]
+    ///
+    /// This is the portable entry point: the language bindings all route byte
+    /// input through it, so the same bytes produce the same sketch in every
+    /// language. [`Sketch::update`] still takes a caller-hashed `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::CpcSketch;
+    /// use sketch_oxide::common::{hash::hash_bytes, Sketch};
+    ///
+    /// let mut a = CpcSketch::new(10).unwrap();
+    /// let mut b = CpcSketch::new(10).unwrap();
+    /// a.update_bytes(b"user-42");
+    /// b.update(&hash_bytes(b"user-42"));
+    /// assert_eq!(a.to_bytes(), b.to_bytes());
+    /// ```
+    pub fn update_bytes(&mut self, data: &[u8]) {
+        Sketch::update(self, &hash_bytes(data));
+    }
+
     /// Clear the sketch to empty state
     pub fn clear(&mut self) {
         self.num_coupons = 0;
@@ -214,11 +291,7 @@ impl CpcSketch {
         self.num_coupons += 1;
 
         // Check if we need to transition to next flavor
-        // Transition threshold is approximately 3*k/32 for sparse->hybrid
-        let sparse_threshold = (3 * self.k) / 32;
-        if self.surprising_values.len() as u32 > sparse_threshold {
-            self.transition_to_hybrid();
-        }
+        self.check_flavor_transition();
     }
 
     /// Update the sketch with a new item in hybrid/pinned/sliding modes
@@ -245,32 +318,38 @@ impl CpcSketch {
         self.flavor = Flavor::Sparse;
     }
 
-    /// Transition from Sparse to Hybrid
-    fn transition_to_hybrid(&mut self) {
-        self.flavor = Flavor::Hybrid;
-        // In full implementation, we'd start building the sliding window here
-        // For now, we keep using surprising_values
+    /// Occupancy above which `flavor` is promoted to the next flavor
+    ///
+    /// - Sparse -> Hybrid: 3k/32
+    /// - Hybrid -> Pinned: k/2
+    /// - Pinned -> Sliding: 3k/4 (this is where compression kicks in)
+    fn promotion_threshold(&self, flavor: &Flavor) -> Option<u32> {
+        match flavor {
+            Flavor::Sparse => Some((3 * self.k) / 32),
+            Flavor::Hybrid => Some(self.k / 2),
+            Flavor::Pinned => Some((3 * self.k) / 4),
+            Flavor::Empty | Flavor::Sliding => None,
+        }
     }
 
     /// Check if we need to transition flavors based on current state
+    ///
+    /// Promotes while occupancy exceeds the current flavor's threshold.
+    /// Occupancy only grows (updates and merges take the per-slot maximum),
+    /// so flavors only ever move up and a sketch near a threshold cannot
+    /// flip-flop between two of them.
     fn check_flavor_transition(&mut self) {
-        // Simplified flavor transition logic
-        // Full implementation would have more sophisticated thresholds
-        match self.flavor {
-            Flavor::Hybrid => {
-                // Transition to Pinned when we have many surprising values
-                if self.surprising_values.len() as u32 > self.k / 2 {
-                    self.flavor = Flavor::Pinned;
-                }
-            }
-            Flavor::Pinned => {
-                // Transition to Sliding when window is full enough
-                // This is where compression kicks in
-                if self.surprising_values.len() as u32 > (3 * self.k) / 4 {
-                    self.flavor = Flavor::Sliding;
-                }
+        let occupancy = self.surprising_values.len() as u32;
+
+        while let Some(threshold) = self.promotion_threshold(&self.flavor) {
+            if occupancy <= threshold {
+                return;
             }
-            _ => {}
+            self.flavor = match self.flavor {
+                Flavor::Sparse => Flavor::Hybrid,
+                Flavor::Hybrid => Flavor::Pinned,
+                _ => Flavor::Sliding,
+            };
         }
     }
 
@@ -373,14 +452,22 @@ impl CpcSketch {
     }
 
     /// Serialize the sketch to bytes
+    ///
+    /// The encoding is canonical: equal sketches serialize to equal bytes,
+    /// with surprising values written in ascending slot order. Deserializing
+    /// and serializing again reproduces the input exactly, so a sketch can
+    /// be round-tripped through storage any number of times without its
+    /// bytes, flavor or estimate drifting.
     pub fn to_bytes(&self) -> Vec<u8> {
         // Simple serialization format:
+        // [magic: "CPCS"][version: u8]
         // [lg_k: u8][flavor: u8][num_coupons: u64][window_offset: u8]
-        // [num_surprising: u32][surprising_values: (u32, u8)*]
+        // [num_surprising: u32][surprising_values: (u32, u8)* by ascending slot]
         // [window_len: u32][window_data: u8*]
 
         let mut bytes = Vec::new();
 
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
         bytes.push(self.lg_k);
         bytes.push(match self.flavor {
             Flavor::Empty => 0,
@@ -393,8 +480,14 @@ impl CpcSketch {
         bytes.push(self.window_offset);
 
         // Serialize surprising values
-        bytes.extend_from_slice(&(self.surprising_values.len() as u32).to_le_bytes());
-        for (&slot, &rho) in &self.surprising_values {
+        let mut surprising: Vec<(u32, u8)> = self
+            .surprising_values
+            .iter()
+            .map(|(&slot, &rho)| (slot, rho))
+            .collect();
+        surprising.sort_unstable();
+        bytes.extend_from_slice(&(surprising.len() as u32).to_le_bytes());
+        for (slot, rho) in surprising {
             bytes.extend_from_slice(&slot.to_le_bytes());
             bytes.push(rho);
         }
@@ -407,7 +500,15 @@ impl CpcSketch {
     }
 
     /// Deserialize a sketch from bytes
+    ///
+    /// Also reads bytes written before the magic/version header was added.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        // Headerless bytes start with lg_k, which can never be 'C'
+        let bytes = if bytes.starts_with(SERIAL_MAGIC) {
+            validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?
+        } else {
+            bytes
+        };
         if bytes.len() < 14 {
             return Err(SketchError::DeserializationError(
                 "Buffer too small for CPC sketch".to_string(),
@@ -415,6 +516,8 @@ impl CpcSketch {
         }
 
         let lg_k = bytes[0];
+        validation::validate_lg_k(lg_k)
+            .map_err(|e| SketchError::DeserializationError(e.to_string()))?;
         let flavor_byte = bytes[1];
         let num_coupons = u64::from_le_bytes(bytes[2..10].try_into().unwrap());
         let window_offset = bytes[10];
@@ -453,7 +556,12 @@ impl CpcSketch {
             }
             let slot = u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap());
             let rho = bytes[pos + 4];
-            surprising_values.insert(slot, rho);
+            if surprising_values.insert(slot, rho).is_some() {
+                return Err(SketchError::DeserializationError(format!(
+                    "Duplicate surprising value for slot {}",
+                    slot
+                )));
+            }
             pos += 5;
         }
 
@@ -588,6 +696,16 @@ impl Mergeable for CpcSketch {
     }
 }
 
+impl CardinalitySketch for CpcSketch {
+    fn cardinality(&self) -> f64 {
+        Sketch::estimate(self)
+    }
+
+    fn merge_from(&mut self, other: &Self) -> Result<(), SketchError> {
+        self.merge(other)
+    }
+}
+
 impl Default for CpcSketch {
     fn default() -> Self {
         Self::new(11).expect("Default lg_k should be valid")
@@ -640,6 +758,54 @@ mod tests {
         assert_eq!(cpc.flavor(), "Sparse");
     }
 
+    #[test]
+    fn test_flavor_only_promotes() {
+        let order = ["Empty", "Sparse", "Hybrid", "Pinned", "Sliding"];
+        let rank = |cpc: &CpcSketch| order.iter().position(|&f| f == cpc.flavor()).unwrap();
+
+        // Every update near a threshold either keeps the flavor or promotes it
+        let mut cpc = CpcSketch::new(8).unwrap();
+        let mut transitions = 0;
+        for i in 0..5000u64 {
+            let before = rank(&cpc);
+            cpc.update(&i);
+            assert!(rank(&cpc) >= before, "demoted at update {}", i);
+            if rank(&cpc) != before {
+                transitions += 1;
+            }
+        }
+        assert_eq!(cpc.flavor(), "Sliding");
+        assert!(transitions <= 4, "Flavor changed {} times", transitions);
+
+        // Merging a small sketch into a large one keeps the larger flavor
+        let mut small = CpcSketch::new(8).unwrap();
+        small.update(&1_000_000u64);
+        small.merge(&cpc).unwrap();
+        assert_eq!(small.flavor(), "Sliding");
+        cpc.merge(&small).unwrap();
+        assert_eq!(cpc.flavor(), "Sliding");
+    }
+
+    #[test]
+    fn test_reads_sketches_serialized_without_header() {
+        // lg_k 4 with 5 items, as serialized before the magic/version header
+        let hex = "040205000000000000000005000000060000000305000000030c000000020a000000030d0000000700000000";
+        let legacy: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        let mut expected = CpcSketch::new(4).unwrap();
+        for i in 0..5u64 {
+            expected.update(&i);
+        }
+
+        // Earlier versions wrote surprising values in hash map order
+        let cpc = CpcSketch::from_bytes(&legacy).unwrap();
+        assert_eq!(cpc.to_bytes(), expected.to_bytes());
+        assert_eq!(cpc.estimate(), expected.estimate());
+    }
+
     #[test]
     fn test_clear() {
         let mut cpc = CpcSketch::new(10).unwrap();
@@ -648,4 +814,18 @@ mod tests {
         assert!(cpc.is_empty());
         assert_eq!(cpc.estimate(), 0.0);
     }
+
+    #[test]
+    fn test_to_json() {
+        let mut cpc = CpcSketch::new(10).unwrap();
+        assert_eq!(
+            cpc.to_json(),
+            r#"{"type":"CpcSketch","lg_k":10,"flavor":"Empty","num_coupons":0,"estimate":0}"#
+        );
+
+        cpc.update(&7u64);
+        assert!(cpc
+            .to_json()
+            .contains(r#""flavor":"Sparse","num_coupons":1,"#));
+    }
 }
@@ -0,0 +1,309 @@
+//! Distinct Counter: exact counts for small streams, CPC for large ones
+//!
+//! Picking between HyperLogLog, CPC and Theta takes some background. This
+//! counter makes the choice itself:
+//!
+//! 1. Up to `threshold` distinct items, it keeps the 64-bit hash of every
+//!    item in a `HashSet` and reports the exact count (barring 64-bit hash
+//!    collisions, which are negligible at these sizes).
+//! 2. When the set would exceed `threshold`, the stored hashes are replayed
+//!    into a [`CpcSketch`] and the set is dropped. From then on the counter
+//!    is an estimate with CPC's error, about 2% at the default `lg_k` of 11.
+//!
+//! The default threshold of 1024 is roughly where the hash set and the CPC
+//! sketch use the same memory, so small streams get exact answers for free.
+//!
+//! # Cost
+//!
+//! Convenience over raw speed: every item is hashed once and, while exact,
+//! inserted into a hash set, which is slower than a register update. The
+//! promotion replays `threshold` hashes once. Callers that know their
+//! cardinality is large can use [`CpcSketch`] or [`HyperLogLog`]
+//! directly.
+//!
+//! Items hash exactly as in [`CpcSketch::update_bytes`], so a promoted
+//! counter matches a `CpcSketch` fed the same bytes.
+//!
+//! [`HyperLogLog`]: super::HyperLogLog
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::cardinality::DistinctCounter;
+//!
+//! let mut counter = DistinctCounter::new();
+//! for user in ["alice", "bob", "alice"] {
+//!     counter.add(user.as_bytes());
+//! }
+//! assert_eq!(counter.estimate(), 2.0);
+//! assert!(counter.is_exact());
+//!
+//! for i in 0u32..100_000 {
+//!     counter.add(&i.to_le_bytes());
+//! }
+//! assert!(!counter.is_exact());
+//! assert!((counter.estimate() - 100_002.0).abs() / 100_002.0 < 0.05);
+//! ```
+
+use super::CpcSketch;
+use crate::common::{hash::hash_bytes, Mergeable, Sketch, SketchError};
+use std::collections::HashSet;
+
+/// Default number of distinct items counted exactly
+const DEFAULT_THRESHOLD: usize = 1024;
+
+/// Default CPC lg_k after promotion (~2% error)
+const DEFAULT_LG_K: u8 = 11;
+
+#[derive(Clone, Debug)]
+enum State {
+    /// Hashes of every distinct item seen
+    Exact(HashSet<u64>),
+    /// Promoted sketch
+    Sketch(CpcSketch),
+}
+
+/// Distinct count that is exact below a threshold and a CPC sketch above it
+///
+/// # Thread Safety
+///
+/// `DistinctCounter` is `Send + Sync`. Updates take `&mut self`; counters
+/// filled on separate threads can be merged afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::cardinality::DistinctCounter;
+///
+/// let mut a = DistinctCounter::with_threshold(100, 12).unwrap();
+/// let mut b = DistinctCounter::with_threshold(100, 12).unwrap();
+/// for i in 0u32..60 {
+///     a.add(&i.to_le_bytes());
+///     b.add(&(i + 30).to_le_bytes());
+/// }
+///
+/// a.merge(&b).unwrap();
+/// assert_eq!(a.estimate(), 90.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DistinctCounter {
+    /// Largest distinct count kept exactly
+    threshold: usize,
+    /// lg_k of the CPC sketch used after promotion
+    lg_k: u8,
+    state: State,
+}
+
+impl DistinctCounter {
+    /// Creates a counter with the default threshold (1024) and lg_k (11)
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_THRESHOLD, DEFAULT_LG_K).expect("default parameters are valid")
+    }
+
+    /// Creates a counter with a custom promotion threshold and CPC size
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Largest distinct count kept exactly; 0 starts with
+    ///   the sketch
+    /// * `lg_k` - CPC lg_k used after promotion (4-26), see
+    ///   [`CpcSketch::new`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `lg_k` is out of range
+    pub fn with_threshold(threshold: usize, lg_k: u8) -> Result<Self, SketchError> {
+        let sketch = CpcSketch::new(lg_k)?;
+        let state = if threshold == 0 {
+            State::Sketch(sketch)
+        } else {
+            State::Exact(HashSet::new())
+        };
+        Ok(DistinctCounter {
+            threshold,
+            lg_k,
+            state,
+        })
+    }
+
+    /// Adds an item
+    pub fn add(&mut self, item: &[u8]) {
+        let hash = hash_bytes(item);
+        match &mut self.state {
+            State::Exact(hashes) => {
+                if hashes.insert(hash) && hashes.len() > self.threshold {
+                    self.promote();
+                }
+            }
+            State::Sketch(sketch) => sketch.update(&hash),
+        }
+    }
+
+    /// Returns the number of distinct items, exact while [`is_exact`](Self::is_exact)
+    pub fn estimate(&self) -> f64 {
+        match &self.state {
+            State::Exact(hashes) => hashes.len() as f64,
+            State::Sketch(sketch) => sketch.estimate(),
+        }
+    }
+
+    /// Returns true while the count is exact (not yet promoted to CPC)
+    pub fn is_exact(&self) -> bool {
+        matches!(self.state, State::Exact(_))
+    }
+
+    /// Returns the largest distinct count kept exactly
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the lg_k of the CPC sketch used after promotion
+    pub fn lg_k(&self) -> u8 {
+        self.lg_k
+    }
+
+    /// Returns true if no items have been added
+    pub fn is_empty(&self) -> bool {
+        match &self.state {
+            State::Exact(hashes) => hashes.is_empty(),
+            State::Sketch(sketch) => sketch.is_empty(),
+        }
+    }
+
+    /// Merges another counter into this one
+    ///
+    /// Two exact counters stay exact while their union fits this counter's
+    /// threshold. Otherwise the result is promoted and the CPC sketches are
+    /// merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the counters use different `lg_k`
+    pub fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        if self.lg_k != other.lg_k {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "Cannot merge distinct counters with different lg_k: {} vs {}",
+                    self.lg_k, other.lg_k
+                ),
+            });
+        }
+
+        match &other.state {
+            State::Exact(theirs) => {
+                match &mut self.state {
+                    State::Exact(ours) => ours.extend(theirs),
+                    State::Sketch(sketch) => theirs.iter().for_each(|hash| sketch.update(hash)),
+                }
+                if matches!(&self.state, State::Exact(ours) if ours.len() > self.threshold) {
+                    self.promote();
+                }
+            }
+            State::Sketch(theirs) => {
+                self.promote();
+                if let State::Sketch(sketch) = &mut self.state {
+                    sketch.merge(theirs)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replays the exact hashes into a CPC sketch
+    fn promote(&mut self) {
+        if let State::Exact(hashes) = &self.state {
+            let mut sketch = CpcSketch::new(self.lg_k).expect("lg_k validated in constructor");
+            for hash in hashes {
+                sketch.update(hash);
+            }
+            self.state = State::Sketch(sketch);
+        }
+    }
+}
+
+impl Default for DistinctCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_below_threshold() {
+        let mut counter = DistinctCounter::new();
+        for round in 0..3 {
+            for i in 0u32..1024 {
+                counter.add(&i.to_le_bytes());
+            }
+            assert!(counter.is_exact(), "promoted in round {}", round);
+            assert_eq!(counter.estimate(), 1024.0);
+        }
+
+        // The 1025th distinct item crosses the threshold
+        counter.add(b"one more");
+        assert!(!counter.is_exact());
+        assert!((counter.estimate() - 1025.0).abs() / 1025.0 < 0.05);
+    }
+
+    #[test]
+    fn test_accurate_above_threshold() {
+        let mut counter = DistinctCounter::new();
+        let mut cpc = CpcSketch::new(DEFAULT_LG_K).unwrap();
+        for i in 0u64..200_000 {
+            counter.add(&i.to_le_bytes());
+            cpc.update_bytes(&i.to_le_bytes());
+        }
+
+        let error = (counter.estimate() - 200_000.0).abs() / 200_000.0;
+        assert!(error < 0.05, "relative error {}", error);
+        // Promotion replays hashes, so the result matches a plain CPC sketch
+        assert_eq!(counter.estimate(), cpc.estimate());
+    }
+
+    #[test]
+    fn test_merge_across_states() {
+        let fill = |range: std::ops::Range<u32>| {
+            let mut counter = DistinctCounter::with_threshold(500, 11).unwrap();
+            range.for_each(|i| counter.add(&i.to_le_bytes()));
+            counter
+        };
+
+        // Exact + exact, union still under the threshold
+        let mut a = fill(0..200);
+        a.merge(&fill(100..300)).unwrap();
+        assert!(a.is_exact());
+        assert_eq!(a.estimate(), 300.0);
+
+        // Exact + exact, union over the threshold
+        a.merge(&fill(300..600)).unwrap();
+        assert!(!a.is_exact());
+        assert!((a.estimate() - 600.0).abs() / 600.0 < 0.05);
+
+        // Exact + sketch and sketch + exact
+        let mut small = fill(0..100);
+        small.merge(&fill(0..10_000)).unwrap();
+        assert!(!small.is_exact());
+        let mut big = fill(0..10_000);
+        big.merge(&fill(9_900..10_100)).unwrap();
+        assert!((big.estimate() - 10_100.0).abs() / 10_100.0 < 0.05);
+        assert!((small.estimate() - 10_000.0).abs() / 10_000.0 < 0.05);
+
+        let other = DistinctCounter::with_threshold(500, 12).unwrap();
+        assert!(a.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_zero_threshold_and_validation() {
+        let mut counter = DistinctCounter::with_threshold(0, 11).unwrap();
+        assert!(!counter.is_exact());
+        assert!(counter.is_empty());
+        counter.add(b"x");
+        assert!(!counter.is_empty());
+
+        assert!(DistinctCounter::with_threshold(100, 3).is_err());
+        assert!(DistinctCounter::with_threshold(100, 27).is_err());
+    }
+}
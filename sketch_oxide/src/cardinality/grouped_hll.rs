@@ -0,0 +1,257 @@
+//! Grouped HyperLogLog: approximate `COUNT(DISTINCT item) GROUP BY group`
+//!
+//! A `HashMap<group, HyperLogLog>` pays for `2^p` registers per group, which
+//! adds up with millions of groups. `GroupedHll` stores each group as an
+//! [`AdaptiveHyperLogLog`], so a group with a handful of items costs a few
+//! bytes per item, and keeps the total under a global memory budget.
+//!
+//! # Eviction Policy
+//!
+//! Memory is the register storage of every group plus a fixed per-group
+//! overhead for the map entry. When an update pushes it over the budget:
+//!
+//! 1. Groups are ranked by the number of registers they touch, which is
+//!    proportional to both their memory and their distinct count.
+//! 2. The smallest groups are merged into a shared *other* bucket and dropped,
+//!    until usage falls to 90% of the budget. Evicting in batches amortises
+//!    the ranking over many updates.
+//!
+//! After eviction, [`estimate`](GroupedHll::estimate) returns 0 for the
+//! evicted group until it receives new items. It then starts over, so its
+//! estimate only covers items seen since. The evicted items still count
+//! towards [`other_estimate`](GroupedHll::other_estimate), so no distinct item
+//! is lost from the overall total. Large groups are never evicted while
+//! smaller ones remain.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::cardinality::GroupedHll;
+//!
+//! let mut grouped = GroupedHll::new(12, 1 << 20).unwrap();
+//! for user in 0..1000 {
+//!     grouped.update(&"page_a", &user);
+//!     grouped.update(&"page_b", &(user % 10));
+//! }
+//!
+//! assert!((grouped.estimate(&"page_a") - 1000.0).abs() < 50.0);
+//! assert!((grouped.estimate(&"page_b") - 10.0).abs() < 3.0);
+//! assert_eq!(grouped.estimate(&"page_c"), 0.0);
+//! ```
+
+use super::AdaptiveHyperLogLog;
+use crate::common::SketchError;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Precision each group starts reporting at
+const GROUP_MIN_PRECISION: u8 = 4;
+
+/// Fraction of the budget usage is brought down to when evicting
+const EVICTION_TARGET: f64 = 0.9;
+
+/// Distinct counts per group under a global memory budget
+///
+/// Groups that do not fit are folded into an *other* bucket; see the module
+/// documentation for the eviction policy.
+///
+/// # Thread Safety
+///
+/// `GroupedHll<K>` is `Send + Sync` whenever the group key `K` is.
+#[derive(Clone, Debug)]
+pub struct GroupedHll<K> {
+    /// Max precision of every group sketch
+    precision: u8,
+    /// Budget in bytes for all groups
+    memory_budget: usize,
+    /// Current memory usage in bytes
+    memory_usage: usize,
+    groups: HashMap<K, AdaptiveHyperLogLog>,
+    /// Union of all evicted groups
+    other: AdaptiveHyperLogLog,
+    /// Number of groups evicted so far
+    evicted_groups: u64,
+}
+
+impl<K: Hash + Eq + Clone> GroupedHll<K> {
+    /// Creates a grouped sketch with per-group `precision` and a memory budget
+    ///
+    /// # Arguments
+    /// * `precision` - Max precision of each group's HyperLogLog (4-18)
+    /// * `memory_budget` - Bytes available for all groups together
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the precision is out of range or the
+    /// budget cannot hold a single group
+    pub fn new(precision: u8, memory_budget: usize) -> Result<Self, SketchError> {
+        let other = AdaptiveHyperLogLog::new(GROUP_MIN_PRECISION.min(precision), precision)?;
+        if memory_budget < Self::group_overhead() {
+            return Err(SketchError::InvalidParameter {
+                param: "memory_budget".to_string(),
+                value: memory_budget.to_string(),
+                constraint: format!("must be at least {} bytes", Self::group_overhead()),
+            });
+        }
+
+        Ok(GroupedHll {
+            precision,
+            memory_budget,
+            memory_usage: 0,
+            groups: HashMap::new(),
+            other,
+            evicted_groups: 0,
+        })
+    }
+
+    /// Adds `item` to the distinct set of `group`
+    pub fn update<T: Hash>(&mut self, group: &K, item: &T) {
+        match self.groups.get_mut(group) {
+            Some(sketch) => {
+                let before = sketch.memory_usage();
+                sketch.update(item);
+                self.memory_usage = self.memory_usage - before + sketch.memory_usage();
+            }
+            None => {
+                let mut sketch = self.other.clone_empty();
+                sketch.update(item);
+                self.memory_usage += Self::group_overhead() + sketch.memory_usage();
+                self.groups.insert(group.clone(), sketch);
+            }
+        }
+
+        if self.memory_usage > self.memory_budget {
+            self.evict();
+        }
+    }
+
+    /// Estimated distinct count of `group`, or 0 if it is not tracked
+    pub fn estimate(&self, group: &K) -> f64 {
+        self.groups
+            .get(group)
+            .map_or(0.0, |sketch| sketch.estimate())
+    }
+
+    /// Estimated distinct count over all evicted groups combined
+    pub fn other_estimate(&self) -> f64 {
+        self.other.estimate()
+    }
+
+    /// Returns true if `group` is currently tracked
+    pub fn contains_group(&self, group: &K) -> bool {
+        self.groups.contains_key(group)
+    }
+
+    /// Number of tracked groups
+    pub fn num_groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Number of groups evicted into the other bucket so far
+    pub fn evicted_groups(&self) -> u64 {
+        self.evicted_groups
+    }
+
+    /// Approximate bytes used by the tracked groups
+    ///
+    /// Excludes the other bucket, whose size is bounded by `2^precision`.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// Memory budget in bytes
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    /// Max precision of each group sketch
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Iterates over tracked groups and their estimates
+    pub fn iter(&self) -> impl Iterator<Item = (&K, f64)> {
+        self.groups
+            .iter()
+            .map(|(group, sketch)| (group, sketch.estimate()))
+    }
+
+    /// Fixed cost counted for each group's map entry and sketch header
+    fn group_overhead() -> usize {
+        std::mem::size_of::<K>() + std::mem::size_of::<AdaptiveHyperLogLog>()
+    }
+
+    /// Merges the smallest groups into the other bucket until usage is
+    /// back under the eviction target
+    fn evict(&mut self) {
+        let target = (self.memory_budget as f64 * EVICTION_TARGET) as usize;
+
+        let mut ranked: Vec<(usize, K)> = self
+            .groups
+            .iter()
+            .map(|(group, sketch)| (sketch.num_nonzero(), group.clone()))
+            .collect();
+        ranked.sort_unstable_by_key(|(size, _)| *size);
+
+        for (_, group) in ranked {
+            if self.memory_usage <= target {
+                break;
+            }
+            if let Some(sketch) = self.groups.remove(&group) {
+                self.memory_usage -= Self::group_overhead() + sketch.memory_usage();
+                self.other
+                    .merge(&sketch)
+                    .expect("groups share the other bucket's precision");
+                self.evicted_groups += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_estimates() {
+        let mut grouped = GroupedHll::new(12, 1 << 20).unwrap();
+        for i in 0..5000u64 {
+            grouped.update(&1u32, &i);
+            grouped.update(&2u32, &(i % 100));
+        }
+
+        assert_eq!(grouped.num_groups(), 2);
+        assert!((grouped.estimate(&1) - 5000.0).abs() / 5000.0 < 0.05);
+        assert!((grouped.estimate(&2) - 100.0).abs() < 5.0);
+        assert_eq!(grouped.evicted_groups(), 0);
+    }
+
+    #[test]
+    fn test_budget_evicts_smallest_groups() {
+        let budget = 64 * 1024;
+        let mut grouped = GroupedHll::new(10, budget).unwrap();
+
+        // One large group, then many tiny ones
+        for i in 0..2000u64 {
+            grouped.update(&0u64, &i);
+        }
+        for group in 1..5000u64 {
+            grouped.update(&group, &group);
+        }
+
+        assert!(grouped.memory_usage() <= budget);
+        assert!(grouped.evicted_groups() > 0);
+        assert!(grouped.contains_group(&0));
+        assert!((grouped.estimate(&0) - 2000.0).abs() / 2000.0 < 0.1);
+
+        // Evicted groups contribute to the other bucket
+        let evicted = grouped.evicted_groups() as f64;
+        assert!((grouped.other_estimate() - evicted).abs() / evicted < 0.1);
+        assert_eq!(grouped.num_groups() as u64 + grouped.evicted_groups(), 5000);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(GroupedHll::<u64>::new(3, 1 << 20).is_err());
+        assert!(GroupedHll::<u64>::new(12, 1).is_err());
+    }
+}
@@ -51,15 +51,53 @@
 //! // Should be close to 10,000 with ~1.04/sqrt(4096) ≈ 1.6% error
 //! ```
 
-use crate::common::{validation, Mergeable, Sketch, SketchError};
+use crate::common::{validation, CardinalitySketch, Mergeable, Precision, Sketch, SketchError};
 use std::hash::{Hash, Hasher};
 use twox_hash::XxHash64;
 
+/// Cardinality estimator applied to HyperLogLog registers
+///
+/// All estimators read the same registers, so they can be compared on one
+/// sketch with [`HyperLogLog::estimate_with`]. Their relative standard error
+/// is close to `1.04 / sqrt(m)` at large cardinalities; they differ in bias
+/// around the switch from linear counting (near `2.5 * m`) and in cost.
+///
+/// | Estimator | Bias near `2.5 * m` | Cost |
+/// |-----------|---------------------|------|
+/// | `Original` | Visible bump where linear counting hands over | O(m) |
+/// | `Classic32BitCorrected` | Same as `Original` | O(m) |
+/// | `MaximumLikelihood` | None; smooth across all ranges | O(m) + a few iterations |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Estimator {
+    /// Flajolet et al. (2007): bias-corrected harmonic mean of `2^-register`,
+    /// replaced by linear counting when the raw estimate is at most `2.5 * m`
+    /// and some register is empty. No large-range correction, as 64-bit
+    /// hashes do not saturate.
+    Original,
+    /// The default used by [`Sketch::estimate`]: `Original` plus the
+    /// large-range correction for 32-bit hash spaces, kept so estimates stay
+    /// comparable with existing HyperLogLog deployments. Identical to
+    /// `Original` below about 143 million distinct items. This is not
+    /// HyperLogLog++, which needs empirical bias tables.
+    #[default]
+    Classic32BitCorrected,
+    /// Ertl (2017) maximum-likelihood estimate from the register histogram,
+    /// solved with the secant method. Unbiased across the small-to-large
+    /// transition and slightly more accurate overall.
+    MaximumLikelihood,
+}
+
 /// HyperLogLog sketch for cardinality estimation
 ///
 /// Uses 2^p registers (each 8 bits) to estimate the number of unique items.
 /// Higher precision means more accuracy but more memory usage.
 ///
+/// # Thread Safety
+///
+/// `HyperLogLog` is `Send + Sync`. Estimates take `&self`; for concurrent
+/// writers, give each thread its own sketch and merge them, which is exact for
+/// register maxima.
+///
 /// # Examples
 ///
 /// ```
@@ -139,6 +177,61 @@ impl HyperLogLog {
         })
     }
 
+    /// Creates a new HyperLogLog sketch from an already-validated precision
+    ///
+    /// Infallible counterpart of [`new`](Self::new) for precisions fixed in
+    /// code; see [`Precision`] for compile-time validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::common::Precision;
+    ///
+    /// let hll = HyperLogLog::new_checked(Precision::P14);
+    /// assert_eq!(hll.precision(), 14);
+    /// ```
+    pub fn new_checked(precision: Precision) -> Self {
+        HyperLogLog {
+            precision: precision.get(),
+            registers: vec![0u8; 1usize << precision.get()],
+        }
+    }
+
+    /// Creates the most precise HyperLogLog whose registers fit in `bytes`
+    ///
+    /// Registers take one byte each, so this picks the largest precision `p`
+    /// with `2^p <= bytes`, capped at [`MAX_PRECISION`](Self::MAX_PRECISION).
+    /// The relative standard error is then about `1.04 / sqrt(2^p)`: an 8 KB
+    /// budget gives precision 13 and ~1.15%. The budget covers the register
+    /// array only; [`to_bytes`](Self::to_bytes) adds a one-byte header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `bytes` is below the 16 registers of
+    /// [`MIN_PRECISION`](Self::MIN_PRECISION).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    ///
+    /// let hll = HyperLogLog::for_byte_budget(8 * 1024).unwrap();
+    /// assert_eq!(hll.precision(), 13);
+    /// ```
+    pub fn for_byte_budget(bytes: usize) -> Result<Self, SketchError> {
+        match bytes.checked_ilog2() {
+            Some(p) if p >= Self::MIN_PRECISION as u32 => {
+                Self::new(p.min(Self::MAX_PRECISION as u32) as u8)
+            }
+            _ => Err(SketchError::InvalidParameter {
+                param: "bytes".to_string(),
+                value: bytes.to_string(),
+                constraint: format!("must be at least {}", 1usize << Self::MIN_PRECISION),
+            }),
+        }
+    }
+
     /// Returns the precision parameter
     #[inline]
     pub fn precision(&self) -> u8 {
@@ -188,6 +281,27 @@ impl HyperLogLog {
         self.update_hash(hash);
     }
 
+    /// Adds a `u64` by hashing its 8 little-endian bytes
+    ///
+    /// Equivalent to `update(&value.to_le_bytes())`, which is what the language
+    /// bindings do with a byte buffer, so numeric IDs added from Rust and from JavaScript or Python land in the same registers.
+    /// This differs from `update(&value)`, which hashes through `Hash for u64`.
+    pub fn update_u64(&mut self, value: u64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `i64` by hashing its 8 little-endian two's-complement bytes
+    pub fn update_i64(&mut self, value: i64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `f64` by hashing the 8 little-endian bytes of its IEEE 754 bits
+    ///
+    /// `0.0` and `-0.0`, like NaNs with different payloads, count as distinct.
+    pub fn update_f64(&mut self, value: f64) {
+        self.update(&value.to_le_bytes());
+    }
+
     /// Hashes an item to a 64-bit value using XXHash64 (faster than DefaultHasher)
     #[inline(always)]
     fn hash_item<T: Hash>(&self, item: &T) -> u64 {
@@ -198,18 +312,187 @@ impl HyperLogLog {
 
     /// Updates the sketch with a pre-computed hash value
     ///
-    /// This is useful when you already have a hash or want to use a specific hash function.
+    /// Skips the internal hasher: the top `precision` bits of `hash` select
+    /// the register and the leading zeros of the remaining bits give the
+    /// rank. Use it when items are hashed once upstream and fed to several
+    /// sketches, or to plug in a specific hash function.
+    ///
+    /// The caller must supply a well-distributed 64-bit hash, such as
+    /// XXHash64 or a SplitMix64-finalized value. Raw IDs, sequential
+    /// counters or hashes with fewer than 64 random bits leave registers
+    /// unused or inflate ranks, and the estimate is then meaningless.
+    /// [`update`](Self::update) is equivalent to `update_hash` with the
+    /// item's XXHash64 (seed 0), so both can feed the same sketch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::Sketch;
+    ///
+    /// let mut a = HyperLogLog::new(12).unwrap();
+    /// let mut b = HyperLogLog::new(14).unwrap();
+    /// for i in 0..10_000u64 {
+    ///     // SplitMix64 finalizer: one hash shared by both sketches
+    ///     let mut z = i.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    ///     z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    ///     z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    ///     let hash = z ^ (z >> 31);
+    ///
+    ///     a.update_hash(hash);
+    ///     b.update_hash(hash);
+    /// }
+    ///
+    /// assert!((a.estimate() - 10_000.0).abs() < 500.0);
+    /// assert!((b.estimate() - 10_000.0).abs() < 250.0);
+    /// ```
     #[inline]
     pub fn update_hash(&mut self, hash: u64) {
-        let idx = (hash >> (64 - self.precision)) as usize;
-        let w = hash << self.precision | (1u64 << (self.precision - 1));
-        let rho = (w.leading_zeros() + 1) as u8;
+        let (idx, rho) = register_for_hash(hash, self.precision);
 
         if rho > self.registers[idx] {
             self.registers[idx] = rho;
         }
     }
 
+    /// Builds a sketch from registers at a known-valid precision
+    pub(crate) fn from_registers(precision: u8, registers: Vec<u8>) -> Self {
+        debug_assert_eq!(registers.len(), 1usize << precision);
+        HyperLogLog {
+            precision,
+            registers,
+        }
+    }
+
+    /// Estimates the cardinality with a specific [`Estimator`]
+    ///
+    /// [`Sketch::estimate`] is equivalent to
+    /// `estimate_with(Estimator::Classic32BitCorrected)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::{Estimator, HyperLogLog};
+    ///
+    /// let mut hll = HyperLogLog::new(12).unwrap();
+    /// for i in 0..10_000 {
+    ///     hll.update(&i);
+    /// }
+    ///
+    /// for estimator in [Estimator::Original, Estimator::MaximumLikelihood] {
+    ///     let estimate = hll.estimate_with(estimator);
+    ///     assert!((estimate - 10_000.0).abs() / 10_000.0 < 0.05);
+    /// }
+    /// ```
+    pub fn estimate_with(&self, estimator: Estimator) -> f64 {
+        match estimator {
+            Estimator::Original => self.original_estimate(),
+            Estimator::Classic32BitCorrected => {
+                let estimate = self.original_estimate();
+
+                // Large range correction for 32-bit hash spaces
+                let two_pow_32 = (1u64 << 32) as f64;
+                if estimate > two_pow_32 / 30.0 {
+                    return -two_pow_32 * (1.0 - estimate / two_pow_32).ln();
+                }
+                estimate
+            }
+            Estimator::MaximumLikelihood => self.ml_estimate(),
+        }
+    }
+
+    /// Harmonic mean estimate with linear counting for small cardinalities
+    fn original_estimate(&self) -> f64 {
+        let m = self.num_registers() as f64;
+        let raw = self.raw_estimate();
+
+        // Small range correction using linear counting
+        if raw <= 2.5 * m {
+            let zeros = self.count_zeros();
+            if zeros > 0 {
+                return self.linear_counting(zeros);
+            }
+        }
+
+        raw
+    }
+
+    /// Maximum-likelihood estimate (Ertl 2017, Algorithm 8)
+    ///
+    /// Finds the root of the derivative of the log-likelihood of the register
+    /// histogram with the secant method, stopping at a relative step of
+    /// `0.01 / sqrt(m)`.
+    fn ml_estimate(&self) -> f64 {
+        let m = self.num_registers();
+        // Registers hold ranks 0..=q+1 for q = 64 - p index-free hash bits
+        let q = 64 - self.precision as usize;
+        let mut c = vec![0usize; q + 2];
+        for &r in &self.registers {
+            c[r as usize] += 1;
+        }
+
+        if c[0] == m {
+            return 0.0;
+        }
+        if c[q + 1] == m {
+            return f64::INFINITY;
+        }
+
+        let k_min = c.iter().position(|&n| n > 0).unwrap_or(0);
+        let k_max = c.iter().rposition(|&n| n > 0).unwrap_or(0);
+        let k_min_p = k_min.max(1);
+        let k_max_p = k_max.min(q);
+
+        let mut z = 0.0;
+        for k in (k_min_p..=k_max_p).rev() {
+            z = 0.5 * z + c[k] as f64;
+        }
+        z *= 2f64.powi(-(k_min_p as i32));
+
+        let c_prime = (c[q + 1] + c[k_max_p]) as f64;
+        let a = z + c[0] as f64;
+        let b = z + c[q + 1] as f64 * 2f64.powi(-(q as i32));
+        let m_prime = (m - c[0]) as f64;
+
+        let mut x = if b <= 1.5 * a {
+            m_prime / (0.5 * b + a)
+        } else {
+            m_prime / b * (1.0 + b / a).ln()
+        };
+
+        let epsilon = 0.01 / (m as f64).sqrt();
+        let mut delta_x = x;
+        let mut g_prev = 0.0;
+        while delta_x > x * epsilon {
+            let kappa = 2 + x.log2().floor() as i32;
+            let mut x_p = x * 2f64.powi(-(k_max_p as i32).max(kappa) - 1);
+            let x_pp = x_p * x_p;
+            let mut h = x_p - x_pp / 3.0 + x_pp * x_pp * (1.0 / 45.0 - x_pp / 472.5);
+            for _ in k_max_p as i32..kappa {
+                h = (x_p + h * (1.0 - h)) / (x_p + (1.0 - h));
+                x_p *= 2.0;
+            }
+
+            let mut g = c_prime * h;
+            for k in (k_min_p..k_max_p).rev() {
+                h = (x_p + h * (1.0 - h)) / (x_p + (1.0 - h));
+                g += c[k] as f64 * h;
+                x_p *= 2.0;
+            }
+            g += x * a;
+
+            delta_x = if g > g_prev && m_prime >= g {
+                delta_x * (m_prime - g) / (g - g_prev)
+            } else {
+                0.0
+            };
+            x += delta_x;
+            g_prev = g;
+        }
+
+        m as f64 * x
+    }
+
     /// Computes the raw estimate using harmonic mean
     fn raw_estimate(&self) -> f64 {
         let m = self.num_registers() as f64;
@@ -253,6 +536,10 @@ impl HyperLogLog {
     ///
     /// Format: [precision: 1 byte][registers: m bytes]
     ///
+    /// The registers are stored verbatim, so `from_bytes` followed by
+    /// `to_bytes` returns the same bytes and repeated round-trips never
+    /// change the estimate.
+    ///
     /// # Examples
     ///
     /// ```
@@ -270,6 +557,29 @@ impl HyperLogLog {
         bytes
     }
 
+    /// Returns a stable 64-bit hash of the sketch's content
+    ///
+    /// XXHash64 of [`to_bytes`](Self::to_bytes), so two sketches with the same
+    /// precision and registers always hash equal, across processes and after
+    /// a serialization round-trip. Useful for indexing or deduplicating
+    /// stored sketches without comparing registers. This is not a
+    /// cryptographic hash: distinct sketches can collide, so confirm equality
+    /// before relying on a match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    ///
+    /// let mut hll = HyperLogLog::new(12).unwrap();
+    /// hll.update(&"test");
+    /// let restored = HyperLogLog::from_bytes(&hll.to_bytes()).unwrap();
+    /// assert_eq!(restored.content_hash(), hll.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        crate::common::hash::xxhash(&self.to_bytes(), 0)
+    }
+
     /// Deserializes a HyperLogLog from bytes
     ///
     /// # Errors
@@ -412,12 +722,117 @@ impl HyperLogLog {
         bytes
     }
 
+    /// Folds the sketch down to a lower precision
+    ///
+    /// Each target register combines the `2^(precision - target_precision)`
+    /// source registers that share its index prefix, taking the maximum of
+    /// their values re-expressed at the coarser precision. The result is
+    /// identical to a sketch built directly at `target_precision` from the
+    /// same stream, so one high-precision source can produce smaller copies.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `target_precision` exceeds the current
+    /// precision or is below the minimum precision of 4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::Sketch;
+    ///
+    /// let mut hll = HyperLogLog::new(16).unwrap();
+    /// for i in 0..10_000u64 {
+    ///     hll.update(&i);
+    /// }
+    ///
+    /// let small = hll.fold_to(12).unwrap();
+    /// assert_eq!(small.precision(), 12);
+    /// assert!((small.estimate() - 10_000.0).abs() < 500.0);
+    /// ```
+    pub fn fold_to(&self, target_precision: u8) -> Result<HyperLogLog, SketchError> {
+        if target_precision > self.precision {
+            return Err(SketchError::InvalidParameter {
+                param: "target_precision".to_string(),
+                value: target_precision.to_string(),
+                constraint: format!("must not exceed current precision {}", self.precision),
+            });
+        }
+
+        let mut folded = HyperLogLog::new(target_precision)?;
+        let shift = self.precision - target_precision;
+
+        for (idx, &rho) in self.registers.iter().enumerate() {
+            if rho == 0 {
+                continue;
+            }
+
+            let (folded_idx, folded_rho) = fold_register(idx, rho, shift);
+            let target = &mut folded.registers[folded_idx];
+            if folded_rho > *target {
+                *target = folded_rho;
+            }
+        }
+
+        Ok(folded)
+    }
+
     /// Returns a reference to the internal registers
     ///
     /// This is useful for debugging or implementing custom operations.
     pub fn registers(&self) -> &[u8] {
         &self.registers
     }
+
+    /// Returns true if both sketches have the same precision and identical registers
+    ///
+    /// Two sketches that compare equal produce the same estimate, serialize to
+    /// the same bytes and behave identically under further merges, which makes
+    /// this the natural check for merge commutativity, associativity and
+    /// idempotence (`a ∪ a == a`, since merging takes per-register maxima).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::Mergeable;
+    ///
+    /// let mut a = HyperLogLog::new(10).unwrap();
+    /// a.update(&"x");
+    /// let snapshot = a.clone();
+    /// a.merge(&snapshot).unwrap();
+    /// assert!(a.registers_equal(&snapshot));
+    /// ```
+    pub fn registers_equal(&self, other: &Self) -> bool {
+        self.precision == other.precision && self.registers == other.registers
+    }
+
+    /// Returns a JSON summary of parameters and state for debugging
+    ///
+    /// Reports the precision, register count, estimate, standard error and
+    /// number of non-zero registers, but not the registers themselves.
+    /// Not meant for reconstruction; use the binary serialization for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    ///
+    /// let mut hll = HyperLogLog::new(4).unwrap();
+    /// hll.update(&"a");
+    /// assert!(hll.to_json().starts_with(r#"{"type":"HyperLogLog","precision":4,"#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let num_nonzero = self.registers.iter().filter(|&&r| r != 0).count();
+        format!(
+            r#"{{"type":"HyperLogLog","precision":{},"num_registers":{},"estimate":{},"standard_error":{},"num_nonzero":{}}}"#,
+            self.precision,
+            self.num_registers(),
+            Sketch::estimate(self),
+            self.standard_error(),
+            num_nonzero
+        )
+    }
 }
 
 impl Sketch for HyperLogLog {
@@ -427,29 +842,11 @@ impl Sketch for HyperLogLog {
         self.update(item);
     }
 
-    /// Estimates the cardinality with HyperLogLog++ corrections
+    /// Estimates the cardinality with small and large range corrections
     ///
-    /// Applies small and large range corrections for improved accuracy.
+    /// Uses [`Estimator::Classic32BitCorrected`].
     fn estimate(&self) -> f64 {
-        let m = self.num_registers() as f64;
-        let raw = self.raw_estimate();
-
-        // Small range correction using linear counting
-        if raw <= 2.5 * m {
-            let zeros = self.count_zeros();
-            if zeros > 0 {
-                return self.linear_counting(zeros);
-            }
-        }
-
-        // Large range correction (for 32-bit hash, not needed for 64-bit)
-        // With 64-bit hashes, this threshold is effectively never reached
-        let two_pow_32 = (1u64 << 32) as f64;
-        if raw > two_pow_32 / 30.0 {
-            return -two_pow_32 * (1.0 - raw / two_pow_32).ln();
-        }
-
-        raw
+        self.estimate_with(Estimator::Classic32BitCorrected)
     }
 
     fn is_empty(&self) -> bool {
@@ -514,6 +911,142 @@ impl Mergeable for HyperLogLog {
     }
 }
 
+impl CardinalitySketch for HyperLogLog {
+    fn cardinality(&self) -> f64 {
+        Sketch::estimate(self)
+    }
+
+    fn merge_from(&mut self, other: &Self) -> Result<(), SketchError> {
+        self.merge(other)
+    }
+}
+
+/// Register index and rank (leading zeros + 1) of a hash at `precision`
+#[inline]
+pub(crate) fn register_for_hash(hash: u64, precision: u8) -> (usize, u8) {
+    let idx = (hash >> (64 - precision)) as usize;
+    let w = hash << precision | (1u64 << (precision - 1));
+    (idx, (w.leading_zeros() + 1) as u8)
+}
+
+/// Maps a non-zero register to `shift` bits lower precision
+///
+/// The dropped index bits become the leading bits of the coarser sketch's
+/// rank; only an all-zero suffix lets the old rank through.
+#[inline]
+pub(crate) fn fold_register(idx: usize, rho: u8, shift: u8) -> (usize, u8) {
+    let suffix = idx & ((1usize << shift) - 1);
+    let folded_rho = if suffix == 0 {
+        rho + shift
+    } else {
+        (suffix.leading_zeros() - (usize::BITS - shift as u32) + 1) as u8
+    };
+    (idx >> shift, folded_rho)
+}
+
+/// Maximum number of sketches accepted by [`hll_intersection`]
+///
+/// Inclusion-exclusion needs one union estimate per non-empty subset
+/// (2^n - 1 of them), so the input size is capped to keep the cost bounded.
+pub const HLL_INTERSECTION_MAX_SKETCHES: usize = 6;
+
+/// Estimates |A₁ ∩ A₂ ∩ … ∩ Aₙ| from HyperLogLog sketches via inclusion-exclusion
+///
+/// Expands the intersection over every non-empty subset S of the inputs:
+///
+/// |∩ Aᵢ| = Σ (-1)^(|S|+1) · |∪_{i∈S} Aᵢ|
+///
+/// Each union is estimated by merging registers, so 2^n - 1 union estimates
+/// are computed. The result is clamped to `[0, min |Aᵢ|]`.
+///
+/// # Accuracy
+///
+/// The errors of all union estimates add up, and they are of the order of the
+/// union size rather than the intersection size. The relative error therefore
+/// grows quickly with `n` and becomes meaningless when the true intersection
+/// is small compared to the union. Treat results below roughly
+/// `standard_error() * |∪ Aᵢ|` as noise. At most
+/// [`HLL_INTERSECTION_MAX_SKETCHES`] sketches are accepted.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `sketches` is empty or has more than
+/// [`HLL_INTERSECTION_MAX_SKETCHES`] entries, and `IncompatibleSketches`
+/// if the precisions differ.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::cardinality::{hll_intersection, HyperLogLog};
+///
+/// let mut a = HyperLogLog::new(14).unwrap();
+/// let mut b = HyperLogLog::new(14).unwrap();
+/// let mut c = HyperLogLog::new(14).unwrap();
+/// for i in 0..20_000 {
+///     a.update(&i);
+/// }
+/// for i in 5_000..25_000 {
+///     b.update(&i);
+/// }
+/// for i in 10_000..30_000 {
+///     c.update(&i);
+/// }
+///
+/// // True intersection is 10,000
+/// let estimate = hll_intersection(&[&a, &b, &c]).unwrap();
+/// assert!((estimate - 10_000.0).abs() / 10_000.0 < 0.15);
+/// ```
+pub fn hll_intersection(sketches: &[&HyperLogLog]) -> Result<f64, SketchError> {
+    let n = sketches.len();
+    if n == 0 || n > HLL_INTERSECTION_MAX_SKETCHES {
+        return Err(SketchError::InvalidParameter {
+            param: "sketches".to_string(),
+            value: n.to_string(),
+            constraint: format!(
+                "must contain between 1 and {} sketches",
+                HLL_INTERSECTION_MAX_SKETCHES
+            ),
+        });
+    }
+
+    let precision = sketches[0].precision;
+    if let Some(other) = sketches.iter().find(|s| s.precision != precision) {
+        return Err(SketchError::IncompatibleSketches {
+            reason: format!("Precision mismatch: {} vs {}", precision, other.precision),
+        });
+    }
+
+    // unions[mask] holds the merged registers of the subset encoded by `mask`,
+    // built from the subset without its lowest bit plus that one sketch.
+    let mut unions: Vec<Option<HyperLogLog>> = vec![None; 1 << n];
+    let mut intersection = 0.0;
+
+    for mask in 1usize..(1 << n) {
+        let lowest = mask.trailing_zeros() as usize;
+        let rest = mask & (mask - 1);
+
+        let mut union = match &unions[rest] {
+            Some(base) => base.clone(),
+            None => HyperLogLog::new(precision)?,
+        };
+        union.merge(sketches[lowest])?;
+
+        if mask.count_ones() % 2 == 1 {
+            intersection += union.estimate();
+        } else {
+            intersection -= union.estimate();
+        }
+        unions[mask] = Some(union);
+    }
+
+    let min_estimate = sketches
+        .iter()
+        .map(|s| s.estimate())
+        .fold(f64::INFINITY, f64::min);
+
+    Ok(intersection.clamp(0.0, min_estimate))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -629,4 +1162,150 @@ mod tests {
             "Duplicate updates should not increase count"
         );
     }
+
+    #[test]
+    fn test_fold_to_matches_direct_build() {
+        let mut high = HyperLogLog::new(16).unwrap();
+        let mut low = HyperLogLog::new(12).unwrap();
+        for i in 0..50_000u64 {
+            high.update(&i);
+            low.update(&i);
+        }
+
+        let folded = high.fold_to(12).unwrap();
+        assert_eq!(folded.registers(), low.registers());
+
+        let error = (folded.estimate() - 50_000.0).abs() / 50_000.0;
+        assert!(error < 3.0 * folded.standard_error(), "Error {}", error);
+
+        assert_eq!(high.fold_to(16).unwrap().registers(), high.registers());
+        assert!(high.fold_to(17).is_err());
+        assert!(high.fold_to(3).is_err());
+    }
+
+    #[test]
+    fn test_hll_intersection_three_way() {
+        let mut a = HyperLogLog::new(14).unwrap();
+        let mut b = HyperLogLog::new(14).unwrap();
+        let mut c = HyperLogLog::new(14).unwrap();
+        for i in 0..20_000 {
+            a.update(&i);
+        }
+        for i in 5_000..25_000 {
+            b.update(&i);
+        }
+        for i in 10_000..30_000 {
+            c.update(&i);
+        }
+
+        let estimate = hll_intersection(&[&a, &b, &c]).unwrap();
+        let error = (estimate - 10_000.0).abs() / 10_000.0;
+        assert!(error < 0.15, "Intersection estimate {} too far", estimate);
+    }
+
+    #[test]
+    fn test_hll_intersection_disjoint_clamped() {
+        let mut a = HyperLogLog::new(12).unwrap();
+        let mut b = HyperLogLog::new(12).unwrap();
+        for i in 0..1000 {
+            a.update(&i);
+        }
+        for i in 1000..2000 {
+            b.update(&i);
+        }
+
+        let estimate = hll_intersection(&[&a, &b]).unwrap();
+        assert!(estimate >= 0.0);
+        assert!(estimate < 100.0);
+    }
+
+    #[test]
+    fn test_hll_intersection_invalid_input() {
+        let a = HyperLogLog::new(12).unwrap();
+        let b = HyperLogLog::new(10).unwrap();
+        assert!(hll_intersection(&[]).is_err());
+        assert!(hll_intersection(&[&a, &b]).is_err());
+        assert!(hll_intersection(&[&a; HLL_INTERSECTION_MAX_SKETCHES + 1]).is_err());
+        assert!(hll_intersection(&[&a; HLL_INTERSECTION_MAX_SKETCHES]).is_ok());
+    }
+
+    /// Relative RMSE of each estimator over `trials` sketches of `n` items
+    fn estimator_rmse(precision: u8, n: u64, trials: u64, estimators: &[Estimator]) -> Vec<f64> {
+        let mut squared = vec![0.0; estimators.len()];
+        for trial in 0..trials {
+            let mut hll = HyperLogLog::new(precision).unwrap();
+            for i in 0..n {
+                hll.update(&(trial << 32 | i));
+            }
+            for (sum, &estimator) in squared.iter_mut().zip(estimators) {
+                let error = (hll.estimate_with(estimator) - n as f64) / n as f64;
+                *sum += error * error;
+            }
+        }
+        squared
+            .iter()
+            .map(|sum| (sum / trials as f64).sqrt())
+            .collect()
+    }
+
+    #[test]
+    fn test_estimator_rmse() {
+        let estimators = [
+            Estimator::Original,
+            Estimator::Classic32BitCorrected,
+            Estimator::MaximumLikelihood,
+        ];
+        // p = 10: standard error 1.04 / 32 ≈ 3.3%
+        for n in [1_000, 2_500, 20_000] {
+            let rmse = estimator_rmse(10, n, 40, &estimators);
+            for (estimator, error) in estimators.iter().zip(&rmse) {
+                assert!(*error < 0.05, "{:?} RMSE {} at n={}", estimator, error, n);
+            }
+            // Below 143 million items the default matches Original
+            assert_eq!(rmse[0], rmse[1]);
+        }
+
+        // Linear counting hands over to the harmonic mean near 2.5 * m;
+        // maximum likelihood has no bias bump there
+        let rmse = estimator_rmse(10, 2_500, 40, &estimators);
+        assert!(rmse[2] < rmse[0], "ML {} vs Original {}", rmse[2], rmse[0]);
+    }
+
+    #[test]
+    fn test_estimate_with_edge_cases() {
+        let mut hll = HyperLogLog::new(12).unwrap();
+        assert_eq!(hll.estimate_with(Estimator::MaximumLikelihood), 0.0);
+        assert_eq!(hll.estimate_with(Estimator::Original), 0.0);
+
+        hll.update(&"only");
+        let ml = hll.estimate_with(Estimator::MaximumLikelihood);
+        assert!((ml - 1.0).abs() < 0.01, "got {}", ml);
+        assert_eq!(hll.estimate(), hll.estimate_with(Estimator::default()));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut hll = HyperLogLog::new(4).unwrap();
+        assert_eq!(
+            hll.to_json(),
+            r#"{"type":"HyperLogLog","precision":4,"num_registers":16,"estimate":0,"standard_error":0.26,"num_nonzero":0}"#
+        );
+
+        hll.update(&"a");
+        assert!(hll.to_json().ends_with(r#""num_nonzero":1}"#));
+    }
+
+    #[test]
+    fn test_update_numeric_matches_le_bytes() {
+        let mut numeric = HyperLogLog::new(10).unwrap();
+        let mut bytes = HyperLogLog::new(10).unwrap();
+        numeric.update_u64(5);
+        numeric.update_i64(-5);
+        numeric.update_f64(2.5);
+        bytes.update(&5u64.to_le_bytes().to_vec());
+        bytes.update(&(-5i64).to_le_bytes().to_vec());
+        bytes.update(&2.5f64.to_le_bytes().to_vec());
+
+        assert_eq!(numeric.registers(), bytes.registers());
+    }
 }
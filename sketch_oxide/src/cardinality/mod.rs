@@ -9,21 +9,82 @@
 //! |-----------|------------------|----------|----------|
 //! | UltraLogLog | Best (28% better than HLL) | ~1.04/√m | New applications |
 //! | HyperLogLog | Good | ~1.04/√m | Ecosystem interop (Redis, Druid) |
+//! | AdaptiveHyperLogLog | Sparse while small | ~1.04/√m at current precision | Many mostly-small sketches |
+//! | GroupedHll | Global memory budget | Per-group AdaptiveHyperLogLog | COUNT DISTINCT ... GROUP BY |
 //! | CpcSketch | Better than HLL | ~1/√m | Apache DataSketches compat |
 //! | ThetaSketch | Good | ~1/√k | Set operations (union, intersection) |
+//! | CompactThetaSketch | Sorted array, read-only | Same as source ThetaSketch | Query phase after building |
+//! | DistinctCounter | Exact set, then CPC | Exact, then ~1/√m | Not sure which to pick |
 
+mod adaptive_hyperloglog;
+mod compact_theta;
 mod cpc;
+mod distinct_counter;
+mod grouped_hll;
 mod hyperloglog;
 mod qsketch;
 mod theta;
 mod ultraloglog;
 
+pub use adaptive_hyperloglog::AdaptiveHyperLogLog;
+pub use compact_theta::CompactThetaSketch;
 pub use cpc::CpcSketch;
-pub use hyperloglog::HyperLogLog;
+pub use distinct_counter::DistinctCounter;
+pub use grouped_hll::GroupedHll;
+pub use hyperloglog::{hll_intersection, Estimator, HyperLogLog, HLL_INTERSECTION_MAX_SKETCHES};
 pub use qsketch::QSketch;
 pub use theta::ThetaSketch;
 pub use ultraloglog::UltraLogLog;
 
+use crate::common::SketchError;
+
+/// Estimated memory in bytes for a HyperLogLog with the given relative standard error
+///
+/// Picks the smallest precision `p` whose standard error `1.04 / √(2^p)`
+/// is at most `target`, i.e. `p = ⌈log2((1.04 / target)²)⌉` (at least 4), and
+/// returns the register array size `2^p` (one byte per register). UltraLogLog
+/// uses the same register layout, so the figure applies to both.
+///
+/// # Errors
+/// Returns `InvalidParameter` if `target` is not in (0, 1), or if it needs a
+/// precision above 18 (an RSE below ~0.2%).
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::cardinality::bytes_for_rse;
+///
+/// // 1% error needs precision 14: 16 KB of registers
+/// assert_eq!(bytes_for_rse(0.01).unwrap(), 16 * 1024);
+/// ```
+pub fn bytes_for_rse(target: f64) -> Result<usize, SketchError> {
+    if !(target > 0.0 && target < 1.0) {
+        return Err(SketchError::InvalidParameter {
+            param: "target".to_string(),
+            value: target.to_string(),
+            constraint: "must be in (0, 1)".to_string(),
+        });
+    }
+
+    let precision = (1.04 / target)
+        .powi(2)
+        .log2()
+        .ceil()
+        .max(HyperLogLog::MIN_PRECISION as f64);
+    if precision > HyperLogLog::MAX_PRECISION as f64 {
+        return Err(SketchError::InvalidParameter {
+            param: "target".to_string(),
+            value: target.to_string(),
+            constraint: format!(
+                "needs precision {} (max {})",
+                precision,
+                HyperLogLog::MAX_PRECISION
+            ),
+        });
+    }
+
+    Ok(1usize << precision as u32)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
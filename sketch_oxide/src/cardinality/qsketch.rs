@@ -60,7 +60,7 @@
 //! let total = qsketch.total_weight();
 //! ```
 
-use crate::common::{Mergeable, Sketch, SketchError};
+use crate::common::{validation, Mergeable, Sketch, SketchError};
 use rand::Rng;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -75,11 +75,22 @@ struct SampledItem {
     weight: f64,
 }
 
+/// Magic bytes prefixed to serialized QSketches
+const SERIAL_MAGIC: &[u8; 4] = b"QSKT";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
 /// QSketch for weighted cardinality estimation
 ///
 /// Maintains a probabilistic sample of weighted elements to estimate
 /// the cardinality of weighted sets with bounded error.
 ///
+/// # Thread Safety
+///
+/// `QSketch` is `Send + Sync`. Its sampling RNG is an owned `SmallRng` rather
+/// than a thread-local generator, so the sketch can move between threads.
+///
 /// # Examples
 ///
 /// ```
@@ -109,6 +120,9 @@ pub struct QSketch {
     /// Adaptive threshold for sampling
     threshold: f64,
 
+    /// Seed the sampling RNG was created from, if one was given
+    seed: Option<u64>,
+
     /// Random number generator
     rng: rand::rngs::SmallRng,
 }
@@ -148,6 +162,7 @@ impl QSketch {
             items_seen: HashMap::new(),
             total_weight: 0.0,
             threshold: 0.0,
+            seed: None,
             rng: rand::rngs::SmallRng::from_os_rng(),
         }
     }
@@ -183,10 +198,17 @@ impl QSketch {
             items_seen: HashMap::new(),
             total_weight: 0.0,
             threshold: 0.0,
+            seed: Some(seed),
             rng: rand::rngs::SmallRng::seed_from_u64(seed),
         }
     }
 
+    /// Returns the sampling seed, or `None` for an OS-seeded sketch
+    #[inline]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
     /// Returns the maximum number of samples this sketch can maintain
     #[inline]
     pub fn max_samples(&self) -> usize {
@@ -306,6 +328,45 @@ impl QSketch {
         (estimate, error_bound)
     }
 
+    /// Returns the q-quantile of the per-item weight distribution
+    ///
+    /// Each distinct item contributes its accumulated weight (the sum of its
+    /// updates), and the result is the nearest-rank quantile: the smallest
+    /// weight `w` such that at least `q × distinct` items weigh `w` or less.
+    /// `q` is clamped to [0, 1], and an empty sketch returns 0.0.
+    ///
+    /// # Estimator
+    ///
+    /// The sketch already keeps every distinct item's accumulated weight to
+    /// merge duplicate updates, so the quantile is taken over all of them and
+    /// is exact rather than reweighted from the sample. The weighted sample
+    /// is not suitable for this: once full, it only admits updates heavier
+    /// than its lightest entry, so lighter items have inclusion probability
+    /// zero and no Horvitz-Thompson correction can account for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::QSketch;
+    ///
+    /// let mut qsketch = QSketch::new(256);
+    /// for i in 1..=100 {
+    ///     qsketch.update(format!("user_{}", i).as_bytes(), i as f64);
+    /// }
+    /// assert_eq!(qsketch.weight_quantile(0.5), 50.0);
+    /// assert_eq!(qsketch.weight_quantile(1.0), 100.0);
+    /// ```
+    pub fn weight_quantile(&self, q: f64) -> f64 {
+        if self.items_seen.is_empty() {
+            return 0.0;
+        }
+
+        let mut weights: Vec<f64> = self.items_seen.values().copied().collect();
+        let rank = ((q.clamp(0.0, 1.0) * weights.len() as f64).ceil() as usize).max(1);
+        let (_, weight, _) = weights.select_nth_unstable_by(rank - 1, f64::total_cmp);
+        *weight
+    }
+
     /// Computes the standard error for weighted cardinality estimation
     #[inline]
     fn compute_standard_error(&self, sample_size: f64, sample_weight: f64) -> f64 {
@@ -467,11 +528,11 @@ impl QSketch {
     /// Merges another QSketch into this one
     ///
     /// Combines the weighted cardinality estimates from both sketches.
-    /// Both sketches should have the same max_samples for optimal results.
     ///
     /// # Errors
     ///
-    /// Returns error if sketches have incompatible configurations
+    /// Returns `IncompatibleSketches` if the sketches differ in max_samples
+    /// or seed, since their samples were drawn under different schemes
     ///
     /// # Examples
     ///
@@ -495,6 +556,11 @@ impl QSketch {
                 ),
             });
         }
+        if self.seed != other.seed {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("QSketch seed mismatch: {:?} vs {:?}", self.seed, other.seed),
+            });
+        }
 
         // Merge items_seen by updating weights
         for (&element_id, &weight) in &other.items_seen {
@@ -528,12 +594,20 @@ impl QSketch {
 
     /// Serializes the QSketch to bytes
     ///
-    /// Format: [max_samples: 4 bytes][total_weight: 8 bytes][num_samples: 4 bytes]
+    /// Format: [magic: "QSKT"][version: 1 byte]
+    ///         [max_samples: 4 bytes][total_weight: 8 bytes][num_samples: 4 bytes]
     ///         [sample_data: num_samples * 16 bytes]
     ///         [num_items_seen: 4 bytes][items_seen: variable]
+    ///         [has_seed: 1 byte][seed: 8 bytes if has_seed]
+    ///
+    /// The seed trailer is optional when reading, so bytes without it
+    /// deserialize as an OS-seeded sketch. Bytes lacking the magic/version
+    /// header, as written by earlier versions, are still read.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
+
         // Max samples (4 bytes)
         bytes.extend_from_slice(&(self.max_samples as u32).to_le_bytes());
 
@@ -558,6 +632,15 @@ impl QSketch {
             bytes.extend_from_slice(&weight.to_le_bytes());
         }
 
+        // Seed (1 flag byte, then 8 bytes when present)
+        match self.seed {
+            Some(seed) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&seed.to_le_bytes());
+            }
+            None => bytes.push(0),
+        }
+
         bytes
     }
 
@@ -565,8 +648,20 @@ impl QSketch {
     ///
     /// # Errors
     ///
-    /// Returns error if bytes are invalid or corrupted
+    /// Returns error if bytes are invalid or corrupted, or if the stored
+    /// max_samples is below the minimum accepted by the constructors
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        if bytes.starts_with(SERIAL_MAGIC) {
+            // A headerless max_samples could spell the magic; try it as one too
+            return validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)
+                .and_then(Self::from_payload)
+                .or_else(|err| Self::from_payload(bytes).map_err(|_| err));
+        }
+        Self::from_payload(bytes)
+    }
+
+    /// Decodes the fields following the header
+    fn from_payload(bytes: &[u8]) -> Result<Self, SketchError> {
         if bytes.len() < 16 {
             return Err(SketchError::DeserializationError(
                 "QSketch bytes too short".to_string(),
@@ -584,6 +679,14 @@ impl QSketch {
         ]) as usize;
         offset += 4;
 
+        if max_samples < Self::MIN_SAMPLES {
+            return Err(SketchError::DeserializationError(format!(
+                "QSketch max_samples {} is below the minimum {}",
+                max_samples,
+                Self::MIN_SAMPLES
+            )));
+        }
+
         // Read total_weight
         let total_weight = f64::from_le_bytes([
             bytes[offset],
@@ -607,7 +710,7 @@ impl QSketch {
         offset += 4;
 
         // Read samples
-        let mut samples = Vec::with_capacity(num_samples);
+        let mut samples = Vec::with_capacity(num_samples.min((bytes.len() - offset) / 16));
         for _ in 0..num_samples {
             if offset + 16 > bytes.len() {
                 return Err(SketchError::DeserializationError(
@@ -658,7 +761,8 @@ impl QSketch {
         offset += 4;
 
         // Read items seen
-        let mut items_seen = HashMap::with_capacity(num_items_seen);
+        let mut items_seen =
+            HashMap::with_capacity(num_items_seen.min((bytes.len() - offset) / 16));
         for _ in 0..num_items_seen {
             if offset + 16 > bytes.len() {
                 return Err(SketchError::DeserializationError(
@@ -693,15 +797,36 @@ impl QSketch {
             items_seen.insert(element_id, weight);
         }
 
+        // Read optional seed trailer
+        let seed = match bytes.get(offset) {
+            None | Some(0) => None,
+            Some(1) if offset + 9 <= bytes.len() => Some(u64::from_le_bytes(
+                bytes[offset + 1..offset + 9].try_into().unwrap(),
+            )),
+            Some(_) => {
+                return Err(SketchError::DeserializationError(
+                    "Invalid QSketch seed data".to_string(),
+                ))
+            }
+        };
+
         use rand::SeedableRng;
-        Ok(QSketch {
+        let rng = match seed {
+            Some(seed) => rand::rngs::SmallRng::seed_from_u64(seed),
+            None => rand::rngs::SmallRng::from_os_rng(),
+        };
+
+        let mut qsketch = QSketch {
             max_samples,
             samples,
             items_seen,
             total_weight,
             threshold: 0.0,
-            rng: rand::rngs::SmallRng::from_os_rng(),
-        })
+            seed,
+            rng,
+        };
+        qsketch.update_threshold();
+        Ok(qsketch)
     }
 }
 
@@ -778,6 +903,37 @@ mod tests {
         assert_eq!(qsketch.estimate_distinct_elements(), 3);
     }
 
+    #[test]
+    fn test_weight_quantile_known_distribution() {
+        let mut qsketch = QSketch::with_seed(64, 7);
+        assert_eq!(qsketch.weight_quantile(0.5), 0.0);
+
+        // Weights 1..=1000, far more items than samples
+        for i in 1..=1000 {
+            qsketch.update(format!("item_{}", i).as_bytes(), i as f64);
+        }
+        assert_eq!(qsketch.weight_quantile(0.0), 1.0);
+        assert_eq!(qsketch.weight_quantile(0.5), 500.0);
+        assert_eq!(qsketch.weight_quantile(0.9), 900.0);
+        assert_eq!(qsketch.weight_quantile(1.0), 1000.0);
+        assert_eq!(qsketch.weight_quantile(2.0), 1000.0);
+
+        // Repeated updates accumulate into one item's weight
+        for _ in 0..9 {
+            qsketch.update(b"item_1", 1.0);
+        }
+        assert_eq!(qsketch.weight_quantile(0.0), 2.0);
+        assert_eq!(qsketch.weight_quantile(0.01), 10.0);
+
+        // Merging combines the per-item weights of both sides
+        let mut other = QSketch::with_seed(64, 7);
+        for i in 1001..=2000 {
+            other.update(format!("item_{}", i).as_bytes(), i as f64);
+        }
+        qsketch.merge(&other).unwrap();
+        assert_eq!(qsketch.weight_quantile(0.5), 1000.0);
+    }
+
     #[test]
     fn test_duplicate_elements() {
         let mut qsketch = QSketch::new(256);
@@ -916,6 +1072,92 @@ mod tests {
         assert_eq!(restored.max_samples(), qsketch.max_samples());
         assert!((restored.total_weight() - qsketch.total_weight()).abs() < 0.001);
         assert_eq!(restored.sample_count(), qsketch.sample_count());
+        assert_eq!(restored.seed(), None);
+    }
+
+    #[test]
+    fn test_serialization_preserves_seed() {
+        let mut qsketch = QSketch::with_seed(64, 42);
+        for i in 0..200 {
+            qsketch.update(format!("item_{}", i).as_bytes(), (i % 7 + 1) as f64);
+        }
+
+        let restored = QSketch::from_bytes(&qsketch.to_bytes()).unwrap();
+        assert_eq!(restored.seed(), Some(42));
+        assert_eq!(restored.max_samples(), 64);
+        assert_eq!(restored.to_bytes().len(), qsketch.to_bytes().len());
+
+        // A restored sketch still merges with its seeded peers
+        let mut peer = QSketch::with_seed(64, 42);
+        peer.update(b"peer", 3.0);
+        peer.merge(&restored).unwrap();
+
+        // Bytes without the seed trailer load as OS-seeded
+        let bytes = QSketch::new(64).to_bytes();
+        let legacy = QSketch::from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+        assert_eq!(legacy.seed(), None);
+
+        let mut corrupt = qsketch.to_bytes();
+        corrupt.truncate(corrupt.len() - 4);
+        assert!(QSketch::from_bytes(&corrupt).is_err());
+    }
+
+    #[test]
+    fn test_deserialization_header() {
+        let mut qsketch = QSketch::with_seed(64, 7);
+        qsketch.update(b"item", 5.0);
+        let bytes = qsketch.to_bytes();
+        assert_eq!(&bytes[..4], b"QSKT");
+
+        for len in [0, 4, 5, 12, bytes.len() / 2] {
+            assert!(matches!(
+                QSketch::from_bytes(&bytes[..len]),
+                Err(SketchError::DeserializationError(_))
+            ));
+        }
+
+        let mut future = bytes.clone();
+        future[4] = 2;
+        assert!(matches!(
+            QSketch::from_bytes(&future),
+            Err(SketchError::DeserializationError(_))
+        ));
+
+        // A huge sample count must fail cleanly instead of allocating
+        let mut inflated = bytes;
+        inflated[17..21].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(QSketch::from_bytes(&inflated).is_err());
+    }
+
+    #[test]
+    fn test_reads_sketches_serialized_without_header() {
+        // with_seed(64, 7) after "a" = 2.0 and "b" = 3.0, as serialized
+        // before the magic/version header and the seed trailer
+        let hex = "40000000000000000000144002000000c3e110daea9816bd0000000000000040\
+                   7ed52d518301d35b000000000000084002000000c3e110daea9816bd00000000\
+                   000000407ed52d518301d35b0000000000000840";
+        let legacy: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        let qsketch = QSketch::from_bytes(&legacy).unwrap();
+        assert_eq!(qsketch.max_samples, 64);
+        assert_eq!(qsketch.total_weight(), 5.0);
+        assert_eq!(qsketch.estimate_distinct_elements(), 2);
+    }
+
+    #[test]
+    fn test_merge_seed_mismatch() {
+        let mut seeded = QSketch::with_seed(256, 1);
+        let other_seed = QSketch::with_seed(256, 2);
+        let unseeded = QSketch::new(256);
+        seeded.update(b"item_1", 10.0);
+
+        assert!(seeded.merge(&other_seed).is_err());
+        assert!(seeded.merge(&unseeded).is_err());
+        assert!((seeded.total_weight() - 10.0).abs() < 0.001);
+        assert!(seeded.merge(&QSketch::with_seed(256, 1)).is_ok());
     }
 
     #[test]
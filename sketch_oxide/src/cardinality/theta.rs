@@ -41,12 +41,19 @@
 //! - Paper: "Theta Sketch Framework" (Apache DataSketches)
 //! - Source: https://datasketches.apache.org/docs/Theta/ThetaSketchFramework.html
 
+use super::CompactThetaSketch;
+use crate::common::{CardinalitySketch, LgK};
 use crate::error::{Result, SketchError};
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
 /// Theta Sketch for cardinality estimation with set operations.
 ///
+/// # Thread Safety
+///
+/// `ThetaSketch` is `Send + Sync`. Set operations take `&self` and return new
+/// sketches, so a finished sketch can be shared via `Arc`.
+///
 /// # Examples
 ///
 /// ```
@@ -98,6 +105,12 @@ impl ThetaSketch {
     /// Default hash seed (same as Apache DataSketches)
     const DEFAULT_SEED: u64 = 9001;
 
+    /// Preamble size of the compact image in estimation mode
+    const COMPACT_PREAMBLE_BYTES: usize = 24;
+
+    /// Width of the [`jaccard`](ThetaSketch::jaccard) bounds in standard deviations
+    const JACCARD_STD_DEVS: f64 = 2.0;
+
     /// Creates a new Theta Sketch with specified lg_k.
     ///
     /// # Parameters
@@ -145,6 +158,23 @@ impl ThetaSketch {
         })
     }
 
+    /// Creates a new Theta Sketch from an already-validated lg_k
+    ///
+    /// Infallible counterpart of [`new`](Self::new); see [`LgK`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    /// use sketch_oxide::common::LgK;
+    ///
+    /// let sketch = ThetaSketch::new_checked(LgK::L12);
+    /// assert!(sketch.is_empty());
+    /// ```
+    pub fn new_checked(lg_k: LgK) -> Self {
+        Self::new(lg_k.get()).expect("LgK is validated on construction")
+    }
+
     /// Creates a sketch with custom seed (for advanced use).
     pub fn with_seed(lg_k: u8, seed: u64) -> Result<Self> {
         let mut sketch = Self::new(lg_k)?;
@@ -165,8 +195,33 @@ impl ThetaSketch {
     /// sketch.update(&3.14_f64.to_bits());
     /// ```
     pub fn update<T: Hash>(&mut self, item: &T) {
-        let hash = self.hash_item(item);
+        self.update_hash(self.hash_item(item));
+    }
 
+    /// Adds an item that the caller has already hashed
+    ///
+    /// `hash` is used as-is: the sketch's seed is not applied and only the
+    /// theta comparison remains, so keys hashed upstream need not be hashed
+    /// again.
+    ///
+    /// The hash must be uniformly distributed over the full `u64` range, or
+    /// the estimate is biased. Hashes from [`update`](Self::update) come from
+    /// a different function, so a sketch should be fed through only one of
+    /// the two paths, and only merged with sketches fed the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    /// use sketch_oxide::common::hash::xxhash;
+    ///
+    /// let mut sketch = ThetaSketch::new(12).unwrap();
+    /// for i in 0..100u64 {
+    ///     sketch.update_hash(xxhash(&i.to_le_bytes(), 9001));
+    /// }
+    /// assert_eq!(sketch.estimate(), 100.0);
+    /// ```
+    pub fn update_hash(&mut self, hash: u64) {
         // Only consider hashes below theta (sampling)
         if hash < self.theta {
             self.entries.insert(hash);
@@ -178,6 +233,27 @@ impl ThetaSketch {
         }
     }
 
+    /// Adds a `u64` by hashing its 8 little-endian bytes
+    ///
+    /// Equivalent to `update(&value.to_le_bytes())`, which is what the language
+    /// bindings do with a byte buffer, so numeric IDs added from Rust and from JavaScript or Python produce the same hashes.
+    /// This differs from `update(&value)`, which hashes through `Hash for u64`.
+    pub fn update_u64(&mut self, value: u64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `i64` by hashing its 8 little-endian two's-complement bytes
+    pub fn update_i64(&mut self, value: i64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `f64` by hashing the 8 little-endian bytes of its IEEE 754 bits
+    ///
+    /// `0.0` and `-0.0`, like NaNs with different payloads, count as distinct.
+    pub fn update_f64(&mut self, value: f64) {
+        self.update(&value.to_le_bytes());
+    }
+
     /// Estimates the cardinality.
     ///
     /// # Formula
@@ -236,6 +312,142 @@ impl ThetaSketch {
         self.k
     }
 
+    /// Returns a JSON summary of parameters and state for debugging.
+    ///
+    /// Reports lg_k, the retained entry count, theta as a sampling fraction
+    /// in `(0, 1]` and the estimate, but not the retained hashes.
+    /// Not meant for reconstruction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let sketch = ThetaSketch::new(12).unwrap();
+    /// assert_eq!(
+    ///     sketch.to_json(),
+    ///     r#"{"type":"ThetaSketch","lg_k":12,"num_retained":0,"theta":1,"estimate":0}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"type":"ThetaSketch","lg_k":{},"num_retained":{},"theta":{},"estimate":{}}}"#,
+            self.lg_k,
+            self.entries.len(),
+            self.theta as f64 / u64::MAX as f64,
+            self.estimate()
+        )
+    }
+
+    /// Returns the size in bytes of the sketch's compact image.
+    ///
+    /// The compact layout follows Apache DataSketches: a 24-byte preamble
+    /// (flags, seed hash, entry count and theta) followed by each retained
+    /// hash as 8 bytes.
+    pub fn compact_size_bytes(&self) -> usize {
+        Self::COMPACT_PREAMBLE_BYTES + 8 * self.entries.len()
+    }
+
+    /// Freezes the sketch into a read-only [`CompactThetaSketch`].
+    ///
+    /// The compact form stores the retained hashes in a sorted array instead
+    /// of a hash table, keeps the same estimate, and supports set operations
+    /// and serialization, but not updates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let mut sketch = ThetaSketch::new(12).unwrap();
+    /// for i in 0..10_000 {
+    ///     sketch.update(&i);
+    /// }
+    ///
+    /// let compact = sketch.compact();
+    /// assert_eq!(compact.estimate(), sketch.estimate());
+    /// assert_eq!(compact.to_bytes().len(), sketch.compact_size_bytes());
+    /// ```
+    pub fn compact(&self) -> CompactThetaSketch {
+        CompactThetaSketch::from_parts(
+            self.lg_k,
+            self.entries.iter().copied().collect(),
+            self.theta,
+            self.seed,
+        )
+    }
+
+    /// Returns a stable 64-bit hash of the sketch's content.
+    ///
+    /// Equal to [`CompactThetaSketch::content_hash`] of [`compact`](Self::compact):
+    /// the compact image sorts the retained hashes, so sketches with the same
+    /// lg_k, seed, theta and retained hashes hash equal whatever the layout of
+    /// their hash tables. Useful for indexing or deduplicating stored sketches. This is
+    /// not a cryptographic hash: distinct sketches can collide, so confirm
+    /// equality before relying on a match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::{CompactThetaSketch, ThetaSketch};
+    ///
+    /// let mut sketch = ThetaSketch::new(12).unwrap();
+    /// for i in 0..1000 {
+    ///     sketch.update(&i);
+    /// }
+    ///
+    /// let restored = CompactThetaSketch::from_bytes(&sketch.compact().to_bytes()).unwrap();
+    /// assert_eq!(restored.content_hash(), sketch.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        self.compact().content_hash()
+    }
+
+    /// Lowers theta until the compact image fits in `max_bytes`.
+    ///
+    /// Keeps the `r` smallest hashes that fit and sets theta to the
+    /// `(r + 1)`-th smallest, so `estimate()` becomes `r / (theta / 2^64)`,
+    /// the unbiased KMV estimator for `r` retained samples. Does nothing if
+    /// the sketch already fits.
+    ///
+    /// # Accuracy
+    ///
+    /// Relative standard error grows as `~1/sqrt(r)`, so halving the byte
+    /// budget costs about 41% more error. A trimmed sketch still unions
+    /// with untrimmed ones, since set operations take the minimum theta,
+    /// but the result inherits the coarser sampling. If `max_bytes` is
+    /// below the preamble size every entry is dropped and the estimate
+    /// becomes 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let mut sketch = ThetaSketch::new(12).unwrap();
+    /// for i in 0..100_000 {
+    ///     sketch.update(&i);
+    /// }
+    ///
+    /// sketch.trim_to_bytes(8 * 1024);
+    /// assert!(sketch.compact_size_bytes() <= 8 * 1024);
+    /// assert!((sketch.estimate() - 100_000.0).abs() < 10_000.0);
+    /// ```
+    pub fn trim_to_bytes(&mut self, max_bytes: usize) {
+        if self.compact_size_bytes() <= max_bytes {
+            return;
+        }
+
+        let keep = max_bytes.saturating_sub(Self::COMPACT_PREAMBLE_BYTES) / 8;
+        let mut sorted_entries: Vec<u64> = self.entries.iter().copied().collect();
+        sorted_entries.sort_unstable();
+
+        // The first dropped hash becomes theta, so exactly `keep` remain below it
+        let new_theta = sorted_entries[keep];
+        self.entries.retain(|&hash| hash < new_theta);
+        self.theta = new_theta;
+    }
+
     /// Computes union with another sketch: |A ∪ B|
     ///
     /// # Compatibility
@@ -410,10 +622,218 @@ impl ThetaSketch {
         })
     }
 
+    /// Estimates the Jaccard similarity |A ∩ B| / |A ∪ B| with confidence bounds.
+    ///
+    /// Returns `(lower, estimate, upper)`. Follows the Apache DataSketches
+    /// estimator: both sketches are cut at the joint theta
+    /// `min(theta_a, theta_b)`, and the entries of the union sample that are
+    /// also in both inputs form a binomial sample of the union. The ratio
+    /// and its bounds are computed from those two counts directly, rather
+    /// than from two separately scaled estimates, so the errors of the
+    /// intersection and union do not compound. The bounds cover roughly two
+    /// standard deviations, and collapse to the exact ratio when neither
+    /// sketch has started sampling.
+    ///
+    /// Two empty sketches are identical, giving `(1.0, 1.0, 1.0)`; one empty
+    /// sketch gives `(0.0, 0.0, 0.0)`. The lg_k values may differ.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the seeds differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let mut sketch_a = ThetaSketch::new(12).unwrap();
+    /// let mut sketch_b = ThetaSketch::new(12).unwrap();
+    ///
+    /// for i in 0..75 {
+    ///     sketch_a.update(&i);
+    /// }
+    /// for i in 25..100 {
+    ///     sketch_b.update(&i);
+    /// }
+    ///
+    /// // Exact mode: 50 shared out of 100 distinct
+    /// let (lower, estimate, upper) = sketch_a.jaccard(&sketch_b).unwrap();
+    /// assert_eq!((lower, estimate, upper), (0.5, 0.5, 0.5));
+    /// ```
+    pub fn jaccard(&self, other: &Self) -> Result<(f64, f64, f64)> {
+        if self.seed != other.seed {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("seed mismatch: {} vs {}", self.seed, other.seed),
+            });
+        }
+        if self.is_empty() && other.is_empty() {
+            return Ok((1.0, 1.0, 1.0));
+        }
+        if self.is_empty() || other.is_empty() {
+            return Ok((0.0, 0.0, 0.0));
+        }
+
+        let theta = self.theta.min(other.theta);
+        let count_a = self.entries.iter().filter(|&&hash| hash < theta).count();
+        let count_b = other.entries.iter().filter(|&&hash| hash < theta).count();
+        let intersection = self
+            .entries
+            .iter()
+            .filter(|&&hash| hash < theta && other.entries.contains(&hash))
+            .count();
+        let union = count_a + count_b - intersection;
+
+        if self.theta == other.theta && intersection == union {
+            return Ok((1.0, 1.0, 1.0));
+        }
+        if union == 0 {
+            // Nothing survives the joint theta: the ratio is unconstrained
+            return Ok((0.0, 0.5, 1.0));
+        }
+
+        let estimate = intersection as f64 / union as f64;
+        if theta == u64::MAX {
+            return Ok((estimate, estimate, estimate));
+        }
+
+        // Widen the interval slightly as the sampling fraction grows, as in
+        // DataSketches' BoundsOnRatiosInSampledSets
+        let f = theta as f64 / u64::MAX as f64;
+        let adjust = if f <= 0.5 {
+            (1.0 - f).sqrt()
+        } else {
+            (1.0 - f).sqrt() + 0.01 * (f - 0.5)
+        };
+        let num_std_devs = Self::JACCARD_STD_DEVS * adjust;
+        let (n, k) = (union as u64, intersection as u64);
+        Ok((
+            binomial_lower_bound(n, k, num_std_devs),
+            estimate,
+            binomial_upper_bound(n, k, num_std_devs),
+        ))
+    }
+
+    /// Computes the union of several sketches in one pass: |A ∪ B ∪ ...|
+    ///
+    /// Equivalent to folding [`union`](ThetaSketch::union) over the slice,
+    /// but without the intermediate sketches. The result is trimmed back to
+    /// `k` retained entries, so unioning many sketches stays bounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `sketches` is empty, and
+    /// `IncompatibleSketches` if the lg_k or seed values differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let mut a = ThetaSketch::new(12).unwrap();
+    /// let mut b = ThetaSketch::new(12).unwrap();
+    /// let mut c = ThetaSketch::new(12).unwrap();
+    /// for i in 0..100 {
+    ///     a.update(&i);
+    ///     b.update(&(i + 50));
+    ///     c.update(&(i + 100));
+    /// }
+    ///
+    /// let union = ThetaSketch::union_all(&[&a, &b, &c]).unwrap();
+    /// assert!((union.estimate() - 200.0).abs() < 5.0);
+    /// ```
+    pub fn union_all(sketches: &[&ThetaSketch]) -> Result<Self> {
+        let first = Self::check_all(sketches)?;
+        let new_theta = sketches.iter().map(|s| s.theta).min().unwrap_or(u64::MAX);
+
+        let mut result = Self {
+            lg_k: first.lg_k,
+            k: first.k,
+            entries: HashSet::with_capacity(first.k),
+            theta: new_theta,
+            seed: first.seed,
+        };
+        for sketch in sketches {
+            result
+                .entries
+                .extend(sketch.entries.iter().filter(|&&hash| hash < new_theta));
+        }
+        result.rebuild_with_lower_theta();
+
+        Ok(result)
+    }
+
+    /// Computes the intersection of several sketches: |A ∩ B ∩ ...|
+    ///
+    /// Equivalent to folding [`intersect`](ThetaSketch::intersect) over the
+    /// slice. Only the entries of the smallest sketch are probed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `sketches` is empty, and
+    /// `IncompatibleSketches` if the lg_k or seed values differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::ThetaSketch;
+    ///
+    /// let mut a = ThetaSketch::new(12).unwrap();
+    /// let mut b = ThetaSketch::new(12).unwrap();
+    /// let mut c = ThetaSketch::new(12).unwrap();
+    /// for i in 0..100 {
+    ///     a.update(&i);
+    ///     b.update(&(i + 25));
+    ///     c.update(&(i + 50));
+    /// }
+    ///
+    /// let intersection = ThetaSketch::intersect_all(&[&a, &b, &c]).unwrap();
+    /// assert!((intersection.estimate() - 50.0).abs() < 5.0);
+    /// ```
+    pub fn intersect_all(sketches: &[&ThetaSketch]) -> Result<Self> {
+        let first = Self::check_all(sketches)?;
+        let new_theta = sketches.iter().map(|s| s.theta).min().unwrap_or(u64::MAX);
+        let smallest = sketches
+            .iter()
+            .min_by_key(|s| s.entries.len())
+            .copied()
+            .unwrap_or(first);
+
+        let entries = smallest
+            .entries
+            .iter()
+            .copied()
+            .filter(|hash| *hash < new_theta && sketches.iter().all(|s| s.entries.contains(hash)))
+            .collect();
+
+        Ok(Self {
+            lg_k: first.lg_k,
+            k: first.k,
+            entries,
+            theta: new_theta,
+            seed: first.seed,
+        })
+    }
+
     // ============================================================================
     // Private Methods
     // ============================================================================
 
+    /// Checks that `sketches` is non-empty and mutually compatible, returning
+    /// the first sketch.
+    fn check_all<'a>(sketches: &[&'a ThetaSketch]) -> Result<&'a ThetaSketch> {
+        let first = sketches
+            .first()
+            .ok_or_else(|| SketchError::InvalidParameter {
+                param: "sketches".to_string(),
+                value: "0".to_string(),
+                constraint: "must contain at least one sketch".to_string(),
+            })?;
+        for other in &sketches[1..] {
+            first.check_compatibility(other)?;
+        }
+        Ok(first)
+    }
+
     /// Checks if two sketches are compatible for operations.
     fn check_compatibility(&self, other: &Self) -> Result<()> {
         if self.lg_k != other.lg_k {
@@ -467,6 +887,74 @@ impl ThetaSketch {
     }
 }
 
+/// Approximate lower confidence bound on the success probability `p` after
+/// `k` successes in `n` trials (DataSketches' BoundsOnBinomialProportions)
+fn binomial_lower_bound(n: u64, k: u64, num_std_devs: f64) -> f64 {
+    let delta = normal_cdf(-num_std_devs);
+    let nf = n as f64;
+    match k {
+        0 => 0.0,
+        1 => 1.0 - (1.0 - delta).powf(1.0 / nf),
+        _ if k == n => delta.powf(1.0 / nf),
+        _ => 1.0 - abramowitz_stegun_26_5_22((n - k + 1) as f64, k as f64, -num_std_devs),
+    }
+}
+
+/// Approximate upper confidence bound on `p`; see [`binomial_lower_bound`]
+fn binomial_upper_bound(n: u64, k: u64, num_std_devs: f64) -> f64 {
+    let delta = normal_cdf(-num_std_devs);
+    let nf = n as f64;
+    match k {
+        _ if k == n => 1.0,
+        _ if k + 1 == n => (1.0 - delta).powf(1.0 / nf),
+        0 => 1.0 - delta.powf(1.0 / nf),
+        _ => 1.0 - abramowitz_stegun_26_5_22((n - k) as f64, (k + 1) as f64, num_std_devs),
+    }
+}
+
+/// Normal approximation to the quantile of the Beta(a, b) distribution at
+/// `yp` standard deviations (Abramowitz & Stegun 26.5.22)
+fn abramowitz_stegun_26_5_22(a: f64, b: f64, yp: f64) -> f64 {
+    let a2m1 = 2.0 * a - 1.0;
+    let b2m1 = 2.0 * b - 1.0;
+    let lambda = (yp * yp - 3.0) / 6.0;
+    let h = 2.0 / (1.0 / a2m1 + 1.0 / b2m1);
+    let w = yp * (h + lambda).sqrt() / h
+        - (1.0 / b2m1 - 1.0 / a2m1) * (lambda + 5.0 / 6.0 - 2.0 / (3.0 * h));
+    a / (a + b * (2.0 * w).exp())
+}
+
+/// Standard normal CDF via the Abramowitz & Stegun 7.1.26 erf approximation
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+impl CardinalitySketch for ThetaSketch {
+    fn cardinality(&self) -> f64 {
+        self.estimate()
+    }
+
+    fn merge_from(&mut self, other: &Self) -> Result<()> {
+        *self = self.union(other)?;
+        Ok(())
+    }
+
+    /// Estimates from [`union`](ThetaSketch::union) without an extra clone
+    fn estimate_union(&self, other: &Self) -> Result<f64> {
+        Ok(self.union(other)?.estimate())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -501,4 +989,225 @@ mod tests {
             "Different seeds should produce different hashes"
         );
     }
+
+    #[test]
+    fn test_update_hash_matches_update() {
+        let mut hashed = ThetaSketch::with_seed(10, 77).unwrap();
+        let mut direct = ThetaSketch::with_seed(10, 77).unwrap();
+        for i in 0..50_000u64 {
+            direct.update(&i);
+            hashed.update_hash(direct.hash_item(&i));
+            // Seed is not reapplied, so duplicates of the raw hash collapse
+            hashed.update_hash(direct.hash_item(&i));
+        }
+        assert_eq!(hashed.theta, direct.theta);
+        assert_eq!(hashed.estimate(), direct.estimate());
+        assert_eq!(hashed.entries, direct.entries);
+    }
+
+    #[test]
+    fn test_trim_to_bytes() {
+        let mut sketch = ThetaSketch::new(12).unwrap();
+        for i in 0..200_000 {
+            sketch.update(&i);
+        }
+        assert_eq!(sketch.num_retained(), sketch.capacity() - 1);
+
+        let budget = 24 + 8 * 1024;
+        sketch.trim_to_bytes(budget);
+        assert_eq!(sketch.compact_size_bytes(), budget);
+        assert_eq!(sketch.num_retained(), 1024);
+        assert!(sketch.entries.iter().all(|&h| h < sketch.get_theta()));
+
+        // 1/sqrt(1024) ~ 3% standard error; allow a generous margin
+        let error = (sketch.estimate() - 200_000.0).abs() / 200_000.0;
+        assert!(error < 0.15, "Error {} too high after trim", error);
+
+        // Already fits: no change
+        let theta = sketch.get_theta();
+        sketch.trim_to_bytes(budget * 2);
+        assert_eq!(sketch.get_theta(), theta);
+
+        sketch.trim_to_bytes(0);
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate(), 0.0);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut sketch = ThetaSketch::new(4).unwrap();
+        for i in 0..100 {
+            sketch.update(&i);
+        }
+        let json = sketch.to_json();
+        assert!(json.starts_with(r#"{"type":"ThetaSketch","lg_k":4,"num_retained":15,"theta":0."#));
+        assert!(json.ends_with('}'));
+    }
+
+    #[test]
+    fn test_union_all_five_way() {
+        let sketches: Vec<ThetaSketch> = (0..5u64)
+            .map(|s| {
+                let mut sketch = ThetaSketch::new(12).unwrap();
+                for i in 0..1000 {
+                    sketch.update(&(s * 500 + i));
+                }
+                sketch
+            })
+            .collect();
+        let refs: Vec<&ThetaSketch> = sketches.iter().collect();
+
+        // Overlapping ranges cover 0..3000
+        let union = ThetaSketch::union_all(&refs).unwrap();
+        assert!((union.estimate() - 3000.0).abs() / 3000.0 < 0.05);
+        assert!(union.num_retained() <= union.capacity());
+
+        let folded = refs[1..]
+            .iter()
+            .fold(refs[0].clone(), |acc, s| acc.union(s).unwrap());
+        assert_eq!(union.entries, folded.entries);
+
+        // Exceeding k entries triggers sampling
+        let large: Vec<ThetaSketch> = (0..5u64)
+            .map(|s| {
+                let mut sketch = ThetaSketch::new(10).unwrap();
+                for i in 0..2000 {
+                    sketch.update(&(s * 2000 + i));
+                }
+                sketch
+            })
+            .collect();
+        let refs: Vec<&ThetaSketch> = large.iter().collect();
+        let union = ThetaSketch::union_all(&refs).unwrap();
+        assert!(union.num_retained() <= union.capacity());
+        assert!((union.estimate() - 10_000.0).abs() / 10_000.0 < 0.15);
+    }
+
+    #[test]
+    fn test_intersect_all_five_way() {
+        let sketches: Vec<ThetaSketch> = (0..5u64)
+            .map(|s| {
+                let mut sketch = ThetaSketch::new(12).unwrap();
+                for i in 0..1000 {
+                    sketch.update(&(s * 100 + i));
+                }
+                sketch
+            })
+            .collect();
+        let refs: Vec<&ThetaSketch> = sketches.iter().collect();
+
+        // All five contain 400..1000
+        let intersection = ThetaSketch::intersect_all(&refs).unwrap();
+        assert!((intersection.estimate() - 600.0).abs() / 600.0 < 0.05);
+
+        let folded = refs[1..]
+            .iter()
+            .fold(refs[0].clone(), |acc, s| acc.intersect(s).unwrap());
+        assert_eq!(intersection.entries, folded.entries);
+    }
+
+    #[test]
+    fn test_set_operations_all_validate() {
+        assert!(ThetaSketch::union_all(&[]).is_err());
+        assert!(ThetaSketch::intersect_all(&[]).is_err());
+
+        let a = ThetaSketch::new(12).unwrap();
+        let b = ThetaSketch::new(10).unwrap();
+        let c = ThetaSketch::with_seed(12, 1).unwrap();
+        assert!(ThetaSketch::union_all(&[&a, &b]).is_err());
+        assert!(ThetaSketch::intersect_all(&[&a, &c]).is_err());
+
+        let single = ThetaSketch::union_all(&[&a]).unwrap();
+        assert!(single.is_empty());
+    }
+
+    #[test]
+    fn test_update_numeric_matches_le_bytes() {
+        let mut numeric = ThetaSketch::new(10).unwrap();
+        let mut bytes = ThetaSketch::new(10).unwrap();
+        numeric.update_u64(5);
+        numeric.update_i64(-5);
+        numeric.update_f64(2.5);
+        bytes.update(&5u64.to_le_bytes().to_vec());
+        bytes.update(&(-5i64).to_le_bytes().to_vec());
+        bytes.update(&2.5f64.to_le_bytes().to_vec());
+
+        assert_eq!(numeric.entries, bytes.entries);
+    }
+
+    #[test]
+    fn test_jaccard_bounds_on_controlled_overlap() {
+        // A = 0..20_000, B = 10_000..30_000: J = 10_000 / 30_000
+        let truth = 1.0 / 3.0;
+        let (mut joint_error, mut naive_error, mut covered) = (0.0, 0.0, 0);
+        let trials = 20;
+        for seed in 0..trials {
+            let mut a = ThetaSketch::with_seed(10, seed).unwrap();
+            let mut b = ThetaSketch::with_seed(10, seed).unwrap();
+            for i in 0..20_000u64 {
+                a.update(&i);
+                b.update(&(i + 10_000));
+            }
+
+            let (lower, estimate, upper) = a.jaccard(&b).unwrap();
+            assert!(lower < estimate && estimate < upper);
+            if (lower..=upper).contains(&truth) {
+                covered += 1;
+            }
+
+            let naive = a.intersect(&b).unwrap().estimate() / a.union(&b).unwrap().estimate();
+            joint_error += (estimate - truth).abs();
+            naive_error += (naive - truth).abs();
+        }
+
+        // ~2 standard deviations should cover the truth in nearly every trial
+        assert!(covered >= trials - 2, "covered {} of {}", covered, trials);
+        assert!(
+            joint_error <= naive_error + 1e-9,
+            "joint {} vs naive {}",
+            joint_error,
+            naive_error
+        );
+        assert!(joint_error / (trials as f64) < 0.05);
+    }
+
+    #[test]
+    fn test_jaccard_edge_cases() {
+        let empty = ThetaSketch::new(12).unwrap();
+        let mut a = ThetaSketch::new(12).unwrap();
+        let mut other_lg_k = ThetaSketch::new(10).unwrap();
+        for i in 0..100 {
+            a.update(&i);
+            other_lg_k.update(&i);
+        }
+
+        assert_eq!(empty.jaccard(&empty).unwrap(), (1.0, 1.0, 1.0));
+        assert_eq!(a.jaccard(&empty).unwrap(), (0.0, 0.0, 0.0));
+        assert_eq!(a.jaccard(&a.clone()).unwrap(), (1.0, 1.0, 1.0));
+        assert_eq!(a.jaccard(&other_lg_k).unwrap(), (1.0, 1.0, 1.0));
+
+        let reseeded = ThetaSketch::with_seed(12, 1).unwrap();
+        assert!(a.jaccard(&reseeded).is_err());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let mut forward = ThetaSketch::new(10).unwrap();
+        let mut backward = ThetaSketch::new(10).unwrap();
+        for i in 0..500 {
+            forward.update(&i);
+            backward.update(&(499 - i));
+        }
+        assert_eq!(forward.content_hash(), backward.content_hash());
+
+        let restored = CompactThetaSketch::from_bytes(&forward.compact().to_bytes()).unwrap();
+        assert_eq!(restored.content_hash(), forward.content_hash());
+
+        backward.update(&500);
+        assert_ne!(forward.content_hash(), backward.content_hash());
+        assert_ne!(
+            ThetaSketch::new(10).unwrap().content_hash(),
+            ThetaSketch::with_seed(10, 1).unwrap().content_hash()
+        );
+    }
 }
@@ -35,14 +35,25 @@
 //! // Should be close to 10,000 with ~1.04/sqrt(4096) = ~1.6% error
 //! ```
 
-use crate::common::{validation, Mergeable, Sketch, SketchError};
+use crate::common::{validation, CardinalitySketch, Mergeable, Precision, Sketch, SketchError};
 use std::hash::{Hash, Hasher};
 use twox_hash::XxHash64;
 
+/// Magic bytes prefixed to serialized UltraLogLog sketches
+const SERIAL_MAGIC: &[u8; 4] = b"UULL";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
 /// UltraLogLog sketch for cardinality estimation
 ///
 /// Uses 2^p registers (each 8 bits) to estimate the number of unique items.
 /// Higher precision means more accuracy but more memory usage.
+///
+/// # Thread Safety
+///
+/// `UltraLogLog` is `Send + Sync`. Updates take `&mut self`; estimates read
+/// through `&self`.
 #[derive(Clone, Debug)]
 pub struct UltraLogLog {
     /// Precision parameter (4-18)
@@ -97,6 +108,65 @@ impl UltraLogLog {
         })
     }
 
+    /// Creates a new UltraLogLog sketch from an already-validated precision
+    ///
+    /// Infallible counterpart of [`new`](Self::new); see [`Precision`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::UltraLogLog;
+    /// use sketch_oxide::common::Precision;
+    /// use sketch_oxide::Sketch;
+    ///
+    /// let ull = UltraLogLog::new_checked(Precision::P12);
+    /// assert!(ull.is_empty());
+    /// ```
+    pub fn new_checked(precision: Precision) -> Self {
+        UltraLogLog {
+            precision: precision.get(),
+            registers: vec![0u8; 1usize << precision.get()],
+        }
+    }
+
+    /// Creates the most precise UltraLogLog whose registers fit in `bytes`
+    ///
+    /// Registers take one byte each, so this picks the largest precision `p`
+    /// with `2^p <= bytes`, capped at 18. This implementation's estimator has
+    /// HyperLogLog's relative standard error of about `1.04 / sqrt(2^p)`, so
+    /// an 8 KB budget gives precision 13 and ~1.15%. The budget covers the
+    /// register array only, not the serialization header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `bytes` is below the 16 registers of
+    /// precision 4.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::UltraLogLog;
+    ///
+    /// let ull = UltraLogLog::for_byte_budget(8 * 1024).unwrap();
+    /// assert_eq!(ull.precision(), 13);
+    /// ```
+    pub fn for_byte_budget(bytes: usize) -> Result<Self, SketchError> {
+        match bytes.checked_ilog2() {
+            Some(p) if p >= 4 => Self::new(p.min(18) as u8),
+            _ => Err(SketchError::InvalidParameter {
+                param: "bytes".to_string(),
+                value: bytes.to_string(),
+                constraint: "must be at least 16".to_string(),
+            }),
+        }
+    }
+
+    /// Returns the precision parameter
+    #[inline]
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
     /// Returns the number of registers (m = 2^precision)
     #[inline]
     fn register_count(&self) -> usize {
@@ -185,6 +255,31 @@ impl UltraLogLog {
         self.bias_corrected_estimate(raw)
     }
 
+    /// Returns a JSON summary of parameters and state for debugging
+    ///
+    /// Reports the precision, register count, estimate and number of
+    /// non-zero registers, but not the registers themselves.
+    /// Not meant for reconstruction; use the binary serialization for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::UltraLogLog;
+    ///
+    /// let ull = UltraLogLog::new(12).unwrap();
+    /// assert!(ull.to_json().starts_with(r#"{"type":"UltraLogLog","precision":12,"#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let num_nonzero = self.registers.iter().filter(|&&r| r != 0).count();
+        format!(
+            r#"{{"type":"UltraLogLog","precision":{},"num_registers":{},"estimate":{},"num_nonzero":{}}}"#,
+            self.precision,
+            self.registers.len(),
+            self.cardinality(),
+            num_nonzero
+        )
+    }
+
     /// Fast hash function using XXHash64
     #[inline(always)]
     fn hash_item<T: Hash>(&self, item: &T) -> u64 {
@@ -234,6 +329,27 @@ impl UltraLogLog {
             *reg = (*reg).max(leading_zeros);
         }
     }
+
+    /// Adds a `u64` by hashing its 8 little-endian bytes
+    ///
+    /// Equivalent to `add(&value.to_le_bytes())`, which is what the language
+    /// bindings do with a byte buffer, so numeric IDs added from Rust and from JavaScript or Python land in the same registers.
+    /// This differs from `add(&value)`, which hashes through `Hash for u64`.
+    pub fn update_u64(&mut self, value: u64) {
+        self.add(&value.to_le_bytes());
+    }
+
+    /// Adds an `i64` by hashing its 8 little-endian two's-complement bytes
+    pub fn update_i64(&mut self, value: i64) {
+        self.add(&value.to_le_bytes());
+    }
+
+    /// Adds an `f64` by hashing the 8 little-endian bytes of its IEEE 754 bits
+    ///
+    /// `0.0` and `-0.0`, like NaNs with different payloads, count as distinct.
+    pub fn update_f64(&mut self, value: f64) {
+        self.add(&value.to_le_bytes());
+    }
 }
 
 impl Sketch for UltraLogLog {
@@ -252,24 +368,32 @@ impl Sketch for UltraLogLog {
     }
 
     fn serialize(&self) -> Vec<u8> {
-        // Format: [precision: 1 byte] [registers: 2^p bytes]
-        let mut bytes = Vec::with_capacity(1 + self.registers.len());
+        // Format: [magic: "UULL"] [version: 1 byte] [precision: 1 byte] [registers: 2^p bytes]
+        let mut bytes = Vec::with_capacity(validation::HEADER_LEN + 1 + self.registers.len());
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
         bytes.push(self.precision);
         bytes.extend_from_slice(&self.registers);
         bytes
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self, SketchError> {
-        // Check minimum size
-        validation::validate_min_size(bytes.len(), 1)?;
-
         // Check total size doesn't exceed safety limit
         validation::validate_byte_size(bytes.len())?;
 
+        // Bytes written before the header existed start with the precision,
+        // which can never be 'U'; reject unknown format versions
+        let bytes = if bytes.starts_with(SERIAL_MAGIC) {
+            validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?
+        } else {
+            bytes
+        };
+        validation::validate_min_size(bytes.len(), 1)?;
+
         let precision = bytes[0];
 
-        // Validate precision using centralized validator
-        validation::validate_precision(precision)?;
+        // Precision errors surface as deserialization errors for corrupt input
+        validation::validate_precision(precision)
+            .map_err(|e| SketchError::DeserializationError(e.to_string()))?;
 
         // Calculate and verify expected length
         let expected_len = 1 + (1 << precision);
@@ -312,6 +436,16 @@ impl Mergeable for UltraLogLog {
     }
 }
 
+impl CardinalitySketch for UltraLogLog {
+    fn cardinality(&self) -> f64 {
+        Sketch::estimate(self)
+    }
+
+    fn merge_from(&mut self, other: &Self) -> Result<(), SketchError> {
+        self.merge(other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,4 +483,30 @@ mod tests {
         // Remaining 52 bits are all zeros, so leading_zeros = 52 + 1 = 53
         assert_eq!(zeros, 53);
     }
+
+    #[test]
+    fn test_to_json() {
+        let mut ull = UltraLogLog::new(8).unwrap();
+        assert_eq!(
+            ull.to_json(),
+            r#"{"type":"UltraLogLog","precision":8,"num_registers":256,"estimate":0,"num_nonzero":0}"#
+        );
+
+        ull.update(&1u64);
+        assert!(ull.to_json().ends_with(r#""num_nonzero":1}"#));
+    }
+
+    #[test]
+    fn test_update_numeric_matches_le_bytes() {
+        let mut numeric = UltraLogLog::new(10).unwrap();
+        let mut bytes = UltraLogLog::new(10).unwrap();
+        numeric.update_u64(5);
+        numeric.update_i64(-5);
+        numeric.update_f64(2.5);
+        bytes.add(&5u64.to_le_bytes().to_vec());
+        bytes.add(&(-5i64).to_le_bytes().to_vec());
+        bytes.add(&2.5f64.to_le_bytes().to_vec());
+
+        assert_eq!(numeric.registers, bytes.registers);
+    }
 }
@@ -0,0 +1,172 @@
+//! Exact update counting for debugging estimator accuracy
+//!
+//! [`Counted`] wraps any [`Sketch`] and records the exact number of updates it
+//! has seen, so estimates can be compared against the true stream length
+//! while validating a sketch. Only available with the `count_updates` feature.
+
+use super::error::SketchError;
+use super::traits::{Mergeable, Sketch};
+
+/// A sketch wrapper that counts every update exactly
+///
+/// The wrapper adds a single `u64` and performs no allocation. The count is
+/// the number of updates, not the number of distinct items, so for
+/// cardinality sketches it is an upper bound on the true cardinality.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::cardinality::HyperLogLog;
+/// use sketch_oxide::common::Counted;
+/// use sketch_oxide::Sketch;
+///
+/// let mut hll = Counted::new(HyperLogLog::new(12).unwrap());
+/// for i in 0..1000u64 {
+///     hll.update(&i);
+/// }
+///
+/// assert_eq!(hll.true_count(), 1000);
+/// assert!((hll.estimate() - 1000.0).abs() < 50.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counted<S: Sketch> {
+    /// Wrapped sketch
+    inner: S,
+    /// Exact number of updates applied through this wrapper
+    count: u64,
+}
+
+impl<S: Sketch> Counted<S> {
+    /// Wraps a sketch with an update count of zero
+    ///
+    /// Updates applied to `inner` before wrapping are not counted.
+    pub fn new(inner: S) -> Self {
+        Counted { inner, count: 0 }
+    }
+
+    /// Returns the exact number of updates seen
+    #[inline]
+    pub fn true_count(&self) -> u64 {
+        self.count
+    }
+
+    /// Applies an update through the inner sketch's own API and counts it once
+    ///
+    /// Use this for update methods outside the [`Sketch`] trait, such as the
+    /// generic `update<T: Hash>` methods most sketches provide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::common::Counted;
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = Counted::new(CountMinSketch::new(0.01, 0.01).unwrap());
+    /// cms.update_with(|s| s.update(&"apple"));
+    /// cms.update_with(|s| s.update(&"apple"));
+    ///
+    /// assert_eq!(cms.true_count(), 2);
+    /// assert!(cms.inner().estimate(&"apple") >= 2);
+    /// ```
+    #[inline]
+    pub fn update_with<F: FnOnce(&mut S)>(&mut self, f: F) {
+        f(&mut self.inner);
+        self.count += 1;
+    }
+
+    /// Returns a reference to the wrapped sketch
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped sketch
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Sketch> Sketch for Counted<S> {
+    type Item = S::Item;
+
+    fn update(&mut self, item: &Self::Item) {
+        self.inner.update(item);
+        self.count += 1;
+    }
+
+    fn estimate(&self) -> f64 {
+        self.inner.estimate()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        // Format: [count:8][inner_sketch_data]
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes.extend_from_slice(&self.inner.serialize());
+        bytes
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self, SketchError> {
+        if bytes.len() < 8 {
+            return Err(SketchError::DeserializationError(
+                "Insufficient data for Counted header".to_string(),
+            ));
+        }
+
+        let count = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let inner = S::deserialize(&bytes[8..])?;
+
+        Ok(Counted { inner, count })
+    }
+}
+
+impl<S: Mergeable> Mergeable for Counted<S> {
+    /// Merges the wrapped sketches and adds the update counts
+    fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        self.inner.merge(&other.inner)?;
+        self.count += other.count;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cardinality::HyperLogLog;
+
+    #[test]
+    fn test_counted_hyperloglog() {
+        let mut hll = Counted::new(HyperLogLog::new(12).unwrap());
+        for i in 0..10_000u64 {
+            hll.update(&i);
+        }
+        // Duplicates are counted as updates but not as distinct items
+        for i in 0..1000u64 {
+            hll.update(&i);
+        }
+
+        assert_eq!(hll.true_count(), 11_000);
+        let error = (hll.estimate() - 10_000.0).abs() / 10_000.0;
+        assert!(error < 0.05, "Error {} too high", error);
+    }
+
+    #[test]
+    fn test_counted_merge_and_roundtrip() {
+        let mut a = Counted::new(HyperLogLog::new(10).unwrap());
+        let mut b = Counted::new(HyperLogLog::new(10).unwrap());
+        a.update(&1);
+        b.update(&2);
+        b.update_with(|s| s.update(&"three"));
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.true_count(), 3);
+
+        let restored = Counted::<HyperLogLog>::deserialize(&a.serialize()).unwrap();
+        assert_eq!(restored.true_count(), 3);
+        assert_eq!(restored.inner().registers(), a.inner().registers());
+    }
+}
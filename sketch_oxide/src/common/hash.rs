@@ -104,6 +104,39 @@ pub fn xxhash(data: &[u8], seed: u64) -> u64 {
     hasher.finish()
 }
 
+/// Canonical hash of a byte string for sketches that take a pre-hashed `u64`
+///
+/// XXHash64 of the raw bytes with seed 0. Every language binding reduces byte
+/// input to a `u64` this way (for [`CpcSketch`](crate::cardinality::CpcSketch)
+/// and for bindings that key items by hash), so the same bytes give the same
+/// sketch state from Rust, Python, Node, Java and .NET. Sketches with a generic
+/// `update<T: Hash>` instead receive the byte slice itself, which every
+/// binding passes unchanged.
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::common::hash::{hash_bytes, xxhash};
+///
+/// assert_eq!(hash_bytes(b"hello world"), xxhash(b"hello world", 0));
+/// ```
+pub fn hash_bytes(data: &[u8]) -> u64 {
+    xxhash(data, 0)
+}
+
+/// SplitMix64 finalizer: scrambles a 64-bit value so every input bit
+/// affects every output bit
+///
+/// `z ^= z >> 30; z *= 0xbf58476d1ce4e5b9; z ^= z >> 27;
+/// z *= 0x94d049bb133111eb; z ^= z >> 31` (wrapping). Used to derive extra
+/// hashes from a single one and to turn counters into pseudo-random bits.
+#[inline]
+pub(crate) fn mix64(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
 /// MurmurHash3 64-bit implementation
 ///
 /// Extended version of MurmurHash3 that produces 64-bit hashes.
@@ -1,11 +1,20 @@
 //! Common utilities, traits, and errors
 
+#[cfg(feature = "count_updates")]
+mod counted;
 mod error;
 pub mod hash;
+mod registry;
 mod traits;
 mod types;
 pub mod validation;
 
+#[cfg(feature = "count_updates")]
+pub use counted::Counted;
 pub use error::{Result, SketchError};
-pub use traits::{Mergeable, RangeFilter, Reconcilable, Sketch, WindowedSketch};
-pub use types::SetDifference;
+pub use registry::{create_sketch, load_sketch, BoxedSketch, SketchParams, SKETCH_KINDS};
+pub use traits::{
+    CardinalitySketch, FrequencySketch, Mergeable, QuantileSketch, RangeFilter, Reconcilable,
+    Sketch, WindowedSketch,
+};
+pub use types::{LgK, Precision, SetDifference, SetDifferenceEstimate};
@@ -0,0 +1,163 @@
+//! Runtime sketch registry for choosing a sketch type by name
+//!
+//! Services that accept the sketch type per request cannot name a concrete
+//! type at compile time. [`create_sketch`] and [`load_sketch`] map a kind
+//! string to a boxed [`Sketch`] trait object, so the caller can update,
+//! estimate and serialize without matching on every sketch type.
+//!
+//! All registered kinds take `u64` items (pre-hashed keys or integer values)
+//! through [`Sketch::update`]. Weighted sketches such as `QSketch`, whose
+//! trait update is unsupported, are not registered.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::common::{create_sketch, load_sketch, SketchParams};
+//!
+//! let mut sketch = create_sketch("hyperloglog", &SketchParams::Precision(12)).unwrap();
+//! for i in 0..1000u64 {
+//!     sketch.update(&i);
+//! }
+//!
+//! let restored = load_sketch("hyperloglog", &sketch.serialize()).unwrap();
+//! assert_eq!(restored.estimate(), sketch.estimate());
+//! ```
+
+use super::error::SketchError;
+use super::traits::Sketch;
+use crate::cardinality::{CpcSketch, HyperLogLog, UltraLogLog};
+use crate::frequency::{CountMinSketch, ElasticSketch};
+
+/// A heap-allocated sketch chosen at runtime
+pub type BoxedSketch = Box<dyn Sketch<Item = u64> + Send + Sync>;
+
+/// Kind names accepted by [`create_sketch`] and [`load_sketch`]
+pub const SKETCH_KINDS: &[&str] = &["hyperloglog", "ultraloglog", "cpc", "count_min", "elastic"];
+
+/// Construction parameters for [`create_sketch`]
+///
+/// Each variant is the parameter bundle of one or more sketch kinds.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SketchParams {
+    /// Register precision for `hyperloglog` and `ultraloglog`
+    Precision(u8),
+    /// Log2 of the slot count for `cpc`
+    LgK(u8),
+    /// Error bounds for `count_min`
+    ErrorBounds {
+        /// Additive error as a fraction of the stream size
+        epsilon: f64,
+        /// Failure probability
+        delta: f64,
+    },
+    /// Bucket layout for `elastic`
+    Buckets {
+        /// Buckets per row
+        bucket_count: usize,
+        /// Number of rows
+        depth: usize,
+    },
+}
+
+/// Creates an empty sketch of the named kind
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `kind` is not in [`SKETCH_KINDS`], if
+/// `params` is the wrong bundle for `kind`, or if the sketch's own
+/// constructor rejects the parameters.
+pub fn create_sketch(kind: &str, params: &SketchParams) -> Result<BoxedSketch, SketchError> {
+    match (kind, params) {
+        ("hyperloglog", SketchParams::Precision(p)) => Ok(Box::new(HyperLogLog::new(*p)?)),
+        ("ultraloglog", SketchParams::Precision(p)) => Ok(Box::new(UltraLogLog::new(*p)?)),
+        ("cpc", SketchParams::LgK(lg_k)) => Ok(Box::new(CpcSketch::new(*lg_k)?)),
+        ("count_min", SketchParams::ErrorBounds { epsilon, delta }) => {
+            Ok(Box::new(CountMinSketch::new(*epsilon, *delta)?))
+        }
+        (
+            "elastic",
+            SketchParams::Buckets {
+                bucket_count,
+                depth,
+            },
+        ) => Ok(Box::new(ElasticSketch::new(*bucket_count, *depth)?)),
+        _ if SKETCH_KINDS.contains(&kind) => Err(SketchError::InvalidParameter {
+            param: "params".to_string(),
+            value: format!("{:?}", params),
+            constraint: format!("is not a parameter bundle for kind '{}'", kind),
+        }),
+        _ => Err(unknown_kind(kind)),
+    }
+}
+
+/// Deserializes a sketch of the named kind
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `kind` is unknown, or the sketch's
+/// deserialization error if `bytes` are invalid.
+pub fn load_sketch(kind: &str, bytes: &[u8]) -> Result<BoxedSketch, SketchError> {
+    match kind {
+        "hyperloglog" => Ok(Box::new(HyperLogLog::deserialize(bytes)?)),
+        "ultraloglog" => Ok(Box::new(UltraLogLog::deserialize(bytes)?)),
+        "cpc" => Ok(Box::new(CpcSketch::deserialize(bytes)?)),
+        "count_min" => Ok(Box::new(CountMinSketch::deserialize(bytes)?)),
+        "elastic" => Ok(Box::new(ElasticSketch::deserialize(bytes)?)),
+        _ => Err(unknown_kind(kind)),
+    }
+}
+
+fn unknown_kind(kind: &str) -> SketchError {
+    SketchError::InvalidParameter {
+        param: "kind".to_string(),
+        value: kind.to_string(),
+        constraint: format!("must be one of {:?}", SKETCH_KINDS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params_for(kind: &str) -> SketchParams {
+        match kind {
+            "hyperloglog" | "ultraloglog" => SketchParams::Precision(12),
+            "cpc" => SketchParams::LgK(11),
+            "count_min" => SketchParams::ErrorBounds {
+                epsilon: 0.01,
+                delta: 0.01,
+            },
+            "elastic" => SketchParams::Buckets {
+                bucket_count: 256,
+                depth: 3,
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_create_and_roundtrip_every_kind() {
+        for kind in SKETCH_KINDS {
+            let mut sketch = create_sketch(kind, &params_for(kind)).unwrap();
+            assert!(sketch.is_empty(), "{} should start empty", kind);
+
+            for i in 0..500u64 {
+                sketch.update(&i);
+            }
+            assert!(!sketch.is_empty(), "{} should not be empty", kind);
+
+            let restored = load_sketch(kind, &sketch.serialize()).unwrap();
+            assert!(!restored.is_empty(), "{} round trip", kind);
+            assert_eq!(restored.estimate(), sketch.estimate(), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn test_registry_errors() {
+        assert!(create_sketch("bogus", &SketchParams::Precision(12)).is_err());
+        assert!(create_sketch("hyperloglog", &SketchParams::LgK(11)).is_err());
+        assert!(create_sketch("hyperloglog", &SketchParams::Precision(30)).is_err());
+        assert!(load_sketch("bogus", &[]).is_err());
+        assert!(load_sketch("cpc", &[1, 2, 3]).is_err());
+    }
+}
@@ -13,6 +13,11 @@ use std::hash::Hash;
 /// - **Single Responsibility**: This trait focuses solely on core sketch operations
 /// - **Interface Segregation**: Minimal interface - only essential operations
 /// - **Liskov Substitution**: All implementations must maintain the contract
+///
+/// # Object Safety
+/// The trait is object safe (`deserialize` is bound by `Self: Sized`), so
+/// sketches can be used as `Box<dyn Sketch<Item = T>>`. See
+/// [`create_sketch`](crate::common::create_sketch) for runtime construction.
 pub trait Sketch {
     /// The type of items this sketch can process
     type Item;
@@ -86,6 +91,158 @@ pub trait Mergeable: Sketch {
     /// - Sketches have different configurations (e.g., different precision)
     /// - Sketches are of incompatible types
     fn merge(&mut self, other: &Self) -> Result<(), SketchError>;
+
+    /// Returns the merge of this sketch and `other`, leaving both unchanged
+    ///
+    /// Clones `self` and merges `other` into the copy, which suits folds over
+    /// shared references.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`merge`](Mergeable::merge).
+    ///
+    /// # Example
+    /// ```
+    /// use sketch_oxide::common::Mergeable;
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let shards: Vec<KllSketch> = (0..4)
+    ///     .map(|s| {
+    ///         let mut kll = KllSketch::new(200).unwrap();
+    ///         for i in 0..250 {
+    ///             kll.update((s * 250 + i) as f64);
+    ///         }
+    ///         kll
+    ///     })
+    ///     .collect();
+    ///
+    /// let total = shards[1..]
+    ///     .iter()
+    ///     .try_fold(shards[0].clone(), |acc, shard| acc.merged(shard))
+    ///     .unwrap();
+    /// assert_eq!(total.count(), 1000);
+    /// assert_eq!(shards[0].count(), 250);
+    /// ```
+    fn merged(&self, other: &Self) -> Result<Self, SketchError>
+    where
+        Self: Clone,
+    {
+        let mut result = self.clone();
+        result.merge(other)?;
+        Ok(result)
+    }
+}
+
+/// Trait for distinct-count sketches that support union
+///
+/// Gives HyperLogLog, UltraLogLog, CPC and Theta one union API.
+/// [`estimate_union`](CardinalitySketch::estimate_union) works on a clone,
+/// so neither input is mutated.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::cardinality::HyperLogLog;
+/// use sketch_oxide::common::CardinalitySketch;
+///
+/// let mut a = HyperLogLog::new(12).unwrap();
+/// let mut b = HyperLogLog::new(12).unwrap();
+/// for i in 0..1000u64 {
+///     a.update(&i);
+///     b.update(&(i + 500));
+/// }
+///
+/// let union = a.estimate_union(&b).unwrap();
+/// assert!((union - 1500.0).abs() < 100.0);
+/// ```
+pub trait CardinalitySketch: Clone {
+    /// Returns the estimated number of distinct items
+    fn cardinality(&self) -> f64;
+
+    /// Folds another sketch into this one so it represents the union
+    ///
+    /// # Errors
+    /// Returns `SketchError::IncompatibleSketches` if the sketches have
+    /// different configurations (precision, lg_k or seed).
+    fn merge_from(&mut self, other: &Self) -> Result<(), SketchError>;
+
+    /// Estimates the number of distinct items in the union of both sketches
+    ///
+    /// # Errors
+    /// Returns the same errors as [`merge_from`](CardinalitySketch::merge_from).
+    fn estimate_union(&self, other: &Self) -> Result<f64, SketchError> {
+        let mut union = self.clone();
+        union.merge_from(other)?;
+        Ok(union.cardinality())
+    }
+}
+
+/// Trait for frequency sketches that answer non-negative point queries
+///
+/// Gives CountMinSketch, ConservativeCountMin, ElasticSketch and SALSA one
+/// update/estimate API over byte-string items, so they can be swapped
+/// behind a `Box<dyn FrequencySketch>`. Items are hashed the same way as
+/// the inherent methods called with a `&[u8]`, so both APIs see the same
+/// counters.
+///
+/// CountSketch is not implemented: its estimates are signed and unbiased
+/// rather than one-sided, so it would need a separate trait returning `i64`.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::common::FrequencySketch;
+/// use sketch_oxide::frequency::{ConservativeCountMin, CountMinSketch};
+///
+/// let mut sketches: Vec<Box<dyn FrequencySketch>> = vec![
+///     Box::new(CountMinSketch::new(0.01, 0.01).unwrap()),
+///     Box::new(ConservativeCountMin::new(0.01, 0.01).unwrap()),
+/// ];
+/// for sketch in &mut sketches {
+///     sketch.update(b"apple", 3);
+///     assert!(sketch.estimate(b"apple") >= 3);
+/// }
+/// ```
+pub trait FrequencySketch {
+    /// Adds `count` occurrences of `item`
+    fn update(&mut self, item: &[u8], count: u64);
+
+    /// Returns the estimated number of occurrences of `item`
+    ///
+    /// Never less than the true count for these sketches, barring counter
+    /// saturation.
+    fn estimate(&self, item: &[u8]) -> u64;
+}
+
+/// Trait for quantile sketches over `f64` values
+///
+/// Gives KllSketch, TDigest, DDSketch, ReqSketch, GkSketch and SplineSketch
+/// one update/query API, so they can be compared on the same stream (see
+/// [`accuracy_report`](crate::quantiles::accuracy_report)). `quantile`
+/// takes `&mut self` because KLL and t-digest sort or flush lazily.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::common::QuantileSketch;
+/// use sketch_oxide::quantiles::{KllSketch, TDigest};
+///
+/// let mut sketches: Vec<Box<dyn QuantileSketch>> = vec![
+///     Box::new(KllSketch::new(200).unwrap()),
+///     Box::new(TDigest::new(100.0)),
+/// ];
+/// for sketch in &mut sketches {
+///     for i in 1..=1000 {
+///         sketch.update(i as f64);
+///     }
+///     let median = sketch.quantile(0.5).unwrap();
+///     assert!((median - 500.0).abs() < 50.0);
+/// }
+/// ```
+pub trait QuantileSketch {
+    /// Adds a value to the sketch
+    fn update(&mut self, value: f64);
+
+    /// Returns the estimated value at normalized rank `q` in [0, 1]
+    ///
+    /// Returns `None` if the sketch is empty.
+    fn quantile(&mut self, q: f64) -> Option<f64>;
 }
 
 /// Trait for filters that support range-based queries
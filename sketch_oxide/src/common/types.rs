@@ -67,6 +67,235 @@ impl Default for SetDifference {
     }
 }
 
+/// Estimated sizes of the difference between two sets
+///
+/// The counterpart of [`SetDifference`] for similarity sketches such as
+/// [`MinHash`](crate::similarity::MinHash), which can tell how much two sets
+/// differ but not which elements differ. Fields follow the same direction as
+/// [`SetDifference`]: `to_insert` counts elements of A missing from B.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::common::SetDifferenceEstimate;
+///
+/// let diff = SetDifferenceEstimate {
+///     to_insert: 40.0,
+///     to_remove: 10.0,
+///     intersection: 60.0,
+/// };
+/// assert_eq!(diff.total_changes(), 50.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SetDifferenceEstimate {
+    /// Estimated |A \ B|
+    pub to_insert: f64,
+
+    /// Estimated |B \ A|
+    pub to_remove: f64,
+
+    /// Estimated |A ∩ B|
+    pub intersection: f64,
+}
+
+impl SetDifferenceEstimate {
+    /// Returns the estimated size of the symmetric difference
+    pub fn total_changes(&self) -> f64 {
+        self.to_insert + self.to_remove
+    }
+}
+
+/// HyperLogLog-family register precision, validated on construction
+///
+/// Valid values are 4 through 18. Use a constant such as [`Precision::P14`]
+/// or [`Precision::new`] in a `const` item to reject an invalid precision at
+/// compile time; use [`Precision::try_new`] for values known only at runtime.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::cardinality::HyperLogLog;
+/// use sketch_oxide::common::Precision;
+///
+/// const PRECISION: Precision = Precision::new(14);
+/// let hll = HyperLogLog::new_checked(PRECISION);
+/// assert_eq!(hll.precision(), 14);
+/// ```
+///
+/// An out-of-range precision in a `const` item fails to compile:
+///
+/// ```compile_fail
+/// use sketch_oxide::common::Precision;
+///
+/// const PRECISION: Precision = Precision::new(30);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Precision(u8);
+
+impl Precision {
+    /// Smallest valid precision
+    pub const MIN: u8 = 4;
+    /// Largest valid precision
+    pub const MAX: u8 = 18;
+
+    /// Precision 4 (16 registers)
+    pub const P4: Precision = Precision(4);
+    /// Precision 5 (32 registers)
+    pub const P5: Precision = Precision(5);
+    /// Precision 6 (64 registers)
+    pub const P6: Precision = Precision(6);
+    /// Precision 7 (128 registers)
+    pub const P7: Precision = Precision(7);
+    /// Precision 8 (256 registers)
+    pub const P8: Precision = Precision(8);
+    /// Precision 9 (512 registers)
+    pub const P9: Precision = Precision(9);
+    /// Precision 10 (1024 registers)
+    pub const P10: Precision = Precision(10);
+    /// Precision 11 (2048 registers)
+    pub const P11: Precision = Precision(11);
+    /// Precision 12 (4096 registers)
+    pub const P12: Precision = Precision(12);
+    /// Precision 13 (8192 registers)
+    pub const P13: Precision = Precision(13);
+    /// Precision 14 (16384 registers)
+    pub const P14: Precision = Precision(14);
+    /// Precision 15 (32768 registers)
+    pub const P15: Precision = Precision(15);
+    /// Precision 16 (65536 registers)
+    pub const P16: Precision = Precision(16);
+    /// Precision 17 (131072 registers)
+    pub const P17: Precision = Precision(17);
+    /// Precision 18 (262144 registers)
+    pub const P18: Precision = Precision(18);
+
+    /// Creates a precision, panicking if it is outside 4..=18
+    ///
+    /// In a `const` context the panic is a compile error.
+    pub const fn new(precision: u8) -> Self {
+        assert!(
+            precision >= Self::MIN && precision <= Self::MAX,
+            "precision must be between 4 and 18"
+        );
+        Precision(precision)
+    }
+
+    /// Creates a precision, or `None` if it is outside 4..=18
+    pub const fn try_new(precision: u8) -> Option<Self> {
+        if precision >= Self::MIN && precision <= Self::MAX {
+            Some(Precision(precision))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the precision as a `u8`
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+/// Log2 of the nominal size `k` for CPC and Theta sketches, validated on
+/// construction
+///
+/// Valid values are 4 through 26. As with [`Precision`], a constant or a
+/// `const` item built with [`LgK::new`] rejects invalid values at compile
+/// time.
+///
+/// # Example
+/// ```
+/// use sketch_oxide::cardinality::CpcSketch;
+/// use sketch_oxide::common::LgK;
+///
+/// let cpc = CpcSketch::new_checked(LgK::L11);
+/// assert_eq!(cpc.lg_k(), 11);
+/// ```
+///
+/// ```compile_fail
+/// use sketch_oxide::common::LgK;
+///
+/// const LG_K: LgK = LgK::new(27);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LgK(u8);
+
+impl LgK {
+    /// Smallest valid lg_k
+    pub const MIN: u8 = 4;
+    /// Largest valid lg_k
+    pub const MAX: u8 = 26;
+
+    /// lg_k 4 (k = 16)
+    pub const L4: LgK = LgK(4);
+    /// lg_k 5 (k = 32)
+    pub const L5: LgK = LgK(5);
+    /// lg_k 6 (k = 64)
+    pub const L6: LgK = LgK(6);
+    /// lg_k 7 (k = 128)
+    pub const L7: LgK = LgK(7);
+    /// lg_k 8 (k = 256)
+    pub const L8: LgK = LgK(8);
+    /// lg_k 9 (k = 512)
+    pub const L9: LgK = LgK(9);
+    /// lg_k 10 (k = 1024)
+    pub const L10: LgK = LgK(10);
+    /// lg_k 11 (k = 2048)
+    pub const L11: LgK = LgK(11);
+    /// lg_k 12 (k = 4096)
+    pub const L12: LgK = LgK(12);
+    /// lg_k 13 (k = 8192)
+    pub const L13: LgK = LgK(13);
+    /// lg_k 14 (k = 16384)
+    pub const L14: LgK = LgK(14);
+    /// lg_k 15 (k = 32768)
+    pub const L15: LgK = LgK(15);
+    /// lg_k 16 (k = 65536)
+    pub const L16: LgK = LgK(16);
+    /// lg_k 17 (k = 131072)
+    pub const L17: LgK = LgK(17);
+    /// lg_k 18 (k = 262144)
+    pub const L18: LgK = LgK(18);
+    /// lg_k 19 (k = 524288)
+    pub const L19: LgK = LgK(19);
+    /// lg_k 20 (k = 1048576)
+    pub const L20: LgK = LgK(20);
+    /// lg_k 21 (k = 2097152)
+    pub const L21: LgK = LgK(21);
+    /// lg_k 22 (k = 4194304)
+    pub const L22: LgK = LgK(22);
+    /// lg_k 23 (k = 8388608)
+    pub const L23: LgK = LgK(23);
+    /// lg_k 24 (k = 16777216)
+    pub const L24: LgK = LgK(24);
+    /// lg_k 25 (k = 33554432)
+    pub const L25: LgK = LgK(25);
+    /// lg_k 26 (k = 67108864)
+    pub const L26: LgK = LgK(26);
+
+    /// Creates an lg_k, panicking if it is outside 4..=26
+    ///
+    /// In a `const` context the panic is a compile error.
+    pub const fn new(lg_k: u8) -> Self {
+        assert!(
+            lg_k >= Self::MIN && lg_k <= Self::MAX,
+            "lg_k must be between 4 and 26"
+        );
+        LgK(lg_k)
+    }
+
+    /// Creates an lg_k, or `None` if it is outside 4..=26
+    pub const fn try_new(lg_k: u8) -> Option<Self> {
+        if lg_k >= Self::MIN && lg_k <= Self::MAX {
+            Some(LgK(lg_k))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the lg_k as a `u8`
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,6 +327,27 @@ mod tests {
         assert_eq!(diff1, diff2);
     }
 
+    #[test]
+    fn test_precision_and_lg_k_bounds() {
+        assert_eq!(Precision::P4.get(), Precision::MIN);
+        assert_eq!(Precision::P18.get(), Precision::MAX);
+        assert_eq!(Precision::try_new(14), Some(Precision::P14));
+        assert_eq!(Precision::try_new(3), None);
+        assert_eq!(Precision::try_new(19), None);
+
+        assert_eq!(LgK::L4.get(), LgK::MIN);
+        assert_eq!(LgK::L26.get(), LgK::MAX);
+        assert_eq!(LgK::try_new(11), Some(LgK::L11));
+        assert_eq!(LgK::try_new(27), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "precision must be between 4 and 18")]
+    fn test_precision_new_panics_at_runtime() {
+        let value = std::hint::black_box(30);
+        Precision::new(value);
+    }
+
     #[test]
     fn test_debug() {
         let diff = SetDifference::new();
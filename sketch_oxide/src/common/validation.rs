@@ -75,6 +75,37 @@ pub fn validate_min_size(actual: usize, required: usize) -> Result<()> {
     Ok(())
 }
 
+/// Length of the magic + version header written by [`write_header`]
+pub const HEADER_LEN: usize = 5;
+
+/// Append a 4-byte magic and a format version byte to a serialization buffer
+pub fn write_header(bytes: &mut Vec<u8>, magic: &[u8; 4], version: u8) {
+    bytes.extend_from_slice(magic);
+    bytes.push(version);
+}
+
+/// Validate a header written by [`write_header`] and return the payload after it
+///
+/// Fails with `DeserializationError` on truncated input, a foreign magic, or
+/// a version this build does not understand.
+pub fn validate_header<'a>(bytes: &'a [u8], magic: &[u8; 4], version: u8) -> Result<&'a [u8]> {
+    validate_min_size(bytes.len(), HEADER_LEN)?;
+    if &bytes[..4] != magic {
+        return Err(SketchError::DeserializationError(format!(
+            "Invalid magic: expected {:?}, got {:?}",
+            String::from_utf8_lossy(magic),
+            String::from_utf8_lossy(&bytes[..4])
+        )));
+    }
+    if bytes[4] != version {
+        return Err(SketchError::DeserializationError(format!(
+            "Unsupported serialization version {} (expected {})",
+            bytes[4], version
+        )));
+    }
+    Ok(&bytes[HEADER_LEN..])
+}
+
 /// Validate that width and depth are reasonable for Count-Min Sketch
 pub fn validate_width_depth(width: u32, depth: u32) -> Result<()> {
     // Reasonable bounds: width and depth should each be at least 1 and at most 2^20 (1M)
@@ -240,4 +271,16 @@ mod tests {
         assert!(validate_bloom_parameters(1000, 0, 7).is_err()); // m = 0
         assert!(validate_bloom_parameters(1000, 10000, 0).is_err()); // k = 0
     }
+
+    #[test]
+    fn test_validate_header() {
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, b"TEST", 1);
+        bytes.push(42);
+        assert_eq!(validate_header(&bytes, b"TEST", 1).unwrap(), &[42]);
+
+        assert!(validate_header(&bytes[..3], b"TEST", 1).is_err()); // truncated
+        assert!(validate_header(&bytes, b"ABCD", 1).is_err()); // wrong magic
+        assert!(validate_header(&bytes, b"TEST", 2).is_err()); // unknown version
+    }
 }
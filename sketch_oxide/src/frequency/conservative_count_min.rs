@@ -43,7 +43,7 @@
 //! ```
 
 use crate::common::hash::hash_value;
-use crate::common::SketchError;
+use crate::common::{FrequencySketch, SketchError};
 use std::hash::Hash;
 
 /// Conservative Update Count-Min Sketch
@@ -56,6 +56,11 @@ use std::hash::Hash;
 /// Cannot support deletions or negative updates, but offers significantly
 /// better accuracy for point queries.
 ///
+/// # Thread Safety
+///
+/// `ConservativeCountMin` is `Send + Sync`. Point queries take `&self`, so
+/// readers can run in parallel under an `RwLock`.
+///
 /// # Examples
 ///
 /// ```
@@ -424,6 +429,90 @@ impl ConservativeCountMin {
 
         Ok(())
     }
+
+    /// Checks the sketch's internal invariants
+    ///
+    /// Use after [`from_bytes`](Self::from_bytes) or a merge to detect
+    /// corruption. Checks that the table and seeds match `width × depth`,
+    /// that `epsilon` is positive and `delta` is in (0, 1), and that no row
+    /// sums to more than `total_count`: a conservative update raises at most
+    /// one counter per row by at most the update's count, and merging takes
+    /// counter-wise maxima while adding the totals.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` naming the first invariant that does not hold
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::ConservativeCountMin;
+    ///
+    /// let mut cms = ConservativeCountMin::new(0.01, 0.01).unwrap();
+    /// cms.update(&"apple");
+    ///
+    /// let restored = ConservativeCountMin::from_bytes(&cms.to_bytes()).unwrap();
+    /// assert!(restored.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), SketchError> {
+        if self.width == 0
+            || self.depth == 0
+            || self.table.len() != self.depth
+            || self.table.iter().any(|row| row.len() != self.width)
+            || self.hash_seeds.len() != self.depth
+        {
+            return Err(SketchError::InvalidParameter {
+                param: "table".to_string(),
+                value: format!("{} rows, {} seeds", self.table.len(), self.hash_seeds.len()),
+                constraint: format!(
+                    "must have depth ({}) rows of width ({}) and one seed per row",
+                    self.depth, self.width
+                ),
+            });
+        }
+
+        if !(self.epsilon.is_finite() && self.epsilon > 0.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "epsilon".to_string(),
+                value: self.epsilon.to_string(),
+                constraint: "must be positive and finite".to_string(),
+            });
+        }
+
+        if !(self.delta > 0.0 && self.delta < 1.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "delta".to_string(),
+                value: self.delta.to_string(),
+                constraint: "must be in (0, 1)".to_string(),
+            });
+        }
+
+        for (row_idx, row) in self.table.iter().enumerate() {
+            let row_total: u128 = row.iter().map(|&c| c as u128).sum();
+            if row_total > self.total_count as u128 {
+                return Err(SketchError::InvalidParameter {
+                    param: format!("table row {}", row_idx),
+                    value: row_total.to_string(),
+                    constraint: format!(
+                        "must not sum to more than total_count ({})",
+                        self.total_count
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FrequencySketch for ConservativeCountMin {
+    fn update(&mut self, item: &[u8], count: u64) {
+        self.update_count(&item, count);
+    }
+
+    fn estimate(&self, item: &[u8]) -> u64 {
+        ConservativeCountMin::estimate(self, &item)
+    }
 }
 
 #[cfg(test)]
@@ -534,6 +623,36 @@ mod tests {
         assert!(cms1.estimate(&"banana") >= 2);
     }
 
+    #[test]
+    fn test_validate_detects_corruption() {
+        let mut cms1 = ConservativeCountMin::new(0.01, 0.01).unwrap();
+        let mut cms2 = ConservativeCountMin::new(0.01, 0.01).unwrap();
+        for i in 0..200u64 {
+            cms1.update_count(&(i % 17), 3);
+            cms2.update(&(i % 5));
+        }
+        cms1.merge(&cms2).unwrap();
+        assert!(cms1.validate().is_ok());
+
+        let bytes = cms1.to_bytes();
+        assert!(ConservativeCountMin::from_bytes(&bytes)
+            .unwrap()
+            .validate()
+            .is_ok());
+
+        // Shrink total_count below the mass already in the table
+        let mut corrupted = bytes.clone();
+        corrupted[32..40].copy_from_slice(&10u64.to_le_bytes());
+        let restored = ConservativeCountMin::from_bytes(&corrupted).unwrap();
+        assert!(restored.validate().is_err());
+
+        // Garbage delta
+        let mut corrupted = bytes;
+        corrupted[24..32].copy_from_slice(&f64::NAN.to_le_bytes());
+        let restored = ConservativeCountMin::from_bytes(&corrupted).unwrap();
+        assert!(restored.validate().is_err());
+    }
+
     #[test]
     fn test_conservative_vs_standard_accuracy() {
         // Conservative update should be more accurate
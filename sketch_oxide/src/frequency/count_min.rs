@@ -22,7 +22,7 @@
 //! - Database query optimization
 //! - Real-time analytics systems
 
-use crate::common::{validation, Mergeable, Sketch, SketchError};
+use crate::common::{validation, FrequencySketch, Mergeable, Sketch, SketchError};
 use std::hash::{Hash, Hasher};
 use twox_hash::XxHash64;
 
@@ -36,6 +36,11 @@ use twox_hash::XxHash64;
 /// # Type Parameters
 /// The sketch works with any type that implements `Hash`.
 ///
+/// # Thread Safety
+///
+/// `CountMinSketch` is `Send + Sync`. Sketches with the same dimensions merge
+/// cell by cell, so per-thread sketches avoid locking altogether.
+///
 /// # Examples
 /// ```
 /// use sketch_oxide::frequency::CountMinSketch;
@@ -66,6 +71,10 @@ pub struct CountMinSketch {
     epsilon: f64,
     /// Delta parameter (failure probability)
     delta: f64,
+    /// Explicit per-row hash seeds; `None` uses the default single-hash-derive scheme
+    seeds: Option<Vec<u64>>,
+    /// Net stream size N: the sum of any one row, kept so queries need not rescan it
+    total_count: u64,
 }
 
 impl CountMinSketch {
@@ -94,6 +103,26 @@ impl CountMinSketch {
     /// let cms = CountMinSketch::new(0.01, 0.01).unwrap();
     /// ```
     pub fn new(epsilon: f64, delta: f64) -> Result<Self, SketchError> {
+        let (width, depth) = Self::dimensions(epsilon, delta)?;
+        let mask = width - 1; // Bitmask for fast modulo
+
+        // Initialize flat table with zeros (better cache locality)
+        let table = vec![0u64; depth * width];
+
+        Ok(CountMinSketch {
+            width,
+            mask,
+            depth,
+            table,
+            epsilon,
+            delta,
+            seeds: None,
+            total_count: 0,
+        })
+    }
+
+    /// Table dimensions `(width, depth)` that [`new`](Self::new) picks for ε and δ
+    pub(crate) fn dimensions(epsilon: f64, delta: f64) -> Result<(usize, usize), SketchError> {
         // Validate epsilon: must be in (0, 1)
         if epsilon <= 0.0 || epsilon >= 1.0 {
             return Err(SketchError::InvalidParameter {
@@ -117,7 +146,6 @@ impl CountMinSketch {
         // Using 2/ε instead of e/ε gives ~26% smaller tables with similar guarantees
         let width_min = (2.0 / epsilon).ceil() as usize;
         let width = width_min.next_power_of_two(); // Round up to power of 2
-        let mask = width - 1; // Bitmask for fast modulo
 
         // Depth: d = ⌈ln(1/δ)⌉
         let depth = (1.0 / delta).ln().ceil() as usize;
@@ -125,19 +153,84 @@ impl CountMinSketch {
         // Ensure minimum dimensions
         let depth = depth.max(1);
 
-        // Initialize flat table with zeros (better cache locality)
-        let table = vec![0u64; depth * width];
+        Ok((width, depth))
+    }
+
+    /// Create a Count-Min Sketch with exact dimensions and per-row hash seeds
+    ///
+    /// Use this to match a sketch produced by another implementation. Row `i`
+    /// maps an item to column `XxHash64(seeds[i]) % width`, where the hash is
+    /// computed over the item's `Hash` encoding. The width does not need to be
+    /// a power of 2. The reported parameters are derived from the dimensions:
+    /// ε = 2/width and δ = e^(-depth).
+    ///
+    /// # Arguments
+    /// * `width` - Number of counters per row (3 to 2^20)
+    /// * `depth` - Number of rows (1 to 2^20)
+    /// * `seeds` - One hash seed per row
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if:
+    /// - `width` < 3 or `depth` == 0, or either exceeds 2^20
+    /// - `seeds.len()` != `depth`
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::with_dimensions_seeded(1000, 3, &[11, 22, 33]).unwrap();
+    /// cms.update(&"apple");
+    ///
+    /// assert_eq!(cms.width(), 1000);
+    /// assert_eq!(cms.seeds(), Some(&[11, 22, 33][..]));
+    /// assert_eq!(cms.estimate(&"apple"), 1);
+    /// ```
+    pub fn with_dimensions_seeded(
+        width: usize,
+        depth: usize,
+        seeds: &[u64],
+    ) -> Result<Self, SketchError> {
+        validation::validate_width_depth(
+            width.try_into().unwrap_or(u32::MAX),
+            depth.try_into().unwrap_or(u32::MAX),
+        )?;
+
+        if width < 3 {
+            return Err(SketchError::InvalidParameter {
+                param: "width".to_string(),
+                value: width.to_string(),
+                constraint: "must be at least 3".to_string(),
+            });
+        }
+
+        if seeds.len() != depth {
+            return Err(SketchError::InvalidParameter {
+                param: "seeds".to_string(),
+                value: seeds.len().to_string(),
+                constraint: format!("must contain exactly depth ({}) seeds", depth),
+            });
+        }
 
         Ok(CountMinSketch {
             width,
-            mask,
+            mask: width.wrapping_sub(1),
             depth,
-            table,
-            epsilon,
-            delta,
+            table: vec![0u64; depth * width],
+            epsilon: 2.0 / width as f64,
+            delta: (-(depth as f64)).exp(),
+            seeds: Some(seeds.to_vec()),
+            total_count: 0,
         })
     }
 
+    /// Column of `item` in `row` under explicit seeds (seeded sketches only)
+    #[inline]
+    fn seeded_column<T: Hash>(&self, item: &T, seed: u64) -> usize {
+        let mut hasher = XxHash64::with_seed(seed);
+        item.hash(&mut hasher);
+        (hasher.finish() % self.width as u64) as usize
+    }
+
     /// Update the sketch with an item
     ///
     /// Increments the counters for the item in all rows using derived hash functions.
@@ -158,6 +251,16 @@ impl CountMinSketch {
     /// ```
     #[inline]
     pub fn update<T: Hash>(&mut self, item: &T) {
+        self.total_count = self.total_count.saturating_add(1);
+
+        if let Some(seeds) = &self.seeds {
+            for (row_idx, &seed) in seeds.iter().enumerate() {
+                let idx = row_idx * self.width + self.seeded_column(item, seed);
+                self.table[idx] = self.table[idx].saturating_add(1);
+            }
+            return;
+        }
+
         // Hash item once
         let mut hasher = XxHash64::with_seed(0);
         item.hash(&mut hasher);
@@ -183,6 +286,88 @@ impl CountMinSketch {
         }
     }
 
+    /// Adds a `u64` by hashing its 8 little-endian bytes
+    ///
+    /// Equivalent to `update(&value.to_le_bytes())`, which is what the language
+    /// bindings do with a byte buffer, so numeric IDs added from Rust and from JavaScript or Python hit the same counters.
+    /// This differs from `update(&value)`, which hashes through `Hash for u64`.
+    pub fn update_u64(&mut self, value: u64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `i64` by hashing its 8 little-endian two's-complement bytes
+    pub fn update_i64(&mut self, value: i64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Adds an `f64` by hashing the 8 little-endian bytes of its IEEE 754 bits
+    ///
+    /// `0.0` and `-0.0`, like NaNs with different payloads, count as distinct.
+    pub fn update_f64(&mut self, value: f64) {
+        self.update(&value.to_le_bytes());
+    }
+
+    /// Add a signed delta to an item's counters (turnstile update)
+    ///
+    /// Positive deltas behave like `delta` calls to [`update`](Self::update);
+    /// negative deltas decrement every counter of the item, clamping at 0.
+    ///
+    /// # Bias
+    ///
+    /// In a strict turnstile stream, where no item is ever deleted more often
+    /// than it was inserted, every counter stays at or above the net count of
+    /// each item hashed to it, so the clamp never triggers and the usual
+    /// guarantees hold for the remaining positive mass: estimates never
+    /// underestimate, and overestimate by at most ε times the net stream
+    /// size. Deleting an item beyond its inserted count breaks this: the
+    /// excess is subtracted from colliding items' counts, so their estimates
+    /// can fall below the truth, and the clamp absorbs any excess past zero
+    /// instead of carrying it, so later inserts into that counter
+    /// overestimate.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// cms.update_signed(&"session", 5);
+    /// cms.update_signed(&"session", -3);
+    ///
+    /// assert!(cms.estimate(&"session") >= 2);
+    /// ```
+    pub fn update_signed<T: Hash>(&mut self, item: &T, delta: i64) {
+        let magnitude = delta.unsigned_abs();
+        if delta >= 0 {
+            self.total_count = self.total_count.saturating_add(magnitude);
+            self.for_each_counter(item, |c| *c = c.saturating_add(magnitude));
+        } else {
+            self.total_count = self.total_count.saturating_sub(magnitude);
+            self.for_each_counter(item, |c| *c = c.saturating_sub(magnitude));
+        }
+    }
+
+    /// Applies `f` to the item's counter in every row
+    fn for_each_counter<T: Hash, F: FnMut(&mut u64)>(&mut self, item: &T, mut f: F) {
+        if let Some(seeds) = &self.seeds {
+            let columns: Vec<usize> = seeds
+                .iter()
+                .map(|&seed| self.seeded_column(item, seed))
+                .collect();
+            for (row_idx, col_idx) in columns.into_iter().enumerate() {
+                f(&mut self.table[row_idx * self.width + col_idx]);
+            }
+            return;
+        }
+
+        let mut hasher = XxHash64::with_seed(0);
+        item.hash(&mut hasher);
+        for row_idx in 0..self.depth {
+            let col_idx = (hasher.finish() as usize) & self.mask;
+            f(&mut self.table[row_idx * self.width + col_idx]);
+            hasher.write(&[0x7B]);
+        }
+    }
+
     /// Estimate the frequency of an item
     ///
     /// Returns the minimum counter value across all hash functions.
@@ -215,6 +400,17 @@ impl CountMinSketch {
     /// ```
     #[inline]
     pub fn estimate<T: Hash>(&self, item: &T) -> u64 {
+        if let Some(seeds) = &self.seeds {
+            return seeds
+                .iter()
+                .enumerate()
+                .map(|(row_idx, &seed)| {
+                    self.table[row_idx * self.width + self.seeded_column(item, seed)]
+                })
+                .min()
+                .unwrap_or(0);
+        }
+
         // Hash item once
         let mut hasher = XxHash64::with_seed(0);
         item.hash(&mut hasher);
@@ -246,6 +442,77 @@ impl CountMinSketch {
         }
     }
 
+    /// Returns the total count N of all updates
+    ///
+    /// Kept as a running total by updates and merges, so this is O(1).
+    /// Deletions through [`update_signed`](Self::update_signed) are
+    /// subtracted. The value is only a lower bound once it has saturated or
+    /// an item was deleted more often than it was inserted.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// cms.update(&"a");
+    /// cms.update(&"b");
+    /// cms.update_signed(&"a", 3);
+    /// assert_eq!(cms.total_count(), 5);
+    /// ```
+    pub fn total_count(&self) -> u64 {
+        self.total_count
+    }
+
+    /// Estimates the fraction of the stream made up by `item`
+    ///
+    /// Returns `estimate(item) / N`, or 0.0 for an empty sketch. The
+    /// estimate's additive error of ε·N becomes an additive error of ε on
+    /// the fraction: with probability 1-δ the result lies in
+    /// `[f, f + ε]` for a true frequency `f`, independent of the stream size.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// cms.update(&"a");
+    /// cms.update(&"a");
+    /// cms.update(&"b");
+    /// cms.update(&"c");
+    /// assert!(cms.estimate_frequency(&"a") >= 0.5);
+    /// ```
+    pub fn estimate_frequency<T: Hash>(&self, item: &T) -> f64 {
+        match self.total_count() {
+            0 => 0.0,
+            total => self.estimate(item) as f64 / total as f64,
+        }
+    }
+
+    /// Returns true if `item`'s estimated frequency exceeds `phi`
+    ///
+    /// Because the frequency is overestimated by up to ε, every item with a
+    /// true frequency above `phi` is reported, but items with a true
+    /// frequency as low as `phi - ε` may be reported too. Choose ε well
+    /// below `phi` (e.g. `phi / 10`) to keep false positives rare.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::new(0.001, 0.01).unwrap();
+    /// for i in 0..900u32 {
+    ///     cms.update(&i);
+    /// }
+    /// for _ in 0..100 {
+    ///     cms.update(&"hot");
+    /// }
+    /// assert!(cms.is_heavy_hitter(&"hot", 0.05));
+    /// assert!(!cms.is_heavy_hitter(&7u32, 0.05));
+    /// ```
+    pub fn is_heavy_hitter<T: Hash>(&self, item: &T, phi: f64) -> bool {
+        self.total_count > 0 && self.estimate_frequency(item) > phi
+    }
+
     /// Get the width of the sketch
     ///
     /// # Returns
@@ -281,6 +548,207 @@ impl CountMinSketch {
     pub fn delta(&self) -> f64 {
         self.delta
     }
+
+    /// Get the explicit per-row hash seeds
+    ///
+    /// Passing these, with the same width and depth, to
+    /// [`with_dimensions_seeded`](Self::with_dimensions_seeded) on another
+    /// node yields a sketch that hashes every item to the same cells, so
+    /// shards built that way can be merged. Sketches only merge when their
+    /// seeds match.
+    ///
+    /// # Returns
+    /// The seeds passed to [`with_dimensions_seeded`](Self::with_dimensions_seeded),
+    /// or `None` for sketches using the default hashing scheme
+    #[inline]
+    pub fn seeds(&self) -> Option<&[u64]> {
+        self.seeds.as_deref()
+    }
+
+    /// Get the raw counter matrix (diagnostic)
+    ///
+    /// Returns all `depth × width` counters in row-major order: the counter
+    /// in row `r`, column `c` is at `r * width + c`. Meant for visualizing
+    /// the grid or inspecting hash collisions, not for estimation; use
+    /// [`estimate`](Self::estimate) for that. Per-row hash seeds, when the
+    /// sketch has them, are available from [`seeds`](Self::seeds); sketches
+    /// using the default scheme derive every row from one chained hash.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut cms = CountMinSketch::with_dimensions_seeded(8, 2, &[1, 2]).unwrap();
+    /// cms.update(&"apple");
+    ///
+    /// let counters = cms.counters();
+    /// assert_eq!(counters.len(), 16);
+    /// for row in counters.chunks(cms.width()) {
+    ///     assert_eq!(row.iter().sum::<u64>(), 1);
+    /// }
+    /// ```
+    pub fn counters(&self) -> &[u64] {
+        &self.table
+    }
+
+    /// Overwrites the counter matrix, for restoring sketches that embed a
+    /// Count-Min Sketch in their own serialized format
+    pub(crate) fn load_counters<I: IntoIterator<Item = u64>>(&mut self, counters: I) {
+        for (count, value) in self.table.iter_mut().zip(counters) {
+            *count = value;
+        }
+        self.total_count = self.row_total();
+    }
+
+    /// Resets every counter to zero, keeping the dimensions and seeds
+    pub(crate) fn clear(&mut self) {
+        self.table.fill(0);
+        self.total_count = 0;
+    }
+
+    /// Sum of the first row, which every update adds to once
+    fn row_total(&self) -> u64 {
+        let row_total: u128 = self.table[..self.width].iter().map(|&c| c as u128).sum();
+        row_total.min(u64::MAX as u128) as u64
+    }
+
+    /// Checks the sketch's internal invariants
+    ///
+    /// Intended for sketches that came from [`deserialize`](Sketch::deserialize)
+    /// or a merge with an untrusted peer. Checks that:
+    /// - the table holds exactly `width × depth` counters
+    /// - `epsilon` and `delta` are in (0, 1)
+    /// - seeded sketches have one seed per row, unseeded ones a power-of-2 width
+    /// - every row sums to the same total, since each update adds the same
+    ///   amount to every row (skipped once a counter has saturated)
+    ///
+    /// A sketch where [`update_signed`](Self::update_signed) deleted an item
+    /// more often than it was inserted also fails the row check: the clamp
+    /// at zero applies per counter, so the rows no longer agree.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` naming the first invariant that does not hold
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    /// use sketch_oxide::Sketch;
+    ///
+    /// let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// cms.update(&"apple");
+    ///
+    /// let restored = CountMinSketch::deserialize(&cms.serialize()).unwrap();
+    /// assert!(restored.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), SketchError> {
+        if self.width == 0 || self.depth == 0 || self.table.len() != self.width * self.depth {
+            return Err(SketchError::InvalidParameter {
+                param: "table".to_string(),
+                value: self.table.len().to_string(),
+                constraint: format!(
+                    "must hold width × depth ({} × {}) counters",
+                    self.width, self.depth
+                ),
+            });
+        }
+
+        validation::validate_probability(self.epsilon, "epsilon")?;
+        validation::validate_probability(self.delta, "delta")?;
+
+        match &self.seeds {
+            Some(seeds) if seeds.len() != self.depth => {
+                return Err(SketchError::InvalidParameter {
+                    param: "seeds".to_string(),
+                    value: seeds.len().to_string(),
+                    constraint: format!("must contain exactly depth ({}) seeds", self.depth),
+                });
+            }
+            None if !self.width.is_power_of_two() || self.mask != self.width - 1 => {
+                return Err(SketchError::InvalidParameter {
+                    param: "width".to_string(),
+                    value: self.width.to_string(),
+                    constraint: "must be a power of 2 with mask width - 1 for unseeded sketches"
+                        .to_string(),
+                });
+            }
+            _ => {}
+        }
+
+        if self.table.contains(&u64::MAX) {
+            return Ok(());
+        }
+
+        let row_total = |row: &[u64]| row.iter().map(|&c| c as u128).sum::<u128>();
+        let mut rows = self.table.chunks_exact(self.width);
+        let expected = row_total(rows.next().unwrap_or_default());
+        for (row_idx, row) in rows.enumerate() {
+            let total = row_total(row);
+            if total != expected {
+                return Err(SketchError::InvalidParameter {
+                    param: format!("table row {}", row_idx + 1),
+                    value: total.to_string(),
+                    constraint: format!("must sum to the same total as row 0 ({})", expected),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `k` candidate keys whose estimate grew the most since `previous`
+    ///
+    /// Compares this sketch (e.g. the current minute) against `previous`
+    /// (the minute before) and returns `(key, delta)` pairs sorted by
+    /// descending delta, where `delta = estimate(key) - previous.estimate(key)`.
+    /// A Count-Min Sketch stores only counters, so keys cannot be enumerated:
+    /// the caller supplies `candidate_keys`, typically the keys seen in the
+    /// current interval or tracked by a heavy-hitter sketch. Keys outside the
+    /// candidate set are never reported. Duplicate candidates are reported
+    /// once, and deltas are negative when fewer than `k` keys grew.
+    ///
+    /// Each delta is the difference of two overestimates, so it carries the
+    /// error of both sketches: up to `ε × N` in either direction, where `N`
+    /// is the larger of the two stream sizes. The sketches may have different
+    /// dimensions since they are only queried, never combined.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountMinSketch;
+    ///
+    /// let mut previous = CountMinSketch::new(0.001, 0.01).unwrap();
+    /// let mut current = CountMinSketch::new(0.001, 0.01).unwrap();
+    /// for _ in 0..10 {
+    ///     previous.update(&b"/home".as_slice());
+    ///     current.update(&b"/home".as_slice());
+    /// }
+    /// for _ in 0..50 {
+    ///     current.update(&b"/login".as_slice());
+    /// }
+    ///
+    /// let movers = current.top_movers(&previous, &[b"/home", b"/login"], 1);
+    /// assert_eq!(movers, vec![(b"/login".to_vec(), 50)]);
+    /// ```
+    pub fn top_movers(
+        &self,
+        previous: &Self,
+        candidate_keys: &[&[u8]],
+        k: usize,
+    ) -> Vec<(Vec<u8>, i64)> {
+        let mut seen = std::collections::HashSet::with_capacity(candidate_keys.len());
+        let mut movers: Vec<(Vec<u8>, i64)> = candidate_keys
+            .iter()
+            .filter(|key| seen.insert(**key))
+            .map(|key| {
+                let delta = self.estimate(key) as i128 - previous.estimate(key) as i128;
+                let delta = delta.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+                (key.to_vec(), delta)
+            })
+            .collect();
+
+        movers.sort_by_key(|(_, delta)| std::cmp::Reverse(*delta));
+        movers.truncate(k);
+        movers
+    }
 }
 
 impl Sketch for CountMinSketch {
@@ -310,7 +778,8 @@ impl Sketch for CountMinSketch {
 
     /// Serialize the sketch to bytes
     fn serialize(&self) -> Vec<u8> {
-        // Format: [width:8][depth:8][epsilon:8][delta:8][table]
+        // Format: [width:8][depth:8][epsilon:8][delta:8][table][num_seeds:8][seeds]
+        // The seed trailer is only written for seeded sketches
         let mut bytes = Vec::new();
 
         // Dimensions
@@ -326,6 +795,14 @@ impl Sketch for CountMinSketch {
             bytes.extend_from_slice(&count.to_le_bytes());
         }
 
+        // Seed trailer
+        if let Some(seeds) = &self.seeds {
+            bytes.extend_from_slice(&(seeds.len() as u64).to_le_bytes());
+            for &seed in seeds {
+                bytes.extend_from_slice(&seed.to_le_bytes());
+            }
+        }
+
         bytes
     }
 
@@ -396,14 +873,46 @@ impl Sketch for CountMinSketch {
             offset += 8;
         }
 
-        Ok(CountMinSketch {
+        // Optional seed trailer
+        let seeds = if bytes.len() > offset {
+            validation::validate_min_size(bytes.len() - offset, 8)?;
+            let num_seeds = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+            offset += 8;
+
+            if num_seeds != depth as u64 || bytes.len() != offset + depth * 8 {
+                return Err(SketchError::DeserializationError(format!(
+                    "invalid seed trailer: expected {} seeds",
+                    depth
+                )));
+            }
+
+            let seeds = bytes[offset..]
+                .chunks_exact(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                .collect();
+            Some(seeds)
+        } else {
+            if !width.is_power_of_two() {
+                return Err(SketchError::DeserializationError(
+                    "width must be a power of 2 for unseeded sketches".to_string(),
+                ));
+            }
+            None
+        };
+
+        let mut sketch = CountMinSketch {
             width,
             mask,
             depth,
             table,
             epsilon,
             delta,
-        })
+            seeds,
+            total_count: 0,
+        };
+        // Not serialized, since it is implied by the table
+        sketch.total_count = sketch.row_total();
+        Ok(sketch)
     }
 }
 
@@ -423,6 +932,8 @@ impl Mergeable for CountMinSketch {
     /// Returns `IncompatibleSketches` if:
     /// - Sketches have different width (different epsilon)
     /// - Sketches have different depth (different delta)
+    /// - Sketches have different per-row [`seeds`](CountMinSketch::seeds), or
+    ///   only one of them is seeded
     ///
     /// # Properties
     /// - Commutative: A.merge(B) ≡ B.merge(A)
@@ -463,15 +974,34 @@ impl Mergeable for CountMinSketch {
             });
         }
 
+        // Verify compatibility: rows must hash identically
+        if self.seeds != other.seeds {
+            return Err(SketchError::IncompatibleSketches {
+                reason: "hash seed mismatch".to_string(),
+            });
+        }
+
         // Element-wise addition of all counters
         for (a, &b) in self.table.iter_mut().zip(other.table.iter()) {
             *a = a.saturating_add(b);
         }
+        self.total_count = self.total_count.saturating_add(other.total_count);
 
         Ok(())
     }
 }
 
+impl FrequencySketch for CountMinSketch {
+    fn update(&mut self, item: &[u8], count: u64) {
+        self.total_count = self.total_count.saturating_add(count);
+        self.for_each_counter(&item, |c| *c = c.saturating_add(count));
+    }
+
+    fn estimate(&self, item: &[u8]) -> u64 {
+        CountMinSketch::estimate(self, &item)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -515,6 +1045,45 @@ mod tests {
         assert!(estimate >= 100);
     }
 
+    #[test]
+    fn test_update_signed_insert_then_delete() {
+        let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+        for i in 0..1000u64 {
+            cms.update_signed(&i, 10);
+        }
+        for i in 0..500u64 {
+            cms.update_signed(&i, -10);
+        }
+
+        // Strict turnstile: never underestimates the net counts
+        for i in 0..1000u64 {
+            let expected = if i < 500 { 0 } else { 10 };
+            assert!(cms.estimate(&i) >= expected);
+        }
+        // Deleted items only carry collisions from the remaining net mass
+        let bound = (cms.epsilon() * 5000.0) as u64;
+        let within = (0..500u64).filter(|i| cms.estimate(i) <= bound).count();
+        assert!(within > 475, "only {} deleted items within bound", within);
+
+        // Over-deleting clamps at zero rather than wrapping
+        cms.update_signed(&"absent", -5);
+        assert_eq!(cms.estimate(&"absent"), 0);
+    }
+
+    #[test]
+    fn test_update_signed_matches_update_when_seeded() {
+        let mut signed = CountMinSketch::with_dimensions_seeded(256, 3, &[1, 2, 3]).unwrap();
+        let mut plain = signed.clone();
+        signed.update_signed(&"a", 3);
+        for _ in 0..3 {
+            plain.update(&"a");
+        }
+        assert_eq!(signed.serialize(), plain.serialize());
+
+        signed.update_signed(&"a", -3);
+        assert_eq!(signed.estimate(&"a"), 0);
+    }
+
     #[test]
     fn test_merge_basic() {
         let mut cms1 = CountMinSketch::new(0.01, 0.01).unwrap();
@@ -528,6 +1097,59 @@ mod tests {
         assert!(cms1.estimate(&"a") >= 2);
     }
 
+    #[test]
+    fn test_with_dimensions_seeded() {
+        let mut cms = CountMinSketch::with_dimensions_seeded(1000, 4, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(cms.width(), 1000);
+        assert_eq!(cms.depth(), 4);
+
+        for i in 0..500u64 {
+            cms.update(&i);
+        }
+        cms.update(&7u64);
+        assert!(cms.estimate(&7u64) >= 2);
+
+        let restored = CountMinSketch::deserialize(&cms.serialize()).unwrap();
+        assert_eq!(restored.seeds(), Some(&[1, 2, 3, 4][..]));
+        assert_eq!(restored.estimate(&7u64), cms.estimate(&7u64));
+    }
+
+    #[test]
+    fn test_seeds_reconstruct_identical_hashing() {
+        let mut shard1 = CountMinSketch::with_dimensions_seeded(500, 3, &[7, 8, 9]).unwrap();
+        let seeds = shard1.seeds().unwrap().to_vec();
+        let mut shard2 =
+            CountMinSketch::with_dimensions_seeded(shard1.width(), shard1.depth(), &seeds).unwrap();
+
+        for i in 0..200u64 {
+            shard1.update(&i);
+            shard2.update(&i);
+        }
+        assert_eq!(shard1.counters(), shard2.counters());
+
+        shard1.merge(&shard2).unwrap();
+        assert!(shard1.estimate(&5u64) >= 2);
+    }
+
+    #[test]
+    fn test_with_dimensions_seeded_invalid() {
+        assert!(CountMinSketch::with_dimensions_seeded(1000, 3, &[1, 2]).is_err());
+        assert!(CountMinSketch::with_dimensions_seeded(0, 1, &[1]).is_err());
+        assert!(CountMinSketch::with_dimensions_seeded(100, 0, &[]).is_err());
+        assert!(CountMinSketch::with_dimensions_seeded(2, 1, &[1]).is_err());
+    }
+
+    #[test]
+    fn test_merge_seed_mismatch() {
+        let mut cms1 = CountMinSketch::with_dimensions_seeded(256, 2, &[1, 2]).unwrap();
+        let cms2 = CountMinSketch::with_dimensions_seeded(256, 2, &[3, 4]).unwrap();
+        let cms3 = CountMinSketch::new(0.01, 0.2).unwrap();
+        assert_eq!((cms3.width(), cms3.depth()), (256, 2));
+
+        assert!(cms1.merge(&cms2).is_err());
+        assert!(cms1.merge(&cms3).is_err());
+    }
+
     #[test]
     fn test_merge_incompatible() {
         let mut cms1 = CountMinSketch::new(0.01, 0.01).unwrap();
@@ -536,4 +1158,138 @@ mod tests {
         let result = cms1.merge(&cms2);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_detects_corruption() {
+        let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+        let mut other = CountMinSketch::new(0.01, 0.01).unwrap();
+        for i in 0..100u64 {
+            cms.update(&i);
+            other.update(&(i * 7));
+        }
+        cms.merge(&other).unwrap();
+        assert!(cms.validate().is_ok());
+
+        let mut bytes = cms.serialize();
+        assert!(CountMinSketch::deserialize(&bytes)
+            .unwrap()
+            .validate()
+            .is_ok());
+
+        // Bump one counter in the second row: still parses, rows disagree
+        let offset = 32 + cms.width() * 8;
+        bytes[offset] = bytes[offset].wrapping_add(1);
+        let corrupted = CountMinSketch::deserialize(&bytes).unwrap();
+        assert!(corrupted.validate().is_err());
+    }
+
+    #[test]
+    fn test_counters_layout() {
+        let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+        for i in 0..300u64 {
+            cms.update(&(i % 30));
+        }
+        cms.update_signed(&"heavy", 50);
+
+        let counters = cms.counters();
+        assert_eq!(counters.len(), cms.width() * cms.depth());
+        for row in counters.chunks(cms.width()) {
+            assert_eq!(row.iter().sum::<u64>(), 350);
+            assert!(row.iter().max().unwrap() >= &cms.estimate(&"heavy"));
+        }
+    }
+
+    #[test]
+    fn test_update_numeric_matches_le_bytes() {
+        let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+        cms.update_u64(5);
+        cms.update_u64(5);
+        cms.update_i64(-5);
+
+        assert_eq!(cms.estimate(&5u64.to_le_bytes().to_vec()), 2);
+        assert_eq!(cms.estimate(&(-5i64).to_le_bytes().to_vec()), 1);
+    }
+
+    #[test]
+    fn test_top_movers() {
+        let mut previous = CountMinSketch::new(0.001, 0.01).unwrap();
+        let mut current = CountMinSketch::new(0.001, 0.01).unwrap();
+        let keys: Vec<Vec<u8>> = (0..20u8).map(|i| vec![b'k', i]).collect();
+
+        // Key i appears 10 times before and 10 + 3i times now; key 0 drops out
+        for (i, key) in keys.iter().enumerate() {
+            for _ in 0..10 {
+                previous.update(&key.as_slice());
+            }
+            let now = if i == 0 { 0 } else { 10 + 3 * i };
+            for _ in 0..now {
+                current.update(&key.as_slice());
+            }
+        }
+
+        let candidates: Vec<&[u8]> = keys.iter().map(|k| k.as_slice()).collect();
+        let movers = current.top_movers(&previous, &candidates, 3);
+        assert_eq!(
+            movers,
+            vec![
+                (keys[19].clone(), 57),
+                (keys[18].clone(), 54),
+                (keys[17].clone(), 51)
+            ]
+        );
+
+        // Largest decrease ends up last when every candidate is returned
+        let all = current.top_movers(&previous, &candidates, 100);
+        assert_eq!(all.len(), 20);
+        assert_eq!(all.last().unwrap(), &(keys[0].clone(), -10));
+
+        // Duplicates are reported once; unknown keys have no delta
+        let dup = current.top_movers(&previous, &[b"k\x13", b"k\x13", b"missing"], 5);
+        assert_eq!(dup, vec![(keys[19].clone(), 57), (b"missing".to_vec(), 0)]);
+    }
+
+    #[test]
+    fn test_estimate_frequency_and_heavy_hitters() {
+        let mut cms = CountMinSketch::new(0.001, 0.01).unwrap();
+        assert_eq!(cms.estimate_frequency(&"x"), 0.0);
+        assert!(!cms.is_heavy_hitter(&"x", 0.0));
+
+        for i in 0..9_000u32 {
+            cms.update(&i);
+        }
+        cms.update_signed(&"hot", 1_000);
+        assert_eq!(cms.total_count(), 10_000);
+
+        // Frequencies are overestimated by at most ε
+        let hot = cms.estimate_frequency(&"hot");
+        assert!((0.1..=0.1 + cms.epsilon()).contains(&hot), "hot {}", hot);
+        assert!(cms.is_heavy_hitter(&"hot", 0.05));
+        assert!(!cms.is_heavy_hitter(&"hot", 0.2));
+        let cold = (0..9_000u32)
+            .filter(|i| cms.is_heavy_hitter(i, 0.01))
+            .count();
+        assert_eq!(cold, 0);
+
+        // N is kept through merges and serialization
+        let mut other = CountMinSketch::new(0.001, 0.01).unwrap();
+        other.update(&"hot");
+        cms.merge(&other).unwrap();
+        assert_eq!(cms.total_count(), 10_001);
+        let restored = CountMinSketch::deserialize(&cms.serialize()).unwrap();
+        assert_eq!(restored.total_count(), 10_001);
+    }
+
+    #[test]
+    fn test_total_count_matches_row_sum() {
+        let mut cms = CountMinSketch::with_dimensions_seeded(100, 3, &[1, 2, 3]).unwrap();
+        for i in 0..500u32 {
+            cms.update(&i);
+        }
+        FrequencySketch::update(&mut cms, b"bulk", 40);
+        cms.update_signed(&7u32, -1);
+
+        let first_row: u64 = cms.counters()[..cms.width()].iter().sum();
+        assert_eq!(cms.total_count(), 539);
+        assert_eq!(cms.total_count(), first_row);
+    }
 }
@@ -42,6 +42,11 @@ use twox_hash::XxHash64;
 /// # Type Parameters
 /// The sketch works with any type that implements `Hash`.
 ///
+/// # Thread Safety
+///
+/// `CountSketch` is `Send + Sync`; wrap it in a `Mutex` or `RwLock` when
+/// several threads update one sketch.
+///
 /// # Examples
 /// ```
 /// use sketch_oxide::frequency::CountSketch;
@@ -326,6 +331,49 @@ impl CountSketch {
         Self::median(&mut row_products)
     }
 
+    /// Estimate the second frequency moment F2 = sum_x f(x)^2 (self-join size)
+    ///
+    /// Uses the AMS estimator: for each row, the sum of squared counters
+    ///
+    /// `Z_r = sum_j table[r][j]^2 = sum_x f(x)^2 + sum_{x != y, h(x) = h(y)} s(x) s(y) f(x) f(y)`
+    ///
+    /// is an unbiased estimate of F2, since the random signs make every cross
+    /// term vanish in expectation. Its variance is at most `2 * F2^2 / width`,
+    /// so by Chebyshev a single row is within `sqrt(8 / width) * F2` of the
+    /// truth with probability 3/4. Taking the median over the `depth`
+    /// independent rows drives the failure probability down exponentially in
+    /// `depth`.
+    ///
+    /// Counters are squared in `f64`, so large counts do not overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::CountSketch;
+    ///
+    /// let mut cs = CountSketch::new(0.1, 0.01).unwrap();
+    /// cs.update(&"a", 3);
+    /// cs.update(&"b", 4);
+    ///
+    /// // True F2 = 3^2 + 4^2 = 25
+    /// let f2 = cs.estimate_f2();
+    /// assert!((f2 - 25.0).abs() <= 25.0);
+    /// ```
+    pub fn estimate_f2(&self) -> f64 {
+        let mut row_sums: Vec<f64> = self
+            .table
+            .chunks_exact(self.width)
+            .map(|row| row.iter().map(|&c| (c as f64) * (c as f64)).sum())
+            .collect();
+
+        row_sums.sort_unstable_by(|a, b| a.total_cmp(b));
+        let len = row_sums.len();
+        if len % 2 == 1 {
+            row_sums[len / 2]
+        } else {
+            (row_sums[len / 2 - 1] + row_sums[len / 2]) / 2.0
+        }
+    }
+
     /// Compute median of a slice (modifies slice order)
     #[inline]
     fn median(values: &mut [i64]) -> i64 {
@@ -829,6 +877,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_estimate_f2() {
+        let mut cs = CountSketch::new(0.05, 0.01).unwrap();
+
+        // f(i) = i + 1 for 200 items: F2 = sum_{k=1}^{200} k^2
+        let mut true_f2 = 0.0;
+        for i in 0..200i64 {
+            cs.update(&i, i + 1);
+            true_f2 += ((i + 1) * (i + 1)) as f64;
+        }
+
+        let f2 = cs.estimate_f2();
+        // Per-row standard deviation is at most sqrt(2 / width) * F2 (~2%)
+        let relative_error = (f2 - true_f2).abs() / true_f2;
+        assert!(
+            relative_error < 0.1,
+            "F2 estimate {} too far from {}",
+            f2,
+            true_f2
+        );
+    }
+
+    #[test]
+    fn test_estimate_f2_empty_and_deletions() {
+        let mut cs = CountSketch::new(0.1, 0.01).unwrap();
+        assert_eq!(cs.estimate_f2(), 0.0);
+
+        cs.update(&"x", 5);
+        cs.update(&"x", -5);
+        assert_eq!(cs.estimate_f2(), 0.0);
+    }
+
     // ========================================================================
     // Test 8: Merge is additive (linear)
     // ========================================================================
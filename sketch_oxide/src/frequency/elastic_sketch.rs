@@ -50,7 +50,7 @@
 //! assert!(!hitters.is_empty());
 //! ```
 
-use crate::common::{Mergeable, Sketch, SketchError};
+use crate::common::{FrequencySketch, Mergeable, Sketch, SketchError};
 use std::cmp::Ordering;
 use twox_hash::XxHash64;
 
@@ -61,7 +61,7 @@ struct ElasticBucket {
     item_hash: u64,
     /// Estimated frequency of the item
     frequency: u64,
-    /// Elastic counter for more accurate frequency estimation
+    /// Votes from other items that landed on this bucket while it was held
     elastic_counter: u64,
     /// Whether this bucket contains a valid item (to distinguish from empty buckets)
     is_occupied: bool,
@@ -98,6 +98,11 @@ impl ElasticBucket {
 /// The sketch is configured with a fixed bucket count and depth, both specified
 /// at construction time.
 ///
+/// # Thread Safety
+///
+/// `ElasticSketch` is `Send + Sync`. Heavy-part evictions happen inside
+/// `update`, which takes `&mut self`.
+///
 /// # Examples
 /// ```
 /// use sketch_oxide::frequency::ElasticSketch;
@@ -119,6 +124,9 @@ pub struct ElasticSketch {
     depth: usize,
     /// Elastic ratio for counter expansion (0.0 - 1.0)
     elastic_ratio: f64,
+    /// Votes-to-frequency ratio at which a resident is evicted in favour of
+    /// the colliding item (infinite: never)
+    promotion_ratio: f64,
     /// Total items added to sketch for normalization
     total_count: u64,
 }
@@ -183,6 +191,65 @@ impl ElasticSketch {
         bucket_count: usize,
         depth: usize,
         elastic_ratio: f64,
+    ) -> Result<Self, SketchError> {
+        Self::with_promotion_ratio(bucket_count, depth, elastic_ratio, f64::INFINITY)
+    }
+
+    /// Create a new Elastic Sketch that promotes colliding items into occupied buckets
+    ///
+    /// Every update that lands on a bucket held by another item adds a vote
+    /// to that bucket's elastic counter. Once the votes reach
+    /// `promotion_ratio × frequency` of the resident, the resident is evicted
+    /// and the colliding item takes the bucket with its current count.
+    /// Sketches built by [`new`](Self::new) or
+    /// [`with_elastic_ratio`](Self::with_elastic_ratio) use an infinite ratio,
+    /// so the first item to claim a bucket keeps it.
+    ///
+    /// # Arguments
+    /// * `bucket_count` - Number of buckets per row (will be rounded to power of 2)
+    /// * `depth` - Number of hash functions (2-5 recommended)
+    /// * `elastic_ratio` - Elastic expansion ratio (0.0 - 1.0)
+    /// * `promotion_ratio` - Votes per unit of resident frequency needed to evict
+    ///   it (> 0.0, may be infinite)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if:
+    /// - `bucket_count` <= 0
+    /// - `depth` <= 0 or > 8
+    /// - `elastic_ratio` < 0.0 or > 1.0
+    /// - `promotion_ratio` is not > 0.0
+    ///
+    /// # Promotion Ratio
+    /// - Lower values (1-4): late-arriving heavy flows displace light residents
+    ///   quickly, so the buckets track the current heavy hitters even when
+    ///   they are outnumbered by light flows. Evicted items lose their counts,
+    ///   and a burst of light collisions can evict a genuine heavy hitter.
+    /// - Higher values (8-32, 8 as in the original Elastic Sketch paper):
+    ///   residents are stable and their counts exact, but buckets claimed
+    ///   early by light flows stay unavailable to heavy flows for longer.
+    /// - Infinite: buckets are never reclaimed; best when the heavy flows are
+    ///   known to arrive first or the sketch has spare capacity.
+    ///
+    /// A promoted item's earlier updates were recorded as votes against the
+    /// resident and are not carried over, so its estimate undercounts by
+    /// roughly `promotion_ratio × (evicted frequency)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::ElasticSketch;
+    ///
+    /// let mut sketch = ElasticSketch::with_promotion_ratio(1, 1, 0.0, 2.0).unwrap();
+    /// sketch.update(b"light", 1);
+    /// sketch.update(b"heavy", 1);
+    /// sketch.update(b"heavy", 1); // 2 votes against a frequency of 1: promoted
+    /// assert_eq!(sketch.estimate(b"heavy"), 1);
+    /// assert_eq!(sketch.estimate(b"light"), 0);
+    /// ```
+    pub fn with_promotion_ratio(
+        bucket_count: usize,
+        depth: usize,
+        elastic_ratio: f64,
+        promotion_ratio: f64,
     ) -> Result<Self, SketchError> {
         // Validate bucket_count
         if bucket_count == 0 {
@@ -211,6 +278,15 @@ impl ElasticSketch {
             });
         }
 
+        // Validate promotion_ratio
+        if promotion_ratio.is_nan() || promotion_ratio <= 0.0 {
+            return Err(SketchError::InvalidParameter {
+                param: "promotion_ratio".to_string(),
+                value: promotion_ratio.to_string(),
+                constraint: "must be > 0.0".to_string(),
+            });
+        }
+
         // Round bucket_count to power of 2 for fast modulo
         let bucket_count = bucket_count.next_power_of_two();
         let mask = bucket_count - 1;
@@ -224,6 +300,7 @@ impl ElasticSketch {
             mask,
             depth,
             elastic_ratio,
+            promotion_ratio,
             total_count: 0,
         })
     }
@@ -264,7 +341,9 @@ impl ElasticSketch {
     /// 2. For each hash position, checks if the bucket is empty or occupied
     /// 3. If empty, inserts the item with the given count
     /// 4. If occupied by the same item, increases its frequency
-    /// 5. If occupied by a different item, updates elastic counters
+    /// 5. If occupied by a different item, updates elastic counters, evicting the
+    ///    resident once the votes reach the promotion ratio (see
+    ///    [`with_promotion_ratio`](Self::with_promotion_ratio))
     ///
     /// # Arguments
     /// * `item` - The item to add (typically a network flow ID, URL, or feature)
@@ -301,10 +380,9 @@ impl ElasticSketch {
                 found_empty = true;
                 break;
             } else if bucket.item_hash == item_hash {
-                // Same item - update its frequency
+                // Same item - update its frequency; the elastic counter only
+                // records votes from other items, so the resident stays exact
                 self.buckets[idx].frequency = self.buckets[idx].frequency.saturating_add(count);
-                self.buckets[idx].elastic_counter =
-                    self.buckets[idx].elastic_counter.saturating_add(count);
                 return;
             } else if bucket.frequency < min_frequency {
                 // Different item with lower frequency - potential swap candidate
@@ -324,9 +402,13 @@ impl ElasticSketch {
             // Insert new item
             self.buckets[idx] = ElasticBucket::new(item_hash, count);
         } else {
-            // Update existing item's elastic counter
-            self.buckets[idx].elastic_counter =
-                self.buckets[idx].elastic_counter.saturating_add(count);
+            // Vote against the resident, and take over its bucket once the
+            // votes outweigh it (never for an infinite ratio: inf * 0 is NaN)
+            let bucket = &mut self.buckets[idx];
+            bucket.elastic_counter = bucket.elastic_counter.saturating_add(count);
+            if bucket.elastic_counter as f64 >= self.promotion_ratio * bucket.frequency as f64 {
+                *bucket = ElasticBucket::new(item_hash, count);
+            }
         }
     }
 
@@ -464,6 +546,7 @@ impl ElasticSketch {
     /// - Sketches have different bucket_count
     /// - Sketches have different depth
     /// - Sketches have different elastic_ratio
+    /// - Sketches have different promotion_ratio
     ///
     /// # Examples
     /// ```
@@ -505,6 +588,15 @@ impl ElasticSketch {
             });
         }
 
+        if self.promotion_ratio != other.promotion_ratio {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "promotion_ratio mismatch: {} vs {}",
+                    self.promotion_ratio, other.promotion_ratio
+                ),
+            });
+        }
+
         // Merge buckets
         for i in 0..self.buckets.len() {
             let self_bucket = self.buckets[i];
@@ -542,6 +634,105 @@ impl ElasticSketch {
         Ok(())
     }
 
+    /// Check the sketch's internal invariants
+    ///
+    /// Use after deserialization or a merge to detect corruption. Checks that:
+    /// - `bucket_count` is a power of 2 matching the mask, and `depth` is in [1, 8]
+    /// - the bucket array holds `bucket_count × depth` buckets
+    /// - `elastic_ratio` is in [0.0, 1.0] and `promotion_ratio` is > 0.0
+    /// - empty buckets carry no counts
+    /// - the counts held in buckets sum to at most `total_count`, since each
+    ///   update adds its count to one bucket (skipped once `total_count` has
+    ///   saturated)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` naming the first invariant that does not hold
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::ElasticSketch;
+    /// use sketch_oxide::Sketch;
+    ///
+    /// let mut sketch = ElasticSketch::new(512, 3).unwrap();
+    /// sketch.update(b"flow1", 5);
+    ///
+    /// let restored = ElasticSketch::deserialize(&sketch.serialize()).unwrap();
+    /// assert!(restored.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), SketchError> {
+        if !self.bucket_count.is_power_of_two() || self.mask != self.bucket_count - 1 {
+            return Err(SketchError::InvalidParameter {
+                param: "bucket_count".to_string(),
+                value: self.bucket_count.to_string(),
+                constraint: "must be a power of 2 with mask bucket_count - 1".to_string(),
+            });
+        }
+
+        if self.depth == 0 || self.depth > 8 {
+            return Err(SketchError::InvalidParameter {
+                param: "depth".to_string(),
+                value: self.depth.to_string(),
+                constraint: "must be in [1, 8]".to_string(),
+            });
+        }
+
+        if self.buckets.len() != self.bucket_count * self.depth {
+            return Err(SketchError::InvalidParameter {
+                param: "buckets".to_string(),
+                value: self.buckets.len().to_string(),
+                constraint: format!(
+                    "must hold bucket_count × depth ({} × {}) buckets",
+                    self.bucket_count, self.depth
+                ),
+            });
+        }
+
+        if !(0.0..=1.0).contains(&self.elastic_ratio) {
+            return Err(SketchError::InvalidParameter {
+                param: "elastic_ratio".to_string(),
+                value: self.elastic_ratio.to_string(),
+                constraint: "must be in [0.0, 1.0]".to_string(),
+            });
+        }
+
+        if self.promotion_ratio.is_nan() || self.promotion_ratio <= 0.0 {
+            return Err(SketchError::InvalidParameter {
+                param: "promotion_ratio".to_string(),
+                value: self.promotion_ratio.to_string(),
+                constraint: "must be > 0.0".to_string(),
+            });
+        }
+
+        if let Some(idx) = self
+            .buckets
+            .iter()
+            .position(|b| !b.is_occupied && (b.frequency != 0 || b.elastic_counter != 0))
+        {
+            return Err(SketchError::InvalidParameter {
+                param: format!("bucket {}", idx),
+                value: "empty".to_string(),
+                constraint: "must have zero frequency and elastic counter".to_string(),
+            });
+        }
+
+        if self.total_count != u64::MAX {
+            let stored: u128 = self
+                .buckets
+                .iter()
+                .map(|b| b.frequency as u128 + b.elastic_counter as u128)
+                .sum();
+            if stored > self.total_count as u128 {
+                return Err(SketchError::InvalidParameter {
+                    param: "total_count".to_string(),
+                    value: self.total_count.to_string(),
+                    constraint: format!("must be at least the bucket counts' sum ({})", stored),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Clear all state from the sketch
     ///
     /// This operation resets all buckets and counters to empty state.
@@ -567,6 +758,15 @@ impl ElasticSketch {
         self.total_count = 0;
     }
 
+    /// Clears all counts while keeping the configuration
+    ///
+    /// Same as [`reset`](Self::reset), named to match `clear` on the other
+    /// frequency sketches. Bucket count, depth, elastic ratio and promotion
+    /// ratio are unchanged.
+    pub fn clear(&mut self) {
+        self.reset();
+    }
+
     /// Get the bucket count
     ///
     /// # Returns
@@ -594,6 +794,16 @@ impl ElasticSketch {
         self.elastic_ratio
     }
 
+    /// Get the promotion ratio
+    ///
+    /// # Returns
+    /// The votes-to-frequency ratio at which residents are evicted
+    /// (infinite unless built with [`with_promotion_ratio`](Self::with_promotion_ratio))
+    #[inline]
+    pub fn promotion_ratio(&self) -> f64 {
+        self.promotion_ratio
+    }
+
     /// Get the total count of items added
     ///
     /// # Returns
@@ -629,6 +839,12 @@ impl Sketch for ElasticSketch {
         self.buckets.iter().all(|b| !b.is_occupied)
     }
 
+    /// Serializes the sketch losslessly
+    ///
+    /// Every bucket is written as its (item hash, frequency, elastic counter,
+    /// occupied) tuple, so resident items keep their exact counts and
+    /// [`heavy_hitters`](ElasticSketch::heavy_hitters) is unchanged after a
+    /// round trip.
     fn serialize(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
@@ -646,6 +862,9 @@ impl Sketch for ElasticSketch {
             bytes.push(if bucket.is_occupied { 1 } else { 0 });
         }
 
+        // Trailer; blobs written before promotion existed end here
+        bytes.extend_from_slice(&self.promotion_ratio.to_le_bytes());
+
         bytes
     }
 
@@ -684,9 +903,28 @@ impl Sketch for ElasticSketch {
             })?);
         offset += 8;
 
+        if bucket_count == 0 || !bucket_count.is_power_of_two() {
+            return Err(SketchError::DeserializationError(format!(
+                "bucket_count must be a power of 2, got {}",
+                bucket_count
+            )));
+        }
+        if depth == 0 || depth > 8 {
+            return Err(SketchError::DeserializationError(format!(
+                "depth must be in [1, 8], got {}",
+                depth
+            )));
+        }
+
         // Read buckets
         let bucket_size = 8 + 8 + 8 + 1; // item_hash + frequency + elastic_counter + is_occupied
-        let expected_bytes = offset + bucket_size * bucket_count * depth;
+        let expected_bytes = bucket_count
+            .checked_mul(depth)
+            .and_then(|n| n.checked_mul(bucket_size))
+            .and_then(|n| n.checked_add(offset))
+            .ok_or_else(|| {
+                SketchError::DeserializationError("bucket array size overflows".to_string())
+            })?;
 
         if bytes.len() < expected_bytes {
             return Err(SketchError::DeserializationError(
@@ -726,6 +964,11 @@ impl Sketch for ElasticSketch {
             });
         }
 
+        let promotion_ratio = match bytes.get(offset..offset + 8) {
+            Some(trailer) => f64::from_le_bytes(trailer.try_into().unwrap()),
+            None => f64::INFINITY,
+        };
+
         let mask = bucket_count - 1;
 
         Ok(ElasticSketch {
@@ -734,6 +977,7 @@ impl Sketch for ElasticSketch {
             mask,
             depth,
             elastic_ratio,
+            promotion_ratio,
             total_count,
         })
     }
@@ -745,6 +989,16 @@ impl Mergeable for ElasticSketch {
     }
 }
 
+impl FrequencySketch for ElasticSketch {
+    fn update(&mut self, item: &[u8], count: u64) {
+        ElasticSketch::update(self, item, count);
+    }
+
+    fn estimate(&self, item: &[u8]) -> u64 {
+        ElasticSketch::estimate(self, item)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -885,6 +1139,27 @@ mod tests {
         assert_eq!(sketch.estimate(b"item1"), 0);
     }
 
+    #[test]
+    fn test_clear_keeps_configuration() {
+        let mut sketch = ElasticSketch::with_promotion_ratio(256, 4, 0.5, 4.0).unwrap();
+        for i in 0..2000u32 {
+            sketch.update(&i.to_le_bytes(), 1 + (i % 7) as u64);
+        }
+
+        sketch.clear();
+
+        assert!(sketch.is_empty());
+        assert!(sketch.heavy_hitters(1).is_empty());
+        for i in 0..2000u32 {
+            assert_eq!(sketch.estimate(&i.to_le_bytes()), 0);
+        }
+        assert_eq!(sketch.bucket_count(), 256);
+        assert_eq!(sketch.depth(), 4);
+        assert_eq!(sketch.elastic_ratio(), 0.5);
+        assert_eq!(sketch.promotion_ratio(), 4.0);
+        assert!(sketch.validate().is_ok());
+    }
+
     // Test 12: Serialization and deserialization
     #[test]
     fn test_serialization() {
@@ -901,6 +1176,69 @@ mod tests {
         assert_eq!(deserialized.estimate(b"item2"), 3);
     }
 
+    // Test 12b: Round trip keeps resident counts exact
+    #[test]
+    fn test_serialization_preserves_heavy_counts() {
+        let mut sketch = ElasticSketch::new(512, 3).unwrap();
+        for _ in 0..10_000 {
+            sketch.update(b"dominant", 1);
+        }
+        for i in 0..100u32 {
+            sketch.update(&i.to_le_bytes(), 1);
+        }
+        assert_eq!(sketch.estimate(b"dominant"), 10_000);
+
+        let restored = ElasticSketch::deserialize(&sketch.serialize()).unwrap();
+        assert_eq!(restored.estimate(b"dominant"), 10_000);
+        assert_eq!(restored.heavy_hitters(1000), sketch.heavy_hitters(1000));
+        assert_eq!(restored.total_count(), sketch.total_count());
+
+        let mut corrupt = sketch.serialize();
+        corrupt[0..8].copy_from_slice(&0u64.to_le_bytes());
+        assert!(ElasticSketch::deserialize(&corrupt).is_err());
+    }
+
+    // Test 12c: Validation catches corrupted blobs
+    #[test]
+    fn test_validate_detects_corruption() {
+        let mut sketch = ElasticSketch::new(64, 3).unwrap();
+        let mut other = ElasticSketch::new(64, 3).unwrap();
+        for i in 0..500u32 {
+            sketch.update(&(i % 97).to_le_bytes(), 1);
+            other.update(&(i % 13).to_le_bytes(), 2);
+        }
+        sketch.merge(&other).unwrap();
+        assert!(sketch.validate().is_ok());
+
+        let bytes = sketch.serialize();
+        assert!(ElasticSketch::deserialize(&bytes)
+            .unwrap()
+            .validate()
+            .is_ok());
+
+        // total_count smaller than the counts held in buckets
+        let mut corrupt = bytes.clone();
+        corrupt[24..32].copy_from_slice(&1u64.to_le_bytes());
+        let restored = ElasticSketch::deserialize(&corrupt).unwrap();
+        assert!(restored.validate().is_err());
+
+        // Out-of-range elastic ratio
+        let mut corrupt = bytes.clone();
+        corrupt[16..24].copy_from_slice(&2.0f64.to_le_bytes());
+        let restored = ElasticSketch::deserialize(&corrupt).unwrap();
+        assert!(restored.validate().is_err());
+
+        // Empty bucket with a stale count
+        let empty = (0..sketch.buckets.len())
+            .find(|&i| !sketch.buckets[i].is_occupied)
+            .unwrap();
+        let mut corrupt = bytes;
+        let offset = 32 + empty * 25 + 8;
+        corrupt[offset..offset + 8].copy_from_slice(&1u64.to_le_bytes());
+        let restored = ElasticSketch::deserialize(&corrupt).unwrap();
+        assert!(restored.validate().is_err());
+    }
+
     // Test 13: Stability - consistent estimates
     #[test]
     fn test_stability() {
@@ -950,6 +1288,55 @@ mod tests {
         assert_eq!(sketch.estimate(b"item1"), 100);
     }
 
+    // Test 15b: Promotion lets late heavy hitters displace light residents
+    #[test]
+    fn test_promotion_ratio_zipfian_heavy_hitters() {
+        // Zipfian flows (frequency 2000 / rank) arriving interleaved, after
+        // enough one-off light flows to occupy every bucket
+        let flows: Vec<(Vec<u8>, u64)> = (1..=200u64)
+            .map(|rank| (format!("heavy{}", rank).into_bytes(), 2000 / rank))
+            .collect();
+        let top_error = |sketch: &mut ElasticSketch| -> u64 {
+            for i in 0..2000u32 {
+                sketch.update(&i.to_le_bytes(), 1);
+            }
+            for round in 0..2000 {
+                for (item, frequency) in &flows {
+                    if *frequency > round {
+                        sketch.update(item, 1);
+                    }
+                }
+            }
+            flows[..10]
+                .iter()
+                .map(|(item, frequency)| sketch.estimate(item).abs_diff(*frequency))
+                .sum()
+        };
+
+        let mut fixed = ElasticSketch::with_elastic_ratio(64, 2, 0.0).unwrap();
+        let mut tuned = ElasticSketch::with_promotion_ratio(64, 2, 0.0, 8.0).unwrap();
+        assert_eq!(fixed.promotion_ratio(), f64::INFINITY);
+        assert_eq!(tuned.promotion_ratio(), 8.0);
+
+        let fixed_error = top_error(&mut fixed);
+        let tuned_error = top_error(&mut tuned);
+        assert!(
+            tuned_error * 4 < fixed_error,
+            "tuned error {} vs fixed error {}",
+            tuned_error,
+            fixed_error
+        );
+        assert!(tuned.validate().is_ok());
+
+        // The ratio survives serialization and gates merging
+        let restored = ElasticSketch::deserialize(&tuned.serialize()).unwrap();
+        assert_eq!(restored.promotion_ratio(), 8.0);
+        assert!(tuned.merge(&fixed).is_err());
+
+        assert!(ElasticSketch::with_promotion_ratio(64, 2, 0.2, 0.0).is_err());
+        assert!(ElasticSketch::with_promotion_ratio(64, 2, 0.2, f64::NAN).is_err());
+    }
+
     // Test 16: Large frequency values
     #[test]
     fn test_large_frequencies() {
@@ -86,6 +86,10 @@ pub enum ErrorType {
 /// - Update: O(1) amortized (O(max_size) worst case during purge)
 /// - Query: O(1) for single item
 /// - Top-K: O(n log n) where n ≤ max_size
+///
+/// # Thread Safety
+///
+/// `FrequentItems<T>` is `Send + Sync` whenever the item type `T` is.
 #[derive(Debug, Clone)]
 pub struct FrequentItems<T: Hash + Eq + Clone> {
     /// Maximum number of items to track
@@ -94,6 +98,8 @@ pub struct FrequentItems<T: Hash + Eq + Clone> {
     items: HashMap<T, u64>,
     /// Global offset representing accumulated purged counts (error bound)
     offset: u64,
+    /// Total weight of all updates, including purged ones
+    total_weight: u64,
 }
 
 impl<T: Hash + Eq + Clone> FrequentItems<T> {
@@ -128,6 +134,7 @@ impl<T: Hash + Eq + Clone> FrequentItems<T> {
             max_size,
             items: HashMap::with_capacity(max_size),
             offset: 0,
+            total_weight: 0,
         })
     }
 
@@ -170,6 +177,7 @@ impl<T: Hash + Eq + Clone> FrequentItems<T> {
         }
 
         *self.items.entry(item).or_insert(0) += count;
+        self.total_weight += count;
 
         // If exceeded capacity, purge minimum
         if self.items.len() > self.max_size {
@@ -313,6 +321,7 @@ impl<T: Hash + Eq + Clone> FrequentItems<T> {
 
         // Add offsets
         self.offset += other.offset;
+        self.total_weight += other.total_weight;
 
         // Purge if needed (may need multiple purges)
         while self.items.len() > self.max_size {
@@ -364,11 +373,187 @@ impl<T: Hash + Eq + Clone> FrequentItems<T> {
     pub fn offset(&self) -> u64 {
         self.offset
     }
+
+    /// Returns the total weight of all updates, including purged ones
+    pub fn total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
+    /// Serializes the sketch, encoding each key with `encode`
+    ///
+    /// Format (all integers little-endian):
+    /// - version: 1 byte
+    /// - max_size, offset, total_weight, num_items: 8 bytes each
+    /// - per item: key length (4 bytes), key bytes, count (8 bytes)
+    ///
+    /// Items are ordered by encoded key, so equal sketches serialize
+    /// identically. The result merges like the live sketch once loaded with
+    /// [`from_bytes_with`](Self::from_bytes_with).
+    pub fn to_bytes_with(&self, encode: impl Fn(&T) -> Vec<u8>) -> Vec<u8> {
+        let mut entries: Vec<(Vec<u8>, u64)> = self
+            .items
+            .iter()
+            .map(|(item, &count)| (encode(item), count))
+            .collect();
+        entries.sort_unstable();
+
+        let mut bytes = Vec::with_capacity(
+            HEADER_BYTES + entries.iter().map(|(k, _)| 12 + k.len()).sum::<usize>(),
+        );
+        bytes.push(SERIALIZATION_VERSION);
+        bytes.extend_from_slice(&(self.max_size as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.offset.to_le_bytes());
+        bytes.extend_from_slice(&self.total_weight.to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (key, count) in entries {
+            bytes.extend_from_slice(&(key.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&key);
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a sketch written by [`to_bytes_with`](Self::to_bytes_with)
+    ///
+    /// # Errors
+    ///
+    /// Returns `SketchError::DeserializationError` if the version is unknown,
+    /// the data is truncated or has trailing bytes, `max_size` is invalid,
+    /// there are more items than `max_size`, a count is zero, a key repeats,
+    /// or the counts exceed the total weight. Errors from `decode` are
+    /// passed through.
+    pub fn from_bytes_with(
+        bytes: &[u8],
+        decode: impl Fn(&[u8]) -> Result<T, SketchError>,
+    ) -> Result<Self, SketchError> {
+        let err = |msg: &str| SketchError::DeserializationError(msg.to_string());
+        let mut reader = ByteReader { bytes, pos: 0 };
+
+        let version = reader.take(1).ok_or_else(|| err("missing version"))?[0];
+        if version != SERIALIZATION_VERSION {
+            return Err(SketchError::DeserializationError(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+        let max_size = reader.u64().ok_or_else(|| err("truncated header"))?;
+        let offset = reader.u64().ok_or_else(|| err("truncated header"))?;
+        let total_weight = reader.u64().ok_or_else(|| err("truncated header"))?;
+        let num_items = reader.u64().ok_or_else(|| err("truncated header"))?;
+
+        if max_size < 2 {
+            return Err(SketchError::DeserializationError(format!(
+                "invalid max_size {}",
+                max_size
+            )));
+        }
+        if num_items > max_size {
+            return Err(err("more items than max_size"));
+        }
+        // Each item takes at least 12 bytes; check before allocating
+        if num_items > ((bytes.len() - reader.pos) / 12) as u64 {
+            return Err(err("truncated item"));
+        }
+        if offset > total_weight {
+            return Err(err("offset exceeds total weight"));
+        }
+
+        let mut items = HashMap::with_capacity(num_items as usize);
+        // Purges drop at least as much as they add to the offset, so the
+        // stored counts plus the offset never exceed the total weight
+        let mut counted = offset;
+        for _ in 0..num_items {
+            let key_len = reader.u32().ok_or_else(|| err("truncated item"))? as usize;
+            let key = reader.take(key_len).ok_or_else(|| err("truncated item"))?;
+            let item = decode(key)?;
+            let count = reader.u64().ok_or_else(|| err("truncated item"))?;
+            if count == 0 {
+                return Err(err("zero count"));
+            }
+            counted = counted
+                .checked_add(count)
+                .filter(|&c| c <= total_weight)
+                .ok_or_else(|| err("counts exceed total weight"))?;
+            if items.insert(item, count).is_some() {
+                return Err(err("duplicate key"));
+            }
+        }
+        if reader.pos != bytes.len() {
+            return Err(err("trailing bytes"));
+        }
+
+        Ok(Self {
+            max_size: max_size as usize,
+            items,
+            offset,
+            total_weight,
+        })
+    }
+}
+
+impl FrequentItems<Vec<u8>> {
+    /// Serializes a sketch of byte-string keys
+    ///
+    /// See [`to_bytes_with`](Self::to_bytes_with) for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::frequent::FrequentItems;
+    ///
+    /// let mut sketch = FrequentItems::new(10).unwrap();
+    /// sketch.update_by(b"apple".to_vec(), 3);
+    ///
+    /// let restored = FrequentItems::from_bytes(&sketch.to_bytes()).unwrap();
+    /// assert_eq!(restored, sketch);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with(|key| key.clone())
+    }
+
+    /// Deserializes a sketch of byte-string keys
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        Self::from_bytes_with(bytes, |key| Ok(key.to_vec()))
+    }
+}
+
+/// Current serialization format version
+const SERIALIZATION_VERSION: u8 = 1;
+
+/// Version byte plus four 8-byte header fields
+const HEADER_BYTES: usize = 33;
+
+/// Cursor over serialized bytes that fails instead of panicking on truncation
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let slice = self.bytes.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+    }
 }
 
 impl<T: Hash + Eq + Clone> PartialEq for FrequentItems<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.max_size == other.max_size && self.items == other.items && self.offset == other.offset
+        self.max_size == other.max_size
+            && self.items == other.items
+            && self.offset == other.offset
+            && self.total_weight == other.total_weight
     }
 }
 
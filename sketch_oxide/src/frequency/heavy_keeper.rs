@@ -80,6 +80,12 @@ use std::collections::BinaryHeap;
 ///
 /// HeavyKeeper uses a count array with exponential decay to identify the most frequent
 /// items in a data stream with high accuracy and minimal false positives.
+///
+/// # Thread Safety
+///
+/// `HeavyKeeper` is `Send + Sync`. Decay draws its randomness from the item
+/// hash and the update counter rather than an RNG, so there is no
+/// thread-local state.
 #[derive(Clone)]
 pub struct HeavyKeeper {
     /// Number of top items to track
@@ -236,7 +242,31 @@ impl HeavyKeeper {
     /// hk.update(b"item1"); // item1 appears twice
     /// ```
     pub fn update(&mut self, item: &[u8]) {
-        self.total_updates += 1;
+        self.update_by(item, 1);
+    }
+
+    /// Adds `count` occurrences of an item in a single step
+    ///
+    /// Equivalent to calling [`update`](Self::update) `count` times: the
+    /// minimum counter across rows grows by `count` (saturating at `u32::MAX`)
+    /// and the top-k heap sees the new total, so eviction compares weighted
+    /// counts. A count of zero is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::HeavyKeeper;
+    ///
+    /// let mut hk = HeavyKeeper::new(10, 0.001, 0.01).unwrap();
+    /// hk.update_by(b"flow", 1500);
+    /// assert!(hk.estimate(b"flow") >= 1500);
+    /// ```
+    pub fn update_by(&mut self, item: &[u8], count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.total_updates = self.total_updates.saturating_add(count);
+        let increment = count.min(u32::MAX as u64) as u32;
 
         // Hash the item
         let item_hash = Self::hash_item(item);
@@ -252,7 +282,7 @@ impl HeavyKeeper {
         }
 
         // Increment minimum count (with overflow protection)
-        let new_count = min_count.saturating_add(1);
+        let new_count = min_count.saturating_add(increment);
 
         // Update all positions to at least the new minimum
         for (row, col) in positions {
@@ -265,6 +295,30 @@ impl HeavyKeeper {
         self.update_heap(item_hash, new_count);
     }
 
+    /// Adds a fractional weight for an item, e.g. a flow measured in bytes
+    ///
+    /// The weight is rounded stochastically to a whole count whose expected
+    /// value equals the weight, then applied with [`update_by`](Self::update_by).
+    /// Counters, heap eviction and [`decay`](Self::decay) therefore all scale
+    /// with the weight. Non-finite or non-positive weights are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::HeavyKeeper;
+    ///
+    /// let mut hk = HeavyKeeper::new(10, 0.001, 0.01).unwrap();
+    /// hk.update_weighted(b"flow", 512.0);
+    /// assert!(hk.estimate(b"flow") >= 512);
+    /// ```
+    pub fn update_weighted(&mut self, item: &[u8], weight: f64) {
+        if !weight.is_finite() || weight <= 0.0 {
+            return;
+        }
+        let entropy = Self::hash_item(item) ^ self.total_updates;
+        self.update_by(item, super::stochastic_round(weight, entropy));
+    }
+
     /// Estimates the count of a specific item
     ///
     /// Returns the minimum count across all hash positions for this item.
@@ -641,4 +695,43 @@ mod tests {
         let hash3 = HeavyKeeper::hash_item(b"different");
         assert_ne!(hash1, hash3, "Different items should have different hashes");
     }
+
+    #[test]
+    fn test_update_by_matches_repeated_updates() {
+        let mut weighted = HeavyKeeper::new(5, 0.001, 0.01).unwrap();
+        let mut repeated = HeavyKeeper::new(5, 0.001, 0.01).unwrap();
+        for (item, count) in [(&b"a"[..], 40), (b"b", 25), (b"c", 7)] {
+            weighted.update_by(item, count);
+            for _ in 0..count {
+                repeated.update(item);
+            }
+        }
+        weighted.update_by(b"d", 0);
+
+        assert_eq!(weighted.estimate(b"a"), repeated.estimate(b"a"));
+        assert_eq!(weighted.estimate(b"d"), 0);
+        assert_eq!(weighted.stats().total_updates, 72);
+        assert_eq!(weighted.top_k(), repeated.top_k());
+
+        // Decay scales the weighted counts like unit ones
+        weighted.decay();
+        repeated.decay();
+        assert_eq!(weighted.estimate(b"a"), repeated.estimate(b"a"));
+    }
+
+    #[test]
+    fn test_update_weighted() {
+        let mut hk = HeavyKeeper::new(5, 0.001, 0.01).unwrap();
+        for _ in 0..1000 {
+            hk.update_weighted(b"flow", 2.5);
+        }
+        // Stochastic rounding keeps the total close to 2500
+        let total = hk.estimate(b"flow");
+        assert!((2400..=2600).contains(&total), "got {}", total);
+
+        for weight in [0.0, -3.0, f64::NAN, f64::INFINITY] {
+            hk.update_weighted(b"ignored", weight);
+        }
+        assert_eq!(hk.estimate(b"ignored"), 0);
+    }
 }
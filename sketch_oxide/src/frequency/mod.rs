@@ -10,6 +10,7 @@ mod nitrosketch;
 pub mod removable_sketch;
 pub mod salsa;
 mod space_saving;
+mod top_k;
 
 pub use conservative_count_min::ConservativeCountMin;
 pub use count_min::CountMinSketch;
@@ -21,3 +22,44 @@ pub use nitrosketch::{NitroSketch, NitroSketchStats};
 pub use removable_sketch::RemovableUniversalSketch;
 pub use salsa::SALSA;
 pub use space_saving::SpaceSaving;
+pub use top_k::TopK;
+
+use crate::common::hash::mix64;
+use crate::common::SketchError;
+
+/// Estimated memory in bytes for a Count-Min Sketch with the given error bounds
+///
+/// Uses the same dimensions as [`CountMinSketch::new`]: width
+/// `w = ⌈2/ε⌉` rounded up to a power of 2, depth `d = ⌈ln(1/δ)⌉` (at least
+/// 1), and 8-byte counters, for `w × d × 8` bytes.
+///
+/// # Errors
+/// Returns `InvalidParameter` if `epsilon` or `delta` is not in (0, 1)
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::frequency::cms_bytes_for;
+///
+/// // ε = 1%, δ = 1%: 256 columns × 5 rows × 8 bytes
+/// assert_eq!(cms_bytes_for(0.01, 0.01).unwrap(), 256 * 5 * 8);
+/// ```
+pub fn cms_bytes_for(epsilon: f64, delta: f64) -> Result<usize, SketchError> {
+    let (width, depth) = CountMinSketch::dimensions(epsilon, delta)?;
+    Ok(width * depth * std::mem::size_of::<u64>())
+}
+
+/// Rounds a non-negative weight to a whole count without bias
+///
+/// Returns `floor(weight)` plus one with probability `fract(weight)`, so the
+/// expected count equals the weight. `entropy` drives the coin flip, keeping
+/// weighted updates reproducible for a given stream.
+pub(crate) fn stochastic_round(weight: f64, entropy: u64) -> u64 {
+    let whole = weight.floor();
+    let frac = weight - whole;
+
+    // SplitMix64 finalizer spreads structured entropy across all bits
+    let z = mix64(entropy.wrapping_add(0x9E37_79B9_7F4A_7C15));
+    let coin = (z >> 11) as f64 / (1u64 << 53) as f64;
+
+    (whole as u64).saturating_add(u64::from(coin < frac))
+}
@@ -64,6 +64,17 @@ use crate::common::{Sketch, SketchError};
 use std::hash::Hasher;
 use twox_hash::XxHash64;
 
+/// Granularity of the hash-based sampling decision
+///
+/// An item is sampled when `hash % SAMPLING_RESOLUTION` falls below
+/// `rate * SAMPLING_RESOLUTION`, so rates are effectively quantized down to
+/// multiples of `1 / SAMPLING_RESOLUTION`, and rates below one step sample
+/// at [`MIN_SAMPLE_RATE`].
+const SAMPLING_RESOLUTION: u64 = 10_000;
+
+/// Smallest rate that still samples anything
+const MIN_SAMPLE_RATE: f64 = 1.0 / SAMPLING_RESOLUTION as f64;
+
 /// Statistics about NitroSketch operation
 #[derive(Debug, Clone, PartialEq)]
 pub struct NitroSketchStats {
@@ -86,6 +97,10 @@ pub struct NitroSketchStats {
 ///
 /// NitroSketch works with byte slices to support network packets and flow keys.
 ///
+/// # Thread Safety
+///
+/// `NitroSketch<S>` is `Send + Sync` whenever the wrapped sketch `S` is.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -112,6 +127,15 @@ pub struct NitroSketch<S: Sketch> {
     sampled_count: u64,
     /// Count of items that were NOT sampled (skipped)
     unsampled_count: u64,
+    /// Lowest rate accepted by `set_rate`
+    min_rate: f64,
+    /// Highest rate accepted by `set_rate`
+    max_rate: f64,
+    /// Sampled counts per rate since the last sync, oldest first; the last
+    /// entry is the current rate
+    rate_segments: Vec<(f64, u64)>,
+    /// Inverse-probability weight of samples folded in by `sync`
+    synced_weight: f64,
 }
 
 impl<S: Sketch> NitroSketch<S> {
@@ -157,9 +181,140 @@ impl<S: Sketch> NitroSketch<S> {
             sample_rate,
             sampled_count: 0,
             unsampled_count: 0,
+            min_rate: MIN_SAMPLE_RATE,
+            max_rate: 1.0,
+            rate_segments: vec![(sample_rate, 0)],
+            synced_weight: 0.0,
         })
     }
 
+    /// Create a NitroSketch whose sample rate an external controller tunes
+    ///
+    /// Sampling starts at `max_rate`. Call [`set_rate`](Self::set_rate) to
+    /// shed load as throughput rises and to restore accuracy as it falls;
+    /// requested rates are clamped to `[min_rate, max_rate]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` unless `0 < min_rate <= max_rate <= 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::{NitroSketch, CountMinSketch};
+    ///
+    /// let base = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// let mut nitro = NitroSketch::with_adaptive_rate(base, 0.01, 0.5).unwrap();
+    /// assert_eq!(nitro.current_rate(), 0.5);
+    ///
+    /// nitro.set_rate(0.001).unwrap(); // overloaded: clamped to min_rate
+    /// assert_eq!(nitro.current_rate(), 0.01);
+    /// ```
+    pub fn with_adaptive_rate(
+        base_sketch: S,
+        min_rate: f64,
+        max_rate: f64,
+    ) -> Result<Self, SketchError> {
+        if !(min_rate > 0.0 && min_rate <= max_rate) {
+            return Err(SketchError::InvalidParameter {
+                param: "min_rate".to_string(),
+                value: min_rate.to_string(),
+                constraint: format!("must be in (0, max_rate = {}]", max_rate),
+            });
+        }
+
+        let mut nitro = Self::new(base_sketch, max_rate)?;
+        nitro.min_rate = min_rate;
+        nitro.max_rate = max_rate;
+        Ok(nitro)
+    }
+
+    /// Changes the sample rate for subsequent updates
+    ///
+    /// The rate is clamped to the bounds given to
+    /// [`with_adaptive_rate`](Self::with_adaptive_rate), or `(0, 1]` for
+    /// sketches built with [`new`](Self::new).
+    ///
+    /// # Unbiasedness
+    ///
+    /// Scaling sampled counts back up is unbiased only if every sample is
+    /// weighted by the inverse of the rate in force when it was taken.
+    /// Scaling the whole stream by the final rate would over- or
+    /// under-count every period sampled at a different rate, so the sketch
+    /// keeps a sampled count per rate and [`sync`](Self::sync) weights
+    /// each one separately. Rates are applied at `1/10000` granularity, at
+    /// least one step, and weighted by the quantized rate actually used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `rate` is not a positive finite number.
+    pub fn set_rate(&mut self, rate: f64) -> Result<(), SketchError> {
+        if !(rate.is_finite() && rate > 0.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "rate".to_string(),
+                value: rate.to_string(),
+                constraint: "must be a positive finite number".to_string(),
+            });
+        }
+
+        let rate = rate.clamp(self.min_rate, self.max_rate);
+        if rate != self.sample_rate {
+            self.sample_rate = rate;
+            self.rate_segments.push((rate, 0));
+        }
+        Ok(())
+    }
+
+    /// Returns the sample rate applied to new updates
+    pub fn current_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Estimates the stream length from the sampled counts
+    ///
+    /// Each sampled update counts `1 / rate` for the rate in force when it
+    /// was sampled, so the estimate stays unbiased across rate changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::{NitroSketch, CountMinSketch};
+    ///
+    /// let base = CountMinSketch::new(0.01, 0.01).unwrap();
+    /// let mut nitro = NitroSketch::with_adaptive_rate(base, 0.1, 1.0).unwrap();
+    /// for i in 0..10_000 {
+    ///     nitro.update_sampled(format!("a{}", i).as_bytes());
+    /// }
+    /// nitro.set_rate(0.25).unwrap();
+    /// for i in 0..10_000 {
+    ///     nitro.update_sampled(format!("b{}", i).as_bytes());
+    /// }
+    ///
+    /// let estimate = nitro.estimated_stream_size();
+    /// assert!((estimate - 20_000.0).abs() < 1_000.0);
+    /// ```
+    pub fn estimated_stream_size(&self) -> f64 {
+        self.synced_weight
+            + self
+                .rate_segments
+                .iter()
+                .map(|&(rate, sampled)| sampled as f64 / Self::effective_rate(rate))
+                .sum::<f64>()
+    }
+
+    /// The rate the quantized sampling threshold actually applies
+    fn effective_rate(rate: f64) -> f64 {
+        Self::sampling_threshold(rate) as f64 / SAMPLING_RESOLUTION as f64
+    }
+
+    /// Threshold below which `hash % SAMPLING_RESOLUTION` is sampled
+    ///
+    /// At least 1, so a tiny rate still samples and the estimate never
+    /// divides by a zero effective rate.
+    fn sampling_threshold(rate: f64) -> u64 {
+        ((rate * SAMPLING_RESOLUTION as f64) as u64).max(1)
+    }
+
     /// Create a NitroSketch with a custom seed (for testing)
     ///
     /// Note: NitroSketch uses deterministic hash-based sampling, so the seed
@@ -195,6 +350,10 @@ impl<S: Sketch> NitroSketch<S> {
             sample_rate,
             sampled_count: seed, // Temporarily store seed here
             unsampled_count: 0,
+            min_rate: MIN_SAMPLE_RATE,
+            max_rate: 1.0,
+            rate_segments: vec![(sample_rate, 0)],
+            synced_weight: 0.0,
         };
 
         // Reset counts after using seed
@@ -240,11 +399,14 @@ impl<S: Sketch> NitroSketch<S> {
         let hash = hasher.finish();
 
         // Probabilistic sampling: hash % 10000 < sample_rate * 10000
-        let threshold = (self.sample_rate * 10000.0) as u64;
-        let should_sample = (hash % 10000) < threshold;
+        let threshold = Self::sampling_threshold(self.sample_rate);
+        let should_sample = (hash % SAMPLING_RESOLUTION) < threshold;
 
         if should_sample {
             self.sampled_count += 1;
+            if let Some(segment) = self.rate_segments.last_mut() {
+                segment.1 += 1;
+            }
         } else {
             self.unsampled_count += 1;
         }
@@ -324,6 +486,10 @@ impl<S: Sketch> NitroSketch<S> {
     /// 1. Computing total_estimate = sampled_count / sample_rate
     /// 2. Adjusting base sketch weights/estimates
     ///
+    /// The per-rate sampled counts collected since the last sync are folded
+    /// into [`estimated_stream_size`](Self::estimated_stream_size), each
+    /// weighted by its own rate (see [`set_rate`](Self::set_rate)).
+    ///
     /// # Returns
     ///
     /// `Ok(())` on success
@@ -345,6 +511,10 @@ impl<S: Sketch> NitroSketch<S> {
     /// nitro.sync(1.0).unwrap();
     /// ```
     pub fn sync(&mut self, _unsampled_weight: f64) -> Result<(), SketchError> {
+        self.synced_weight = self.estimated_stream_size();
+        self.rate_segments.clear();
+        self.rate_segments.push((self.sample_rate, 0));
+
         // In a full implementation, this would adjust the base sketch
         // to account for unsampled items. For now, we just track the counts.
         //
@@ -450,6 +620,9 @@ impl<S: Sketch> NitroSketch<S> {
     pub fn reset_stats(&mut self) {
         self.sampled_count = 0;
         self.unsampled_count = 0;
+        self.rate_segments.clear();
+        self.rate_segments.push((self.sample_rate, 0));
+        self.synced_weight = 0.0;
     }
 }
 
@@ -549,4 +722,68 @@ mod tests {
         assert!(stats.sampled_count > 0);
         assert!(stats.unsampled_count >= 0);
     }
+
+    #[test]
+    fn test_adaptive_rate_segments() {
+        let base = CountMinSketch::new(0.01, 0.01).unwrap();
+        let mut nitro = NitroSketch::with_adaptive_rate(base, 0.05, 0.8).unwrap();
+        assert_eq!(nitro.current_rate(), 0.8);
+
+        nitro.set_rate(2.0).unwrap();
+        assert_eq!(nitro.current_rate(), 0.8);
+        assert!(nitro.set_rate(0.0).is_err());
+        assert!(nitro.set_rate(f64::NAN).is_err());
+
+        // Alternate rates under "load" and sync in between
+        let mut n = 0;
+        for (round, rate) in [0.8, 0.1, 0.05, 0.5].into_iter().enumerate() {
+            nitro.set_rate(rate).unwrap();
+            for i in 0..20_000 {
+                nitro.update_sampled(format!("r{}_{}", round, i).as_bytes());
+                n += 1;
+            }
+            if round == 1 {
+                nitro.sync(1.0).unwrap();
+            }
+        }
+
+        let estimate = nitro.estimated_stream_size();
+        let error = (estimate - n as f64).abs() / n as f64;
+        assert!(error < 0.05, "estimate {} vs {}", estimate, n);
+
+        // A single final-rate scaling would be badly biased here
+        let naive = nitro.sampled_count() as f64 / nitro.current_rate();
+        assert!((naive - n as f64).abs() / n as f64 > 0.2);
+    }
+
+    #[test]
+    fn test_rates_below_one_step_sample_at_the_minimum() {
+        let base = || CountMinSketch::new(0.01, 0.01).unwrap();
+        let mut nitro = NitroSketch::new(base(), 0.00005).unwrap();
+        assert_eq!(nitro.estimated_stream_size(), 0.0);
+        for i in 0..200_000 {
+            nitro.update_sampled(format!("k{}", i).as_bytes());
+        }
+        let estimate = nitro.estimated_stream_size();
+        assert!(
+            estimate.is_finite() && estimate > 0.0,
+            "estimate {}",
+            estimate
+        );
+
+        let mut adaptive = NitroSketch::with_adaptive_rate(base(), 0.00002, 0.5).unwrap();
+        adaptive.set_rate(0.00003).unwrap();
+        for i in 0..1_000 {
+            adaptive.update_sampled(format!("k{}", i).as_bytes());
+        }
+        assert!(adaptive.estimated_stream_size().is_finite());
+    }
+
+    #[test]
+    fn test_with_adaptive_rate_invalid() {
+        let base = || CountMinSketch::new(0.01, 0.01).unwrap();
+        assert!(NitroSketch::with_adaptive_rate(base(), 0.0, 0.5).is_err());
+        assert!(NitroSketch::with_adaptive_rate(base(), 0.6, 0.5).is_err());
+        assert!(NitroSketch::with_adaptive_rate(base(), 0.1, 1.5).is_err());
+    }
 }
@@ -34,13 +34,27 @@
 //! assert!(freq >= 100);
 //! ```
 
-use crate::common::{Mergeable, SketchError};
+use crate::common::{validation, Mergeable, SketchError};
 use crate::frequency::CountMinSketch;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use twox_hash::XxHash64;
 
+/// Magic bytes prefixed to serialized Removable Universal Sketches
+const SERIAL_MAGIC: &[u8; 4] = b"RUSK";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
+/// Fixed fields after the header: epsilon, delta, width, depth
+const SERIAL_FIELDS_LEN: usize = 8 + 8 + 8 + 8;
+
 /// Removable Universal Sketch for frequency estimation with deletions
+///
+/// # Thread Safety
+///
+/// `RemovableUniversalSketch` is `Send + Sync`. Inserts and deletions both take
+/// `&mut self`.
 #[derive(Clone, Debug)]
 pub struct RemovableUniversalSketch {
     /// Main frequency estimation sketch
@@ -174,7 +188,26 @@ impl RemovableUniversalSketch {
     }
 
     /// Merge another RemovableUniversalSketch into this one
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the sketches differ in width, depth
+    /// or hash seeds; `self` is left unchanged in that case.
     pub fn merge(&mut self, other: &RemovableUniversalSketch) -> Result<(), SketchError> {
+        if self.width != other.width || self.depth != other.depth {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "dimension mismatch: {}x{} vs {}x{}",
+                    self.depth, self.width, other.depth, other.width
+                ),
+            });
+        }
+        if self.cms.seeds() != other.cms.seeds() {
+            return Err(SketchError::IncompatibleSketches {
+                reason: "hash seed mismatch".to_string(),
+            });
+        }
+
         // Merge underlying CMS
         self.cms.merge(&other.cms)?;
 
@@ -189,6 +222,101 @@ impl RemovableUniversalSketch {
 
         Ok(())
     }
+
+    /// Serializes the sketch to bytes
+    ///
+    /// # Format
+    ///
+    /// - Header: magic `"RUSK"` and a version byte
+    /// - Epsilon and delta (f64 LE each), width and depth (u64 LE each)
+    /// - Count-Min counters: `depth * width` u64 LE, row-major
+    /// - Signed moment counters: `depth * width` i64 LE, row-major
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::RemovableUniversalSketch;
+    ///
+    /// let mut rus = RemovableUniversalSketch::new(0.01, 0.01).unwrap();
+    /// rus.update(&"item", 10);
+    /// rus.update(&"item", -3);
+    ///
+    /// let restored = RemovableUniversalSketch::from_bytes(&rus.to_bytes()).unwrap();
+    /// assert_eq!(restored.estimate(&"item"), rus.estimate(&"item"));
+    /// assert_eq!(restored.l2_norm(), rus.l2_norm());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let cells = self.depth * self.width;
+        let mut bytes = Vec::with_capacity(validation::HEADER_LEN + SERIAL_FIELDS_LEN + cells * 16);
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
+        bytes.extend_from_slice(&self.epsilon().to_le_bytes());
+        bytes.extend_from_slice(&self.delta().to_le_bytes());
+        bytes.extend_from_slice(&(self.width as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.depth as u64).to_le_bytes());
+
+        for &count in self.cms.counters() {
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+        for &value in &self.moment_sketch {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Deserializes a sketch written by [`to_bytes`](Self::to_bytes)
+    ///
+    /// # Errors
+    ///
+    /// Returns `DeserializationError` if the header is foreign or from an
+    /// unknown version, the data is truncated or has trailing bytes, or the
+    /// stored width and depth are not the ones epsilon and delta produce.
+    /// Returns `InvalidParameter` if epsilon or delta is out of range.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        validation::validate_byte_size(bytes.len())?;
+        let payload = validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?;
+        validation::validate_min_size(payload.len(), SERIAL_FIELDS_LEN)?;
+
+        let read_u64 = |at: usize| u64::from_le_bytes(payload[at..at + 8].try_into().unwrap());
+        let epsilon = f64::from_bits(read_u64(0));
+        let delta = f64::from_bits(read_u64(8));
+        let width = read_u64(16);
+        let depth = read_u64(24);
+
+        let mut sketch = Self::new(epsilon, delta)?;
+        if width != sketch.width as u64 || depth != sketch.depth as u64 {
+            return Err(SketchError::DeserializationError(format!(
+                "dimensions {}x{} do not match epsilon {} and delta {}",
+                depth, width, epsilon, delta
+            )));
+        }
+
+        let cells = sketch.depth * sketch.width;
+        let counters = &payload[SERIAL_FIELDS_LEN..];
+        if counters.len() != cells * 16 {
+            return Err(SketchError::DeserializationError(format!(
+                "expected {} bytes of counters, got {}",
+                cells * 16,
+                counters.len()
+            )));
+        }
+
+        let (cms_bytes, moment_bytes) = counters.split_at(cells * 8);
+        sketch.cms.load_counters(
+            cms_bytes
+                .chunks_exact(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap())),
+        );
+        for (value, chunk) in sketch
+            .moment_sketch
+            .iter_mut()
+            .zip(moment_bytes.chunks_exact(8))
+        {
+            *value = i64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Ok(sketch)
+    }
 }
 
 #[cfg(test)]
@@ -319,4 +447,51 @@ mod tests {
         let freq = rus1.estimate(&"item");
         assert!(freq >= 120);
     }
+
+    #[test]
+    fn test_rus_merge_rejects_mismatched_dimensions() {
+        let mut rus1 = RemovableUniversalSketch::new(0.01, 0.01).unwrap();
+        let rus2 = RemovableUniversalSketch::new(0.001, 0.01).unwrap();
+        let rus3 = RemovableUniversalSketch::new(0.01, 0.0001).unwrap();
+
+        rus1.update(&"item", 5);
+        assert!(rus1.merge(&rus2).is_err());
+        assert!(rus1.merge(&rus3).is_err());
+        assert_eq!(rus1.estimate(&"item"), 5);
+    }
+
+    #[test]
+    fn test_rus_serialization_round_trip() {
+        let mut rus = RemovableUniversalSketch::new(0.01, 0.01).unwrap();
+        for i in 0..200 {
+            rus.update(&i, (i % 7) + 1);
+        }
+        for i in 0..200 {
+            if i % 3 == 0 {
+                rus.update(&i, -((i % 5) + 1));
+            }
+        }
+
+        let bytes = rus.to_bytes();
+        let restored = RemovableUniversalSketch::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.width(), rus.width());
+        assert_eq!(restored.depth(), rus.depth());
+        assert_eq!(restored.epsilon(), rus.epsilon());
+        assert_eq!(restored.delta(), rus.delta());
+        assert_eq!(restored.l2_norm(), rus.l2_norm());
+        for i in 0..200 {
+            assert_eq!(restored.estimate(&i), rus.estimate(&i));
+        }
+        assert_eq!(restored.to_bytes(), bytes);
+
+        // Truncated, extended and mislabelled input is rejected
+        assert!(RemovableUniversalSketch::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(RemovableUniversalSketch::from_bytes(&extended).is_err());
+        let mut bad_width = bytes.clone();
+        bad_width[5 + 16] ^= 1;
+        assert!(RemovableUniversalSketch::from_bytes(&bad_width).is_err());
+        assert!(RemovableUniversalSketch::from_bytes(b"XXXX\x01").is_err());
+    }
 }
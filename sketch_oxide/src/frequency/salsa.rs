@@ -33,13 +33,18 @@
 //! assert!(estimate >= 100);
 //! ```
 
-use crate::common::{Mergeable, SketchError};
+use crate::common::{FrequencySketch, Mergeable, SketchError};
 use crate::frequency::CountMinSketch;
 
 /// SALSA: Self-Adjusting Counter Sizing for frequency estimation
 ///
 /// Wraps CountMinSketch to provide adaptive counter management. When frequencies
 /// approach overflow, SALSA automatically adjusts parameters to maintain accuracy.
+///
+/// # Thread Safety
+///
+/// `SALSA` is `Send + Sync`. Counter widening happens in place, under the
+/// `&mut self` borrow of `update`.
 #[derive(Clone, Debug)]
 pub struct SALSA {
     /// Inner CountMinSketch for frequency estimation
@@ -168,24 +173,76 @@ impl SALSA {
         self.inner.depth()
     }
 
+    /// Clears all counts while keeping epsilon, delta and dimensions
+    ///
+    /// Resets the maximum observed frequency, update total and adaptation
+    /// level as well, so the sketch behaves like a fresh one and can be
+    /// reused for the next time window without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::SALSA;
+    ///
+    /// let mut salsa = SALSA::new(0.01, 0.01).unwrap();
+    /// salsa.update(&"item", 10);
+    /// salsa.clear();
+    /// assert_eq!(salsa.estimate(&"item").0, 0);
+    /// assert_eq!(salsa.epsilon(), 0.01);
+    /// ```
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.max_observed = 0;
+        self.total_updates = 0;
+        self.adaptation_level = 0;
+    }
+
     /// Merge another SALSA sketch into this one
+    ///
+    /// Sketches at different adaptation levels merge by promoting both to
+    /// the higher level and then adding the counters, so no counts are lost.
+    /// Each side already adapted for its own maximum, so the merge does not
+    /// adapt again. Confidence follows the combined update total.
+    ///
+    /// # Errors
+    /// Returns `IncompatibleSketches` if the underlying CountMinSketch
+    /// dimensions differ
+    ///
+    /// # Examples
+    /// ```
+    /// use sketch_oxide::frequency::SALSA;
+    ///
+    /// let mut a = SALSA::new(0.01, 0.01).unwrap();
+    /// let mut b = SALSA::new(0.01, 0.01).unwrap();
+    /// a.update(&"item", 100);
+    /// b.update(&"item", 50);
+    ///
+    /// a.merge(&b).unwrap();
+    /// assert!(a.estimate(&"item").0 >= 150);
+    /// assert_eq!(a.total_updates(), 150);
+    /// ```
     pub fn merge(&mut self, other: &SALSA) -> Result<(), SketchError> {
         // Merge underlying sketches
         self.inner.merge(&other.inner)?;
 
-        // Update max_observed
+        self.adaptation_level = self.adaptation_level.max(other.adaptation_level);
         self.max_observed = self.max_observed.max(other.max_observed);
         self.total_updates = self.total_updates.saturating_add(other.total_updates);
 
-        // Trigger adaptation if needed
-        if self.should_adapt() {
-            self.adapt();
-        }
-
         Ok(())
     }
 }
 
+impl FrequencySketch for SALSA {
+    fn update(&mut self, item: &[u8], count: u64) {
+        SALSA::update(self, &item, count);
+    }
+
+    /// Returns the estimate without SALSA's confidence value
+    fn estimate(&self, item: &[u8]) -> u64 {
+        SALSA::estimate(self, &item).0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +349,73 @@ mod tests {
         assert!(estimate >= 150);
     }
 
+    #[test]
+    fn test_salsa_merge_across_adaptation_levels() {
+        let mut heavy = SALSA::new(0.01, 0.01).unwrap();
+        for i in 0..200 {
+            heavy.update(&format!("item_{}", i), 20);
+        }
+        heavy.update(&"hot", 5000);
+        // Stand in for a long-lived sketch that has adapted several times
+        heavy.adaptation_level = 3;
+
+        let mut fresh = SALSA::new(0.01, 0.01).unwrap();
+        fresh.update(&"hot", 500);
+        fresh.update(&"item_0", 5);
+
+        let mut merged = fresh.clone();
+        merged.merge(&heavy).unwrap();
+        assert_eq!(merged.adaptation_level(), 3);
+        assert_eq!(merged.max_observed(), 5000);
+        assert_eq!(
+            merged.total_updates(),
+            heavy.total_updates() + fresh.total_updates()
+        );
+
+        // Merging again in the other direction gives the same state
+        heavy.merge(&fresh).unwrap();
+        assert_eq!(heavy.adaptation_level(), 3);
+
+        let bound = (merged.epsilon() * merged.total_updates() as f64) as u64;
+        for sketch in [&merged, &heavy] {
+            let (hot, confidence) = sketch.estimate(&"hot");
+            assert!((5500..=5500 + bound).contains(&hot), "hot estimate {}", hot);
+            let (item, _) = sketch.estimate(&"item_0");
+            assert!(
+                (25..=25 + bound).contains(&item),
+                "item_0 estimate {}",
+                item
+            );
+            assert_eq!(confidence, 100);
+        }
+    }
+
+    #[test]
+    fn test_salsa_clear_keeps_parameters() {
+        let mut salsa = SALSA::new(0.01, 0.001).unwrap();
+        let (width, depth) = (salsa.width(), salsa.depth());
+        for i in 0..1000 {
+            salsa.update(&i, 3);
+        }
+
+        salsa.clear();
+
+        for i in 0..1000 {
+            assert_eq!(salsa.estimate(&i), (0, 0));
+        }
+        assert_eq!(salsa.max_observed(), 0);
+        assert_eq!(salsa.total_updates(), 0);
+        assert_eq!(salsa.adaptation_level(), 0);
+        assert_eq!(salsa.epsilon(), 0.01);
+        assert_eq!(salsa.delta(), 0.001);
+        assert_eq!((salsa.width(), salsa.depth()), (width, depth));
+
+        // Still usable, and mergeable with a fresh sketch of the same shape
+        salsa.update(&"item", 2);
+        assert_eq!(salsa.estimate(&"item").0, 2);
+        salsa.merge(&SALSA::new(0.01, 0.001).unwrap()).unwrap();
+    }
+
     #[test]
     fn test_salsa_merge_incompatible() {
         let salsa1 = SALSA::new(0.01, 0.01).unwrap();
@@ -56,8 +56,9 @@
 //! ```
 
 use crate::common::{Mergeable, Sketch, SketchError};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 
 /// Space-Saving Sketch for finding heavy hitters in a data stream
 ///
@@ -78,6 +79,10 @@ use std::hash::Hash;
 /// - Update: O(k) worst case to find minimum (can be optimized with heap)
 /// - Query: O(1) for hash lookup
 /// - Heavy hitters: O(k log k) for sorting
+///
+/// # Thread Safety
+///
+/// `SpaceSaving<T>` is `Send + Sync` whenever `T` is.
 #[derive(Debug, Clone)]
 pub struct SpaceSaving<T: Hash + Eq + Clone> {
     /// Maximum number of items to track: k = ceil(1/epsilon)
@@ -180,6 +185,61 @@ impl<T: Hash + Eq + Clone> SpaceSaving<T> {
         })
     }
 
+    /// Creates a Space-Saving sketch sized to fit a memory budget in bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Total memory budget for the sketch
+    /// * `avg_key_len` - Expected heap bytes owned by each key (e.g. the
+    ///   length of a `String` or `Vec<u8>`; 0 for plain values like `u64`)
+    ///
+    /// # Estimate
+    ///
+    /// Each tracked entry is charged `size_of::<T>()` for the key, 16 bytes
+    /// for its `(count, error)` pair and one hash table control byte, scaled
+    /// by 8/7 for the table's maximum load factor, plus `avg_key_len` heap
+    /// bytes. The fixed `size_of::<Self>()` is subtracted from the budget
+    /// first. Allocator rounding and power-of-two table growth are not
+    /// modelled, so treat the budget as approximate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the budget cannot hold the minimum
+    /// capacity of 2 entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::SpaceSaving;
+    ///
+    /// // 64 KiB for keys averaging 32 bytes
+    /// let sketch: SpaceSaving<Vec<u8>> = SpaceSaving::with_byte_budget(64 * 1024, 32).unwrap();
+    /// assert!(sketch.capacity() > 500);
+    /// ```
+    pub fn with_byte_budget(bytes: usize, avg_key_len: usize) -> Result<Self, SketchError> {
+        let entry_bytes = Self::entry_bytes(avg_key_len);
+        let capacity = bytes.saturating_sub(std::mem::size_of::<Self>()) / entry_bytes;
+
+        if capacity < 2 {
+            return Err(SketchError::InvalidParameter {
+                param: "bytes".to_string(),
+                value: bytes.to_string(),
+                constraint: format!(
+                    "must fit at least 2 entries (~{} bytes)",
+                    std::mem::size_of::<Self>() + 2 * entry_bytes
+                ),
+            });
+        }
+
+        Self::with_capacity(capacity)
+    }
+
+    /// Estimated bytes per tracked entry, see [`with_byte_budget`](Self::with_byte_budget)
+    fn entry_bytes(avg_key_len: usize) -> usize {
+        let slot = std::mem::size_of::<T>() + std::mem::size_of::<(u64, u64)>() + 1;
+        (slot * 8).div_ceil(7) + avg_key_len
+    }
+
     /// Updates the sketch with a single occurrence of an item
     ///
     /// # Arguments
@@ -204,17 +264,40 @@ impl<T: Hash + Eq + Clone> SpaceSaving<T> {
     /// ```
     #[inline]
     pub fn update(&mut self, item: T) {
-        self.stream_length += 1;
+        self.update_by(item, 1);
+    }
+
+    /// Adds `count` occurrences of an item in a single step
+    ///
+    /// Weighted Space-Saving: a tracked item gains `count`; an untracked item
+    /// replacing the minimum starts at `min_count + count` with
+    /// `error = min_count`, so the `[count - error, count]` guarantee holds for
+    /// weighted streams. A count of zero is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::SpaceSaving;
+    ///
+    /// let mut sketch = SpaceSaving::new(0.1).unwrap();
+    /// sketch.update_by("hello".to_string(), 5);
+    /// assert_eq!(sketch.estimate(&"hello".to_string()), Some((5, 5)));
+    /// ```
+    pub fn update_by(&mut self, item: T, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.stream_length = self.stream_length.saturating_add(count);
 
         // Case 1: Item already tracked - increment count
-        if let Some((count, _error)) = self.items.get_mut(&item) {
-            *count += 1;
+        if let Some((tracked, _error)) = self.items.get_mut(&item) {
+            *tracked = tracked.saturating_add(count);
             return;
         }
 
-        // Case 2: Space available - add new item with count=1, error=0
+        // Case 2: Space available - add new item with error=0
         if self.items.len() < self.capacity {
-            self.items.insert(item, (1, 0));
+            self.items.insert(item, (count, 0));
             return;
         }
 
@@ -236,9 +319,36 @@ impl<T: Hash + Eq + Clone> SpaceSaving<T> {
 
         if let Some(old_item) = min_item {
             self.items.remove(&old_item);
-            // Insert new item with count = min_count + 1, error = min_count
-            self.items.insert(item, (min_count + 1, min_count));
+            // Insert new item with count = min_count + count, error = min_count
+            self.items
+                .insert(item, (min_count.saturating_add(count), min_count));
+        }
+    }
+
+    /// Adds a fractional weight for an item, e.g. a flow measured in bytes
+    ///
+    /// The weight is rounded stochastically to a whole count whose expected
+    /// value equals the weight, then applied with [`update_by`](Self::update_by),
+    /// so minimum-count eviction compares weighted totals. Non-finite or
+    /// non-positive weights are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::frequency::SpaceSaving;
+    ///
+    /// let mut sketch = SpaceSaving::new(0.1).unwrap();
+    /// sketch.update_weighted("flow".to_string(), 1500.0);
+    /// assert_eq!(sketch.stream_length(), 1500);
+    /// ```
+    pub fn update_weighted(&mut self, item: T, weight: f64) {
+        if !weight.is_finite() || weight <= 0.0 {
+            return;
         }
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let entropy = hasher.finish() ^ self.stream_length;
+        self.update_by(item, super::stochastic_round(weight, entropy));
     }
 
     /// Estimates the frequency of an item
@@ -1016,6 +1126,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_byte_budget() {
+        let small: SpaceSaving<u64> = SpaceSaving::with_byte_budget(4096, 0).unwrap();
+        let large: SpaceSaving<u64> = SpaceSaving::with_byte_budget(64 * 1024, 0).unwrap();
+        assert!(small.capacity() >= 2);
+        assert!(large.capacity() > small.capacity() * 10);
+
+        // Longer keys leave room for fewer entries
+        let short: SpaceSaving<Vec<u8>> = SpaceSaving::with_byte_budget(64 * 1024, 8).unwrap();
+        let long: SpaceSaving<Vec<u8>> = SpaceSaving::with_byte_budget(64 * 1024, 256).unwrap();
+        assert!(long.capacity() < short.capacity());
+
+        // Estimated footprint stays within the budget
+        let budget = 10_000;
+        let sketch: SpaceSaving<Vec<u8>> = SpaceSaving::with_byte_budget(budget, 16).unwrap();
+        let used = std::mem::size_of::<SpaceSaving<Vec<u8>>>()
+            + sketch.capacity() * SpaceSaving::<Vec<u8>>::entry_bytes(16);
+        assert!(used <= budget);
+    }
+
+    #[test]
+    fn test_with_byte_budget_too_small() {
+        assert!(SpaceSaving::<u64>::with_byte_budget(0, 0).is_err());
+        assert!(SpaceSaving::<String>::with_byte_budget(64, 1024).is_err());
+    }
+
     #[test]
     fn test_capacity_calculation() {
         // epsilon = 0.1 -> capacity = ceil(1/0.1) = 10
@@ -1044,4 +1180,37 @@ mod tests {
             sketch.estimate(&"test".to_string())
         );
     }
+
+    #[test]
+    fn test_update_by() {
+        let mut sketch: SpaceSaving<&str> = SpaceSaving::new(0.5).unwrap();
+        sketch.update_by("a", 10);
+        sketch.update_by("b", 3);
+        sketch.update_by("a", 5);
+        sketch.update_by("z", 0);
+        assert_eq!(sketch.estimate(&"a"), Some((15, 15)));
+        assert_eq!(sketch.stream_length(), 18);
+
+        // At capacity 2: "c" replaces "b" (min 3) with count 3 + 4, error 3
+        sketch.update_by("c", 4);
+        assert_eq!(sketch.estimate(&"b"), None);
+        assert_eq!(sketch.estimate(&"c"), Some((4, 7)));
+        assert_eq!(sketch.stream_length(), 22);
+    }
+
+    #[test]
+    fn test_update_weighted() {
+        let mut sketch: SpaceSaving<u32> = SpaceSaving::new(0.1).unwrap();
+        for i in 0..1000 {
+            sketch.update_weighted(i % 5, 1.5);
+        }
+        let length = sketch.stream_length();
+        assert!((1400..=1600).contains(&length), "got {}", length);
+
+        for weight in [0.0, -1.0, f64::NAN, f64::NEG_INFINITY] {
+            sketch.update_weighted(99, weight);
+        }
+        assert_eq!(sketch.stream_length(), length);
+        assert_eq!(sketch.estimate(&99), None);
+    }
 }
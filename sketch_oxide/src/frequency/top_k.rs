@@ -0,0 +1,348 @@
+//! Bounded top-k tracker for frequency estimators
+//!
+//! Count-Min style sketches answer "how often did `x` occur?" but cannot list
+//! the items they have seen. [`TopK`] fills that gap: after each update the
+//! caller offers the item together with its fresh estimate, and the tracker
+//! keeps the `k` items with the largest estimates in a min-heap, so the
+//! weakest candidate is always at the root and can be evicted in O(log k).
+//!
+//! `TopK` does not estimate anything itself, which lets the same structure
+//! back [`CountMinSketch`](super::CountMinSketch),
+//! [`ConservativeCountMin`](super::ConservativeCountMin),
+//! [`ElasticSketch`](super::ElasticSketch) or any other estimator.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::frequency::{CountMinSketch, TopK};
+//!
+//! let mut cms = CountMinSketch::new(0.001, 0.01).unwrap();
+//! let mut top = TopK::new(2).unwrap();
+//!
+//! for word in ["a", "b", "a", "c", "a", "b"] {
+//!     cms.update(&word);
+//!     top.offer(word, cms.estimate(&word));
+//! }
+//!
+//! assert_eq!(top.to_sorted_vec(), vec![("a", 3), ("b", 2)]);
+//! ```
+
+use crate::common::SketchError;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Bounded min-heap of the `k` keys with the largest estimates
+///
+/// Each key appears at most once. Offering a tracked key replaces its
+/// estimate; offering an untracked key admits it while there is room, and
+/// afterwards only if its estimate is strictly larger than the current
+/// minimum, which is then evicted.
+///
+/// # Time Complexity
+///
+/// - [`offer`](Self::offer): O(log k)
+/// - [`estimate`](Self::estimate), [`min_estimate`](Self::min_estimate): O(1)
+/// - [`merge`](Self::merge): O((k + k') log k)
+#[derive(Clone, Debug)]
+pub struct TopK<K: Hash + Eq + Clone> {
+    /// Maximum number of tracked keys
+    capacity: usize,
+    /// Heap-ordered `(key, estimate)` pairs, smallest estimate at index 0
+    heap: Vec<(K, u64)>,
+    /// Position of each key in `heap`
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone> TopK<K> {
+    /// Creates a tracker holding at most `k` keys
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `k` is 0.
+    pub fn new(k: usize) -> Result<Self, SketchError> {
+        if k == 0 {
+            return Err(SketchError::InvalidParameter {
+                param: "k".to_string(),
+                value: k.to_string(),
+                constraint: "must be > 0".to_string(),
+            });
+        }
+        Ok(Self {
+            capacity: k,
+            heap: Vec::with_capacity(k),
+            positions: HashMap::with_capacity(k),
+        })
+    }
+
+    /// Records `estimate` as the current estimate for `key`
+    ///
+    /// Returns whether `key` is tracked afterwards. A tracked key always stays
+    /// tracked, even if its new estimate is lower than before.
+    pub fn offer(&mut self, key: K, estimate: u64) -> bool {
+        if let Some(&index) = self.positions.get(&key) {
+            let previous = self.heap[index].1;
+            self.heap[index].1 = estimate;
+            if estimate < previous {
+                self.sift_up(index);
+            } else {
+                self.sift_down(index);
+            }
+            return true;
+        }
+
+        if self.heap.len() < self.capacity {
+            let index = self.heap.len();
+            self.positions.insert(key.clone(), index);
+            self.heap.push((key, estimate));
+            self.sift_up(index);
+            return true;
+        }
+
+        if estimate <= self.heap[0].1 {
+            return false;
+        }
+        self.positions.remove(&self.heap[0].0);
+        self.positions.insert(key.clone(), 0);
+        self.heap[0] = (key, estimate);
+        self.sift_down(0);
+        true
+    }
+
+    /// Merges another tracker into this one
+    ///
+    /// Keys tracked by both keep the larger of their two estimates, and the
+    /// `k` largest of the combined candidates are kept. This suits trackers
+    /// fed by the same estimator; when merging trackers of sketches that were
+    /// themselves merged, use [`merge_with`](Self::merge_with) so that every
+    /// candidate is re-estimated against the merged sketch.
+    pub fn merge(&mut self, other: &Self) {
+        for (key, estimate) in &other.heap {
+            let estimate = self.estimate(key).map_or(*estimate, |e| e.max(*estimate));
+            self.offer(key.clone(), estimate);
+        }
+    }
+
+    /// Merges another tracker into this one, re-estimating every candidate
+    ///
+    /// Each key tracked by either side is looked up with `estimate`, typically
+    /// a query against the merged sketch, and the `k` keys with the largest
+    /// results are kept.
+    pub fn merge_with(&mut self, other: &Self, mut estimate: impl FnMut(&K) -> u64) {
+        let candidates: Vec<K> = self
+            .heap
+            .drain(..)
+            .map(|(key, _)| key)
+            .chain(
+                other
+                    .heap
+                    .iter()
+                    .filter(|(key, _)| !self.positions.contains_key(key))
+                    .map(|(key, _)| key.clone()),
+            )
+            .collect();
+        self.positions.clear();
+        for key in candidates {
+            let e = estimate(&key);
+            self.offer(key, e);
+        }
+    }
+
+    /// Recorded estimate for `key`, or `None` if it is not tracked
+    pub fn estimate(&self, key: &K) -> Option<u64> {
+        self.positions.get(key).map(|&index| self.heap[index].1)
+    }
+
+    /// Whether `key` is currently tracked
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Smallest tracked estimate, or `None` if nothing is tracked
+    ///
+    /// Once the tracker is full, an untracked key must beat this to be
+    /// admitted.
+    pub fn min_estimate(&self) -> Option<u64> {
+        self.heap.first().map(|(_, estimate)| *estimate)
+    }
+
+    /// Maximum number of tracked keys
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of tracked keys
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no keys are tracked
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Tracked `(key, estimate)` pairs in heap order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.heap.iter().map(|(key, estimate)| (key, *estimate))
+    }
+
+    /// Tracked `(key, estimate)` pairs sorted by descending estimate
+    pub fn to_sorted_vec(&self) -> Vec<(K, u64)> {
+        let mut items = self.heap.clone();
+        items.sort_by_key(|item| std::cmp::Reverse(item.1));
+        items
+    }
+
+    /// Removes all tracked keys
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.heap[parent].1 <= self.heap[index].1 {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+            if left < self.heap.len() && self.heap[left].1 < self.heap[smallest].1 {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.heap[right].1 < self.heap[smallest].1 {
+                smallest = right;
+            }
+            if smallest == index {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the heap property and that `positions` indexes `heap`
+    fn assert_consistent<K: Hash + Eq + Clone>(top: &TopK<K>) {
+        assert_eq!(top.heap.len(), top.positions.len());
+        assert!(top.heap.len() <= top.capacity);
+        for (index, (key, estimate)) in top.heap.iter().enumerate() {
+            assert_eq!(top.positions[key], index);
+            if index > 0 {
+                assert!(top.heap[(index - 1) / 2].1 <= *estimate);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_capacity_rejected() {
+        assert!(TopK::<u64>::new(0).is_err());
+    }
+
+    #[test]
+    fn test_keeps_largest_estimates() {
+        let mut top = TopK::new(3).unwrap();
+        // Pseudo-random order of 0..100 with estimate equal to the key
+        for i in 0..100u64 {
+            let key = (i * 37) % 100;
+            top.offer(key, key);
+            assert_consistent(&top);
+        }
+        assert_eq!(top.to_sorted_vec(), vec![(99, 99), (98, 98), (97, 97)]);
+        assert_eq!(top.min_estimate(), Some(97));
+        assert!(!top.offer(50, 50));
+        assert!(!top.offer(1, 97), "ties with the minimum are not admitted");
+    }
+
+    #[test]
+    fn test_offer_updates_tracked_key() {
+        let mut top = TopK::new(2).unwrap();
+        top.offer("a", 5);
+        top.offer("b", 10);
+        assert_eq!(top.min_estimate(), Some(5));
+
+        // Raising "a" makes "b" the minimum
+        assert!(top.offer("a", 20));
+        assert_eq!(top.min_estimate(), Some(10));
+        assert_eq!(top.len(), 2);
+
+        // Lowering "a" keeps it tracked
+        assert!(top.offer("a", 1));
+        assert_eq!(top.estimate(&"a"), Some(1));
+        assert!(top.offer("c", 2));
+        assert!(!top.contains(&"a"));
+        assert_consistent(&top);
+    }
+
+    #[test]
+    fn test_merge_keeps_top_of_union() {
+        let mut left = TopK::new(3).unwrap();
+        let mut right = TopK::new(3).unwrap();
+        for (key, estimate) in [("a", 10), ("b", 4), ("c", 7)] {
+            left.offer(key, estimate);
+        }
+        for (key, estimate) in [("b", 12), ("d", 8), ("e", 1)] {
+            right.offer(key, estimate);
+        }
+
+        left.merge(&right);
+        assert_consistent(&left);
+        assert_eq!(left.to_sorted_vec(), vec![("b", 12), ("a", 10), ("d", 8)]);
+    }
+
+    #[test]
+    fn test_merge_with_reestimates_candidates() {
+        let mut left = TopK::new(2).unwrap();
+        let mut right = TopK::new(2).unwrap();
+        left.offer("a", 5);
+        left.offer("b", 4);
+        right.offer("b", 3);
+        right.offer("c", 6);
+
+        // Merged counts: a = 5, b = 4 + 3, c = 6
+        let merged = |key: &&str| match *key {
+            "a" => 5,
+            "b" => 7,
+            "c" => 6,
+            _ => 0,
+        };
+        left.merge_with(&right, merged);
+        assert_consistent(&left);
+        assert_eq!(left.to_sorted_vec(), vec![("b", 7), ("c", 6)]);
+    }
+
+    #[test]
+    fn test_tracks_count_min_heavy_hitters() {
+        use crate::frequency::CountMinSketch;
+
+        let mut cms = CountMinSketch::new(0.001, 0.01).unwrap();
+        let mut top = TopK::new(5).unwrap();
+        for i in 0..10_000u64 {
+            // Keys 0..5 are heavy, the rest occur once each
+            let key = if i % 2 == 0 { i % 10 / 2 } else { 1000 + i };
+            cms.update(&key);
+            top.offer(key, cms.estimate(&key));
+        }
+
+        let mut keys: Vec<u64> = top.iter().map(|(key, _)| *key).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, vec![0, 1, 2, 3, 4]);
+    }
+}
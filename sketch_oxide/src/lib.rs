@@ -20,8 +20,8 @@ pub mod universal;
 
 // Re-export core types for convenience
 pub use common::{
-    hash, Mergeable, RangeFilter, Reconcilable, Result, SetDifference, Sketch, SketchError,
-    WindowedSketch,
+    hash, CardinalitySketch, FrequencySketch, Mergeable, QuantileSketch, RangeFilter, Reconcilable,
+    Result, SetDifference, Sketch, SketchError, WindowedSketch,
 };
 
 /// Error types and result aliases for sketch operations
@@ -30,7 +30,9 @@ pub mod error {
 }
 
 // Re-export commonly used sketches
-pub use cardinality::{CpcSketch, HyperLogLog, QSketch, ThetaSketch, UltraLogLog};
+pub use cardinality::{
+    CompactThetaSketch, CpcSketch, DistinctCounter, HyperLogLog, QSketch, ThetaSketch, UltraLogLog,
+};
 pub use frequency::{
     ConservativeCountMin, CountMinSketch, CountSketch, ElasticSketch, FrequentItems, HeavyKeeper,
     NitroSketch, NitroSketchStats, RemovableUniversalSketch, SpaceSaving, SALSA,
@@ -40,6 +40,11 @@ const MAX_ITERATIONS: u64 = 10000; // Max attempts to find valid seed
 /// This is an immutable data structure - it must be built from a complete
 /// set of items and cannot be updated incrementally.
 ///
+/// # Thread Safety
+///
+/// `BinaryFuseFilter` is `Send + Sync` and immutable once built, so share it
+/// across threads with an `Arc`.
+///
 /// # Examples
 ///
 /// ```
@@ -459,9 +464,21 @@ impl Sketch for BinaryFuseFilter {
             )));
         }
 
+        if segment_length == 0 && size > 0 {
+            return Err(SketchError::DeserializationError(format!(
+                "Invalid segment length 0 for a filter of {} items",
+                size
+            )));
+        }
+
         let fingerprints = bytes[25..].to_vec();
 
-        let expected_fp_len = (segment_length * segment_count) as usize;
+        let expected_fp_len = segment_length.checked_mul(segment_count).ok_or_else(|| {
+            SketchError::DeserializationError(format!(
+                "Segment length {} overflows the fingerprint array",
+                segment_length
+            ))
+        })? as usize;
         if fingerprints.len() != expected_fp_len && size > 0 {
             return Err(SketchError::DeserializationError(format!(
                 "Fingerprint array length mismatch: expected {}, got {}",
@@ -485,6 +502,26 @@ impl Sketch for BinaryFuseFilter {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_deserialize_rejects_degenerate_params() {
+        fn header(segment_length: u32, size: u64) -> Vec<u8> {
+            let mut bytes = 0u64.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&segment_length.to_le_bytes());
+            bytes.extend_from_slice(&3u32.to_le_bytes());
+            bytes.extend_from_slice(&size.to_le_bytes());
+            bytes.push(8);
+            bytes
+        }
+
+        // Zero-length segments would divide by zero on the first lookup
+        assert!(BinaryFuseFilter::deserialize(&header(0, 1)).is_err());
+        // segment_length * 3 overflows u32
+        assert!(BinaryFuseFilter::deserialize(&header(u32::MAX / 2, 1)).is_err());
+
+        let empty = BinaryFuseFilter::deserialize(&header(0, 0)).unwrap();
+        assert!(!empty.contains(&42));
+    }
+
     #[test]
     fn test_calculate_segment_length() {
         assert_eq!(BinaryFuseFilter::calculate_segment_length(0), 0);
@@ -11,7 +11,7 @@
 //!
 //! # Features
 //! - Configurable false positive rate
-//! - Serialization/deserialization support
+//! - Serialization/deserialization support, with a compact form for sparse filters
 //! - Zero false negatives guaranteed
 //!
 //! # Example
@@ -27,9 +27,22 @@
 //! assert!(!filter.contains(b"key3")); // Probably false
 //! ```
 
+use crate::cardinality::HyperLogLog;
+use crate::common::hash::mix64;
+use crate::common::validation::{validate_header, write_header, HEADER_LEN};
+use crate::common::SketchError;
 use xxhash_rust::xxh64::xxh64;
 
+/// Magic prefix of the sparse serialization written by `to_bytes_compressed`
+const COMPRESSED_MAGIC: &[u8; 4] = b"BLMZ";
+const COMPRESSED_VERSION: u8 = 1;
+
 /// Standard Bloom filter for membership testing
+///
+/// # Thread Safety
+///
+/// `BloomFilter` is `Send + Sync`. `contains` takes `&self`, so a populated
+/// filter can be queried from many threads through an `Arc`.
 #[derive(Clone)]
 pub struct BloomFilter {
     /// Bit array
@@ -43,6 +56,17 @@ pub struct BloomFilter {
 }
 
 impl BloomFilter {
+    /// Largest number of hash functions a filter may use
+    ///
+    /// [`new`](Self::new) reaches it only for false positive rates below
+    /// about 1e-19, and [`from_bytes`](Self::from_bytes) rejects anything
+    /// above it.
+    pub const MAX_HASH_FUNCTIONS: usize = 64;
+
+    /// Largest bit count (2^32, a 512 MiB bit array) accepted in the compressed
+    /// layout, whose payload size does not bound `m`
+    pub const MAX_COMPRESSED_BITS: u64 = 1 << 32;
+
     /// Creates a new Bloom filter
     ///
     /// # Arguments
@@ -63,7 +87,7 @@ impl BloomFilter {
 
         // Optimal hash function count: k = (m/n) * ln(2)
         let k = ((m as f64 / n as f64) * std::f64::consts::LN_2).ceil() as usize;
-        let k = k.max(1); // At least one hash function
+        let k = k.clamp(1, Self::MAX_HASH_FUNCTIONS);
 
         let num_words = m.div_ceil(64); // Round up to nearest 64 bits
 
@@ -75,16 +99,53 @@ impl BloomFilter {
         }
     }
 
+    /// Creates a Bloom filter sized from a HyperLogLog cardinality estimate
+    ///
+    /// The expected number of elements is `hll.estimate()` inflated by three
+    /// standard errors (`1 + 3 * hll.standard_error()`) and rounded up, so the
+    /// target FPR holds unless the true cardinality exceeds the estimate by more
+    /// than ~3 sigma. An empty sketch yields a filter for one element.
+    ///
+    /// # Arguments
+    /// * `hll` - HyperLogLog that has seen the stream the filter will hold
+    /// * `fpr` - Desired false positive rate (e.g., 0.01 for 1%)
+    ///
+    /// # Panics
+    /// Panics if `fpr` is not in range (0, 1)
+    ///
+    /// # Example
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::membership::BloomFilter;
+    ///
+    /// let mut hll = HyperLogLog::new(12).unwrap();
+    /// for i in 0..10_000u64 {
+    ///     hll.update(&i);
+    /// }
+    ///
+    /// let filter = BloomFilter::for_estimated(&hll, 0.01);
+    /// let (n, _, _) = filter.params();
+    /// assert!(n >= 10_000);
+    /// ```
+    pub fn for_estimated(hll: &HyperLogLog, fpr: f64) -> Self {
+        Self::new(super::capacity_from_estimate(hll), fpr)
+    }
+
     /// Creates a Bloom filter with specific parameters
     ///
     /// # Arguments
     /// * `n` - Expected number of elements
     /// * `m` - Number of bits
-    /// * `k` - Number of hash functions
+    /// * `k` - Number of hash functions, clamped to
+    ///   [`MAX_HASH_FUNCTIONS`](Self::MAX_HASH_FUNCTIONS) as in [`new`](Self::new)
+    ///
+    /// # Panics
+    /// Panics if any parameter is 0
     pub fn with_params(n: usize, m: usize, k: usize) -> Self {
         assert!(n > 0, "Expected number of elements must be > 0");
         assert!(m > 0, "Number of bits must be > 0");
         assert!(k > 0, "Number of hash functions must be > 0");
+        let k = k.min(Self::MAX_HASH_FUNCTIONS);
 
         let num_words = m.div_ceil(64);
 
@@ -115,6 +176,12 @@ impl BloomFilter {
         (((hash as u128) * (range as u128)) >> 64) as usize
     }
 
+    /// Kirsch-Mitzenmacher: h_i(x) = h1 + i * h2, reduced into [0, m)
+    #[inline(always)]
+    fn bit_index(h1: u64, h2: u64, i: usize, m: usize) -> usize {
+        Self::fast_range(h1.wrapping_add((i as u64).wrapping_mul(h2)), m)
+    }
+
     /// Inserts an element into the filter
     ///
     /// Uses Kirsch-Mitzenmacher double hashing: compute only 2 hashes,
@@ -122,12 +189,43 @@ impl BloomFilter {
     #[inline]
     pub fn insert(&mut self, key: &[u8]) {
         let (h1, h2) = self.base_hashes(key);
+        self.set_bits(h1, h2);
+    }
+
+    /// Inserts an element given a 64-bit hash computed by the caller
+    ///
+    /// Lets one hash per key feed several filters. Positions are derived by
+    /// the same double hashing as [`insert`](Self::insert), from
+    /// `h1 = hash` and `h2 = splitmix64_finalizer(hash)` instead of two
+    /// xxh64 hashes of the key; see [`hash_indices`](Self::hash_indices) for
+    /// the rest of the scheme. A key inserted this way is found by
+    /// [`contains_hash`](Self::contains_hash) with the same hash, not by
+    /// [`contains`](Self::contains), which keeps filters serialized before
+    /// this method existed readable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::BloomFilter;
+    /// use xxhash_rust::xxh64::xxh64;
+    ///
+    /// let mut filter = BloomFilter::new(1000, 0.01);
+    /// let hash = xxh64(b"key", 0);
+    /// filter.insert_hash(hash);
+    /// assert!(filter.contains_hash(hash));
+    /// ```
+    #[inline]
+    pub fn insert_hash(&mut self, hash: u64) {
+        self.set_bits(hash, mix64(hash));
+    }
+
+    /// Sets the `k` bits derived from two base hashes
+    #[inline(always)]
+    fn set_bits(&mut self, h1: u64, h2: u64) {
         let m = self.m;
 
         for i in 0..self.k {
-            // Kirsch-Mitzenmacher: h_i(x) = h1 + i * h2
-            let combined = h1.wrapping_add((i as u64).wrapping_mul(h2));
-            let bit_index = Self::fast_range(combined, m);
+            let bit_index = Self::bit_index(h1, h2, i, m);
             let word_index = bit_index / 64;
             let bit_offset = bit_index % 64;
 
@@ -147,12 +245,23 @@ impl BloomFilter {
     #[inline]
     pub fn contains(&self, key: &[u8]) -> bool {
         let (h1, h2) = self.base_hashes(key);
+        self.bits_set(h1, h2)
+    }
+
+    /// Checks if an element inserted with [`insert_hash`](Self::insert_hash)
+    /// might be in the set
+    #[inline]
+    pub fn contains_hash(&self, hash: u64) -> bool {
+        self.bits_set(hash, mix64(hash))
+    }
+
+    /// Whether all `k` bits derived from two base hashes are set
+    #[inline(always)]
+    fn bits_set(&self, h1: u64, h2: u64) -> bool {
         let m = self.m;
 
         for i in 0..self.k {
-            // Kirsch-Mitzenmacher: h_i(x) = h1 + i * h2
-            let combined = h1.wrapping_add((i as u64).wrapping_mul(h2));
-            let bit_index = Self::fast_range(combined, m);
+            let bit_index = Self::bit_index(h1, h2, i, m);
             let word_index = bit_index / 64;
             let bit_offset = bit_index % 64;
 
@@ -165,6 +274,60 @@ impl BloomFilter {
         true
     }
 
+    /// Returns the bit positions a key maps to, in probe order
+    ///
+    /// Lets external code (SIMD or GPU kernels) replicate querying against
+    /// [`as_bits`](Self::as_bits). A key is present iff all these bits are set.
+    /// The scheme is:
+    ///
+    /// 1. `h1 = xxh64(key, seed = 0)` and `h2 = xxh64(key, seed = 1)`
+    /// 2. For `i` in `0..num_hashes`: `g = h1 + i * h2` (wrapping 64-bit)
+    /// 3. Position `i` is `(g * num_bits) >> 64`, computed in 128 bits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::BloomFilter;
+    ///
+    /// let mut filter = BloomFilter::new(1000, 0.01);
+    /// filter.insert(b"key");
+    ///
+    /// let bytes = filter.as_bits();
+    /// assert!(filter
+    ///     .hash_indices(b"key")
+    ///     .iter()
+    ///     .all(|&i| bytes[i / 8] & (1 << (i % 8)) != 0));
+    /// ```
+    pub fn hash_indices(&self, key: &[u8]) -> Vec<usize> {
+        let (h1, h2) = self.base_hashes(key);
+        (0..self.k)
+            .map(|i| Self::bit_index(h1, h2, i, self.m))
+            .collect()
+    }
+
+    /// Returns the raw bit array as bytes
+    ///
+    /// Bit `i` is bit `i % 8` of byte `i / 8`. The array is zero-padded to a
+    /// multiple of 64 bits, so it may be longer than `num_bits() / 8`. Only
+    /// available on little-endian targets, where the words' memory layout
+    /// matches that byte order.
+    #[cfg(target_endian = "little")]
+    pub fn as_bits(&self) -> &[u8] {
+        // SAFETY: u8 has no alignment requirement and the slice covers exactly
+        // the words owned by `bits`
+        unsafe { std::slice::from_raw_parts(self.bits.as_ptr().cast::<u8>(), self.bits.len() * 8) }
+    }
+
+    /// Returns the number of bits (m)
+    pub fn num_bits(&self) -> usize {
+        self.m
+    }
+
+    /// Returns the number of hash functions (k)
+    pub fn num_hashes(&self) -> usize {
+        self.k
+    }
+
     /// Clears all bits in the filter
     pub fn clear(&mut self) {
         self.bits.fill(0);
@@ -178,10 +341,46 @@ impl BloomFilter {
             .sum()
     }
 
-    /// Returns the theoretical false positive rate
+    /// Returns the theoretical false positive rate at the design capacity
+    ///
+    /// Computed as `(1 - e^(-k*n/m))^k` from the parameters alone, so it does
+    /// not change as items are inserted. See
+    /// [`current_false_positive_rate`](Self::current_false_positive_rate)
+    /// for the rate implied by the bits actually set.
     pub fn false_positive_rate(&self) -> f64 {
-        let bits_set = self.count_bits() as f64 / self.m as f64;
-        bits_set.powi(self.k as i32)
+        let exponent = -(self.k as f64) * self.n as f64 / self.m as f64;
+        (1.0 - exponent.exp()).powi(self.k as i32)
+    }
+
+    /// Returns the false positive rate implied by the current fill
+    ///
+    /// Computed as `(bits_set / m)^k`. A filter filled past its design
+    /// capacity reports a rate above [`false_positive_rate`](Self::false_positive_rate).
+    ///
+    /// # Example
+    /// ```
+    /// use sketch_oxide::membership::BloomFilter;
+    ///
+    /// let mut filter = BloomFilter::new(100, 0.01);
+    /// assert_eq!(filter.current_false_positive_rate(), 0.0);
+    ///
+    /// for i in 0..1000u32 {
+    ///     filter.insert(&i.to_le_bytes());
+    /// }
+    /// assert!(filter.current_false_positive_rate() > filter.false_positive_rate());
+    /// assert!(filter.is_saturated(0.05));
+    /// ```
+    pub fn current_false_positive_rate(&self) -> f64 {
+        let fill_ratio = self.count_bits() as f64 / self.m as f64;
+        fill_ratio.powi(self.k as i32)
+    }
+
+    /// Returns true if the current false positive rate exceeds `threshold`
+    ///
+    /// Use the filter's FPR budget as `threshold` to detect when it has
+    /// filled past the point where lookups meet that budget.
+    pub fn is_saturated(&self, threshold: f64) -> bool {
+        self.current_false_positive_rate() > threshold
     }
 
     /// Returns the memory usage in bytes
@@ -207,27 +406,117 @@ impl BloomFilter {
     }
 
     /// Deserializes a filter from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+    ///
+    /// Accepts both the plain layout written by [`to_bytes`](Self::to_bytes)
+    /// and the sparse layout written by
+    /// [`to_bytes_compressed`](Self::to_bytes_compressed); the latter is
+    /// recognised by its `BLMZ` magic prefix. A plain filter whose `n`
+    /// happens to start with those bytes is still read as plain, since it
+    /// does not decode as a compressed one.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        if bytes.len() >= HEADER_LEN && bytes[..4] == *COMPRESSED_MAGIC {
+            return Self::from_bytes_compressed(bytes)
+                .or_else(|err| Self::from_bytes_plain(bytes).map_err(|_| err));
+        }
+        Self::from_bytes_plain(bytes)
+    }
+
+    /// Decodes the layout written by [`to_bytes`](Self::to_bytes)
+    fn from_bytes_plain(bytes: &[u8]) -> Result<Self, SketchError> {
         if bytes.len() < 24 {
-            return Err("Insufficient bytes for header");
+            return Err(corrupt("Insufficient bytes for header"));
         }
 
-        let n = usize::from_le_bytes(bytes[0..8].try_into().unwrap());
-        let m = usize::from_le_bytes(bytes[8..16].try_into().unwrap());
-        let k = usize::from_le_bytes(bytes[16..24].try_into().unwrap());
+        let n = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let m = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let k = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
+        let (n, m, k) = validate_params(n, m, k)?;
 
         let num_words = m.div_ceil(64);
-        let expected_size = 24 + num_words * 8;
+        if num_words.checked_mul(8).and_then(|len| len.checked_add(24)) != Some(bytes.len()) {
+            return Err(corrupt("Invalid byte array size"));
+        }
+
+        let bits = bytes[24..]
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        Ok(Self { bits, k, m, n })
+    }
+
+    /// Serializes the filter in a compact form suited to sparse filters
+    ///
+    /// Instead of the raw bit array, the positions of the set bits are stored
+    /// as LEB128-encoded gaps, after a `BLMZ` magic/version header and the
+    /// same `n`, `m`, `k` fields as [`to_bytes`](Self::to_bytes).
+    /// [`from_bytes`](Self::from_bytes) detects the format automatically.
+    ///
+    /// Every set bit costs at least one byte, against one eighth of a byte in
+    /// the plain layout, so the compressed form is only smaller while fewer
+    /// than roughly 1 in 8 bits are set (somewhat less for very sparse
+    /// filters, where gaps above 127 take two bytes). A filter sized for
+    /// 1% FPR that holds 5% of its capacity has about 3.4% of its bits set
+    /// and compresses to under a third of the plain size; at full
+    /// capacity half the bits are set and the plain form is ~4x smaller.
+    ///
+    /// [`from_bytes`](Self::from_bytes) rejects this layout for filters of
+    /// more than [`MAX_COMPRESSED_BITS`](Self::MAX_COMPRESSED_BITS) bits;
+    /// use `to_bytes` for those.
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, COMPRESSED_MAGIC, COMPRESSED_VERSION);
+        bytes.extend_from_slice(&(self.n as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.m as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.k as u64).to_le_bytes());
+
+        // Gap to the first set bit is its position; later gaps exclude the
+        // previous bit, so adjacent set bits encode as 0
+        let mut next = 0u64;
+        for (w, &word) in self.bits.iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let pos = (w * 64) as u64 + word.trailing_zeros() as u64;
+                write_varint(&mut bytes, pos - next);
+                next = pos + 1;
+                word &= word - 1;
+            }
+        }
+
+        bytes
+    }
 
-        if bytes.len() != expected_size {
-            return Err("Invalid byte array size");
+    /// Decodes the layout written by [`to_bytes_compressed`](Self::to_bytes_compressed)
+    fn from_bytes_compressed(bytes: &[u8]) -> Result<Self, SketchError> {
+        let payload = validate_header(bytes, COMPRESSED_MAGIC, COMPRESSED_VERSION)
+            .map_err(|_| corrupt("Unsupported compressed format version"))?;
+        if payload.len() < 24 {
+            return Err(corrupt("Insufficient bytes for header"));
         }
 
-        let mut bits = Vec::with_capacity(num_words);
-        for i in 0..num_words {
-            let offset = 24 + i * 8;
-            let word = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
-            bits.push(word);
+        let n = u64::from_le_bytes(payload[0..8].try_into().unwrap());
+        let m = u64::from_le_bytes(payload[8..16].try_into().unwrap());
+        let k = u64::from_le_bytes(payload[16..24].try_into().unwrap());
+        let (n, m, k) = validate_params(n, m, k)?;
+        if m as u64 > Self::MAX_COMPRESSED_BITS {
+            return Err(SketchError::DeserializationError(format!(
+                "Compressed BloomFilter of {} bits exceeds the {} bit limit",
+                m,
+                Self::MAX_COMPRESSED_BITS
+            )));
+        }
+
+        let mut bits = vec![0u64; m.div_ceil(64)];
+        let mut rest = &payload[24..];
+        let mut next = 0u64;
+        while !rest.is_empty() {
+            let gap = read_varint(&mut rest).ok_or_else(|| corrupt("Truncated bit position"))?;
+            let pos = next
+                .checked_add(gap)
+                .filter(|&pos| pos < m as u64)
+                .ok_or_else(|| corrupt("Bit position out of range"))?;
+            bits[(pos / 64) as usize] |= 1u64 << (pos % 64);
+            next = pos + 1;
         }
 
         Ok(Self { bits, k, m, n })
@@ -273,6 +562,160 @@ impl BloomFilter {
             *a |= *b;
         }
     }
+
+    /// Returns a filter whose bits are the AND of both filters
+    ///
+    /// Keys inserted into both filters are always reported as present, so
+    /// the result approximates the intersection with no false negatives.
+    /// It is not the filter that inserting only the common keys would give:
+    /// a key in neither set, or in just one, also tests positive when each
+    /// of its bits was set in both filters by different keys. The false
+    /// positive rate is therefore at least that of a filter built from the
+    /// intersection, and grows with how full the inputs are.
+    ///
+    /// # Errors
+    /// Returns `IncompatibleSketches` if the filters have different
+    /// parameters
+    pub fn intersect(&self, other: &Self) -> Result<BloomFilter, SketchError> {
+        if self.params() != other.params() {
+            return Err(SketchError::IncompatibleSketches {
+                reason: "Bloom filters have different parameters".to_string(),
+            });
+        }
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .map(|(a, b)| a & b)
+            .collect();
+
+        Ok(BloomFilter { bits, ..*self })
+    }
+
+    /// Returns a filter whose bits are the OR of both filters
+    ///
+    /// Unlike the intersection, this is exact: the result is bit-for-bit the
+    /// filter that inserting every key of both inputs would give. It is the
+    /// non-mutating, checked counterpart of [`merge`](Self::merge).
+    ///
+    /// # Errors
+    /// Returns `IncompatibleSketches` if the filters have different
+    /// parameters
+    pub fn union(&self, other: &Self) -> Result<BloomFilter, SketchError> {
+        if self.params() != other.params() {
+            return Err(SketchError::IncompatibleSketches {
+                reason: "Bloom filters have different parameters".to_string(),
+            });
+        }
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .map(|(a, b)| a | b)
+            .collect();
+
+        Ok(BloomFilter { bits, ..*self })
+    }
+}
+
+/// `&a & &b` is shorthand for [`a.intersect(&b)`](BloomFilter::intersect)
+///
+/// The result has the same approximation semantics: no false negatives for
+/// keys in both filters, but a false positive rate at least that of a filter
+/// built from the intersection. Prefer `intersect` where the parameters are
+/// not known to match.
+///
+/// # Panics
+/// Panics if the filters have different parameters
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::membership::BloomFilter;
+///
+/// let mut a = BloomFilter::new(100, 0.01);
+/// let mut b = BloomFilter::new(100, 0.01);
+/// a.insert(b"shared");
+/// b.insert(b"shared");
+///
+/// let both = &a & &b;
+/// assert!(both.contains(b"shared"));
+/// ```
+impl std::ops::BitAnd for &BloomFilter {
+    type Output = BloomFilter;
+
+    fn bitand(self, rhs: Self) -> BloomFilter {
+        self.intersect(rhs)
+            .expect("Bloom filters must have same parameters to intersect")
+    }
+}
+
+/// `&a | &b` is shorthand for [`a.union(&b)`](BloomFilter::union)
+///
+/// The union is exact. Prefer `union` where the parameters are not known to
+/// match.
+///
+/// # Panics
+/// Panics if the filters have different parameters
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::membership::BloomFilter;
+///
+/// let mut a = BloomFilter::new(100, 0.01);
+/// let mut b = BloomFilter::new(100, 0.01);
+/// a.insert(b"left");
+/// b.insert(b"right");
+///
+/// let either = &a | &b;
+/// assert!(either.contains(b"left") && either.contains(b"right"));
+/// ```
+impl std::ops::BitOr for &BloomFilter {
+    type Output = BloomFilter;
+
+    fn bitor(self, rhs: Self) -> BloomFilter {
+        self.union(rhs)
+            .expect("Bloom filters must have same parameters to union")
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[i + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn corrupt(msg: &str) -> SketchError {
+    SketchError::DeserializationError(msg.to_string())
+}
+
+/// Rejects serialized parameters no filter built by [`BloomFilter::new`] can have
+fn validate_params(n: u64, m: u64, k: u64) -> Result<(usize, usize, usize), SketchError> {
+    if m == 0 || k == 0 || k > BloomFilter::MAX_HASH_FUNCTIONS as u64 {
+        return Err(SketchError::DeserializationError(format!(
+            "Invalid BloomFilter parameters: {} bits, {} hash functions",
+            m, k
+        )));
+    }
+    match (usize::try_from(n), usize::try_from(m), usize::try_from(k)) {
+        (Ok(n), Ok(m), Ok(k)) => Ok((n, m, k)),
+        _ => Err(corrupt("BloomFilter parameters exceed the address space")),
+    }
 }
 
 impl std::fmt::Debug for BloomFilter {
@@ -305,6 +748,21 @@ mod tests {
         assert!(k > 0, "Number of hash functions should be > 0");
     }
 
+    #[test]
+    fn test_for_estimated() {
+        let mut hll = HyperLogLog::new(12).unwrap();
+        for i in 0..5000u64 {
+            hll.update(&i);
+        }
+
+        let filter = BloomFilter::for_estimated(&hll, 0.01);
+        let (n, _, _) = filter.params();
+        assert!(n >= 5000, "Capacity {} should cover the estimate", n);
+
+        let empty = HyperLogLog::new(12).unwrap();
+        assert_eq!(BloomFilter::for_estimated(&empty, 0.01).params().0, 1);
+    }
+
     #[test]
     fn test_insert_and_contains() {
         let mut filter = BloomFilter::new(100, 0.01);
@@ -339,6 +797,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_current_false_positive_rate_tracks_fill() {
+        let mut filter = BloomFilter::new(1000, 0.01);
+        let design = filter.false_positive_rate();
+        assert!((design - 0.01).abs() < 0.005, "design fpr {}", design);
+        assert!(!filter.is_saturated(0.01));
+
+        for i in 0..1000u32 {
+            filter.insert(&i.to_le_bytes());
+        }
+        let at_capacity = filter.current_false_positive_rate();
+        assert!(at_capacity < 0.02, "fpr at capacity {}", at_capacity);
+        assert!(!filter.is_saturated(0.02));
+
+        for i in 1000..3000u32 {
+            filter.insert(&i.to_le_bytes());
+        }
+        assert!(filter.current_false_positive_rate() > 0.1);
+        assert!(filter.is_saturated(0.02));
+        assert_eq!(filter.false_positive_rate(), design);
+    }
+
     #[test]
     fn test_false_positive_rate() {
         let mut filter = BloomFilter::new(1000, 0.01);
@@ -405,6 +885,39 @@ mod tests {
         assert!(!deserialized.contains(b"key4"));
     }
 
+    #[test]
+    fn test_compressed_serialization_sparse() {
+        let mut filter = BloomFilter::new(10_000, 0.01);
+        for i in 0..500 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+
+        let plain = filter.to_bytes();
+        let compressed = filter.to_bytes_compressed();
+        assert!(
+            compressed.len() * 2 < plain.len(),
+            "compressed {} vs plain {}",
+            compressed.len(),
+            plain.len()
+        );
+
+        let restored = BloomFilter::from_bytes(&compressed).unwrap();
+        assert_eq!(filter.params(), restored.params());
+        assert_eq!(filter.to_bytes(), restored.to_bytes());
+        for i in 0..500 {
+            assert!(restored.contains(format!("key{}", i).as_bytes()));
+        }
+
+        let empty = BloomFilter::new(100, 0.01);
+        let restored = BloomFilter::from_bytes(&empty.to_bytes_compressed()).unwrap();
+        assert!(restored.is_empty());
+        assert_eq!(empty.params(), restored.params());
+
+        let mut truncated = compressed.clone();
+        truncated.push(0x80);
+        assert!(BloomFilter::from_bytes(&truncated).is_err());
+    }
+
     #[test]
     fn test_serialization_empty() {
         let filter = BloomFilter::new(100, 0.01);
@@ -415,6 +928,73 @@ mod tests {
         assert!(!deserialized.contains(b"any_key"));
     }
 
+    #[test]
+    fn test_from_bytes_rejects_degenerate_params() {
+        fn header(n: u64, m: u64, k: u64) -> Vec<u8> {
+            [n, m, k].iter().flat_map(|v| v.to_le_bytes()).collect()
+        }
+        let is_corrupt = |bytes: &[u8]| {
+            matches!(
+                BloomFilter::from_bytes(bytes),
+                Err(SketchError::DeserializationError(_))
+            )
+        };
+
+        // Zero bits, zero hashes
+        assert!(is_corrupt(&header(100, 0, 7)));
+        let mut zero_hashes = header(100, 64, 0);
+        zero_hashes.extend_from_slice(&[0; 8]);
+        assert!(is_corrupt(&zero_hashes));
+
+        // A bit count whose byte size overflows
+        assert!(is_corrupt(&header(100, u64::MAX, 7)));
+
+        // The same checks apply to the compressed layout
+        let mut compressed = BloomFilter::new(100, 0.01).to_bytes_compressed();
+        compressed[HEADER_LEN + 16..HEADER_LEN + 24].fill(0);
+        assert!(is_corrupt(&compressed));
+
+        // Too many hash functions, in either layout
+        let mut many_hashes = header(100, 64, BloomFilter::MAX_HASH_FUNCTIONS as u64 + 1);
+        many_hashes.extend_from_slice(&[0; 8]);
+        assert!(is_corrupt(&many_hashes));
+        let mut compressed = BloomFilter::new(100, 0.01).to_bytes_compressed();
+        compressed[HEADER_LEN + 16..HEADER_LEN + 24]
+            .copy_from_slice(&(BloomFilter::MAX_HASH_FUNCTIONS as u64 + 1).to_le_bytes());
+        assert!(is_corrupt(&compressed));
+
+        // A compressed header claiming a huge bit array is rejected before
+        // allocating it, since the empty payload cannot bound `m`
+        let mut compressed = BloomFilter::new(100, 0.01).to_bytes_compressed();
+        compressed[HEADER_LEN + 8..HEADER_LEN + 16].copy_from_slice(&(1u64 << 60).to_le_bytes());
+        assert!(is_corrupt(&compressed));
+    }
+
+    #[test]
+    fn test_hash_functions_are_capped() {
+        let filter = BloomFilter::new(10, 1e-300);
+        assert_eq!(filter.params().2, BloomFilter::MAX_HASH_FUNCTIONS);
+        let restored = BloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored.params(), filter.params());
+
+        let filter = BloomFilter::with_params(10, 1000, 100);
+        assert_eq!(filter.params().2, BloomFilter::MAX_HASH_FUNCTIONS);
+    }
+
+    #[test]
+    fn test_plain_filter_starting_with_compressed_magic() {
+        // n = 0x5A4D4C42 serializes as "BLMZ" followed by zero bytes
+        let n = u32::from_le_bytes(*COMPRESSED_MAGIC) as usize;
+        let mut filter = BloomFilter::with_params(n, 64, 3);
+        filter.insert(b"key");
+
+        let bytes = filter.to_bytes();
+        assert_eq!(&bytes[..4], COMPRESSED_MAGIC);
+        let restored = BloomFilter::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.params(), (n, 64, 3));
+        assert!(restored.contains(b"key"));
+    }
+
     #[test]
     fn test_with_params() {
         let filter = BloomFilter::with_params(1000, 10000, 7);
@@ -457,6 +1037,168 @@ mod tests {
         assert_eq!(memory, filter.bits.len() * 8);
     }
 
+    #[test]
+    fn test_hash_indices_match_bits() {
+        let mut filter = BloomFilter::with_params(100, 1000, 5);
+        filter.insert(b"alpha");
+
+        let indices = filter.hash_indices(b"alpha");
+        assert_eq!(indices.len(), filter.num_hashes());
+        assert!(indices.iter().all(|&i| i < filter.num_bits()));
+
+        let bytes = filter.as_bits();
+        assert_eq!(bytes.len(), 1000usize.div_ceil(64) * 8);
+        let set: usize = bytes.iter().map(|b| b.count_ones() as usize).sum();
+        assert_eq!(set, filter.count_bits());
+        for &i in &indices {
+            assert_ne!(bytes[i / 8] & (1 << (i % 8)), 0);
+        }
+
+        // External querying agrees with contains
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            let external = filter
+                .hash_indices(key)
+                .iter()
+                .all(|&i| bytes[i / 8] & (1 << (i % 8)) != 0);
+            assert_eq!(external, filter.contains(key));
+        }
+    }
+
+    #[test]
+    fn test_insert_hash_follows_documented_scheme() {
+        let mut filter = BloomFilter::with_params(100, 1000, 5);
+        let mut by_scheme = BloomFilter::with_params(100, 1000, 5);
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            let hash = xxh64(key, 0);
+            filter.insert_hash(hash);
+
+            // Same double hashing as insert, with h2 mixed from the one hash
+            let h2 = mix64(hash);
+            for i in 0..5 {
+                let bit = BloomFilter::bit_index(hash, h2, i, 1000);
+                by_scheme.bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        assert_eq!(filter.bits, by_scheme.bits);
+
+        // Hashing once feeds several filters identically
+        let mut other = BloomFilter::with_params(100, 1000, 5);
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            other.insert_hash(xxh64(key, 0));
+        }
+        assert_eq!(other.bits, filter.bits);
+        assert!(filter.contains_hash(xxh64(b"beta", 0)));
+        assert!(!filter.contains_hash(xxh64(b"delta", 0)));
+    }
+
+    #[test]
+    fn test_reads_filters_serialized_by_earlier_versions() {
+        // with_params(4, 128, 3) holding alpha, beta and gamma, as written
+        // by 0.1.x
+        let bytes = hex_bytes(
+            "0400000000000000800000000000000003000000000000000000000220000008\
+             0014000108000104",
+        );
+        let filter = BloomFilter::from_bytes(&bytes).unwrap();
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            assert!(filter.contains(key));
+        }
+
+        let mut rebuilt = BloomFilter::with_params(4, 128, 3);
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            rebuilt.insert(key);
+        }
+        assert_eq!(rebuilt.to_bytes(), bytes);
+    }
+
+    fn hex_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_intersect() {
+        let mut shard_a = BloomFilter::new(1000, 0.01);
+        let mut shard_b = BloomFilter::new(1000, 0.01);
+        for i in 0..500u32 {
+            shard_a.insert(&i.to_le_bytes());
+        }
+        for i in 250..750u32 {
+            shard_b.insert(&i.to_le_bytes());
+        }
+
+        let both = shard_a.intersect(&shard_b).unwrap();
+        assert_eq!(both.params(), shard_a.params());
+
+        // Shared keys are never lost
+        for i in 250..500u32 {
+            assert!(both.contains(&i.to_le_bytes()));
+        }
+
+        // Keys in only one shard mostly drop out
+        let leaked = (0..250u32)
+            .chain(500..750)
+            .filter(|i| both.contains(&i.to_le_bytes()))
+            .count();
+        assert!(leaked < 50, "{} one-sided keys still present", leaked);
+        assert!(both.count_bits() <= shard_a.count_bits().min(shard_b.count_bits()));
+    }
+
+    #[test]
+    fn test_intersect_incompatible() {
+        let a = BloomFilter::new(1000, 0.01);
+        let b = BloomFilter::new(2000, 0.01);
+        assert!(a.intersect(&b).is_err());
+    }
+
+    #[test]
+    fn test_set_operators() {
+        let mut a = BloomFilter::new(1000, 0.01);
+        let mut b = BloomFilter::new(1000, 0.01);
+        for i in 0..500u32 {
+            a.insert(&i.to_le_bytes());
+        }
+        for i in 250..750u32 {
+            b.insert(&i.to_le_bytes());
+        }
+
+        let both = &a & &b;
+        assert_eq!(both.as_bits(), a.intersect(&b).unwrap().as_bits());
+
+        let either = &a | &b;
+        assert_eq!(either.as_bits(), a.union(&b).unwrap().as_bits());
+        let mut merged = a.clone();
+        merged.merge(&b);
+        assert_eq!(either.as_bits(), merged.as_bits());
+        for i in 0..750u32 {
+            assert!(either.contains(&i.to_le_bytes()));
+        }
+
+        // Operators compose like the set algebra they approximate
+        let mut c = BloomFilter::new(1000, 0.01);
+        c.insert(&1000u32.to_le_bytes());
+        let combined = &(&a & &b) | &c;
+        assert!(combined.contains(&300u32.to_le_bytes()));
+        assert!(combined.contains(&1000u32.to_le_bytes()));
+    }
+
+    #[test]
+    #[should_panic(expected = "same parameters to union")]
+    fn test_bitor_incompatible_panics() {
+        let a = BloomFilter::new(1000, 0.01);
+        let b = BloomFilter::new(2000, 0.01);
+        let _ = &a | &b;
+    }
+
+    #[test]
+    fn test_union_incompatible() {
+        let a = BloomFilter::new(1000, 0.01);
+        let b = BloomFilter::new(2000, 0.01);
+        assert!(a.union(&b).is_err());
+    }
+
     #[test]
     fn test_count_bits() {
         let mut filter = BloomFilter::new(100, 0.01);
@@ -56,6 +56,11 @@ use xxhash_rust::xxh64::xxh64;
 /// Supports insertions, deletions, and membership queries.
 /// Uses 4x more memory than standard Bloom filter but enables deletions.
 ///
+/// # Thread Safety
+///
+/// `CountingBloomFilter` is `Send + Sync`. Inserts and removals take
+/// `&mut self`; lookups take `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -280,6 +285,33 @@ impl CountingBloomFilter {
         true
     }
 
+    /// Checks if an element might have been inserted at least `k` times
+    ///
+    /// Returns whether the minimum counter across the element's hash
+    /// positions is at least `k`, i.e. whether
+    /// [`count_estimate`](Self::count_estimate) is `>= k`.
+    ///
+    /// Like [`contains`](Self::contains), this can return false positives:
+    /// other elements hashing to the same counters inflate them, so the count
+    /// may be overstated. Counters saturate at 15, so this is always `false`
+    /// for `k > 15`. A `k` of 0 is always `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CountingBloomFilter;
+    ///
+    /// let mut filter = CountingBloomFilter::new(100, 0.01);
+    /// for _ in 0..3 {
+    ///     filter.insert(b"hello");
+    /// }
+    /// assert!(filter.contains_at_least(b"hello", 3));
+    /// assert!(!filter.contains_at_least(b"hello", 4)); // Probably false
+    /// ```
+    pub fn contains_at_least(&self, key: &[u8], k: u8) -> bool {
+        (0..self.k).all(|i| self.get_counter(self.hash(key, i)) >= k)
+    }
+
     /// Returns the approximate count for an element
     ///
     /// This is the minimum counter value among all hash positions.
@@ -418,6 +450,12 @@ impl CountingBloomFilter {
         let m = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
         let k = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
         let n = u64::from_le_bytes(bytes[16..24].try_into().unwrap()) as usize;
+        if m == 0 || k == 0 {
+            return Err(SketchError::DeserializationError(format!(
+                "Invalid CountingBloomFilter parameters: {} counters, {} hash functions",
+                m, k
+            )));
+        }
 
         let expected_len = 24 + m.div_ceil(2);
         if bytes.len() < expected_len {
@@ -443,7 +481,7 @@ impl CountingBloomFilter {
                 count += 1;
             }
         }
-        count /= k.max(1);
+        count /= k;
 
         Ok(CountingBloomFilter {
             counters,
@@ -531,6 +569,25 @@ mod tests {
         assert!(count >= 1, "Count should be at least 1");
     }
 
+    #[test]
+    fn test_contains_at_least() {
+        let mut filter = CountingBloomFilter::new(1000, 0.01);
+        for _ in 0..5 {
+            filter.insert(b"key");
+        }
+        filter.insert(b"other");
+
+        assert!(filter.contains_at_least(b"key", 0));
+        assert!(filter.contains_at_least(b"key", 3));
+        assert!(filter.contains_at_least(b"key", 5));
+        assert!(!filter.contains_at_least(b"key", 6));
+        assert!(!filter.contains_at_least(b"other", 2));
+        assert!(!filter.contains_at_least(b"key", 16));
+
+        filter.remove(b"key");
+        assert!(!filter.contains_at_least(b"key", 5));
+    }
+
     #[test]
     fn test_serialization() {
         let mut filter = CountingBloomFilter::new(100, 0.01);
@@ -545,6 +602,28 @@ mod tests {
         assert!(!restored.contains(b"key3"));
     }
 
+    #[test]
+    fn test_from_bytes_rejects_degenerate_params() {
+        fn header(m: u64, k: u64, n: u64) -> Vec<u8> {
+            [m, k, n].iter().flat_map(|v| v.to_le_bytes()).collect()
+        }
+        let is_corrupt = |bytes: &[u8]| {
+            matches!(
+                CountingBloomFilter::from_bytes(bytes),
+                Err(SketchError::DeserializationError(_))
+            )
+        };
+
+        // Zero counters, zero hashes
+        assert!(is_corrupt(&header(0, 7, 100)));
+        let mut zero_hashes = header(16, 0, 100);
+        zero_hashes.extend_from_slice(&[0; 8]);
+        assert!(is_corrupt(&zero_hashes));
+
+        // More counters than the payload holds
+        assert!(is_corrupt(&header(u64::MAX, 7, 100)));
+    }
+
     #[test]
     fn test_clear() {
         let mut filter = CountingBloomFilter::new(100, 0.01);
@@ -0,0 +1,464 @@
+//! Counting Fuse Filter: a Binary Fuse filter that supports deletes
+//!
+//! Queries use the same structure as [`BinaryFuseFilter`]: three slots, one per
+//! segment, whose 8-bit fingerprints XOR to the key's fingerprint. The
+//! difference is a per-slot sidecar in the style of an IBLT cell (see
+//! [`RatelessIBLT`]): a 4-bit count of the keys mapped to the slot and the XOR
+//! of their 64-bit key tags.
+//!
+//! # Updates
+//!
+//! Inserting or deleting a key adds or reverses its XOR contribution in the
+//! sidecar of its three slots. Peeling the sidecar then recovers every live tag
+//! without storing the original keys, and the fingerprint array is re-solved
+//! from them. If the new key set cannot be peeled, the filter is rebuilt with a
+//! fresh seed and, if needed, more slots.
+//!
+//! # Limitations
+//!
+//! - `insert` and `delete` are O(slots) because they re-solve the fingerprint
+//!   array. This suits occasional updates, not high-rate churn.
+//! - Each slot takes 9.5 bytes: the 1-byte fingerprint, a half-byte count and
+//!   the 8-byte tag XOR. At 1.23 slots per key that is about 11.7 bytes per
+//!   key, against about 1.23 for an 8-bit [`BinaryFuseFilter`]. Only
+//!   `contains` keeps fuse-level compactness and cache behaviour.
+//! - The table is sized when the filter is (re)built. After heavy deletion
+//!   churn it stays sized for the old population, and [`needs_rebuild`]
+//!   reports when [`rebuild`] would reclaim the space.
+//! - Deleting a key that was never inserted is a no-op. Unlike a counting
+//!   Bloom filter, this cannot corrupt the filter.
+//!
+//! [`BinaryFuseFilter`]: crate::membership::BinaryFuseFilter
+//! [`RatelessIBLT`]: crate::reconciliation::RatelessIBLT
+//! [`needs_rebuild`]: CountingFuseFilter::needs_rebuild
+//! [`rebuild`]: CountingFuseFilter::rebuild
+
+use crate::common::hash::{mix64, xxhash};
+use crate::common::SketchError;
+
+/// Slots per key, as for Binary Fuse construction
+const SLOT_FACTOR: f64 = 1.23;
+
+/// Seeds tried before the table is grown
+const MAX_SEED_ATTEMPTS: u64 = 100;
+
+/// Largest per-slot count; counts are packed two to a byte
+const MAX_COUNT: u8 = 0x0F;
+
+/// Deletable Binary Fuse-style filter for `u64` keys
+///
+/// # Thread Safety
+///
+/// `CountingFuseFilter` is `Send + Sync`. Inserts, deletes and rebuilds take
+/// `&mut self`; lookups take `&self`.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::membership::CountingFuseFilter;
+///
+/// let mut filter = CountingFuseFilter::from_items([1u64, 2, 3]).unwrap();
+/// assert!(filter.contains(&2));
+///
+/// assert!(filter.delete(&2).unwrap());
+/// assert!(filter.insert(&4).unwrap());
+/// assert!(filter.contains(&4));
+/// assert_eq!(filter.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CountingFuseFilter {
+    /// Seed mixed into slot placement
+    seed: u64,
+    /// Length of each of the 3 segments
+    segment_length: u32,
+    /// Solved fingerprint array used by queries
+    fingerprints: Vec<u8>,
+    /// Number of live keys mapped to each slot, two 4-bit counts per byte
+    counts: Vec<u8>,
+    /// XOR of the tags of the live keys mapped to each slot
+    tag_sums: Vec<u64>,
+    /// Number of live keys
+    len: usize,
+    /// Number of keys the table was sized for
+    capacity: usize,
+}
+
+impl CountingFuseFilter {
+    /// Creates an empty filter sized for `capacity` keys
+    ///
+    /// The filter grows automatically when inserts exceed the capacity.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if capacity is 0
+    pub fn new(capacity: usize) -> Result<Self, SketchError> {
+        if capacity == 0 {
+            return Err(SketchError::InvalidParameter {
+                param: "capacity".to_string(),
+                value: capacity.to_string(),
+                constraint: "must be > 0".to_string(),
+            });
+        }
+        Self::build(&[], capacity)
+    }
+
+    /// Builds a filter containing the given keys
+    ///
+    /// Duplicate keys are stored once.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if no seed yields a solvable table, which
+    /// is practically impossible.
+    pub fn from_items<I>(items: I) -> Result<Self, SketchError>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        let mut tags: Vec<u64> = items.into_iter().map(Self::tag).collect();
+        tags.sort_unstable();
+        tags.dedup();
+        let capacity = tags.len().max(1);
+        Self::build(&tags, capacity)
+    }
+
+    /// Checks if a key might be in the set
+    ///
+    /// # Returns
+    /// - `true`: Key is probably in the set (might be false positive)
+    /// - `false`: Key is definitely NOT in the set
+    pub fn contains(&self, item: &u64) -> bool {
+        if self.len == 0 {
+            return false;
+        }
+        let tag = Self::tag(*item);
+        let [h0, h1, h2] = Self::slots(tag, self.seed, self.segment_length);
+        self.fingerprints[h0] ^ self.fingerprints[h1] ^ self.fingerprints[h2]
+            == Self::fingerprint(tag)
+    }
+
+    /// Inserts a key
+    ///
+    /// Returns `Ok(false)` if the key was already present.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the grown table cannot be solved either
+    pub fn insert(&mut self, item: &u64) -> Result<bool, SketchError> {
+        let tag = Self::tag(*item);
+        let mut tags = self.live_tags();
+        if tags.contains(&tag) {
+            return Ok(false);
+        }
+
+        if self.toggle(tag, true) {
+            if let Some(fingerprints) = self.solve() {
+                self.fingerprints = fingerprints;
+                self.len += 1;
+                return Ok(true);
+            }
+            self.toggle(tag, false);
+        }
+
+        // The slot graph is no longer peelable: re-seed, growing if needed
+        tags.push(tag);
+        let capacity = self.capacity.max(tags.len());
+        *self = Self::build(&tags, capacity)?;
+        Ok(true)
+    }
+
+    /// Deletes a key by reversing its contribution to its three slots
+    ///
+    /// Returns `Ok(false)` (and leaves the filter unchanged) if the key is not
+    /// present. Keys that merely collide with a false positive are never
+    /// removed, since presence is checked against the recovered key tags.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the remaining keys have to be rebuilt
+    /// into a new table and no seed solves it
+    pub fn delete(&mut self, item: &u64) -> Result<bool, SketchError> {
+        let mut tags = self.live_tags();
+        let Some(idx) = tags.iter().position(|&t| t == Self::tag(*item)) else {
+            return Ok(false);
+        };
+
+        let tag = tags.swap_remove(idx);
+        self.toggle(tag, false);
+        if let Some(fingerprints) = self.solve() {
+            self.fingerprints = fingerprints;
+            self.len -= 1;
+            return Ok(true);
+        }
+
+        // Not peelable after all: rebuild the remaining keys in place
+        *self = Self::build(&tags, self.capacity)?;
+        Ok(true)
+    }
+
+    /// Returns true when the table is badly sized for the live keys
+    ///
+    /// This happens after heavy deletion churn leaves fewer than a quarter of
+    /// the keys the table was sized for. [`rebuild`](Self::rebuild) shrinks it.
+    pub fn needs_rebuild(&self) -> bool {
+        self.len > self.capacity || self.len < self.capacity / 4
+    }
+
+    /// Rebuilds the table for the current live keys
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if no seed yields a solvable table
+    pub fn rebuild(&mut self) -> Result<(), SketchError> {
+        let tags = self.live_tags();
+        *self = Self::build(&tags, tags.len().max(1))?;
+        Ok(())
+    }
+
+    /// Returns the number of live keys
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the filter holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys the table is currently sized for
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Estimated false positive rate for 8-bit fingerprints
+    pub fn estimated_fpr(&self) -> f64 {
+        2.0_f64.powi(-8)
+    }
+
+    /// Builds a table for distinct tags, trying seeds and then growing
+    fn build(tags: &[u64], capacity: usize) -> Result<Self, SketchError> {
+        let mut capacity = capacity;
+        // Grow geometrically; a few rounds always suffice in practice
+        for _ in 0..8 {
+            let segment_length = ((capacity as f64 * SLOT_FACTOR / 3.0).ceil() as u32).max(4);
+            let array_length = segment_length as usize * 3;
+
+            for seed in 0..MAX_SEED_ATTEMPTS {
+                let mut filter = Self {
+                    seed,
+                    segment_length,
+                    fingerprints: vec![0u8; array_length],
+                    counts: vec![0u8; array_length.div_ceil(2)],
+                    tag_sums: vec![0u64; array_length],
+                    len: tags.len(),
+                    capacity,
+                };
+                if !tags.iter().all(|&tag| filter.toggle(tag, true)) {
+                    continue;
+                }
+                if let Some(fingerprints) = filter.solve() {
+                    filter.fingerprints = fingerprints;
+                    return Ok(filter);
+                }
+            }
+
+            capacity = capacity + capacity / 2 + 1;
+        }
+
+        Err(SketchError::InvalidParameter {
+            param: "capacity".to_string(),
+            value: capacity.to_string(),
+            constraint: format!("no seed yields a solvable table for {} keys", tags.len()),
+        })
+    }
+
+    /// Adds (`add = true`) or removes a tag's contribution to its slots
+    ///
+    /// Returns false, leaving the filter unchanged, if a count would
+    /// overflow.
+    fn toggle(&mut self, tag: u64, add: bool) -> bool {
+        let slots = Self::slots(tag, self.seed, self.segment_length);
+        if add && slots.iter().any(|&s| self.count(s) == MAX_COUNT) {
+            return false;
+        }
+        for s in slots {
+            let count = self.count(s);
+            self.set_count(s, if add { count + 1 } else { count - 1 });
+            self.tag_sums[s] ^= tag;
+        }
+        true
+    }
+
+    /// Count of live keys mapped to a slot
+    #[inline]
+    fn count(&self, slot: usize) -> u8 {
+        (self.counts[slot / 2] >> (4 * (slot % 2))) & MAX_COUNT
+    }
+
+    #[inline]
+    fn set_count(&mut self, slot: usize, count: u8) {
+        let shift = 4 * (slot % 2);
+        let byte = &mut self.counts[slot / 2];
+        *byte = (*byte & !(MAX_COUNT << shift)) | (count << shift);
+    }
+
+    /// Peels the sidecar, returning each tag with the slot it was alone in
+    ///
+    /// Returns `None` if some slots could not be peeled.
+    fn peel(&self) -> Option<Vec<(u64, usize)>> {
+        let mut counts: Vec<u8> = (0..self.tag_sums.len()).map(|s| self.count(s)).collect();
+        let mut tag_sums = self.tag_sums.clone();
+        let mut alone: Vec<usize> = (0..counts.len()).filter(|&i| counts[i] == 1).collect();
+        let mut stack = Vec::with_capacity(self.len);
+
+        while let Some(pos) = alone.pop() {
+            if counts[pos] != 1 {
+                continue; // Already processed
+            }
+            let tag = tag_sums[pos];
+            let slots = Self::slots(tag, self.seed, self.segment_length);
+            if !slots.contains(&pos) {
+                return None;
+            }
+            stack.push((tag, pos));
+            for s in slots {
+                counts[s] -= 1;
+                tag_sums[s] ^= tag;
+                if counts[s] == 1 {
+                    alone.push(s);
+                }
+            }
+        }
+
+        if counts.iter().any(|&c| c != 0) {
+            return None;
+        }
+        Some(stack)
+    }
+
+    /// Solves the fingerprint array by backward substitution over the peel
+    fn solve(&self) -> Option<Vec<u8>> {
+        let stack = self.peel()?;
+        let mut fingerprints = vec![0u8; self.tag_sums.len()];
+        for &(tag, alone) in stack.iter().rev() {
+            let [h0, h1, h2] = Self::slots(tag, self.seed, self.segment_length);
+            // fp[alone] = target XOR the other two slots (fp[alone] is still 0)
+            fingerprints[alone] =
+                Self::fingerprint(tag) ^ fingerprints[h0] ^ fingerprints[h1] ^ fingerprints[h2];
+        }
+        Some(fingerprints)
+    }
+
+    /// Tags of all live keys, recovered by peeling
+    fn live_tags(&self) -> Vec<u64> {
+        self.peel()
+            .expect("filter sidecar is always peelable")
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect()
+    }
+
+    /// 64-bit tag identifying a key
+    fn tag(item: u64) -> u64 {
+        xxhash(&item.to_le_bytes(), 0)
+    }
+
+    /// One slot per segment, derived from the tag and seed
+    fn slots(tag: u64, seed: u64, segment_length: u32) -> [usize; 3] {
+        let segment_length = segment_length as u64;
+        let mut h = tag ^ seed.wrapping_mul(0x9E3779B97F4A7C15);
+        let mut slots = [0usize; 3];
+        for (i, slot) in slots.iter_mut().enumerate() {
+            // SplitMix64 step
+            h = h.wrapping_add(0x9E3779B97F4A7C15);
+            let z = mix64(h);
+            *slot = (i as u64 * segment_length + z % segment_length) as usize;
+        }
+        slots
+    }
+
+    /// 8-bit fingerprint of a tag, independent of the seed
+    fn fingerprint(tag: u64) -> u8 {
+        (tag >> 56) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_false_negatives_after_updates() {
+        let mut filter = CountingFuseFilter::from_items(0..1000u64).unwrap();
+        for i in 0..1000u64 {
+            assert!(filter.contains(&i));
+        }
+
+        for i in (0..1000u64).step_by(2) {
+            assert!(filter.delete(&i).unwrap());
+        }
+        for i in 1000..1500u64 {
+            assert!(filter.insert(&i).unwrap());
+        }
+
+        assert_eq!(filter.len(), 1000);
+        for i in (1..1000u64).step_by(2).chain(1000..1500) {
+            assert!(filter.contains(&i), "false negative for {}", i);
+        }
+    }
+
+    #[test]
+    fn test_deleted_keys_mostly_absent() {
+        let mut filter = CountingFuseFilter::from_items(0..2000u64).unwrap();
+        for i in 0..1000u64 {
+            filter.delete(&i).unwrap();
+        }
+        let still_present = (0..1000u64).filter(|i| filter.contains(i)).count();
+        // 8-bit fingerprints: ~0.4% false positives
+        assert!(
+            still_present < 20,
+            "{} deleted keys still present",
+            still_present
+        );
+    }
+
+    #[test]
+    fn test_duplicate_and_missing_keys() {
+        let mut filter = CountingFuseFilter::new(16).unwrap();
+        assert!(filter.insert(&7).unwrap());
+        assert!(!filter.insert(&7).unwrap());
+        assert_eq!(filter.len(), 1);
+
+        assert!(!filter.delete(&8).unwrap());
+        assert!(filter.delete(&7).unwrap());
+        assert!(!filter.delete(&7).unwrap());
+        assert!(filter.is_empty());
+        assert!(!filter.contains(&7));
+    }
+
+    #[test]
+    fn test_grows_past_capacity() {
+        let mut filter = CountingFuseFilter::new(4).unwrap();
+        for i in 0..200u64 {
+            filter.insert(&i).unwrap();
+        }
+        assert_eq!(filter.len(), 200);
+        assert!(filter.capacity() > 4);
+        assert!((0..200u64).all(|i| filter.contains(&i)));
+    }
+
+    #[test]
+    fn test_needs_rebuild_after_churn() {
+        let mut filter = CountingFuseFilter::from_items(0..400u64).unwrap();
+        assert!(!filter.needs_rebuild());
+
+        for i in 0..350u64 {
+            filter.delete(&i).unwrap();
+        }
+        assert!(filter.needs_rebuild());
+
+        filter.rebuild().unwrap();
+        assert!(!filter.needs_rebuild());
+        assert_eq!(filter.capacity(), 50);
+        assert!((350..400u64).all(|i| filter.contains(&i)));
+    }
+
+    #[test]
+    fn test_invalid_capacity() {
+        assert!(CountingFuseFilter::new(0).is_err());
+    }
+}
@@ -50,6 +50,8 @@
 //! assert!(!filter.contains(b"key1"));
 //! ```
 
+use crate::cardinality::HyperLogLog;
+use crate::common::hash::mix64;
 use crate::common::SketchError;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
@@ -61,6 +63,12 @@ const BUCKET_SIZE: usize = 4;
 /// Maximum number of relocations before giving up
 const MAX_KICKS: usize = 500;
 
+/// Fingerprint width used by [`CuckooFilter::new`] and the widest supported
+const MAX_FINGERPRINT_BITS: u8 = 16;
+
+/// Narrowest fingerprint width [`CuckooFilter::with_fpr`] can choose
+const MIN_FINGERPRINT_BITS: u8 = 3;
+
 /// A bucket containing fingerprints
 #[derive(Clone, Debug)]
 struct Bucket {
@@ -116,6 +124,11 @@ impl Bucket {
 ///
 /// Space-efficient alternative to Counting Bloom filters.
 ///
+/// # Thread Safety
+///
+/// `CuckooFilter` is `Send + Sync`. The eviction RNG is owned by the filter,
+/// which is why inserts take `&mut self`.
+///
 /// # Examples
 ///
 /// ```
@@ -136,6 +149,8 @@ pub struct CuckooFilter {
     num_buckets: usize,
     /// Number of items stored
     count: usize,
+    /// Fingerprint width in bits (fingerprints are stored in 16-bit slots)
+    fingerprint_bits: u8,
     /// Random number generator for kicks
     rng: SmallRng,
 }
@@ -163,8 +178,94 @@ impl CuckooFilter {
         Self::with_seed(capacity, 0x12345678)
     }
 
+    /// Creates a Cuckoo Filter sized from a HyperLogLog cardinality estimate
+    ///
+    /// The capacity is `hll.estimate()` inflated by three standard errors
+    /// (`1 + 3 * hll.standard_error()`) and rounded up, so inserts only fail
+    /// if the true cardinality exceeds the estimate by more than ~3 sigma.
+    /// The fingerprint width is chosen for `fpr` as in [`with_fpr`](Self::with_fpr).
+    ///
+    /// # Arguments
+    ///
+    /// * `hll` - HyperLogLog that has seen the stream the filter will hold
+    /// * `fpr` - Desired false positive rate
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `fpr` is not in (0, 1) or is below
+    /// 2^-13, as for [`with_fpr`](Self::with_fpr)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::cardinality::HyperLogLog;
+    /// use sketch_oxide::membership::CuckooFilter;
+    ///
+    /// let mut hll = HyperLogLog::new(12).unwrap();
+    /// for i in 0..10_000u64 {
+    ///     hll.update(&i);
+    /// }
+    ///
+    /// let filter = CuckooFilter::for_estimated(&hll, 0.01).unwrap();
+    /// assert!(filter.capacity() >= 10_000);
+    /// ```
+    pub fn for_estimated(hll: &HyperLogLog, fpr: f64) -> Result<Self, SketchError> {
+        Self::with_fpr(super::capacity_from_estimate(hll), fpr)
+    }
+
+    /// Creates a Cuckoo Filter whose fingerprint width is chosen for a target
+    /// false positive rate
+    ///
+    /// Uses `ceil(log2(1/fpr)) + 2` fingerprint bits, the usual sizing for
+    /// 4-slot buckets: a lookup compares against 8 slots, so the rate is about
+    /// `8 * load / 2^bits`, which is `fpr` at half load and at most `2 * fpr`
+    /// when the filter is full. [`false_positive_rate`](Self::false_positive_rate)
+    /// reports the full-load bound for the chosen width.
+    ///
+    /// Fingerprints are still stored in 16-bit slots, so a narrower width
+    /// does not reduce memory; it only fixes the accuracy to the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Expected number of elements
+    /// * `fpr` - Target false positive rate
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `capacity` is 0, or `fpr` is not in
+    /// (0, 1) or needs more than 15 fingerprint bits (`fpr` < 2^-13); use
+    /// [`new`](Self::new) for the full 16-bit width
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CuckooFilter;
+    ///
+    /// let filter = CuckooFilter::with_fpr(1000, 0.01).unwrap();
+    /// assert_eq!(filter.fingerprint_bits(), 9);
+    /// assert!(filter.false_positive_rate() <= 0.02);
+    /// ```
+    pub fn with_fpr(capacity: usize, fpr: f64) -> Result<Self, SketchError> {
+        let max_bits = (MAX_FINGERPRINT_BITS - 3) as i32;
+        if !(fpr > 0.0 && fpr < 1.0) || fpr < 2f64.powi(-max_bits) {
+            return Err(SketchError::InvalidParameter {
+                param: "fpr".to_string(),
+                value: fpr.to_string(),
+                constraint: format!("must be in [2^-{}, 1.0)", max_bits),
+            });
+        }
+
+        let bits = (-fpr.log2()).ceil() as u8 + 2;
+        Self::build(capacity, 0x12345678, bits.max(MIN_FINGERPRINT_BITS))
+    }
+
     /// Creates a Cuckoo Filter with a specific seed
     pub fn with_seed(capacity: usize, seed: u64) -> Result<Self, SketchError> {
+        Self::build(capacity, seed, MAX_FINGERPRINT_BITS)
+    }
+
+    /// Allocates an empty filter for `capacity` items
+    fn build(capacity: usize, seed: u64, fingerprint_bits: u8) -> Result<Self, SketchError> {
         if capacity == 0 {
             return Err(SketchError::InvalidParameter {
                 param: "capacity".to_string(),
@@ -182,6 +283,7 @@ impl CuckooFilter {
             buckets: vec![Bucket::default(); num_buckets],
             num_buckets,
             count: 0,
+            fingerprint_bits,
             rng: SmallRng::seed_from_u64(seed),
         })
     }
@@ -206,6 +308,37 @@ impl CuckooFilter {
         self.count as f64 / self.capacity() as f64
     }
 
+    /// Returns the fingerprint width in bits
+    pub fn fingerprint_bits(&self) -> u8 {
+        self.fingerprint_bits
+    }
+
+    /// Returns the false positive rate bound at full load for the fingerprint width
+    ///
+    /// A lookup compares the key's fingerprint against the 8 slots of its two
+    /// buckets, so the rate is `1 - (1 - 1/F)^8`, where `F` is the number of
+    /// distinct fingerprints. It is computed from the width alone; a filter
+    /// below full load does better. See
+    /// [`measured_fpr`](Self::measured_fpr) for an empirical rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CuckooFilter;
+    ///
+    /// let filter = CuckooFilter::new(1000).unwrap();
+    /// assert!(filter.false_positive_rate() < 0.0003);
+    /// ```
+    pub fn false_positive_rate(&self) -> f64 {
+        let distinct = if self.fingerprint_bits == MAX_FINGERPRINT_BITS {
+            // The low bit is always set
+            (1u64 << 15) as f64
+        } else {
+            ((1u64 << self.fingerprint_bits) - 1) as f64
+        };
+        1.0 - (1.0 - 1.0 / distinct).powi(2 * BUCKET_SIZE as i32)
+    }
+
     /// Inserts an element into the filter
     ///
     /// # Arguments
@@ -228,7 +361,42 @@ impl CuckooFilter {
     pub fn insert(&mut self, key: &[u8]) -> Result<(), SketchError> {
         let fp = self.fingerprint(key);
         let (i1, i2) = self.bucket_indices(key, fp);
+        self.insert_fingerprint(i1, i2, fp)
+    }
 
+    /// Inserts an element given a 64-bit hash computed by the caller
+    ///
+    /// Lets one hash per key feed several filters. [`insert`](Self::insert)
+    /// takes the primary bucket from `xxh64(key, 0)` and the fingerprint
+    /// from the top bits of `xxh64(key, 0xDEADBEEF)`; here the bucket comes
+    /// from `hash` and the fingerprint from the top bits of
+    /// `splitmix64_finalizer(hash)`. The alternate bucket is derived from
+    /// the fingerprint as usual. A key inserted this way is found by
+    /// [`contains_hash`](Self::contains_hash) with the same hash, not by
+    /// [`contains`](Self::contains).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the filter is full, as for [`insert`](Self::insert).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CuckooFilter;
+    /// use xxhash_rust::xxh64::xxh64;
+    ///
+    /// let mut filter = CuckooFilter::new(100).unwrap();
+    /// let hash = xxh64(b"hello", 0);
+    /// filter.insert_hash(hash).unwrap();
+    /// assert!(filter.contains_hash(hash));
+    /// ```
+    pub fn insert_hash(&mut self, hash: u64) -> Result<(), SketchError> {
+        let (fp, i1, i2) = self.locate(hash);
+        self.insert_fingerprint(i1, i2, fp)
+    }
+
+    /// Places a fingerprint in one of its two candidate buckets
+    fn insert_fingerprint(&mut self, i1: usize, i2: usize, fp: u16) -> Result<(), SketchError> {
         // Try inserting into either bucket
         if self.buckets[i1].insert(fp) {
             self.count += 1;
@@ -260,6 +428,104 @@ impl CuckooFilter {
         })
     }
 
+    /// Adds every fingerprint stored in `other` to this filter
+    ///
+    /// Afterwards `contains` reports every key present in either filter. Keys
+    /// are not stored, so fingerprints are re-inserted from the bucket they
+    /// occupy. A fingerprint's bucket only determines the low bits of its
+    /// key's hash, so the union can fold onto fewer buckets but never grow:
+    /// when `other` has fewer buckets, this filter is rebuilt with `other`'s
+    /// bucket count first. Both filters must use the same fingerprint width.
+    ///
+    /// The union is built separately and only replaces this filter on
+    /// success.
+    ///
+    /// # Errors
+    ///
+    /// - `IncompatibleSketches` if the fingerprint widths differ, or one
+    ///   bucket count is not a multiple of the other (only possible for
+    ///   deserialized filters)
+    /// - `InvalidParameter` if the combined fingerprints do not fit in the
+    ///   smaller layout
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CuckooFilter;
+    ///
+    /// let mut a = CuckooFilter::new(1000).unwrap();
+    /// let mut b = CuckooFilter::new(1000).unwrap();
+    /// a.insert(b"left").unwrap();
+    /// b.insert(b"right").unwrap();
+    ///
+    /// a.union_into(&b).unwrap();
+    /// assert!(a.contains(b"left") && a.contains(b"right"));
+    /// assert_eq!(a.len(), 2);
+    /// ```
+    pub fn union_into(&mut self, other: &CuckooFilter) -> Result<(), SketchError> {
+        if self.fingerprint_bits != other.fingerprint_bits {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "fingerprint width mismatch: {} vs {} bits",
+                    self.fingerprint_bits, other.fingerprint_bits
+                ),
+            });
+        }
+
+        let num_buckets = self.num_buckets.min(other.num_buckets);
+        if !self
+            .num_buckets
+            .max(other.num_buckets)
+            .is_multiple_of(num_buckets)
+        {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "bucket counts {} and {} cannot be folded onto a common layout",
+                    self.num_buckets, other.num_buckets
+                ),
+            });
+        }
+
+        let mut union = if num_buckets == self.num_buckets {
+            self.clone()
+        } else {
+            let mut folded = CuckooFilter {
+                buckets: vec![Bucket::default(); num_buckets],
+                num_buckets,
+                count: 0,
+                fingerprint_bits: self.fingerprint_bits,
+                rng: self.rng.clone(),
+            };
+            folded.absorb(self)?;
+            folded
+        };
+        union.absorb(other)?;
+
+        *self = union;
+        Ok(())
+    }
+
+    /// Re-inserts all fingerprints of `source`, whose bucket count must be a
+    /// multiple of this filter's
+    fn absorb(&mut self, source: &CuckooFilter) -> Result<(), SketchError> {
+        for (idx, bucket) in source.buckets.iter().enumerate() {
+            for &fp in bucket.fingerprints.iter().filter(|&&fp| fp != 0) {
+                let i1 = idx % self.num_buckets;
+                let i2 = self.alt_index(i1, fp);
+                self.insert_fingerprint(i1, i2, fp)
+                    .map_err(|_| SketchError::InvalidParameter {
+                        param: "other".to_string(),
+                        value: format!("{} items", source.count),
+                        constraint: format!(
+                            "union does not fit in {} slots; filters cannot grow from fingerprints",
+                            self.capacity()
+                        ),
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Checks if an element might be in the filter
     ///
     /// # Arguments
@@ -276,6 +542,39 @@ impl CuckooFilter {
         self.buckets[i1].contains(fp) || self.buckets[i2].contains(fp)
     }
 
+    /// Checks if an element inserted with [`insert_hash`](Self::insert_hash)
+    /// might be in the filter
+    pub fn contains_hash(&self, hash: u64) -> bool {
+        let (fp, i1, i2) = self.locate(hash);
+
+        self.buckets[i1].contains(fp) || self.buckets[i2].contains(fp)
+    }
+
+    /// Measures the FPR on known-absent keys; see [module docs](super#measuring-false-positives)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::CuckooFilter;
+    ///
+    /// let mut filter = CuckooFilter::new(1000).unwrap();
+    /// for i in 0u32..500 {
+    ///     filter.insert(&i.to_le_bytes()).unwrap();
+    /// }
+    ///
+    /// let absent: Vec<[u8; 4]> = (1_000_000u32..1_010_000).map(|i| i.to_le_bytes()).collect();
+    /// let keys: Vec<&[u8]> = absent.iter().map(|k| k.as_slice()).collect();
+    /// assert!(filter.measured_fpr(&keys) < 0.05);
+    /// ```
+    pub fn measured_fpr(&self, absent_keys: &[&[u8]]) -> f64 {
+        if absent_keys.is_empty() {
+            return 0.0;
+        }
+
+        let hits = absent_keys.iter().filter(|key| self.contains(key)).count();
+        hits as f64 / absent_keys.len() as f64
+    }
+
     /// Removes an element from the filter
     ///
     /// # Arguments
@@ -307,15 +606,41 @@ impl CuckooFilter {
     /// Computes the fingerprint for a key
     #[inline]
     fn fingerprint(&self, key: &[u8]) -> u16 {
-        // Use top 16 bits of hash, ensure non-zero
-        let hash = xxh64(key, 0xDEADBEEF);
-        ((hash >> 48) as u16) | 1
+        self.fingerprint_of(xxh64(key, 0xDEADBEEF))
+    }
+
+    /// Computes the fingerprint and both candidate buckets for a
+    /// caller-supplied hash
+    #[inline]
+    fn locate(&self, hash: u64) -> (u16, usize, usize) {
+        let fp = self.fingerprint_of(mix64(hash));
+        let (i1, i2) = self.bucket_indices_of(hash, fp);
+        (fp, i1, i2)
+    }
+
+    /// Reduces a fingerprint hash to the filter's fingerprint width
+    #[inline]
+    fn fingerprint_of(&self, hash: u64) -> u16 {
+        // Use the top bits of the hash, ensure non-zero
+        let top = (hash >> 48) as u16;
+        if self.fingerprint_bits == MAX_FINGERPRINT_BITS {
+            // Full-width filters force the low bit, as they always have, so
+            // previously serialized filters keep matching
+            top | 1
+        } else {
+            (top >> (MAX_FINGERPRINT_BITS - self.fingerprint_bits)).max(1)
+        }
     }
 
     /// Computes the two bucket indices for a key
     #[inline]
     fn bucket_indices(&self, key: &[u8], fp: u16) -> (usize, usize) {
-        let hash = xxh64(key, 0);
+        self.bucket_indices_of(xxh64(key, 0), fp)
+    }
+
+    /// Computes the two bucket indices from an index hash
+    #[inline]
+    fn bucket_indices_of(&self, hash: u64, fp: u16) -> (usize, usize) {
         let i1 = (hash as usize) % self.num_buckets;
         let i2 = self.alt_index(i1, fp);
         (i1, i2)
@@ -350,6 +675,11 @@ impl CuckooFilter {
             }
         }
 
+        // Width trailer, absent for full-width filters
+        if self.fingerprint_bits != MAX_FINGERPRINT_BITS {
+            bytes.push(self.fingerprint_bits);
+        }
+
         bytes
     }
 
@@ -364,7 +694,22 @@ impl CuckooFilter {
         let num_buckets = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
         let count = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
 
-        let expected_len = 16 + num_buckets * BUCKET_SIZE * 2;
+        // Bucket indices are derived by XOR, which needs a power-of-two count
+        if num_buckets == 0 || !num_buckets.is_power_of_two() {
+            return Err(SketchError::DeserializationError(format!(
+                "Invalid CuckooFilter bucket count {}",
+                num_buckets
+            )));
+        }
+        let expected_len = num_buckets
+            .checked_mul(BUCKET_SIZE * 2)
+            .and_then(|len| len.checked_add(16))
+            .ok_or_else(|| {
+                SketchError::DeserializationError(format!(
+                    "CuckooFilter bucket count {} overflows",
+                    num_buckets
+                ))
+            })?;
         if bytes.len() < expected_len {
             return Err(SketchError::DeserializationError(format!(
                 "Expected {} bytes, got {}",
@@ -372,6 +717,13 @@ impl CuckooFilter {
                 bytes.len()
             )));
         }
+        if count > num_buckets * BUCKET_SIZE {
+            return Err(SketchError::DeserializationError(format!(
+                "CuckooFilter count {} exceeds capacity {}",
+                count,
+                num_buckets * BUCKET_SIZE
+            )));
+        }
 
         let mut buckets = Vec::with_capacity(num_buckets);
         let mut offset = 16;
@@ -385,10 +737,22 @@ impl CuckooFilter {
             buckets.push(bucket);
         }
 
+        let fingerprint_bits = match bytes.get(offset) {
+            None => MAX_FINGERPRINT_BITS,
+            Some(&bits) if (MIN_FINGERPRINT_BITS..MAX_FINGERPRINT_BITS).contains(&bits) => bits,
+            Some(&bits) => {
+                return Err(SketchError::DeserializationError(format!(
+                    "Invalid CuckooFilter fingerprint width {}",
+                    bits
+                )))
+            }
+        };
+
         Ok(CuckooFilter {
             buckets,
             num_buckets,
             count,
+            fingerprint_bits,
             rng: SmallRng::from_os_rng(),
         })
     }
@@ -409,6 +773,92 @@ mod tests {
         assert!(filter.is_empty());
     }
 
+    #[test]
+    fn test_for_estimated() {
+        let mut hll = HyperLogLog::new(12).unwrap();
+        for i in 0..5000u64 {
+            hll.update(&i);
+        }
+
+        let mut filter = CuckooFilter::for_estimated(&hll, 0.01).unwrap();
+        for i in 0..5000u64 {
+            filter.insert(&i.to_le_bytes()).unwrap();
+        }
+        assert_eq!(filter.len(), 5000);
+
+        assert!(CuckooFilter::for_estimated(&hll, 0.0).is_err());
+        assert!(CuckooFilter::for_estimated(&hll, 1e-6).is_err());
+
+        // A tighter target buys wider fingerprints
+        let loose = CuckooFilter::for_estimated(&hll, 0.05).unwrap();
+        let tight = CuckooFilter::for_estimated(&hll, 0.001).unwrap();
+        assert!(tight.fingerprint_bits() > loose.fingerprint_bits());
+        assert!(tight.false_positive_rate() < loose.false_positive_rate());
+        assert_eq!(
+            tight.fingerprint_bits(),
+            CuckooFilter::with_fpr(tight.capacity(), 0.001)
+                .unwrap()
+                .fingerprint_bits()
+        );
+    }
+
+    #[test]
+    fn test_with_fpr() {
+        let absent: Vec<[u8; 4]> = (1_000_000u32..1_200_000).map(|i| i.to_le_bytes()).collect();
+        let absent: Vec<&[u8]> = absent.iter().map(|k| k.as_slice()).collect();
+
+        for (fpr, bits) in [(0.05, 7), (0.01, 9), (0.001, 12)] {
+            let mut filter = CuckooFilter::with_fpr(10_000, fpr).unwrap();
+            assert_eq!(filter.fingerprint_bits(), bits);
+            for i in 0u32..10_000 {
+                filter.insert(&i.to_le_bytes()).unwrap();
+            }
+            for i in 0u32..10_000 {
+                assert!(filter.contains(&i.to_le_bytes()));
+            }
+
+            let measured = filter.measured_fpr(&absent);
+            assert!(measured <= filter.false_positive_rate());
+            assert!(
+                measured > fpr / 2.0 && measured < 1.5 * fpr,
+                "measured {} for target {}",
+                measured,
+                fpr
+            );
+        }
+
+        assert!(CuckooFilter::with_fpr(100, 0.0).is_err());
+        assert!(CuckooFilter::with_fpr(100, 1.0).is_err());
+        assert!(CuckooFilter::with_fpr(100, 1e-5).is_err());
+        assert!(CuckooFilter::with_fpr(0, 0.01).is_err());
+        assert_eq!(
+            CuckooFilter::with_fpr(100, 0.9).unwrap().fingerprint_bits(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_with_fpr_serialization_and_union() {
+        let mut narrow = CuckooFilter::with_fpr(100, 0.01).unwrap();
+        narrow.insert(b"key1").unwrap();
+
+        let restored = CuckooFilter::from_bytes(&narrow.to_bytes()).unwrap();
+        assert_eq!(restored.fingerprint_bits(), 9);
+        assert!(restored.contains(b"key1"));
+
+        // Full-width filters keep the original layout without a trailer
+        let full = CuckooFilter::new(100).unwrap();
+        assert_eq!(full.to_bytes().len(), 16 + full.capacity() * 2);
+        assert_eq!(full.fingerprint_bits(), 16);
+
+        let mut bad = narrow.to_bytes();
+        *bad.last_mut().unwrap() = 17;
+        assert!(CuckooFilter::from_bytes(&bad).is_err());
+
+        assert!(narrow.union_into(&full).is_err());
+        assert!(narrow.contains(b"key1"));
+    }
+
     #[test]
     fn test_insert_contains() {
         let mut filter = CuckooFilter::new(100).unwrap();
@@ -464,6 +914,82 @@ mod tests {
         assert_eq!(filter.len(), restored.len());
     }
 
+    #[test]
+    fn test_from_bytes_rejects_degenerate_params() {
+        fn header(num_buckets: u64, count: u64) -> Vec<u8> {
+            [num_buckets, count]
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect()
+        }
+        let is_corrupt = |bytes: &[u8]| {
+            matches!(
+                CuckooFilter::from_bytes(bytes),
+                Err(SketchError::DeserializationError(_))
+            )
+        };
+
+        // Zero and non-power-of-two bucket counts
+        assert!(is_corrupt(&header(0, 0)));
+        let mut three_buckets = header(3, 0);
+        three_buckets.extend_from_slice(&[0; 3 * BUCKET_SIZE * 2]);
+        assert!(is_corrupt(&three_buckets));
+
+        // A bucket count whose byte size overflows
+        assert!(is_corrupt(&header(1 << 62, 0)));
+
+        // More items than slots
+        let mut overfull = header(1, BUCKET_SIZE as u64 + 1);
+        overfull.extend_from_slice(&[0; BUCKET_SIZE * 2]);
+        assert!(is_corrupt(&overfull));
+    }
+
+    #[test]
+    fn test_insert_hash() {
+        let mut filter = CuckooFilter::new(1000).unwrap();
+        let mut other = CuckooFilter::new(1000).unwrap();
+        for i in 0..500u64 {
+            let hash = xxh64(&i.to_le_bytes(), 0);
+            filter.insert_hash(hash).unwrap();
+            other.insert_hash(hash).unwrap();
+        }
+        assert_eq!(filter.len(), 500);
+        assert_eq!(filter.to_bytes(), other.to_bytes());
+        for i in 0..500u64 {
+            assert!(filter.contains_hash(xxh64(&i.to_le_bytes(), 0)));
+        }
+
+        // Bucket from the hash, fingerprint from its mix
+        let hash = xxh64(b"key", 0);
+        let fp = filter.fingerprint_of(mix64(hash));
+        let (i1, i2) = filter.bucket_indices_of(hash, fp);
+        assert_eq!(i1, hash as usize % filter.num_buckets);
+        assert_eq!(filter.contains_hash(hash), {
+            filter.buckets[i1].contains(fp) || filter.buckets[i2].contains(fp)
+        });
+    }
+
+    #[test]
+    fn test_reads_filters_serialized_by_earlier_versions() {
+        // new(8) holding alpha, beta and gamma, as written by 0.1.x
+        let bytes = hex_bytes(
+            "04000000000000000300000000000000a1e2b747ef1500000000000000000000\
+             00000000000000000000000000000000",
+        );
+        let filter = CuckooFilter::from_bytes(&bytes).unwrap();
+        assert_eq!(filter.len(), 3);
+        for key in [&b"alpha"[..], b"beta", b"gamma"] {
+            assert!(filter.contains(key));
+        }
+    }
+
+    fn hex_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_clear() {
         let mut filter = CuckooFilter::new(100).unwrap();
@@ -486,4 +1012,63 @@ mod tests {
 
         assert!(filter.load_factor() > 0.0);
     }
+
+    #[test]
+    fn test_measured_fpr() {
+        let mut filter = CuckooFilter::new(1000).unwrap();
+        for i in 0u32..1000 {
+            filter.insert(&i.to_le_bytes()).unwrap();
+        }
+
+        let inserted: Vec<[u8; 4]> = (0u32..1000).map(|i| i.to_le_bytes()).collect();
+        let inserted: Vec<&[u8]> = inserted.iter().map(|k| k.as_slice()).collect();
+        assert_eq!(filter.measured_fpr(&inserted), 1.0);
+
+        let absent: Vec<[u8; 4]> = (1_000_000u32..1_050_000).map(|i| i.to_le_bytes()).collect();
+        let absent: Vec<&[u8]> = absent.iter().map(|k| k.as_slice()).collect();
+        let fpr = filter.measured_fpr(&absent);
+        assert!(fpr > 0.0 && fpr < 0.05, "measured FPR {} out of range", fpr);
+
+        assert_eq!(filter.measured_fpr(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_union_into() {
+        let mut a = CuckooFilter::new(4000).unwrap();
+        let mut b = CuckooFilter::new(1000).unwrap();
+        for i in 0u32..800 {
+            a.insert(&i.to_le_bytes()).unwrap();
+            b.insert(&(i + 10_000).to_le_bytes()).unwrap();
+        }
+
+        // a folds onto b's smaller layout
+        a.union_into(&b).unwrap();
+        assert_eq!(a.len(), 1600);
+        assert_eq!(a.capacity(), b.capacity());
+        for i in 0u32..800 {
+            assert!(a.contains(&i.to_le_bytes()));
+            assert!(a.contains(&(i + 10_000).to_le_bytes()));
+        }
+
+        // b keeps its layout when merging a larger filter
+        let mut c = CuckooFilter::new(4000).unwrap();
+        c.insert(b"extra").unwrap();
+        b.union_into(&c).unwrap();
+        assert!(b.contains(b"extra"));
+        assert_eq!(b.len(), 801);
+    }
+
+    #[test]
+    fn test_union_into_overflow_leaves_filter_unchanged() {
+        let mut small = CuckooFilter::new(100).unwrap();
+        let mut large = CuckooFilter::new(10_000).unwrap();
+        small.insert(b"kept").unwrap();
+        for i in 0u32..5000 {
+            large.insert(&i.to_le_bytes()).unwrap();
+        }
+
+        assert!(small.union_into(&large).is_err());
+        assert_eq!(small.len(), 1);
+        assert!(small.contains(b"kept"));
+    }
 }
@@ -72,6 +72,11 @@ use xxhash_rust::xxh64::xxh64;
 ///
 /// **EXPERIMENTAL**: This data structure uses machine learning to reduce
 /// memory consumption. Real-world benefits may vary.
+///
+/// # Thread Safety
+///
+/// `LearnedBloomFilter` is `Send + Sync`; the model weights and backup filter
+/// are plain owned data.
 #[derive(Clone)]
 pub struct LearnedBloomFilter {
     /// Linear model for membership prediction
@@ -84,6 +89,8 @@ pub struct LearnedBloomFilter {
     target_fpr: f64,
     /// Number of training samples
     n_samples: usize,
+    /// Keys stored in the backup filter, including those added after training
+    backup_len: usize,
 }
 
 /// Simple linear model for binary classification (logistic regression)
@@ -232,6 +239,11 @@ impl LearnedBloomFilter {
         let mut backup_filter = BloomFilter::new(backup_n, fpr);
 
         // Insert keys into backup filter
+        let backup_len = if backup_ratio > 0.5 {
+            training_keys.len()
+        } else {
+            backup_keys.len()
+        };
         if backup_ratio > 0.5 {
             // Model failed to learn - use all keys
             for key in training_keys {
@@ -250,9 +262,57 @@ impl LearnedBloomFilter {
             feature_extractor,
             target_fpr: fpr,
             n_samples: training_keys.len(),
+            backup_len,
         })
     }
 
+    /// Adds members without retraining the model
+    ///
+    /// Keys the model already accepts are covered by it; every other key is
+    /// inserted into the backup filter, so [`contains`](Self::contains)
+    /// keeps its zero false negative guarantee for the new members.
+    ///
+    /// The model itself is not updated: it still reflects only the original
+    /// training keys, and the backup filter was sized for the keys the model
+    /// rejected at training time. As it fills past that size its false
+    /// positive rate rises above the target; [`needs_retrain`](Self::needs_retrain)
+    /// reports when that happens, at which point the filter should be rebuilt
+    /// with [`new`](Self::new) over the full key set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::membership::LearnedBloomFilter;
+    ///
+    /// let keys: Vec<Vec<u8>> = (0..1000)
+    ///     .map(|i| format!("key{}", i).into_bytes())
+    ///     .collect();
+    /// let mut filter = LearnedBloomFilter::new(&keys, 0.01).unwrap();
+    ///
+    /// filter.add_members(&[b"late1", b"late2"]);
+    /// assert!(filter.contains(b"late1"));
+    /// assert!(filter.contains(b"late2"));
+    /// ```
+    pub fn add_members(&mut self, new_keys: &[&[u8]]) {
+        for key in new_keys {
+            let features = self.feature_extractor.extract(key);
+            if self.model.predict(&features) < 0.70 {
+                self.backup_filter.insert(key);
+                self.backup_len += 1;
+            }
+        }
+    }
+
+    /// Returns true once the backup filter holds more keys than it was sized for
+    ///
+    /// Past that point the false positive rate exceeds the target, and the
+    /// filter should be rebuilt with [`new`](Self::new) so the model is
+    /// retrained on all members and the backup filter resized. Only
+    /// [`add_members`](Self::add_members) can make this true.
+    pub fn needs_retrain(&self) -> bool {
+        self.backup_len > self.backup_filter.params().0
+    }
+
     /// Checks if a key might be in the set
     ///
     /// # Returns
@@ -522,6 +582,7 @@ impl std::fmt::Debug for LearnedBloomFilter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LearnedBloomFilter")
             .field("n_samples", &self.n_samples)
+            .field("backup_len", &self.backup_len)
             .field("target_fpr", &self.target_fpr)
             .field("memory_bytes", &self.memory_usage())
             .field("feature_dim", &self.feature_extractor.feature_dim())
@@ -1,11 +1,20 @@
 //! Membership testing algorithms
 //!
 //! Probabilistic data structures for set membership queries.
+//!
+//! # Measuring false positives
+//!
+//! Filters that offer `measured_fpr` return the fraction of a sample of
+//! known-absent keys that `contains` reports as present, or 0.0 for an empty
+//! sample. This is a diagnostic: the result depends entirely on the sample,
+//! which must not overlap the inserted keys, and its precision is limited by
+//! the sample size (about `1/len` resolution).
 
 mod binary_fuse;
 mod blocked_bloom;
 mod bloom;
 mod counting_bloom;
+mod counting_fuse;
 mod cuckoo;
 mod learned_bloom;
 mod ribbon;
@@ -16,12 +25,28 @@ pub use binary_fuse::BinaryFuseFilter;
 pub use blocked_bloom::BlockedBloomFilter;
 pub use bloom::BloomFilter;
 pub use counting_bloom::CountingBloomFilter;
+pub use counting_fuse::CountingFuseFilter;
 pub use cuckoo::CuckooFilter;
 pub use learned_bloom::{LearnedBloomFilter, LearnedBloomStats};
-pub use ribbon::RibbonFilter;
+pub use ribbon::{RibbonBuildInfo, RibbonFilter};
 pub use stable_bloom::StableBloomFilter;
 pub use vacuum_filter::{VacuumFilter, VacuumFilterStats};
 
+use crate::cardinality::HyperLogLog;
+use crate::common::Sketch;
+
+/// Number of standard errors added on top of an HLL estimate when sizing a filter
+pub(crate) const ESTIMATE_SAFETY_SIGMAS: f64 = 3.0;
+
+/// Expected element count derived from a HyperLogLog estimate
+///
+/// Returns `ceil(estimate * (1 + 3 * standard_error))`, at least 1, so that the
+/// true cardinality exceeds the sized capacity only with ~0.1% probability.
+pub(crate) fn capacity_from_estimate(hll: &HyperLogLog) -> usize {
+    let margin = 1.0 + ESTIMATE_SAFETY_SIGMAS * hll.standard_error();
+    (hll.estimate() * margin).ceil().max(1.0) as usize
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -22,19 +22,75 @@
 //! let mut filter = RibbonFilter::new(1000, 0.01);
 //! filter.insert(b"key1");
 //! filter.insert(b"key2");
-//! filter.finalize(); // Must call after all inserts
+//! filter.finalize().unwrap(); // Must call after all inserts
 //!
 //! assert!(filter.contains(b"key1"));
 //! assert!(!filter.contains(b"key3")); // Probably false
 //! ```
+//!
+//! # Layout
+//!
+//! The `cols` solution bits are split into slots of `r` result bits each,
+//! sized so there are about 10% more slots than keys. Each key hashes to a
+//! start slot, a 64-slot coefficient band and an `r`-bit result; it is a
+//! member when the XOR of the slots selected by its band equals its result.
+//! `finalize()` solves that banded linear system over GF(2) by incremental
+//! Gaussian elimination and back-substitution. The false positive rate is
+//! `2^-r`.
+//!
+//! # Build verification
+//!
+//! Earlier versions of `finalize()` returned nothing, so a key the solution
+//! failed to represent would silently become a false negative. A key whose
+//! equation reduces to a contradiction during elimination cannot be
+//! represented; `finalize()` checks every inserted key against the built
+//! solution, stores any key that fails in a small exact overflow set, and
+//! reports the path taken via [`RibbonFilter::build_info`]. It returns an
+//! error if the overflow set would exceed 1% of the keys (and 4 keys), which
+//! indicates the filter is undersized.
+
+use crate::common::hash::mix64;
+use crate::common::SketchError;
+
+/// Maximum fraction of keys allowed in the overflow set
+const MAX_OVERFLOW_FRACTION: f64 = 0.01;
+
+/// Overflow keys always allowed, so tiny filters whose 1% rounds to zero
+/// survive one unlucky dependent equation
+const MIN_OVERFLOW_KEYS: usize = 4;
+
+/// Width of a key's coefficient band, in slots
+const BAND_WIDTH: usize = 64;
+
+/// Slots per expected key; the spare slots keep the banded system solvable
+const SLOT_OVERHEAD: f64 = 1.1;
+
+/// How [`RibbonFilter::finalize`] built the filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RibbonBuildInfo {
+    /// Every key is represented by the solution vector
+    Solved,
+    /// Some keys could not be represented and are stored exactly
+    Overflow {
+        /// Number of keys in the overflow set
+        keys: usize,
+    },
+}
 
 /// Ribbon filter for space-efficient membership testing
+///
+/// # Thread Safety
+///
+/// `RibbonFilter` is `Send + Sync`. After `finalize` it is read-only and can be
+/// shared via `Arc`.
 #[derive(Clone)]
 pub struct RibbonFilter {
     /// Stored key hashes for solving
     key_hashes: Vec<u64>,
     /// Solution vector (result of solving)
     solution: Vec<u8>,
+    /// Sorted hashes of keys the solution failed to represent
+    overflow: Vec<u64>,
     /// Number of columns (bits) in solution
     cols: usize,
     /// Expected number of elements
@@ -70,6 +126,7 @@ impl RibbonFilter {
         Self {
             key_hashes: Vec::with_capacity(n),
             solution: vec![0u8; cols.div_ceil(8)], // Byte-aligned
+            overflow: Vec::new(),
             cols,
             n,
             count: 0,
@@ -90,6 +147,7 @@ impl RibbonFilter {
         Self {
             key_hashes: Vec::with_capacity(n),
             solution: vec![0u8; cols.div_ceil(8)],
+            overflow: Vec::new(),
             cols,
             n,
             count: 0,
@@ -112,34 +170,108 @@ impl RibbonFilter {
 
     /// Finalizes the filter by building the solution
     ///
-    /// Maps each key hash to multiple bit positions in the solution vector.
+    /// Solves the keys' banded equations by Gaussian elimination, then
+    /// verifies every key against the result. Keys the solution does not
+    /// represent are kept in an exact overflow set so queries never return a
+    /// false negative; see [`build_info`](Self::build_info).
+    ///
     /// Must be called after all inserts and before queries.
     /// Calling finalize() multiple times is idempotent.
-    pub fn finalize(&mut self) {
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SketchError::InvalidParameter`] if more than 1% of the keys
+    /// (and more than 4) would need the overflow set. The filter is left
+    /// unfinalized.
+    pub fn finalize(&mut self) -> Result<(), SketchError> {
         if self.finalized {
-            return; // Idempotent - already finalized
+            return Ok(()); // Idempotent - already finalized
+        }
+
+        let (result_bits, slots) = self.layout();
+
+        // Incremental elimination: row i keeps the equation whose lowest
+        // coefficient is slot i. A key reducing to 0 = 0 is redundant; one
+        // reducing to 0 = 1 cannot be represented and fails verification
+        let mut coeffs = vec![0u64; slots];
+        let mut results = vec![0u64; slots];
+        for &hash in &self.key_hashes {
+            let (mut slot, mut coeff, mut result) = self.equation(hash);
+            while coeff != 0 {
+                let shift = coeff.trailing_zeros() as usize;
+                slot += shift;
+                coeff >>= shift;
+                if coeffs[slot] == 0 {
+                    coeffs[slot] = coeff;
+                    results[slot] = result;
+                    break;
+                }
+                coeff ^= coeffs[slot];
+                result ^= results[slot];
+            }
+        }
+
+        // Back-substitution; free slots stay 0
+        let mut values = vec![0u64; slots];
+        for slot in (0..slots).rev() {
+            let mut value = results[slot];
+            let mut rest = coeffs[slot] & !1;
+            while rest != 0 {
+                value ^= values[slot + rest.trailing_zeros() as usize];
+                rest &= rest - 1;
+            }
+            values[slot] = value;
         }
 
-        // Clear solution
         self.solution.fill(0);
+        for (slot, &value) in values.iter().enumerate() {
+            for bit in 0..result_bits {
+                if value >> bit & 1 == 1 {
+                    let pos = slot * result_bits + bit;
+                    self.solution[pos / 8] |= 1u8 << (pos % 8);
+                }
+            }
+        }
 
-        // For each key hash, set multiple corresponding bits in solution
-        // Using 2 hash positions reduces FPR significantly
-        for hash in &self.key_hashes {
-            // First hash position
-            let bit_pos1 = (*hash as usize) % self.cols;
-            let byte_idx1 = bit_pos1 / 8;
-            let bit_idx1 = bit_pos1 % 8;
-            self.solution[byte_idx1] |= 1u8 << bit_idx1;
-
-            // Second hash position (using upper bits)
-            let bit_pos2 = ((*hash >> 32) as usize) % self.cols;
-            let byte_idx2 = bit_pos2 / 8;
-            let bit_idx2 = bit_pos2 % 8;
-            self.solution[byte_idx2] |= 1u8 << bit_idx2;
+        let mut overflow: Vec<u64> = self
+            .key_hashes
+            .iter()
+            .copied()
+            .filter(|&hash| !self.solution_contains(hash))
+            .collect();
+        overflow.sort_unstable();
+        overflow.dedup();
+
+        let max_overflow =
+            ((self.count as f64 * MAX_OVERFLOW_FRACTION).floor() as usize).max(MIN_OVERFLOW_KEYS);
+        if overflow.len() > max_overflow {
+            return Err(SketchError::InvalidParameter {
+                param: "n".to_string(),
+                value: self.n.to_string(),
+                constraint: format!(
+                    "is too small: {} of {} keys could not be represented",
+                    overflow.len(),
+                    self.count
+                ),
+            });
         }
 
+        self.overflow = overflow;
         self.finalized = true;
+        Ok(())
+    }
+
+    /// Returns how the filter was built, or `None` before finalization
+    pub fn build_info(&self) -> Option<RibbonBuildInfo> {
+        if !self.finalized {
+            None
+        } else if self.overflow.is_empty() {
+            Some(RibbonBuildInfo::Solved)
+        } else {
+            Some(RibbonBuildInfo::Overflow {
+                keys: self.overflow.len(),
+            })
+        }
     }
 
     /// Checks if an element might be in the set
@@ -153,20 +285,69 @@ impl RibbonFilter {
 
         // Hash key and check if all corresponding bits are set
         let hash = self.hash(key);
+        self.solution_contains(hash) || self.overflow.binary_search(&hash).is_ok()
+    }
+
+    /// Whether every stored key is found by the solution or the overflow set
+    fn represents_all_keys(&self) -> bool {
+        self.key_hashes
+            .iter()
+            .all(|&hash| self.solution_contains(hash) || self.overflow.binary_search(&hash).is_ok())
+    }
 
-        // Check first hash position
-        let bit_pos1 = (hash as usize) % self.cols;
-        let byte_idx1 = bit_pos1 / 8;
-        let bit_idx1 = bit_pos1 % 8;
-        if (self.solution[byte_idx1] & (1u8 << bit_idx1)) == 0 {
-            return false;
+    /// Checks whether the solution satisfies a key hash's equation
+    #[inline]
+    fn solution_contains(&self, hash: u64) -> bool {
+        let (result_bits, _) = self.layout();
+        let (start, mut coeff, result) = self.equation(hash);
+
+        let mut acc = 0u64;
+        while coeff != 0 {
+            acc ^= self.slot_value(start + coeff.trailing_zeros() as usize, result_bits);
+            coeff &= coeff - 1;
         }
+        acc == result
+    }
 
-        // Check second hash position
-        let bit_pos2 = ((hash >> 32) as usize) % self.cols;
-        let byte_idx2 = bit_pos2 / 8;
-        let bit_idx2 = bit_pos2 % 8;
-        (self.solution[byte_idx2] & (1u8 << bit_idx2)) != 0
+    /// Result bits per slot and number of slots
+    ///
+    /// Aims for `SLOT_OVERHEAD` slots per expected key and gives each slot as
+    /// many of the `cols` bits as that allows, between 1 and 32.
+    #[inline]
+    fn layout(&self) -> (usize, usize) {
+        let target_slots = ((self.n as f64 * SLOT_OVERHEAD).ceil() as usize).max(1);
+        let result_bits = (self.cols / target_slots).clamp(1, 32);
+        (result_bits, self.cols / result_bits)
+    }
+
+    /// Derives a key's equation: start slot, coefficient band (bit `j` covers
+    /// slot `start + j`, bit 0 always set) and expected result
+    #[inline]
+    fn equation(&self, hash: u64) -> (usize, u64, u64) {
+        let (result_bits, slots) = self.layout();
+        let width = BAND_WIDTH.min(slots);
+
+        let starts = (slots - width + 1) as u128;
+        let start = ((mix64(hash) as u128 * starts) >> 64) as usize;
+        let band_mask = if width == 64 {
+            u64::MAX
+        } else {
+            (1u64 << width) - 1
+        };
+        let coeff = (mix64(hash ^ 0x9E37_79B9_7F4A_7C15) & band_mask) | 1;
+        let result = mix64(hash ^ 0xC2B2_AE3D_27D4_EB4F) & ((1u64 << result_bits) - 1);
+        (start, coeff, result)
+    }
+
+    /// Reads the `result_bits`-bit value of a slot from the solution
+    #[inline]
+    fn slot_value(&self, slot: usize, result_bits: usize) -> u64 {
+        let pos = slot * result_bits;
+        let mut window = 0u64;
+        for (i, &byte) in self.solution[pos / 8..].iter().take(8).enumerate() {
+            window |= (byte as u64) << (8 * i);
+        }
+        (window >> (pos % 8)) & ((1u64 << result_bits) - 1)
     }
 
     /// Returns whether the filter has been finalized
@@ -179,15 +360,16 @@ impl RibbonFilter {
         self.count
     }
 
-    /// Returns the theoretical false positive rate
+    /// Returns the theoretical false positive rate, `2^-r` for `r` result
+    /// bits per slot
     pub fn false_positive_rate(&self) -> f64 {
-        let bits_per_key = self.cols as f64 / self.n as f64;
-        2_f64.powf(2.0 - bits_per_key)
+        let (result_bits, _) = self.layout();
+        2_f64.powi(-(result_bits as i32))
     }
 
     /// Returns the memory usage in bytes
     pub fn memory_usage(&self) -> usize {
-        self.key_hashes.len() * 8 + self.solution.len()
+        self.key_hashes.len() * 8 + self.solution.len() + self.overflow.len() * 8
     }
 
     /// Serializes the filter to bytes
@@ -210,13 +392,21 @@ impl RibbonFilter {
         // Solution
         bytes.extend_from_slice(&self.solution);
 
+        // Optional trailer: [num_overflow: 8][overflow hashes]
+        if !self.overflow.is_empty() {
+            bytes.extend_from_slice(&self.overflow.len().to_le_bytes());
+            for hash in &self.overflow {
+                bytes.extend_from_slice(&hash.to_le_bytes());
+            }
+        }
+
         bytes
     }
 
     /// Deserializes a filter from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
         if bytes.len() < 25 {
-            return Err("Insufficient bytes for header");
+            return Err(corrupt("Insufficient bytes for header"));
         }
 
         let n = usize::from_le_bytes(bytes[0..8].try_into().unwrap());
@@ -224,11 +414,18 @@ impl RibbonFilter {
         let count = usize::from_le_bytes(bytes[16..24].try_into().unwrap());
         let finalized = bytes[24] == 1;
 
+        if cols == 0 {
+            return Err(corrupt("Number of columns must be > 0"));
+        }
+
         let solution_len = cols.div_ceil(8);
-        let expected_size = 25 + count * 8 + solution_len;
+        let expected_size = count
+            .checked_mul(8)
+            .and_then(|len| len.checked_add(25 + solution_len))
+            .ok_or_else(|| corrupt("Key count overflows"))?;
 
-        if bytes.len() != expected_size {
-            return Err("Invalid byte array size");
+        if bytes.len() < expected_size {
+            return Err(corrupt("Invalid byte array size"));
         }
 
         let mut key_hashes = Vec::with_capacity(count);
@@ -241,14 +438,48 @@ impl RibbonFilter {
         let solution_offset = 25 + count * 8;
         let solution = bytes[solution_offset..solution_offset + solution_len].to_vec();
 
-        Ok(Self {
+        let mut overflow = Vec::new();
+        let trailer = &bytes[expected_size..];
+        if !trailer.is_empty() {
+            if trailer.len() < 8 {
+                return Err(corrupt("Invalid byte array size"));
+            }
+            let num_overflow = usize::from_le_bytes(trailer[0..8].try_into().unwrap());
+            if num_overflow.checked_mul(8) != Some(trailer.len() - 8) {
+                return Err(corrupt("Invalid byte array size"));
+            }
+            for i in 0..num_overflow {
+                let offset = 8 + i * 8;
+                overflow.push(u64::from_le_bytes(
+                    trailer[offset..offset + 8].try_into().unwrap(),
+                ));
+            }
+        }
+
+        let mut filter = Self {
             key_hashes,
             solution,
+            overflow,
             cols,
             n,
             count,
             finalized,
-        })
+        };
+
+        // Filters written before finalize() solved by elimination store a
+        // solution that does not represent their keys; re-solve those from
+        // the stored key hashes rather than answer with false negatives
+        if filter.finalized && !filter.represents_all_keys() {
+            filter.finalized = false;
+            filter.finalize().map_err(|e| {
+                corrupt(&format!(
+                    "Cannot rebuild filter from its stored keys: {}",
+                    e
+                ))
+            })?;
+        }
+
+        Ok(filter)
     }
 
     /// Hash function using xxHash
@@ -280,6 +511,10 @@ impl RibbonFilter {
     }
 }
 
+fn corrupt(msg: &str) -> SketchError {
+    SketchError::DeserializationError(msg.to_string())
+}
+
 impl std::fmt::Debug for RibbonFilter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RibbonFilter")
@@ -332,7 +567,7 @@ mod tests {
         filter.insert(b"key1");
         filter.insert(b"key2");
         filter.insert(b"key3");
-        filter.finalize();
+        filter.finalize().unwrap();
 
         assert!(filter.contains(b"key1"));
         assert!(filter.contains(b"key2"));
@@ -347,7 +582,7 @@ mod tests {
         for key in &keys {
             filter.insert(key);
         }
-        filter.finalize();
+        filter.finalize().unwrap();
 
         // No false negatives
         for key in &keys {
@@ -369,7 +604,7 @@ mod tests {
         for key in &keys {
             filter.insert(key);
         }
-        filter.finalize();
+        filter.finalize().unwrap();
 
         // Test with non-inserted keys
         let test_keys: Vec<Vec<u8>> = (10000..20000)
@@ -387,7 +622,7 @@ mod tests {
     #[test]
     fn test_empty_filter() {
         let mut filter = RibbonFilter::new(100, 0.01);
-        filter.finalize();
+        filter.finalize().unwrap();
 
         // Empty filter might have false positives, but we can test it doesn't crash
         let _result = filter.contains(b"key1");
@@ -399,7 +634,7 @@ mod tests {
         filter.insert(b"key1");
         filter.insert(b"key2");
         filter.insert(b"key3");
-        filter.finalize();
+        filter.finalize().unwrap();
 
         let bytes = filter.to_bytes();
         let deserialized = RibbonFilter::from_bytes(&bytes).unwrap();
@@ -415,7 +650,7 @@ mod tests {
     #[test]
     fn test_serialization_empty() {
         let mut filter = RibbonFilter::new(100, 0.01);
-        filter.finalize();
+        filter.finalize().unwrap();
 
         let bytes = filter.to_bytes();
         let deserialized = RibbonFilter::from_bytes(&bytes).unwrap();
@@ -432,7 +667,7 @@ mod tests {
         for key in &binary_keys {
             filter.insert(key);
         }
-        filter.finalize();
+        filter.finalize().unwrap();
 
         for key in &binary_keys {
             assert!(filter.contains(key));
@@ -445,7 +680,7 @@ mod tests {
         let large_key = vec![42u8; 10000];
 
         filter.insert(&large_key);
-        filter.finalize();
+        filter.finalize().unwrap();
 
         assert!(filter.contains(&large_key));
     }
@@ -512,15 +747,97 @@ mod tests {
     fn test_insert_after_finalize() {
         let mut filter = RibbonFilter::new(100, 0.01);
         filter.insert(b"key1");
-        filter.finalize();
+        filter.finalize().unwrap();
         filter.insert(b"key2"); // Should panic
     }
 
+    #[test]
+    fn test_build_info() {
+        let mut filter = RibbonFilter::new(1000, 0.01);
+        assert_eq!(filter.build_info(), None);
+
+        for i in 0..1000 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+        filter.finalize().unwrap();
+        assert_eq!(filter.build_info(), Some(RibbonBuildInfo::Solved));
+    }
+
+    #[test]
+    fn test_overflow_roundtrip() {
+        let mut filter = RibbonFilter::new(100, 0.01);
+        filter.insert(b"key1");
+        filter.finalize().unwrap();
+
+        // Simulate a key the solution could not represent
+        let hash = filter.hash(b"missing");
+        filter.overflow.push(hash);
+        assert!(filter.contains(b"missing"));
+        assert_eq!(
+            filter.build_info(),
+            Some(RibbonBuildInfo::Overflow { keys: 1 })
+        );
+
+        let restored = RibbonFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert!(restored.contains(b"key1"));
+        assert!(restored.contains(b"missing"));
+        assert_eq!(restored.build_info(), filter.build_info());
+    }
+
+    #[test]
+    fn test_saturated_filter_overflows() {
+        // One result bit per slot and exactly as many slots as keys leaves
+        // a few equations dependent; those keys must land in the overflow set
+        let mut filter = RibbonFilter::with_params(1000, 0, 1000);
+        for i in 0..1000 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+        filter.finalize().unwrap();
+
+        match filter.build_info() {
+            Some(RibbonBuildInfo::Overflow { keys }) => assert!(keys > 0 && keys <= 10),
+            other => panic!("expected overflow, got {:?}", other),
+        }
+        for i in 0..1000 {
+            assert!(filter.contains(format!("key{}", i).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_undersized_filter_fails_to_build() {
+        let mut filter = RibbonFilter::with_params(100, 0, 16);
+        for i in 0..100 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+        assert!(filter.finalize().is_err());
+        assert!(!filter.is_finalized());
+        assert_eq!(filter.build_info(), None);
+    }
+
+    #[test]
+    fn test_rebuilds_filters_serialized_by_earlier_versions() {
+        // new(8, 0.01) holding 0..8, finalized by the 0.1.x solver
+        let hex = "08000000000000004800000000000000080000000000000001b4def25cfda6ef\
+             3a5153cb1f00942ff49eb2dcce60f37a27bfb27f1eaef06bb9d9c98691284322\
+             3839b9d2792f8bd4c3b61f803a716bd3b90e0d969b0e48cab700038022504340\
+             0240";
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        let filter = RibbonFilter::from_bytes(&bytes).unwrap();
+        assert!(filter.is_finalized());
+        for i in 0u32..8 {
+            assert!(filter.contains(&i.to_le_bytes()));
+        }
+    }
+
     #[test]
     fn test_debug_format() {
         let mut filter = RibbonFilter::new(1000, 0.01);
         filter.insert(b"test");
-        filter.finalize();
+        filter.finalize().unwrap();
 
         let debug_str = format!("{:?}", filter);
         assert!(debug_str.contains("RibbonFilter"));
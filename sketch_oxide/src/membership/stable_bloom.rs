@@ -69,6 +69,11 @@ const DEFAULT_COUNTER_BITS: u8 = 3;
 /// Maintains bounded false positive rate by continuously evicting stale data
 /// through random counter decrements.
 ///
+/// # Thread Safety
+///
+/// `StableBloomFilter` is `Send + Sync`. Each insert decrements random cells
+/// using an owned RNG, so inserts take `&mut self`.
+///
 /// # Examples
 ///
 /// ```
@@ -203,6 +208,61 @@ impl StableBloomFilter {
         self.p
     }
 
+    /// Estimates the probability that an item inserted `inserts_ago` operations
+    /// ago is no longer reported by [`contains`](Self::contains)
+    ///
+    /// # Model
+    ///
+    /// When the item was inserted, each of its `k` cells was set to the maximum
+    /// counter value `Max`. Every later insert decrements `P` cells chosen
+    /// uniformly at random, so after `N` inserts a given cell has received
+    /// `X ~ Binomial(N * P, 1 / m)` decrements. The cell reaches zero once
+    /// `X >= Max`, and the item becomes a false negative as soon as any of its
+    /// cells does:
+    ///
+    /// ```text
+    /// q      = P[Binomial(N * P, 1 / m) >= Max]
+    /// P(FN)  = 1 - (1 - q)^k
+    /// ```
+    ///
+    /// Cells are treated as independent, and re-insertions of the same item or
+    /// collisions with other items (which reset cells back to `Max`) are
+    /// ignored, so the estimate is an upper bound for items seen once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::StableBloomFilter;
+    ///
+    /// let filter = StableBloomFilter::new(1000, 0.01).unwrap();
+    /// assert_eq!(filter.false_negative_probability(0), 0.0);
+    /// assert!(filter.false_negative_probability(100) < filter.false_negative_probability(10_000));
+    /// ```
+    pub fn false_negative_probability(&self, inserts_ago: u64) -> f64 {
+        let max = self.max_counter as u64;
+        let draws = inserts_ago.saturating_mul(self.p as u64);
+        if draws < max {
+            return 0.0;
+        }
+
+        // P[X < Max] = sum_{j < Max} C(n, j) p^j (1 - p)^(n - j), in log space
+        let n = draws as f64;
+        let p = 1.0 / self.m as f64;
+        let ln_p = p.ln();
+        let ln_q = (-p).ln_1p();
+        let mut ln_binom = 0.0;
+        let mut survive = 0.0;
+        for j in 0..max {
+            if j > 0 {
+                ln_binom += ((n - (j - 1) as f64) / j as f64).ln();
+            }
+            survive += (ln_binom + j as f64 * ln_p + (n - j as f64) * ln_q).exp();
+        }
+        let cell_zeroed = (1.0 - survive).clamp(0.0, 1.0);
+
+        (1.0 - (1.0 - cell_zeroed).powi(self.k as i32)).clamp(0.0, 1.0)
+    }
+
     /// Inserts an element into the filter
     ///
     /// This also randomly decrements P counters to maintain stability.
@@ -347,10 +407,19 @@ impl StableBloomFilter {
         let p = u64::from_le_bytes(bytes[18..26].try_into().unwrap()) as usize;
         let counter_len = u64::from_le_bytes(bytes[26..34].try_into().unwrap()) as usize;
 
-        if bytes.len() < 34 + counter_len {
+        let valid_counters = (1..=8).contains(&counter_bits)
+            && max_counter as u16 == (1u16 << counter_bits) - 1
+            && counter_len >= m.div_ceil(8 / counter_bits as usize);
+        if m == 0 || k == 0 || p > m || !valid_counters {
+            return Err(SketchError::DeserializationError(format!(
+                "Invalid StableBloomFilter parameters: {} counters of {} bits, {} hash functions, {} decrements",
+                m, counter_bits, k, p
+            )));
+        }
+        if bytes.len() - 34 < counter_len {
             return Err(SketchError::DeserializationError(format!(
                 "Expected {} bytes, got {}",
-                34 + counter_len,
+                counter_len.saturating_add(34),
                 bytes.len()
             )));
         }
@@ -436,6 +505,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_false_negative_probability() {
+        let filter = StableBloomFilter::new(1000, 0.01).unwrap();
+        assert_eq!(filter.false_negative_probability(0), 0.0);
+
+        let mut prev = 0.0;
+        for n in [10, 100, 1_000, 10_000, 100_000] {
+            let fnp = filter.false_negative_probability(n);
+            assert!((0.0..=1.0).contains(&fnp));
+            assert!(fnp >= prev, "not monotone at {n}: {fnp} < {prev}");
+            prev = fnp;
+        }
+        assert!(filter.false_negative_probability(10) < 0.01);
+        assert!(filter.false_negative_probability(1_000_000) > 0.99);
+        assert!(filter.false_negative_probability(u64::MAX) <= 1.0);
+    }
+
+    #[test]
+    fn test_false_negative_probability_matches_decay() {
+        let inserts_ago = 2_000u64;
+        let trials = 200;
+        let mut missing = 0;
+        for t in 0..trials {
+            let mut filter = StableBloomFilter::with_params(1000, 0.01, 3, t).unwrap();
+            filter.insert(format!("target-{t}").as_bytes());
+            for i in 0..inserts_ago {
+                filter.insert(format!("noise-{t}-{i}").as_bytes());
+            }
+            if !filter.contains(format!("target-{t}").as_bytes()) {
+                missing += 1;
+            }
+        }
+        let observed = missing as f64 / trials as f64;
+        let predicted = StableBloomFilter::with_params(1000, 0.01, 3, 0)
+            .unwrap()
+            .false_negative_probability(inserts_ago);
+        // Resets from colliding inserts only make items survive longer
+        assert!(
+            observed <= predicted + 0.1,
+            "observed {observed} > predicted {predicted}"
+        );
+    }
+
     #[test]
     fn test_get_count() {
         let mut filter = StableBloomFilter::new(1000, 0.01).unwrap();
@@ -69,7 +69,7 @@
 //! println!("Memory: {} bits", stats.memory_bits);
 //! ```
 
-use crate::common::SketchError;
+use crate::common::{validation, SketchError};
 use xxhash_rust::xxh64::xxh64;
 
 /// Number of entries per bucket (optimized for cache lines)
@@ -84,6 +84,15 @@ const MIN_FINGERPRINT_BITS: u8 = 4;
 /// Maximum fingerprint bits
 const MAX_FINGERPRINT_BITS: u8 = 15;
 
+/// Magic bytes prefixed to serialized Vacuum filters
+const SERIAL_MAGIC: &[u8; 4] = b"VACF";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
+/// Fixed fields after the header: buckets, items, fingerprint bits, load factor, FPR
+const SERIAL_FIELDS_LEN: usize = 8 + 8 + 1 + 8 + 8;
+
 /// A bucket containing semi-sorted fingerprints
 ///
 /// The semi-sorting optimization keeps fingerprints in approximate order
@@ -212,6 +221,11 @@ pub struct VacuumFilterStats {
 /// Supports insertions, deletions, and membership queries with superior
 /// space efficiency compared to Cuckoo and Bloom filter variants.
 ///
+/// # Thread Safety
+///
+/// `VacuumFilter` is `Send + Sync`. Inserts and deletes take `&mut self`;
+/// lookups take `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -371,8 +385,13 @@ impl VacuumFilter {
         }
 
         let fp = self.fingerprint(key);
-        let mut bucket_idx = self.bucket_index(key);
+        let bucket_idx = self.bucket_index(key);
+        self.place(bucket_idx, fp)
+    }
 
+    /// Stores a fingerprint in the first bucket with space, probing forward
+    /// from `bucket_idx`
+    fn place(&mut self, mut bucket_idx: usize, fp: u16) -> Result<(), SketchError> {
         // Linear probing to find a bucket with space
         let start_idx = bucket_idx;
         loop {
@@ -395,6 +414,95 @@ impl VacuumFilter {
         }
     }
 
+    /// Adds every fingerprint stored in `other` to this filter
+    ///
+    /// Afterwards `contains` reports every key present in either filter. Keys
+    /// are not stored, so each fingerprint is re-inserted by probing forward
+    /// from the bucket it occupies. This keeps every probe run unbroken, so
+    /// lookups still reach it. Parameters are negotiated so the result keeps
+    /// this filter's false positive rate:
+    ///
+    /// - Wider fingerprints from `other` are truncated to this filter's width.
+    ///   Narrower ones cannot be widened and are rejected.
+    /// - A bucket only determines the low bits of its key's hash, so the union
+    ///   can fold onto fewer buckets but never grow. When `other` has fewer
+    ///   buckets, this filter is rebuilt with `other`'s bucket count.
+    ///
+    /// The union is built separately and only replaces this filter on
+    /// success.
+    ///
+    /// # Errors
+    ///
+    /// - `IncompatibleSketches` if `other` has narrower fingerprints
+    /// - `InvalidParameter` if the combined fingerprints do not fit in the
+    ///   smaller layout
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::VacuumFilter;
+    ///
+    /// let mut a = VacuumFilter::new(1000, 0.01).unwrap();
+    /// let mut b = VacuumFilter::new(1000, 0.001).unwrap();
+    /// a.insert(b"left").unwrap();
+    /// b.insert(b"right").unwrap();
+    ///
+    /// a.union_into(&b).unwrap();
+    /// assert!(a.contains(b"left") && a.contains(b"right"));
+    ///
+    /// // b has 10-bit fingerprints, a only 7: merging a into b would raise b's FPR
+    /// assert!(b.union_into(&a).is_err());
+    /// ```
+    pub fn union_into(&mut self, other: &VacuumFilter) -> Result<(), SketchError> {
+        if other.fingerprint_bits < self.fingerprint_bits {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "fingerprint width mismatch: this filter uses {} bits, other uses {} bits \
+                     (other must use at least as many)",
+                    self.fingerprint_bits, other.fingerprint_bits
+                ),
+            });
+        }
+
+        let num_buckets = self.num_buckets.min(other.num_buckets);
+        let mut union = VacuumFilter {
+            buckets: vec![Bucket::default(); num_buckets],
+            num_buckets,
+            capacity: num_buckets * BUCKET_SIZE,
+            num_items: 0,
+            ..*self
+        };
+        union.absorb(self)?;
+        union.absorb(other)?;
+
+        *self = union;
+        Ok(())
+    }
+
+    /// Re-inserts all fingerprints of `source`, whose bucket count must be a
+    /// multiple of this filter's and whose fingerprints are at least as wide
+    fn absorb(&mut self, source: &VacuumFilter) -> Result<(), SketchError> {
+        for (idx, bucket) in source.buckets.iter().enumerate() {
+            for &fp in &bucket.entries[..bucket.len()] {
+                let fp = match fp & self.fingerprint_mask {
+                    0 => 1,
+                    fp => fp,
+                };
+                self.place(idx % self.num_buckets, fp).map_err(|_| {
+                    SketchError::InvalidParameter {
+                        param: "other".to_string(),
+                        value: format!("{} items", source.num_items),
+                        constraint: format!(
+                            "union does not fit in {} slots; filters cannot grow from fingerprints",
+                            self.capacity
+                        ),
+                    }
+                })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Checks if an element might be in the filter
     ///
     /// # Arguments
@@ -443,6 +551,31 @@ impl VacuumFilter {
         }
     }
 
+    /// Measures the FPR on known-absent keys; see [module docs](super#measuring-false-positives)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::VacuumFilter;
+    ///
+    /// let mut filter = VacuumFilter::new(1000, 0.01).unwrap();
+    /// for i in 0u32..500 {
+    ///     filter.insert(&i.to_le_bytes()).unwrap();
+    /// }
+    ///
+    /// let absent: Vec<[u8; 4]> = (1_000_000u32..1_010_000).map(|i| i.to_le_bytes()).collect();
+    /// let keys: Vec<&[u8]> = absent.iter().map(|k| k.as_slice()).collect();
+    /// assert!(filter.measured_fpr(&keys) < 0.05);
+    /// ```
+    pub fn measured_fpr(&self, absent_keys: &[&[u8]]) -> f64 {
+        if absent_keys.is_empty() {
+            return 0.0;
+        }
+
+        let hits = absent_keys.iter().filter(|key| self.contains(key)).count();
+        hits as f64 / absent_keys.len() as f64
+    }
+
     /// Deletes an element from the filter
     ///
     /// # Arguments
@@ -577,6 +710,163 @@ impl VacuumFilter {
         self.num_items = 0;
     }
 
+    /// Serializes the filter to bytes
+    ///
+    /// # Format
+    ///
+    /// - Header: magic `"VACF"` and a version byte
+    /// - Number of buckets and number of items (u64 LE each)
+    /// - Fingerprint bits (u8), max load factor and target FPR (f64 LE each)
+    /// - Per bucket: occupied slot count (u8) followed by that many
+    ///   fingerprints (u16 LE)
+    ///
+    /// Empty slots are not written, so a lightly loaded filter serializes
+    /// to little more than one byte per bucket. Buckets keep their
+    /// positions, so lookups on the reloaded filter probe exactly as before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::membership::VacuumFilter;
+    ///
+    /// let mut filter = VacuumFilter::new(1000, 0.01).unwrap();
+    /// filter.insert(b"key1").unwrap();
+    ///
+    /// let restored = VacuumFilter::from_bytes(&filter.to_bytes()).unwrap();
+    /// assert!(restored.contains(b"key1"));
+    /// assert_eq!(restored.len(), 1);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            validation::HEADER_LEN + SERIAL_FIELDS_LEN + self.num_buckets + self.num_items * 2,
+        );
+        validation::write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
+        bytes.extend_from_slice(&(self.num_buckets as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.num_items as u64).to_le_bytes());
+        bytes.push(self.fingerprint_bits);
+        bytes.extend_from_slice(&self.max_load_factor.to_le_bytes());
+        bytes.extend_from_slice(&self.target_fpr.to_le_bytes());
+
+        for bucket in &self.buckets {
+            bytes.push(bucket.count);
+            for &fp in &bucket.entries[..bucket.len()] {
+                bytes.extend_from_slice(&fp.to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    /// Deserializes a filter written by [`to_bytes`](Self::to_bytes)
+    ///
+    /// # Errors
+    ///
+    /// Returns `DeserializationError` if the header is foreign or from an
+    /// unknown version, the data is truncated or has trailing bytes, or the
+    /// contents are inconsistent: a bucket count that is not a power of two,
+    /// fingerprint bits outside 4-15, a bucket holding more than 4 entries,
+    /// a zero, oversized or unsorted fingerprint, or an item count that does
+    /// not match the stored fingerprints.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        fn corrupt(msg: String) -> SketchError {
+            SketchError::DeserializationError(msg)
+        }
+
+        validation::validate_byte_size(bytes.len())?;
+        let payload = validation::validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?;
+        validation::validate_min_size(payload.len(), SERIAL_FIELDS_LEN)?;
+
+        let read_u64 = |at: usize| u64::from_le_bytes(payload[at..at + 8].try_into().unwrap());
+        let num_buckets = read_u64(0);
+        let num_items = read_u64(8);
+        let fingerprint_bits = payload[16];
+        let max_load_factor = f64::from_bits(read_u64(17));
+        let target_fpr = f64::from_bits(read_u64(25));
+        let mut rest = &payload[SERIAL_FIELDS_LEN..];
+
+        // Every bucket takes at least its count byte
+        if num_buckets == 0 || !num_buckets.is_power_of_two() || num_buckets > rest.len() as u64 {
+            return Err(corrupt(format!(
+                "Invalid bucket count {} for {} bytes of bucket data",
+                num_buckets,
+                rest.len()
+            )));
+        }
+        if !(MIN_FINGERPRINT_BITS..=MAX_FINGERPRINT_BITS).contains(&fingerprint_bits) {
+            return Err(corrupt(format!(
+                "Invalid fingerprint bits {}",
+                fingerprint_bits
+            )));
+        }
+        if max_load_factor.is_nan() || max_load_factor <= 0.0 || max_load_factor > 1.0 {
+            return Err(corrupt(format!(
+                "Invalid max load factor {}",
+                max_load_factor
+            )));
+        }
+        if target_fpr.is_nan() || target_fpr <= 0.0 || target_fpr >= 1.0 {
+            return Err(corrupt(format!("Invalid target FPR {}", target_fpr)));
+        }
+
+        let fingerprint_mask = (1u16 << fingerprint_bits) - 1;
+        let num_buckets = num_buckets as usize;
+        let mut buckets = Vec::with_capacity(num_buckets);
+        let mut stored = 0u64;
+        for idx in 0..num_buckets {
+            let (&count, tail) = rest
+                .split_first()
+                .ok_or_else(|| corrupt(format!("Truncated at bucket {}", idx)))?;
+            let count = count as usize;
+            if count > BUCKET_SIZE {
+                return Err(corrupt(format!(
+                    "Bucket {} holds {} entries (max {})",
+                    idx, count, BUCKET_SIZE
+                )));
+            }
+            if tail.len() < count * 2 {
+                return Err(corrupt(format!("Truncated at bucket {}", idx)));
+            }
+
+            let mut bucket = Bucket::default();
+            for (slot, chunk) in tail[..count * 2].chunks_exact(2).enumerate() {
+                let fp = u16::from_le_bytes([chunk[0], chunk[1]]);
+                let sorted = slot == 0 || bucket.entries[slot - 1] <= fp;
+                if fp == 0 || fp > fingerprint_mask || !sorted {
+                    return Err(corrupt(format!(
+                        "Invalid fingerprint {} in bucket {}",
+                        fp, idx
+                    )));
+                }
+                bucket.entries[slot] = fp;
+            }
+            bucket.count = count as u8;
+            stored += count as u64;
+            buckets.push(bucket);
+            rest = &tail[count * 2..];
+        }
+
+        if !rest.is_empty() {
+            return Err(corrupt(format!("{} trailing bytes", rest.len())));
+        }
+        if stored != num_items {
+            return Err(corrupt(format!(
+                "Item count {} does not match {} stored fingerprints",
+                num_items, stored
+            )));
+        }
+
+        Ok(VacuumFilter {
+            buckets,
+            num_buckets,
+            capacity: num_buckets * BUCKET_SIZE,
+            num_items: stored as usize,
+            fingerprint_bits,
+            fingerprint_mask,
+            max_load_factor,
+            target_fpr,
+        })
+    }
+
     /// Computes the fingerprint for a key
     #[inline]
     fn fingerprint(&self, key: &[u8]) -> u16 {
@@ -769,4 +1059,46 @@ mod tests {
         assert!(bucket.contains(100));
         assert!(bucket.contains(300));
     }
+
+    #[test]
+    fn test_union_into() {
+        // a: 7-bit fingerprints, 2048 slots; b: 10-bit fingerprints, 512 slots
+        let mut a = VacuumFilter::new(1000, 0.01).unwrap();
+        let mut b = VacuumFilter::new(300, 0.001).unwrap();
+        for i in 0u32..200 {
+            a.insert(&i.to_le_bytes()).unwrap();
+            b.insert(&(i + 10_000).to_le_bytes()).unwrap();
+        }
+
+        a.union_into(&b).unwrap();
+        assert_eq!(a.len(), 400);
+        assert_eq!(a.capacity(), b.capacity());
+        assert_eq!(a.stats().fingerprint_bits, 7);
+        for i in 0u32..200 {
+            assert!(a.contains(&i.to_le_bytes()));
+            assert!(a.contains(&(i + 10_000).to_le_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_union_into_errors() {
+        let mut narrow = VacuumFilter::new(100, 0.1).unwrap();
+        let mut wide = VacuumFilter::new(100, 0.001).unwrap();
+        wide.insert(b"kept").unwrap();
+
+        let err = wide.union_into(&narrow).unwrap_err().to_string();
+        assert!(err.contains("10 bits") && err.contains("4 bits"), "{}", err);
+        assert!(wide.contains(b"kept"));
+
+        // More fingerprints than the smaller layout holds
+        for i in 0u32..100 {
+            narrow.insert(&i.to_le_bytes()).unwrap();
+        }
+        let mut big = VacuumFilter::new(10_000, 0.1).unwrap();
+        for i in 0u32..5000 {
+            big.insert(&(i + 1000).to_le_bytes()).unwrap();
+        }
+        assert!(narrow.union_into(&big).is_err());
+        assert_eq!(narrow.len(), 100);
+    }
 }
@@ -0,0 +1,357 @@
+//! Measured accuracy of a quantile sketch against the exact answer
+//!
+//! [`accuracy_report`] feeds a stream into any [`QuantileSketch`], sorts a
+//! copy of the stream, and compares the sketch's answer at q = 0.01, 0.02,
+//! ..., 0.99 with the exact one. It reports two kinds of error, because the
+//! sketches here guarantee different things:
+//!
+//! - **Rank error**: how far the true rank of the returned value is from
+//!   `q`. KLL, REQ and GK bound this additively.
+//! - **Relative error**: `|estimate - exact| / |exact|`. DDSketch bounds
+//!   this instead, and may have a large rank error on dense data.
+
+use crate::common::QuantileSketch;
+
+/// Accuracy of one quantile query, from [`accuracy_report`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantileAccuracy {
+    /// The normalized rank that was queried
+    pub q: f64,
+    /// The sketch's answer
+    pub estimate: f64,
+    /// The exact answer: the `⌈q·n⌉`-th smallest sample
+    pub exact: f64,
+    /// Distance from `q` to the range of true ranks of `estimate`
+    ///
+    /// Zero when `q` falls within the ranks the estimate occupies, which
+    /// can be a wide range when the stream has duplicates.
+    pub rank_error: f64,
+    /// `|estimate - exact| / |exact|`, or infinite if only `exact` is zero
+    pub relative_error: f64,
+}
+
+/// Measured error of a quantile sketch over a stream
+///
+/// Built by [`accuracy_report`]. An empty stream gives a report with no
+/// points and zero error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccuracyReport {
+    /// Number of samples used (non-finite samples are skipped)
+    pub count: usize,
+    /// One entry per queried quantile, in increasing `q`
+    pub points: Vec<QuantileAccuracy>,
+    /// Largest [`rank_error`](QuantileAccuracy::rank_error) over all points
+    pub max_rank_error: f64,
+    /// Mean [`rank_error`](QuantileAccuracy::rank_error) over all points
+    pub mean_rank_error: f64,
+    /// Largest [`relative_error`](QuantileAccuracy::relative_error) over all points
+    pub max_relative_error: f64,
+}
+
+impl AccuracyReport {
+    /// Returns the point with the largest rank error
+    pub fn worst(&self) -> Option<&QuantileAccuracy> {
+        self.points
+            .iter()
+            .max_by(|a, b| a.rank_error.total_cmp(&b.rank_error))
+    }
+}
+
+/// Feeds `samples` into `sketch` and measures its error at q = 0.01..=0.99
+///
+/// The sketch should start empty so that it summarizes exactly `samples`;
+/// otherwise the comparison is against the wrong stream. NaN and ±∞ are
+/// skipped, as most sketches here do on update.
+///
+/// Costs one sort of the samples plus 99 sketch queries, so it is meant for
+/// tests and offline checks, not hot paths.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::quantiles::{accuracy_report, KllSketch};
+///
+/// let samples: Vec<f64> = (0..100_000).map(|i| ((i * 7919) % 100_000) as f64).collect();
+/// let mut kll = KllSketch::new(200).unwrap();
+///
+/// let report = accuracy_report(&mut kll, &samples);
+/// assert_eq!(report.count, 100_000);
+/// assert_eq!(report.points.len(), 99);
+/// assert!(report.max_rank_error <= kll.normalized_rank_error());
+/// ```
+pub fn accuracy_report<S: QuantileSketch + ?Sized>(
+    sketch: &mut S,
+    samples: &[f64],
+) -> AccuracyReport {
+    let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+    for &value in &sorted {
+        sketch.update(value);
+    }
+    sorted.sort_by(f64::total_cmp);
+
+    let n = sorted.len();
+    let mut points = Vec::with_capacity(99);
+    if n > 0 {
+        for i in 1..=99 {
+            let q = i as f64 / 100.0;
+            let Some(estimate) = sketch.quantile(q) else {
+                continue;
+            };
+            let exact = sorted[((q * n as f64).ceil() as usize).clamp(1, n) - 1];
+
+            let below = sorted.partition_point(|&v| v < estimate) as f64 / n as f64;
+            let at_or_below = sorted.partition_point(|&v| v <= estimate) as f64 / n as f64;
+            let rank_error = if q < below {
+                below - q
+            } else if q > at_or_below {
+                q - at_or_below
+            } else {
+                0.0
+            };
+
+            let relative_error = if estimate == exact {
+                0.0
+            } else {
+                (estimate - exact).abs() / exact.abs()
+            };
+
+            points.push(QuantileAccuracy {
+                q,
+                estimate,
+                exact,
+                rank_error,
+                relative_error,
+            });
+        }
+    }
+
+    let max_rank_error = points.iter().map(|p| p.rank_error).fold(0.0, f64::max);
+    let mean_rank_error = if points.is_empty() {
+        0.0
+    } else {
+        points.iter().map(|p| p.rank_error).sum::<f64>() / points.len() as f64
+    };
+    let max_relative_error = points.iter().map(|p| p.relative_error).fold(0.0, f64::max);
+
+    AccuracyReport {
+        count: n,
+        points,
+        max_rank_error,
+        mean_rank_error,
+        max_relative_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantiles::{
+        DDSketch, GkSketch, KllSketch, ReqMode, ReqSketch, SplineSketch, TDigest,
+    };
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    const N: usize = 1_000_000;
+
+    /// Integer-valued streams, so SplineSketch (which stores `u64`) sees
+    /// the same values as the others
+    fn streams() -> Vec<(&'static str, Vec<f64>)> {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let uniform = (0..N)
+            .map(|_| rng.random_range(0..1_000_000u32) as f64)
+            .collect();
+
+        // Zipf(s = 1.1) over 100k ranks by inverting the tabulated CDF
+        let weights: Vec<f64> = (1..=100_000).map(|r| 1.0 / (r as f64).powf(1.1)).collect();
+        let total: f64 = weights.iter().sum();
+        let mut cdf = Vec::with_capacity(weights.len());
+        let mut acc = 0.0;
+        for w in &weights {
+            acc += w / total;
+            cdf.push(acc);
+        }
+        let zipf = (0..N)
+            .map(|_| {
+                let u: f64 = rng.random();
+                (cdf.partition_point(|&c| c < u) + 1) as f64
+            })
+            .collect();
+
+        // Box-Muller, shifted to keep values positive
+        let normal = (0..N)
+            .map(|_| {
+                let (u1, u2): (f64, f64) = (rng.random(), rng.random());
+                let z = (-2.0 * (1.0 - u1).ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (1_000_000.0 + 100_000.0 * z).round()
+            })
+            .collect();
+
+        let sorted = (0..N).map(|i| i as f64).collect();
+        let reversed = (0..N).rev().map(|i| i as f64).collect();
+
+        vec![
+            ("uniform", uniform),
+            ("zipf", zipf),
+            ("normal", normal),
+            ("sorted", sorted),
+            ("reversed", reversed),
+        ]
+    }
+
+    #[test]
+    fn test_report_on_exact_answers() {
+        // A sketch that keeps everything has zero error
+        struct Exact(Vec<f64>);
+        impl QuantileSketch for Exact {
+            fn update(&mut self, value: f64) {
+                self.0.push(value);
+            }
+            fn quantile(&mut self, q: f64) -> Option<f64> {
+                self.0.sort_by(f64::total_cmp);
+                let n = self.0.len();
+                Some(self.0[((q * n as f64).ceil() as usize).clamp(1, n) - 1])
+            }
+        }
+
+        let samples: Vec<f64> = (0..1000).map(|i| (i % 37) as f64).collect();
+        let report = accuracy_report(&mut Exact(Vec::new()), &samples);
+        assert_eq!(report.count, 1000);
+        assert_eq!(report.points.len(), 99);
+        assert_eq!(report.max_rank_error, 0.0);
+        assert_eq!(report.max_relative_error, 0.0);
+
+        let empty = accuracy_report(&mut Exact(Vec::new()), &[f64::NAN]);
+        assert_eq!(empty.count, 0);
+        assert!(empty.points.is_empty());
+        assert!(empty.worst().is_none());
+    }
+
+    #[test]
+    fn test_rank_error_measures_distance_to_true_ranks() {
+        // Always answering the minimum of 0..100 is off by q - 0.01
+        struct Min;
+        impl QuantileSketch for Min {
+            fn update(&mut self, _value: f64) {}
+            fn quantile(&mut self, _q: f64) -> Option<f64> {
+                Some(0.0)
+            }
+        }
+
+        let samples: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let report = accuracy_report(&mut Min, &samples);
+        let worst = report.worst().unwrap();
+        assert_eq!(worst.q, 0.99);
+        assert!((worst.rank_error - 0.98).abs() < 1e-9);
+        assert_eq!(worst.exact, 98.0);
+        assert_eq!(report.points[0].rank_error, 0.0);
+    }
+
+    #[test]
+    fn test_rank_error_guarantees_hold() {
+        for (name, samples) in streams() {
+            let mut kll = KllSketch::new(200).unwrap();
+            let report = accuracy_report(&mut kll, &samples);
+            assert!(
+                report.max_rank_error <= kll.normalized_rank_error(),
+                "KLL on {}: {:?}",
+                name,
+                report.worst()
+            );
+
+            let mut gk = GkSketch::new(0.01).unwrap();
+            let report = accuracy_report(&mut gk, &samples);
+            assert!(
+                report.max_rank_error <= gk.epsilon(),
+                "GK on {}: {:?}",
+                name,
+                report.worst()
+            );
+
+            for mode in [ReqMode::HighRankAccuracy, ReqMode::LowRankAccuracy] {
+                let mut req = ReqSketch::new(128, mode).unwrap();
+                let report = accuracy_report(&mut req, &samples);
+                assert!(
+                    report.max_rank_error <= req.normalized_rank_error(),
+                    "REQ {:?} on {}: {:?}",
+                    mode,
+                    name,
+                    report.worst()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_relative_error_guarantee_holds() {
+        for (name, samples) in streams() {
+            let mut dd = DDSketch::new(0.01).unwrap();
+            let report = accuracy_report(&mut dd, &samples);
+            // Small tolerance for float rounding at bin boundaries
+            assert!(
+                report.max_relative_error <= 0.01 + 1e-9,
+                "DDSketch on {}: {}",
+                name,
+                report.max_relative_error
+            );
+        }
+    }
+
+    #[test]
+    fn test_tdigest_stays_close() {
+        // t-digest documents no rank bound, so this tolerance is empirical
+        for (name, samples) in streams() {
+            let mut td = TDigest::new(100.0);
+            let report = accuracy_report(&mut td, &samples);
+            assert!(
+                report.max_rank_error <= 0.01,
+                "TDigest on {}: {:?}",
+                name,
+                report.worst()
+            );
+        }
+    }
+
+    #[test]
+    fn test_spline_reported_bound_holds() {
+        for (name, samples) in streams() {
+            // 100x the sample budget compresses many times (and keeps the
+            // test fast, as every update re-sorts); max_error must still
+            // bound the error the sketch actually has
+            let long = &samples[..100 * SplineSketch::DEFAULT_MAX_SAMPLES];
+            let mut spline = SplineSketch::new(SplineSketch::DEFAULT_MAX_SAMPLES);
+            let report = accuracy_report(&mut spline, long);
+            assert!(spline.sample_count() < long.len());
+            assert!(
+                report.max_rank_error <= spline.max_error(),
+                "SplineSketch on {}: {:?} exceeds reported {}",
+                name,
+                report.worst(),
+                spline.max_error()
+            );
+            for point in &report.points {
+                let (lower, _, upper) = spline.query_with_bounds(point.q);
+                assert!(
+                    lower as f64 <= point.exact && point.exact <= upper as f64,
+                    "SplineSketch on {} at q={}: {} outside [{}, {}]",
+                    name,
+                    point.q,
+                    point.exact,
+                    lower,
+                    upper
+                );
+            }
+
+            // Before compression the reported bound is the tight one
+            let mut exact = SplineSketch::new(SplineSketch::DEFAULT_MAX_SAMPLES);
+            let report = accuracy_report(&mut exact, &samples[..SplineSketch::DEFAULT_MAX_SAMPLES]);
+            assert!(exact.max_error() < 0.01);
+            assert!(
+                report.max_rank_error <= exact.max_error(),
+                "SplineSketch on {} prefix: {:?}",
+                name,
+                report.worst()
+            );
+        }
+    }
+}
@@ -15,6 +15,9 @@
 //! - **Fast updates**: O(1) insertion
 //! - **Fast queries**: O(k) quantile retrieval where k = number of bins
 //! - **Handles all values**: Positive, negative, and zero
+//! - **Non-finite values**: NaN and ±∞ are skipped and tallied in
+//!   [`rejected_count`](DDSketch::rejected_count); [`try_add`](DDSketch::try_add)
+//!   reports them as errors instead
 //!
 //! # Use Cases
 //!
@@ -47,9 +50,93 @@
 //! - Paper: "DDSketch: A Fast and Fully-Mergeable Quantile Sketch with Relative-Error Guarantees" (VLDB 2019)
 //! - Datadog blog: https://www.datadoghq.com/blog/engineering/computing-accurate-percentiles-with-ddsketch/
 
-use crate::common::{Mergeable, Sketch, SketchError};
+use crate::common::{Mergeable, QuantileSketch, Sketch, SketchError};
 use std::collections::HashMap;
 
+/// Index mapping used to assign values to bins
+///
+/// All mappings keep the relative accuracy guarantee. The interpolated
+/// mappings approximate the logarithm from the floating-point exponent and
+/// significand instead of calling `ln`, which makes [`DDSketch::add`] faster
+/// at the cost of a few more bins for the same accuracy: about 44% more for
+/// linear interpolation and about 1% more for cubic interpolation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mapping {
+    /// Exact logarithm, the fewest bins
+    #[default]
+    Logarithmic,
+    /// Cubic interpolation of `log2` between powers of two
+    CubicallyInterpolated,
+    /// Linear interpolation of `log2` between powers of two
+    LinearlyInterpolated,
+}
+
+impl Mapping {
+    /// Coefficients of the cubic `log2` approximation on [1, 2)
+    const CUBIC_A: f64 = 6.0 / 35.0;
+    const CUBIC_B: f64 = -3.0 / 5.0;
+    const CUBIC_C: f64 = 10.0 / 7.0;
+
+    /// Ratio of the approximation's steepest slope to the exact logarithm's,
+    /// used to shrink gamma so the approximation still meets the accuracy
+    fn correcting_factor(self) -> f64 {
+        match self {
+            Mapping::Logarithmic => 1.0,
+            Mapping::CubicallyInterpolated => 7.0 / (10.0 * std::f64::consts::LN_2),
+            Mapping::LinearlyInterpolated => std::f64::consts::LOG2_E,
+        }
+    }
+
+    /// Approximate `log2(value)` for positive, finite `value`
+    fn approx_log2(self, value: f64) -> f64 {
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+        let s = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000) - 1.0;
+        let fraction = match self {
+            Mapping::CubicallyInterpolated => {
+                ((Self::CUBIC_A * s + Self::CUBIC_B) * s + Self::CUBIC_C) * s
+            }
+            _ => s,
+        };
+        exponent as f64 + fraction
+    }
+
+    /// Inverse of [`approx_log2`](Self::approx_log2)
+    fn approx_exp2(self, log: f64) -> f64 {
+        let exponent = log.floor();
+        let fraction = log - exponent;
+        let s = match self {
+            Mapping::CubicallyInterpolated => {
+                // Real root of the cubic via Cardano's formula
+                let (a, b, c) = (Self::CUBIC_A, Self::CUBIC_B, Self::CUBIC_C);
+                let d0 = b * b - 3.0 * a * c;
+                let d1 = 2.0 * b * b * b - 9.0 * a * b * c - 27.0 * a * a * fraction;
+                let p = ((d1 - (d1 * d1 - 4.0 * d0 * d0 * d0).sqrt()) / 2.0).cbrt();
+                -(b + p + d0 / p) / (3.0 * a)
+            }
+            _ => fraction,
+        };
+        (1.0 + s) * 2.0_f64.powi(exponent as i32)
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Mapping::Logarithmic => 0,
+            Mapping::CubicallyInterpolated => 1,
+            Mapping::LinearlyInterpolated => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Mapping::Logarithmic),
+            1 => Some(Mapping::CubicallyInterpolated),
+            2 => Some(Mapping::LinearlyInterpolated),
+            _ => None,
+        }
+    }
+}
+
 /// Store for binned values
 ///
 /// Maintains histogram bins with counts, along with min/max tracking.
@@ -76,6 +163,15 @@ impl Store {
         self.count += 1;
     }
 
+    /// Total count of the bins with index in `[first, last]`
+    fn count_in(&self, first: i32, last: i32) -> u64 {
+        self.bins
+            .iter()
+            .filter(|(&index, _)| index >= first && index <= last)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
     fn merge(&mut self, other: &Store) {
         for (&index, &count) in &other.bins {
             *self.bins.entry(index).or_insert(0) += count;
@@ -115,16 +211,22 @@ impl Store {
 /// ```
 ///
 /// This relative error guarantee holds for all quantiles.
+///
+/// # Thread Safety
+///
+/// `DDSketch` is `Send + Sync`, and quantile queries take `&self`.
 #[derive(Debug, Clone)]
 pub struct DDSketch {
     alpha: f64,    // Relative accuracy parameter (e.g., 0.01 = 1%)
     gamma: f64,    // Bin width: (1 + alpha) / (1 - alpha)
     gamma_ln: f64, // ln(gamma) for efficiency
     offset: f64,   // Bias for log mapping
+    mapping: Mapping,
 
     store_positive: Store, // Positive values
     store_negative: Store, // Negative values (stored as absolute values)
     zero_count: u64,       // Count of zero values
+    rejected: u64,         // Non-finite values skipped by add (not serialized)
 }
 
 impl DDSketch {
@@ -153,6 +255,31 @@ impl DDSketch {
     /// - α = 0.01 (1%): ~500 bins for 6 orders of magnitude
     /// - α = 0.001 (0.1%): ~5000 bins for 6 orders of magnitude
     pub fn new(relative_accuracy: f64) -> Result<Self, SketchError> {
+        Self::with_mapping(relative_accuracy, Mapping::Logarithmic)
+    }
+
+    /// Creates a new DDSketch with the given index mapping
+    ///
+    /// [`new`](Self::new) is equivalent to `with_mapping(alpha, Mapping::Logarithmic)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if alpha ≤ 0 or alpha ≥ 1
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::{DDSketch, Mapping};
+    ///
+    /// let mut dd = DDSketch::with_mapping(0.01, Mapping::CubicallyInterpolated).unwrap();
+    /// for i in 1..=1000 {
+    ///     dd.add(i as f64);
+    /// }
+    ///
+    /// let p50 = dd.quantile(0.5).unwrap();
+    /// assert!((p50 - 500.0).abs() <= 0.01 * 500.0);
+    /// ```
+    pub fn with_mapping(relative_accuracy: f64, mapping: Mapping) -> Result<Self, SketchError> {
         if relative_accuracy <= 0.0 || relative_accuracy >= 1.0 {
             return Err(SketchError::InvalidParameter {
                 param: "relative_accuracy".to_string(),
@@ -161,7 +288,8 @@ impl DDSketch {
             });
         }
 
-        let gamma = (1.0 + relative_accuracy) / (1.0 - relative_accuracy);
+        let gamma = ((1.0 + relative_accuracy) / (1.0 - relative_accuracy))
+            .powf(1.0 / mapping.correcting_factor());
         let gamma_ln = gamma.ln();
         let offset = 0.0; // Can be adjusted for numerical stability
 
@@ -170,14 +298,20 @@ impl DDSketch {
             gamma,
             gamma_ln,
             offset,
+            mapping,
             store_positive: Store::new(),
             store_negative: Store::new(),
             zero_count: 0,
+            rejected: 0,
         })
     }
 
     /// Updates the sketch with a new value
     ///
+    /// NaN and ±∞ have no bin, so they are skipped and counted in
+    /// [`rejected_count`](Self::rejected_count) instead. Use
+    /// [`try_add`](Self::try_add) to treat them as errors.
+    ///
     /// # Time Complexity
     ///
     /// O(1) average case (hash map insertion)
@@ -191,9 +325,15 @@ impl DDSketch {
     /// dd.add(42.0);
     /// dd.add(-10.5);
     /// dd.add(0.0);
+    /// dd.add(f64::NAN);
+    ///
+    /// assert_eq!(dd.count(), 3);
+    /// assert_eq!(dd.rejected_count(), 1);
     /// ```
     pub fn add(&mut self, value: f64) {
-        if value > 0.0 {
+        if !value.is_finite() {
+            self.rejected += 1;
+        } else if value > 0.0 {
             let index = self.key(value);
             self.store_positive.add(index);
             self.store_positive.min = self.store_positive.min.min(value);
@@ -213,10 +353,17 @@ impl DDSketch {
     /// Formula: k = ceil(log_gamma(value)) + offset
     ///
     /// This ensures that values within a factor of gamma are mapped to the same bin,
-    /// which provides the relative error guarantee.
+    /// which provides the relative error guarantee. Interpolated mappings use
+    /// `floor(approx_log2(value) / log2(gamma))` instead.
     fn key(&self, value: f64) -> i32 {
-        // DDSketch mapping: k = ceil(log_gamma(value)) + offset
-        ((value.ln() / self.gamma_ln + self.offset).ceil()) as i32
+        match self.mapping {
+            // DDSketch mapping: k = ceil(log_gamma(value)) + offset
+            Mapping::Logarithmic => ((value.ln() / self.gamma_ln + self.offset).ceil()) as i32,
+            _ => {
+                let multiplier = std::f64::consts::LN_2 / self.gamma_ln;
+                (self.mapping.approx_log2(value) * multiplier + self.offset).floor() as i32
+            }
+        }
     }
 
     /// Maps a bin index back to a representative value
@@ -225,7 +372,16 @@ impl DDSketch {
     ///
     /// This returns the geometric midpoint of the bin's range [gamma^(k-1), gamma^k],
     /// which provides better accuracy than using just the upper bound.
+    /// Interpolated mappings return the bin's lower bound scaled by `1 + alpha`.
     fn value(&self, index: i32) -> f64 {
+        if self.mapping != Mapping::Logarithmic {
+            let multiplier = std::f64::consts::LN_2 / self.gamma_ln;
+            let lower = self
+                .mapping
+                .approx_exp2((index as f64 - self.offset) / multiplier);
+            return lower * (1.0 + self.alpha);
+        }
+
         // Geometric midpoint: 2 * gamma^(k-1) / (1 + 1/gamma)
         // Simplified: 2 * gamma^(k-1) * gamma / (gamma + 1)
         // Which equals: 2 * gamma^k / (gamma + 1)
@@ -234,6 +390,48 @@ impl DDSketch {
         2.0 * gamma_k / (self.gamma + 1.0)
     }
 
+    /// Updates the sketch with a new value, rejecting non-finite values
+    ///
+    /// Strict counterpart of [`add`](Self::add): finite values are added the
+    /// same way, while NaN and ±∞ leave the sketch untouched, including
+    /// [`rejected_count`](Self::rejected_count).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `value` is NaN or infinite
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::DDSketch;
+    ///
+    /// let mut dd = DDSketch::new(0.01).unwrap();
+    /// assert!(dd.try_add(42.0).is_ok());
+    /// assert!(dd.try_add(f64::INFINITY).is_err());
+    /// assert_eq!(dd.count(), 1);
+    /// assert_eq!(dd.rejected_count(), 0);
+    /// ```
+    pub fn try_add(&mut self, value: f64) -> Result<(), SketchError> {
+        if !value.is_finite() {
+            return Err(SketchError::InvalidParameter {
+                param: "value".to_string(),
+                value: value.to_string(),
+                constraint: "must be finite".to_string(),
+            });
+        }
+        self.add(value);
+        Ok(())
+    }
+
+    /// Returns the number of non-finite values skipped by [`add`](Self::add)
+    ///
+    /// Merging adds the other sketch's rejected count. The count is kept in
+    /// memory only: serialization does not store it, so deserialized sketches
+    /// start from 0.
+    pub fn rejected_count(&self) -> u64 {
+        self.rejected
+    }
+
     /// Returns the total count of values
     ///
     /// # Example
@@ -405,6 +603,65 @@ impl DDSketch {
         Some(result)
     }
 
+    /// Estimates how many values fell in `[low, high]`
+    ///
+    /// Equivalent to `count × (cdf(high) - cdf(low⁻))`, where `cdf(low⁻)`
+    /// is the fraction strictly below `low`, evaluated at bin granularity.
+    ///
+    /// # Approximation
+    ///
+    /// A bin is counted in full when it holds any value in `[low, high]`,
+    /// so values in the two bins holding the bounds are counted even if they
+    /// lie just outside: the result can overcount values within a relative
+    /// distance `alpha` of either bound, and never undercounts. In
+    /// particular, `low == high` counts every value sharing that value's
+    /// bin. Bounds beyond [`min`](Self::min) and [`max`](Self::max) are
+    /// exact, so a range covering both returns [`count`](Self::count). Zeros
+    /// are counted exactly. Returns 0 if `low > high` or either bound is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::DDSketch;
+    ///
+    /// let mut dd = DDSketch::new(0.01).unwrap();
+    /// for i in 1..=1000 {
+    ///     dd.add(i as f64);
+    /// }
+    ///
+    /// assert_eq!(dd.count_between(0.0, 2000.0), 1000.0);
+    /// let middle = dd.count_between(250.0, 750.0);
+    /// assert!((middle - 501.0).abs() < 25.0);
+    /// ```
+    pub fn count_between(&self, low: f64, high: f64) -> f64 {
+        if low.is_nan() || high.is_nan() || low > high {
+            return 0.0;
+        }
+
+        let mut count = 0;
+        if low <= 0.0 && high >= 0.0 {
+            count += self.zero_count;
+        }
+        if high > 0.0 {
+            count += self.store_count_between(&self.store_positive, low.max(0.0), high);
+        }
+        if low < 0.0 {
+            count += self.store_count_between(&self.store_negative, (-high).max(0.0), -low);
+        }
+        count as f64
+    }
+
+    /// Count of a store's values in bins overlapping magnitudes `[low, high]`
+    fn store_count_between(&self, store: &Store, low: f64, high: f64) -> u64 {
+        if store.count == 0 || high < store.min || low > store.max {
+            return 0;
+        }
+        if low <= store.min && high >= store.max {
+            return store.count;
+        }
+        store.count_in(self.key(low.max(store.min)), self.key(high.min(store.max)))
+    }
+
     /// Returns the relative accuracy parameter (alpha)
     ///
     /// This is the same value passed to `DDSketch::new()`.
@@ -420,6 +677,16 @@ impl DDSketch {
     pub fn alpha(&self) -> f64 {
         self.alpha
     }
+
+    /// Returns the index mapping
+    pub fn mapping(&self) -> Mapping {
+        self.mapping
+    }
+
+    /// Returns the number of non-empty bins
+    pub fn num_bins(&self) -> usize {
+        self.store_positive.bins.len() + self.store_negative.bins.len()
+    }
 }
 
 impl Sketch for DDSketch {
@@ -467,6 +734,11 @@ impl Sketch for DDSketch {
             bytes.extend_from_slice(&count.to_le_bytes());
         }
 
+        // Mapping trailer, absent in bytes written before mappings existed
+        if self.mapping != Mapping::Logarithmic {
+            bytes.push(self.mapping.to_byte());
+        }
+
         bytes
     }
 
@@ -523,11 +795,19 @@ impl Sketch for DDSketch {
             neg_bins.insert(index, count);
         }
 
+        let mapping = match bytes.get(pos) {
+            None => Mapping::Logarithmic,
+            Some(&byte) => Mapping::from_byte(byte).ok_or_else(|| {
+                SketchError::DeserializationError(format!("Unknown DDSketch mapping {}", byte))
+            })?,
+        };
+
         Ok(DDSketch {
             alpha,
             gamma,
             gamma_ln,
             offset,
+            mapping,
             store_positive: Store {
                 bins: pos_bins,
                 count: pos_count,
@@ -541,6 +821,7 @@ impl Sketch for DDSketch {
                 max: neg_max,
             },
             zero_count,
+            rejected: 0,
         })
     }
 }
@@ -550,7 +831,7 @@ impl Mergeable for DDSketch {
     ///
     /// # Errors
     ///
-    /// Returns `IncompatibleSketches` if the accuracy parameters differ
+    /// Returns `IncompatibleSketches` if the accuracy parameters or mappings differ
     ///
     /// # Time Complexity
     ///
@@ -586,15 +867,357 @@ impl Mergeable for DDSketch {
                 ),
             });
         }
+        if self.mapping != other.mapping {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "Cannot merge sketches with different mappings: {:?} vs {:?}",
+                    self.mapping, other.mapping
+                ),
+            });
+        }
 
         self.store_positive.merge(&other.store_positive);
         self.store_negative.merge(&other.store_negative);
         self.zero_count += other.zero_count;
+        self.rejected += other.rejected;
 
         Ok(())
     }
 }
 
+/// Protobuf encoding compatible with DataDog's `ddsketch.proto`
+///
+/// The messages mirror the reference schema used by sketches-go and
+/// sketches-java, so the bytes can be exchanged with those implementations.
+#[cfg(feature = "ddsketch-proto")]
+mod proto {
+    use super::{DDSketch, Mapping, Store};
+    use crate::common::SketchError;
+    use prost::Message;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// `DDSketch` message
+    #[derive(Clone, PartialEq, Message)]
+    struct DDSketchProto {
+        #[prost(message, optional, tag = "1")]
+        mapping: Option<IndexMappingProto>,
+        #[prost(message, optional, tag = "2")]
+        positive_values: Option<StoreProto>,
+        #[prost(message, optional, tag = "3")]
+        negative_values: Option<StoreProto>,
+        #[prost(double, tag = "4")]
+        zero_count: f64,
+    }
+
+    /// `IndexMapping` message; `interpolation` is the `Interpolation` enum
+    #[derive(Clone, PartialEq, Message)]
+    struct IndexMappingProto {
+        #[prost(double, tag = "1")]
+        gamma: f64,
+        #[prost(double, tag = "2")]
+        index_offset: f64,
+        #[prost(int32, tag = "3")]
+        interpolation: i32,
+    }
+
+    /// `Store` message, holding sparse and/or contiguous bin counts
+    #[derive(Clone, PartialEq, Message)]
+    struct StoreProto {
+        #[prost(btree_map = "sint32, double", tag = "1")]
+        bin_counts: BTreeMap<i32, f64>,
+        #[prost(double, repeated, tag = "2")]
+        contiguous_bin_counts: Vec<f64>,
+        #[prost(sint32, tag = "3")]
+        contiguous_bin_index_offset: i32,
+    }
+
+    /// `Interpolation.NONE`, i.e. the exact logarithmic mapping
+    const INTERPOLATION_NONE: i32 = 0;
+
+    /// Reference index of a value is `floor(log_gamma(x) + offset)`, while
+    /// [`DDSketch::key`] uses `ceil`, one higher away from bin boundaries
+    const INDEX_SHIFT: i32 = 1;
+
+    impl DDSketch {
+        /// Serializes the sketch in DataDog's protobuf `DDSketch` format
+        ///
+        /// Writes the index mapping (gamma, index offset, no interpolation),
+        /// the positive and negative stores as contiguous bin counts, and
+        /// the zero count, as sketches-go does for a dense store.
+        ///
+        /// Values lying exactly on a bin boundary (a power of gamma, such as
+        /// 1.0) are binned with `ceil` here and `floor` in the reference
+        /// implementations, so they land one bin lower than the reference
+        /// would place them. That bin is still within the relative accuracy.
+        /// Min and max are not part of the format.
+        ///
+        /// # Errors
+        ///
+        /// Returns `SerializationError` for interpolated mappings, whose
+        /// index conventions are not exchanged
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use sketch_oxide::quantiles::DDSketch;
+        ///
+        /// let mut dd = DDSketch::new(0.01).unwrap();
+        /// dd.add(12.5);
+        /// let bytes = dd.to_proto_bytes().unwrap();
+        ///
+        /// let restored = DDSketch::from_proto_bytes(&bytes).unwrap();
+        /// assert_eq!(restored.count(), 1);
+        /// ```
+        pub fn to_proto_bytes(&self) -> Result<Vec<u8>, SketchError> {
+            if self.mapping != Mapping::Logarithmic {
+                return Err(SketchError::SerializationError(format!(
+                    "{:?} mapping cannot be encoded as protobuf; use Mapping::Logarithmic",
+                    self.mapping
+                )));
+            }
+
+            let message = DDSketchProto {
+                mapping: Some(IndexMappingProto {
+                    gamma: self.gamma,
+                    index_offset: self.offset,
+                    interpolation: INTERPOLATION_NONE,
+                }),
+                positive_values: Some(store_to_proto(&self.store_positive)),
+                negative_values: Some(store_to_proto(&self.store_negative)),
+                zero_count: self.zero_count as f64,
+            };
+            Ok(message.encode_to_vec())
+        }
+
+        /// Deserializes a sketch from DataDog's protobuf `DDSketch` format
+        ///
+        /// Accepts stores with sparse `binCounts`, contiguous bin counts, or
+        /// both. Counts are rounded to whole numbers. The relative accuracy
+        /// is derived from gamma, and min/max are taken as the
+        /// representative values of the outermost bins.
+        ///
+        /// # Errors
+        ///
+        /// Returns `DeserializationError` if the bytes are not a valid
+        /// message, the mapping is missing or interpolated, or a count is
+        /// negative or not finite
+        pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+            let message = DDSketchProto::decode(bytes).map_err(|e| {
+                SketchError::DeserializationError(format!("Invalid DDSketch protobuf: {}", e))
+            })?;
+
+            let mapping = message.mapping.ok_or_else(|| {
+                SketchError::DeserializationError("DDSketch protobuf has no mapping".to_string())
+            })?;
+            if mapping.interpolation != INTERPOLATION_NONE {
+                return Err(SketchError::DeserializationError(format!(
+                    "Unsupported DDSketch interpolation {}",
+                    mapping.interpolation
+                )));
+            }
+            if !mapping.gamma.is_finite() || mapping.gamma <= 1.0 {
+                return Err(SketchError::DeserializationError(format!(
+                    "Invalid DDSketch gamma {}",
+                    mapping.gamma
+                )));
+            }
+            if !mapping.index_offset.is_finite() {
+                return Err(SketchError::DeserializationError(format!(
+                    "Invalid DDSketch index offset {}",
+                    mapping.index_offset
+                )));
+            }
+
+            let alpha = (mapping.gamma - 1.0) / (mapping.gamma + 1.0);
+            let mut sketch = DDSketch::with_mapping(alpha, Mapping::Logarithmic)
+                .map_err(|e| SketchError::DeserializationError(e.to_string()))?;
+            sketch.gamma = mapping.gamma;
+            sketch.gamma_ln = mapping.gamma.ln();
+            sketch.offset = mapping.index_offset;
+
+            sketch.store_positive = sketch.store_from_proto(message.positive_values)?;
+            sketch.store_negative = sketch.store_from_proto(message.negative_values)?;
+            sketch.zero_count = count_from_proto(message.zero_count)?;
+
+            Ok(sketch)
+        }
+
+        /// Rebuilds a store from reference bin indices
+        fn store_from_proto(&self, proto: Option<StoreProto>) -> Result<Store, SketchError> {
+            let mut store = Store::new();
+            let Some(proto) = proto else {
+                return Ok(store);
+            };
+
+            let contiguous = proto
+                .contiguous_bin_counts
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| (proto.contiguous_bin_index_offset as i64 + i as i64, count));
+            let sparse = proto
+                .bin_counts
+                .iter()
+                .map(|(&index, &count)| (index as i64, count));
+
+            let mut bins: HashMap<i32, u64> = HashMap::new();
+            for (index, count) in contiguous.chain(sparse) {
+                let count = count_from_proto(count)?;
+                if count == 0 {
+                    continue;
+                }
+                let key = i32::try_from(index + INDEX_SHIFT as i64).map_err(|_| {
+                    SketchError::DeserializationError(format!("Bin index {} out of range", index))
+                })?;
+                *bins.entry(key).or_insert(0) += count;
+                store.count += count;
+            }
+
+            if let (Some(&low), Some(&high)) = (bins.keys().min(), bins.keys().max()) {
+                store.min = self.value(low);
+                store.max = self.value(high);
+            }
+            store.bins = bins;
+            Ok(store)
+        }
+    }
+
+    /// Encodes a store as contiguous counts from its lowest to highest bin
+    fn store_to_proto(store: &Store) -> StoreProto {
+        let (Some(&low), Some(&high)) = (store.bins.keys().min(), store.bins.keys().max()) else {
+            return StoreProto::default();
+        };
+
+        let mut counts = vec![0.0; (high as i64 - low as i64 + 1) as usize];
+        for (&key, &count) in &store.bins {
+            counts[(key as i64 - low as i64) as usize] = count as f64;
+        }
+
+        StoreProto {
+            bin_counts: BTreeMap::new(),
+            contiguous_bin_counts: counts,
+            contiguous_bin_index_offset: low - INDEX_SHIFT,
+        }
+    }
+
+    /// Converts a protobuf count to a whole count
+    fn count_from_proto(count: f64) -> Result<u64, SketchError> {
+        if !count.is_finite() || count < 0.0 {
+            return Err(SketchError::DeserializationError(format!(
+                "Invalid DDSketch bin count {}",
+                count
+            )));
+        }
+        Ok(count.round() as u64)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Reference encoding of a sketch with relative accuracy 0.01 holding
+        /// 1.01, 1.03, 1.03, -10.0, 0.0 and 0.0: gamma only in the mapping,
+        /// positive bins [1, 2] from index 0, negative bin [1] at index 115
+        /// (sint32 zigzag 0xe6 0x01) and a zero count of 2.
+        const FIXTURE: &str = "0a0909fd4a815abf52f03f12121210000000000000f03f000000000000004\
+                               01a0d1208000000000000f03f18e601210000000000000040";
+
+        fn fixture_bytes() -> Vec<u8> {
+            (0..FIXTURE.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&FIXTURE[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        fn fixture_sketch() -> DDSketch {
+            let mut dd = DDSketch::new(0.01).unwrap();
+            for value in [1.01, 1.03, 1.03, -10.0, 0.0, 0.0] {
+                dd.add(value);
+            }
+            dd
+        }
+
+        #[test]
+        fn test_proto_matches_fixture() {
+            assert_eq!(fixture_sketch().to_proto_bytes().unwrap(), fixture_bytes());
+        }
+
+        #[test]
+        fn test_proto_reads_fixture() {
+            let dd = DDSketch::from_proto_bytes(&fixture_bytes()).unwrap();
+            assert_eq!(dd.count(), 6);
+            assert!((dd.alpha() - 0.01).abs() < 1e-12);
+
+            let expected = fixture_sketch();
+            for q in [0.0, 0.2, 0.4, 0.6, 0.8, 1.0] {
+                assert_eq!(dd.quantile(q), expected.quantile(q), "q={}", q);
+            }
+            assert!((dd.min().unwrap() + 10.0).abs() <= 0.1);
+            assert!((dd.max().unwrap() - 1.03).abs() <= 0.011);
+        }
+
+        #[test]
+        fn test_proto_round_trip_and_sparse_bins() {
+            let mut dd = DDSketch::new(0.02).unwrap();
+            for i in 1..=1000 {
+                dd.add(i as f64 * 0.37);
+                dd.add(-(i as f64));
+            }
+            let restored = DDSketch::from_proto_bytes(&dd.to_proto_bytes().unwrap()).unwrap();
+            assert_eq!(restored.count(), dd.count());
+            for q in [0.01, 0.25, 0.5, 0.75, 0.99] {
+                assert_eq!(restored.quantile(q), dd.quantile(q));
+            }
+
+            // Sparse binCounts are accepted alongside contiguous counts
+            let message = DDSketchProto {
+                mapping: Some(IndexMappingProto {
+                    gamma: dd.gamma,
+                    index_offset: 0.0,
+                    interpolation: INTERPOLATION_NONE,
+                }),
+                positive_values: Some(StoreProto {
+                    bin_counts: BTreeMap::from([(10, 2.0), (20, 1.0)]),
+                    contiguous_bin_counts: vec![3.0],
+                    contiguous_bin_index_offset: 10,
+                }),
+                negative_values: None,
+                zero_count: 0.0,
+            };
+            let sparse = DDSketch::from_proto_bytes(&message.encode_to_vec()).unwrap();
+            assert_eq!(sparse.count(), 6);
+            assert_eq!(sparse.num_bins(), 2);
+        }
+
+        #[test]
+        fn test_proto_rejects_unsupported() {
+            let cubic = DDSketch::with_mapping(0.01, Mapping::CubicallyInterpolated).unwrap();
+            assert!(cubic.to_proto_bytes().is_err());
+
+            let interpolated = DDSketchProto {
+                mapping: Some(IndexMappingProto {
+                    gamma: 1.02,
+                    index_offset: 0.0,
+                    interpolation: 3,
+                }),
+                ..Default::default()
+            };
+            assert!(DDSketch::from_proto_bytes(&interpolated.encode_to_vec()).is_err());
+            assert!(DDSketch::from_proto_bytes(&DDSketchProto::default().encode_to_vec()).is_err());
+            assert!(DDSketch::from_proto_bytes(&[0xff, 0xff]).is_err());
+        }
+    }
+}
+
+impl QuantileSketch for DDSketch {
+    fn update(&mut self, value: f64) {
+        self.add(value);
+    }
+
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        DDSketch::quantile(self, q)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -622,6 +1245,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_interpolated_key_value_inverse() {
+        for mapping in [
+            Mapping::CubicallyInterpolated,
+            Mapping::LinearlyInterpolated,
+        ] {
+            let dd = DDSketch::with_mapping(0.01, mapping).unwrap();
+            for i in -200..200 {
+                let original = 1.07_f64.powi(i);
+                let recovered = dd.value(dd.key(original));
+                let relative_error = (recovered - original).abs() / original;
+                assert!(
+                    relative_error <= 0.01 + 1e-12,
+                    "{:?}: {} -> {}, error {}",
+                    mapping,
+                    original,
+                    recovered,
+                    relative_error
+                );
+            }
+
+            // The cubic approximation and its inverse agree
+            for log in [-3.7, -1.0, 0.0, 0.25, 0.5, 5.9] {
+                let back = mapping.approx_log2(mapping.approx_exp2(log));
+                assert!(
+                    (back - log).abs() < 1e-9,
+                    "{:?}: {} -> {}",
+                    mapping,
+                    log,
+                    back
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_between() {
+        let mut dd = DDSketch::new(0.01).unwrap();
+        // Exponential(1) quantiles: half the mass lies below ln 2
+        let n = 10_000;
+        for i in 0..n {
+            dd.add(-(1.0 - (i as f64 + 0.5) / n as f64).ln());
+        }
+
+        let below_median = dd.count_between(0.0, std::f64::consts::LN_2);
+        assert!((below_median - 5000.0).abs() < 100.0, "{}", below_median);
+        // The bin holding 3.0 spans ~0.06 and is counted in full: ~30 extra
+        let tail = dd.count_between(3.0, 100.0);
+        let expected = n as f64 * (-3.0f64).exp();
+        assert!(tail >= expected - 1.0 && tail < expected + 40.0, "{}", tail);
+
+        // Bounds beyond min and max are exact
+        assert_eq!(dd.count_between(-1.0, 1e9), n as f64);
+        assert_eq!(dd.count_between(1e9, 2e9), 0.0);
+        assert_eq!(dd.count_between(2.0, 1.0), 0.0);
+        assert_eq!(dd.count_between(f64::NAN, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_count_between_signs_and_equal_bounds() {
+        let mut dd = DDSketch::new(0.01).unwrap();
+        for _ in 0..10 {
+            dd.add(5.0);
+        }
+        for _ in 0..3 {
+            dd.add(100.0);
+        }
+        dd.add(-3.0);
+        dd.add(0.0);
+        dd.add(0.0);
+
+        assert_eq!(dd.count_between(5.0, 5.0), 10.0);
+        assert_eq!(dd.count_between(0.0, 0.0), 2.0);
+        assert_eq!(dd.count_between(-5.0, 0.0), 3.0);
+        assert_eq!(dd.count_between(-5.0, -1.0), 1.0);
+        assert_eq!(dd.count_between(1.0, 50.0), 10.0);
+        assert_eq!(dd.count_between(-3.0, 100.0), 16.0);
+        assert_eq!(dd.count_between(6.0, 99.0), 0.0);
+    }
+
     #[test]
     fn test_gamma_calculation() {
         let dd = DDSketch::new(0.01).unwrap();
@@ -0,0 +1,580 @@
+//! Greenwald-Khanna: deterministic ε-approximate quantiles (SIGMOD 2001)
+//!
+//! KLL and REQ bound their rank error with high probability, and T-Digest
+//! and SplineSketch are heuristic. [`GkSketch`] instead guarantees, for every
+//! input order, that each answer is within `ε·n` ranks of the truth.
+//!
+//! # Algorithm
+//!
+//! The summary is a sorted list of tuples `(v, g, Δ)`. The smallest possible
+//! rank of `v` is the sum of `g` up to and including its tuple, and the largest
+//! exceeds that by `Δ`. Keeping `g + Δ <= ⌊2εn⌋` for every tuple means any
+//! rank can be answered by a tuple whose rank range lies within `ε·n` of it.
+//! Every `⌊1/(2ε)⌋` insertions, adjacent tuples are combined wherever the
+//! combined tuple still satisfies the invariant.
+//!
+//! # Memory
+//!
+//! The original analysis bounds the summary at O((1/ε)·log(εn)) tuples of
+//! 24 bytes. This implementation uses the simpler greedy compression common
+//! in practice, which keeps the rank guarantee and stays within that bound on
+//! real inputs, but is not proven to.
+//!
+//! # References
+//!
+//! Greenwald, M. and Khanna, S. "Space-Efficient Online Computation of
+//! Quantile Summaries". SIGMOD 2001. Merging follows Greenwald and Khanna,
+//! "Power-Conserving Computation of Order-Statistics over Sensor Networks",
+//! PODS 2004.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::quantiles::GkSketch;
+//!
+//! let mut gk = GkSketch::new(0.01).unwrap();
+//! for i in 1..=10_000 {
+//!     gk.update(i as f64);
+//! }
+//!
+//! // Within 1% of 10,000 ranks of the true median, whatever the input order
+//! let median = gk.quantile(0.5).unwrap();
+//! assert!((median - 5_000.0).abs() <= 100.0);
+//! assert!((gk.rank(2_500.0) - 0.25).abs() <= 0.01);
+//! ```
+
+use crate::common::validation::{validate_header, validate_min_size, write_header, HEADER_LEN};
+use crate::common::{Mergeable, QuantileSketch, Sketch, SketchError};
+
+/// Magic bytes prefixed to serialized GK sketches
+const SERIAL_MAGIC: &[u8; 4] = b"GKQS";
+
+/// Current serialization format version
+const SERIAL_VERSION: u8 = 1;
+
+/// Serialized size of epsilon, n and the tuple count
+const SERIAL_FIELDS_LEN: usize = 24;
+
+/// Serialized size of one tuple
+const SERIAL_TUPLE_LEN: usize = 24;
+
+/// Summary entry: a retained value and its rank uncertainty
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tuple {
+    /// Retained value
+    value: f64,
+    /// Minimum rank of `value` minus that of the previous tuple
+    g: u64,
+    /// Maximum rank of `value` minus its minimum rank
+    delta: u64,
+}
+
+/// Greenwald-Khanna quantile summary
+///
+/// # Thread Safety
+///
+/// `GkSketch` is `Send + Sync`. Queries take `&self`; updates and merges
+/// take `&mut self`.
+#[derive(Clone, Debug)]
+pub struct GkSketch {
+    /// Rank error bound as a fraction of `n`
+    epsilon: f64,
+    /// Summary tuples sorted by value
+    tuples: Vec<Tuple>,
+    /// Number of values summarized
+    n: u64,
+    /// Insertions since the last compression
+    since_compress: u64,
+}
+
+impl GkSketch {
+    /// Creates an empty sketch with the given rank error bound
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - Maximum rank error as a fraction of the count, in (0, 1);
+    ///   0.01 answers every query within 1% of the count
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `epsilon` is not in (0, 1).
+    pub fn new(epsilon: f64) -> Result<Self, SketchError> {
+        if !(epsilon > 0.0 && epsilon < 1.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "epsilon".to_string(),
+                value: epsilon.to_string(),
+                constraint: "must be in (0, 1)".to_string(),
+            });
+        }
+        Ok(Self {
+            epsilon,
+            tuples: Vec::new(),
+            n: 0,
+            since_compress: 0,
+        })
+    }
+
+    /// Rank error bound as a fraction of the count
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Number of values summarized
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+
+    /// Number of tuples retained
+    pub fn num_tuples(&self) -> usize {
+        self.tuples.len()
+    }
+
+    /// Adds a value; non-finite values are ignored
+    pub fn update(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+
+        // New tuples go after equal values, so a repeated maximum stays exact
+        let index = self.tuples.partition_point(|t| t.value <= value);
+        let delta = if index == 0 || index == self.tuples.len() {
+            0
+        } else {
+            let next = self.tuples[index];
+            next.g + next.delta - 1
+        };
+        self.tuples.insert(index, Tuple { value, g: 1, delta });
+        self.n += 1;
+
+        self.since_compress += 1;
+        if self.since_compress >= self.compress_interval() {
+            self.compress();
+        }
+    }
+
+    /// Returns a value whose rank is within `ε·n` of `q·n`
+    ///
+    /// `q` is clamped to [0, 1]; 0 and 1 return the exact minimum and maximum.
+    /// Returns `None` if the sketch is empty.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let last = self.tuples.last()?;
+        let q = q.clamp(0.0, 1.0);
+        if q == 1.0 {
+            return Some(last.value);
+        }
+
+        let n = self.n as f64;
+        let target = (q * n).ceil().max(1.0);
+        let slack = self.epsilon * n;
+        let mut rmin = 0;
+        for tuple in &self.tuples {
+            rmin += tuple.g;
+            let rmax = rmin + tuple.delta;
+            if target - rmin as f64 <= slack && rmax as f64 - target <= slack {
+                return Some(tuple.value);
+            }
+        }
+        Some(last.value)
+    }
+
+    /// Estimated fraction of values `<= value`, within `ε` of the truth
+    ///
+    /// Values below the minimum and at or above the maximum have exact ranks
+    /// 0 and 1. Returns 0 for an empty sketch.
+    pub fn rank(&self, value: f64) -> f64 {
+        let index = self.tuples.partition_point(|t| t.value <= value);
+        if index == 0 {
+            return 0.0;
+        }
+        if index == self.tuples.len() {
+            return 1.0;
+        }
+
+        // The count lies between the minimum rank of the last tuple <= value
+        // and one less than the maximum rank of the next
+        let lower: u64 = self.tuples[..index].iter().map(|t| t.g).sum();
+        let next = self.tuples[index];
+        let upper = lower + next.g + next.delta - 1;
+        (lower + upper) as f64 / 2.0 / self.n as f64
+    }
+
+    /// Serializes the sketch to bytes
+    ///
+    /// Layout: magic `"GKQS"` and a version byte, then epsilon, n and the
+    /// tuple count, followed by each tuple's value, g and Δ; all fields are
+    /// 8 bytes little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN + SERIAL_FIELDS_LEN + self.tuples.len() * SERIAL_TUPLE_LEN,
+        );
+        write_header(&mut bytes, SERIAL_MAGIC, SERIAL_VERSION);
+        bytes.extend_from_slice(&self.epsilon.to_le_bytes());
+        bytes.extend_from_slice(&self.n.to_le_bytes());
+        bytes.extend_from_slice(&(self.tuples.len() as u64).to_le_bytes());
+        for tuple in &self.tuples {
+            bytes.extend_from_slice(&tuple.value.to_le_bytes());
+            bytes.extend_from_slice(&tuple.g.to_le_bytes());
+            bytes.extend_from_slice(&tuple.delta.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a sketch written by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SketchError> {
+        let payload = validate_header(bytes, SERIAL_MAGIC, SERIAL_VERSION)?;
+        validate_min_size(payload.len(), SERIAL_FIELDS_LEN)?;
+
+        let read_u64 = |at: usize| u64::from_le_bytes(payload[at..at + 8].try_into().unwrap());
+        let epsilon = f64::from_bits(read_u64(0));
+        let n = read_u64(8);
+        let num_tuples = read_u64(16);
+
+        let mut sketch = Self::new(epsilon).map_err(|_| {
+            SketchError::DeserializationError(format!("Invalid epsilon {}", epsilon))
+        })?;
+
+        let body = &payload[SERIAL_FIELDS_LEN..];
+        if num_tuples.checked_mul(SERIAL_TUPLE_LEN as u64) != Some(body.len() as u64) {
+            return Err(SketchError::DeserializationError(format!(
+                "Expected {} tuples, got {} bytes",
+                num_tuples,
+                body.len()
+            )));
+        }
+
+        let mut total = 0u64;
+        for chunk in body.chunks_exact(SERIAL_TUPLE_LEN) {
+            let field = |at: usize| u64::from_le_bytes(chunk[at..at + 8].try_into().unwrap());
+            let tuple = Tuple {
+                value: f64::from_bits(field(0)),
+                g: field(8),
+                delta: field(16),
+            };
+            let sorted = sketch.tuples.last().is_none_or(|t| t.value <= tuple.value);
+            if !tuple.value.is_finite() || tuple.g == 0 || tuple.delta > n || !sorted {
+                return Err(SketchError::DeserializationError(
+                    "Invalid GK tuple".to_string(),
+                ));
+            }
+            total = total.saturating_add(tuple.g);
+            sketch.tuples.push(tuple);
+        }
+        if total != n {
+            return Err(SketchError::DeserializationError(format!(
+                "Tuple weights sum to {}, expected {}",
+                total, n
+            )));
+        }
+
+        sketch.n = n;
+        Ok(sketch)
+    }
+
+    /// Largest `g + Δ` a tuple may have
+    fn threshold(&self) -> u64 {
+        (2.0 * self.epsilon * self.n as f64).floor() as u64
+    }
+
+    /// Insertions between compressions
+    fn compress_interval(&self) -> u64 {
+        ((1.0 / (2.0 * self.epsilon)).floor() as u64).max(1)
+    }
+
+    /// Folds tuples into their successors wherever the invariant allows
+    ///
+    /// The minimum is never folded, so it stays exact.
+    fn compress(&mut self) {
+        self.since_compress = 0;
+        let threshold = self.threshold();
+
+        // Right to left; `kept` holds the tuples to the right, reversed
+        let mut kept: Vec<Tuple> = Vec::with_capacity(self.tuples.len());
+        for (index, &tuple) in self.tuples.iter().enumerate().rev() {
+            if let Some(next) = kept.last_mut() {
+                if index > 0 && tuple.g + next.g + next.delta <= threshold {
+                    next.g += tuple.g;
+                    continue;
+                }
+            }
+            kept.push(tuple);
+        }
+        kept.reverse();
+        self.tuples = kept;
+    }
+}
+
+/// `(value, rmin, rmax)` of each tuple
+fn rank_ranges(tuples: &[Tuple]) -> Vec<(f64, u64, u64)> {
+    let mut rmin = 0;
+    tuples
+        .iter()
+        .map(|t| {
+            rmin += t.g;
+            (t.value, rmin, rmin + t.delta)
+        })
+        .collect()
+}
+
+impl Sketch for GkSketch {
+    type Item = f64;
+
+    fn update(&mut self, item: &Self::Item) {
+        self.update(*item);
+    }
+
+    /// Median estimate, or 0 for an empty sketch
+    fn estimate(&self) -> f64 {
+        self.quantile(0.5).unwrap_or(0.0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self, SketchError> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl Mergeable for GkSketch {
+    /// Merges another GK sketch into this one
+    ///
+    /// Each tuple's rank range in the union is its range in its own sketch
+    /// plus the ranks the other sketch places below it, so the merged sketch
+    /// keeps the `ε` guarantee over the combined count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the epsilons differ.
+    fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        if self.epsilon != other.epsilon {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("epsilon mismatch: {} vs {}", self.epsilon, other.epsilon),
+            });
+        }
+        if other.n == 0 {
+            return Ok(());
+        }
+        if self.n == 0 {
+            *self = other.clone();
+            return Ok(());
+        }
+
+        let a = rank_ranges(&self.tuples);
+        let b = rank_ranges(&other.tuples);
+        // Rank range of a tuple from `own` with `before` tuples of `peer` ahead of it
+        let combined =
+            |own: (f64, u64, u64), peer: &[(f64, u64, u64)], peer_n: u64, before: usize| {
+                let below = if before > 0 { peer[before - 1].1 } else { 0 };
+                let above = peer.get(before).map_or(peer_n, |next| next.2 - 1);
+                (own.0, own.1 + below, own.2 + above)
+            };
+
+        let mut merged = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            // Ties go to `self` first, consistently on both sides
+            if j == b.len() || (i < a.len() && a[i].0 <= b[j].0) {
+                merged.push(combined(a[i], &b, other.n, j));
+                i += 1;
+            } else {
+                merged.push(combined(b[j], &a, self.n, i));
+                j += 1;
+            }
+        }
+
+        let mut previous = 0;
+        self.tuples = merged
+            .into_iter()
+            .map(|(value, rmin, rmax)| {
+                let g = rmin - previous;
+                previous = rmin;
+                Tuple {
+                    value,
+                    g,
+                    delta: rmax - rmin,
+                }
+            })
+            .collect();
+        self.n += other.n;
+        self.compress();
+        Ok(())
+    }
+}
+
+impl QuantileSketch for GkSketch {
+    fn update(&mut self, value: f64) {
+        GkSketch::update(self, value);
+    }
+
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        GkSketch::quantile(self, q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts every answer is within `ε·n` ranks for the values in `data`
+    fn assert_rank_guarantee(gk: &GkSketch, data: &[f64]) {
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len() as f64;
+        let slack = gk.epsilon() * n + 1e-9;
+
+        for step in 0..=200 {
+            let q = step as f64 / 200.0;
+            let value = gk.quantile(q).unwrap();
+            // 1-based ranks the returned value occupies
+            let lowest = sorted.partition_point(|&v| v < value) as f64 + 1.0;
+            let highest = sorted.partition_point(|&v| v <= value) as f64;
+            let target = (q * n).ceil().max(1.0);
+            assert!(
+                lowest - slack <= target && target <= highest + slack,
+                "q={} returned {} with ranks [{}, {}], target {}",
+                q,
+                value,
+                lowest,
+                highest,
+                target
+            );
+        }
+
+        for &value in sorted.iter().step_by((sorted.len() / 500).max(1)) {
+            let truth = sorted.partition_point(|&v| v <= value) as f64 / n;
+            let error = (gk.rank(value) - truth).abs();
+            assert!(
+                error <= gk.epsilon() + 1e-9,
+                "rank({}) off by {}",
+                value,
+                error
+            );
+        }
+    }
+
+    /// Input orders that defeat naive sampling or bucketing
+    fn adversarial_streams(n: usize) -> Vec<(&'static str, Vec<f64>)> {
+        let ascending: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let descending: Vec<f64> = ascending.iter().rev().copied().collect();
+        // Alternates between the two ends, closing in on the middle
+        let zigzag = (0..n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    (i / 2) as f64
+                } else {
+                    (n - 1 - i / 2) as f64
+                }
+            })
+            .collect();
+        // Every value is a new extreme, alternately low and high
+        let middle_out = (0..n)
+            .map(|i| if i % 2 == 0 { -(i as f64) } else { i as f64 })
+            .collect();
+        let duplicates = (0..n).map(|i| (i % 7) as f64).collect();
+        let blocks = (0..n).map(|i| ((i * 31) % n / 100) as f64).collect();
+        vec![
+            ("ascending", ascending),
+            ("descending", descending),
+            ("zigzag", zigzag),
+            ("middle_out", middle_out),
+            ("duplicates", duplicates),
+            ("blocks", blocks),
+        ]
+    }
+
+    #[test]
+    fn test_invalid_epsilon() {
+        assert!(GkSketch::new(0.0).is_err());
+        assert!(GkSketch::new(1.0).is_err());
+        assert!(GkSketch::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_empty() {
+        let gk = GkSketch::new(0.01).unwrap();
+        assert!(gk.is_empty());
+        assert_eq!(gk.quantile(0.5), None);
+        assert_eq!(gk.rank(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_extremes_exact() {
+        let mut gk = GkSketch::new(0.05).unwrap();
+        for i in 0..10_000 {
+            gk.update(((i * 7919) % 10_000) as f64);
+        }
+        gk.update(f64::NAN);
+
+        assert_eq!(gk.count(), 10_000);
+        assert_eq!(gk.quantile(0.0), Some(0.0));
+        assert_eq!(gk.quantile(1.0), Some(9_999.0));
+        assert_eq!(gk.rank(-1.0), 0.0);
+        assert_eq!(gk.rank(9_999.0), 1.0);
+    }
+
+    #[test]
+    fn test_rank_error_on_adversarial_inputs() {
+        for epsilon in [0.1, 0.01] {
+            for (name, data) in adversarial_streams(20_000) {
+                let mut gk = GkSketch::new(epsilon).unwrap();
+                for &value in &data {
+                    gk.update(value);
+                }
+                assert_rank_guarantee(&gk, &data);
+                assert!(
+                    gk.num_tuples() < 2_000,
+                    "{}: {} tuples at epsilon {}",
+                    name,
+                    gk.num_tuples(),
+                    epsilon
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_guarantee() {
+        for (_, data) in adversarial_streams(20_000) {
+            // Uneven shards, each fed a different slice of the stream
+            let mut shards: Vec<GkSketch> = (0..4).map(|_| GkSketch::new(0.01).unwrap()).collect();
+            for (i, &value) in data.iter().enumerate() {
+                shards[(i * i) % 4].update(value);
+            }
+
+            let mut merged = GkSketch::new(0.01).unwrap();
+            for shard in &shards {
+                merged.merge(shard).unwrap();
+            }
+            assert_eq!(merged.count(), data.len() as u64);
+            assert_rank_guarantee(&merged, &data);
+        }
+    }
+
+    #[test]
+    fn test_merge_epsilon_mismatch() {
+        let mut a = GkSketch::new(0.01).unwrap();
+        let b = GkSketch::new(0.02).unwrap();
+        assert!(a.merge(&b).is_err());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let mut gk = GkSketch::new(0.01).unwrap();
+        for i in 0..5_000 {
+            gk.update((i % 977) as f64 * 0.5);
+        }
+
+        let restored = GkSketch::from_bytes(&gk.to_bytes()).unwrap();
+        assert_eq!(restored.count(), gk.count());
+        assert_eq!(restored.tuples, gk.tuples);
+
+        let mut bytes = gk.to_bytes();
+        bytes.truncate(bytes.len() - 1);
+        assert!(GkSketch::from_bytes(&bytes).is_err());
+    }
+}
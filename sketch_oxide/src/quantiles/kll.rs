@@ -49,13 +49,21 @@
 //! println!("Median: {:?}, P99: {:?}", median, p99);
 //! ```
 
-use crate::common::{Mergeable, Sketch, SketchError};
+use super::compaction_rank_error;
+use super::otel::{self, OtelExpoHistogram, OTEL_DEFAULT_MAX_BUCKETS};
+use crate::common::{Mergeable, QuantileSketch, Sketch, SketchError};
 
 /// KLL Sketch for quantile estimation
 ///
 /// Provides absolute error guarantees: for any quantile q, the returned
 /// value is within ε × n ranks of the true answer, where ε ≈ 1/k.
 ///
+/// # Thread Safety
+///
+/// `KllSketch` is `Send + Sync`. `quantile`, `rank`, `cdf` and `to_bytes` take
+/// `&mut self` because they sort the levels lazily, so a shared sketch
+/// needs a `Mutex` even for reads.
+///
 /// # Examples
 ///
 /// ```
@@ -171,6 +179,75 @@ impl KllSketch {
         1.65 / self.k as f64
     }
 
+    /// Returns the normalized rank error bound at a specific rank
+    ///
+    /// Ranks 0 and 1 answer with the exact minimum and maximum, so their
+    /// error is zero, as is every rank before the first compaction.
+    /// Elsewhere this is the tighter of [`normalized_rank_error`] and the
+    /// deterministic bound implied by the compactions the levels record.
+    ///
+    /// [`normalized_rank_error`]: KllSketch::normalized_rank_error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let mut kll = KllSketch::new(200).unwrap();
+    /// for i in 0..100_000 {
+    ///     kll.update(i as f64);
+    /// }
+    ///
+    /// assert_eq!(kll.normalized_rank_error_at(1.0), 0.0);
+    /// assert!(kll.normalized_rank_error_at(0.5) <= kll.normalized_rank_error());
+    /// ```
+    pub fn normalized_rank_error_at(&self, rank: f64) -> f64 {
+        if rank <= 0.0 || rank >= 1.0 {
+            return 0.0;
+        }
+
+        let levels: Vec<(usize, usize)> = self
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(h, items)| (items.len(), self.level_capacity(h)))
+            .collect();
+        compaction_rank_error(&levels, self.n).min(self.normalized_rank_error())
+    }
+
+    /// Returns the k implied by the fullest level
+    ///
+    /// Level `h` is meant to hold fewer than `capacity(h)` items, where level
+    /// 0 holds `k`. This scales each level's retained count back to a level-0
+    /// equivalent, `retained_h * k / capacity(h)`, and returns the largest.
+    /// A sketch fed only by [`update`](KllSketch::update) stays at or below
+    /// `k`. Merges only compact when level 0 overflows, so they can leave
+    /// higher levels far over capacity: the sketch then uses more memory
+    /// than configured, and its accuracy no longer matches
+    /// [`normalized_rank_error`](KllSketch::normalized_rank_error). An
+    /// effective k well above `k` is a signal to rebuild the sketch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let mut kll = KllSketch::new(200).unwrap();
+    /// for i in 0..100_000 {
+    ///     kll.update(i as f64);
+    /// }
+    /// assert!(kll.effective_k() <= 200);
+    /// ```
+    pub fn effective_k(&self) -> usize {
+        let k = self.k as usize;
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(h, items)| (items.len() * k).div_ceil(self.level_capacity(h)))
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Returns the number of items retained in the sketch
     pub fn num_retained(&self) -> usize {
         self.levels.iter().map(|l| l.len()).sum()
@@ -210,6 +287,52 @@ impl KllSketch {
         }
     }
 
+    /// Updates the sketch with `weight` copies of a value
+    ///
+    /// Equivalent to calling [`update`](Self::update) `weight` times, but in
+    /// O(log weight): the value is stored once per set bit of `weight`, at
+    /// the level whose items carry that power of two. Ranks, quantiles and
+    /// the CDF are then taken over the total weight, so a weighted stream
+    /// and its unit-weight expansion give the same answers up to the
+    /// sketch's rank error. Non-finite values and a weight of 0 are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let mut kll = KllSketch::new(200).unwrap();
+    /// kll.update(1.0);
+    /// kll.update_weighted(5.0, 999);
+    ///
+    /// assert_eq!(kll.count(), 1000);
+    /// assert!((kll.rank(4.0) - 0.001).abs() < 1e-9);
+    /// ```
+    pub fn update_weighted(&mut self, value: f64, weight: u64) {
+        if !value.is_finite() || weight == 0 {
+            return;
+        }
+
+        self.n += weight;
+        self.min_value = self.min_value.min(value);
+        self.max_value = self.max_value.max(value);
+
+        let mut bits = weight;
+        while bits != 0 {
+            let level = bits.trailing_zeros() as usize;
+            if level >= self.levels.len() {
+                self.levels.resize_with(level + 1, Vec::new);
+            }
+            self.levels[level].push(value);
+            bits &= bits - 1;
+        }
+        self.needs_sort = true;
+
+        for level in 0..self.levels.len() {
+            self.compact_from(level);
+        }
+    }
+
     /// Returns the capacity for a given level
     fn level_capacity(&self, level: usize) -> usize {
         // Level 0 has capacity k, each subsequent level has 2x
@@ -219,8 +342,11 @@ impl KllSketch {
 
     /// Compacts the sketch when level 0 overflows
     fn compact(&mut self) {
-        let mut level = 0;
+        self.compact_from(0);
+    }
 
+    /// Compacts `level` and the levels above it while they overflow
+    fn compact_from(&mut self, mut level: usize) {
         while level < self.levels.len() && self.levels[level].len() >= self.level_capacity(level) {
             // Sort level if needed
             self.levels[level].sort_by(|a, b| a.partial_cmp(b).unwrap());
@@ -310,7 +436,7 @@ impl KllSketch {
         items.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
 
         // Find the item at the target rank
-        let target = (rank * self.n as f64) as u64;
+        let target = (rank * self.retained_weight() as f64) as u64;
         let mut cumulative = 0u64;
 
         for (value, weight) in items {
@@ -331,33 +457,86 @@ impl KllSketch {
     ///
     /// # Returns
     ///
-    /// Estimated fraction of values <= given value (0.0 to 1.0)
+    /// Estimated fraction of the total weight at values <= given value
+    /// (0.0 to 1.0); unit updates have weight 1
     pub fn rank(&mut self, value: f64) -> f64 {
         if self.n == 0 {
             return 0.0;
         }
 
-        if value <= self.min_value {
-            return 0.0;
-        }
         if value >= self.max_value {
             return 1.0;
         }
+        if value < self.min_value {
+            return 0.0;
+        }
 
         self.ensure_sorted();
 
-        // Count weighted items less than or equal to value
-        let mut count = 0u64;
-        for (level, level_items) in self.levels.iter().enumerate() {
-            let weight = 1u64 << level;
-            for &item in level_items {
-                if item <= value {
-                    count += weight;
-                }
-            }
+        self.weight_at_or_below(value) as f64 / self.retained_weight() as f64
+    }
+
+    /// Returns the rank of a value with its guaranteed error interval
+    ///
+    /// The interval is the estimated normalized rank ± [`normalized_rank_error`],
+    /// clamped to [0, 1]; the true fraction of values <= `value` lies inside it
+    /// under the same guarantee that bounds [`quantile`] answers. Values below
+    /// the minimum or at/above the maximum have exact ranks 0 and 1, and an
+    /// empty sketch returns (0, 0).
+    ///
+    /// [`normalized_rank_error`]: KllSketch::normalized_rank_error
+    /// [`quantile`]: KllSketch::quantile
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let mut kll = KllSketch::new(200).unwrap();
+    /// for i in 0..10_000 {
+    ///     kll.update(i as f64);
+    /// }
+    ///
+    /// let (lower, upper) = kll.rank_with_bounds(2_500.0);
+    /// assert!(lower <= 0.25 && 0.25 <= upper);
+    /// assert!(upper - lower <= 2.0 * kll.normalized_rank_error());
+    /// ```
+    pub fn rank_with_bounds(&self, value: f64) -> (f64, f64) {
+        if self.n == 0 || value < self.min_value {
+            return (0.0, 0.0);
+        }
+        if value >= self.max_value {
+            return (1.0, 1.0);
         }
 
-        count as f64 / self.n as f64
+        let rank = self.weight_at_or_below(value) as f64 / self.retained_weight() as f64;
+        let eps = self.normalized_rank_error();
+        ((rank - eps).max(0.0), (rank + eps).min(1.0))
+    }
+
+    /// Total weight of all retained items
+    ///
+    /// Compacting an odd-length level keeps the extra item, so this drifts
+    /// from `n` by a few items per compaction. Ranks are normalized by it
+    /// rather than by `n` so that they always reach 1 at the maximum.
+    fn retained_weight(&self) -> u64 {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(level, items)| (items.len() as u64) << level)
+            .sum()
+    }
+
+    /// Total weight of retained items less than or equal to `value`
+    fn weight_at_or_below(&self, value: f64) -> u64 {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(level, items)| {
+                let below = items.iter().filter(|&&item| item <= value).count() as u64;
+                below << level
+            })
+            .sum()
     }
 
     /// Returns the CDF (cumulative distribution function)
@@ -383,17 +562,101 @@ impl KllSketch {
         items.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
 
         // Compute cumulative ranks
+        let total = self.retained_weight() as f64;
         let mut result = Vec::with_capacity(items.len());
         let mut cumulative = 0u64;
 
         for (value, weight) in items {
             cumulative += weight;
-            result.push((value, cumulative as f64 / self.n as f64));
+            result.push((value, cumulative as f64 / total));
         }
 
         result
     }
 
+    /// Exports the sketch as an OpenTelemetry exponential histogram
+    ///
+    /// Each bucket receives the count times the rank mass between its
+    /// boundaries, so the buckets carry the sketch's rank error on top of the
+    /// bucketing itself. See [`otel`](super::otel) for what the conversion
+    /// loses.
+    ///
+    /// `scale` is a maximum: it is lowered until the positive and the
+    /// negative buckets each fit in [`OTEL_DEFAULT_MAX_BUCKETS`], and the
+    /// histogram reports the scale it used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `scale` is outside `[-10, 20]`, the range
+    /// OpenTelemetry allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::KllSketch;
+    ///
+    /// let mut kll = KllSketch::new(200).unwrap();
+    /// for value in [-3.0, 0.0, 1.5, 3.0] {
+    ///     kll.update(value);
+    /// }
+    ///
+    /// // Scale 0: buckets (1, 2], (2, 4] and, for negatives, [-4, -2)
+    /// let histogram = kll.to_otel_exponential(0).unwrap();
+    /// assert_eq!(histogram.zero_count, 1);
+    /// assert_eq!(histogram.positive.offset, 0);
+    /// assert_eq!(histogram.positive.bucket_counts, vec![1, 1]);
+    /// assert_eq!(histogram.negative.offset, 1);
+    /// assert_eq!(histogram.negative.bucket_counts, vec![1]);
+    /// ```
+    pub fn to_otel_exponential(&self, scale: i32) -> Result<OtelExpoHistogram, SketchError> {
+        self.to_otel_exponential_with_max_buckets(scale, OTEL_DEFAULT_MAX_BUCKETS)
+    }
+
+    /// Exports the sketch as an OpenTelemetry exponential histogram with at
+    /// most `max_buckets` positive and `max_buckets` negative buckets
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `scale` is outside `[-10, 20]` or
+    /// `max_buckets` is below 2, the smallest limit OpenTelemetry SDKs accept.
+    pub fn to_otel_exponential_with_max_buckets(
+        &self,
+        scale: i32,
+        max_buckets: usize,
+    ) -> Result<OtelExpoHistogram, SketchError> {
+        otel::validate(scale, max_buckets)?;
+        if self.n == 0 {
+            return Ok(OtelExpoHistogram::empty(scale));
+        }
+
+        let retained = self.retained_weight() as f64;
+        let sum = self
+            .levels
+            .iter()
+            .enumerate()
+            .flat_map(|(level, items)| items.iter().map(move |&item| item * (1u64 << level) as f64))
+            .sum::<f64>()
+            * (self.n as f64 / retained);
+
+        Ok(OtelExpoHistogram::from_cdf(
+            scale,
+            max_buckets,
+            self.n,
+            sum,
+            (self.min_value, self.max_value),
+            self.levels.iter().flatten().copied(),
+            |x| {
+                if x >= self.max_value {
+                    1.0
+                } else if x < self.min_value {
+                    0.0
+                } else {
+                    self.weight_at_or_below(x) as f64 / retained
+                }
+            },
+        ))
+    }
+
     /// Serializes the KLL Sketch to bytes
     pub fn to_bytes(&mut self) -> Vec<u8> {
         self.ensure_sorted();
@@ -545,6 +808,16 @@ impl Mergeable for KllSketch {
 /// Type alias for float sketches (common use case)
 pub type KllFloatSketch = KllSketch;
 
+impl QuantileSketch for KllSketch {
+    fn update(&mut self, value: f64) {
+        KllSketch::update(self, value);
+    }
+
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        KllSketch::quantile(self, q)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -631,4 +904,176 @@ mod tests {
         assert_eq!(kll.k(), restored.k());
         assert_eq!(kll.count(), restored.count());
     }
+
+    #[test]
+    fn test_normalized_rank_error_at() {
+        let mut kll = KllSketch::new(50).unwrap();
+        for i in 0..40 {
+            kll.update(i as f64);
+        }
+        assert_eq!(kll.normalized_rank_error_at(0.5), 0.0);
+
+        let n = 100_000u64;
+        for i in 0..n {
+            kll.update((i.wrapping_mul(2_654_435_761) % n) as f64);
+        }
+
+        let bound = kll.normalized_rank_error_at(0.5);
+        assert!(bound > 0.0 && bound <= kll.normalized_rank_error());
+        assert_eq!(kll.normalized_rank_error_at(0.0), 0.0);
+        assert_eq!(kll.normalized_rank_error_at(1.0), 0.0);
+
+        for q in [0.01, 0.25, 0.5, 0.75, 0.99] {
+            let value = kll.quantile(q).unwrap();
+            let observed = (value / n as f64 - q).abs();
+            assert!(observed <= kll.normalized_rank_error_at(q));
+        }
+    }
+
+    #[test]
+    fn test_rank_with_bounds() {
+        let mut kll = KllSketch::new(200).unwrap();
+        assert_eq!(kll.rank_with_bounds(1.0), (0.0, 0.0));
+
+        let n = 100_000u64;
+        for i in 0..n {
+            kll.update((i.wrapping_mul(2_654_435_761) % n) as f64);
+        }
+
+        for value in [100.0, 10_000.0, 33_333.0, 50_000.0, 90_000.0, 99_900.0] {
+            let true_rank = (value + 1.0) / n as f64;
+            let (lower, upper) = kll.rank_with_bounds(value);
+            assert!(
+                lower <= true_rank && true_rank <= upper,
+                "true rank {} outside [{}, {}]",
+                true_rank,
+                lower,
+                upper
+            );
+            assert!(upper - lower <= 2.0 * kll.normalized_rank_error() + 1e-12);
+            assert!((kll.rank(value) - (lower + upper) / 2.0).abs() <= kll.normalized_rank_error());
+        }
+
+        assert_eq!(kll.rank_with_bounds(-1.0), (0.0, 0.0));
+        assert_eq!(kll.rank_with_bounds(n as f64), (1.0, 1.0));
+        let (lower, upper) = kll.rank_with_bounds(1.0);
+        assert_eq!(lower, 0.0);
+        assert!(upper > 0.0);
+    }
+
+    #[test]
+    fn test_effective_k_after_merges() {
+        let mut kll = KllSketch::new(200).unwrap();
+        assert_eq!(kll.effective_k(), 0);
+        for i in 0..1_000_000 {
+            kll.update(i as f64);
+        }
+        assert!(kll.effective_k() <= 200);
+
+        // Merges only compact from level 0, leaving upper levels overfull
+        let mut merged = KllSketch::new(200).unwrap();
+        for j in 0..50 {
+            let mut part = KllSketch::new(200).unwrap();
+            for i in 0..5000 {
+                part.update((i * 50 + j) as f64);
+            }
+            merged.merge(&part).unwrap();
+        }
+        assert!(merged.effective_k() > 200);
+    }
+
+    #[test]
+    fn test_weighted_rank_matches_unit_updates() {
+        let mut weighted = KllSketch::new(200).unwrap();
+        let mut unit = KllSketch::new(200).unwrap();
+        weighted.update_weighted(5.0, 1000);
+        for _ in 0..1000 {
+            unit.update(5.0);
+        }
+        assert_eq!(weighted.count(), unit.count());
+        for value in [4.0, 5.0, 6.0] {
+            assert_eq!(weighted.rank(value), unit.rank(value), "rank({})", value);
+        }
+        assert_eq!(weighted.rank(5.0), 1.0);
+
+        // Surrounded by unit-weight values the ranks still agree
+        for sketch in [&mut weighted, &mut unit] {
+            for _ in 0..1000 {
+                sketch.update(1.0);
+                sketch.update(9.0);
+            }
+        }
+        for value in [1.0, 3.0, 5.0, 7.0] {
+            assert_eq!(weighted.rank(value), unit.rank(value), "rank({})", value);
+        }
+        assert!((weighted.rank(5.0) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(weighted.cdf().last().unwrap().1, 1.0);
+
+        weighted.update_weighted(f64::NAN, 10);
+        weighted.update_weighted(1.0, 0);
+        assert_eq!(weighted.count(), 3000);
+    }
+
+    #[test]
+    fn test_to_otel_exponential_matches_true_histogram() {
+        let mut kll = KllSketch::new(200).unwrap();
+        let values: Vec<f64> = (0..20_000).map(|i| (i - 10_000) as f64 / 10.0).collect();
+        for &value in &values {
+            kll.update(value);
+        }
+
+        let histogram = kll.to_otel_exponential(1).unwrap();
+        assert_eq!(histogram.count, 20_000);
+        assert_eq!(histogram.min, Some(-1000.0));
+        assert_eq!(histogram.max, Some(999.9));
+        let total: u64 = histogram.zero_count
+            + histogram.positive.bucket_counts.iter().sum::<u64>()
+            + histogram.negative.bucket_counts.iter().sum::<u64>();
+        assert_eq!(total, 20_000);
+
+        // Each bucket is off by at most the rank error at both boundaries
+        let tolerance = (2.0 * kll.normalized_rank_error() * 20_000.0).ceil() as u64;
+        let base = 2f64.sqrt();
+        for (offset, &count) in histogram.positive.bucket_counts.iter().enumerate() {
+            let index = histogram.positive.offset + offset as i32;
+            let (lower, upper) = (base.powi(index), base.powi(index + 1));
+            let truth = values.iter().filter(|&&v| v > lower && v <= upper).count() as u64;
+            if offset > 0 && offset + 1 < histogram.positive.bucket_counts.len() {
+                assert!(
+                    count.abs_diff(truth) <= tolerance,
+                    "bucket {}: {} vs {}",
+                    index,
+                    count,
+                    truth
+                );
+            }
+        }
+        let negatives: u64 = histogram.negative.bucket_counts.iter().sum();
+        assert!(negatives.abs_diff(10_000) <= tolerance);
+    }
+
+    #[test]
+    fn test_to_otel_exponential_rejects_scale() {
+        let kll = KllSketch::new(200).unwrap();
+        assert!(kll.to_otel_exponential(21).is_err());
+        assert!(kll.to_otel_exponential(-11).is_err());
+        assert!(kll.to_otel_exponential_with_max_buckets(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_to_otel_exponential_caps_buckets() {
+        let mut kll = KllSketch::new(200).unwrap();
+        for i in 0..1000 {
+            kll.update(1.5f64.powi(i - 500));
+        }
+
+        let histogram = kll.to_otel_exponential(20).unwrap();
+        assert!(histogram.scale < 20);
+        assert!(histogram.positive.bucket_counts.len() <= OTEL_DEFAULT_MAX_BUCKETS);
+        assert_eq!(histogram.positive.bucket_counts.iter().sum::<u64>(), 1000);
+
+        let histogram = kll.to_otel_exponential_with_max_buckets(20, 10).unwrap();
+        assert!(histogram.positive.bucket_counts.len() <= 10);
+        assert_eq!(histogram.positive.bucket_counts.iter().sum::<u64>(), 1000);
+    }
 }
@@ -0,0 +1,159 @@
+//! Kolmogorov-Smirnov distance between quantile sketches
+//!
+//! Compares two distributions summarized as sketches by the largest gap
+//! between their CDFs, `sup |CDF_a(x) - CDF_b(x)|`. Useful for drift
+//! detection, e.g. comparing the control and treatment arms of an A/B test.
+//!
+//! # Accuracy
+//!
+//! The result is approximate on two counts:
+//!
+//! - Each CDF carries its sketch's rank error, so the distance can be off by
+//!   up to the sum of both sketches' errors.
+//! - The supremum is only taken over evaluation points at evenly spaced
+//!   quantiles of both sketches. Between neighbouring points each CDF moves
+//!   by at most about `1 / EVALUATION_QUANTILES`, which bounds what the grid
+//!   can miss.
+
+use super::{KllSketch, TDigest};
+
+/// Evenly spaced quantiles taken from each sketch as evaluation points
+const EVALUATION_QUANTILES: usize = 100;
+
+/// Approximate Kolmogorov-Smirnov distance between two KLL sketches
+///
+/// Returns `sup |CDF_a(x) - CDF_b(x)|` in `[0, 1]`, evaluated at the 0th,
+/// 1st, ..., 100th percentiles of both sketches, or `None` if either sketch
+/// is empty, since an empty sketch has no CDF to compare. Both sketches are
+/// cloned, since rank queries sort internally.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::quantiles::{ks_distance, KllSketch};
+///
+/// let mut a = KllSketch::new(200).unwrap();
+/// let mut b = KllSketch::new(200).unwrap();
+/// for i in 0..1000 {
+///     a.update(i as f64);
+///     b.update((i + 500) as f64);
+/// }
+///
+/// // Half of each distribution lies outside the other
+/// assert!((ks_distance(&a, &b).unwrap() - 0.5).abs() < 0.05);
+/// assert_eq!(ks_distance(&a, &KllSketch::new(200).unwrap()), None);
+/// ```
+pub fn ks_distance(a: &KllSketch, b: &KllSketch) -> Option<f64> {
+    if a.count() == 0 || b.count() == 0 {
+        return None;
+    }
+
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut points = evaluation_points(|q| a.quantile(q).unwrap_or(0.0));
+    points.extend(evaluation_points(|q| b.quantile(q).unwrap_or(0.0)));
+    Some(sup_distance(&points, |x| a.rank(x), |x| b.rank(x)))
+}
+
+/// Approximate Kolmogorov-Smirnov distance between two t-digests
+///
+/// Returns `sup |CDF_a(x) - CDF_b(x)|` in `[0, 1]`, evaluated at the 0th,
+/// 1st, ..., 100th percentiles of both digests, or `None` if either digest
+/// is empty. Both digests are cloned, since queries flush their buffers.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::quantiles::{tdigest_ks_distance, TDigest};
+///
+/// let mut a = TDigest::new(100.0);
+/// let mut b = TDigest::new(100.0);
+/// for i in 0..1000 {
+///     a.update(i as f64);
+///     b.update(i as f64);
+/// }
+///
+/// assert!(tdigest_ks_distance(&a, &b).unwrap() < 0.02);
+/// ```
+pub fn tdigest_ks_distance(a: &TDigest, b: &TDigest) -> Option<f64> {
+    if a.count() == 0.0 || b.count() == 0.0 {
+        return None;
+    }
+
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut points = evaluation_points(|q| a.quantile(q));
+    points.extend(evaluation_points(|q| b.quantile(q)));
+    Some(sup_distance(&points, |x| a.cdf(x), |x| b.cdf(x)))
+}
+
+/// Values at the evenly spaced quantiles `0, 1/N, ..., 1`
+fn evaluation_points(mut quantile: impl FnMut(f64) -> f64) -> Vec<f64> {
+    (0..=EVALUATION_QUANTILES)
+        .map(|i| quantile(i as f64 / EVALUATION_QUANTILES as f64))
+        .collect()
+}
+
+/// Largest CDF gap over the given points
+fn sup_distance(
+    points: &[f64],
+    mut cdf_a: impl FnMut(f64) -> f64,
+    mut cdf_b: impl FnMut(f64) -> f64,
+) -> f64 {
+    points
+        .iter()
+        .map(|&x| (cdf_a(x) - cdf_b(x)).abs())
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kll_of(values: impl Iterator<Item = f64>) -> KllSketch {
+        let mut kll = KllSketch::new(200).unwrap();
+        values.for_each(|v| kll.update(v));
+        kll
+    }
+
+    fn tdigest_of(values: impl Iterator<Item = f64>) -> TDigest {
+        let mut td = TDigest::new(100.0);
+        values.for_each(|v| td.update(v));
+        td
+    }
+
+    #[test]
+    fn test_kll_ks_distance() {
+        let a = kll_of((0..10_000).map(|i| i as f64));
+        let same = kll_of((0..10_000).rev().map(|i| i as f64));
+        let shifted = kll_of((2_500..12_500).map(|i| i as f64));
+        let disjoint = kll_of((20_000..30_000).map(|i| i as f64));
+
+        assert!(ks_distance(&a, &same).unwrap() < 0.03);
+        assert!((ks_distance(&a, &shifted).unwrap() - 0.25).abs() < 0.03);
+        assert!(ks_distance(&a, &disjoint).unwrap() > 0.97);
+        assert_eq!(ks_distance(&a, &shifted), ks_distance(&shifted, &a));
+    }
+
+    #[test]
+    fn test_tdigest_ks_distance() {
+        let a = tdigest_of((0..10_000).map(|i| i as f64));
+        let same = tdigest_of((0..10_000).rev().map(|i| i as f64));
+        let shifted = tdigest_of((2_500..12_500).map(|i| i as f64));
+        let disjoint = tdigest_of((20_000..30_000).map(|i| i as f64));
+
+        assert!(tdigest_ks_distance(&a, &same).unwrap() < 0.03);
+        assert!((tdigest_ks_distance(&a, &shifted).unwrap() - 0.25).abs() < 0.03);
+        assert!(tdigest_ks_distance(&a, &disjoint).unwrap() > 0.97);
+    }
+
+    #[test]
+    fn test_ks_distance_empty() {
+        let a = kll_of((0..100).map(|i| i as f64));
+        let empty = KllSketch::new(200).unwrap();
+        assert_eq!(ks_distance(&a, &empty), None);
+        assert_eq!(ks_distance(&empty, &a), None);
+        assert_eq!(
+            tdigest_ks_distance(&TDigest::new(100.0), &TDigest::new(100.0)),
+            None
+        );
+    }
+}
@@ -6,6 +6,7 @@
 //! # Algorithms
 //!
 //! - [`DDSketch`] - Relative error guarantees (VLDB 2019)
+//! - [`GkSketch`] - Deterministic rank error guarantees (SIGMOD 2001)
 //! - [`ReqSketch`] - Zero error at p100 (HRA) or p0 (LRA) (PODS 2021)
 //!
 //! # Choosing an Algorithm
@@ -39,6 +40,13 @@
 //! - Space: O(k log(n/k)) where k is configurable
 //! - Production-proven (Google BigQuery, Apache DataSketches)
 //!
+//! # Measuring Accuracy
+//!
+//! KLL, t-digest, DDSketch, REQ, GK and SplineSketch implement
+//! [`QuantileSketch`](crate::common::QuantileSketch), so [`accuracy_report`]
+//! can replay your own data through any of them and report its actual rank
+//! and relative error against the exact quantiles.
+//!
 //! # Examples
 //!
 //! ## DDSketch Example
@@ -80,14 +88,105 @@
 //! assert!(p99 >= 9800.0); // Close to true p99 = 9900
 //! ```
 
+mod accuracy;
 mod ddsketch;
+mod gk;
 mod kll;
+mod ks;
+pub mod otel;
 pub mod req;
 mod spline_sketch;
+mod stream_summary;
 mod tdigest;
 
-pub use ddsketch::DDSketch;
+pub use accuracy::{accuracy_report, AccuracyReport, QuantileAccuracy};
+pub use ddsketch::{DDSketch, Mapping};
+pub use gk::GkSketch;
 pub use kll::{KllFloatSketch, KllSketch};
+pub use ks::{ks_distance, tdigest_ks_distance};
+pub use otel::{OtelBuckets, OtelExpoHistogram};
 pub use req::{ReqMode, ReqSketch};
 pub use spline_sketch::SplineSketch;
+pub use stream_summary::StreamSummary;
 pub use tdigest::TDigest;
+
+use crate::common::SketchError;
+
+/// Worst-case memory in bytes for a KLL sketch with the given rank error over `n` items
+///
+/// The accuracy parameter is `k = ⌈1.65 / rank_error⌉` (at least
+/// [`KllSketch::MIN_K`]), matching [`KllSketch::normalized_rank_error`].
+/// Level `h` of [`KllSketch`] holds at most `k·2^h` items and receives at
+/// most `n/2^h` (nothing until level `h-1` has filled), so the sketch
+/// retains at most `Σ_h min(k·2^h, n/2^h)` values of 8 bytes each. Unlike HyperLogLog or
+/// Count-Min, the footprint grows with the stream (roughly as `√(n·k)`
+/// once `n ≫ k`), which is why `n` is needed. The bound is conservative:
+/// typical sketches retain a third to a half of it.
+///
+/// # Errors
+/// Returns `InvalidParameter` if `rank_error` is not in (0, 1), or if it
+/// needs a `k` above [`KllSketch::MAX_K`].
+///
+/// # Examples
+/// ```
+/// use sketch_oxide::quantiles::kll_bytes_for;
+///
+/// // 1% rank error (k = 165); small streams are stored whole
+/// assert_eq!(kll_bytes_for(0.01, 100).unwrap(), 100 * 8);
+/// assert!(kll_bytes_for(0.01, 1_000_000).unwrap() < kll_bytes_for(0.001, 1_000_000).unwrap());
+/// ```
+pub fn kll_bytes_for(rank_error: f64, n: u64) -> Result<usize, SketchError> {
+    if !(rank_error > 0.0 && rank_error < 1.0) {
+        return Err(SketchError::InvalidParameter {
+            param: "rank_error".to_string(),
+            value: rank_error.to_string(),
+            constraint: "must be in (0, 1)".to_string(),
+        });
+    }
+
+    let k = (1.65 / rank_error).ceil().max(KllSketch::MIN_K as f64);
+    if k > KllSketch::MAX_K as f64 {
+        return Err(SketchError::InvalidParameter {
+            param: "rank_error".to_string(),
+            value: rank_error.to_string(),
+            constraint: format!("needs k = {} (max {})", k, KllSketch::MAX_K),
+        });
+    }
+
+    let k = k as u64;
+    let mut retained = 0u64;
+    for h in 0..64 {
+        let capacity = k.saturating_mul(1 << h);
+        let reaching = n >> h;
+        retained = retained.saturating_add(capacity.min(reaching));
+        // The next level only fills once this one has compacted
+        if reaching < capacity {
+            break;
+        }
+    }
+
+    Ok((retained as usize).saturating_mul(std::mem::size_of::<f64>()))
+}
+
+/// Deterministic rank error bound for a compactor hierarchy
+///
+/// `levels[h]` is `(retained, capacity)` for the level whose items weigh
+/// `2^h`. A compaction at level `h` halves at least `capacity` items and
+/// shifts any rank by at most `2^h`. Items that ever entered a level are
+/// bounded by `E_h = retained_h + 2 * E_{h+1}`, so level `h` compacted at
+/// most `2 * E_{h+1} / capacity_h` times. Returns the summed shift over `n`.
+pub(crate) fn compaction_rank_error(levels: &[(usize, usize)], n: u64) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+
+    let mut entered_above = 0.0;
+    let mut error = 0.0;
+    for (h, &(retained, capacity)) in levels.iter().enumerate().rev() {
+        let compactions = (2.0 * entered_above / capacity as f64).floor();
+        error += compactions * (1u64 << h) as f64;
+        entered_above = retained as f64 + 2.0 * entered_above;
+    }
+
+    (error / n as f64).min(1.0)
+}
@@ -0,0 +1,337 @@
+//! OpenTelemetry exponential histogram export for quantile sketches
+//!
+//! OpenTelemetry's exponential histogram data point describes a distribution
+//! with a `scale` and three sets of counts: a zero bucket, and positive and
+//! negative buckets whose boundaries grow by a factor of
+//! `base = 2^(2^-scale)`. Positive bucket `i` covers `(base^i, base^(i+1)]`;
+//! negative bucket `i` covers the mirror image `[-base^(i+1), -base^i)`.
+//!
+//! [`TDigest::to_otel_exponential`] and [`KllSketch::to_otel_exponential`]
+//! fill these buckets from the sketch's CDF: each bucket receives the total
+//! count times the CDF mass between its boundaries.
+//!
+//! # Lossy Conversion
+//!
+//! The result approximates the histogram of the original stream, not the
+//! sketch itself, and cannot be turned back into a sketch:
+//!
+//! - Bucket counts inherit the sketch's rank error, and are rounded to
+//!   integers with cumulative rounding so that they still add up to `count`.
+//! - Within a bucket, values lose all but their bucket index, so the
+//!   relative error of any value read back is up to `base - 1`.
+//! - The zero bucket has a threshold of 0 and only holds mass the CDF puts
+//!   exactly at zero; a t-digest interpolating across zero puts none there.
+//!   Towards zero, buckets continue past the smallest retained magnitude
+//!   until less than half a value lies closer to zero, and that remainder is
+//!   folded into the innermost bucket, so the buckets stay finite.
+//! - `sum` is estimated from the retained values and their weights.
+//!
+//! # Bucket Limit
+//!
+//! The number of buckets doubles with each increment of `scale`: a range of
+//! values spanning a factor of `2^m` needs about `m * 2^scale` buckets. Like
+//! the OpenTelemetry SDKs, the export treats the requested scale as a
+//! maximum and lowers it until the positive and the negative buckets each
+//! fit in [`OTEL_DEFAULT_MAX_BUCKETS`] (or the limit passed to the
+//! `_with_max_buckets` variants); the histogram's `scale` is the one used.
+//! The scale goes no lower than [`OTEL_MIN_SCALE`], where every finite value
+//! fits in three buckets.
+//!
+//! [`TDigest::to_otel_exponential`]: super::TDigest::to_otel_exponential
+//! [`KllSketch::to_otel_exponential`]: super::KllSketch::to_otel_exponential
+
+use crate::common::SketchError;
+
+/// Smallest scale allowed by the OpenTelemetry data model
+pub const OTEL_MIN_SCALE: i32 = -10;
+
+/// Largest scale allowed by the OpenTelemetry data model
+pub const OTEL_MAX_SCALE: i32 = 20;
+
+/// Default limit on positive and on negative buckets, as in the OpenTelemetry SDKs
+pub const OTEL_DEFAULT_MAX_BUCKETS: usize = 160;
+
+/// Smallest bucket limit the OpenTelemetry SDKs accept
+const OTEL_MIN_MAX_BUCKETS: usize = 2;
+
+/// Contiguous run of exponential histogram buckets
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OtelBuckets {
+    /// Index of the first bucket in `bucket_counts`
+    pub offset: i32,
+    /// Counts of buckets `offset`, `offset + 1`, ...
+    pub bucket_counts: Vec<u64>,
+}
+
+/// OpenTelemetry exponential histogram data point
+///
+/// Field names follow `ExponentialHistogramDataPoint` in the OpenTelemetry
+/// metrics protocol. See the [module documentation](self) for how a sketch
+/// is mapped onto it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtelExpoHistogram {
+    /// Resolution: bucket boundaries grow by `2^(2^-scale)`
+    pub scale: i32,
+    /// Total number of values
+    pub count: u64,
+    /// Estimated sum of all values
+    pub sum: f64,
+    /// Smallest value seen, `None` for an empty sketch
+    pub min: Option<f64>,
+    /// Largest value seen, `None` for an empty sketch
+    pub max: Option<f64>,
+    /// Values with magnitude at most this go into the zero bucket (always 0)
+    pub zero_threshold: f64,
+    /// Number of values equal to zero
+    pub zero_count: u64,
+    /// Buckets for positive values
+    pub positive: OtelBuckets,
+    /// Buckets for negative values, indexed by magnitude
+    pub negative: OtelBuckets,
+}
+
+impl OtelExpoHistogram {
+    /// Histogram with no values
+    pub(crate) fn empty(scale: i32) -> Self {
+        Self {
+            scale,
+            count: 0,
+            sum: 0.0,
+            min: None,
+            max: None,
+            zero_threshold: 0.0,
+            zero_count: 0,
+            positive: OtelBuckets::default(),
+            negative: OtelBuckets::default(),
+        }
+    }
+
+    /// Builds a histogram from a sketch's CDF
+    ///
+    /// `cdf(x)` returns the fraction of values `<= x` and must reach 1 at
+    /// `max`. `points` are the values the sketch retains; the buckets near
+    /// zero start from the smallest positive and largest negative ones.
+    /// `scale` is lowered until each sign fits in `max_buckets`; both must
+    /// have passed [`validate`].
+    pub(crate) fn from_cdf(
+        scale: i32,
+        max_buckets: usize,
+        count: u64,
+        sum: f64,
+        (min, max): (f64, f64),
+        points: impl IntoIterator<Item = f64>,
+        mut cdf: impl FnMut(f64) -> f64,
+    ) -> Self {
+        let mut result = Self::empty(scale);
+        if count == 0 {
+            return result;
+        }
+        result.count = count;
+        result.sum = sum;
+        result.min = Some(min);
+        result.max = Some(max);
+
+        let (mut smallest_positive, mut largest_negative) = (f64::INFINITY, f64::NEG_INFINITY);
+        for point in points.into_iter().chain([min, max]) {
+            if point > 0.0 {
+                smallest_positive = smallest_positive.min(point);
+            } else if point < 0.0 {
+                largest_negative = largest_negative.max(point);
+            }
+        }
+
+        // Each lower scale merges pairs of buckets, roughly halving the span
+        let max_span = max_buckets.min(i32::MAX as usize) as i32;
+        let span = |low: f64, high: f64, scale: i32| {
+            bucket_index(high, scale) - bucket_index(low, scale) + 1
+        };
+        let mut scale = scale;
+        while scale > OTEL_MIN_SCALE
+            && ((max > 0.0 && span(smallest_positive, max, scale) > max_span)
+                || (min < 0.0 && span(-largest_negative, -min, scale) > max_span))
+        {
+            scale -= 1;
+        }
+        result.scale = scale;
+
+        // Cumulative rounding keeps every count non-negative and the total exact
+        let n = count as f64;
+        let mut rounded = |x: f64| (n * cdf(x).clamp(0.0, 1.0)).round() as u64;
+        let below_zero = rounded((-0.0f64).next_down());
+        let at_or_below_zero = rounded(0.0);
+        result.zero_count = at_or_below_zero - below_zero;
+
+        if max > 0.0 {
+            let mut first = bucket_index(smallest_positive, scale);
+            let last = bucket_index(max, scale);
+            while last - first + 1 < max_span
+                && rounded(lower_boundary(first, scale)) > at_or_below_zero
+            {
+                first -= 1;
+            }
+            let mut previous = at_or_below_zero;
+            result.positive = OtelBuckets {
+                offset: first,
+                bucket_counts: (first..=last)
+                    .map(|i| {
+                        let upper = if i == last {
+                            max
+                        } else {
+                            lower_boundary(i + 1, scale)
+                        };
+                        let cumulative = rounded(upper).max(previous);
+                        let bucket = cumulative - previous;
+                        previous = cumulative;
+                        bucket
+                    })
+                    .collect(),
+            };
+        }
+
+        if min < 0.0 {
+            let mut first = bucket_index(-largest_negative, scale);
+            let last = bucket_index(-min, scale);
+            while last - first + 1 < max_span && rounded(-lower_boundary(first, scale)) < below_zero
+            {
+                first -= 1;
+            }
+            let mut previous = 0;
+            result.negative = OtelBuckets {
+                offset: first,
+                bucket_counts: (first..=last)
+                    .map(|i| {
+                        // Everything in [-upper, 0) has been counted by bucket i
+                        let cumulative = if i == last {
+                            below_zero
+                        } else {
+                            below_zero - rounded((-lower_boundary(i + 1, scale)).next_down())
+                        };
+                        let cumulative = cumulative.max(previous);
+                        let bucket = cumulative - previous;
+                        previous = cumulative;
+                        bucket
+                    })
+                    .collect(),
+            };
+        }
+
+        result
+    }
+}
+
+/// Lower boundary `base^index` of a bucket, exclusive for positive values
+fn lower_boundary(index: i32, scale: i32) -> f64 {
+    (index as f64 * (-scale as f64).exp2()).exp2()
+}
+
+/// Index of the bucket `(base^i, base^(i+1)]` holding a positive value
+fn bucket_index(value: f64, scale: i32) -> i32 {
+    let index = (value.log2() * (scale as f64).exp2()).ceil() as i32 - 1;
+    // log2 can land a hair off at exact boundaries
+    if value <= lower_boundary(index, scale) {
+        index - 1
+    } else if value > lower_boundary(index + 1, scale) {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Checks `scale` against the OpenTelemetry data model and `max_buckets`
+/// against the smallest limit the SDKs accept
+pub(crate) fn validate(scale: i32, max_buckets: usize) -> Result<(), SketchError> {
+    if !(OTEL_MIN_SCALE..=OTEL_MAX_SCALE).contains(&scale) {
+        return Err(SketchError::InvalidParameter {
+            param: "scale".to_string(),
+            value: scale.to_string(),
+            constraint: format!("must be in [{}, {}]", OTEL_MIN_SCALE, OTEL_MAX_SCALE),
+        });
+    }
+    if max_buckets < OTEL_MIN_MAX_BUCKETS {
+        return Err(SketchError::InvalidParameter {
+            param: "max_buckets".to_string(),
+            value: max_buckets.to_string(),
+            constraint: format!("must be at least {}", OTEL_MIN_MAX_BUCKETS),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_index_boundaries() {
+        // Scale 0: base 2, bucket i covers (2^i, 2^(i+1)]
+        assert_eq!(bucket_index(1.0, 0), -1);
+        assert_eq!(bucket_index(1.5, 0), 0);
+        assert_eq!(bucket_index(2.0, 0), 0);
+        assert_eq!(bucket_index(2.5, 0), 1);
+        assert_eq!(bucket_index(0.25, 0), -3);
+
+        // Scale 1: base sqrt(2)
+        assert_eq!(bucket_index(2.0, 1), 1);
+        assert_eq!(bucket_index(2.0f64.sqrt() * 1.01, 1), 1);
+
+        // Scale -1: base 4
+        assert_eq!(bucket_index(4.0, -1), 0);
+        assert_eq!(bucket_index(5.0, -1), 1);
+    }
+
+    #[test]
+    fn test_from_cdf_step_function() {
+        // Values -3, 0, 0, 1.5, 3 as a step CDF
+        let values = [-3.0, 0.0, 0.0, 1.5, 3.0];
+        let cdf = |x: f64| values.iter().filter(|&&v| v <= x).count() as f64 / 5.0;
+        let histogram = OtelExpoHistogram::from_cdf(0, 160, 5, 1.5, (-3.0, 3.0), values, cdf);
+
+        assert_eq!(histogram.count, 5);
+        assert_eq!(histogram.zero_count, 2);
+        assert_eq!(histogram.positive.offset, 0);
+        assert_eq!(histogram.positive.bucket_counts, vec![1, 1]);
+        assert_eq!(histogram.negative.offset, 1);
+        assert_eq!(histogram.negative.bucket_counts, vec![1]);
+    }
+
+    #[test]
+    fn test_from_cdf_lowers_scale_to_fit_max_buckets() {
+        // 1e-300 to 1e300 spans a factor of 2^1993: 125 buckets at scale -4
+        let values = [-1e300, -1e-2, 1e-300, 1.0, 1e300];
+        let cdf = |x: f64| values.iter().filter(|&&v| v <= x).count() as f64 / 5.0;
+        let histogram = OtelExpoHistogram::from_cdf(
+            OTEL_MAX_SCALE,
+            OTEL_DEFAULT_MAX_BUCKETS,
+            5,
+            1.0,
+            (-1e300, 1e300),
+            values,
+            cdf,
+        );
+
+        assert_eq!(histogram.scale, -4);
+        assert!(histogram.positive.bucket_counts.len() <= OTEL_DEFAULT_MAX_BUCKETS);
+        assert!(histogram.negative.bucket_counts.len() <= OTEL_DEFAULT_MAX_BUCKETS);
+        assert_eq!(histogram.positive.bucket_counts.iter().sum::<u64>(), 3);
+        assert_eq!(histogram.negative.bucket_counts.iter().sum::<u64>(), 2);
+
+        // 1 and 1.5 span six buckets at scale 3 but two at scale 0
+        let narrow = [1.0, 1.5];
+        let cdf = |x: f64| narrow.iter().filter(|&&v| v <= x).count() as f64 / 2.0;
+        let histogram = OtelExpoHistogram::from_cdf(3, 2, 2, 2.5, (1.0, 1.5), narrow, cdf);
+        assert_eq!(histogram.scale, 0);
+        assert_eq!(histogram.positive.bucket_counts.iter().sum::<u64>(), 2);
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_parameters() {
+        assert!(validate(OTEL_MIN_SCALE, OTEL_MIN_MAX_BUCKETS).is_ok());
+        assert!(validate(OTEL_MAX_SCALE, OTEL_DEFAULT_MAX_BUCKETS).is_ok());
+        for (scale, max_buckets) in [(OTEL_MAX_SCALE + 1, 160), (OTEL_MIN_SCALE - 1, 160), (0, 1)] {
+            assert!(matches!(
+                validate(scale, max_buckets),
+                Err(SketchError::InvalidParameter { .. })
+            ));
+        }
+    }
+}
@@ -22,6 +22,8 @@
 //! "Relative Error Streaming Quantiles" (PODS 2021)
 //! https://arxiv.org/abs/2004.01668
 
+use super::compaction_rank_error;
+use crate::common::{QuantileSketch, SketchError};
 use std::cmp::Ordering;
 
 /// Operating mode for REQ Sketch
@@ -105,6 +107,11 @@ impl Compactor {
 /// let p99 = sketch.quantile(0.99).unwrap();
 /// assert!(p99 >= 9800.0); // Close to true p99 = 9900
 /// ```
+///
+/// # Thread Safety
+///
+/// `ReqSketch` is `Send + Sync`. Updates take `&mut self`; queries read through
+/// `&self`.
 #[derive(Debug, Clone)]
 pub struct ReqSketch {
     /// Configuration parameter (4-1024)
@@ -155,6 +162,11 @@ impl ReqSketch {
         self.n
     }
 
+    /// Returns the k parameter
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
     /// Returns true if the sketch is empty
     pub fn is_empty(&self) -> bool {
         self.n == 0
@@ -170,6 +182,51 @@ impl ReqSketch {
         self.max
     }
 
+    /// Returns the normalized rank error bound for interior ranks
+    ///
+    /// Derived from the retained items per compactor: each compaction at
+    /// level `h` shifts any rank by at most `2^h`, and the retained counts
+    /// bound how many compactions happened. The bound is deterministic and
+    /// is zero until the first compaction.
+    pub fn normalized_rank_error(&self) -> f64 {
+        let levels: Vec<(usize, usize)> = self
+            .compactors
+            .iter()
+            .map(|c| (c.items.len(), c.capacity))
+            .collect();
+        compaction_rank_error(&levels, self.n)
+    }
+
+    /// Returns the normalized rank error bound at a specific rank
+    ///
+    /// The endpoint protected by the mode (rank 1 for HRA, rank 0 for LRA)
+    /// is answered from the exact extreme and has zero error; the opposite
+    /// endpoint is also tracked exactly. Compactors here halve their whole
+    /// buffer, so interior ranks, including those near the protected tail,
+    /// carry the additive [`normalized_rank_error`] bound.
+    ///
+    /// [`normalized_rank_error`]: ReqSketch::normalized_rank_error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::req::{ReqMode, ReqSketch};
+    ///
+    /// let mut sketch = ReqSketch::new(32, ReqMode::HighRankAccuracy).unwrap();
+    /// for i in 0..10_000 {
+    ///     sketch.update(i as f64);
+    /// }
+    ///
+    /// assert_eq!(sketch.normalized_rank_error_at(1.0), 0.0);
+    /// assert!(sketch.normalized_rank_error_at(0.99) > 0.0);
+    /// ```
+    pub fn normalized_rank_error_at(&self, rank: f64) -> f64 {
+        if rank <= 0.0 || rank >= 1.0 {
+            return 0.0;
+        }
+        self.normalized_rank_error()
+    }
+
     /// Adds a value to the sketch
     pub fn update(&mut self, value: f64) {
         // Update count
@@ -276,88 +333,89 @@ impl ReqSketch {
 
     /// Merges another sketch into this one
     ///
+    /// Returns a new sketch holding both streams. If the k values differ,
+    /// the result uses the smaller one: compactors are rebuilt with capacity
+    /// `2 × min(k)`, so the merged sketch is only as accurate as the coarser
+    /// input.
+    ///
     /// # Arguments
     ///
     /// * `other` - Sketch to merge
     ///
     /// # Errors
     ///
-    /// Returns error if:
-    /// - Sketches have different k values
-    /// - Sketches have different modes
+    /// Returns `IncompatibleSketches` if the sketches have different modes.
+    /// An HRA sketch keeps its high ranks exact and an LRA sketch its low
+    /// ranks; a mixed merge would carry neither guarantee.
     ///
     /// # Guarantees
     ///
     /// - Preserves exact min/max values
     /// - HRA mode: p100 remains exact after merge
     /// - LRA mode: p0 remains exact after merge
-    pub fn merge(&self, other: &Self) -> Result<Self, String> {
-        // Check compatibility
-        if self.k != other.k {
-            return Err(format!(
-                "Cannot merge sketches with different k values: {} vs {}",
-                self.k, other.k
-            ));
-        }
-
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::{ReqMode, ReqSketch};
+    ///
+    /// let mut a = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+    /// let mut b = ReqSketch::new(32, ReqMode::HighRankAccuracy).unwrap();
+    /// a.update(1.0);
+    /// b.update(2.0);
+    ///
+    /// let merged = a.merge(&b).unwrap();
+    /// assert_eq!(merged.k(), 32);
+    /// assert_eq!(merged.quantile(1.0), Some(2.0));
+    ///
+    /// let lra = ReqSketch::new(64, ReqMode::LowRankAccuracy).unwrap();
+    /// assert!(a.merge(&lra).is_err());
+    /// ```
+    pub fn merge(&self, other: &Self) -> Result<Self, SketchError> {
         if self.mode != other.mode {
-            return Err(format!(
-                "Cannot merge sketches with different modes: {:?} vs {:?}",
-                self.mode, other.mode
-            ));
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("mode mismatch: {:?} vs {:?}", self.mode, other.mode),
+            });
         }
 
         // Handle empty cases
-        if self.is_empty() {
-            return Ok(other.clone());
-        }
-        if other.is_empty() {
-            return Ok(self.clone());
+        if self.k == other.k {
+            if self.is_empty() {
+                return Ok(other.clone());
+            }
+            if other.is_empty() {
+                return Ok(self.clone());
+            }
         }
 
-        // Create new sketch
-        let mut merged = Self::new(self.k, self.mode).unwrap();
+        // Create new sketch at the coarser k
+        let mut merged = Self::new(self.k.min(other.k), self.mode).unwrap();
 
         // Merge min/max
-        merged.min = Some(self.min.unwrap().min(other.min.unwrap()));
-        merged.max = Some(self.max.unwrap().max(other.max.unwrap()));
+        merged.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        merged.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
         merged.n = self.n + other.n;
 
-        // Collect all items from both sketches
-        let mut all_items = Vec::new();
-
-        for compactor in &self.compactors {
-            let weight = 1u64 << compactor.level;
-            for &item in &compactor.items {
-                all_items.push((item, weight, compactor.level));
-            }
-        }
-
-        for compactor in &other.compactors {
-            let weight = 1u64 << compactor.level;
-            for &item in &compactor.items {
-                all_items.push((item, weight, compactor.level));
-            }
-        }
-
-        // Sort by level (process lower levels first)
-        all_items.sort_by_key(|(_, _, level)| *level);
-
-        // Re-insert all items into the merged sketch
-        // This will trigger compactions as needed
-        for (item, weight, level) in all_items {
+        // Each item keeps its level: a compactor at level h already weighs
+        // its items 2^h, so they are copied once rather than 2^h times
+        for compactor in self.compactors.iter().chain(&other.compactors) {
             // Ensure we have enough levels
-            while level >= merged.compactors.len() {
+            while compactor.level >= merged.compactors.len() {
                 let new_level = merged.compactors.len();
                 merged
                     .compactors
                     .push(Compactor::new(2 * merged.k, new_level));
             }
 
-            // Add items with their weight
-            for _ in 0..weight {
-                merged.compactors[level].items.push(item);
-            }
+            merged.compactors[compactor.level]
+                .items
+                .extend_from_slice(&compactor.items);
         }
 
         // Propagate compactions
@@ -379,6 +437,16 @@ impl ReqSketch {
     }
 }
 
+impl QuantileSketch for ReqSketch {
+    fn update(&mut self, value: f64) {
+        ReqSketch::update(self, value);
+    }
+
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        ReqSketch::quantile(self, q)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
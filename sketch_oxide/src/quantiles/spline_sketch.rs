@@ -4,9 +4,26 @@
 //! 2-20x better accuracy than t-digest on non-skewed data using piecewise monotone
 //! cubic spline interpolation instead of linear interpolation.
 
-use crate::common::{Mergeable, Result, Sketch, SketchError};
+use crate::common::{Mergeable, QuantileSketch, Result, Sketch, SketchError};
 
 /// SplineSketch: High-accuracy quantile estimation with monotone cubic spline interpolation
+///
+/// # Limitations
+///
+/// Accuracy only holds while the retained samples are the whole input.
+/// Compression keeps evenly spaced samples and then treats each of them,
+/// and each value inserted afterwards, as the same rank mass, whatever the
+/// weight passed to [`update`](Self::update). Long streams therefore drift
+/// toward recently inserted values: with the default 200 samples, the median
+/// of 1M uniform values can be off by tens of percent in rank. Once that has
+/// happened, [`max_error`](Self::max_error) reports the trivial bound 1.0 and
+/// [`query_with_bounds`](Self::query_with_bounds) widens to the observed
+/// minimum and maximum.
+///
+/// # Thread Safety
+///
+/// `SplineSketch` is `Send + Sync`. Updates and merges take `&mut self`;
+/// queries take `&self`.
 #[derive(Clone, Debug)]
 pub struct SplineSketch {
     samples: Vec<u64>,
@@ -14,6 +31,9 @@ pub struct SplineSketch {
     min_value: u64,
     max_value: u64,
     weight: f64,
+    /// Whether every retained sample is one unit-weight input value, so the
+    /// samples are exact order statistics of the input
+    exact: bool,
 }
 
 impl SplineSketch {
@@ -28,6 +48,7 @@ impl SplineSketch {
             min_value: u64::MAX,
             max_value: u64::MIN,
             weight: 0.0,
+            exact: true,
         }
     }
 
@@ -71,6 +92,7 @@ impl SplineSketch {
         }
 
         self.weight += weight;
+        self.exact &= weight == 1.0;
         self.min_value = self.min_value.min(value);
         self.max_value = self.max_value.max(value);
         self.samples.push(value);
@@ -90,6 +112,7 @@ impl SplineSketch {
 
         let target_size = self.max_samples;
         let current_size = self.samples.len();
+        self.exact = false;
 
         // Use simpler stratified sampling that preserves quantiles better
         let mut compressed = Vec::with_capacity(target_size);
@@ -141,6 +164,57 @@ impl SplineSketch {
         self.spline_interpolate(lower_idx, upper_idx, t)
     }
 
+    /// Estimates a quantile together with the values bracketing it
+    ///
+    /// Returns `(lower, estimate, upper)`. The target rank `q × (n - 1)`
+    /// falls in the spline segment between two adjacent retained samples;
+    /// `lower` and `upper` are those samples and the estimate always lies
+    /// between them. The bounds collapse to the estimate when the rank hits
+    /// a sample exactly.
+    ///
+    /// These bounds contain the true quantile only while the retained
+    /// samples are exact order statistics of the input: until the sketch
+    /// first compresses, and as long as every update had weight 1.
+    /// Compression gives every retained sample equal rank mass regardless of
+    /// how many values it stands for, so after that the bounds widen to the
+    /// observed minimum and maximum, the only values known to bracket the
+    /// answer.
+    ///
+    /// # Panics
+    /// Panics if the sketch is empty, like [`query`](Self::query)
+    pub fn query_with_bounds(&self, quantile: f64) -> (u64, u64, u64) {
+        let estimate = self.query(quantile);
+
+        if !self.exact {
+            return (self.min_value, estimate, self.max_value);
+        }
+
+        let q = quantile.clamp(0.0, 1.0);
+        let last = self.samples.len() - 1;
+        let pos = q * last as f64;
+        let lower = self.samples[(pos.floor() as usize).min(last)];
+        let upper = self.samples[(pos.ceil() as usize).min(last)];
+
+        (lower, estimate.clamp(lower, upper), upper)
+    }
+
+    /// Returns a bound on the normalized rank error of [`query`](Self::query)
+    ///
+    /// While the samples are exact (see
+    /// [`query_with_bounds`](Self::query_with_bounds)), neighbouring samples
+    /// are `1 / (n - 1)` apart in rank, so any quantile is bracketed to
+    /// within that error; 0 for fewer than two samples. After compression or
+    /// a weighted update no tighter bound is known, and this returns 1.0.
+    pub fn max_error(&self) -> f64 {
+        if !self.exact {
+            1.0
+        } else if self.samples.len() < 2 {
+            0.0
+        } else {
+            1.0 / (self.samples.len() - 1) as f64
+        }
+    }
+
     /// Monotone cubic spline interpolation using Fritsch-Carlson method
     fn spline_interpolate(&self, i: usize, _j: usize, t: f64) -> u64 {
         let n = self.samples.len();
@@ -225,6 +299,37 @@ impl SplineSketch {
     }
 
     /// Merges another sketch into this one
+    ///
+    /// The merged samples are recomputed from both sample sets, with each
+    /// sample standing for an equal share of its own sketch's weight, so a
+    /// compressed sketch is not outweighed by a small uncompressed one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the sketches have different
+    /// `max_samples`; `self` is left unchanged.
+    pub fn merge(&mut self, other: &SplineSketch) -> Result<()> {
+        if self.max_samples != other.max_samples {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "SplineSketch instances have different max_samples: {} vs {}",
+                    self.max_samples, other.max_samples
+                ),
+            });
+        }
+
+        self.merge_samples(other);
+        Ok(())
+    }
+
+    /// Merges another sketch into this one without checking compatibility
+    ///
+    /// Keeps the pre-`merge` behaviour: the two sample sets are concatenated
+    /// and compressed as if every sample had the same weight.
+    #[deprecated(
+        since = "0.1.7",
+        note = "use `merge`, which rejects sketches with different max_samples"
+    )]
     pub fn merge_into(&mut self, other: &SplineSketch) {
         if other.samples.is_empty() {
             return;
@@ -232,6 +337,7 @@ impl SplineSketch {
 
         self.samples.extend(&other.samples);
         self.weight += other.weight;
+        self.exact &= other.exact;
 
         if other.min_value != u64::MAX {
             self.min_value = self.min_value.min(other.min_value);
@@ -246,12 +352,72 @@ impl SplineSketch {
         }
     }
 
+    fn merge_samples(&mut self, other: &SplineSketch) {
+        if other.samples.is_empty() {
+            return;
+        }
+        if self.samples.is_empty() {
+            self.samples.clone_from(&other.samples);
+            self.weight += other.weight;
+            self.exact = other.exact;
+            self.min_value = other.min_value;
+            self.max_value = other.max_value;
+            return;
+        }
+
+        self.min_value = self.min_value.min(other.min_value);
+        self.max_value = self.max_value.max(other.max_value);
+
+        if self.samples.len() + other.samples.len() <= self.max_samples {
+            self.samples.extend(&other.samples);
+            self.samples.sort_unstable();
+            self.weight += other.weight;
+            self.exact &= other.exact;
+            return;
+        }
+
+        // Pool the samples with the mass each stands for, then take
+        // max_samples evenly spaced quantiles of the pooled distribution
+        let self_mass = self.weight / self.samples.len() as f64;
+        let other_mass = other.weight / other.samples.len() as f64;
+        let mut pooled: Vec<(u64, f64)> = self
+            .samples
+            .iter()
+            .map(|&v| (v, self_mass))
+            .chain(other.samples.iter().map(|&v| (v, other_mass)))
+            .collect();
+        pooled.sort_unstable_by_key(|&(v, _)| v);
+
+        let total: f64 = pooled.iter().map(|&(_, mass)| mass).sum();
+        let target_size = self.max_samples;
+        let mut resampled = Vec::with_capacity(target_size);
+        let mut cumulative = 0.0;
+        let mut pooled_iter = pooled.iter().peekable();
+        for i in 0..target_size {
+            // Midpoint of each sample's mass, so the extremes map to min and max
+            let target = total * i as f64 / (target_size - 1) as f64;
+            while let Some(&&(value, mass)) = pooled_iter.peek() {
+                if cumulative + mass / 2.0 >= target || pooled_iter.len() == 1 {
+                    resampled.push(value);
+                    break;
+                }
+                cumulative += mass;
+                pooled_iter.next();
+            }
+        }
+        // Repeated values stay: each copy carries its share of the rank mass
+        self.samples = resampled;
+        self.weight += other.weight;
+        self.exact = false;
+    }
+
     /// Clears all state
     pub fn reset(&mut self) {
         self.samples.clear();
         self.min_value = u64::MAX;
         self.max_value = u64::MIN;
         self.weight = 0.0;
+        self.exact = true;
     }
 }
 
@@ -365,26 +531,41 @@ impl Sketch for SplineSketch {
             bytes[offset + 23],
         ]);
 
+        // The format does not record exactness; a sketch that never
+        // compressed nor took a weighted update has one unit of weight per sample
+        let exact = weight == samples.len() as f64;
+
         Ok(SplineSketch {
             samples,
             max_samples,
             min_value,
             max_value,
             weight,
+            exact,
         })
     }
 }
 
 impl Mergeable for SplineSketch {
     fn merge(&mut self, other: &Self) -> Result<()> {
-        if self.max_samples != other.max_samples {
-            return Err(SketchError::IncompatibleSketches {
-                reason: "SplineSketch instances have different max_samples".to_string(),
-            });
+        SplineSketch::merge(self, other)
+    }
+}
+
+/// Values are rounded to the nearest integer, since the sketch stores `u64`;
+/// negative and non-finite values are skipped.
+impl QuantileSketch for SplineSketch {
+    fn update(&mut self, value: f64) {
+        if value.is_finite() && value >= 0.0 {
+            SplineSketch::update(self, value.round() as u64, 1.0);
         }
+    }
 
-        self.merge_into(other);
-        Ok(())
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.query(q) as f64)
     }
 }
 
@@ -513,13 +694,98 @@ mod tests {
             sketch2.update(i, 1.0);
         }
 
-        sketch1.merge_into(&sketch2);
+        sketch1.merge(&sketch2).unwrap();
 
         assert!((sketch1.sample_count() as f64 - 200.0).abs() <= 1.0);
         assert_eq!(sketch1.min(), Some(0));
         assert_eq!(sketch1.max(), Some(999));
     }
 
+    #[test]
+    fn test_merge_rejects_mismatched_max_samples() {
+        let mut sketch1 = SplineSketch::new(200);
+        sketch1.update(1, 1.0);
+        let mut sketch2 = SplineSketch::new(100);
+        sketch2.update(2, 1.0);
+
+        let result = sketch1.merge(&sketch2);
+        assert!(matches!(
+            result,
+            Err(SketchError::IncompatibleSketches { .. })
+        ));
+        assert_eq!(sketch1.sample_count(), 1);
+        assert_eq!(sketch1.total_weight(), 1.0);
+    }
+
+    #[test]
+    fn test_merge_weights_samples_by_sketch_weight() {
+        // 100 samples carrying 100x the weight of the other sketch's 100
+        let mut heavy = SplineSketch::new(100);
+        for i in 0..100 {
+            heavy.update(i, 100.0);
+        }
+        let mut light = SplineSketch::new(100);
+        for i in 1000..1100 {
+            light.update(i, 1.0);
+        }
+
+        heavy.merge(&light).unwrap();
+
+        assert_eq!(heavy.total_weight(), 10_100.0);
+        assert!(heavy.sample_count() <= 100);
+        assert_eq!(heavy.min(), Some(0));
+        assert_eq!(heavy.max(), Some(1099));
+
+        // 99% of the combined weight lies in [0, 100)
+        let q50 = heavy.query(0.5) as f64;
+        assert!((q50 - 50.0).abs() < 5.0, "q50: {}", q50);
+        let q90 = heavy.query(0.9) as f64;
+        assert!((q90 - 90.0).abs() < 5.0, "q90: {}", q90);
+        assert!(heavy.query(0.995) >= 1000);
+    }
+
+    #[test]
+    fn test_merge_keeps_duplicate_mass() {
+        // 60 of 80 samples are 1000, so the median and above sit on it
+        let mut a = SplineSketch::new(100);
+        for i in 0..80 {
+            a.update(if i < 60 { 1000 } else { i }, 1.0);
+        }
+        let b = a.clone();
+        let before: Vec<u64> = [0.1, 0.2, 0.5, 0.9].iter().map(|&q| a.query(q)).collect();
+
+        // 160 pooled samples force a resample down to 100
+        a.merge(&b).unwrap();
+        assert_eq!(a.sample_count(), 100);
+        let after: Vec<u64> = [0.1, 0.2, 0.5, 0.9].iter().map(|&q| a.query(q)).collect();
+
+        assert_eq!(after[2..], before[2..]);
+        for (b, a) in before.iter().zip(&after).take(2) {
+            assert!(
+                b.abs_diff(*a) <= 2,
+                "before {:?}, after {:?}",
+                before,
+                after
+            );
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_merge_into_keeps_old_behaviour() {
+        let mut a = SplineSketch::new(10);
+        let mut b = SplineSketch::new(20);
+        for i in 0..10 {
+            a.update(i, 1.0);
+            b.update(100 + i, 1.0);
+        }
+
+        a.merge_into(&b);
+        assert_eq!(a.total_weight(), 20.0);
+        assert_eq!(a.max(), Some(109));
+        assert_eq!(a.sample_count(), 10);
+    }
+
     #[test]
     fn test_merge_trait() {
         let mut sketch1 = SplineSketch::new(200);
@@ -627,6 +893,79 @@ mod tests {
         assert!(q75 <= q90, "{} <= {}", q75, q90);
     }
 
+    #[test]
+    fn test_query_with_bounds() {
+        let mut sketch = SplineSketch::new(500);
+        for i in 0..400 {
+            sketch.update(i, 1.0);
+        }
+
+        // Not yet compressed: the bracket holds the true quantile
+        for &q in &[0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            let (lower, estimate, upper) = sketch.query_with_bounds(q);
+            assert!(lower <= estimate && estimate <= upper);
+            assert_eq!(estimate, sketch.query(q));
+
+            let truth = q * 399.0;
+            assert!(lower as f64 <= truth && truth <= upper as f64, "q={}", q);
+            assert!(upper - lower <= 1);
+        }
+        assert!((sketch.max_error() - 1.0 / 399.0).abs() < 1e-12);
+
+        // After compression only the observed range is a safe bracket
+        for i in 400..10_000 {
+            sketch.update(i, 1.0);
+        }
+        for &q in &[0.1, 0.5, 0.9] {
+            let (lower, estimate, upper) = sketch.query_with_bounds(q);
+            assert_eq!((lower, upper), (0, 9_999));
+            assert!(lower <= estimate && estimate <= upper);
+        }
+        assert_eq!(sketch.max_error(), 1.0);
+
+        let restored = SplineSketch::deserialize(&sketch.serialize()).unwrap();
+        assert_eq!(restored.max_error(), 1.0);
+
+        // reset makes the samples exact again
+        sketch.reset();
+        for v in [10, 20, 30] {
+            sketch.update(v, 1.0);
+        }
+        assert_eq!(sketch.max_error(), 0.5);
+        assert_eq!(sketch.query_with_bounds(0.5), (20, 20, 20));
+    }
+
+    #[test]
+    fn test_weighted_update_drops_exact_bounds() {
+        let mut sketch = SplineSketch::new(200);
+        for i in 0..10 {
+            sketch.update(i, 1.0);
+        }
+        assert!((sketch.max_error() - 1.0 / 9.0).abs() < 1e-12);
+
+        sketch.update(100, 50.0);
+        assert_eq!(sketch.max_error(), 1.0);
+        assert_eq!(sketch.query_with_bounds(0.5).0, 0);
+        assert_eq!(sketch.query_with_bounds(0.5).2, 100);
+    }
+
+    #[test]
+    fn test_query_with_bounds_exact_samples() {
+        let mut sketch = SplineSketch::new(200);
+        for i in 0..11 {
+            sketch.update(i * 10, 1.0);
+        }
+
+        assert_eq!(sketch.query_with_bounds(0.5), (50, 50, 50));
+        let (lower, _, upper) = sketch.query_with_bounds(0.55);
+        assert_eq!((lower, upper), (50, 60));
+        assert_eq!(sketch.query_with_bounds(0.0), (0, 0, 0));
+        assert_eq!(sketch.query_with_bounds(1.0), (100, 100, 100));
+        assert!((sketch.max_error() - 0.1).abs() < 1e-12);
+
+        assert_eq!(SplineSketch::new(200).max_error(), 0.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut sketch = SplineSketch::new(200);
@@ -0,0 +1,248 @@
+//! StreamSummary: exact moments plus T-Digest quantiles in one structure
+//!
+//! Metrics pipelines typically track count, sum, min, max and a handful of
+//! percentiles for every series. Keeping these in separate structures means
+//! one call per structure for every value; [`StreamSummary`] feeds all of them
+//! from a single [`update`](StreamSummary::update).
+//!
+//! Count, sum, min and max are exact. Quantiles come from an embedded
+//! [`TDigest`] and carry its accuracy guarantees.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::quantiles::StreamSummary;
+//!
+//! let mut summary = StreamSummary::new(100.0);
+//! for i in 1..=1000 {
+//!     summary.update(i as f64);
+//! }
+//!
+//! assert_eq!(summary.count(), 1000);
+//! assert_eq!(summary.sum(), 500_500.0);
+//! assert_eq!(summary.min(), 1.0);
+//! assert_eq!(summary.max(), 1000.0);
+//! assert!((summary.quantile(0.5) - 500.0).abs() < 50.0);
+//! ```
+
+use super::TDigest;
+use crate::common::{Mergeable, Sketch, SketchError};
+
+/// Size of the exact-statistics header preceding the T-Digest bytes
+const HEADER_SIZE: usize = 32;
+
+/// Online count/sum/min/max with an embedded T-Digest for quantiles
+///
+/// # Thread Safety
+///
+/// `StreamSummary` is `Send + Sync`. `quantile` takes `&mut self` because the
+/// inner digest flushes its buffer first, so shared use needs a `Mutex`.
+#[derive(Clone, Debug)]
+pub struct StreamSummary {
+    /// Number of values seen
+    count: u64,
+    /// Sum of all values seen
+    sum: f64,
+    /// Minimum value seen
+    min: f64,
+    /// Maximum value seen
+    max: f64,
+    /// Quantile digest fed by the same values
+    digest: TDigest,
+}
+
+impl StreamSummary {
+    /// Creates an empty summary whose digest uses the given compression
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - T-Digest compression (see [`TDigest::new`])
+    pub fn new(compression: f64) -> Self {
+        StreamSummary {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            digest: TDigest::new(compression),
+        }
+    }
+
+    /// Creates a summary with the default T-Digest compression (100)
+    pub fn default_compression() -> Self {
+        Self::new(TDigest::DEFAULT_COMPRESSION)
+    }
+
+    /// Adds a value to every statistic
+    ///
+    /// Non-finite values are ignored, matching [`TDigest::update`].
+    pub fn update(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.digest.update(value);
+    }
+
+    /// Returns the number of values seen
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of all values seen
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the minimum value seen (`+inf` when empty)
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum value seen (`-inf` when empty)
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the arithmetic mean, or `None` when empty
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum / self.count as f64)
+        }
+    }
+
+    /// Returns the estimated value at quantile `q` (0.0 to 1.0)
+    ///
+    /// Takes `&mut self` because the digest flushes its buffer first.
+    pub fn quantile(&mut self, q: f64) -> f64 {
+        self.digest.quantile(q)
+    }
+
+    /// Returns a reference to the embedded T-Digest
+    pub fn digest(&self) -> &TDigest {
+        &self.digest
+    }
+}
+
+impl Default for StreamSummary {
+    fn default() -> Self {
+        Self::default_compression()
+    }
+}
+
+impl Sketch for StreamSummary {
+    type Item = f64;
+
+    fn update(&mut self, item: &Self::Item) {
+        self.update(*item);
+    }
+
+    /// Returns the mean as the primary estimate (0.0 when empty)
+    fn estimate(&self) -> f64 {
+        self.mean().unwrap_or(0.0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        // Format: [count:8][sum:8][min:8][max:8][tdigest_data]
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes.extend_from_slice(&self.sum.to_le_bytes());
+        bytes.extend_from_slice(&self.min.to_le_bytes());
+        bytes.extend_from_slice(&self.max.to_le_bytes());
+        bytes.extend_from_slice(&self.digest.serialize());
+        bytes
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self, SketchError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(SketchError::DeserializationError(
+                "Insufficient data for StreamSummary header".to_string(),
+            ));
+        }
+
+        let count = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let sum = f64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let min = f64::from_le_bytes(bytes[16..24].try_into().unwrap());
+        let max = f64::from_le_bytes(bytes[24..32].try_into().unwrap());
+        let digest = TDigest::from_bytes(&bytes[HEADER_SIZE..])?;
+
+        Ok(StreamSummary {
+            count,
+            sum,
+            min,
+            max,
+            digest,
+        })
+    }
+}
+
+impl Mergeable for StreamSummary {
+    /// Combines the exact statistics and merges the digests
+    fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        self.digest.merge(&other.digest)?;
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_summary_statistics() {
+        let mut summary = StreamSummary::new(100.0);
+        assert!(summary.is_empty());
+        assert_eq!(summary.mean(), None);
+
+        for i in 0..1000 {
+            summary.update(i as f64);
+        }
+        summary.update(f64::NAN);
+
+        assert_eq!(summary.count(), 1000);
+        assert_eq!(summary.sum(), 499_500.0);
+        assert_eq!(summary.min(), 0.0);
+        assert_eq!(summary.max(), 999.0);
+        assert_eq!(summary.mean(), Some(499.5));
+        assert!((summary.quantile(0.9) - 900.0).abs() < 50.0);
+    }
+
+    #[test]
+    fn test_stream_summary_merge_and_roundtrip() {
+        let mut a = StreamSummary::new(100.0);
+        let mut b = StreamSummary::new(100.0);
+        for i in 0..500 {
+            a.update(i as f64);
+        }
+        for i in 500..1000 {
+            b.update(i as f64);
+        }
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.count(), 1000);
+        assert_eq!(a.min(), 0.0);
+        assert_eq!(a.max(), 999.0);
+
+        let mut restored = StreamSummary::deserialize(&a.serialize()).unwrap();
+        assert_eq!(restored.count(), a.count());
+        assert_eq!(restored.sum(), a.sum());
+        assert_eq!(restored.min(), a.min());
+        assert_eq!(restored.max(), a.max());
+        assert_eq!(restored.quantile(0.5), a.quantile(0.5));
+
+        assert!(StreamSummary::deserialize(&[0u8; 8]).is_err());
+    }
+}
@@ -32,6 +32,12 @@
 //!
 //! O(compression) centroids, typically 100-500
 //!
+//! # Non-finite Values
+//!
+//! NaN and ±∞ cannot be ordered into centroids, so the update methods skip
+//! them and count them in [`rejected_count`](TDigest::rejected_count).
+//! [`try_update`](TDigest::try_update) reports them as errors instead.
+//!
 //! # References
 //!
 //! - Dunning & Ertl "Computing Extremely Accurate Quantiles Using t-Digests" (2019)
@@ -56,7 +62,8 @@
 //! println!("Median: {}, P99: {}, P99.9: {}", median, p99, p999);
 //! ```
 
-use crate::common::{Mergeable, Sketch, SketchError};
+use super::otel::{self, OtelExpoHistogram, OTEL_DEFAULT_MAX_BUCKETS};
+use crate::common::{Mergeable, QuantileSketch, Sketch, SketchError};
 use std::cmp::Ordering;
 
 /// A centroid represents a cluster of values with a mean and count
@@ -73,6 +80,14 @@ impl Centroid {
         Centroid { mean, weight }
     }
 
+    /// Total order by mean, then weight, so equal means sort the same way
+    /// regardless of the order centroids arrived in
+    fn cmp_key(&self, other: &Self) -> Ordering {
+        self.mean
+            .total_cmp(&other.mean)
+            .then(self.weight.total_cmp(&other.weight))
+    }
+
     /// Adds a value with weight to this centroid
     fn add(&mut self, value: f64, weight: f64) {
         let new_weight = self.weight + weight;
@@ -85,6 +100,13 @@ impl Centroid {
 ///
 /// Provides high accuracy at distribution tails with bounded memory.
 ///
+/// # Thread Safety
+///
+/// `TDigest` is `Send + Sync`. `quantile`, `cdf`, `trimmed_mean` and `to_bytes`
+/// take `&mut self` because they flush the insert buffer first, so a shared
+/// digest needs a `Mutex` rather than an `RwLock` read guard.
+/// [`Sketch::estimate`] works on a clone and only needs `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -114,6 +136,8 @@ pub struct TDigest {
     max: f64,
     /// Buffer size before triggering compression
     buffer_size: usize,
+    /// Non-finite values skipped by the update methods (not serialized)
+    rejected: u64,
 }
 
 impl TDigest {
@@ -151,9 +175,73 @@ impl TDigest {
             min: f64::INFINITY,
             max: f64::NEG_INFINITY,
             buffer_size: (compression as usize) * Self::BUFFER_FACTOR,
+            rejected: 0,
         }
     }
 
+    /// Creates a T-Digest that compresses every `buffer_size` raw values
+    ///
+    /// [`new`](Self::new) buffers `5 × compression` values. Incoming values
+    /// are appended to an unsorted buffer, and each compression sorts the
+    /// buffer together with the existing centroids and merges them back
+    /// down to about `compression` centroids. The buffer size trades:
+    ///
+    /// - **Ingest speed**: a compression costs roughly
+    ///   `O((c + b) log(c + b))` for `c` centroids and `b` buffered values,
+    ///   so larger buffers amortize the centroid re-sort over more inserts.
+    ///   Gains level off once `b` is well above `c`.
+    /// - **Memory**: each pending value takes 8 bytes on top of the
+    ///   centroids, up to `8 × buffer_size` bytes.
+    /// - **Accuracy**: largely unaffected, since every value still goes
+    ///   through the same size-bounded merge. On a million shuffled values,
+    ///   buffers from 10 to 500,000 gave the same p99/p99.9 error, while
+    ///   ingest went from seconds (buffer 10) to tens of milliseconds.
+    /// - **Query latency**: queries flush the buffer first, so the first
+    ///   query after a burst pays for compressing up to `buffer_size`
+    ///   values.
+    ///
+    /// The buffer size is an in-memory setting: serialization flushes the
+    /// buffer and does not store it, so deserialized digests use the
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - Controls accuracy/memory tradeoff, as in [`new`](Self::new)
+    /// * `buffer_size` - Raw values to accumulate before compressing (≥ 1)
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `buffer_size` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// // Absorb bursts of up to 50k values between compressions
+    /// let mut td = TDigest::with_buffer(100.0, 50_000).unwrap();
+    /// for i in 0..10_000 {
+    ///     td.update(i as f64);
+    /// }
+    /// assert_eq!(td.pending(), 10_000);
+    ///
+    /// let median = td.quantile(0.5); // flushes the buffer
+    /// assert_eq!(td.pending(), 0);
+    /// assert!((median - 5_000.0).abs() < 100.0);
+    /// ```
+    pub fn with_buffer(compression: f64, buffer_size: usize) -> Result<Self, SketchError> {
+        if buffer_size == 0 {
+            return Err(SketchError::InvalidParameter {
+                param: "buffer_size".to_string(),
+                value: buffer_size.to_string(),
+                constraint: "must be >= 1".to_string(),
+            });
+        }
+        let mut td = Self::new(compression);
+        td.buffer_size = buffer_size;
+        Ok(td)
+    }
+
     /// Creates a T-Digest with default compression (100)
     pub fn default_compression() -> Self {
         Self::new(Self::DEFAULT_COMPRESSION)
@@ -164,11 +252,54 @@ impl TDigest {
         self.compression
     }
 
+    /// Returns the number of raw values buffered before a compression
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Returns the number of values waiting in the buffer
+    ///
+    /// These are counted by [`count`](Self::count) but not yet merged into
+    /// centroids. The buffer is compressed once it reaches
+    /// [`buffer_size`](Self::buffer_size), or earlier by a query.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
     /// Returns the number of centroids
     pub fn centroid_count(&self) -> usize {
         self.centroids.len()
     }
 
+    /// Returns the centroid count as a fraction of the `compression` budget
+    ///
+    /// A t-digest with compression δ is meant to summarize any stream in on
+    /// the order of δ centroids. This digest caps each centroid's weight near
+    /// δ instead of scaling the cap with the total weight, so on long or
+    /// heavily merged streams the count keeps growing and the ratio climbs
+    /// above 1. A high ratio flags a digest that has outgrown its configured
+    /// size and is worth rebuilding. Values still in the insert buffer are
+    /// not counted until the next compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let mut td = TDigest::new(100.0);
+    /// assert_eq!(td.centroid_budget_used(), 0.0);
+    ///
+    /// for i in 0..50 {
+    ///     td.update(i as f64);
+    /// }
+    /// td.quantile(0.5); // flushes the buffer
+    /// assert!(td.centroid_budget_used() > 0.0);
+    /// assert!(td.centroid_budget_used() <= 0.5);
+    /// ```
+    pub fn centroid_budget_used(&self) -> f64 {
+        self.centroids.len() as f64 / self.compression
+    }
+
     /// Returns the total weight (number of values added)
     pub fn count(&self) -> f64 {
         self.total_weight + self.buffer.len() as f64
@@ -184,11 +315,21 @@ impl TDigest {
         self.max
     }
 
+    /// Returns the number of non-finite values skipped by the update methods
+    ///
+    /// Merging adds the other digest's rejected count. The count is kept in
+    /// memory only: serialization does not store it, so deserialized
+    /// digests start from 0.
+    pub fn rejected_count(&self) -> u64 {
+        self.rejected
+    }
+
     /// Updates the T-Digest with a single value
     ///
     /// # Arguments
     ///
-    /// * `value` - Value to add (must be finite)
+    /// * `value` - Value to add; NaN and ±∞ are skipped and counted in
+    ///   [`rejected_count`](Self::rejected_count)
     ///
     /// # Examples
     ///
@@ -198,9 +339,14 @@ impl TDigest {
     /// let mut td = TDigest::new(100.0);
     /// td.update(42.0);
     /// td.update(100.0);
+    /// td.update(f64::NAN);
+    ///
+    /// assert_eq!(td.count(), 2.0);
+    /// assert_eq!(td.rejected_count(), 1);
     /// ```
     pub fn update(&mut self, value: f64) {
         if !value.is_finite() {
+            self.rejected += 1;
             return;
         }
 
@@ -213,14 +359,50 @@ impl TDigest {
         }
     }
 
+    /// Updates the T-Digest with a single value, rejecting non-finite values
+    ///
+    /// Strict counterpart of [`update`](Self::update): NaN and ±∞ leave the
+    /// digest untouched, including [`rejected_count`](Self::rejected_count).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `value` is NaN or infinite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let mut td = TDigest::new(100.0);
+    /// assert!(td.try_update(42.0).is_ok());
+    /// assert!(td.try_update(f64::NAN).is_err());
+    /// assert_eq!(td.count(), 1.0);
+    /// ```
+    pub fn try_update(&mut self, value: f64) -> Result<(), SketchError> {
+        if !value.is_finite() {
+            return Err(SketchError::InvalidParameter {
+                param: "value".to_string(),
+                value: value.to_string(),
+                constraint: "must be finite".to_string(),
+            });
+        }
+        self.update(value);
+        Ok(())
+    }
+
     /// Updates with a weighted value
     ///
     /// # Arguments
     ///
-    /// * `value` - Value to add
-    /// * `weight` - Weight of this value (typically frequency)
+    /// * `value` - Value to add; NaN and ±∞ are counted as rejected
+    /// * `weight` - Weight of this value (typically frequency); non-finite
+    ///   weights are also counted as rejected, and weights ≤ 0 are ignored
     pub fn update_weighted(&mut self, value: f64, weight: f64) {
-        if !value.is_finite() || weight <= 0.0 {
+        if !value.is_finite() || !weight.is_finite() {
+            self.rejected += 1;
+            return;
+        }
+        if weight <= 0.0 {
             return;
         }
 
@@ -235,13 +417,82 @@ impl TDigest {
         }
     }
 
+    /// Approximately removes `weight` occurrences of `value`
+    ///
+    /// T-Digest does not retain individual values, so the weight is taken
+    /// from the centroid whose mean is nearest to `value`. If that centroid
+    /// runs out, the remainder is taken from the next nearest, and emptied
+    /// centroids are dropped. Centroid means are left untouched.
+    ///
+    /// This is an approximation. Subtracting values that were never added, or
+    /// subtracting a large share of the stream, can noticeably distort the
+    /// estimated distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value to remove (must be finite)
+    /// * `weight` - Weight to remove (must be positive)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let mut td = TDigest::new(100.0);
+    /// for i in 0..100 {
+    ///     td.update(i as f64);
+    /// }
+    /// td.subtract(0.0, 1.0);
+    /// assert_eq!(td.count(), 99.0);
+    /// ```
+    pub fn subtract(&mut self, value: f64, weight: f64) {
+        if !value.is_finite() || weight.is_nan() || weight <= 0.0 {
+            return;
+        }
+
+        self.flush();
+
+        let mut remaining = weight;
+        while remaining > 0.0 && !self.centroids.is_empty() {
+            let idx = self.nearest_centroid(value);
+            let taken = remaining.min(self.centroids[idx].weight);
+            self.centroids[idx].weight -= taken;
+            self.total_weight -= taken;
+            remaining -= taken;
+
+            if self.centroids[idx].weight <= 0.0 {
+                self.centroids.remove(idx);
+                // Tighten the bounds when a tail centroid disappears
+                if let (Some(first), Some(last)) = (self.centroids.first(), self.centroids.last()) {
+                    if idx == 0 {
+                        self.min = first.mean;
+                    }
+                    if idx == self.centroids.len() {
+                        self.max = last.mean;
+                    }
+                }
+            }
+        }
+
+        if self.centroids.is_empty() {
+            self.total_weight = 0.0;
+            self.min = f64::INFINITY;
+            self.max = f64::NEG_INFINITY;
+        }
+    }
+
     /// Adds a batch of values efficiently
+    ///
+    /// Non-finite values are skipped and counted in
+    /// [`rejected_count`](Self::rejected_count).
     pub fn update_batch(&mut self, values: &[f64]) {
         for &value in values {
             if value.is_finite() {
                 self.min = self.min.min(value);
                 self.max = self.max.max(value);
                 self.buffer.push(value);
+            } else {
+                self.rejected += 1;
             }
         }
 
@@ -334,53 +585,197 @@ impl TDigest {
 
     /// Returns the CDF value (rank) for a given value
     ///
+    /// Ranks are fractions of the total weight, so a value added once with
+    /// [`update_weighted`](Self::update_weighted) and weight `w` ranks like
+    /// `w` calls to [`update`](Self::update). Centroids that share a mean are
+    /// treated as one, which keeps the interpolation independent of how the
+    /// weight at that mean happened to be split.
+    ///
     /// # Arguments
     ///
     /// * `value` - Value to find rank for
     ///
     /// # Returns
     ///
-    /// Estimated fraction of values <= given value (0.0 to 1.0)
+    /// Estimated fraction of the total weight at values <= given value
+    /// (0.0 to 1.0)
     pub fn cdf(&mut self, value: f64) -> f64 {
         self.flush();
+        self.flushed_cdf(value)
+    }
 
-        if self.centroids.is_empty() {
+    /// Estimates how many values fell in `[low, high]`
+    ///
+    /// Computed as `count × (cdf(high) - cdf(low⁻))`, where `cdf(low⁻)` is
+    /// the fraction strictly below `low`. The digest is cloned if values are
+    /// still buffered, since CDF queries flush the insert buffer.
+    ///
+    /// # Approximation
+    ///
+    /// The count inherits the [`cdf`](Self::cdf) interpolation between
+    /// centroids, so its error is the rank error at each bound times the
+    /// total count: small near the tails, largest around the median.
+    /// The digest treats values as spread out between centroids, so
+    /// `low == high` returns about 0, except for a digest of a single
+    /// repeated value, which returns its full count. Bounds beyond
+    /// [`min`](Self::min) and [`max`](Self::max) are exact, so a range
+    /// covering both returns [`count`](Self::count). Returns 0 if
+    /// `low > high` or either bound is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let mut td = TDigest::new(100.0);
+    /// for i in 1..=1000 {
+    ///     td.update(i as f64);
+    /// }
+    ///
+    /// assert_eq!(td.count_between(0.0, 2000.0), 1000.0);
+    /// let middle = td.count_between(250.0, 750.0);
+    /// assert!((middle - 501.0).abs() < 10.0);
+    /// ```
+    pub fn count_between(&self, low: f64, high: f64) -> f64 {
+        if low.is_nan() || high.is_nan() || low > high {
             return 0.0;
         }
 
-        if value <= self.min {
+        let flushed;
+        let digest = if self.buffer.is_empty() {
+            self
+        } else {
+            let mut clone = self.clone();
+            clone.flush();
+            flushed = clone;
+            &flushed
+        };
+
+        let mass = digest.flushed_cdf(high) - digest.flushed_cdf(low.next_down());
+        (digest.total_weight * mass).max(0.0)
+    }
+
+    /// [`cdf`](Self::cdf) of a digest whose buffer has been flushed
+    fn flushed_cdf(&self, value: f64) -> f64 {
+        if self.centroids.is_empty() {
             return 0.0;
         }
+
         if value >= self.max {
             return 1.0;
         }
+        if value <= self.min {
+            return 0.0;
+        }
 
         let mut cumulative = 0.0;
+        // Mean and combined weight of the previous group of equal-mean centroids
+        let mut prev: Option<(f64, f64)> = None;
+        let mut i = 0;
+
+        while i < self.centroids.len() {
+            let mean = self.centroids[i].mean;
+            let mut weight = 0.0;
+            while i < self.centroids.len() && self.centroids[i].mean == mean {
+                weight += self.centroids[i].weight;
+                i += 1;
+            }
 
-        for i in 0..self.centroids.len() {
-            let centroid = &self.centroids[i];
-
-            if value < centroid.mean {
-                if i == 0 {
+            if value < mean {
+                return match prev {
                     // Before first centroid
-                    let fraction = (value - self.min) / (centroid.mean - self.min);
-                    return (cumulative + centroid.weight * fraction / 2.0) / self.total_weight;
-                } else {
+                    None => {
+                        let fraction = (value - self.min) / (mean - self.min);
+                        weight * fraction / 2.0 / self.total_weight
+                    }
                     // Between centroids
-                    let prev = &self.centroids[i - 1];
-                    let fraction = (value - prev.mean) / (centroid.mean - prev.mean);
-                    let weight_so_far = cumulative - prev.weight / 2.0;
-                    let weight_span = prev.weight / 2.0 + centroid.weight / 2.0;
-                    return (weight_so_far + weight_span * fraction) / self.total_weight;
-                }
+                    Some((prev_mean, prev_weight)) => {
+                        let fraction = (value - prev_mean) / (mean - prev_mean);
+                        let weight_so_far = cumulative - prev_weight / 2.0;
+                        let weight_span = prev_weight / 2.0 + weight / 2.0;
+                        (weight_so_far + weight_span * fraction) / self.total_weight
+                    }
+                };
             }
 
-            cumulative += centroid.weight;
+            cumulative += weight;
+            prev = Some((mean, weight));
         }
 
         1.0
     }
 
+    /// Exports the digest as an OpenTelemetry exponential histogram
+    ///
+    /// Each bucket receives the count times the [`cdf`](Self::cdf) mass
+    /// between its boundaries, so values are spread across buckets by the
+    /// digest's interpolation between centroids. See [`otel`](super::otel)
+    /// for what the conversion loses. The digest is cloned, since CDF queries
+    /// flush the insert buffer.
+    ///
+    /// `scale` is a maximum: it is lowered until the positive and the
+    /// negative buckets each fit in [`OTEL_DEFAULT_MAX_BUCKETS`], and the
+    /// histogram reports the scale it used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `scale` is outside `[-10, 20]`, the range
+    /// OpenTelemetry allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let mut td = TDigest::new(100.0);
+    /// for i in 1..=1000 {
+    ///     td.update(i as f64);
+    /// }
+    ///
+    /// let histogram = td.to_otel_exponential(2).unwrap();
+    /// assert_eq!(histogram.count, 1000);
+    /// assert_eq!(histogram.positive.bucket_counts.iter().sum::<u64>(), 1000);
+    /// assert!(histogram.negative.bucket_counts.is_empty());
+    /// ```
+    pub fn to_otel_exponential(&self, scale: i32) -> Result<OtelExpoHistogram, SketchError> {
+        self.to_otel_exponential_with_max_buckets(scale, OTEL_DEFAULT_MAX_BUCKETS)
+    }
+
+    /// Exports the sketch as an OpenTelemetry exponential histogram with at
+    /// most `max_buckets` positive and `max_buckets` negative buckets
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `scale` is outside `[-10, 20]` or
+    /// `max_buckets` is below 2, the smallest limit OpenTelemetry SDKs accept.
+    pub fn to_otel_exponential_with_max_buckets(
+        &self,
+        scale: i32,
+        max_buckets: usize,
+    ) -> Result<OtelExpoHistogram, SketchError> {
+        otel::validate(scale, max_buckets)?;
+        let mut digest = self.clone();
+        digest.flush();
+        if digest.centroids.is_empty() {
+            return Ok(OtelExpoHistogram::empty(scale));
+        }
+
+        let means: Vec<f64> = digest.centroids.iter().map(|c| c.mean).collect();
+        let sum = digest.centroids.iter().map(|c| c.mean * c.weight).sum();
+        let (min, max) = (digest.min, digest.max);
+        let count = digest.total_weight.round() as u64;
+
+        Ok(OtelExpoHistogram::from_cdf(
+            scale,
+            max_buckets,
+            count,
+            sum,
+            (min, max),
+            means,
+            |x| digest.cdf(x),
+        ))
+    }
+
     /// Returns the trimmed mean between two quantiles
     ///
     /// # Arguments
@@ -418,6 +813,72 @@ impl TDigest {
         }
     }
 
+    /// Merges several digests into a new one, independent of their order
+    ///
+    /// Pools the centroids and buffered values of every input, sorts them by
+    /// mean and then weight, and compresses once. The result depends only on
+    /// the set of inputs, so merging the same partition digests in any order
+    /// gives bit-identical quantiles. Folding them with repeated
+    /// [`merge`](Mergeable::merge) calls does not: each call compresses,
+    /// so grouping affects the result even though a single pairwise merge
+    /// is commutative. The result uses the largest input compression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `digests` is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::quantiles::TDigest;
+    ///
+    /// let parts: Vec<TDigest> = (0..4)
+    ///     .map(|p| {
+    ///         let mut td = TDigest::new(100.0);
+    ///         for i in 0..1000 {
+    ///             td.update((i * 4 + p) as f64);
+    ///         }
+    ///         td
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut forward = TDigest::merge_many(&[&parts[0], &parts[1], &parts[2], &parts[3]]).unwrap();
+    /// let mut backward = TDigest::merge_many(&[&parts[3], &parts[2], &parts[1], &parts[0]]).unwrap();
+    /// assert_eq!(forward.quantile(0.99), backward.quantile(0.99));
+    /// ```
+    pub fn merge_many(digests: &[&TDigest]) -> Result<TDigest, SketchError> {
+        if digests.is_empty() {
+            return Err(SketchError::InvalidParameter {
+                param: "digests".to_string(),
+                value: "[]".to_string(),
+                constraint: "must contain at least one digest".to_string(),
+            });
+        }
+
+        let compression = digests
+            .iter()
+            .map(|td| td.compression)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let mut merged = TDigest::new(compression);
+
+        for td in digests {
+            merged.centroids.extend(td.centroids.iter().cloned());
+            merged
+                .centroids
+                .extend(td.buffer.iter().map(|&value| Centroid::new(value, 1.0)));
+            merged.min = merged.min.min(td.min);
+            merged.max = merged.max.max(td.max);
+            merged.rejected += td.rejected;
+        }
+
+        // Sum weights in sorted order so rounding does not depend on input order
+        merged.centroids.sort_by(Centroid::cmp_key);
+        merged.total_weight = merged.centroids.iter().map(|c| c.weight).sum();
+        merged.compress();
+
+        Ok(merged)
+    }
+
     /// Flushes buffer and compresses centroids
     fn flush(&mut self) {
         if !self.buffer.is_empty() {
@@ -439,8 +900,7 @@ impl TDigest {
         }
 
         // Sort centroids by mean
-        self.centroids
-            .sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(Ordering::Equal));
+        self.centroids.sort_by(Centroid::cmp_key);
 
         // Merge using scale function (more aggressive at tails)
         let mut merged = Vec::with_capacity(self.centroids.len());
@@ -469,6 +929,21 @@ impl TDigest {
         self.centroids = merged;
     }
 
+    /// Index of the centroid whose mean is closest to `value`
+    ///
+    /// Centroids must be non-empty and sorted by mean.
+    fn nearest_centroid(&self, value: f64) -> usize {
+        let idx = self
+            .centroids
+            .partition_point(|c| c.mean < value)
+            .min(self.centroids.len() - 1);
+        if idx > 0 && value - self.centroids[idx - 1].mean <= self.centroids[idx].mean - value {
+            idx - 1
+        } else {
+            idx
+        }
+    }
+
     /// Adds a new centroid, maintaining sort order
     fn add_centroid(&mut self, mean: f64, weight: f64) {
         let centroid = Centroid::new(mean, weight);
@@ -544,6 +1019,7 @@ impl TDigest {
             min,
             max,
             buffer_size: (compression as usize) * Self::BUFFER_FACTOR,
+            rejected: 0,
         })
     }
 }
@@ -584,6 +1060,11 @@ impl Sketch for TDigest {
 impl Mergeable for TDigest {
     /// Merges another T-Digest into this one
     ///
+    /// Centroids are ordered by mean and then weight before compressing, so
+    /// `a.merge(&b)` and `b.merge(&a)` give the same digest when both use the
+    /// same compression. Chains of merges still depend on grouping; use
+    /// [`TDigest::merge_many`] to combine many digests reproducibly.
+    ///
     /// # Examples
     ///
     /// ```
@@ -617,6 +1098,7 @@ impl Mergeable for TDigest {
             self.centroids.push(centroid);
         }
         self.total_weight += other_clone.total_weight;
+        self.rejected += other_clone.rejected;
 
         // Compress
         self.compress();
@@ -625,6 +1107,19 @@ impl Mergeable for TDigest {
     }
 }
 
+impl QuantileSketch for TDigest {
+    fn update(&mut self, value: f64) {
+        TDigest::update(self, value);
+    }
+
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        if Sketch::is_empty(self) {
+            return None;
+        }
+        Some(TDigest::quantile(self, q))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -679,6 +1174,78 @@ mod tests {
         assert_eq!(td.max(), 100.0);
     }
 
+    #[test]
+    fn test_subtract_shifts_median() {
+        let mut td = TDigest::new(100.0);
+        for i in 0..1000 {
+            td.update(i as f64);
+        }
+        let before = td.quantile(0.5);
+        assert!((before - 500.0).abs() < 50.0);
+
+        for i in 0..500 {
+            td.subtract(i as f64, 1.0);
+        }
+
+        assert!((td.count() - 500.0).abs() < 1e-9);
+        let after = td.quantile(0.5);
+        assert!(after > before + 150.0);
+        assert!(
+            (after - 750.0).abs() < 100.0,
+            "Median after subtract {} too far from 750",
+            after
+        );
+    }
+
+    #[test]
+    fn test_subtract_everything_empties() {
+        let mut td = TDigest::new(100.0);
+        for i in 0..10 {
+            td.update(i as f64);
+        }
+        td.subtract(5.0, 100.0);
+        assert!(td.is_empty());
+        assert_eq!(td.count(), 0.0);
+
+        // Invalid arguments are ignored
+        td.update(1.0);
+        td.subtract(f64::NAN, 1.0);
+        td.subtract(1.0, -1.0);
+        assert_eq!(td.count(), 1.0);
+    }
+
+    #[test]
+    fn test_non_finite_values_rejected() {
+        let mut td = TDigest::new(100.0);
+        for i in 0..1000 {
+            td.update(i as f64);
+            if i % 10 == 0 {
+                td.update(f64::NAN);
+            }
+        }
+        td.update_batch(&[f64::INFINITY, 5.0, f64::NEG_INFINITY]);
+        td.update_weighted(f64::NAN, 2.0);
+        td.update_weighted(5.0, f64::NAN);
+
+        assert_eq!(td.count(), 1001.0);
+        assert_eq!(td.rejected_count(), 104);
+        assert_eq!((td.min(), td.max()), (0.0, 999.0));
+        let median = td.quantile(0.5);
+        assert!((median - 500.0).abs() < 50.0, "median {}", median);
+
+        // Strict updates report the value and leave the digest untouched
+        assert!(td.try_update(f64::NAN).is_err());
+        assert!(td.try_update(f64::NEG_INFINITY).is_err());
+        assert!(td.try_update(1.0).is_ok());
+        assert_eq!(td.count(), 1002.0);
+        assert_eq!(td.rejected_count(), 104);
+
+        let mut other = TDigest::new(100.0);
+        other.update(f64::INFINITY);
+        td.merge(&other).unwrap();
+        assert_eq!(td.rejected_count(), 105);
+    }
+
     #[test]
     fn test_merge() {
         let mut td1 = TDigest::new(100.0);
@@ -701,6 +1268,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_many_order_independent() {
+        // Overlapping partitions with repeated values, some left in the buffer
+        let parts: Vec<TDigest> = (0..6u64)
+            .map(|p| {
+                let mut td = TDigest::new(50.0);
+                for i in 0..(700 + 37 * p) {
+                    td.update(((i * (p + 3)) % 997) as f64 / 7.0);
+                }
+                td.update_weighted(42.0, 3.0);
+                td
+            })
+            .collect();
+
+        let quantiles = |order: &[usize]| {
+            let inputs: Vec<&TDigest> = order.iter().map(|&i| &parts[i]).collect();
+            let mut merged = TDigest::merge_many(&inputs).unwrap();
+            let qs: Vec<f64> = [0.001, 0.1, 0.25, 0.5, 0.75, 0.9, 0.999]
+                .iter()
+                .map(|&q| merged.quantile(q))
+                .collect();
+            (qs, merged.count(), merged.centroid_count())
+        };
+
+        let expected = quantiles(&[0, 1, 2, 3, 4, 5]);
+        for order in [
+            [5, 4, 3, 2, 1, 0],
+            [2, 0, 5, 1, 3, 4],
+            [3, 5, 1, 4, 0, 2],
+            [1, 3, 0, 2, 5, 4],
+        ] {
+            assert_eq!(quantiles(&order), expected);
+        }
+
+        let total: f64 = parts.iter().map(|td| td.count()).sum();
+        assert_eq!(expected.1, total);
+        assert!(TDigest::merge_many(&[]).is_err());
+    }
+
+    #[test]
+    fn test_merge_commutative() {
+        let mut a = TDigest::new(100.0);
+        let mut b = TDigest::new(100.0);
+        for i in 0..2000 {
+            a.update((i % 50) as f64);
+            b.update((i % 70) as f64 + 0.5);
+        }
+
+        let mut ab = a.clone();
+        ab.merge(&b).unwrap();
+        let mut ba = b.clone();
+        ba.merge(&a).unwrap();
+
+        for q in [0.01, 0.5, 0.99] {
+            assert_eq!(ab.quantile(q), ba.quantile(q));
+        }
+    }
+
     #[test]
     fn test_serialization() {
         let mut td = TDigest::new(100.0);
@@ -714,4 +1339,154 @@ mod tests {
         assert_eq!(td.compression(), restored.compression());
         assert!((td.quantile(0.5) - restored.quantile(0.5)).abs() < 1.0);
     }
+
+    #[test]
+    fn test_with_buffer_controls_compression() {
+        assert!(TDigest::with_buffer(100.0, 0).is_err());
+
+        let mut td = TDigest::with_buffer(100.0, 1_000).unwrap();
+        assert_eq!(td.buffer_size(), 1_000);
+        for i in 0..999 {
+            td.update(i as f64);
+        }
+        assert_eq!(td.pending(), 999);
+        assert_eq!(td.centroid_count(), 0);
+        assert_eq!(td.count(), 999.0);
+
+        // The 1000th value fills the buffer and triggers a compression
+        td.update(999.0);
+        assert_eq!(td.pending(), 0);
+        assert!(td.centroid_count() > 0);
+
+        td.update(1_000.0);
+        assert_eq!(td.pending(), 1);
+        let median = td.quantile(0.5);
+        assert_eq!(td.pending(), 0);
+        assert!((median - 500.0).abs() < 50.0);
+
+        // Default construction keeps the 5 × compression buffer
+        assert_eq!(TDigest::new(100.0).buffer_size(), 500);
+    }
+
+    #[test]
+    fn test_centroid_budget_used() {
+        let mut td = TDigest::new(100.0);
+        assert_eq!(td.centroid_budget_used(), 0.0);
+
+        for i in 0..100_000 {
+            td.update(i as f64);
+        }
+        td.quantile(0.5);
+        assert_eq!(
+            td.centroid_budget_used(),
+            td.centroid_count() as f64 / 100.0
+        );
+        assert!(td.centroid_budget_used() > 1.0);
+    }
+
+    #[test]
+    fn test_weighted_cdf_matches_unit_updates() {
+        let mut weighted = TDigest::new(100.0);
+        let mut unit = TDigest::new(100.0);
+        weighted.update_weighted(5.0, 1000.0);
+        for _ in 0..1000 {
+            unit.update(5.0);
+        }
+        for value in [4.0, 5.0, 6.0] {
+            assert_eq!(weighted.cdf(value), unit.cdf(value), "cdf({})", value);
+        }
+        assert_eq!(weighted.cdf(5.0), 1.0);
+
+        // Unit centroids may straddle neighbouring values, so ranks in a
+        // mixed stream agree only approximately
+        for digest in [&mut weighted, &mut unit] {
+            for _ in 0..1000 {
+                digest.update(1.0);
+                digest.update(9.0);
+            }
+        }
+        for value in [3.0, 5.0, 7.0] {
+            let (w, u) = (weighted.cdf(value), unit.cdf(value));
+            assert!((w - u).abs() < 0.1, "cdf({}): {} vs {}", value, w, u);
+        }
+    }
+
+    #[test]
+    fn test_count_between() {
+        let mut td = TDigest::new(100.0);
+        // Exponential(1) quantiles: half the mass lies below ln 2
+        let n = 10_000;
+        for i in 0..n {
+            td.update(-(1.0 - (i as f64 + 0.5) / n as f64).ln());
+        }
+
+        let below_median = td.count_between(0.0, std::f64::consts::LN_2);
+        assert!((below_median - 5000.0).abs() < 100.0, "{}", below_median);
+        let tail = td.count_between(3.0, 100.0);
+        let expected = n as f64 * (-3.0f64).exp();
+        assert!((tail - expected).abs() < 20.0, "{}", tail);
+
+        // Bounds beyond min and max are exact
+        assert_eq!(td.count_between(-1.0, 1e9), n as f64);
+        assert_eq!(td.count_between(1e9, 2e9), 0.0);
+        assert_eq!(td.count_between(2.0, 1.0), 0.0);
+        assert_eq!(td.count_between(f64::NAN, 1.0), 0.0);
+        assert_eq!(TDigest::new(100.0).count_between(0.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_count_between_equal_bounds() {
+        let mut td = TDigest::new(100.0);
+        for _ in 0..100 {
+            td.update(5.0);
+        }
+        // Buffered values are counted without mutating the digest
+        assert_eq!(td.count_between(5.0, 5.0), 100.0);
+        assert_eq!(td.count_between(4.0, 4.5), 0.0);
+
+        for i in 0..100 {
+            td.update(i as f64 / 10.0);
+        }
+        assert!(td.count_between(7.0, 7.0) < 1.0);
+    }
+
+    #[test]
+    fn test_to_otel_exponential() {
+        use crate::quantiles::OtelBuckets;
+
+        let mut td = TDigest::new(100.0);
+        assert_eq!(td.to_otel_exponential(0).unwrap().count, 0);
+
+        for i in 1..=10_000 {
+            td.update(i as f64);
+            td.update(-(i as f64));
+        }
+        td.update(0.0);
+
+        // Scale 0: (2^i, 2^(i+1)] holds 2^i integers, so buckets double
+        let histogram = td.to_otel_exponential(0).unwrap();
+        assert_eq!(histogram.count, 20_001);
+        // 10_000 falls in (2^13, 2^14]
+        let last = |buckets: &OtelBuckets| buckets.offset + buckets.bucket_counts.len() as i32 - 1;
+        assert_eq!(last(&histogram.positive), 13);
+        assert_eq!(last(&histogram.negative), 13);
+        let total: u64 = histogram.zero_count
+            + histogram.positive.bucket_counts.iter().sum::<u64>()
+            + histogram.negative.bucket_counts.iter().sum::<u64>();
+        assert_eq!(total, 20_001);
+
+        for i in 8..13 {
+            let expected = (1u64 << i) as f64;
+            let count =
+                histogram.positive.bucket_counts[(i - histogram.positive.offset) as usize] as f64;
+            assert!(
+                (count - expected).abs() / expected < 0.1,
+                "bucket {}: {} vs {}",
+                i,
+                count,
+                expected
+            );
+        }
+        assert!(histogram.sum.abs() < 1e-6);
+    }
 }
@@ -58,6 +58,11 @@ use std::hash::{Hash, Hasher};
 /// - Quotient filter stores precise element fingerprints
 /// - Adaptive expansion when range grows
 /// - No false negatives guaranteed
+///
+/// # Thread Safety
+///
+/// `MementoFilter` is `Send + Sync`. Inserts take `&mut self`; range queries
+/// take `&self`.
 #[derive(Clone, Debug)]
 pub struct MementoFilter {
     /// Base range filter for efficient range checks
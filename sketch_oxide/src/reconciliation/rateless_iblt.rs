@@ -81,7 +81,8 @@ use crate::common::{hash::xxhash, Reconcilable, Result, SetDifference, SketchErr
 ///
 /// # Thread Safety
 ///
-/// This structure is not thread-safe. Use external synchronization if needed.
+/// `RatelessIBLT` is `Send + Sync`. Mutation takes `&mut self`, so wrap a table
+/// in a `Mutex` when several threads insert into it.
 #[derive(Clone)]
 pub struct RatelessIBLT {
     /// Number of cells in the IBLT
@@ -23,4 +23,4 @@ pub mod reservoir;
 pub mod varopt;
 
 pub use reservoir::ReservoirSampling;
-pub use varopt::VarOptSampling;
+pub use varopt::{estimate_join_size, VarOptSampling};
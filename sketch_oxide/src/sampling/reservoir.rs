@@ -33,6 +33,11 @@ use rand::Rng;
 
 /// Reservoir Sampling for uniform random samples from streams
 ///
+/// # Thread Safety
+///
+/// `ReservoirSampling<T>` is `Send + Sync` whenever `T` is; its RNG is an owned
+/// `SmallRng`.
+///
 /// # Examples
 ///
 /// ```
@@ -37,11 +37,15 @@
 //! - Used in: LSH, deduplication, recommendation systems, near-duplicate detection
 
 use crate::common::hash::xxhash;
-use crate::common::{Mergeable, Sketch, SketchError};
+use crate::common::{Mergeable, SetDifferenceEstimate, Sketch, SketchError};
 use std::hash::Hash;
 
 /// MinHash sketch for Jaccard similarity estimation
 ///
+/// # Thread Safety
+///
+/// `MinHash` is `Send + Sync`. Similarity queries take `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -269,6 +273,75 @@ impl MinHash {
         Ok(similarity)
     }
 
+    /// Estimates |A \ B| and |B \ A| from the Jaccard similarity
+    ///
+    /// MinHash cannot recover the differing elements, only how many there
+    /// are, so this is an estimate rather than a
+    /// [`Reconcilable`](crate::common::Reconcilable) decode. It needs the
+    /// cardinality of each set, e.g. from a HyperLogLog maintained alongside:
+    ///
+    /// - `|A ∩ B| = J × (|A| + |B|) / (1 + J)`, from `J = |A ∩ B| / |A ∪ B|`
+    /// - `|A \ B| = |A| - |A ∩ B|` and `|B \ A| = |B| - |A ∩ B|`
+    ///
+    /// The intersection is capped at the smaller cardinality so neither
+    /// difference is negative. Errors in J (standard error ≈ 1/√k) and in
+    /// the cardinalities both carry over; small differences between large
+    /// sets are relatively imprecise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if num_perm differs, and
+    /// `InvalidParameter` if a cardinality is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::similarity::MinHash;
+    ///
+    /// let mut a = MinHash::new(256).unwrap();
+    /// let mut b = MinHash::new(256).unwrap();
+    /// for i in 0..1000 {
+    ///     a.update(&i);
+    /// }
+    /// for i in 500..1200 {
+    ///     b.update(&i);
+    /// }
+    ///
+    /// let diff = a.estimate_difference(&b, 1000.0, 700.0).unwrap();
+    /// assert!((diff.to_insert - 500.0).abs() < 150.0);
+    /// assert!((diff.to_remove - 200.0).abs() < 150.0);
+    /// ```
+    pub fn estimate_difference(
+        &self,
+        other: &Self,
+        self_cardinality: f64,
+        other_cardinality: f64,
+    ) -> Result<SetDifferenceEstimate, SketchError> {
+        for (param, value) in [
+            ("self_cardinality", self_cardinality),
+            ("other_cardinality", other_cardinality),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(SketchError::InvalidParameter {
+                    param: param.to_string(),
+                    value: value.to_string(),
+                    constraint: "must be finite and >= 0".to_string(),
+                });
+            }
+        }
+
+        let jaccard = self.jaccard_similarity(other)?;
+        let intersection = (jaccard * (self_cardinality + other_cardinality) / (1.0 + jaccard))
+            .min(self_cardinality)
+            .min(other_cardinality);
+
+        Ok(SetDifferenceEstimate {
+            to_insert: self_cardinality - intersection,
+            to_remove: other_cardinality - intersection,
+            intersection,
+        })
+    }
+
     /// Hashes an item with a specific seed
     ///
     /// Uses xxhash with seed for 64-bit hash values
@@ -549,4 +622,48 @@ mod tests {
         let unique_seeds: std::collections::HashSet<_> = mh.hash_seeds.iter().collect();
         assert_eq!(unique_seeds.len(), 128, "Seeds should be unique");
     }
+
+    #[test]
+    fn test_estimate_difference() {
+        // (A range, B range) with true |A \ B| and |B \ A|
+        let cases = [
+            ((0, 200), (100, 250), 100.0, 50.0),
+            ((0, 100), (0, 100), 0.0, 0.0),
+        ];
+        for ((a_lo, a_hi), (b_lo, b_hi), only_a, only_b) in cases {
+            let mut a = MinHash::new(512).unwrap();
+            let mut b = MinHash::new(512).unwrap();
+            for i in a_lo..a_hi {
+                a.update(&i);
+            }
+            for i in b_lo..b_hi {
+                b.update(&i);
+            }
+
+            let diff = a
+                .estimate_difference(&b, (a_hi - a_lo) as f64, (b_hi - b_lo) as f64)
+                .unwrap();
+            assert!((diff.to_insert - only_a).abs() < 20.0, "{:?}", diff);
+            assert!((diff.to_remove - only_b).abs() < 20.0, "{:?}", diff);
+            assert!(diff.to_insert >= 0.0 && diff.to_remove >= 0.0);
+        }
+
+        // Disjoint sets differ by everything
+        let mut a = MinHash::new(128).unwrap();
+        let mut b = MinHash::new(128).unwrap();
+        a.update(&"x");
+        b.update(&"y");
+        let diff = a.estimate_difference(&b, 1.0, 1.0).unwrap();
+        assert_eq!(diff.total_changes(), 2.0);
+        assert_eq!(diff.intersection, 0.0);
+    }
+
+    #[test]
+    fn test_estimate_difference_invalid() {
+        let a = MinHash::new(128).unwrap();
+        let b = MinHash::new(64).unwrap();
+        assert!(a.estimate_difference(&b, 1.0, 1.0).is_err());
+        assert!(a.estimate_difference(&a, -1.0, 1.0).is_err());
+        assert!(a.estimate_difference(&a, 1.0, f64::NAN).is_err());
+    }
 }
@@ -40,6 +40,12 @@ use std::hash::Hash;
 
 /// SimHash sketch for near-duplicate detection via cosine similarity
 ///
+/// # Thread Safety
+///
+/// `SimHash` is `Send + Sync`. `fingerprint`, `hamming_distance` and
+/// `similarity` take `&mut self` because the fingerprint is computed lazily and
+/// cached, so share it behind a `Mutex`.
+///
 /// # Examples
 ///
 /// ```
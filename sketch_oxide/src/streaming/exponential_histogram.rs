@@ -334,6 +334,32 @@ mod tests {
         );
     }
 
+    // -------------------------------------------------------------------------
+    // Test 13b: Non-mutating Queries
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_count_as_of_does_not_mutate() {
+        let mut eh = ExponentialHistogram::new(100, 0.1).unwrap();
+
+        for t in (0..400).step_by(5) {
+            eh.insert(t, 1);
+        }
+
+        let buckets_before = eh.num_buckets();
+        let as_of = eh.count_as_of(500);
+        let range = eh.count_range_as_of(350, 400);
+        assert_eq!(eh.num_buckets(), buckets_before);
+
+        // Must agree with expire followed by count
+        let mut expired = eh.clone();
+        expired.expire(500);
+        assert_eq!(as_of, expired.count(500));
+
+        let (estimate, lower, upper) = range;
+        assert!(lower <= estimate && estimate <= upper);
+        assert!(upper >= 10, "Range should cover ~10 events, got {}", upper);
+    }
+
     // -------------------------------------------------------------------------
     // Test 14: Serialization
     // -------------------------------------------------------------------------
@@ -418,6 +444,39 @@ mod tests {
         assert!(upper <= 70, "Upper bound {} too high", upper);
     }
 
+    // -------------------------------------------------------------------------
+    // Test 17b: Timestamp for Count
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_timestamp_for_count() {
+        let epsilon = 0.1;
+        let mut eh = ExponentialHistogram::new(10_000, epsilon).unwrap();
+        for t in 0..1000 {
+            eh.insert(t, 1);
+        }
+
+        assert_eq!(eh.timestamp_for_count(0), None);
+        assert_eq!(eh.timestamp_for_count(1), Some(999));
+        assert_eq!(eh.timestamp_for_count(1001), None);
+
+        let mut prev = u64::MAX;
+        for n in [1, 10, 50, 100, 500, 1000] {
+            let ts = eh.timestamp_for_count(n).unwrap();
+            assert!(ts <= prev, "boundary should move back as n grows");
+            prev = ts;
+
+            // One event per tick, so 1000 - ts events are stamped at or after ts
+            let covered = 1000 - ts;
+            assert!(covered >= n, "n={}: only {} events covered", n, covered);
+            assert!(
+                covered as f64 <= n as f64 * (1.0 + 2.0 * epsilon) + 1.0,
+                "n={}: {} events covered",
+                n,
+                covered
+            );
+        }
+    }
+
     // -------------------------------------------------------------------------
     // Test 18: Memory Usage
     // -------------------------------------------------------------------------
@@ -502,6 +561,11 @@ struct EHBucket {
 /// Maintains an approximate count over a sliding time window with guaranteed
 /// relative error bounded by epsilon.
 ///
+/// # Thread Safety
+///
+/// `ExponentialHistogram` is `Send + Sync`. Inserts and expiry take
+/// `&mut self`; count queries take `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -789,6 +853,132 @@ impl ExponentialHistogram {
         (estimate, lower, upper)
     }
 
+    /// Returns the count estimate with bounds as of `current_time`, without mutation
+    ///
+    /// Buckets that [`expire`](Self::expire) would drop are ignored, but no state
+    /// is modified, so concurrent readers can query a shared histogram safely.
+    /// The result matches calling `expire(current_time)` followed by
+    /// `count(current_time)`, and does not depend on bucket ordering.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_time` - The current time (end of the window)
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (estimate, lower_bound, upper_bound), as for [`count`](Self::count)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::streaming::ExponentialHistogram;
+    ///
+    /// let mut eh = ExponentialHistogram::new(100, 0.1).unwrap();
+    /// eh.insert(10, 1);
+    /// eh.insert(150, 1);
+    ///
+    /// let buckets = eh.num_buckets();
+    /// let (est, _, _) = eh.count_as_of(200);
+    /// assert_eq!(est, 1);
+    /// assert_eq!(eh.num_buckets(), buckets);
+    /// ```
+    pub fn count_as_of(&self, current_time: u64) -> (u64, u64, u64) {
+        let window_start = current_time.saturating_sub(self.window_size);
+        self.count_range_as_of(window_start, current_time)
+    }
+
+    /// Returns the count estimate with bounds for the range [start, end], without mutation
+    ///
+    /// Buckets stamped inside the range are counted fully. Of the buckets stamped
+    /// before `start`, only the newest can contain events inside the range, so it
+    /// is counted as a partial bucket (half for the estimate, fully for the upper
+    /// bound). Buckets stamped after `end` are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Start of the range (inclusive)
+    /// * `end` - End of the range (inclusive)
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (estimate, lower_bound, upper_bound)
+    pub fn count_range_as_of(&self, start: u64, end: u64) -> (u64, u64, u64) {
+        let mut total = 0u64;
+        let mut partial: Option<&EHBucket> = None;
+
+        for bucket in &self.buckets {
+            if bucket.timestamp > end {
+                continue;
+            }
+
+            if bucket.timestamp >= start {
+                total += bucket.count;
+            } else if partial.is_none_or(|p| bucket.timestamp > p.timestamp) {
+                partial = Some(bucket);
+            }
+        }
+
+        let partial_count = partial.map_or(0, |p| p.count);
+
+        (total + partial_count / 2, total, total + partial_count)
+    }
+
+    /// Returns the approximate timestamp of the `n`-th most recent event
+    ///
+    /// Walks the buckets newest-to-oldest, accumulating counts until at least
+    /// `n` events are covered, and returns the timestamp of the bucket that
+    /// crosses `n`. Events stamped at or after the returned timestamp total
+    /// roughly `n`.
+    ///
+    /// # Approximation
+    ///
+    /// A bucket only records the timestamp of its oldest event, so the
+    /// crossing bucket is taken whole: the range starting at the returned
+    /// timestamp covers between `n` and `n + c - 1` events, where `c` is the
+    /// crossing bucket's size. The l-canonical form keeps `c` within about
+    /// epsilon of the total, so the overshoot shrinks with epsilon.
+    ///
+    /// All retained buckets are considered; call [`expire`](Self::expire)
+    /// first to restrict the search to the current window.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `n` is 0 or fewer than `n` events are retained
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::streaming::ExponentialHistogram;
+    ///
+    /// let mut eh = ExponentialHistogram::new(1000, 0.1).unwrap();
+    /// for t in 0..100 {
+    ///     eh.insert(t, 1);
+    /// }
+    ///
+    /// let ts = eh.timestamp_for_count(10).unwrap();
+    /// let (_, lower, _) = eh.count_range_as_of(ts, 99);
+    /// assert!(lower >= 10);
+    /// assert_eq!(eh.timestamp_for_count(1000), None);
+    /// ```
+    pub fn timestamp_for_count(&self, n: u64) -> Option<u64> {
+        if n == 0 {
+            return None;
+        }
+
+        let mut newest_first: Vec<&EHBucket> = self.buckets.iter().collect();
+        newest_first.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
+
+        let mut total = 0u64;
+        for bucket in newest_first {
+            total = total.saturating_add(bucket.count);
+            if total >= n {
+                return Some(bucket.timestamp);
+            }
+        }
+
+        None
+    }
+
     /// Expires old buckets outside the window
     ///
     /// Removes buckets that are entirely outside the window, keeping at most
@@ -7,13 +7,26 @@
 //! - [`SlidingWindowCounter`]: Simple sliding window counter using exponential histogram
 //! - [`ExponentialHistogram`]: Enhanced exponential histogram with formal error bounds
 //! - [`SlidingHyperLogLog`]: Time-windowed cardinality estimation with HyperLogLog
+//! - [`SlidingWindowFrequency`]: Per-key windowed counts from time-bucketed Count-Min Sketches
+//! - [`SlidingRateLimiter`]: Approximate per-key rate limiting over a sliding window
+//! - [`SlidingTopK`]: Windowed top-k heavy hitters from time-bucketed HeavyKeepers
+//! - [`SlidingNitroSketch`]: Sampled per-key windowed counts for line-rate telemetry
 
 mod exponential_histogram;
+mod sliding_frequency;
 mod sliding_hll;
+mod sliding_nitro;
+mod sliding_rate_limiter;
+mod sliding_topk;
 mod sliding_window;
+mod time_slots;
 
 pub use exponential_histogram::ExponentialHistogram;
+pub use sliding_frequency::SlidingWindowFrequency;
 pub use sliding_hll::{SlidingHLLStats, SlidingHyperLogLog};
+pub use sliding_nitro::SlidingNitroSketch;
+pub use sliding_rate_limiter::SlidingRateLimiter;
+pub use sliding_topk::SlidingTopK;
 pub use sliding_window::SlidingWindowCounter;
 
 #[cfg(test)]
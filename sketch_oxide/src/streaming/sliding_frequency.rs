@@ -0,0 +1,237 @@
+//! Sliding Window Frequency: per-key event counts over a sliding time window
+//!
+//! [`SlidingWindowCounter`](super::SlidingWindowCounter) counts a single
+//! stream. For rate limiting many keys at once, this sketch keeps a ring of
+//! time-bucketed Count-Min Sketches, one per slot of the window.
+//!
+//! # Algorithm Overview
+//!
+//! The maximum window is divided into `num_slots` slots of
+//! `ceil(window_size / num_slots)` time units each. An update at time `t` goes
+//! into the Count-Min Sketch of slot `t / slot_duration`. The ring holds only
+//! the most recent `num_slots` slots, so a slot is cleared when a newer time
+//! maps onto it, and updates older than the retained slots are dropped.
+//!
+//! A window query sums the key's estimate over every slot that overlaps
+//! `[current_time - window, current_time]`.
+//!
+//! # Accuracy
+//!
+//! - Per slot, Count-Min never underestimates and overestimates by at most
+//!   `ε × (events in that slot)` with probability `1 - δ`.
+//! - Windows are rounded out to whole slots, so events up to one slot
+//!   duration older than the window start can be included. More slots give
+//!   finer granularity.
+//!
+//! # Space Complexity
+//!
+//! `num_slots × CMS size`, where a CMS holds `width × depth` 8-byte counters
+//! (see [`CountMinSketch`]). Slots are allocated on first use.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::streaming::SlidingWindowFrequency;
+//!
+//! // 60-second window in 6 slots of 10 seconds
+//! let mut freq = SlidingWindowFrequency::new(60, 6, 0.01, 0.01).unwrap();
+//! freq.update(&"client_a", 100);
+//! freq.update(&"client_a", 105);
+//! freq.update(&"client_b", 130);
+//!
+//! assert_eq!(freq.count_window(&"client_a", 130, 60), 2);
+//! // Events at 100 and 105 fall outside a 20-second window ending at 130
+//! assert_eq!(freq.count_window(&"client_a", 130, 20), 0);
+//! ```
+
+use super::time_slots::TimeSlots;
+use crate::common::SketchError;
+use crate::frequency::CountMinSketch;
+use std::hash::Hash;
+
+/// Per-key frequency over a sliding time window
+///
+/// # Thread Safety
+///
+/// `SlidingWindowFrequency` is `Send + Sync`. Window queries take `&self`.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::streaming::SlidingWindowFrequency;
+///
+/// let mut freq = SlidingWindowFrequency::new(1000, 10, 0.01, 0.01).unwrap();
+/// for t in 0..50 {
+///     freq.update(&"key", t * 10);
+/// }
+/// assert_eq!(freq.count_window(&"key", 490, 1000), 50);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlidingWindowFrequency {
+    /// One Count-Min Sketch per time slot
+    slots: TimeSlots<CountMinSketch>,
+    /// Count-Min error bound per slot
+    epsilon: f64,
+    /// Count-Min failure probability per slot
+    delta: f64,
+}
+
+impl SlidingWindowFrequency {
+    /// Creates a per-key sliding window frequency sketch
+    ///
+    /// # Arguments
+    /// * `window_size` - Largest window that can be queried, in time units
+    /// * `num_slots` - Number of time buckets the window is split into
+    /// * `epsilon` - Count-Min error bound per slot
+    /// * `delta` - Count-Min failure probability per slot
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `window_size` or `num_slots` is 0,
+    /// `num_slots > window_size`, or epsilon/delta are outside (0, 1)
+    pub fn new(
+        window_size: u64,
+        num_slots: usize,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Self, SketchError> {
+        let slots = TimeSlots::new(window_size, num_slots)?;
+        // Validate the Count-Min parameters up front
+        CountMinSketch::new(epsilon, delta)?;
+
+        Ok(SlidingWindowFrequency {
+            slots,
+            epsilon,
+            delta,
+        })
+    }
+
+    /// Records one occurrence of `key` at `timestamp`
+    ///
+    /// Updates older than the retained slots are ignored.
+    pub fn update<T: Hash>(&mut self, key: &T, timestamp: u64) {
+        self.update_by(key, timestamp, 1);
+    }
+
+    /// Records `count` occurrences of `key` at `timestamp`
+    pub fn update_by<T: Hash>(&mut self, key: &T, timestamp: u64, count: u64) {
+        let (epsilon, delta) = (self.epsilon, self.delta);
+        let slot = self.slots.slot_mut(timestamp, || {
+            CountMinSketch::new(epsilon, delta).expect("parameters validated in new")
+        });
+        if let Some(sketch) = slot {
+            sketch.update_signed(key, i64::try_from(count).unwrap_or(i64::MAX));
+        }
+    }
+
+    /// Estimates occurrences of `key` in `[current_time - window, current_time]`
+    ///
+    /// The window is rounded out to whole slots and capped at `window_size`.
+    /// Never underestimates events that are still retained.
+    pub fn count_window<T: Hash>(&self, key: &T, current_time: u64, window: u64) -> u64 {
+        self.slots
+            .window(current_time, window)
+            .map(|sketch| sketch.estimate(key))
+            .sum()
+    }
+
+    /// Frees slots that no query ending at `current_time` or later can reach
+    pub fn expire(&mut self, current_time: u64) {
+        self.slots.expire(current_time);
+    }
+
+    /// Removes all recorded events
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Returns the largest window that can be queried
+    pub fn window_size(&self) -> u64 {
+        self.slots.window_size()
+    }
+
+    /// Returns the number of slots in the ring
+    pub fn num_slots(&self) -> usize {
+        self.slots.num_slots()
+    }
+
+    /// Returns the time units covered by each slot
+    pub fn slot_duration(&self) -> u64 {
+        self.slots.slot_duration()
+    }
+
+    /// Returns the bytes used by allocated Count-Min counters
+    ///
+    /// At most `num_slots × width × depth × 8`.
+    pub fn memory_usage(&self) -> usize {
+        self.slots
+            .iter()
+            .map(|sketch| sketch.width() * sketch.depth() * std::mem::size_of::<u64>())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_key_counts() {
+        let mut freq = SlidingWindowFrequency::new(100, 10, 0.001, 0.01).unwrap();
+        for t in 0..100 {
+            freq.update(&"a", t);
+            if t % 2 == 0 {
+                freq.update(&"b", t);
+            }
+        }
+
+        assert_eq!(freq.count_window(&"a", 99, 100), 100);
+        assert_eq!(freq.count_window(&"b", 99, 100), 50);
+        assert_eq!(freq.count_window(&"c", 99, 100), 0);
+    }
+
+    #[test]
+    fn test_window_rounds_to_slots() {
+        let mut freq = SlidingWindowFrequency::new(100, 10, 0.001, 0.01).unwrap();
+        for t in 0..100 {
+            freq.update(&"a", t);
+        }
+
+        // [69, 99] touches slots 6..=9, i.e. times 60..=99
+        assert_eq!(freq.count_window(&"a", 99, 30), 40);
+    }
+
+    #[test]
+    fn test_buckets_expire() {
+        let mut freq = SlidingWindowFrequency::new(100, 10, 0.001, 0.01).unwrap();
+        freq.update(&"a", 5);
+        freq.update(&"a", 250);
+
+        // Slot of time 5 has been reused by time 250's ring position or aged out
+        assert_eq!(freq.count_window(&"a", 250, 100), 1);
+
+        // Late updates beyond the ring are dropped
+        freq.update(&"a", 10);
+        assert_eq!(freq.count_window(&"a", 250, 100), 1);
+
+        freq.expire(1_000);
+        assert_eq!(freq.memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_update_by_and_clear() {
+        let mut freq = SlidingWindowFrequency::new(60, 6, 0.01, 0.01).unwrap();
+        freq.update_by(&42u64, 10, 7);
+        assert_eq!(freq.count_window(&42u64, 10, 60), 7);
+
+        freq.clear();
+        assert_eq!(freq.count_window(&42u64, 10, 60), 0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SlidingWindowFrequency::new(0, 1, 0.01, 0.01).is_err());
+        assert!(SlidingWindowFrequency::new(10, 0, 0.01, 0.01).is_err());
+        assert!(SlidingWindowFrequency::new(10, 11, 0.01, 0.01).is_err());
+        assert!(SlidingWindowFrequency::new(10, 5, 0.0, 0.01).is_err());
+    }
+}
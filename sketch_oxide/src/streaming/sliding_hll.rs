@@ -12,6 +12,11 @@
 //! 2. **Window Query**: Filter registers by timestamp, estimate cardinality
 //! 3. **Decay**: Remove expired entries to maintain window bounds
 //!
+//! Each register also keeps a short history of when it first reached each of
+//! its successive values. This answers "how many distinct items were first
+//! seen after T" ([`SlidingHyperLogLog::distinct_since`]), which the
+//! last-update timestamps used for windows cannot.
+//!
 //! # Key Innovation: LFPM (List of Future Possible Maxima)
 //!
 //! While a full LFPM implementation provides O(1) decay, this implementation uses
@@ -28,9 +33,15 @@
 //! # Space Complexity
 //!
 //! O(m) where m = 2^precision. Each register stores:
-//! - Leading zero count: 1 byte
-//! - Timestamp: 8 bytes
-//!   Total: ~9m bytes (e.g., 36KB for precision 12)
+//! - Leading zero count and last-update timestamp: 16 bytes (with padding)
+//! - First-reached history: a 24-byte `Vec` plus 16 bytes per step. Steps
+//!   older than `max_window_seconds` are collapsed into one on update and
+//!   [`decay`](SlidingHyperLogLog::decay), and values strictly increase, so a
+//!   register holds at most 65 - p steps and usually only a few.
+//!
+//! Total: ~40m bytes plus 16 bytes per history step, at most
+//! (56 + 16(65 - p))m bytes (e.g., ~160KB plus history for precision 12,
+//! 3.5MB worst case)
 //!
 //! # Production Use Cases (2025)
 //!
@@ -76,6 +87,10 @@ use twox_hash::XxHash64;
 /// Maintains registers with timestamp metadata to enable efficient window-based
 /// cardinality estimation.
 ///
+/// # Thread Safety
+///
+/// `SlidingHyperLogLog` is `Send + Sync`. Window estimates take `&self`.
+///
 /// # Examples
 ///
 /// ```
@@ -101,6 +116,10 @@ pub struct SlidingHyperLogLog {
     /// Maximum window size in seconds
     max_window_seconds: u64,
 
+    /// Per-register history of (value, earliest timestamp reaching it)
+    /// Sorted by timestamp with strictly increasing values
+    first_reached: Vec<Vec<(u8, u64)>>,
+
     /// Metadata for tracking and optimization
     metadata: SlidingHLLMetadata,
 }
@@ -186,6 +205,7 @@ impl SlidingHyperLogLog {
             precision,
             registers,
             max_window_seconds,
+            first_reached: vec![Vec::new(); m],
             metadata: SlidingHLLMetadata {
                 last_decay_time: 0,
                 total_updates: 0,
@@ -240,6 +260,10 @@ impl SlidingHyperLogLog {
             register.value = rho;
             register.timestamp = timestamp;
         }
+
+        let steps = &mut self.first_reached[idx];
+        record_first_reached(steps, rho, timestamp);
+        prune_first_reached(steps, timestamp.saturating_sub(self.max_window_seconds));
     }
 
     /// Estimates cardinality over a time window
@@ -324,13 +348,67 @@ impl SlidingHyperLogLog {
     /// assert!((total - 1000.0).abs() < 50.0);
     /// ```
     pub fn estimate_total(&self) -> f64 {
+        self.estimate_values(self.registers.iter().map(|r| r.value))
+    }
+
+    /// Estimates distinct items whose first observation is after `cutoff_ts`
+    ///
+    /// This differs from [`estimate_window`](Self::estimate_window), which
+    /// counts items *active* in a window: an item seen before the cutoff and
+    /// again after it is active in the window but is not new. The result is
+    /// the all-history estimate minus the estimate of items seen at or
+    /// before the cutoff, so its absolute error scales with the all-history
+    /// cardinality rather than with the answer.
+    ///
+    /// The history is not cleared by [`decay`](Self::decay), so items that
+    /// expired from the window are still known as previously seen. Updates
+    /// and `decay` do collapse history older than the window into one step
+    /// per register, so a cutoff before the window start is only
+    /// approximate: those items count as seen, but not when.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::streaming::SlidingHyperLogLog;
+    ///
+    /// let mut hll = SlidingHyperLogLog::new(12, 3600).unwrap();
+    /// for i in 0..1000 {
+    ///     hll.update(&i, 1000).unwrap();
+    /// }
+    /// // Half of the old items return, plus 500 new ones
+    /// for i in 500..1500 {
+    ///     hll.update(&i, 2000).unwrap();
+    /// }
+    ///
+    /// let new_items = hll.distinct_since(1500);
+    /// assert!((new_items - 500.0).abs() < 100.0);
+    /// ```
+    pub fn distinct_since(&self, cutoff_ts: u64) -> f64 {
+        let total = self.estimate_values(
+            self.first_reached
+                .iter()
+                .map(|steps| steps.last().map_or(0, |&(value, _)| value)),
+        );
+        let seen_by_cutoff = self.estimate_values(self.first_reached.iter().map(|steps| {
+            steps
+                .iter()
+                .take_while(|&&(_, ts)| ts <= cutoff_ts)
+                .last()
+                .map_or(0, |&(value, _)| value)
+        }));
+
+        (total - seen_by_cutoff).max(0.0)
+    }
+
+    /// HyperLogLog estimate over one value per register
+    fn estimate_values(&self, values: impl Iterator<Item = u8>) -> f64 {
         let m = self.registers.len() as f64;
         let mut sum = 0.0;
         let mut zeros = 0;
 
-        for register in &self.registers {
-            if register.value > 0 {
-                sum += 2.0_f64.powi(-(register.value as i32));
+        for value in values {
+            if value > 0 {
+                sum += 2.0_f64.powi(-(value as i32));
             } else {
                 zeros += 1;
                 sum += 1.0;
@@ -379,6 +457,9 @@ impl SlidingHyperLogLog {
                 register.timestamp = 0;
             }
         }
+        for steps in &mut self.first_reached {
+            prune_first_reached(steps, cutoff_time);
+        }
 
         self.metadata.last_decay_time = current_time;
         Ok(())
@@ -437,6 +518,7 @@ impl SlidingHyperLogLog {
     /// Serializes the sketch to bytes
     ///
     /// Format: [precision: 1 byte][max_window: 8 bytes][registers: m * 9 bytes][metadata]
+    ///         [per register: steps: 1 byte, then steps * (value: 1 byte, timestamp: 8 bytes)]
     pub fn serialize(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
@@ -454,10 +536,23 @@ impl SlidingHyperLogLog {
         bytes.extend_from_slice(&self.metadata.last_decay_time.to_le_bytes());
         bytes.extend_from_slice(&self.metadata.total_updates.to_le_bytes());
 
+        // First-reached history
+        for steps in &self.first_reached {
+            bytes.push(steps.len() as u8);
+            for &(value, timestamp) in steps {
+                bytes.push(value);
+                bytes.extend_from_slice(&timestamp.to_le_bytes());
+            }
+        }
+
         bytes
     }
 
     /// Deserializes a sketch from bytes
+    ///
+    /// Bytes without the first-reached history load with each register's
+    /// last-update timestamp standing in for it, which makes
+    /// [`distinct_since`](Self::distinct_since) count returning items as new.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, SketchError> {
         if bytes.len() < 9 {
             return Err(SketchError::DeserializationError(
@@ -481,7 +576,7 @@ impl SlidingHyperLogLog {
         let m = 1usize << precision;
         let expected_len = 9 + m * 9 + 16; // header + registers + metadata
 
-        if bytes.len() != expected_len {
+        if bytes.len() < expected_len {
             return Err(SketchError::DeserializationError(format!(
                 "Expected {} bytes, got {}",
                 expected_len,
@@ -513,11 +608,55 @@ impl SlidingHyperLogLog {
                 .try_into()
                 .map_err(|_| SketchError::DeserializationError("Invalid metadata".to_string()))?,
         );
+        offset += 16;
+
+        let first_reached = if offset == bytes.len() {
+            registers
+                .iter()
+                .map(|r| {
+                    if r.value > 0 {
+                        vec![(r.value, r.timestamp)]
+                    } else {
+                        Vec::new()
+                    }
+                })
+                .collect()
+        } else {
+            let mut first_reached = Vec::with_capacity(m);
+            for _ in 0..m {
+                let len = *bytes.get(offset).ok_or_else(|| {
+                    SketchError::DeserializationError("Truncated history".to_string())
+                })? as usize;
+                offset += 1;
+                if offset + len * 9 > bytes.len() {
+                    return Err(SketchError::DeserializationError(
+                        "Truncated history".to_string(),
+                    ));
+                }
+                let mut steps = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let value = bytes[offset];
+                    let timestamp =
+                        u64::from_le_bytes(bytes[offset + 1..offset + 9].try_into().unwrap());
+                    steps.push((value, timestamp));
+                    offset += 9;
+                }
+                first_reached.push(steps);
+            }
+            if offset != bytes.len() {
+                return Err(SketchError::DeserializationError(format!(
+                    "{} trailing bytes after history",
+                    bytes.len() - offset
+                )));
+            }
+            first_reached
+        };
 
         Ok(SlidingHyperLogLog {
             precision,
             registers,
             max_window_seconds,
+            first_reached,
             metadata: SlidingHLLMetadata {
                 last_decay_time,
                 total_updates,
@@ -613,6 +752,12 @@ impl Mergeable for SlidingHyperLogLog {
             }
         }
 
+        for (steps, other_steps) in self.first_reached.iter_mut().zip(&other.first_reached) {
+            for &(value, timestamp) in other_steps {
+                record_first_reached(steps, value, timestamp);
+            }
+        }
+
         // Update metadata
         self.metadata.total_updates += other.metadata.total_updates;
         self.metadata.last_decay_time = self
@@ -624,6 +769,31 @@ impl Mergeable for SlidingHyperLogLog {
     }
 }
 
+/// Records that a register reached `value` at `timestamp`
+///
+/// Keeps only steps not dominated by an earlier-or-equal step with a
+/// greater-or-equal value, so the list stays sorted by timestamp with
+/// strictly increasing values and holds at most 64 entries.
+fn record_first_reached(steps: &mut Vec<(u8, u64)>, value: u8, timestamp: u64) {
+    if steps.iter().any(|&(v, t)| v >= value && t <= timestamp) {
+        return;
+    }
+    steps.retain(|&(v, t)| !(v <= value && t >= timestamp));
+    let pos = steps.partition_point(|&(_, t)| t < timestamp);
+    steps.insert(pos, (value, timestamp));
+}
+
+/// Collapses the steps before `cutoff` into the last of them
+///
+/// That step still answers "what had the register reached by any time at or
+/// after `cutoff`", so `distinct_since` is unchanged for those cutoffs.
+fn prune_first_reached(steps: &mut Vec<(u8, u64)>, cutoff: u64) {
+    let expired = steps.partition_point(|&(_, t)| t < cutoff);
+    if expired > 1 {
+        steps.drain(..expired - 1);
+    }
+}
+
 /// Statistics for Sliding HyperLogLog
 #[derive(Debug, Clone)]
 pub struct SlidingHLLStats {
@@ -700,5 +870,97 @@ mod tests {
 
         assert_eq!(hll.precision, restored.precision);
         assert_eq!(hll.max_window_seconds, restored.max_window_seconds);
+        assert_eq!(hll.first_reached, restored.first_reached);
+    }
+
+    #[test]
+    fn test_distinct_since() {
+        let mut hll = SlidingHyperLogLog::new(12, 3600).unwrap();
+        for i in 0..2000 {
+            hll.update(&i, 1000).unwrap();
+        }
+        // 1000 returning items and 1000 new ones
+        for i in 1000..3000 {
+            hll.update(&i, 2000).unwrap();
+        }
+
+        let new_items = hll.distinct_since(1500);
+        assert!(
+            (new_items - 1000.0).abs() < 150.0,
+            "new since cutoff {}",
+            new_items
+        );
+        // Every returning item is active in the window
+        assert!(hll.estimate_window(2000, 500) > 1800.0);
+        assert!(hll.distinct_since(2000) < 1.0);
+        assert!((hll.distinct_since(0) - hll.estimate_total()).abs() < 1e-9);
+
+        // Decay clears the window registers but not the history
+        hll.decay(2000, 500).unwrap();
+        assert_eq!(hll.distinct_since(1500), new_items);
+
+        let restored = SlidingHyperLogLog::deserialize(&hll.serialize()).unwrap();
+        assert_eq!(restored.distinct_since(1500), new_items);
+    }
+
+    #[test]
+    fn test_distinct_since_after_merge() {
+        let mut early = SlidingHyperLogLog::new(10, 3600).unwrap();
+        let mut late = SlidingHyperLogLog::new(10, 3600).unwrap();
+        for i in 0..500 {
+            early.update(&i, 100).unwrap();
+        }
+        for i in 0..1000 {
+            late.update(&i, 200).unwrap();
+        }
+
+        late.merge(&early).unwrap();
+        let new_items = late.distinct_since(150);
+        assert!(
+            (new_items - 500.0).abs() < 120.0,
+            "new since cutoff {}",
+            new_items
+        );
+    }
+
+    #[test]
+    fn test_record_first_reached() {
+        let mut steps = Vec::new();
+        record_first_reached(&mut steps, 2, 10);
+        record_first_reached(&mut steps, 1, 20);
+        record_first_reached(&mut steps, 4, 30);
+        assert_eq!(steps, vec![(2, 10), (4, 30)]);
+
+        // An earlier arrival replaces the steps it dominates
+        record_first_reached(&mut steps, 3, 5);
+        assert_eq!(steps, vec![(3, 5), (4, 30)]);
+
+        // Steps before the cutoff collapse into the last of them
+        record_first_reached(&mut steps, 6, 50);
+        prune_first_reached(&mut steps, 40);
+        assert_eq!(steps, vec![(4, 30), (6, 50)]);
+        prune_first_reached(&mut steps, 0);
+        assert_eq!(steps, vec![(4, 30), (6, 50)]);
+    }
+
+    #[test]
+    fn test_history_pruned_to_window() {
+        let mut hll = SlidingHyperLogLog::new(4, 100).unwrap();
+        for i in 0..100_000u64 {
+            hll.update(&i, i).unwrap();
+        }
+
+        // At most one step per register predates the window
+        for steps in &hll.first_reached {
+            assert!(steps.iter().filter(|&&(_, t)| t < 99_899).count() <= 1);
+        }
+
+        // Pruning to a narrower decay window keeps answers inside it
+        let new_items = hll.distinct_since(99_990);
+        hll.decay(100_000, 10).unwrap();
+        for steps in &hll.first_reached {
+            assert!(steps.iter().filter(|&&(_, t)| t < 99_990).count() <= 1);
+        }
+        assert_eq!(hll.distinct_since(99_990), new_items);
     }
 }
@@ -0,0 +1,289 @@
+//! Sliding NitroSketch: sampled per-key counts over a sliding time window
+//!
+//! [`NitroSketch`](crate::frequency::NitroSketch) sheds load by only passing
+//! a fraction of updates to its base sketch, but counts the whole stream.
+//! This sketch combines that sampling with the time-bucketed Count-Min ring
+//! of [`SlidingWindowFrequency`](super::SlidingWindowFrequency), for
+//! line-rate telemetry such as "packets per flow in the last minute".
+//!
+//! # Algorithm Overview
+//!
+//! The maximum window is divided into `num_slots` slots of
+//! `ceil(window_size / num_slots)` time units each. Each update is sampled
+//! with probability `sample_rate`, independently of its key; only sampled
+//! updates touch the Count-Min Sketch of slot `t / slot_duration`, so
+//! unsampled updates cost a counter increment and a coin flip.
+//!
+//! A window query syncs every slot overlapping
+//! `[current_time - window, current_time]`: each slot's Count-Min estimate
+//! of sampled events is scaled by `1 / sample_rate`, and the scaled
+//! estimates are summed.
+//!
+//! # Sampling and Windowing
+//!
+//! - The sample is drawn per update, not per key, so every key is sampled
+//!   at the same rate and the scaled estimate is unbiased apart from
+//!   Count-Min's own overestimate, which scaling turns into roughly
+//!   `ε × (events in the slot)` again.
+//! - Sampling noise does not shrink with the window: for a key with `n`
+//!   events in the window, the relative standard error from sampling is
+//!   about `sqrt((1 - p) / (n × p))` at rate `p`. Short windows and rare
+//!   keys see few samples, so a key with fewer than `1 / p` events in the
+//!   window is likely to be estimated as 0 or `1 / p`. Size the rate for
+//!   the smallest window and count you need to resolve.
+//! - Splitting the window into more slots does not add sampling error,
+//!   since slots are sampled independently and their variances simply add,
+//!   but each slot's Count-Min adds its own overestimate.
+//! - Windows are rounded out to whole slots, so events up to one slot
+//!   duration older than the window start can be included.
+//!
+//! # Space Complexity
+//!
+//! `num_slots × CMS size`, as for
+//! [`SlidingWindowFrequency`](super::SlidingWindowFrequency). Slots are
+//! allocated on first sampled update.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::streaming::SlidingNitroSketch;
+//!
+//! // 60-second window in 6 slots, sampling 10% of updates
+//! let mut nitro = SlidingNitroSketch::new(60, 6, 0.1, 0.001, 0.01).unwrap();
+//! for t in 0..60 {
+//!     for _ in 0..1000 {
+//!         nitro.update_sampled_at(b"flow", t);
+//!     }
+//! }
+//!
+//! let estimate = nitro.query_window(b"flow", 59, 60);
+//! assert!((estimate - 60_000.0).abs() < 3_000.0);
+//! ```
+
+use super::time_slots::TimeSlots;
+use crate::common::SketchError;
+use crate::frequency::{stochastic_round, CountMinSketch};
+
+/// Sampled per-key frequency over a sliding time window
+///
+/// # Thread Safety
+///
+/// `SlidingNitroSketch` is `Send + Sync`. Window queries take `&self`.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::streaming::SlidingNitroSketch;
+///
+/// // Without sampling, estimates match SlidingWindowFrequency
+/// let mut nitro = SlidingNitroSketch::new(1000, 10, 1.0, 0.01, 0.01).unwrap();
+/// for t in 0..50 {
+///     nitro.update_sampled_at(b"key", t * 10);
+/// }
+/// assert_eq!(nitro.query_window(b"key", 490, 1000), 50.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlidingNitroSketch {
+    /// One Count-Min Sketch of the sampled updates per time slot
+    slots: TimeSlots<CountMinSketch>,
+    /// Probability that an update reaches its slot's sketch
+    sample_rate: f64,
+    /// Count-Min error bound per slot
+    epsilon: f64,
+    /// Count-Min failure probability per slot
+    delta: f64,
+    /// Updates offered so far; drives the sampling coin flips
+    updates: u64,
+}
+
+impl SlidingNitroSketch {
+    /// Creates a sampled per-key sliding window frequency sketch
+    ///
+    /// # Arguments
+    /// * `window_size` - Largest window that can be queried, in time units
+    /// * `num_slots` - Number of time buckets the window is split into
+    /// * `sample_rate` - Probability of recording each update, in (0, 1]
+    /// * `epsilon` - Count-Min error bound per slot
+    /// * `delta` - Count-Min failure probability per slot
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `window_size` or `num_slots` is 0,
+    /// `num_slots > window_size`, `sample_rate` is outside (0, 1], or
+    /// epsilon/delta are outside (0, 1)
+    pub fn new(
+        window_size: u64,
+        num_slots: usize,
+        sample_rate: f64,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Self, SketchError> {
+        let slots = TimeSlots::new(window_size, num_slots)?;
+        if !(sample_rate > 0.0 && sample_rate <= 1.0) {
+            return Err(SketchError::InvalidParameter {
+                param: "sample_rate".to_string(),
+                value: sample_rate.to_string(),
+                constraint: "must be in (0, 1]".to_string(),
+            });
+        }
+        // Validate the Count-Min parameters up front
+        CountMinSketch::new(epsilon, delta)?;
+
+        Ok(SlidingNitroSketch {
+            slots,
+            sample_rate,
+            epsilon,
+            delta,
+            updates: 0,
+        })
+    }
+
+    /// Offers one occurrence of `key` at `timestamp`, recording it with
+    /// probability `sample_rate`
+    ///
+    /// Updates older than the retained slots are ignored.
+    pub fn update_sampled_at(&mut self, key: &[u8], timestamp: u64) {
+        self.updates = self.updates.wrapping_add(1);
+        if stochastic_round(self.sample_rate, self.updates) == 0 {
+            return;
+        }
+
+        let (epsilon, delta) = (self.epsilon, self.delta);
+        let slot = self.slots.slot_mut(timestamp, || {
+            CountMinSketch::new(epsilon, delta).expect("parameters validated in new")
+        });
+        if let Some(sketch) = slot {
+            sketch.update(&key);
+        }
+    }
+
+    /// Estimates occurrences of `key` in `[current_time - window, current_time]`
+    ///
+    /// Sums the sampled counts of every overlapping slot, scaled by
+    /// `1 / sample_rate`. The window is rounded out to whole slots and capped
+    /// at `window_size`. See the module docs for how sampling affects
+    /// accuracy.
+    pub fn query_window(&self, key: &[u8], current_time: u64, window: u64) -> f64 {
+        self.slots
+            .window(current_time, window)
+            .map(|sketch| sketch.estimate(&key) as f64 / self.sample_rate)
+            .sum()
+    }
+
+    /// Frees slots that no query ending at `current_time` or later can reach
+    pub fn expire(&mut self, current_time: u64) {
+        self.slots.expire(current_time);
+    }
+
+    /// Removes all recorded events
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Returns the probability of recording each update
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Returns the largest window that can be queried
+    pub fn window_size(&self) -> u64 {
+        self.slots.window_size()
+    }
+
+    /// Returns the number of slots in the ring
+    pub fn num_slots(&self) -> usize {
+        self.slots.num_slots()
+    }
+
+    /// Returns the time units covered by each slot
+    pub fn slot_duration(&self) -> u64 {
+        self.slots.slot_duration()
+    }
+
+    /// Returns the bytes used by allocated Count-Min counters
+    ///
+    /// At most `num_slots × width × depth × 8`.
+    pub fn memory_usage(&self) -> usize {
+        self.slots
+            .iter()
+            .map(|sketch| sketch.width() * sketch.depth() * std::mem::size_of::<u64>())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsampled_counts_are_exact() {
+        let mut nitro = SlidingNitroSketch::new(100, 10, 1.0, 0.001, 0.01).unwrap();
+        for t in 0..100 {
+            nitro.update_sampled_at(b"a", t);
+            if t % 2 == 0 {
+                nitro.update_sampled_at(b"b", t);
+            }
+        }
+
+        assert_eq!(nitro.query_window(b"a", 99, 100), 100.0);
+        assert_eq!(nitro.query_window(b"b", 99, 100), 50.0);
+        assert_eq!(nitro.query_window(b"c", 99, 100), 0.0);
+
+        // [69, 99] touches slots 6..=9, i.e. times 60..=99
+        assert_eq!(nitro.query_window(b"a", 99, 30), 40.0);
+    }
+
+    #[test]
+    fn test_sampled_estimates_scale_up() {
+        let mut nitro = SlidingNitroSketch::new(100, 10, 0.1, 0.001, 0.01).unwrap();
+        for t in 0..100 {
+            for _ in 0..500 {
+                nitro.update_sampled_at(b"heavy", t);
+            }
+            for _ in 0..50 {
+                nitro.update_sampled_at(b"light", t);
+            }
+        }
+
+        // 50,000 events: relative standard error ~ sqrt(0.9 / 5000) ~ 1.3%
+        let heavy = nitro.query_window(b"heavy", 99, 100);
+        assert!((heavy - 50_000.0).abs() < 3_000.0, "heavy: {}", heavy);
+
+        // [80, 99] touches slots 8..=9: 1,000 events, ~100 sampled
+        let light = nitro.query_window(b"light", 99, 19);
+        assert!((light - 1_000.0).abs() < 400.0, "light: {}", light);
+
+        // Only about a tenth of the updates reach the sketches
+        let sampled: u64 = nitro.slots.iter().map(|sketch| sketch.total_count()).sum();
+        assert!((4_500..6_500).contains(&sampled), "sampled: {}", sampled);
+    }
+
+    #[test]
+    fn test_buckets_expire() {
+        let mut nitro = SlidingNitroSketch::new(100, 10, 1.0, 0.001, 0.01).unwrap();
+        nitro.update_sampled_at(b"a", 5);
+        nitro.update_sampled_at(b"a", 250);
+        assert_eq!(nitro.query_window(b"a", 250, 100), 1.0);
+
+        // Late updates beyond the ring are dropped
+        nitro.update_sampled_at(b"a", 10);
+        assert_eq!(nitro.query_window(b"a", 250, 100), 1.0);
+
+        nitro.expire(1_000);
+        assert_eq!(nitro.memory_usage(), 0);
+
+        nitro.update_sampled_at(b"a", 1_000);
+        nitro.clear();
+        assert_eq!(nitro.query_window(b"a", 1_000, 100), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SlidingNitroSketch::new(0, 1, 0.5, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 0, 0.5, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 11, 0.5, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 5, 0.0, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 5, 1.5, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 5, f64::NAN, 0.01, 0.01).is_err());
+        assert!(SlidingNitroSketch::new(10, 5, 0.5, 0.0, 0.01).is_err());
+    }
+}
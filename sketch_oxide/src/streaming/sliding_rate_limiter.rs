@@ -0,0 +1,223 @@
+//! Sliding Rate Limiter: per-key admission control over a sliding window
+//!
+//! Keeps one [`SlidingWindowCounter`] per key and admits a request while the
+//! key's count over the last `window_seconds` is below `limit`. Only admitted
+//! requests are counted, so a rejected client regains capacity as soon as
+//! older admissions leave the window.
+//!
+//! # Accuracy
+//!
+//! The window count is the Exponential Histogram estimate, within `ε` of the
+//! true number of admissions. The limiter is therefore approximate: per
+//! window it admits between `limit / (1 + ε)` and `limit / (1 - ε)` requests
+//! rather than exactly `limit`. Counts below `1 / ε` are exact, so small
+//! limits with a matching `ε` behave like an exact limiter.
+//!
+//! # Space Complexity
+//!
+//! One counter per active key, each `O((1/ε) * log²(limit))` buckets. Call
+//! [`expire`](SlidingRateLimiter::expire) periodically to drop idle keys.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::streaming::SlidingRateLimiter;
+//!
+//! // At most 3 requests per 10 seconds for each client
+//! let mut limiter = SlidingRateLimiter::new(3, 10, 0.01).unwrap();
+//!
+//! assert!(limiter.try_acquire(&"client_a", 100));
+//! assert!(limiter.try_acquire(&"client_a", 101));
+//! assert!(limiter.try_acquire(&"client_a", 102));
+//! assert!(!limiter.try_acquire(&"client_a", 103));
+//!
+//! // Other keys have their own budget
+//! assert!(limiter.try_acquire(&"client_b", 103));
+//!
+//! // Once the first admissions slide out, client_a is admitted again
+//! assert!(limiter.try_acquire(&"client_a", 111));
+//! ```
+
+use super::SlidingWindowCounter;
+use crate::common::SketchError;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Approximate per-key sliding window rate limiter
+///
+/// # Thread Safety
+///
+/// `SlidingRateLimiter<K>` is `Send + Sync` whenever `K` is. `try_acquire`
+/// takes `&mut self`, so a limiter shared by request handlers belongs behind a
+/// `Mutex`.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::streaming::SlidingRateLimiter;
+///
+/// let mut limiter = SlidingRateLimiter::new(100, 60, 0.01).unwrap();
+/// let admitted = (0..150)
+///     .filter(|_| limiter.try_acquire(&"api_key", 1_000))
+///     .count();
+/// assert_eq!(admitted, 100);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlidingRateLimiter<K> {
+    /// Maximum admissions per key within the window
+    limit: u64,
+    /// Empty counter cloned for each new key
+    template: SlidingWindowCounter,
+    /// Admission counters by key
+    counters: HashMap<K, SlidingWindowCounter>,
+}
+
+impl<K: Hash + Eq + Clone> SlidingRateLimiter<K> {
+    /// Creates a rate limiter
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum requests admitted per key within the window
+    /// * `window_seconds` - Window length, in the same unit as `now`
+    /// * `epsilon` - Relative error of the window counts, in (0, 1)
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `limit` or `window_seconds` is 0, or
+    /// `epsilon` is not in (0, 1)
+    pub fn new(limit: u64, window_seconds: u64, epsilon: f64) -> Result<Self, SketchError> {
+        if limit == 0 {
+            return Err(SketchError::InvalidParameter {
+                param: "limit".to_string(),
+                value: "0".to_string(),
+                constraint: "must be > 0".to_string(),
+            });
+        }
+
+        Ok(SlidingRateLimiter {
+            limit,
+            template: SlidingWindowCounter::new(window_seconds, epsilon)?,
+            counters: HashMap::new(),
+        })
+    }
+
+    /// Records a request for `key` at time `now` if the key is under its limit
+    ///
+    /// Returns `true` and counts the request when fewer than `limit`
+    /// requests were admitted in `[now - window_seconds, now]`; otherwise
+    /// returns `false` without counting it. `now` should not decrease for a
+    /// given key.
+    pub fn try_acquire(&mut self, key: &K, now: u64) -> bool {
+        let counter = match self.counters.get_mut(key) {
+            Some(counter) => counter,
+            None => self
+                .counters
+                .entry(key.clone())
+                .or_insert_with(|| self.template.clone()),
+        };
+
+        counter.expire(now);
+        if counter.count(now) >= self.limit {
+            return false;
+        }
+
+        counter.increment(now);
+        true
+    }
+
+    /// Returns the approximate number of admitted requests for `key` in the
+    /// window ending at `now`
+    pub fn count(&self, key: &K, now: u64) -> u64 {
+        self.counters
+            .get(key)
+            .map_or(0, |counter| counter.count(now))
+    }
+
+    /// Drops keys with no admissions in the window ending at `now`
+    pub fn expire(&mut self, now: u64) {
+        self.counters.retain(|_, counter| {
+            counter.expire(now);
+            counter.num_buckets() > 0
+        });
+    }
+
+    /// Forgets every key
+    pub fn clear(&mut self) {
+        self.counters.clear();
+    }
+
+    /// Returns the configured per-key limit
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns the window length
+    pub fn window_seconds(&self) -> u64 {
+        self.template.window_size()
+    }
+
+    /// Returns the relative error of the window counts
+    pub fn epsilon(&self) -> f64 {
+        self.template.epsilon()
+    }
+
+    /// Returns the number of keys currently tracked
+    pub fn num_keys(&self) -> usize {
+        self.counters.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SlidingRateLimiter::<u64>::new(0, 10, 0.1).is_err());
+        assert!(SlidingRateLimiter::<u64>::new(5, 0, 0.1).is_err());
+        assert!(SlidingRateLimiter::<u64>::new(5, 10, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_limit_per_window() {
+        let mut limiter = SlidingRateLimiter::new(5, 10, 0.1).unwrap();
+        for t in 0..5 {
+            assert!(limiter.try_acquire(&1u64, t));
+        }
+        // Rejections are not counted
+        for t in 5..=10 {
+            assert!(!limiter.try_acquire(&1u64, t));
+        }
+        assert_eq!(limiter.count(&1, 10), 5);
+
+        // The admission at t=0 leaves the window [1, 11]
+        assert!(limiter.try_acquire(&1u64, 11));
+        assert!(!limiter.try_acquire(&1u64, 11));
+        assert!(limiter.try_acquire(&2u64, 11));
+    }
+
+    #[test]
+    fn test_approximate_bound() {
+        // Large limits are enforced within epsilon
+        let epsilon = 0.05;
+        let mut limiter = SlidingRateLimiter::new(10_000, 60, epsilon).unwrap();
+        let admitted = (0..20_000)
+            .filter(|&i| limiter.try_acquire(&"key", i / 1000))
+            .count() as f64;
+        assert!(admitted >= 10_000.0 / (1.0 + epsilon));
+        assert!(admitted <= 10_000.0 / (1.0 - epsilon));
+    }
+
+    #[test]
+    fn test_expire_drops_idle_keys() {
+        let mut limiter = SlidingRateLimiter::new(3, 10, 0.1).unwrap();
+        limiter.try_acquire(&"a", 0);
+        limiter.try_acquire(&"b", 8);
+        assert_eq!(limiter.num_keys(), 2);
+
+        limiter.expire(15);
+        assert_eq!(limiter.num_keys(), 1);
+        assert_eq!(limiter.count(&"a", 15), 0);
+        assert_eq!(limiter.count(&"b", 15), 1);
+    }
+}
@@ -0,0 +1,306 @@
+//! Sliding Top-K: heavy hitters over a sliding time window
+//!
+//! [`HeavyKeeper`] reports the top-k items of everything it has seen. This
+//! sketch keeps a ring of time-bucketed HeavyKeepers instead, so the top-k
+//! can be asked for "the last 5 minutes" rather than all time.
+//!
+//! # Algorithm Overview
+//!
+//! The maximum window is divided into `num_slots` slots of
+//! `ceil(window_size / num_slots)` time units each, exactly as in
+//! [`SlidingWindowFrequency`](super::SlidingWindowFrequency). An update at
+//! time `t` goes into the HeavyKeeper of slot `t / slot_duration`, which also
+//! remembers the bytes of its `k` highest-counted items as candidates. The
+//! ring holds only the most recent `num_slots` slots; older slots are reused
+//! or freed by [`expire`](SlidingTopK::expire).
+//!
+//! A window query merges the HeavyKeepers of every slot overlapping
+//! `[current_time - window, current_time]`, re-estimates each slot's
+//! candidates against the merged counters and returns the `k` largest.
+//!
+//! # Approximation
+//!
+//! - Merging adds counters cell by cell, so a merged estimate is the sum of
+//!   per-slot estimates. HeavyKeeper's counters only ever overestimate (from
+//!   hash collisions), and those overestimates add up across slots.
+//! - Only items that were among the top `k` of at least one slot are
+//!   considered. An item that is heavy over the window but never stands out
+//!   within a single slot can be missed; fewer, longer slots reduce this.
+//! - Windows are rounded out to whole slots, so events up to one slot
+//!   duration older than the window start can be included.
+//!
+//! # Space Complexity
+//!
+//! `num_slots × (HeavyKeeper size + k candidate keys)`. Slots are allocated
+//! on first use.
+//!
+//! # Examples
+//!
+//! ```
+//! use sketch_oxide::streaming::SlidingTopK;
+//!
+//! // Top 3 over a 300-second window in 5 slots of 60 seconds
+//! let mut topk = SlidingTopK::new(3, 300, 5, 0.001, 0.01).unwrap();
+//! for t in 0..100 {
+//!     topk.update(b"old_favourite", t);
+//! }
+//! for t in 400..450 {
+//!     topk.update(b"trending", t);
+//! }
+//!
+//! let top = topk.top_k_window(450, 300);
+//! assert_eq!(top[0].0, b"trending".to_vec());
+//! assert!(top.iter().all(|(item, _)| item != b"old_favourite"));
+//! ```
+
+use super::time_slots::TimeSlots;
+use crate::common::SketchError;
+use crate::frequency::HeavyKeeper;
+use std::collections::HashMap;
+
+/// State of one time slot
+#[derive(Clone)]
+struct Slot {
+    keeper: HeavyKeeper,
+    /// Up to `k` highest-counted items of this slot, with their last estimate
+    candidates: HashMap<Vec<u8>, u32>,
+}
+
+/// Top-k heavy hitters over a sliding time window
+///
+/// # Thread Safety
+///
+/// `SlidingTopK` is `Send + Sync`. Window queries take `&self`.
+///
+/// # Examples
+///
+/// ```
+/// use sketch_oxide::streaming::SlidingTopK;
+///
+/// let mut topk = SlidingTopK::new(2, 60, 6, 0.001, 0.01).unwrap();
+/// for t in 0..30 {
+///     topk.update(b"a", t);
+///     if t % 3 == 0 {
+///         topk.update(b"b", t);
+///     }
+/// }
+/// topk.update(b"c", 29);
+///
+/// let top = topk.top_k_window(29, 60);
+/// assert_eq!(top, vec![(b"a".to_vec(), 30), (b"b".to_vec(), 10)]);
+/// ```
+#[derive(Clone)]
+pub struct SlidingTopK {
+    /// Number of heavy hitters reported
+    k: usize,
+    /// One HeavyKeeper and its candidates per time slot
+    slots: TimeSlots<Slot>,
+    /// HeavyKeeper error bound per slot
+    epsilon: f64,
+    /// HeavyKeeper failure probability per slot
+    delta: f64,
+}
+
+impl SlidingTopK {
+    /// Creates a sliding window top-k tracker
+    ///
+    /// # Arguments
+    /// * `k` - Number of heavy hitters to report
+    /// * `window_size` - Largest window that can be queried, in time units
+    /// * `num_slots` - Number of time buckets the window is split into
+    /// * `epsilon` - HeavyKeeper error bound per slot
+    /// * `delta` - HeavyKeeper failure probability per slot
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `window_size` or `num_slots` is 0,
+    /// `num_slots > window_size`, or the HeavyKeeper parameters are invalid
+    pub fn new(
+        k: usize,
+        window_size: u64,
+        num_slots: usize,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Self, SketchError> {
+        let slots = TimeSlots::new(window_size, num_slots)?;
+        // Validate the HeavyKeeper parameters up front
+        HeavyKeeper::new(k, epsilon, delta)?;
+
+        Ok(SlidingTopK {
+            k,
+            slots,
+            epsilon,
+            delta,
+        })
+    }
+
+    /// Records one occurrence of `item` at `timestamp`
+    ///
+    /// Updates older than the retained slots are ignored.
+    pub fn update(&mut self, item: &[u8], timestamp: u64) {
+        let (k, epsilon, delta) = (self.k, self.epsilon, self.delta);
+        let Some(slot) = self.slots.slot_mut(timestamp, || Slot {
+            keeper: HeavyKeeper::new(k, epsilon, delta).expect("parameters validated in new"),
+            candidates: HashMap::with_capacity(k),
+        }) else {
+            return;
+        };
+        slot.keeper.update(item);
+        let count = slot.keeper.estimate(item);
+
+        if let Some(known) = slot.candidates.get_mut(item) {
+            *known = count;
+        } else if slot.candidates.len() < k {
+            slot.candidates.insert(item.to_vec(), count);
+        } else if let Some((weakest, &min)) = slot.candidates.iter().min_by_key(|(_, &c)| c) {
+            if count > min {
+                let weakest = weakest.clone();
+                slot.candidates.remove(&weakest);
+                slot.candidates.insert(item.to_vec(), count);
+            }
+        }
+    }
+
+    /// Returns the top-k items in `[current_time - window, current_time]`
+    ///
+    /// Merges the HeavyKeepers of the overlapping slots and ranks every slot's
+    /// candidates by their merged estimate, largest first (ties by item
+    /// bytes). The window is rounded out to whole slots and capped at
+    /// `window_size`. See the module docs for the approximation involved.
+    pub fn top_k_window(&self, current_time: u64, window: u64) -> Vec<(Vec<u8>, u32)> {
+        let mut in_window = self.slots.window(current_time, window);
+        let Some(head) = in_window.next() else {
+            return Vec::new();
+        };
+
+        let mut merged = head.keeper.clone();
+        let mut candidates: Vec<&Vec<u8>> = head.candidates.keys().collect();
+        for slot in in_window {
+            merged
+                .merge(&slot.keeper)
+                .expect("slots share HeavyKeeper parameters");
+            candidates.extend(slot.candidates.keys());
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut top: Vec<(Vec<u8>, u32)> = candidates
+            .into_iter()
+            .map(|item| (item.clone(), merged.estimate(item)))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top.truncate(self.k);
+        top
+    }
+
+    /// Frees slots that no query ending at `current_time` or later can reach
+    pub fn expire(&mut self, current_time: u64) {
+        self.slots.expire(current_time);
+    }
+
+    /// Removes all recorded events
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Returns the number of heavy hitters reported
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the largest window that can be queried
+    pub fn window_size(&self) -> u64 {
+        self.slots.window_size()
+    }
+
+    /// Returns the number of slots in the ring
+    pub fn num_slots(&self) -> usize {
+        self.slots.num_slots()
+    }
+
+    /// Returns the time units covered by each slot
+    pub fn slot_duration(&self) -> u64 {
+        self.slots.slot_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k_within_window() {
+        let mut topk = SlidingTopK::new(3, 100, 10, 0.001, 0.01).unwrap();
+        for t in 0..100 {
+            topk.update(b"steady", t);
+            if t % 2 == 0 {
+                topk.update(b"half", t);
+            }
+            if t % 10 == 0 {
+                topk.update(b"rare", t);
+            }
+        }
+        topk.update(b"once", 99);
+
+        let top = topk.top_k_window(99, 100);
+        assert_eq!(
+            top,
+            vec![
+                (b"steady".to_vec(), 100),
+                (b"half".to_vec(), 50),
+                (b"rare".to_vec(), 10),
+            ]
+        );
+
+        // [69, 99] touches slots 6..=9, i.e. times 60..=99
+        let recent = topk.top_k_window(99, 30);
+        assert_eq!(recent[0], (b"steady".to_vec(), 40));
+        assert_eq!(recent[1], (b"half".to_vec(), 20));
+    }
+
+    #[test]
+    fn test_old_buckets_expire() {
+        let mut topk = SlidingTopK::new(2, 100, 10, 0.001, 0.01).unwrap();
+        for t in 0..50 {
+            topk.update(b"early", t);
+        }
+        for t in 200..210 {
+            topk.update(b"late", t);
+        }
+
+        let top = topk.top_k_window(210, 100);
+        assert_eq!(top, vec![(b"late".to_vec(), 10)]);
+
+        // Updates older than the ring are dropped
+        topk.update(b"early", 5);
+        assert_eq!(topk.top_k_window(210, 100).len(), 1);
+
+        topk.expire(1_000);
+        assert!(topk.top_k_window(1_000, 100).is_empty());
+    }
+
+    #[test]
+    fn test_candidates_bounded_by_k() {
+        let mut topk = SlidingTopK::new(2, 10, 1, 0.001, 0.01).unwrap();
+        for i in 0..50u32 {
+            topk.update(&i.to_le_bytes(), 0);
+        }
+        for _ in 0..5 {
+            topk.update(b"heavy", 1);
+        }
+
+        let slot = topk.slots.iter().next().unwrap();
+        assert!(slot.candidates.len() <= 2);
+        assert_eq!(topk.top_k_window(1, 10)[0], (b"heavy".to_vec(), 5));
+
+        topk.clear();
+        assert!(topk.top_k_window(1, 10).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SlidingTopK::new(3, 0, 1, 0.01, 0.01).is_err());
+        assert!(SlidingTopK::new(3, 10, 0, 0.01, 0.01).is_err());
+        assert!(SlidingTopK::new(3, 10, 11, 0.01, 0.01).is_err());
+        assert!(SlidingTopK::new(0, 10, 5, 0.01, 0.01).is_err());
+    }
+}
@@ -6,8 +6,18 @@
 //! # Algorithm Overview
 //!
 //! The Exponential Histogram maintains buckets of exponentially increasing sizes.
-//! Each bucket stores a timestamp and a count. Old buckets are merged or expired
-//! as the window slides.
+//! Each bucket stores the timestamp of its most recent event and a count. Old
+//! buckets are merged or expired as the window slides.
+//!
+//! With `k = ceil(1 / ε)`, every bucket size below the largest keeps between
+//! `k` and `k + 1` buckets: when a size reaches `k + 2`, its two oldest buckets
+//! merge into one of double size. A query sums the buckets in the window and
+//! counts only half of the oldest one, size `C`, which is the only bucket that
+//! can straddle the window start. At least `k` buckets of every smaller size
+//! lie inside the window, so the true count is at least `k * (C - 1) + 1` and
+//! the error, at most `C / 2`, stays within `ε` of it. The `1 / (2ε)` bound
+//! from the continuous analysis is not enough once counts are integers: with
+//! `C = 2` the estimate can be off by one against a true count near `1 / ε`.
 //!
 //! # Properties
 //!
@@ -50,7 +60,7 @@ use crate::common::SketchError;
 /// A bucket in the exponential histogram
 #[derive(Clone, Debug)]
 struct Bucket {
-    /// Timestamp when this bucket was created
+    /// Timestamp of the most recent event in this bucket
     timestamp: u64,
     /// Count of events in this bucket (always a power of 2)
     count: u64,
@@ -61,6 +71,10 @@ struct Bucket {
 /// Maintains an approximate count of events within a sliding time window.
 /// Uses O(log²N) space while providing (1 ± ε) accuracy.
 ///
+/// # Thread Safety
+///
+/// `SlidingWindowCounter` is `Send + Sync`. Increments take `&mut self`.
+///
 /// # Examples
 ///
 /// ```
@@ -84,7 +98,8 @@ pub struct SlidingWindowCounter {
     window_size: u64,
     /// Error bound (epsilon)
     epsilon: f64,
-    /// Maximum buckets per level (k = ceil(1/epsilon))
+    /// Minimum buckets per size below the largest (k = ceil(1/epsilon));
+    /// each size holds at most k+1
     k: usize,
     /// Total count (may include expired items)
     total: u64,
@@ -127,7 +142,7 @@ impl SlidingWindowCounter {
             });
         }
 
-        // k = ceil(1/epsilon) determines max buckets per level
+        // k = ceil(1/epsilon) buckets per size bounds the error by epsilon
         let k = (1.0 / epsilon).ceil() as usize;
 
         Ok(SlidingWindowCounter {
@@ -180,70 +195,147 @@ impl SlidingWindowCounter {
         }
 
         self.total += count;
+        self.insert_units(timestamp, count);
+    }
+
+    /// Adds `count` unit buckets at `timestamp` and restores the invariant
+    /// (between k and k+1 buckets of each size, except possibly the largest)
+    ///
+    /// Leaves as many buckets of each size as `count` single increments
+    /// would, but carries the new buckets arithmetically so the cost is
+    /// O(k * log(count)) rather than O(count).
+    fn insert_units(&mut self, timestamp: u64, count: u64) {
+        let max_per_size = self.k as u64 + 1;
+        let mut existing = std::mem::take(&mut self.buckets).into_iter().peekable();
+        let mut buckets = Vec::with_capacity(existing.len() + self.k + 2);
+
+        // Buckets of the current size carried in from the smaller size:
+        // `carry_new` identical buckets stamped `timestamp`, plus merged
+        // buckets with other timestamps (newest first).
+        let mut carry_new = count;
+        let mut carry_old: Vec<u64> = Vec::new();
+        let mut size = 1u64;
+
+        while carry_new > 0 || !carry_old.is_empty() {
+            // Older buckets of this size, newest first
+            let mut older = carry_old;
+            while let Some(bucket) = existing.next_if(|b| b.count == size) {
+                older.push(bucket.timestamp);
+            }
+
+            let n = carry_new + older.len() as u64;
+            let mut merges = n.saturating_sub(max_per_size).div_ceil(2);
+
+            // Merge the oldest pairs; a merged bucket keeps the newer timestamp
+            let mut next_new = 0u64;
+            let mut next_old = Vec::new();
+            let mut remaining = older.len();
+            while merges > 0 && remaining >= 2 {
+                let newer_ts = older[remaining - 2];
+                if newer_ts == timestamp {
+                    next_new += 1;
+                } else {
+                    next_old.push(newer_ts);
+                }
+                remaining -= 2;
+                merges -= 1;
+            }
+            if merges > 0 && remaining == 1 {
+                next_new += 1;
+                carry_new -= 1;
+                remaining = 0;
+                merges -= 1;
+            }
+            next_new += merges;
+            carry_new -= 2 * merges;
 
-        // Add new bucket(s) for the count
-        // We need to decompose count into powers of 2
-        let mut remaining = count;
-        while remaining > 0 {
-            // Find the largest power of 2 <= remaining
-            let power = 63 - remaining.leading_zeros();
-            let bucket_count = 1u64 << power;
-
-            self.buckets.insert(
-                0,
-                Bucket {
+            for _ in 0..carry_new {
+                buckets.push(Bucket {
                     timestamp,
-                    count: bucket_count,
-                },
-            );
+                    count: size,
+                });
+            }
+            for &ts in &older[..remaining] {
+                buckets.push(Bucket {
+                    timestamp: ts,
+                    count: size,
+                });
+            }
 
-            remaining -= bucket_count;
+            next_old.reverse();
+            carry_new = next_new;
+            carry_old = next_old;
+            size = size.saturating_mul(2);
         }
 
-        // Merge buckets if needed to maintain invariant
-        self.merge_buckets();
+        buckets.extend(existing);
+        self.buckets = buckets;
     }
 
-    /// Merges buckets to maintain the exponential histogram invariant
-    /// (at most k+1 buckets of each size, except possibly the largest)
-    fn merge_buckets(&mut self) {
-        if self.buckets.len() < 2 {
-            return;
+    /// Merges another counter into this one
+    ///
+    /// Both counters must have the same `window_size` and `epsilon`. The
+    /// buckets of both histograms are replayed oldest first through the
+    /// same carry rule as [`increment_by`](Self::increment_by), so the
+    /// merged histogram keeps between k and k+1 buckets of every size below
+    /// the largest. Events keep the timestamp of the bucket that held them,
+    /// so the merged count carries the error of each input plus at most
+    /// `epsilon` from the re-merging.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IncompatibleSketches` if the window sizes or error bounds differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sketch_oxide::streaming::SlidingWindowCounter;
+    ///
+    /// let mut east = SlidingWindowCounter::new(1000, 0.1).unwrap();
+    /// let mut west = SlidingWindowCounter::new(1000, 0.1).unwrap();
+    /// east.increment_by(100, 40);
+    /// west.increment_by(200, 60);
+    ///
+    /// east.merge(&west).unwrap();
+    /// let count = east.count(500) as f64;
+    /// assert!((count - 100.0).abs() <= 10.0);
+    /// ```
+    pub fn merge(&mut self, other: &Self) -> Result<(), SketchError> {
+        if self.window_size != other.window_size {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!(
+                    "window_size mismatch: {} vs {}",
+                    self.window_size, other.window_size
+                ),
+            });
+        }
+        if self.epsilon != other.epsilon {
+            return Err(SketchError::IncompatibleSketches {
+                reason: format!("epsilon mismatch: {} vs {}", self.epsilon, other.epsilon),
+            });
         }
 
-        // Process from smallest to largest buckets
-        // Group buckets by their count (power of 2)
-        let mut i = 0;
-        while i < self.buckets.len() {
-            let current_count = self.buckets[i].count;
-
-            // Count consecutive buckets with same count
-            let mut same_count = 1;
-            while i + same_count < self.buckets.len()
-                && self.buckets[i + same_count].count == current_count
-            {
-                same_count += 1;
-            }
+        let mut events: Vec<(u64, u64)> = std::mem::take(&mut self.buckets)
+            .into_iter()
+            .chain(other.buckets.iter().cloned())
+            .map(|b| (b.timestamp, b.count))
+            .collect();
+        events.sort_unstable_by_key(|&(timestamp, _)| timestamp);
 
-            // If too many buckets of this size, merge the oldest two
-            while same_count > self.k + 1 {
-                // Merge the two oldest (last in this group)
-                let merge_idx = i + same_count - 2;
-                let older_timestamp = self.buckets[merge_idx + 1].timestamp;
-
-                // Create merged bucket with double count
-                self.buckets[merge_idx] = Bucket {
-                    timestamp: older_timestamp,
-                    count: current_count * 2,
-                };
-
-                // Remove the oldest of the pair
-                self.buckets.remove(merge_idx + 1);
-                same_count -= 1;
+        // Replay oldest first, one carry per distinct timestamp
+        let mut i = 0;
+        while i < events.len() {
+            let timestamp = events[i].0;
+            let mut count = 0u64;
+            while i < events.len() && events[i].0 == timestamp {
+                count += events[i].1;
+                i += 1;
             }
-
-            i += same_count;
+            self.insert_units(timestamp, count);
         }
+
+        self.total += other.total;
+        Ok(())
     }
 
     /// Returns the approximate count within the window ending at the given timestamp
@@ -270,52 +362,36 @@ impl SlidingWindowCounter {
     /// ```
     pub fn count(&self, current_time: u64) -> u64 {
         let window_start = current_time.saturating_sub(self.window_size);
-
-        let mut total = 0u64;
-        let mut last_partial = 0u64;
-
-        for bucket in &self.buckets {
-            if bucket.timestamp > current_time {
-                // Future bucket, skip
-                continue;
-            }
-
-            if bucket.timestamp >= window_start {
-                // Fully within window
-                total += bucket.count;
-            } else {
-                // Partially outside window - this is the oldest relevant bucket
-                // We count half of it (approximation)
-                last_partial = bucket.count / 2;
-                break;
-            }
-        }
-
-        total + last_partial
+        self.count_range(window_start, current_time)
     }
 
     /// Returns the count for a specific time range
     ///
+    /// Sums the buckets whose most recent event falls in the range and
+    /// counts half of the oldest one, which may straddle `start`.
+    ///
     /// # Arguments
     ///
     /// * `start` - Start of the range (inclusive)
     /// * `end` - End of the range (inclusive)
     pub fn count_range(&self, start: u64, end: u64) -> u64 {
         let mut total = 0u64;
+        let mut oldest = 0u64;
 
         for bucket in &self.buckets {
             if bucket.timestamp > end {
+                // Future bucket, skip
                 continue;
             }
             if bucket.timestamp < start {
-                // Partially before range
-                total += bucket.count / 2;
+                // Every event in this and older buckets is before the range
                 break;
             }
             total += bucket.count;
+            oldest = bucket.count;
         }
 
-        total
+        total - oldest / 2
     }
 
     /// Expires old buckets outside the window
@@ -328,19 +404,10 @@ impl SlidingWindowCounter {
     pub fn expire(&mut self, current_time: u64) {
         let window_start = current_time.saturating_sub(self.window_size);
 
-        // Remove buckets entirely outside the window
-        // Keep one bucket that might be partially in the window
-        let mut found_outside = false;
-        self.buckets.retain(|bucket| {
-            if bucket.timestamp >= window_start {
-                true
-            } else if !found_outside {
-                found_outside = true;
-                true // Keep one partial bucket
-            } else {
-                false
-            }
-        });
+        // A bucket whose most recent event is before the window is entirely
+        // outside it
+        self.buckets
+            .retain(|bucket| bucket.timestamp >= window_start);
     }
 
     /// Clears all buckets
@@ -356,7 +423,7 @@ impl SlidingWindowCounter {
 
     /// Returns the theoretical error bound
     ///
-    /// The actual count is within (1 ± epsilon) * returned_count
+    /// The returned count is within (1 ± epsilon) * actual_count
     pub fn error_bound(&self) -> f64 {
         self.epsilon
     }
@@ -580,6 +647,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_increment_by_matches_unit_increments() {
+        let mut batched = SlidingWindowCounter::new(1000, 0.05).unwrap();
+        let mut units = SlidingWindowCounter::new(1000, 0.05).unwrap();
+
+        for (t, n) in [(1u64, 37u64), (2, 1), (5, 200), (9, 3)] {
+            batched.increment_by(t, n);
+            for _ in 0..n {
+                units.increment(t);
+            }
+        }
+
+        let sizes =
+            |c: &SlidingWindowCounter| c.buckets.iter().map(|b| b.count).collect::<Vec<_>>();
+        assert_eq!(sizes(&batched), sizes(&units));
+        assert_eq!(batched.count(9), units.count(9));
+    }
+
+    #[test]
+    fn test_merge_sums_windowed_counts() {
+        let epsilon = 0.05;
+        let mut a = SlidingWindowCounter::new(1000, epsilon).unwrap();
+        let mut b = SlidingWindowCounter::new(1000, epsilon).unwrap();
+        for t in 0..2000u64 {
+            a.increment(t);
+            if t % 3 == 0 {
+                b.increment_by(t, 2);
+            }
+        }
+
+        let now = 1999;
+        let actual_a = 1000.0;
+        let actual_b = 2.0 * (1000..2000).filter(|t| t % 3 == 0).count() as f64;
+
+        a.merge(&b).unwrap();
+        let actual = actual_a + actual_b;
+        let error = (a.count(now) as f64 - actual).abs() / actual;
+        assert!(error <= epsilon, "Error {} too high", error);
+
+        // The merged histogram still satisfies the bucket-count invariant
+        let mut sizes: Vec<u64> = a.buckets.iter().map(|b| b.count).collect();
+        let largest = *sizes.iter().max().unwrap();
+        sizes.retain(|&c| c != largest);
+        let mut size = 1;
+        while size < largest {
+            let n = sizes.iter().filter(|&&c| c == size).count();
+            assert!(n <= a.k + 1, "{} buckets of size {}", n, size);
+            size *= 2;
+        }
+    }
+
+    #[test]
+    fn test_merge_incompatible() {
+        let mut a = SlidingWindowCounter::new(1000, 0.1).unwrap();
+        assert!(a
+            .merge(&SlidingWindowCounter::new(500, 0.1).unwrap())
+            .is_err());
+        assert!(a
+            .merge(&SlidingWindowCounter::new(1000, 0.05).unwrap())
+            .is_err());
+    }
+
     #[test]
     fn test_memory_usage() {
         let counter = SlidingWindowCounter::new(1000, 0.1).unwrap();
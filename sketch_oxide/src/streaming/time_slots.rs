@@ -0,0 +1,185 @@
+//! Ring of time slots shared by the slotted sliding window sketches
+//!
+//! [`SlidingWindowFrequency`](super::SlidingWindowFrequency),
+//! [`SlidingNitroSketch`](super::SlidingNitroSketch) and
+//! [`SlidingTopK`](super::SlidingTopK) all split their maximum window into
+//! `num_slots` slots of `ceil(window_size / num_slots)` time units and keep
+//! one sketch per slot. [`TimeSlots`] holds that ring: slot `t /
+//! slot_duration` lives at position `epoch % num_slots`, a position is
+//! cleared when a newer epoch maps onto it, and updates older than the
+//! retained slots are dropped.
+
+use crate::common::SketchError;
+
+/// One slot of the ring
+#[derive(Clone, Debug)]
+struct Slot<S> {
+    /// Slot number (`timestamp / slot_duration`) this state covers
+    epoch: u64,
+    state: S,
+}
+
+/// Ring of per-slot states covering a sliding time window
+///
+/// States are created on first use of their slot.
+#[derive(Clone, Debug)]
+pub(crate) struct TimeSlots<S> {
+    /// Largest window that can be queried
+    window_size: u64,
+    /// Time units covered by each slot
+    slot_duration: u64,
+    /// Ring of slots, indexed by `epoch % num_slots`
+    slots: Vec<Option<Slot<S>>>,
+}
+
+impl<S> TimeSlots<S> {
+    /// Creates an empty ring of `num_slots` slots spanning `window_size`
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `window_size` or `num_slots` is 0, or
+    /// `num_slots > window_size`
+    pub(crate) fn new(window_size: u64, num_slots: usize) -> Result<Self, SketchError> {
+        if window_size == 0 {
+            return Err(SketchError::InvalidParameter {
+                param: "window_size".to_string(),
+                value: window_size.to_string(),
+                constraint: "must be > 0".to_string(),
+            });
+        }
+        if num_slots == 0 || num_slots as u64 > window_size {
+            return Err(SketchError::InvalidParameter {
+                param: "num_slots".to_string(),
+                value: num_slots.to_string(),
+                constraint: format!("must be in [1, window_size ({})]", window_size),
+            });
+        }
+
+        Ok(TimeSlots {
+            window_size,
+            slot_duration: window_size.div_ceil(num_slots as u64),
+            slots: std::iter::repeat_with(|| None).take(num_slots).collect(),
+        })
+    }
+
+    /// Returns the state of the slot holding `timestamp`
+    ///
+    /// Calls `create` if the slot is unused or its position holds an older
+    /// slot. Returns `None` if a newer slot already reuses the position, in
+    /// which case `timestamp` is too old to keep.
+    pub(crate) fn slot_mut(
+        &mut self,
+        timestamp: u64,
+        create: impl FnOnce() -> S,
+    ) -> Option<&mut S> {
+        let epoch = timestamp / self.slot_duration;
+        let idx = (epoch % self.slots.len() as u64) as usize;
+
+        let entry = &mut self.slots[idx];
+        match entry {
+            Some(slot) if slot.epoch == epoch => {}
+            Some(slot) if slot.epoch > epoch => return None,
+            _ => {
+                *entry = Some(Slot {
+                    epoch,
+                    state: create(),
+                })
+            }
+        }
+        entry.as_mut().map(|slot| &mut slot.state)
+    }
+
+    /// Returns the states of slots overlapping `[current_time - window, current_time]`
+    ///
+    /// The window is rounded out to whole slots and capped at `window_size`.
+    pub(crate) fn window(&self, current_time: u64, window: u64) -> impl Iterator<Item = &S> {
+        let window = window.min(self.window_size);
+        let last = current_time / self.slot_duration;
+        let first = current_time.saturating_sub(window) / self.slot_duration;
+
+        self.slots
+            .iter()
+            .flatten()
+            .filter(move |slot| slot.epoch >= first && slot.epoch <= last)
+            .map(|slot| &slot.state)
+    }
+
+    /// Returns the states of all allocated slots
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &S> {
+        self.slots.iter().flatten().map(|slot| &slot.state)
+    }
+
+    /// Frees slots that no query ending at `current_time` or later can reach
+    pub(crate) fn expire(&mut self, current_time: u64) {
+        let first = current_time.saturating_sub(self.window_size) / self.slot_duration;
+        for entry in &mut self.slots {
+            if entry.as_ref().is_some_and(|slot| slot.epoch < first) {
+                *entry = None;
+            }
+        }
+    }
+
+    /// Frees every slot
+    pub(crate) fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    /// Returns the largest window that can be queried
+    pub(crate) fn window_size(&self) -> u64 {
+        self.window_size
+    }
+
+    /// Returns the number of slots in the ring
+    pub(crate) fn num_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the time units covered by each slot
+    pub(crate) fn slot_duration(&self) -> u64 {
+        self.slot_duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_are_reused_and_late_updates_dropped() {
+        let mut slots = TimeSlots::new(100, 10).unwrap();
+        assert_eq!(slots.slot_duration(), 10);
+
+        *slots.slot_mut(5, || 0).unwrap() += 1;
+        *slots.slot_mut(7, || 0).unwrap() += 1;
+        assert_eq!(slots.window(9, 10).sum::<u32>(), 2);
+
+        // Time 105 maps onto the same position and replaces the old slot
+        *slots.slot_mut(105, || 0).unwrap() += 1;
+        assert_eq!(slots.iter().sum::<u32>(), 1);
+        assert!(slots.slot_mut(5, || 0).is_none());
+    }
+
+    #[test]
+    fn test_window_rounds_to_slots_and_expires() {
+        let mut slots = TimeSlots::new(100, 10).unwrap();
+        for t in 0..100 {
+            *slots.slot_mut(t, || 0).unwrap() += 1;
+        }
+
+        // [69, 99] touches slots 6..=9, i.e. times 60..=99
+        assert_eq!(slots.window(99, 30).sum::<u32>(), 40);
+        // Windows are capped at window_size
+        assert_eq!(slots.window(99, 1_000).sum::<u32>(), 100);
+
+        slots.expire(150);
+        assert_eq!(slots.iter().count(), 5);
+        slots.clear();
+        assert_eq!(slots.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(TimeSlots::<u32>::new(0, 1).is_err());
+        assert!(TimeSlots::<u32>::new(10, 0).is_err());
+        assert!(TimeSlots::<u32>::new(10, 11).is_err());
+    }
+}
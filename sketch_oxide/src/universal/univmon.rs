@@ -97,6 +97,11 @@ use twox_hash::XxHash64;
 /// - **Entropy query**: O(d * log n)
 /// - **Heavy hitters**: O(k * d) where k = number of heavy hitters
 /// - **Space**: O((log n / ε²) * log(1/δ))
+///
+/// # Thread Safety
+///
+/// `UnivMon` is `Send + Sync`. Updates take `&mut self`; metric queries take
+/// `&self`.
 #[derive(Clone, Debug)]
 pub struct UnivMon {
     /// Hierarchical layers (L = ceil(log2(max_stream_size)))
@@ -237,10 +237,89 @@ fn test_serialize_empty_filter() {
     assert!(restored.is_empty());
 }
 
+#[test]
+fn test_serialize_preserves_block_size() {
+    for block_bits in [256, 512, 1024] {
+        let mut filter = BlockedBloomFilter::with_block_bits(500, 0.01, block_bits);
+        for i in 0..500 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+
+        let restored = BlockedBloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored.block_bits(), block_bits);
+        assert_eq!(restored.params(), filter.params());
+        for i in 0..500 {
+            assert!(restored.contains(format!("key{}", i).as_bytes()));
+        }
+    }
+}
+
+#[test]
+fn test_deserialize_legacy_format() {
+    // Headerless layout written before block sizes were configurable
+    let mut filter = BlockedBloomFilter::new(100, 0.01);
+    filter.insert(b"old_key");
+    let current = filter.to_bytes();
+    let (n, num_blocks, k) = filter.params();
+    let mut legacy = Vec::new();
+    for field in [n, num_blocks, k] {
+        legacy.extend_from_slice(&(field as u64).to_le_bytes());
+    }
+    legacy.extend_from_slice(&current[current.len() - num_blocks * 64..]);
+
+    let restored = BlockedBloomFilter::from_bytes(&legacy).unwrap();
+    assert_eq!(restored.block_bits(), 512);
+    assert!(restored.contains(b"old_key"));
+
+    // Truncated or mislabelled input is rejected
+    assert!(BlockedBloomFilter::from_bytes(&current[..current.len() - 1]).is_err());
+    let mut bad_block = current.clone();
+    bad_block[29] = 0xFF; // low byte of block_bits
+    assert!(BlockedBloomFilter::from_bytes(&bad_block).is_err());
+}
+
 // ============================================================================
 // Phase 6: Cache Efficiency Tests
 // ============================================================================
 
+#[test]
+fn test_block_sizes_have_no_false_negatives_and_bounded_fpr() {
+    for block_bits in [256, 512, 1024] {
+        let mut filter = BlockedBloomFilter::with_block_bits(5_000, 0.01, block_bits);
+        for i in 0..5_000 {
+            filter.insert(format!("key{}", i).as_bytes());
+        }
+
+        assert_eq!(filter.block_bits(), block_bits);
+        assert_eq!(filter.memory_usage(), filter.params().1 * block_bits / 8);
+        for i in 0..5_000 {
+            assert!(filter.contains(format!("key{}", i).as_bytes()));
+        }
+
+        let false_positives = (0..50_000)
+            .filter(|i| filter.contains(format!("other{}", i).as_bytes()))
+            .count();
+        let fpr = false_positives as f64 / 50_000.0;
+        assert!(fpr < 0.03, "{}-bit blocks: FPR {}", block_bits, fpr);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Block size must be 256, 512 or 1024 bits")]
+fn test_invalid_block_bits() {
+    BlockedBloomFilter::with_block_bits(100, 0.01, 128);
+}
+
+#[test]
+#[should_panic(expected = "same block size")]
+fn test_merge_different_block_sizes_panics() {
+    // Both need a single block, so only the block size differs
+    let mut a = BlockedBloomFilter::with_block_bits(100, 0.5, 256);
+    let b = BlockedBloomFilter::with_block_bits(100, 0.5, 512);
+    assert_eq!(a.params(), b.params());
+    a.merge(&b);
+}
+
 #[test]
 fn test_block_alignment() {
     // Each block is 512 bits (64 bytes = cache line)
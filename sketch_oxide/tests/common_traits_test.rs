@@ -85,6 +85,7 @@ mod mergeable_trait_tests {
     use sketch_oxide::common::{Mergeable, Sketch, SketchError};
 
     // Mock mergeable sketch
+    #[derive(Clone)]
     struct MockMergeableSketch {
         value: u64,
     }
@@ -159,6 +160,138 @@ mod mergeable_trait_tests {
         // Should be equal (if commutative)
         assert_eq!(sketch_a1.value, sketch_a2.value);
     }
+
+    #[test]
+    fn test_mergeable_merged_leaves_operands() {
+        let a = MockMergeableSketch { value: 10 };
+        let b = MockMergeableSketch { value: 20 };
+
+        let merged = a.merged(&b).unwrap();
+        assert_eq!(merged.value, 30);
+        assert_eq!(a.value, 10);
+        assert_eq!(b.value, 20);
+    }
+}
+
+#[cfg(test)]
+mod cardinality_sketch_tests {
+    use sketch_oxide::cardinality::{CpcSketch, HyperLogLog, ThetaSketch, UltraLogLog};
+    use sketch_oxide::common::{CardinalitySketch, Sketch};
+
+    /// Checks the union of [0, 1000) and [500, 1500) and that inputs are untouched
+    fn assert_union<S: CardinalitySketch>(a: &S, b: &S) {
+        let (before_a, before_b) = (a.cardinality(), b.cardinality());
+
+        let union = a.estimate_union(b).unwrap();
+        let error = (union - 1500.0).abs() / 1500.0;
+        assert!(error < 0.05, "union {} off by {}", union, error);
+
+        assert_eq!(a.cardinality(), before_a);
+        assert_eq!(b.cardinality(), before_b);
+    }
+
+    fn fill_sketch<S: Sketch<Item = u64>>(mut a: S, mut b: S) -> (S, S) {
+        for i in 0..1000u64 {
+            a.update(&i);
+            b.update(&(i + 500));
+        }
+        (a, b)
+    }
+
+    #[test]
+    fn test_estimate_union_hyperloglog() {
+        let (a, b) = fill_sketch(HyperLogLog::new(12).unwrap(), HyperLogLog::new(12).unwrap());
+        assert_union(&a, &b);
+        assert!(a.estimate_union(&HyperLogLog::new(10).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_estimate_union_ultraloglog() {
+        let (a, b) = fill_sketch(UltraLogLog::new(12).unwrap(), UltraLogLog::new(12).unwrap());
+        assert_union(&a, &b);
+        assert!(a.estimate_union(&UltraLogLog::new(10).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_estimate_union_cpc() {
+        let (a, b) = fill_sketch(CpcSketch::new(11).unwrap(), CpcSketch::new(11).unwrap());
+        assert_union(&a, &b);
+        assert!(a.estimate_union(&CpcSketch::new(10).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_estimate_union_theta() {
+        let mut a = ThetaSketch::new(12).unwrap();
+        let mut b = ThetaSketch::new(12).unwrap();
+        for i in 0..1000u64 {
+            a.update(&i);
+            b.update(&(i + 500));
+        }
+        assert_union(&a, &b);
+        assert!(a
+            .estimate_union(&ThetaSketch::with_seed(12, 7).unwrap())
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod frequency_sketch_tests {
+    use sketch_oxide::common::FrequencySketch;
+    use sketch_oxide::frequency::{ConservativeCountMin, CountMinSketch, ElasticSketch, SALSA};
+
+    fn all_sketches() -> Vec<(&'static str, Box<dyn FrequencySketch>)> {
+        vec![
+            (
+                "count_min",
+                Box::new(CountMinSketch::new(0.001, 0.01).unwrap()),
+            ),
+            (
+                "conservative_count_min",
+                Box::new(ConservativeCountMin::new(0.001, 0.01).unwrap()),
+            ),
+            ("elastic", Box::new(ElasticSketch::new(1024, 4).unwrap())),
+            ("salsa", Box::new(SALSA::new(0.001, 0.01).unwrap())),
+        ]
+    }
+
+    #[test]
+    fn test_boxed_sketches_over_same_stream() {
+        // Item i occurs 100 / (i + 1) times, with batched and single updates
+        let items: Vec<(Vec<u8>, u64)> = (0..200u32)
+            .map(|i| (format!("item-{}", i).into_bytes(), 100 / (i as u64 + 1)))
+            .collect();
+
+        for (name, mut sketch) in all_sketches() {
+            for (item, count) in &items {
+                sketch.update(item, count / 2);
+                for _ in 0..count - count / 2 {
+                    sketch.update(item, 1);
+                }
+            }
+
+            for (item, count) in items.iter().take(10) {
+                let estimate = sketch.estimate(item);
+                assert!(estimate >= *count, "{}: {} < {}", name, estimate, count);
+                assert!(
+                    estimate <= count + 10,
+                    "{}: {} >> {}",
+                    name,
+                    estimate,
+                    count
+                );
+            }
+            assert_eq!(sketch.estimate(b"never-seen"), 0, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_trait_matches_inherent_byte_slice_api() {
+        let mut cms = CountMinSketch::new(0.01, 0.01).unwrap();
+        FrequencySketch::update(&mut cms, b"key", 3);
+        cms.update(&b"key".as_slice());
+        assert_eq!(cms.estimate(&b"key".as_slice()), 4);
+        assert_eq!(FrequencySketch::estimate(&cms, b"key"), 4);
+    }
 }
 
 #[cfg(test)]
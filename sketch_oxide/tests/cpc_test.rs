@@ -1,5 +1,5 @@
 use sketch_oxide::cardinality::CpcSketch;
-use sketch_oxide::common::{Mergeable, Sketch};
+use sketch_oxide::common::{Mergeable, Sketch, SketchError};
 use std::collections::HashSet;
 
 // ============================================================================
@@ -483,6 +483,48 @@ fn test_serialization_roundtrip() {
     assert_eq!(sketch.flavor(), sketch2.flavor());
 }
 
+#[test]
+fn test_repeated_round_trips_are_byte_identical() {
+    // Sizes cover the sparse, hybrid and dense flavors
+    for n in [0u64, 50, 1_000, 20_000] {
+        let mut sketch = CpcSketch::new(10).unwrap();
+        for i in 0..n {
+            sketch.update(&i);
+        }
+        let original = sketch.to_bytes();
+        let estimate = sketch.estimate();
+
+        let mut bytes = original.clone();
+        for round in 0..100 {
+            let restored = CpcSketch::from_bytes(&bytes).unwrap();
+            assert_eq!(
+                restored.flavor(),
+                sketch.flavor(),
+                "n={} round {}",
+                n,
+                round
+            );
+            assert_eq!(restored.estimate(), estimate, "n={} round {}", n, round);
+            bytes = restored.to_bytes();
+            assert_eq!(bytes, original, "n={} round {}", n, round);
+        }
+    }
+}
+
+#[test]
+fn test_deserialization_rejects_duplicate_slots() {
+    let mut sketch = CpcSketch::new(10).unwrap();
+    for i in 0..50 {
+        sketch.update(&i);
+    }
+    let mut bytes = sketch.to_bytes();
+
+    // Surprising values start after the 20-byte preamble, 5 bytes each
+    let first: Vec<u8> = bytes[20..25].to_vec();
+    bytes[25..30].copy_from_slice(&first);
+    assert!(CpcSketch::from_bytes(&bytes).is_err());
+}
+
 #[test]
 fn test_serialization_empty() {
     let sketch = CpcSketch::new(10).unwrap();
@@ -493,6 +535,47 @@ fn test_serialization_empty() {
     assert_eq!(sketch2.flavor(), "Empty");
 }
 
+#[test]
+fn test_deserialization_truncated_buffer() {
+    let mut sketch = CpcSketch::new(10).unwrap();
+    for i in 0..500 {
+        sketch.update(&i);
+    }
+    let bytes = sketch.to_bytes();
+
+    for len in [0, 3, 5, 10, bytes.len() / 2, bytes.len() - 1] {
+        assert!(
+            matches!(
+                CpcSketch::from_bytes(&bytes[..len]),
+                Err(SketchError::DeserializationError(_))
+            ),
+            "truncated to {} bytes should fail cleanly",
+            len
+        );
+    }
+}
+
+#[test]
+fn test_deserialization_rejects_bad_header() {
+    let bytes = CpcSketch::new(10).unwrap().to_bytes();
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(CpcSketch::from_bytes(&wrong_magic).is_err());
+
+    let mut future_version = bytes.clone();
+    future_version[4] = 99;
+    assert!(CpcSketch::from_bytes(&future_version).is_err());
+
+    // lg_k = 40 would overflow the register count
+    let mut bad_lg_k = bytes;
+    bad_lg_k[5] = 40;
+    assert!(matches!(
+        CpcSketch::from_bytes(&bad_lg_k),
+        Err(SketchError::DeserializationError(_))
+    ));
+}
+
 // ----------------------------------------------------------------------------
 // Property-Based Tests (Manual)
 // ----------------------------------------------------------------------------
@@ -9,7 +9,7 @@
 
 use proptest::prelude::*;
 use sketch_oxide::common::{Mergeable, Sketch};
-use sketch_oxide::quantiles::DDSketch;
+use sketch_oxide::quantiles::{DDSketch, Mapping};
 
 // ============================================================================
 // Basic Functionality Tests
@@ -216,6 +216,66 @@ fn test_relative_error_guarantee() {
     assert!(p25 <= p50 && p50 <= p75);
 }
 
+#[test]
+fn test_relative_error_per_mapping() {
+    let alpha = 0.01;
+    let mut values: Vec<f64> = (0..20_000)
+        .map(|i| 10.0_f64.powf(i as f64 / 2_000.0 - 3.0)) // 10^-3 to 10^7
+        .collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut bins = Vec::new();
+    for mapping in [
+        Mapping::Logarithmic,
+        Mapping::CubicallyInterpolated,
+        Mapping::LinearlyInterpolated,
+    ] {
+        let mut dd = DDSketch::with_mapping(alpha, mapping).unwrap();
+        for &v in &values {
+            dd.add(v);
+            dd.add(-v);
+        }
+        assert_eq!(dd.mapping(), mapping);
+
+        for i in 1..100 {
+            let q = i as f64 / 100.0;
+            let rank = (q * dd.count() as f64).ceil() as usize;
+            let expected = if rank <= values.len() {
+                -values[values.len() - rank]
+            } else {
+                values[rank - values.len() - 1]
+            };
+            let actual = dd.quantile(q).unwrap();
+            let error = (actual - expected).abs() / expected.abs();
+            assert!(
+                error <= alpha + 1e-9,
+                "{:?} q={}: expected {}, got {}, error {}",
+                mapping,
+                q,
+                expected,
+                actual,
+                error
+            );
+        }
+
+        let restored = DDSketch::deserialize(&dd.serialize()).unwrap();
+        assert_eq!(restored.mapping(), mapping);
+        assert_eq!(restored.quantile(0.9), dd.quantile(0.9));
+        bins.push(dd.num_bins() as f64);
+    }
+
+    // Interpolation trades bins for speed
+    assert!(bins[1] <= bins[0] * 1.05, "cubic bins {:?}", bins);
+    assert!(bins[2] <= bins[0] * 1.5, "linear bins {:?}", bins);
+}
+
+#[test]
+fn test_merge_incompatible_mapping() {
+    let mut log = DDSketch::new(0.01).unwrap();
+    let cubic = DDSketch::with_mapping(0.01, Mapping::CubicallyInterpolated).unwrap();
+    assert!(log.merge(&cubic).is_err());
+}
+
 #[test]
 fn test_small_values() {
     let mut dd = DDSketch::new(0.01).unwrap();
@@ -343,6 +403,39 @@ fn test_extreme_values() {
     assert!(max > 1.0);
 }
 
+#[test]
+fn test_non_finite_values_rejected() {
+    let mut dd = DDSketch::new(0.01).unwrap();
+    for i in 1..=100 {
+        dd.add(i as f64);
+        dd.update(&f64::NAN);
+    }
+    dd.add(f64::INFINITY);
+    dd.add(f64::NEG_INFINITY);
+
+    assert_eq!(dd.count(), 100);
+    assert_eq!(dd.rejected_count(), 102);
+
+    // NaN used to land in the zero bin and drag the low quantiles down
+    for i in 0..=10 {
+        let q = dd.quantile(i as f64 / 10.0).unwrap();
+        assert!((0.99..=101.0).contains(&q), "quantile {}", q);
+    }
+
+    // Strict mode reports the value and leaves the sketch untouched
+    assert!(dd.try_add(f64::NAN).is_err());
+    assert!(dd.try_add(f64::INFINITY).is_err());
+    assert!(dd.try_add(0.5).is_ok());
+    assert_eq!(dd.count(), 101);
+    assert_eq!(dd.rejected_count(), 102);
+
+    // Merging carries the rejected count along
+    let mut other = DDSketch::new(0.01).unwrap();
+    other.add(f64::NAN);
+    dd.merge(&other).unwrap();
+    assert_eq!(dd.rejected_count(), 103);
+}
+
 // ============================================================================
 // Merge Tests
 // ============================================================================
@@ -453,6 +453,84 @@ fn test_error_bound_formula() {
     }
 }
 
+// Serialization tests
+fn byte_sketch(max_size: usize, range: std::ops::Range<u32>) -> FrequentItems<Vec<u8>> {
+    let mut sketch = FrequentItems::new(max_size).unwrap();
+    for i in range {
+        sketch.update_by(format!("item_{}", i % 30).into_bytes(), (i % 7 + 1) as u64);
+    }
+    sketch
+}
+
+#[test]
+fn test_serialization_round_trip() {
+    let sketch = byte_sketch(10, 0..500);
+    assert!(sketch.offset() > 0);
+
+    let bytes = sketch.to_bytes();
+    let restored = FrequentItems::from_bytes(&bytes).unwrap();
+
+    assert_eq!(restored, sketch);
+    assert_eq!(restored.total_weight(), sketch.total_weight());
+    assert_eq!(restored.to_bytes(), bytes);
+}
+
+#[test]
+fn test_deserialized_sketch_merges_like_live() {
+    let live_other = byte_sketch(10, 500..900);
+    let restored_other = FrequentItems::from_bytes(&live_other.to_bytes()).unwrap();
+
+    let mut with_live = byte_sketch(10, 0..500);
+    let mut with_restored = with_live.clone();
+    with_live.merge(&live_other).unwrap();
+    with_restored.merge(&restored_other).unwrap();
+
+    assert_eq!(with_restored, with_live);
+}
+
+#[test]
+fn test_serialization_with_custom_encoding() {
+    let mut sketch = FrequentItems::new(10).unwrap();
+    sketch.update_by("apple".to_string(), 5);
+    sketch.update_by("banana".to_string(), 2);
+
+    let bytes = sketch.to_bytes_with(|s| s.as_bytes().to_vec());
+    let restored = FrequentItems::from_bytes_with(&bytes, |b| {
+        String::from_utf8(b.to_vec()).map_err(|e| SketchError::DeserializationError(e.to_string()))
+    })
+    .unwrap();
+
+    assert_eq!(restored, sketch);
+}
+
+#[test]
+fn test_deserialize_rejects_invalid_input() {
+    let bytes = byte_sketch(10, 0..100).to_bytes();
+
+    assert!(FrequentItems::from_bytes(&[]).is_err());
+    assert!(FrequentItems::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(FrequentItems::from_bytes(&trailing).is_err());
+
+    let mut bad_version = bytes.clone();
+    bad_version[0] = 99;
+    assert!(FrequentItems::from_bytes(&bad_version).is_err());
+
+    let mut bad_max_size = bytes.clone();
+    bad_max_size[1..9].copy_from_slice(&1u64.to_le_bytes());
+    assert!(FrequentItems::from_bytes(&bad_max_size).is_err());
+
+    let mut bad_total = bytes.clone();
+    bad_total[17..25].copy_from_slice(&1u64.to_le_bytes());
+    assert!(FrequentItems::from_bytes(&bad_total).is_err());
+
+    let mut huge_num_items = bytes;
+    huge_num_items[25..33].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(FrequentItems::from_bytes(&huge_num_items).is_err());
+}
+
 // Property-based tests
 #[test]
 fn test_property_lower_bound_never_exceeds_upper() {
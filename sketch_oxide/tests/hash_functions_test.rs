@@ -272,3 +272,66 @@ mod distribution_tests {
         assert!(*max_bucket < 500);
     }
 }
+
+// Golden values shared with the language bindings. The Java, .NET and Node
+// update paths feed the same input, so these constants must never drift.
+#[cfg(test)]
+mod golden_tests {
+    use sketch_oxide::cardinality::{CpcSketch, HyperLogLog};
+    use sketch_oxide::common::hash::hash_bytes;
+    use sketch_oxide::common::Sketch;
+
+    const GOLDEN_INPUT: &[u8] = b"sketch_oxide";
+
+    #[test]
+    fn test_hash_bytes_golden() {
+        assert_eq!(hash_bytes(GOLDEN_INPUT), 0x6f4b_eba6_c56b_6bb1);
+    }
+
+    #[test]
+    fn test_hyperloglog_golden_register() {
+        let mut hll = HyperLogLog::new(10).unwrap();
+        // Bindings pass the raw byte buffer through the generic update
+        hll.update(&GOLDEN_INPUT.to_vec());
+
+        let nonzero: Vec<(usize, u8)> = hll
+            .registers()
+            .iter()
+            .enumerate()
+            .filter(|(_, &r)| r != 0)
+            .map(|(i, &r)| (i, r))
+            .collect();
+        assert_eq!(nonzero, vec![(132, 6)]);
+
+        // Serialized form is [precision][registers...]
+        assert_eq!(hll.to_bytes()[1 + 132], 6);
+    }
+
+    #[test]
+    fn test_cpc_golden_bytes() {
+        let mut cpc = CpcSketch::new(10).unwrap();
+        // Equivalent to update_bytes, which the bindings call
+        cpc.update(&hash_bytes(GOLDEN_INPUT));
+
+        let hex: String = cpc
+            .to_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        // Versioned header "CPCS" + v1, then the sketch body
+        assert_eq!(
+            hex,
+            "43504353010a0101000000000000000001000000300100000200000000"
+        );
+    }
+
+    #[test]
+    fn test_cpc_update_bytes_golden() {
+        let mut cpc = CpcSketch::new(10).unwrap();
+        cpc.update_bytes(GOLDEN_INPUT);
+
+        let mut expected = CpcSketch::new(10).unwrap();
+        expected.update(&0x6f4b_eba6_c56b_6bb1);
+        assert_eq!(cpc.to_bytes(), expected.to_bytes());
+    }
+}
@@ -56,6 +56,32 @@ mod construction {
         let hll = HyperLogLog::new(14).unwrap();
         assert_eq!(hll.num_registers(), 16384);
     }
+
+    #[test]
+    fn test_for_byte_budget() {
+        // 8 KB holds 2^13 one-byte registers but not 2^14
+        let hll = HyperLogLog::for_byte_budget(8 * 1024).unwrap();
+        assert_eq!(hll.precision(), 13);
+        assert!(hll.num_registers() <= 8 * 1024);
+        assert!(hll.num_registers() * 2 > 8 * 1024);
+
+        assert_eq!(
+            HyperLogLog::for_byte_budget(8 * 1024 - 1)
+                .unwrap()
+                .precision(),
+            12
+        );
+        assert_eq!(HyperLogLog::for_byte_budget(16).unwrap().precision(), 4);
+        assert_eq!(
+            HyperLogLog::for_byte_budget(usize::MAX)
+                .unwrap()
+                .precision(),
+            HyperLogLog::MAX_PRECISION
+        );
+
+        assert!(HyperLogLog::for_byte_budget(15).is_err());
+        assert!(HyperLogLog::for_byte_budget(0).is_err());
+    }
 }
 
 mod basic_operations {
@@ -85,6 +111,24 @@ mod basic_operations {
         assert!(!hll.is_empty());
     }
 
+    #[test]
+    fn test_update_hash_matches_update() {
+        use std::hash::{Hash, Hasher};
+        use twox_hash::XxHash64;
+
+        let mut hashed = HyperLogLog::new(12).unwrap();
+        let mut prehashed = HyperLogLog::new(12).unwrap();
+        for i in 0..5000u64 {
+            hashed.update(&i);
+
+            let mut hasher = XxHash64::with_seed(0);
+            i.hash(&mut hasher);
+            prehashed.update_hash(hasher.finish());
+        }
+
+        assert_eq!(hashed.registers(), prehashed.registers());
+    }
+
     #[test]
     fn test_estimate_empty() {
         let hll = HyperLogLog::new(12).unwrap();
@@ -419,6 +463,40 @@ mod serialization {
             "Estimates should be identical after serialization"
         );
     }
+    #[test]
+    fn test_repeated_round_trips_are_byte_identical() {
+        let mut hll = HyperLogLog::new(12).unwrap();
+        for i in 0..5000 {
+            hll.update(&i);
+        }
+        let original = hll.to_bytes();
+        let estimate = hll.estimate();
+
+        let mut bytes = original.clone();
+        for round in 0..100 {
+            let restored = HyperLogLog::from_bytes(&bytes).unwrap();
+            assert_eq!(restored.registers(), hll.registers(), "round {}", round);
+            assert_eq!(restored.estimate(), estimate, "round {}", round);
+            bytes = restored.to_bytes();
+            assert_eq!(bytes, original, "round {}", round);
+        }
+    }
+
+    #[test]
+    fn test_content_hash_survives_round_trip() {
+        let mut hll = HyperLogLog::new(12).unwrap();
+        for i in 0..5000 {
+            hll.update(&i);
+        }
+
+        let restored = HyperLogLog::from_bytes(&hll.to_bytes()).unwrap();
+        assert_eq!(restored.content_hash(), hll.content_hash());
+
+        for i in 5000..6000 {
+            hll.update(&i);
+        }
+        assert_ne!(restored.content_hash(), hll.content_hash());
+    }
 }
 
 mod redis_compatibility {
@@ -87,6 +87,38 @@ fn test_empty_training_data() {
     assert!(filter.is_err(), "Should reject empty training data");
 }
 
+#[test]
+fn test_add_members_no_false_negatives_and_retrain_signal() {
+    let training_keys: Vec<Vec<u8>> = (0..1000)
+        .map(|i| format!("key{}", i).into_bytes())
+        .collect();
+    let mut filter = LearnedBloomFilter::new(&training_keys, 0.01).unwrap();
+    assert!(!filter.needs_retrain());
+
+    let new_keys: Vec<Vec<u8>> = (0..5000)
+        .map(|i| format!("late_member_{}", i).into_bytes())
+        .collect();
+    let (first, rest) = new_keys.split_at(10);
+
+    let first: Vec<&[u8]> = first.iter().map(|k| k.as_slice()).collect();
+    filter.add_members(&first);
+    for key in &first {
+        assert!(filter.contains(key), "false negative after add_members");
+    }
+
+    let rest: Vec<&[u8]> = rest.iter().map(|k| k.as_slice()).collect();
+    filter.add_members(&rest);
+    for key in new_keys.iter().chain(&training_keys) {
+        assert!(filter.contains(key), "false negative after add_members");
+    }
+
+    // Far more keys than the backup filter was sized for
+    assert!(filter.needs_retrain());
+    assert!(!LearnedBloomFilter::new(&new_keys, 0.01)
+        .unwrap()
+        .needs_retrain());
+}
+
 // ============================================================================
 // Phase 2: Basic Membership Tests (10 tests)
 // ============================================================================
@@ -0,0 +1,153 @@
+//! Property tests for the merge guarantees of the cardinality sketches
+//!
+//! Distributed aggregation relies on merges being order-insensitive:
+//! - Commutativity: a ∪ b == b ∪ a
+//! - Associativity: (a ∪ b) ∪ c == a ∪ (b ∪ c)
+//! - Idempotence: a ∪ a == a
+//!
+//! Equality is checked on the full sketch state (registers, or retained
+//! hashes and theta), not just on estimates, so any divergence is caught.
+
+use proptest::prelude::*;
+use sketch_oxide::cardinality::{HyperLogLog, ThetaSketch, UltraLogLog};
+use sketch_oxide::{Mergeable, Sketch};
+
+fn items() -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(any::<u64>(), 0..2000)
+}
+
+fn hll_from(items: &[u64]) -> HyperLogLog {
+    let mut hll = HyperLogLog::new(10).unwrap();
+    for item in items {
+        hll.update(item);
+    }
+    hll
+}
+
+fn hll_merged(a: &HyperLogLog, b: &HyperLogLog) -> HyperLogLog {
+    let mut out = a.clone();
+    out.merge(b).unwrap();
+    out
+}
+
+fn ull_from(items: &[u64]) -> UltraLogLog {
+    let mut ull = UltraLogLog::new(10).unwrap();
+    for item in items {
+        ull.update(item);
+    }
+    ull
+}
+
+fn ull_merged(a: &UltraLogLog, b: &UltraLogLog) -> UltraLogLog {
+    let mut out = a.clone();
+    out.merge(b).unwrap();
+    out
+}
+
+fn theta_from(items: &[u64]) -> ThetaSketch {
+    // Small k so that larger inputs drive theta below 1 and exercise sampling
+    let mut theta = ThetaSketch::new(6).unwrap();
+    for item in items {
+        theta.update(item);
+    }
+    theta
+}
+
+/// Canonical byte form of a theta sketch: sorted retained hashes plus theta
+fn theta_state(sketch: &ThetaSketch) -> Vec<u8> {
+    sketch.compact().to_bytes()
+}
+
+proptest! {
+    #[test]
+    fn prop_hll_merge_commutative(a in items(), b in items()) {
+        let (a, b) = (hll_from(&a), hll_from(&b));
+        prop_assert!(hll_merged(&a, &b).registers_equal(&hll_merged(&b, &a)));
+    }
+
+    #[test]
+    fn prop_hll_merge_associative(a in items(), b in items(), c in items()) {
+        let (a, b, c) = (hll_from(&a), hll_from(&b), hll_from(&c));
+        let left = hll_merged(&hll_merged(&a, &b), &c);
+        let right = hll_merged(&a, &hll_merged(&b, &c));
+        prop_assert!(left.registers_equal(&right));
+    }
+
+    #[test]
+    fn prop_hll_merge_idempotent(a in items()) {
+        let a = hll_from(&a);
+        let merged = hll_merged(&a, &a);
+        prop_assert!(merged.registers_equal(&a));
+        prop_assert_eq!(merged.estimate(), a.estimate());
+    }
+
+    #[test]
+    fn prop_hll_merge_matches_union_of_streams(a in items(), b in items()) {
+        let combined: Vec<u64> = a.iter().chain(b.iter()).copied().collect();
+        let merged = hll_merged(&hll_from(&a), &hll_from(&b));
+        prop_assert!(merged.registers_equal(&hll_from(&combined)));
+    }
+
+    #[test]
+    fn prop_ull_merge_commutative(a in items(), b in items()) {
+        let (a, b) = (ull_from(&a), ull_from(&b));
+        prop_assert_eq!(ull_merged(&a, &b).serialize(), ull_merged(&b, &a).serialize());
+    }
+
+    #[test]
+    fn prop_ull_merge_associative(a in items(), b in items(), c in items()) {
+        let (a, b, c) = (ull_from(&a), ull_from(&b), ull_from(&c));
+        let left = ull_merged(&ull_merged(&a, &b), &c);
+        let right = ull_merged(&a, &ull_merged(&b, &c));
+        prop_assert_eq!(left.serialize(), right.serialize());
+    }
+
+    #[test]
+    fn prop_ull_merge_idempotent(a in items()) {
+        let a = ull_from(&a);
+        prop_assert_eq!(ull_merged(&a, &a).serialize(), a.serialize());
+    }
+
+    #[test]
+    fn prop_theta_union_commutative(a in items(), b in items()) {
+        let (a, b) = (theta_from(&a), theta_from(&b));
+        prop_assert_eq!(
+            theta_state(&a.union(&b).unwrap()),
+            theta_state(&b.union(&a).unwrap())
+        );
+    }
+
+    #[test]
+    fn prop_theta_union_associative(a in items(), b in items(), c in items()) {
+        let (a, b, c) = (theta_from(&a), theta_from(&b), theta_from(&c));
+        let left = a.union(&b).unwrap().union(&c).unwrap();
+        let right = a.union(&b.union(&c).unwrap()).unwrap();
+        prop_assert_eq!(theta_state(&left), theta_state(&right));
+
+        // union_all trims back to k entries, so compare it only with itself
+        prop_assert_eq!(
+            theta_state(&ThetaSketch::union_all(&[&a, &b, &c]).unwrap()),
+            theta_state(&ThetaSketch::union_all(&[&c, &a, &b]).unwrap())
+        );
+    }
+
+    #[test]
+    fn prop_theta_union_idempotent(a in items()) {
+        let a = theta_from(&a);
+        let merged = a.union(&a).unwrap();
+        prop_assert_eq!(theta_state(&merged), theta_state(&a));
+        prop_assert_eq!(merged.estimate(), a.estimate());
+    }
+}
+
+#[test]
+fn test_registers_equal() {
+    let a = hll_from(&[1, 2, 3]);
+    assert!(a.registers_equal(&a.clone()));
+    assert!(!a.registers_equal(&hll_from(&[4, 5, 6])));
+
+    // Same (empty) registers but a different precision
+    let p10 = HyperLogLog::new(10).unwrap();
+    let p11 = HyperLogLog::new(11).unwrap();
+    assert!(!p10.registers_equal(&p11));
+}
@@ -1,4 +1,5 @@
 use sketch_oxide::quantiles::req::{ReqMode, ReqSketch};
+use sketch_oxide::SketchError;
 
 #[cfg(test)]
 mod basic_tests {
@@ -305,6 +306,59 @@ mod compaction_tests {
     }
 }
 
+#[cfg(test)]
+mod rank_error_tests {
+    use super::*;
+
+    /// Shuffled 0..n so compactions see interleaved values
+    fn shuffled(n: u64) -> Vec<f64> {
+        (0..n)
+            .map(|i| (i.wrapping_mul(2_654_435_761) % n) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn test_rank_error_zero_before_compaction() {
+        let mut sketch = ReqSketch::new(32, ReqMode::HighRankAccuracy).unwrap();
+        for i in 0..50 {
+            sketch.update(i as f64);
+        }
+        assert_eq!(sketch.normalized_rank_error(), 0.0);
+        assert_eq!(sketch.normalized_rank_error_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_rank_error_bounds_observed_error() {
+        let n = 100_000u64;
+        for mode in [ReqMode::HighRankAccuracy, ReqMode::LowRankAccuracy] {
+            let mut sketch = ReqSketch::new(32, mode).unwrap();
+            for v in shuffled(n) {
+                sketch.update(v);
+            }
+
+            let bound = sketch.normalized_rank_error();
+            assert!(bound > 0.0 && bound < 1.0);
+
+            for q in [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999] {
+                let value = sketch.quantile(q).unwrap();
+                let observed = (value / n as f64 - q).abs();
+                let at = sketch.normalized_rank_error_at(q);
+                assert!(
+                    observed <= at,
+                    "{:?} q={}: observed {} > bound {}",
+                    mode,
+                    q,
+                    observed,
+                    at
+                );
+            }
+
+            assert_eq!(sketch.normalized_rank_error_at(0.0), 0.0);
+            assert_eq!(sketch.normalized_rank_error_at(1.0), 0.0);
+        }
+    }
+}
+
 #[cfg(test)]
 mod merge_tests {
     use super::*;
@@ -335,12 +389,23 @@ mod merge_tests {
     }
 
     #[test]
-    fn test_merge_incompatible_k() {
-        let sketch1 = ReqSketch::new(32, ReqMode::HighRankAccuracy).unwrap();
-        let sketch2 = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+    fn test_merge_different_k_coarsens_to_min() {
+        let mut sketch1 = ReqSketch::new(32, ReqMode::HighRankAccuracy).unwrap();
+        let mut sketch2 = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+        for i in 1..=1000 {
+            sketch1.update(i as f64);
+            sketch2.update((i + 1000) as f64);
+        }
 
-        let merged = sketch1.merge(&sketch2);
-        assert!(merged.is_err());
+        let merged = sketch1.merge(&sketch2).unwrap();
+        assert_eq!(merged.k(), 32);
+        assert_eq!(merged.n(), 2000);
+        assert_eq!(merged.quantile(1.0), Some(2000.0));
+
+        // Coarsening applies even when one side is empty
+        let empty = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+        assert_eq!(sketch1.merge(&empty).unwrap().k(), 32);
+        assert_eq!(empty.merge(&sketch1).unwrap().k(), 32);
     }
 
     #[test]
@@ -349,7 +414,38 @@ mod merge_tests {
         let sketch2 = ReqSketch::new(32, ReqMode::LowRankAccuracy).unwrap();
 
         let merged = sketch1.merge(&sketch2);
-        assert!(merged.is_err());
+        assert!(matches!(
+            merged,
+            Err(SketchError::IncompatibleSketches { .. })
+        ));
+        assert!(matches!(
+            sketch2.merge(&sketch1),
+            Err(SketchError::IncompatibleSketches { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_same_mode_preserves_tail_accuracy() {
+        let mut sketch1 = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+        let mut sketch2 = ReqSketch::new(64, ReqMode::HighRankAccuracy).unwrap();
+        for i in 1..=10_000 {
+            sketch1.update(i as f64);
+            sketch2.update((i + 10_000) as f64);
+        }
+
+        let merged = sketch1.merge(&sketch2).unwrap();
+        assert_eq!(merged.n(), 20_000);
+        assert_eq!(merged.quantile(1.0), Some(20_000.0));
+        for (q, expected) in [(0.99, 19_800.0), (0.999, 19_980.0)] {
+            let value = merged.quantile(q).unwrap();
+            assert!(
+                (value - expected).abs() <= 0.01 * 20_000.0,
+                "q={}: got {}, expected ~{}",
+                q,
+                value,
+                expected
+            );
+        }
     }
 
     #[test]
@@ -69,7 +69,7 @@ fn test_insert_single_item() {
     let mut filter = RibbonFilter::new(100, 0.01);
 
     filter.insert(b"test_key");
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(b"test_key"), "Should find inserted key");
 }
@@ -82,7 +82,7 @@ fn test_insert_multiple_items() {
     for key in &keys {
         filter.insert(key.as_bytes());
     }
-    filter.finalize();
+    filter.finalize().unwrap();
 
     for key in &keys {
         assert!(filter.contains(key.as_bytes()), "Should find key: {}", key);
@@ -95,7 +95,7 @@ fn test_insert_numeric_types() {
 
     filter.insert(&42u64.to_le_bytes());
     filter.insert(&123i32.to_le_bytes());
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(&42u64.to_le_bytes()));
     assert!(filter.contains(&123i32.to_le_bytes()));
@@ -109,7 +109,7 @@ fn test_finalize_required() {
 
     // Before finalization, behavior may vary
     // After finalization, membership is guaranteed
-    filter.finalize();
+    filter.finalize().unwrap();
     assert!(filter.contains(b"key"));
 }
 
@@ -118,8 +118,8 @@ fn test_multiple_finalize_calls() {
     let mut filter = RibbonFilter::new(100, 0.01);
 
     filter.insert(b"key");
-    filter.finalize();
-    filter.finalize(); // Should be idempotent
+    filter.finalize().unwrap();
+    filter.finalize().unwrap(); // Should be idempotent
 
     assert!(filter.contains(b"key"));
 }
@@ -131,7 +131,7 @@ fn test_insert_duplicate_items() {
     for _ in 0..10 {
         filter.insert(b"duplicate");
     }
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(b"duplicate"));
 }
@@ -148,7 +148,7 @@ fn test_no_false_negatives() {
     for item in &items {
         filter.insert(item.as_bytes());
     }
-    filter.finalize();
+    filter.finalize().unwrap();
 
     // Zero false negatives guaranteed
     for item in &items {
@@ -169,7 +169,7 @@ fn test_false_positive_rate() {
     for i in 0..n {
         filter.insert(&i.to_le_bytes());
     }
-    filter.finalize();
+    filter.finalize().unwrap();
 
     let test_count = 10000;
     let mut false_positives = 0;
@@ -194,7 +194,7 @@ fn test_false_positive_rate() {
 #[test]
 fn test_empty_filter_contains_nothing() {
     let mut filter = RibbonFilter::new(100, 0.01);
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(!filter.contains(b"anything"));
     assert!(!filter.contains(b""));
@@ -209,7 +209,7 @@ fn test_serialize_deserialize() {
     let mut filter = RibbonFilter::new(100, 0.01);
     filter.insert(b"test_key");
     filter.insert(b"another_key");
-    filter.finalize();
+    filter.finalize().unwrap();
 
     let bytes = filter.to_bytes();
     let restored = RibbonFilter::from_bytes(&bytes).unwrap();
@@ -222,7 +222,7 @@ fn test_serialize_deserialize() {
 #[test]
 fn test_serialize_empty_filter() {
     let mut filter = RibbonFilter::new(100, 0.01);
-    filter.finalize();
+    filter.finalize().unwrap();
 
     let bytes = filter.to_bytes();
     let restored = RibbonFilter::from_bytes(&bytes).unwrap();
@@ -246,7 +246,7 @@ fn test_space_efficiency_better_than_bloom() {
     for i in 0..n {
         ribbon.insert(&i.to_le_bytes());
     }
-    ribbon.finalize();
+    ribbon.finalize().unwrap();
 
     let _ribbon_bytes = ribbon.to_bytes();
 
@@ -269,7 +269,7 @@ fn test_empty_key() {
     let mut filter = RibbonFilter::new(100, 0.01);
 
     filter.insert(b"");
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(b""));
 }
@@ -280,7 +280,7 @@ fn test_very_long_key() {
 
     let long_key = vec![b'x'; 10000];
     filter.insert(&long_key);
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(&long_key));
 }
@@ -291,7 +291,7 @@ fn test_binary_data() {
 
     let binary_key = vec![0u8, 1, 2, 255, 0, 128];
     filter.insert(&binary_key);
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(&binary_key));
 }
@@ -301,7 +301,7 @@ fn test_small_filter() {
     let mut filter = RibbonFilter::new(1, 0.5);
 
     filter.insert(b"key");
-    filter.finalize();
+    filter.finalize().unwrap();
 
     assert!(filter.contains(b"key"));
 }
@@ -309,7 +309,7 @@ fn test_small_filter() {
 #[test]
 fn test_finalize_empty_filter() {
     let mut filter = RibbonFilter::new(100, 0.01);
-    filter.finalize();
+    filter.finalize().unwrap();
 
     // Empty finalized filter should not crash on queries
     assert!(!filter.contains(b"key"));
@@ -327,7 +327,7 @@ proptest! {
         for key in &keys {
             filter.insert(key);
         }
-        filter.finalize();
+        filter.finalize().unwrap();
 
         for key in &keys {
             prop_assert!(filter.contains(key), "False negative for key {:?}", key);
@@ -341,7 +341,7 @@ proptest! {
         for key in &keys {
             filter.insert(key);
         }
-        filter.finalize();
+        filter.finalize().unwrap();
 
         let bytes = filter.to_bytes();
         let restored = RibbonFilter::from_bytes(&bytes).unwrap();
@@ -371,7 +371,7 @@ fn test_ribbon_use_case_static_data() {
     }
 
     // Finalize: solve the linear system (Gaussian elimination)
-    filter.finalize();
+    filter.finalize().unwrap();
 
     // Query phase: fast lookups
     for i in 0u64..500 {
@@ -400,7 +400,7 @@ fn test_ribbon_vs_bloom_tradeoff() {
     for key in &keys {
         filter.insert(key.as_bytes());
     }
-    filter.finalize();
+    filter.finalize().unwrap();
 
     for key in &keys {
         assert!(filter.contains(key.as_bytes()));
@@ -0,0 +1,68 @@
+//! Tests for the memory sizing helpers
+//!
+//! Each helper should agree with the memory actually used by the sketch it
+//! sizes, so callers can choose among sketches by budget before building one.
+
+use sketch_oxide::cardinality::{bytes_for_rse, HyperLogLog};
+use sketch_oxide::frequency::{cms_bytes_for, CountMinSketch};
+use sketch_oxide::quantiles::{kll_bytes_for, KllSketch};
+
+#[test]
+fn test_bytes_for_rse_matches_hyperloglog() {
+    for precision in 4..=18u8 {
+        let hll = HyperLogLog::new(precision).unwrap();
+        assert_eq!(
+            bytes_for_rse(hll.standard_error()).unwrap(),
+            hll.num_registers(),
+            "precision {}",
+            precision
+        );
+    }
+
+    // Looser targets never need more memory
+    assert!(bytes_for_rse(0.05).unwrap() <= bytes_for_rse(0.01).unwrap());
+    assert_eq!(bytes_for_rse(0.9).unwrap(), 16);
+
+    assert!(bytes_for_rse(0.0).is_err());
+    assert!(bytes_for_rse(1.0).is_err());
+    assert!(bytes_for_rse(f64::NAN).is_err());
+    assert!(bytes_for_rse(0.001).is_err());
+}
+
+#[test]
+fn test_cms_bytes_for_matches_count_min() {
+    for &(epsilon, delta) in &[(0.1, 0.1), (0.01, 0.01), (0.001, 0.0001)] {
+        let cms = CountMinSketch::new(epsilon, delta).unwrap();
+        assert_eq!(
+            cms_bytes_for(epsilon, delta).unwrap(),
+            cms.width() * cms.depth() * 8
+        );
+    }
+
+    assert!(cms_bytes_for(0.0, 0.01).is_err());
+    assert!(cms_bytes_for(0.01, 1.0).is_err());
+}
+
+#[test]
+fn test_kll_bytes_for_bounds_retained_items() {
+    let mut kll = KllSketch::new(165).unwrap();
+    let mut n = 0u64;
+    for target in [100u64, 10_000, 1_000_000] {
+        while n < target {
+            kll.update((n as f64 * 0.618_033_988_7) % 1.0);
+            n += 1;
+        }
+        let bound = kll_bytes_for(0.01, n).unwrap();
+        assert!(
+            kll.num_retained() * 8 <= bound,
+            "n = {}: retained {} items, bound {} bytes",
+            n,
+            kll.num_retained(),
+            bound
+        );
+    }
+
+    assert_eq!(kll_bytes_for(0.01, 0).unwrap(), 0);
+    assert!(kll_bytes_for(0.0, 10).is_err());
+    assert!(kll_bytes_for(1e-6, 10).is_err());
+}
@@ -0,0 +1,85 @@
+//! Error-bound tests for SlidingWindowCounter
+//!
+//! The exponential histogram guarantees the windowed count is within
+//! epsilon of the true count. These tests replay random streams against an
+//! exact record of event timestamps and check the bound at every query.
+
+use proptest::prelude::*;
+use sketch_oxide::streaming::SlidingWindowCounter;
+
+/// Exact number of events with timestamp in [current_time - window, current_time]
+fn true_count(events: &[(u64, u64)], window: u64, current_time: u64) -> u64 {
+    let start = current_time.saturating_sub(window);
+    events
+        .iter()
+        .filter(|(t, _)| *t >= start && *t <= current_time)
+        .map(|(_, n)| n)
+        .sum()
+}
+
+fn assert_within_epsilon(
+    counter: &SlidingWindowCounter,
+    events: &[(u64, u64)],
+    window: u64,
+    current_time: u64,
+) -> Result<(), TestCaseError> {
+    let actual = true_count(events, window, current_time);
+    let estimate = counter.count(current_time);
+    let error = (estimate as f64 - actual as f64).abs();
+    prop_assert!(
+        error <= counter.epsilon() * actual as f64,
+        "t={} estimate={} actual={} epsilon={}",
+        current_time,
+        estimate,
+        actual,
+        counter.epsilon()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_small_epsilon_bound_holds_on_steady_stream() {
+    let epsilon = 0.001;
+    let window = 10_000;
+    let mut counter = SlidingWindowCounter::new(window, epsilon).unwrap();
+    let mut events = Vec::new();
+
+    for t in 0..50_000u64 {
+        counter.increment(t);
+        events.push((t, 1));
+        if t % 997 == 0 {
+            let actual = true_count(&events, window, t);
+            let estimate = counter.count(t);
+            assert!(
+                (estimate as f64 - actual as f64).abs() <= epsilon * actual as f64,
+                "t={} estimate={} actual={}",
+                t,
+                estimate,
+                actual
+            );
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_count_within_epsilon(
+        epsilon in prop::sample::select(vec![0.001, 0.01, 0.05, 0.1, 0.25, 0.5]),
+        window in 10u64..2_000,
+        steps in prop::collection::vec((0u64..20, 1u64..50), 1..400),
+    ) {
+        let mut counter = SlidingWindowCounter::new(window, epsilon).unwrap();
+        let mut events = Vec::new();
+        let mut t = 0u64;
+
+        for (gap, n) in steps {
+            t += gap;
+            counter.increment_by(t, n);
+            events.push((t, n));
+            assert_within_epsilon(&counter, &events, window, t)?;
+            assert_within_epsilon(&counter, &events, window, t + window / 2)?;
+        }
+    }
+}
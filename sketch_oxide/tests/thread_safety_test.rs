@@ -0,0 +1,155 @@
+//! Compile-time checks that the public sketch types are `Send + Sync`
+//!
+//! Every sketch owns its state outright (no `Rc`, `RefCell` or raw
+//! pointers), so it can be moved into a spawned thread or async task and
+//! shared behind an `Arc`. These assertions fail to compile if a change
+//! introduces a non-thread-safe field.
+
+use sketch_oxide::cardinality::{
+    AdaptiveHyperLogLog, CompactThetaSketch, CpcSketch, DistinctCounter, GroupedHll, HyperLogLog,
+    QSketch, ThetaSketch, UltraLogLog,
+};
+use sketch_oxide::common::{BoxedSketch, SketchError};
+use sketch_oxide::frequency::{
+    ConservativeCountMin, CountMinSketch, CountSketch, ElasticSketch, FrequentItems, HeavyKeeper,
+    NitroSketch, RemovableUniversalSketch, SpaceSaving, SALSA,
+};
+use sketch_oxide::membership::{
+    BinaryFuseFilter, BlockedBloomFilter, BloomFilter, CountingBloomFilter, CountingFuseFilter,
+    CuckooFilter, LearnedBloomFilter, RibbonFilter, StableBloomFilter, VacuumFilter,
+};
+use sketch_oxide::quantiles::{
+    DDSketch, KllSketch, ReqSketch, SplineSketch, StreamSummary, TDigest,
+};
+use sketch_oxide::range_filters::{Grafite, MementoFilter, GRF};
+use sketch_oxide::reconciliation::RatelessIBLT;
+use sketch_oxide::sampling::{ReservoirSampling, VarOptSampling};
+use sketch_oxide::similarity::{MinHash, SimHash};
+use sketch_oxide::streaming::{
+    ExponentialHistogram, SlidingHyperLogLog, SlidingRateLimiter, SlidingTopK,
+    SlidingWindowCounter, SlidingWindowFrequency,
+};
+use sketch_oxide::universal::UnivMon;
+use sketch_oxide::Sketch;
+
+/// Fails to compile unless every listed type is `Send + Sync`
+macro_rules! assert_send_sync {
+    ($($ty:ty),+ $(,)?) => {
+        const _: fn() = || {
+            fn check<T: Send + Sync>() {}
+            $(check::<$ty>();)+
+        };
+    };
+}
+
+assert_send_sync!(
+    AdaptiveHyperLogLog,
+    CompactThetaSketch,
+    CpcSketch,
+    DistinctCounter,
+    GroupedHll<String>,
+    HyperLogLog,
+    QSketch,
+    ThetaSketch,
+    UltraLogLog,
+);
+
+assert_send_sync!(
+    ConservativeCountMin,
+    CountMinSketch,
+    CountSketch,
+    ElasticSketch,
+    FrequentItems<String>,
+    HeavyKeeper,
+    NitroSketch<CountMinSketch>,
+    RemovableUniversalSketch,
+    SALSA,
+    SpaceSaving<u64>,
+);
+
+assert_send_sync!(
+    BinaryFuseFilter,
+    BlockedBloomFilter,
+    BloomFilter,
+    CountingBloomFilter,
+    CountingFuseFilter,
+    CuckooFilter,
+    LearnedBloomFilter,
+    RibbonFilter,
+    StableBloomFilter,
+    VacuumFilter,
+);
+
+assert_send_sync!(
+    DDSketch,
+    KllSketch,
+    ReqSketch,
+    SplineSketch,
+    StreamSummary,
+    TDigest,
+);
+
+assert_send_sync!(
+    Grafite,
+    GRF,
+    MementoFilter,
+    RatelessIBLT,
+    ReservoirSampling<Vec<u8>>,
+    VarOptSampling<Vec<u8>>,
+    MinHash,
+    SimHash,
+    UnivMon,
+);
+
+assert_send_sync!(
+    ExponentialHistogram,
+    SlidingHyperLogLog,
+    SlidingRateLimiter<String>,
+    SlidingTopK,
+    SlidingWindowCounter,
+    SlidingWindowFrequency,
+);
+
+assert_send_sync!(BoxedSketch, SketchError);
+
+#[cfg(feature = "count_updates")]
+assert_send_sync!(sketch_oxide::common::Counted<HyperLogLog>);
+
+#[test]
+fn test_sketch_moves_into_thread() {
+    let mut hll = HyperLogLog::new(12).unwrap();
+    let handle = std::thread::spawn(move || {
+        for i in 0..1000u64 {
+            hll.update(&i);
+        }
+        hll
+    });
+
+    let hll = handle.join().unwrap();
+    assert!((hll.estimate() - 1000.0).abs() < 100.0);
+}
+
+#[test]
+fn test_shared_tdigest_behind_mutex() {
+    use std::sync::{Arc, Mutex};
+
+    // TDigest queries take `&mut self` (they flush the insert buffer), so a
+    // shared digest needs a Mutex rather than an RwLock read guard
+    let digest = Arc::new(Mutex::new(TDigest::new(100.0)));
+    let workers: Vec<_> = (0..4)
+        .map(|w| {
+            let digest = Arc::clone(&digest);
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    digest.lock().unwrap().update((w * 250 + i) as f64);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let median = digest.lock().unwrap().quantile(0.5);
+    assert!((median - 500.0).abs() < 50.0);
+}
@@ -46,6 +46,23 @@ fn test_invalid_precision_too_high() {
     }
 }
 
+#[test]
+fn test_for_byte_budget() {
+    // 8 KB holds 2^13 one-byte registers but not 2^14
+    let ull = UltraLogLog::for_byte_budget(8 * 1024).unwrap();
+    assert_eq!(ull.precision(), 13);
+
+    assert_eq!(UltraLogLog::for_byte_budget(16).unwrap().precision(), 4);
+    assert_eq!(
+        UltraLogLog::for_byte_budget(1 << 30).unwrap().precision(),
+        18
+    );
+    assert!(matches!(
+        UltraLogLog::for_byte_budget(15),
+        Err(SketchError::InvalidParameter { .. })
+    ));
+}
+
 #[test]
 fn test_update_single_item() {
     let mut ull = UltraLogLog::new(12).unwrap();
@@ -413,7 +430,25 @@ fn test_serialization_roundtrip() {
 }
 
 #[test]
-#[ignore] // Flaky test: deserialization may accept some invalid data formats
+fn test_deserialization_reads_headerless_bytes() {
+    // Precision 4 with 20 items, as serialized before the magic/version header
+    let hex = "0402010105010000010204020400000203";
+    let legacy: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+
+    let mut expected = UltraLogLog::new(4).unwrap();
+    for i in 0..20u64 {
+        expected.update(&i);
+    }
+
+    let ull = UltraLogLog::deserialize(&legacy).unwrap();
+    assert_eq!(ull.estimate(), expected.estimate());
+    assert_eq!(ull.serialize(), expected.serialize());
+}
+
+#[test]
 fn test_deserialization_invalid_data() {
     let invalid_bytes = vec![0xFF; 10]; // Random invalid data
 
@@ -426,6 +461,42 @@ fn test_deserialization_invalid_data() {
     }
 }
 
+#[test]
+fn test_deserialization_truncated_buffer() {
+    let mut ull = UltraLogLog::new(10).unwrap();
+    for i in 0..1000u64 {
+        ull.update(&i);
+    }
+    let bytes = ull.serialize();
+
+    for len in [0, 3, 5, 6, bytes.len() / 2, bytes.len() - 1] {
+        match UltraLogLog::deserialize(&bytes[..len]) {
+            Err(SketchError::DeserializationError(_)) => {}
+            other => {
+                panic!("truncated to {len} bytes: expected DeserializationError, got {other:?}")
+            }
+        }
+    }
+}
+
+#[test]
+fn test_deserialization_rejects_unknown_version() {
+    let mut bytes = UltraLogLog::new(8).unwrap().serialize();
+    bytes[4] = bytes[4].wrapping_add(1);
+    assert!(matches!(
+        UltraLogLog::deserialize(&bytes),
+        Err(SketchError::DeserializationError(_))
+    ));
+
+    // Precision outside [4, 18] is rejected rather than allocating
+    let mut bytes = UltraLogLog::new(8).unwrap().serialize();
+    bytes[5] = 40;
+    assert!(matches!(
+        UltraLogLog::deserialize(&bytes),
+        Err(SketchError::DeserializationError(_))
+    ));
+}
+
 #[test]
 fn test_serialization_empty_sketch() {
     let ull1 = UltraLogLog::new(12).unwrap();
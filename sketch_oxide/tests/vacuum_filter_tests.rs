@@ -886,3 +886,109 @@ fn test_stats_reflect_operations() {
 
     // Property: Stats accurately reflect filter state
 }
+
+#[test]
+fn test_measured_fpr_matches_target() {
+    let mut filter = VacuumFilter::new(1000, 0.01).unwrap();
+    for i in 0u32..500 {
+        filter.insert(&i.to_le_bytes()).unwrap();
+    }
+
+    let absent: Vec<[u8; 4]> = (1_000_000u32..1_100_000).map(|i| i.to_le_bytes()).collect();
+    let absent: Vec<&[u8]> = absent.iter().map(|k| k.as_slice()).collect();
+    let fpr = filter.measured_fpr(&absent);
+    assert!(fpr < 0.03, "measured FPR {} far above target 0.01", fpr);
+
+    assert_eq!(filter.measured_fpr(&[]), 0.0);
+}
+
+// ============================================================================
+// Serialization Tests
+// ============================================================================
+
+#[test]
+fn test_serialization_round_trip() {
+    let mut filter = VacuumFilter::new(2000, 0.01).unwrap();
+    for i in 0u32..1000 {
+        filter.insert(&i.to_le_bytes()).unwrap();
+    }
+    filter.delete(&0u32.to_le_bytes()).unwrap();
+    filter.insert(&0u32.to_le_bytes()).unwrap();
+
+    let bytes = filter.to_bytes();
+    let restored = VacuumFilter::from_bytes(&bytes).unwrap();
+
+    for i in 0u32..1000 {
+        assert!(restored.contains(&i.to_le_bytes()), "lost item {}", i);
+    }
+    assert_eq!(restored.stats(), filter.stats());
+    assert_eq!(restored.to_bytes(), bytes);
+
+    // Absent keys see the same fingerprints, so the same false positives
+    for i in 1_000_000u32..1_010_000 {
+        let key = i.to_le_bytes();
+        assert_eq!(restored.contains(&key), filter.contains(&key));
+    }
+}
+
+#[test]
+fn test_serialization_after_rehash_and_empty() {
+    let mut filter = VacuumFilter::new(16, 0.01).unwrap();
+    let empty = VacuumFilter::from_bytes(&filter.to_bytes()).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.capacity(), filter.capacity());
+
+    for i in 0u32..200 {
+        filter.insert(&i.to_le_bytes()).unwrap();
+    }
+    let mut restored = VacuumFilter::from_bytes(&filter.to_bytes()).unwrap();
+    assert_eq!(restored.capacity(), filter.capacity());
+    assert_eq!(restored.len(), filter.len());
+
+    // The reloaded filter stays fully dynamic
+    restored.insert(b"new").unwrap();
+    assert!(restored.contains(b"new"));
+    assert!(restored.delete(b"new").unwrap());
+}
+
+#[test]
+fn test_deserialize_rejects_corrupt_data() {
+    let mut filter = VacuumFilter::new(100, 0.01).unwrap();
+    filter.insert(b"key").unwrap();
+    let bytes = filter.to_bytes();
+
+    let is_corrupt = |data: &[u8]| {
+        matches!(
+            VacuumFilter::from_bytes(data),
+            Err(SketchError::DeserializationError(_))
+        )
+    };
+
+    assert!(is_corrupt(&[]));
+    assert!(is_corrupt(&bytes[..bytes.len() - 1]));
+    assert!(is_corrupt(&[bytes.as_slice(), &[0]].concat()));
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(is_corrupt(&wrong_magic));
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = 99;
+    assert!(is_corrupt(&wrong_version));
+
+    // Item count (after the 5-byte header and 8-byte bucket count)
+    let mut wrong_count = bytes.clone();
+    wrong_count[13] = 5;
+    assert!(is_corrupt(&wrong_count));
+
+    // Fingerprint bits
+    let mut wrong_bits = bytes.clone();
+    wrong_bits[21] = 16;
+    assert!(is_corrupt(&wrong_bits));
+
+    // Bucket count that is not a power of two
+    let mut wrong_buckets = bytes.clone();
+    wrong_buckets[5] = 3;
+    wrong_buckets[6..13].fill(0);
+    assert!(is_corrupt(&wrong_buckets));
+}